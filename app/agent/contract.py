@@ -25,9 +25,9 @@ class BountyForgeClient:
         seeds = [b"attest", solution_id_buffer]
         return Pubkey.find_program_address_sync(seeds, program_id)
     
-    def _derive_bounty_pda(self, bounty_id: int, program_id: Pubkey) -> tuple[Pubkey, int]:
+    def _derive_bounty_pda(self, creator: Pubkey, bounty_id: int, program_id: Pubkey) -> tuple[Pubkey, int]:
         bounty_id_buffer = bounty_id.to_bytes(8, 'little')
-        seeds = [b"bounty", bounty_id_buffer]
+        seeds = [b"bounty", bytes(creator), bounty_id_buffer]
         return Pubkey.find_program_address_sync(seeds, program_id)
     
     def _derive_reputation_pda(self, agent_pubkey: Pubkey, program_id: Pubkey) -> tuple[Pubkey, int]:
@@ -3,10 +3,117 @@
 // configured from the workspace's Anchor.toml.
 
 import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { Bountyforge } from "../target/types/bountyforge";
 
 module.exports = async function (provider: anchor.AnchorProvider) {
   // Configure client to use the provider.
   anchor.setProvider(provider);
 
-  // Add your deploy script here.
+  // The deployer becomes the Config admin; post_bounty needs the account to exist.
+  const program = anchor.workspace.bountyforge as Program<Bountyforge>;
+  const [configPda] = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  );
+
+  if (await provider.connection.getAccountInfo(configPda)) {
+    // accounts from before BountyStats and Leaderboard were zero-copy are
+    // too short to load until padded, so an upgrade migrates them
+    for (const { account: config } of await program.account.config.all()) {
+      const [statsPda] = PublicKey.findProgramAddressSync(
+        [Buffer.from("stats"), Buffer.from(config.market)],
+        program.programId
+      );
+      const stats = await provider.connection.getAccountInfo(statsPda);
+      if (stats && stats.data.length < 8 + 80) {
+        await program.methods
+          .migrateStats(config.market)
+          .accountsPartial({ payer: provider.wallet.publicKey })
+          .rpc();
+      }
+    }
+    const [leaderboardPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("leaderboard")],
+      program.programId
+    );
+    const leaderboard = await provider.connection.getAccountInfo(
+      leaderboardPda
+    );
+    if (leaderboard && leaderboard.data.length < 8 + 648) {
+      await program.methods
+        .migrateLeaderboard()
+        .accountsPartial({ payer: provider.wallet.publicKey })
+        .rpc();
+    }
+    return;
+  }
+
+  await program.methods
+    .initializeConfig({
+      feeBps: 0,
+      treasury: provider.wallet.publicKey,
+      verifier: provider.wallet.publicKey,
+      trustedAttestor: null,
+      attestors: [],
+      defaultChallengePeriodSlots: new anchor.BN(0),
+      challengeBondLamports: new anchor.BN(0),
+      challengeRewardBps: 0,
+      stakeSlashTreasuryBps: 0,
+      defaultSettleWindowSlots: new anchor.BN(0),
+      maxOpenPerCreator: 10,
+      minReward: new anchor.BN(1),
+      maxReward: new anchor.BN(10).pow(new anchor.BN(15)),
+      // seven years, for accounting
+      receiptRetentionSeconds: new anchor.BN(7 * 365 * 24 * 60 * 60),
+      attestorFeeBps: 0,
+      // a point for every doubling past 100 USDC, up to 10 a bounty
+      scoreUnit: new anchor.BN(100 * 10 ** 6),
+      maxScorePerBounty: new anchor.BN(10),
+      // a quarter without work halves an agent's score
+      decayPeriodSeconds: new anchor.BN(90 * 24 * 60 * 60),
+      allowSelfSettlement: false,
+      // 0.1% of an expired bounty's refund for whoever cleans it up
+      crankFeeBps: 10,
+      // set with update_config once the x402 gateway's signing key is known
+      paymentGateway: null,
+      // a rejected agent waits ten minutes before trying again
+      defaultResubmissionCooldownSeconds: new anchor.BN(10 * 60),
+      // a bounty untouched for a year can be swept to the treasury
+      abandonmentSeconds: new anchor.BN(365 * 24 * 60 * 60),
+      // vouched for with update_config when a partner mint needs an extension we refuse
+      trustedMints: [],
+      // 1% of the reward features a bounty for a week
+      boostFeeBps: 100,
+      boostDurationSeconds: new anchor.BN(7 * 24 * 60 * 60),
+      // unused attestations can be cleared after a week, tipping the cranker 5000 lamports
+      attestationTtlSeconds: new anchor.BN(7 * 24 * 60 * 60),
+      attestationCloseTipLamports: new anchor.BN(5_000),
+      // no insurance until the admin funds it and enables it with update_config
+      insuranceFeeBps: 0,
+      maxInsuranceTopup: new anchor.BN(0),
+      // total_earned sums every mint until update_config names the main one
+      primaryMint: null,
+      // rejected agents get three days to appeal to the bounty's arbiter
+      appealWindowSeconds: new anchor.BN(3 * 24 * 60 * 60),
+      // a dismissed challenge pays the solver a tenth of its bond
+      challengeForfeitBps: 1_000,
+      // any non-zero x402 receipt counts until the gateway's price is set with update_config
+      minVerificationFee: new anchor.BN(0),
+    })
+    .accountsPartial({ admin: provider.wallet.publicKey })
+    .rpc();
+
+  // protocol-wide counters every posting and settlement updates
+  await program.methods
+    .initializeStats()
+    .accountsPartial({ payer: provider.wallet.publicKey })
+    .rpc();
+
+  // the top agents by score, updated wherever a score changes
+  await program.methods
+    .initializeLeaderboard()
+    .accountsPartial({ payer: provider.wallet.publicKey })
+    .rpc();
 };
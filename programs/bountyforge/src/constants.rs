@@ -1 +1,102 @@
+use anchor_lang::prelude::*;
+
 pub const ANCHOR_DISCRIMINATOR: usize = 8;
+
+// Bounty layout of this release, bumped with every change to it so migrate_bounty
+// can tell older accounts apart. Version 0 is the 0.1.0 layout
+#[constant]
+pub const CURRENT_BOUNTY_VERSION: u8 = 1;
+
+// byte offsets into a Bounty account, discriminator included, for getProgramAccounts memcmp filters
+#[constant]
+pub const BOUNTY_VERSION_OFFSET: usize = ANCHOR_DISCRIMINATOR;
+#[constant]
+pub const BOUNTY_ID_OFFSET: usize = BOUNTY_VERSION_OFFSET + 1;
+#[constant]
+pub const BOUNTY_STATUS_OFFSET: usize = BOUNTY_ID_OFFSET + 8;
+#[constant]
+pub const BOUNTY_CREATOR_OFFSET: usize = BOUNTY_STATUS_OFFSET + 1;
+#[constant]
+pub const BOUNTY_ORIGINAL_CREATOR_OFFSET: usize = BOUNTY_CREATOR_OFFSET + 32;
+#[constant]
+pub const BOUNTY_REWARD_MINT_OFFSET: usize = BOUNTY_ORIGINAL_CREATOR_OFFSET + 32;
+#[constant]
+pub const BOUNTY_REWARD_OFFSET: usize = BOUNTY_REWARD_MINT_OFFSET + 32;
+#[constant]
+pub const BOUNTY_BUMP_OFFSET: usize = BOUNTY_REWARD_OFFSET + 8;
+#[constant]
+pub const BOUNTY_BOOSTED_UNTIL_OFFSET: usize = BOUNTY_BUMP_OFFSET + 1;
+#[constant]
+pub const BOUNTY_TARGET_OFFSET: usize = BOUNTY_BOOSTED_UNTIL_OFFSET + 8;
+// only the variant tag is fixed, Custom codes follow it
+#[constant]
+pub const BOUNTY_TYPE_OFFSET: usize = BOUNTY_TARGET_OFFSET + 32;
+
+// bytes, also the max_len of Bounty::description
+pub const MAX_DESCRIPTION_LEN: usize = 50;
+pub const MAX_METADATA_URI_LEN: usize = 200;
+
+pub const SCORE_PER_SUBMISSION: u64 = 1;
+
+// successful bounties needed for each reputation tier
+pub const BRONZE_TIER_SUCCESSES: u64 = 5;
+pub const SILVER_TIER_SUCCESSES: u64 = 25;
+pub const GOLD_TIER_SUCCESSES: u64 = 100;
+
+// the four built-in bounty types plus one bucket for every custom code
+pub const BOUNTY_TYPE_SLOTS: usize = 5;
+// reward mints a reputation keeps earnings for, later ones are only counted
+pub const EARNING_MINT_SLOTS: usize = 4;
+
+// stars a creator can give the solver of a settled bounty
+pub const MIN_RATING: u8 = 1;
+pub const MAX_RATING: u8 = 5;
+
+pub const MIN_POST_INTERVAL_SECONDS: i64 = 2;
+// bounties per post_bounties call, each one creates two accounts
+pub const MAX_BATCH_POSTS: usize = 5;
+// bounties per settle_bounties call, each one passes nine accounts
+pub const MAX_BATCH_SETTLEMENTS: usize = 3;
+pub const MAX_INDEXED_BOUNTIES: usize = 64;
+
+pub const MAX_FEE_BPS: u16 = 10_000;
+// decay_bps_per_hour is charged pro rata over this
+pub const SECONDS_PER_HOUR: i64 = 3_600;
+
+pub const MAX_ORACLE_STALENESS_SECONDS: i64 = 60;
+pub const MAX_ORACLE_CONFIDENCE_BPS: u64 = 200;
+// e.g. a price feed alongside a liquidity feed for token screening
+pub const MAX_ORACLE_FEEDS: usize = 3;
+// keeps bounds and feed values within i128 once brought to a common exponent
+pub const MAX_ORACLE_EXPONENT: u32 = 18;
+
+pub const MAX_ATTESTATION_AGE_SECONDS: i64 = 24 * 60 * 60;
+pub const MAX_ATTESTORS: usize = 8;
+// reward mints the admin vouches for despite extensions that could claw back escrow
+pub const MAX_TRUSTED_MINTS: usize = 8;
+// bytes of a market name, the default market's is empty
+pub const MAX_MARKET_LEN: usize = 8;
+
+pub const MAX_SOLUTION_PAYLOAD_LEN: usize = 512;
+// a longer criteria document is committed to through a short manifest of its URI and hash
+pub const MAX_CRITERIA_LEN: usize = 512;
+
+pub const MAX_PRIZES: usize = 5;
+
+pub const MAX_ALLOWLIST: usize = 10;
+
+pub const MAX_MILESTONES: usize = 4;
+
+// members sharing one submission's reward
+pub const MAX_TEAM_MEMBERS: usize = 4;
+
+// agents ranked on the Leaderboard account
+#[constant]
+pub const LEADERBOARD_SIZE: usize = 16;
+
+// siblings in a reputation snapshot proof, enough for 2^32 agents
+pub const MAX_PROOF_LEN: usize = 32;
+
+// soulbound Token-2022 badges settle_bounty mints to solvers
+pub const BADGE_NAME_PREFIX: &str = "BountyForge Badge #";
+pub const BADGE_SYMBOL: &str = "BFBADGE";
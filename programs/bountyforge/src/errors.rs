@@ -26,4 +26,442 @@ pub enum BountyForgeError {
     OracleDataStale,
     #[msg("Invalid account owner")]
     InvalidAccountOwner,
+    #[msg("Creator has too many open bounties")]
+    TooManyOpenBounties,
+    #[msg("Creator must wait before posting another bounty")]
+    PostCooldownActive,
+    #[msg("Bounty cannot be cancelled after a solution was submitted")]
+    CannotCancelSubmitted,
+    #[msg("Only the bounty creator can cancel the bounty")]
+    UnauthorizedCancellation,
+    #[msg("Bounty deadline must be in the future")]
+    InvalidDeadline,
+    #[msg("Bounty deadline has passed")]
+    BountyDeadlinePassed,
+    #[msg("Bounty deadline has not passed yet")]
+    BountyDeadlineNotReached,
+    #[msg("Bounty settlement has not been approved")]
+    BountyNotApproved,
+    #[msg("Only the recorded solver can claim the reward")]
+    UnauthorizedClaim,
+    #[msg("Only a submitted solution can be rejected")]
+    NotRejectable,
+    #[msg("Agent does not match the bounty's recorded solver")]
+    SolverMismatch,
+    #[msg("Token account mint does not match the bounty reward mint")]
+    RewardMintMismatch,
+    #[msg("Token accounts are required for SPL bounties")]
+    MissingTokenAccount,
+    #[msg("Payout would leave the bounty account below rent exemption")]
+    BelowRentExemption,
+    #[msg("Oracle-backed bounties must name their oracle feed")]
+    OracleFeedRequired,
+    #[msg("Config has already been initialized")]
+    ConfigAlreadyInitialized,
+    #[msg("Only the config admin can do this")]
+    UnauthorizedAdmin,
+    #[msg("Fee basis points cannot exceed 10000")]
+    InvalidFeeBps,
+    #[msg("Signer is not the pending admin")]
+    NotPendingAdmin,
+    #[msg("The program is paused")]
+    ProgramPaused,
+    #[msg("Treasury account does not match the config treasury")]
+    TreasuryMismatch,
+    #[msg("Treasury accounts are required when a protocol fee is charged")]
+    MissingTreasury,
+    #[msg("Protocol fee calculation overflowed")]
+    FeeCalculationOverflow,
+    #[msg("Attestation was made for a different bounty")]
+    AttestationBountyMismatch,
+    #[msg("Attestation is older than the bounty allows")]
+    AttestationExpired,
+    #[msg("Attestation timestamp is in the future")]
+    AttestationFromFuture,
+    #[msg("Attestation age limit must be positive")]
+    InvalidAttestationAge,
+    #[msg("Attestation has not been verified")]
+    AttestationNotVerified,
+    #[msg("Signer is not the config verifier")]
+    UnauthorizedVerifier,
+    #[msg("Attestation must be preceded by an ed25519 signature from the trusted attestor")]
+    AttestorSignatureMissing,
+    #[msg("Ed25519 instruction is malformed or references other instructions")]
+    InvalidEd25519Instruction,
+    #[msg("Ed25519 signature is not from the trusted attestor")]
+    AttestorMismatch,
+    #[msg("Attestor signed a different message than the attestation")]
+    AttestorMessageMismatch,
+    #[msg("Attestation has been revoked")]
+    AttestationRevoked,
+    #[msg("Only the agent or the config verifier can revoke an attestation")]
+    UnauthorizedRevoker,
+    #[msg("Attestation was already used by a submission")]
+    AttestationAlreadyConsumed,
+    #[msg("Not enough distinct registered attestors vouched for this solution")]
+    InsufficientAttestations,
+    #[msg("Too many attestors registered")]
+    TooManyAttestors,
+    #[msg("Required attestations exceed the maximum number of attestors")]
+    InvalidAttestationThreshold,
+    #[msg("Signer is not an active registered attestor")]
+    UnregisteredAttestor,
+    #[msg("Attestor name is too long")]
+    AttestorNameTooLong,
+    #[msg("Solution payload is too large")]
+    SolutionPayloadTooLarge,
+    #[msg("Solution must be revealed before settlement")]
+    SolutionNotRevealed,
+    #[msg("Attestation hash algorithm does not match the bounty")]
+    HashAlgoMismatch,
+    #[msg("Agent already has a pending submission for this bounty")]
+    SubmissionAlreadyExists,
+    #[msg("Submission count overflowed")]
+    SubmissionCountOverflow,
+    #[msg("Bounty does not accept competing submissions")]
+    BountyNotCompetitive,
+    #[msg("A winner has already been selected for this bounty")]
+    WinnerAlreadySelected,
+    #[msg("Submission does not belong to this bounty")]
+    SubmissionBountyMismatch,
+    #[msg("Submission is no longer pending")]
+    SubmissionNotPending,
+    #[msg("Submission is still in the running for this bounty")]
+    SubmissionStillActive,
+    #[msg("Prize schedule needs 1 to 5 nonzero places on a competitive bounty without reveal")]
+    InvalidPrizeSchedule,
+    #[msg("Prize schedule must add up to the reward")]
+    PrizeScheduleMismatch,
+    #[msg("Bounty has no prize schedule")]
+    NoPrizeSchedule,
+    #[msg("Bounty pays by prize schedule, settle it with settle_prizes")]
+    BountyHasPrizeSchedule,
+    #[msg("Number of winners must match the prize schedule")]
+    WinnerCountMismatch,
+    #[msg("The same agent cannot win more than one place")]
+    DuplicateWinner,
+    #[msg("Bounty is assigned to a different agent")]
+    NotAssignedAgent,
+    #[msg("Only the bounty creator can assign it")]
+    UnauthorizedAssignment,
+    #[msg("Bounty does not take applications")]
+    ApplicationsNotRequired,
+    #[msg("Application does not belong to this bounty")]
+    ApplicationBountyMismatch,
+    #[msg("Bounty is already assigned to an agent")]
+    BountyAlreadyAssigned,
+    #[msg("Bounty requires an accepted application from the agent")]
+    ApplicationNotAccepted,
+    #[msg("Application was accepted for a bounty that is still running")]
+    ApplicationStillActive,
+    #[msg("Solution is still in its challenge period")]
+    ChallengePeriodActive,
+    #[msg("Challenge period is over")]
+    ChallengeWindowClosed,
+    #[msg("Bounty is already challenged")]
+    BountyAlreadyChallenged,
+    #[msg("Bounty is not in Challenged status")]
+    BountyNotChallenged,
+    #[msg("Bounty is not in Cancelled status")]
+    BountyNotCancelled,
+    #[msg("Bounty has no arbiter to settle a dispute")]
+    NoArbiter,
+    #[msg("Only the creator or the solver can open a dispute")]
+    UnauthorizedDisputant,
+    #[msg("Bounty is not in Disputed status")]
+    BountyNotDisputed,
+    #[msg("Only the bounty's arbiter can resolve the dispute")]
+    UnauthorizedArbiter,
+    #[msg("Split must be between 0 and 10000 bps")]
+    InvalidSplitBps,
+    #[msg("A creator bond needs a non-zero settle window")]
+    SettleWindowRequired,
+    #[msg("Creator bond account is required")]
+    MissingCreatorBond,
+    #[msg("Bounty has no settle window to force settlement after")]
+    ForceSettleNotAllowed,
+    #[msg("Creator can still settle the bounty")]
+    SettleWindowActive,
+    #[msg("Bounty must be settled, cancelled or expired")]
+    BountyNotFinished,
+    #[msg("Only the creator can change the reward")]
+    UnauthorizedRewardChange,
+    #[msg("Reward is locked once a solution has been submitted")]
+    RewardLocked,
+    #[msg("Top-up must be greater than zero")]
+    ZeroTopUp,
+    #[msg("Reward overflow")]
+    RewardOverflow,
+    #[msg("Only the creator can update the bounty")]
+    UnauthorizedUpdate,
+    #[msg("Description is too long")]
+    DescriptionTooLong,
+    #[msg("Deadline can only be extended")]
+    DeadlineShortened,
+    #[msg("Only the creator can transfer the bounty")]
+    UnauthorizedOwnershipTransfer,
+    #[msg("Bounty can only change hands while Open or Submitted")]
+    BountyNotTransferable,
+    #[msg("Signer is not the pending creator")]
+    NotPendingCreator,
+    #[msg("New creator must differ from the current one")]
+    InvalidNewCreator,
+    #[msg("Escrow still holds funds")]
+    EscrowNotEmpty,
+    #[msg("Contributions have not all been refunded")]
+    ContributionsOutstanding,
+    #[msg("Close every submission before closing the reputation")]
+    ReputationInUse,
+    #[msg("A reputation with failed bounties cannot be closed")]
+    ReputationHasFailures,
+    #[msg("Bounty counter is already initialized")]
+    BountyCounterAlreadyInitialized,
+    #[msg("Bounty id overflow")]
+    BountyIdOverflow,
+    #[msg("Creator index is full, close a finished bounty first")]
+    IndexFull,
+    #[msg("Description must not be empty")]
+    EmptyDescription,
+    #[msg("URI is too long")]
+    UriTooLong,
+    #[msg("URI must be ASCII")]
+    UriNotAscii,
+    #[msg("Agent has not won enough bounties to submit")]
+    InsufficientReputation,
+    #[msg("Agent is not on the bounty's allowlist")]
+    AgentNotAllowlisted,
+    #[msg("Agent is already on the bounty's allowlist")]
+    AgentAlreadyAllowlisted,
+    #[msg("Bounty allowlist is full")]
+    AllowlistFull,
+    #[msg("Only the bounty creator can change the allowlist")]
+    UnauthorizedAllowlistChange,
+    #[msg("Agent has been banned by the admin")]
+    AgentBanned,
+    #[msg("Reward is below the configured minimum")]
+    RewardTooSmall,
+    #[msg("Reward is above the configured maximum")]
+    RewardTooLarge,
+    #[msg("Minimum reward must be non-zero and at most the maximum")]
+    InvalidRewardBounds,
+    #[msg("Escrow holds less than the bounty pays out")]
+    EscrowUnderfunded,
+    #[msg("Creator does not have enough lamports to wrap the reward")]
+    InsufficientLamports,
+    #[msg("Unwrapping a wrapped SOL refund needs the unwrap account")]
+    MissingUnwrapAccount,
+    #[msg("Milestones must be 1 to 4 non-zero payouts on a single-solver bounty")]
+    InvalidMilestoneSchedule,
+    #[msg("Milestone amounts must sum to the reward")]
+    MilestoneScheduleMismatch,
+    #[msg("Bounty has no milestones")]
+    NotMilestoneBounty,
+    #[msg("Milestone bounties are submitted one milestone at a time")]
+    MilestoneBounty,
+    #[msg("No milestone at that index")]
+    InvalidMilestoneIndex,
+    #[msg("Milestone has already been completed")]
+    MilestoneCompleted,
+    #[msg("Milestone has already been paid")]
+    MilestoneAlreadyPaid,
+    #[msg("Milestone has not been submitted")]
+    MilestoneNotSubmitted,
+    #[msg("Earlier milestones must be paid first")]
+    MilestoneOutOfOrder,
+    #[msg("Vesting can't be combined with prizes or milestones")]
+    InvalidVestingSchedule,
+    #[msg("Bounty has not been settled")]
+    BountyNotSettled,
+    #[msg("Bounty reward is not vesting")]
+    NotVesting,
+    #[msg("Nothing has vested since the last claim")]
+    NothingVested,
+    #[msg("Solver has not claimed the whole vested reward yet")]
+    VestingIncomplete,
+    #[msg("Only the bounty creator can close its receipt")]
+    UnauthorizedReceiptClose,
+    #[msg("Settlement receipt is still within its retention period")]
+    ReceiptRetentionActive,
+    #[msg("Account does not belong to the submission's attestor")]
+    NotSubmissionAttestor,
+    #[msg("Reputation account is already on the current layout")]
+    ReputationAlreadyMigrated,
+    #[msg("Maximum score per bounty must be at least 1")]
+    InvalidScoreCap,
+    #[msg("Reputation account already initialized")]
+    ReputationAlreadyInitialized,
+    #[msg("Only the bounty creator can leave feedback")]
+    UnauthorizedFeedback,
+    #[msg("Feedback was already left for this bounty")]
+    FeedbackAlreadyGiven,
+    #[msg("Rating must be between 1 and 5")]
+    InvalidRating,
+    #[msg("Creators cannot solve their own bounties")]
+    SelfDealingNotAllowed,
+    #[msg("Snapshot needs a nonzero root and at least one leaf")]
+    InvalidSnapshot,
+    #[msg("A snapshot for this epoch already exists")]
+    SnapshotAlreadyExists,
+    #[msg("Proof does not match the snapshot root")]
+    InvalidReputationProof,
+    #[msg("Bounty is on an older layout, run migrate_bounty first")]
+    AccountNeedsMigration,
+    #[msg("Bounty account is already on the current layout")]
+    BountyAlreadyMigrated,
+    #[msg("Bounty account is on a layout migrate_bounty does not know")]
+    UnknownBountyLayout,
+    #[msg("Batch is empty or holds more items than allowed")]
+    InvalidBatchSize,
+    #[msg("Batch accounts do not match the bounties being posted")]
+    BatchAccountMismatch,
+    #[msg("The bounty creator's account is needed when the settlement authority signs")]
+    MissingBountyCreator,
+    #[msg("Only the creator can change the settlement authority")]
+    UnauthorizedDelegation,
+    #[msg("An agent can't be its own operator")]
+    InvalidOperator,
+    #[msg("Signer is neither the agent nor one of its registered operators")]
+    UnauthorizedOperator,
+    #[msg("Reward decrease must be greater than zero")]
+    ZeroRewardDecrease,
+    #[msg("Reward can't drop below what contributors put in")]
+    RewardBelowContributions,
+    #[msg("Too many oracle feeds, or the same feed named twice")]
+    InvalidOracleFeeds,
+    #[msg(
+        "Oracle bounds need an oracle, a minimum no greater than the maximum and a sane exponent"
+    )]
+    InvalidOracleBounds,
+    #[msg("Oracle value is outside the bounty's bounds")]
+    OracleValueOutOfRange,
+    #[msg("No payment gateway is configured to sign receipts")]
+    PaymentGatewayNotConfigured,
+    #[msg("Settlement needs the gateway's payment receipt in the preceding ed25519 instruction")]
+    PaymentReceiptMissing,
+    #[msg("Payment receipt was not signed by the configured gateway")]
+    PaymentReceiptSignerMismatch,
+    #[msg("Payment receipt is for a different bounty or solution")]
+    PaymentReceiptMismatch,
+    #[msg("SAS attestations can't count towards an attestor threshold")]
+    InvalidAttestationSource,
+    #[msg("The bounty's attestation source needs its attestation account")]
+    MissingAttestation,
+    #[msg("Not a Solana Attestation Service attestation under the bounty's schema")]
+    SasAttestationInvalid,
+    #[msg("SAS attestation was issued to someone other than the agent")]
+    SasHolderMismatch,
+    #[msg("SAS attestation has expired")]
+    SasAttestationExpired,
+    #[msg("Repeatable bounties need max_claims of at least 1 and a single plain payout per claim")]
+    InvalidClaimSchedule,
+    #[msg("Not available on a repeatable bounty, settle each claim with settle_bounty")]
+    RepeatableBounty,
+    #[msg("Agent has already been paid a claim on this bounty")]
+    AlreadyClaimed,
+    #[msg("Agent must wait out the resubmission cooldown after a rejection")]
+    CooldownActive,
+    #[msg("Abandonment period must be greater than zero")]
+    InvalidAbandonmentPeriod,
+    #[msg("Bounty still owes its escrow to an agent or contributors and can't be swept")]
+    BountyNotSweepable,
+    #[msg("Bounty has seen activity within the abandonment period")]
+    BountyNotAbandoned,
+    #[msg("Protocol stats counter overflow")]
+    StatsOverflow,
+    #[msg("Bounty mints a badge, pass the badge accounts to settle it")]
+    MissingBadgeAccounts,
+    #[msg("Too many trusted mints")]
+    TooManyTrustedMints,
+    #[msg("Reward mint has a permanent delegate that could move the escrow")]
+    UnsafeRewardMintPermanentDelegate,
+    #[msg("Reward mint has a transfer hook into a program that could block payouts")]
+    UnsafeRewardMintTransferHook,
+    #[msg("Reward mint freezes new token accounts by default")]
+    UnsafeRewardMintDefaultFrozen,
+    #[msg("Reward mint can be frozen by the creator")]
+    UnsafeRewardMintFreezeAuthority,
+    #[msg("Boosting is disabled")]
+    BoostingDisabled,
+    #[msg("Boost window or total overflow")]
+    BoostOverflow,
+    #[msg("Decay schedule needs a cutoff, a decay rate and a floor of at most 10000 bps, on a single-payout bounty")]
+    InvalidDecaySchedule,
+    #[msg("Team split needs up to 4 distinct members with non-zero shares summing to 10000 bps, on a single-payout bounty without vesting")]
+    InvalidTeamSplit,
+    #[msg("Pass every team member's payout account, in table order")]
+    MissingTeamMemberAccount,
+    #[msg("Account isn't the team member's payout account")]
+    TeamMemberAccountMismatch,
+    #[msg("Team submissions can't be settled in a batch, use settle_bounty")]
+    TeamSettlementUnsupported,
+    #[msg("Creator must reveal the committed acceptance criteria first")]
+    CriteriaNotRevealed,
+    #[msg("Criteria don't hash to the bounty's criteria_hash")]
+    CriteriaHashMismatch,
+    #[msg("Bounty has no acceptance criteria commitment")]
+    NoCriteriaCommitment,
+    #[msg("Acceptance criteria are too large")]
+    CriteriaTooLarge,
+    #[msg("Only the bounty creator can reveal its acceptance criteria")]
+    UnauthorizedCriteriaReveal,
+    #[msg("Closing stale attestations is disabled")]
+    AttestationCleanupDisabled,
+    #[msg("Attestation is younger than the attestation TTL")]
+    AttestationNotStale,
+    #[msg("Attestation is verified, revoked or backing a live submission and can't be closed")]
+    AttestationNotCloseable,
+    #[msg("Instruction must be called directly, not through another program")]
+    CpiNotAllowed,
+    #[msg("Bounty target doesn't fit its type")]
+    InvalidBountyTarget,
+    #[msg("Creator's token account holds less than the reward")]
+    InsufficientRewardBalance,
+    #[msg("Creator's token account is frozen")]
+    TokenAccountFrozen,
+    #[msg("Attestor's track record is below what the bounty requires")]
+    AttestorTrackRecordTooLow,
+    #[msg("Funding pool doesn't have enough unallocated funds")]
+    PoolInsufficient,
+    #[msg("Funding pool and its token account are required")]
+    MissingFundingPool,
+    #[msg("Token account does not belong to the funding pool")]
+    PoolAccountMismatch,
+    #[msg("Bounty already holds its maximum number of submissions")]
+    SubmissionLimitReached,
+    #[msg("Submission can only be withdrawn before the creator acts on it")]
+    SubmissionNotWithdrawable,
+    #[msg("Ciphertext commitment is required exactly when the bounty has an encryption key")]
+    InvalidCiphertextHash,
+    #[msg("Revealed ciphertext doesn't match the submission's commitment")]
+    CiphertextHashMismatch,
+    #[msg("Market name must be 1 to MAX_MARKET_LEN bytes")]
+    InvalidMarketName,
+    #[msg("Account belongs to a different market than the bounty")]
+    MarketMismatch,
+    #[msg("Insurance fund holds less than requested")]
+    InsuranceFundInsufficient,
+    #[msg("Token account does not belong to the insurance fund")]
+    InsuranceAccountMismatch,
+    #[msg("Account's rent was sponsored, pass the sponsor to refund it")]
+    MissingRentPayer,
+    #[msg("Account is not the one that paid the rent")]
+    RentPayerMismatch,
+    #[msg("A rejected agent's appeal is pending, only they can be paid until it ends")]
+    AppealPending,
+    #[msg("Appeal window has closed, or appeals are disabled")]
+    AppealWindowClosed,
+    #[msg("Rejection has already been appealed")]
+    AlreadyAppealed,
+    #[msg("Rejection is not under appeal")]
+    RejectionNotContested,
+    #[msg("Account is already on the zero-copy layout")]
+    AccountAlreadyMigrated,
+    #[msg("Account is not on the Borsh layout the migration grows")]
+    UnknownAccountLayout,
+    #[msg("Rent payer can't cover the bounty and escrow accounts")]
+    InsufficientRentFunds,
+    #[msg("Payment receipt shows less than the verification fee")]
+    PaymentReceiptUnderpaid,
+    #[msg("Creator is not the one recorded on the legacy bounty")]
+    LegacyCreatorMismatch,
 }
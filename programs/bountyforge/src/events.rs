@@ -0,0 +1,321 @@
+use anchor_lang::prelude::*;
+
+use crate::state::{BountyStatus, BountyType, RejectionReason};
+
+#[event]
+pub struct BountyPosted {
+    pub bounty: Pubkey,
+    pub bounty_id: u64,
+    pub creator: Pubkey,
+    pub reward: u64,
+    pub bounty_type: BountyType,
+    pub target: Pubkey,
+    pub metadata_uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountyUpdated {
+    pub bounty: Pubkey,
+    pub old_description: String,
+    pub new_description: String,
+    pub old_deadline: i64,
+    pub new_deadline: i64,
+    pub old_metadata_uri: String,
+    pub new_metadata_uri: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardIncreased {
+    pub bounty: Pubkey,
+    pub old_reward: u64,
+    pub new_reward: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardDecreased {
+    pub bounty: Pubkey,
+    pub old_reward: u64,
+    pub new_reward: u64,
+    pub refund: u64, // taken out of escrow, before any transfer fee
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ContributionReceived {
+    pub bounty: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    pub total: u64, // everything this contributor has put in so far
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ContributionRefunded {
+    pub bounty: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SolutionAttested {
+    pub attestation: Pubkey,
+    pub agent: Pubkey,
+    pub solution_id: u64,
+    pub solution_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SolutionSubmitted {
+    pub bounty: Pubkey,
+    pub agent: Pubkey,
+    pub solution_hash: [u8; 32],
+    pub solution_uri: String,
+    pub remaining_submissions: Option<u16>, // slots left on the bounty, None when unlimited
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct WinnerSelected {
+    pub bounty: Pubkey,
+    pub agent: Pubkey,
+    pub solution_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneSubmitted {
+    pub bounty: Pubkey,
+    pub agent: Pubkey,
+    pub index: u8,
+    pub solution_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct MilestoneSettled {
+    pub bounty: Pubkey,
+    pub solver: Pubkey,
+    pub index: u8,
+    pub solver_amount: u64,
+    pub fee_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct VestedClaimed {
+    pub bounty: Pubkey,
+    pub solver: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CreatorStatsUpdated {
+    pub creator: Pubkey,
+    pub total_posted: u64,
+    pub total_settled: u64,
+    pub total_cancelled: u64,
+    pub total_paid_out: u64,
+    pub avg_settle_delay_slots: u64,
+    pub disputes_lost: u32,
+}
+
+#[event]
+pub struct AttestorStatsUpdated {
+    pub attestor: Pubkey,
+    pub attestations_issued: u64,
+    pub attestations_consumed: u64,
+    pub attestations_disputed: u64,
+    pub track_record: u64,
+}
+
+#[event]
+pub struct FeedbackLeft {
+    pub bounty: Pubkey,
+    pub agent: Pubkey,
+    pub rating: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReputationSnapshotted {
+    pub epoch: u64,
+    pub root: [u8; 32],
+    pub leaf_count: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ReputationDecayed {
+    pub agent: Pubkey,
+    pub halvings: u32,
+    pub new_score: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountySettled {
+    pub bounty: Pubkey,
+    pub solver: Pubkey,
+    pub solver_amount: u64,
+    pub fee_amount: u64,
+    pub withheld: u64, // held back from a late solution for the creator, 0 when on time
+    pub insurance_topup: u64, // escrow shortfall the insurance fund made up, 0 when none
+    pub streak: Option<u32>, // solver's current streak, None for prize places which skip reputation
+    pub reward_mint: Pubkey, // Pubkey::default() for Sol bounties
+    pub mint_earned: Option<u64>, // solver's lifetime earnings in reward_mint, None like streak, 0 once out of slots
+    pub payment_amount: Option<u64>, // verification fee a required x402 receipt shows, None without one
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BadgeMinted {
+    pub bounty: Pubkey,
+    pub solver: Pubkey,
+    pub mint: Pubkey, // PDA `["badge", bounty, claim]`
+    pub claim: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountyMigrated {
+    pub bounty: Pubkey,
+    pub legacy_bounty: Pubkey, // the 0.1.0 address it was moved from, now closed
+    pub bounty_id: u64,
+    pub version: u8,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountyCranked {
+    pub bounty: Pubkey,
+    pub cranker: Pubkey,
+    pub refund: u64,    // paid to the creator
+    pub incentive: u64, // paid to the cranker
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountySwept {
+    pub bounty: Pubkey,
+    pub bounty_id: u64,
+    pub creator: Pubkey,
+    pub status: BountyStatus, // before the sweep
+    pub mint: Pubkey,         // Pubkey::default() for Sol bounties
+    pub amount: u64,          // moved from escrow to the treasury
+    pub treasury: Pubkey,
+    pub last_activity_at: i64,
+    pub swept_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttestationClosed {
+    pub attestation: Pubkey,
+    pub bounty: Pubkey,
+    pub agent: Pubkey,
+    pub cranker: Pubkey,
+    pub tip: u64, // lamports of the rent paid to the cranker, the rest went to the agent
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CriteriaRevealed {
+    pub bounty: Pubkey,
+    pub criteria_hash: [u8; 32],
+    pub criteria: Vec<u8>, // kept in the log only, the bounty just records that they matched
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct BountyBoosted {
+    pub bounty: Pubkey,
+    pub booster: Pubkey,
+    pub mint: Pubkey, // Pubkey::default() for Sol bounties
+    pub fee: u64,     // paid to the treasury
+    pub boosted_until: i64,
+    pub total_boost_paid: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OperatorRegistered {
+    pub agent: Pubkey,
+    pub operator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OperatorRevoked {
+    pub agent: Pubkey,
+    pub operator: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FundingPoolUpdated {
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub deposited: u64,
+    pub allocated: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SubmissionWithdrawn {
+    pub bounty: Pubkey,
+    pub agent: Pubkey,
+    pub stake: u64, // returned to the agent with the submission's rent
+    pub withdrawals: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InsuranceFundUpdated {
+    pub fund: Pubkey,
+    pub mint: Pubkey,
+    pub balance: u64,
+    pub total_deposited: u64,
+    pub total_fees: u64,
+    pub total_topups: u64,
+    pub total_withdrawn: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct SolutionRejected {
+    pub bounty: Pubkey,
+    pub agent: Pubkey,
+    pub rejection: Pubkey,
+    pub attempt_index: u32,
+    pub reason: RejectionReason,
+    pub note_hash: Option<[u8; 32]>,
+    pub rejected_by: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RejectionAppealed {
+    pub bounty: Pubkey,
+    pub agent: Pubkey,
+    pub rejection: Pubkey,
+    pub appeal_ends_at: i64, // settling with another agent waits until then, or the arbiter's ruling
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AppealResolved {
+    pub bounty: Pubkey,
+    pub agent: Pubkey,
+    pub rejection: Pubkey,
+    pub upheld: bool, // the rejection was overturned and its failure taken back
+    pub arbiter: Pubkey,
+    pub timestamp: i64,
+}
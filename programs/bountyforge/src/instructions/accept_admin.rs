@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BountyForgeError;
+use crate::state::Config;
+
+#[derive(Accounts)]
+#[instruction(market: String)]
+pub struct AcceptAdmin<'info> {
+    pub new_admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", market.as_bytes()],
+        bump = config.bump,
+        constraint = config.pending_admin == Some(new_admin.key()) @ BountyForgeError::NotPendingAdmin
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<()> {
+        self.config.admin = self.new_admin.key();
+        self.config.pending_admin = None;
+
+        Ok(())
+    }
+}
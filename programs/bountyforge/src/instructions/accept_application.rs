@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::state::{Application, ApplicationStatus, Bounty, BountyStatus};
+
+#[derive(Accounts)]
+pub struct AcceptApplication<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = creator.key() == bounty.creator @ BountyForgeError::UnauthorizedAssignment,
+        constraint = bounty.requires_application @ BountyForgeError::ApplicationsNotRequired,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen,
+        constraint = bounty.assignee.is_none() @ BountyForgeError::BountyAlreadyAssigned
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        mut,
+        constraint = application.bounty == bounty.key() @ BountyForgeError::ApplicationBountyMismatch
+    )]
+    pub application: Account<'info, Application>,
+}
+
+impl<'info> AcceptApplication<'info> {
+    pub fn accept_application(&mut self) -> Result<()> {
+        // accepting locks the bounty to the applicant through its assignee
+        self.application.status = ApplicationStatus::Accepted;
+        self.bounty.assignee = Some(self.application.agent);
+
+        Ok(())
+    }
+}
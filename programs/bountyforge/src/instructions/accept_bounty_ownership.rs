@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{ANCHOR_DISCRIMINATOR, CURRENT_BOUNTY_VERSION};
+use crate::errors::BountyForgeError;
+use crate::state::{Bounty, BountyStatus, Config, CreatorProfile};
+
+#[derive(Accounts)]
+pub struct AcceptBountyOwnership<'info> {
+    #[account(mut)]
+    pub new_creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.pending_creator == Some(new_creator.key()) @ BountyForgeError::NotPendingCreator,
+        constraint = matches!(bounty.status, BountyStatus::Open | BountyStatus::Submitted) @ BountyForgeError::BountyNotTransferable
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator", bounty.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = new_creator,
+        space = ANCHOR_DISCRIMINATOR + CreatorProfile::INIT_SPACE,
+        seeds = [b"creator", new_creator.key().as_ref()],
+        bump
+    )]
+    pub new_creator_profile: Account<'info, CreatorProfile>,
+
+    #[account(seeds = [b"config", bounty.market.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AcceptBountyOwnership<'info> {
+    pub fn accept_bounty_ownership(&mut self, bumps: &AcceptBountyOwnershipBumps) -> Result<()> {
+        // 1. the open bounty slot moves to the new owner's profile, within their limit
+        if self.new_creator_profile.creator == Pubkey::default() {
+            self.new_creator_profile.set_inner(CreatorProfile {
+                creator: self.new_creator.key(),
+                open_bounty_count: 0,
+                last_post_ts: 0,
+                disputes_lost: 0,
+                total_posted: 0,
+                total_settled: 0,
+                total_cancelled: 0,
+                total_paid_out: 0,
+                total_settle_delay_slots: 0,
+                timed_settlements: 0,
+                bump: bumps.new_creator_profile,
+            });
+        }
+        require!(
+            self.new_creator_profile.open_bounty_count < self.config.max_open_per_creator,
+            BountyForgeError::TooManyOpenBounties
+        );
+        self.new_creator_profile.open_bounty_count += 1;
+        self.creator_profile.release_open_slot();
+
+        // 2. every creator-gated instruction now answers to the new owner, whose
+        // settlements go through nobody the previous owner trusted. The rent
+        // comes with the bounty, unless a sponsor paid it and is still owed it
+        if self.bounty.rent_payer == self.bounty.creator {
+            self.bounty.rent_payer = self.new_creator.key();
+        }
+        self.bounty.creator = self.new_creator.key();
+        self.bounty.pending_creator = None;
+        self.bounty.settlement_authority = None;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CURRENT_BOUNTY_VERSION, MAX_ALLOWLIST};
+use crate::errors::BountyForgeError;
+use crate::state::{Bounty, BountyStatus};
+
+#[derive(Accounts)]
+pub struct AddAllowlistedAgent<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = creator.key() == bounty.creator @ BountyForgeError::UnauthorizedAllowlistChange,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+}
+
+impl<'info> AddAllowlistedAgent<'info> {
+    /// Lets `agent` submit, closing the bounty to everyone else if the list was empty
+    pub fn add_allowlisted_agent(&mut self, agent: Pubkey) -> Result<()> {
+        require!(
+            !self.bounty.allowlisted_agents().contains(&agent),
+            BountyForgeError::AgentAlreadyAllowlisted
+        );
+        let len = self.bounty.allowlist_len as usize;
+        require!(len < MAX_ALLOWLIST, BountyForgeError::AllowlistFull);
+
+        self.bounty.allowlist[len] = agent;
+        self.bounty.allowlist_len += 1;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::events::RejectionAppealed;
+use crate::state::{AppealStatus, Bounty, BountyStatus, Config, Rejection};
+
+#[derive(Accounts)]
+pub struct AppealRejection<'info> {
+    pub agent: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = matches!(bounty.status, BountyStatus::Open | BountyStatus::Submitted) @ BountyForgeError::BountyNotOpen
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"rejection",
+            bounty.key().as_ref(),
+            agent.key().as_ref(),
+            rejection.attempt_index.to_le_bytes().as_ref()
+        ],
+        bump = rejection.bump,
+        constraint = rejection.appeal == AppealStatus::Unappealed @ BountyForgeError::AlreadyAppealed
+    )]
+    pub rejection: Account<'info, Rejection>,
+
+    #[account(seeds = [b"config", bounty.market.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> AppealRejection<'info> {
+    /// Contests a rejection within the Config `appeal_window_seconds` of it.
+    /// Until that window has passed or the bounty's arbiter rules with
+    /// resolve_appeal, the creator can only pay the appellant. A bounty holds
+    /// one live appeal at a time.
+    pub fn appeal_rejection(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let window = self.config.appeal_window_seconds;
+        let appeal_ends_at = self.rejection.rejected_at.saturating_add_unsigned(window);
+        require!(
+            window > 0 && now < appeal_ends_at,
+            BountyForgeError::AppealWindowClosed
+        );
+        require!(
+            self.bounty.appellant.is_none() || now >= self.bounty.appeal_ends_at,
+            BountyForgeError::AppealPending
+        );
+
+        self.rejection.appeal = AppealStatus::Contested;
+        self.bounty.appellant = Some(self.agent.key());
+        self.bounty.appeal_ends_at = appeal_ends_at;
+
+        emit!(RejectionAppealed {
+            bounty: self.bounty.key(),
+            agent: self.agent.key(),
+            rejection: self.rejection.key(),
+            appeal_ends_at,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{ANCHOR_DISCRIMINATOR, CURRENT_BOUNTY_VERSION};
+use crate::errors::BountyForgeError;
+use crate::state::{Application, ApplicationStatus, Bounty, BountyStatus};
+
+#[derive(Accounts)]
+pub struct ApplyToBounty<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    #[account(
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.requires_application @ BountyForgeError::ApplicationsNotRequired,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen,
+        constraint = bounty.assignee.is_none() @ BountyForgeError::BountyAlreadyAssigned
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        init,
+        payer = agent,
+        space = ANCHOR_DISCRIMINATOR + Application::INIT_SPACE,
+        seeds = [b"application", bounty.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub application: Account<'info, Application>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ApplyToBounty<'info> {
+    pub fn apply_to_bounty(
+        &mut self,
+        note_hash: [u8; 32],
+        bumps: &ApplyToBountyBumps,
+    ) -> Result<()> {
+        self.application.set_inner(Application {
+            bounty: self.bounty.key(),
+            agent: self.agent.key(),
+            note_hash,
+            timestamp: Clock::get()?.unix_timestamp,
+            status: ApplicationStatus::Pending,
+            bump: bumps.application,
+        });
+
+        Ok(())
+    }
+}
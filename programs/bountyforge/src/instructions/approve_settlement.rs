@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::state::{Bounty, BountyStatus};
+
+#[derive(Accounts)]
+pub struct ApproveSettlement<'info> {
+    /// The creator, or the settlement authority they named on the bounty
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.status == BountyStatus::Submitted @ BountyForgeError::BountyNotSubmitted,
+        constraint = bounty.solver.is_some() @ BountyForgeError::BountyNotSubmitted,
+        constraint = bounty.can_settle(&creator.key()) @ BountyForgeError::UnauthorizedSettlement,
+        constraint = !bounty.requires_reveal || bounty.revealed @ BountyForgeError::SolutionNotRevealed,
+        // the receipt is checked when paying out, which claim_reward can't do on the creator's behalf
+        constraint = !bounty.requires_payment_receipt @ BountyForgeError::PaymentReceiptMissing,
+        constraint = !bounty.is_repeatable() @ BountyForgeError::RepeatableBounty
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+}
+
+impl<'info> ApproveSettlement<'info> {
+    pub fn approve_settlement(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.slot >= self.bounty.challenge_ends_at_slot(),
+            BountyForgeError::ChallengePeriodActive
+        );
+
+        // the solver pulls the reward with claim_reward
+        self.bounty.status = BountyStatus::Approved;
+
+        Ok(())
+    }
+}
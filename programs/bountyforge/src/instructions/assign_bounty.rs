@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::state::{Bounty, BountyStatus, Config};
+
+#[derive(Accounts)]
+pub struct AssignBounty<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = creator.key() == bounty.creator @ BountyForgeError::UnauthorizedAssignment,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(seeds = [b"config", bounty.market.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> AssignBounty<'info> {
+    /// Hands the bounty to `assignee`, or opens it to any agent again with `None`
+    pub fn assign_bounty(&mut self, assignee: Option<Pubkey>) -> Result<()> {
+        if let Some(assignee) = assignee {
+            self.config
+                .check_not_self_dealing(&self.bounty.creator, &assignee)?;
+        }
+        self.bounty.assignee = assignee;
+
+        Ok(())
+    }
+}
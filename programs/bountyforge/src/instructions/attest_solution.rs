@@ -1,43 +1,149 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
 
-use crate::{constants::ANCHOR_DISCRIMINATOR, state::Attestation};
+use crate::{
+    constants::{ANCHOR_DISCRIMINATOR, CURRENT_BOUNTY_VERSION},
+    errors::BountyForgeError,
+    events::SolutionAttested,
+    state::{AgentFlag, Attestation, Attestor, Bounty, Config, HashAlgo, Reputation},
+    utils::{attestation_message, signing_attestor},
+};
 
 #[derive(Accounts)]
-#[instruction(solution_id : u64)]
 pub struct AttestSolution<'info> {
     #[account(mut)]
     pub agent: Signer<'info>,
 
+    /// CHECK: The agent's Attestor PDA, which may not exist yet
+    /// Must be an active registration, checked in the handler
+    #[account(
+        mut,
+        seeds = [b"attestor", bounty.market.as_bytes(), agent.key().as_ref()],
+        bump
+    )]
+    pub attestor_account: UncheckedAccount<'info>,
+
+    /// CHECK: The agent's AgentFlag PDA, which usually doesn't exist
+    /// Not optional so a banned agent can't leave it out, checked in the handler
+    #[account(
+        seeds = [b"flag", bounty.market.as_bytes(), agent.key().as_ref()],
+        bump
+    )]
+    pub agent_flag: UncheckedAccount<'info>,
+
+    #[account(
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    /// Created beforehand with init_reputation, hands out the solution id
+    #[account(
+        mut,
+        seeds = [b"rep", agent.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
     #[account(
         init,
         payer = agent,
         space = ANCHOR_DISCRIMINATOR + Attestation::INIT_SPACE,
-        seeds = [b"attest", solution_id.to_le_bytes().as_ref()],
+        seeds = [
+            b"attestation",
+            agent.key().as_ref(),
+            bounty.key().as_ref(),
+            reputation.next_solution_id.to_le_bytes().as_ref()
+        ],
         bump
     )]
     pub attestation: Account<'info, Attestation>,
 
+    #[account(
+        seeds = [b"config", bounty.market.as_bytes()],
+        bump = config.bump,
+        constraint = !config.paused @ BountyForgeError::ProgramPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Instructions sysvar, only needed when the config names a trusted attestor
+    /// The preceding ed25519 instruction is read from it in the handler
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<AccountInfo<'info>>,
+
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> AttestSolution<'info> {
+    /// Attests under the agent's next solution id, which the attestation's
+    /// address is derived from, so an id can only ever be used once.
     pub fn attest_solution(
         &mut self,
-        solution_id: u64,
         solution_hash: [u8; 32],
+        hash_algo: HashAlgo,
         bumps: &AttestSolutionBumps,
     ) -> Result<()> {
+        // only registered attestors can create attestations
+        require!(
+            self.is_active_attestor()?,
+            BountyForgeError::UnregisteredAttestor
+        );
+        require!(
+            !AgentFlag::is_banned(&self.agent_flag)?,
+            BountyForgeError::AgentBanned
+        );
+
+        let solution_id = self.reputation.take_solution_id()?;
+
+        // a registered attestor may countersign this exact attestation, and must
+        // when the config names a trusted attestor
+        let message = attestation_message(&self.agent.key(), solution_id, &solution_hash);
+        let attestor = match self.instructions_sysvar.as_ref() {
+            Some(instructions_sysvar) => signing_attestor(instructions_sysvar, &message)?,
+            None => None,
+        };
+        match attestor {
+            Some(attestor) => require!(
+                self.config.is_registered_attestor(&attestor),
+                BountyForgeError::AttestorMismatch
+            ),
+            None => require!(
+                self.config.trusted_attestor.is_none(),
+                BountyForgeError::AttestorSignatureMissing
+            ),
+        }
+
         let now = Clock::get()?.unix_timestamp;
 
         self.attestation.set_inner(Attestation {
             solution_id,
             solution_hash,
+            hash_algo,
             timestamp: now,
             agent: self.agent.key(),
+            bounty: self.bounty.key(),
             verified: false,
+            attestor,
+            revoked: false,
+            consumed: false,
+            market: self.bounty.market.clone(),
             bump: bumps.attestation,
         });
+        Attestor::update(&self.attestor_account, |attestor| {
+            attestor.attestations_issued = attestor.attestations_issued.saturating_add(1);
+        })?;
+
+        emit!(SolutionAttested {
+            attestation: self.attestation.key(),
+            agent: self.agent.key(),
+            solution_id,
+            solution_hash,
+            timestamp: now,
+        });
 
         Ok(())
     }
+
+    fn is_active_attestor(&self) -> Result<bool> {
+        Ok(Attestor::load(&self.attestor_account)?.is_some_and(|attestor| attestor.active))
+    }
 }
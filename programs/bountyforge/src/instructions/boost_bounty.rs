@@ -0,0 +1,142 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::events::BountyBoosted;
+use crate::state::{Bounty, BountyStatus, Config, RewardCurrency};
+use crate::utils::split_fee;
+
+#[derive(Accounts)]
+pub struct BoostBounty<'info> {
+    /// Anyone, usually the creator
+    #[account(mut)]
+    pub booster: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        seeds = [b"config", bounty.market.as_bytes()],
+        bump = config.bump,
+        constraint = !config.paused @ BountyForgeError::ProgramPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Protocol treasury wallet, receives the fee of Sol bounties
+    #[account(mut, address = config.treasury @ BountyForgeError::TreasuryMismatch)]
+    pub treasury: AccountInfo<'info>,
+
+    /// Omitted for Sol bounties
+    #[account(
+        mut,
+        constraint = booster_token_account.owner == booster.key(),
+        constraint = booster_token_account.mint == bounty.reward_mint
+    )]
+    pub booster_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Receives the fee of token bounties, omitted for Sol bounties
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == config.treasury @ BountyForgeError::TreasuryMismatch,
+        constraint = treasury_token_account.mint == bounty.reward_mint
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BoostBounty<'info> {
+    /// Features an open bounty for the Config `boost_duration_seconds` in
+    /// exchange for `boost_fee_bps` of its reward, paid to the treasury in the
+    /// reward's currency. Boosting again extends an unexpired window. Nothing
+    /// outside this instruction reads the boost, so settlement is unaffected.
+    pub fn boost_bounty(&mut self) -> Result<()> {
+        let duration = self.config.boost_duration_seconds;
+        require!(duration > 0, BountyForgeError::BoostingDisabled);
+
+        // 1. charging the fee, any rounding in the treasury's favour
+        let (_, fee) = split_fee(self.bounty.reward, self.config.boost_fee_bps)?;
+        if fee > 0 {
+            match self.bounty.reward_currency {
+                RewardCurrency::Spl => self.pay_fee_tokens(fee)?,
+                RewardCurrency::Sol => self.pay_fee_lamports(fee)?,
+            }
+        }
+
+        // 2. extending the window from whichever is later, now or its current end
+        let now = Clock::get()?.unix_timestamp;
+        let bounty = &mut self.bounty;
+        bounty.boosted_until = bounty
+            .boosted_until
+            .max(now)
+            .checked_add(duration as i64)
+            .ok_or(BountyForgeError::BoostOverflow)?;
+        bounty.total_boost_paid = bounty
+            .total_boost_paid
+            .checked_add(fee)
+            .ok_or(BountyForgeError::BoostOverflow)?;
+
+        emit!(BountyBoosted {
+            bounty: bounty.key(),
+            booster: self.booster.key(),
+            mint: bounty.reward_mint,
+            fee,
+            boosted_until: bounty.boosted_until,
+            total_boost_paid: bounty.total_boost_paid,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    fn pay_fee_tokens(&self, fee: u64) -> Result<()> {
+        let reward_mint = self
+            .reward_mint
+            .as_ref()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+        let booster_token_account = self
+            .booster_token_account
+            .as_ref()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+        let treasury_token_account = self
+            .treasury_token_account
+            .as_ref()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+
+        let cpi_accounts = TransferChecked {
+            from: booster_token_account.to_account_info(),
+            mint: reward_mint.to_account_info(),
+            to: treasury_token_account.to_account_info(),
+            authority: self.booster.to_account_info(),
+        };
+        transfer_checked(
+            CpiContext::new(self.token_program.to_account_info(), cpi_accounts),
+            fee,
+            reward_mint.decimals,
+        )
+    }
+
+    fn pay_fee_lamports(&self, fee: u64) -> Result<()> {
+        let cpi_accounts = system_program::Transfer {
+            from: self.booster.to_account_info(),
+            to: self.treasury.to_account_info(),
+        };
+        system_program::transfer(
+            CpiContext::new(self.system_program.to_account_info(), cpi_accounts),
+            fee,
+        )
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::{ANCHOR_DISCRIMINATOR, CURRENT_BOUNTY_VERSION};
+use crate::errors::BountyForgeError;
+use crate::state::{Bounty, BountyStatus, Challenge, Config};
+
+#[derive(Accounts)]
+pub struct ChallengeSolution<'info> {
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.status != BountyStatus::Challenged @ BountyForgeError::BountyAlreadyChallenged,
+        constraint = bounty.status == BountyStatus::Submitted @ BountyForgeError::BountyNotSubmitted
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    // init_if_needed so a second challenge fails on the bounty status above
+    #[account(
+        init_if_needed,
+        payer = challenger,
+        space = ANCHOR_DISCRIMINATOR + Challenge::INIT_SPACE,
+        seeds = [b"challenge", bounty.key().as_ref()],
+        bump
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    #[account(seeds = [b"config", bounty.market.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ChallengeSolution<'info> {
+    pub fn challenge_solution(&mut self, bumps: &ChallengeSolutionBumps) -> Result<()> {
+        require!(
+            Clock::get()?.slot < self.bounty.challenge_ends_at_slot(),
+            BountyForgeError::ChallengeWindowClosed
+        );
+
+        // 1. escrowing the bond on the challenge account, on top of its rent
+        let bond = self.config.challenge_bond_lamports;
+        let cpi_accounts = system_program::Transfer {
+            from: self.challenger.to_account_info(),
+            to: self.challenge.to_account_info(),
+        };
+        system_program::transfer(
+            CpiContext::new(self.system_program.to_account_info(), cpi_accounts),
+            bond,
+        )?;
+
+        self.challenge.set_inner(Challenge {
+            bounty: self.bounty.key(),
+            challenger: self.challenger.key(),
+            bond,
+            created_at: Clock::get()?.unix_timestamp,
+            bump: bumps.challenge,
+        });
+
+        // 2. holding settlement until the challenge is resolved
+        self.bounty.status = BountyStatus::Challenged;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,311 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::{ANCHOR_DISCRIMINATOR, CURRENT_BOUNTY_VERSION};
+use crate::errors::BountyForgeError;
+use crate::events::BountySettled;
+use crate::state::{
+    Bounty, BountyStats, BountyStatus, Config, CreatorProfile, InsuranceFund, Leaderboard,
+    Reputation, SettlementRecord, Submission, SubmissionStatus,
+};
+use crate::utils::{
+    cover_shortfall, mint_badge, pay_attestor_share, pay_from_escrow, pay_insurance, pay_solver,
+    transfer_stake,
+};
+
+#[derive(Accounts)]
+pub struct ClaimReward<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.status == BountyStatus::Approved @ BountyForgeError::BountyNotApproved,
+        constraint = bounty.solver == Some(agent.key()) @ BountyForgeError::UnauthorizedClaim
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator", bounty.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    /// Counters of the bounty's market
+    #[account(mut, seeds = [b"stats", bounty.market.as_bytes()], bump = stats.load()?.bump)]
+    pub stats: AccountLoader<'info, BountyStats>,
+
+    /// Top agents by score
+    #[account(mut, seeds = [b"leaderboard"], bump = leaderboard.load()?.bump)]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+
+    #[account(
+        mut,
+        seeds = [b"rep", agent.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    #[account(
+        init,
+        payer = agent,
+        space = ANCHOR_DISCRIMINATOR + SettlementRecord::INIT_SPACE,
+        seeds = [
+            b"settlement",
+            bounty.key().as_ref(),
+            agent.key().as_ref(),
+            bounty.claims_made.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub settlement_record: Account<'info, SettlementRecord>,
+
+    #[account(
+        mut,
+        seeds = [b"submission", bounty.key().as_ref(), agent.key().as_ref()],
+        bump = submission.bump
+    )]
+    pub submission: Box<Account<'info, Submission>>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = agent,
+        associated_token::token_program = token_program
+    )]
+    pub agent_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = bounty_token_account.owner == bounty.key(),
+        constraint = bounty_token_account.mint == bounty.reward_mint
+    )]
+    pub bounty_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(seeds = [b"config", bounty.market.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Protocol treasury wallet, only needed when a fee is charged
+    #[account(mut, address = config.treasury @ BountyForgeError::TreasuryMismatch)]
+    pub treasury: Option<AccountInfo<'info>>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == config.treasury @ BountyForgeError::TreasuryMismatch,
+        constraint = treasury_token_account.mint == bounty.reward_mint
+    )]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Attestor recorded on the submission, paid on Sol bounties, omitted to
+    /// send their share to the treasury
+    #[account(mut, address = submission.attestor @ BountyForgeError::NotSubmissionAttestor)]
+    pub attestor: Option<AccountInfo<'info>>,
+
+    /// The attestor's token account, paid on token bounties, omitted to send
+    /// their share to the treasury
+    #[account(
+        mut,
+        constraint = attestor_token_account.owner == submission.attestor @ BountyForgeError::NotSubmissionAttestor,
+        constraint = attestor_token_account.mint == bounty.reward_mint
+    )]
+    pub attestor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The market's insurance fund for the reward mint, covers a slightly short
+    /// escrow and takes its share of the fee, omitted to do neither
+    #[account(
+        mut,
+        seeds = [b"insurance", bounty.market.as_bytes(), bounty.reward_mint.as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Option<Box<Account<'info, InsuranceFund>>>,
+
+    /// The fund's token account, checked against the fund in the handler
+    #[account(mut)]
+    pub insurance_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// CHECK: Badge mint, created in the handler, only needed when the bounty mints badges
+    #[account(
+        mut,
+        seeds = [b"badge", bounty.key().as_ref(), bounty.claims_made.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub badge_mint: Option<AccountInfo<'info>>,
+
+    /// CHECK: Mint and metadata authority of every badge
+    #[account(seeds = [b"badge_authority"], bump)]
+    pub badge_authority: Option<AccountInfo<'info>>,
+
+    /// CHECK: The agent's Token-2022 ATA for the badge, created in the handler
+    #[account(mut)]
+    pub agent_badge_account: Option<AccountInfo<'info>>,
+
+    pub badge_token_program: Option<Program<'info, Token2022>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimReward<'info> {
+    pub fn claim_reward(
+        &mut self,
+        bumps: &ClaimRewardBumps,
+        member_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        // the vault must really hold what the bounty records, any surplus stays for close_bounty
+        let insurance_topup = cover_shortfall(
+            &self.bounty,
+            &self.config,
+            self.insurance_fund.as_deref_mut(),
+            self.insurance_token_account.as_deref(),
+            self.bounty_token_account.as_deref_mut(),
+            self.reward_mint.as_deref(),
+            &self.token_program,
+        )?;
+
+        // 1. splitting what escrow holds between the agent, their attestor and the
+        // protocol treasury, leaving what a late solution no longer earns as
+        // surplus for close_bounty
+        let payable = self.bounty.graded_amount(self.bounty.escrow_amount)?;
+        let withheld = self.bounty.escrow_amount.saturating_sub(payable);
+        let (solver_amount, _, fee_amount) = pay_attestor_share(
+            &self.bounty,
+            &self.submission,
+            &self.config,
+            self.bounty_token_account.as_deref(),
+            self.reward_mint.as_deref(),
+            self.attestor.as_ref(),
+            self.attestor_token_account.as_deref(),
+            &self.token_program,
+            payable,
+        )?;
+
+        pay_solver(
+            &self.bounty,
+            &self.submission.team,
+            self.bounty_token_account.as_deref(),
+            self.reward_mint.as_deref(),
+            &self.agent.to_account_info(),
+            self.agent_token_account.as_deref(),
+            member_accounts,
+            &self.token_program,
+            solver_amount,
+        )?;
+
+        // the insurance fund's cut comes out of the protocol fee, never the solver's share
+        let fee_amount = pay_insurance(
+            &self.bounty,
+            &self.config,
+            self.insurance_fund.as_deref_mut(),
+            self.insurance_token_account.as_deref_mut(),
+            self.bounty_token_account.as_deref(),
+            self.reward_mint.as_deref(),
+            &self.token_program,
+            fee_amount,
+        )?;
+
+        // a zero fee skips the second transfer entirely
+        if fee_amount > 0 {
+            let treasury = self
+                .treasury
+                .as_ref()
+                .ok_or(BountyForgeError::MissingTreasury)?;
+            pay_from_escrow(
+                &self.bounty,
+                self.bounty_token_account.as_deref(),
+                self.reward_mint.as_deref(),
+                treasury,
+                self.treasury_token_account.as_deref(),
+                &self.token_program,
+                fee_amount,
+            )?;
+        }
+
+        // the agent's stake goes back along with the reward
+        transfer_stake(
+            &self.submission.to_account_info(),
+            &self.agent.to_account_info(),
+            self.submission.stake,
+        )?;
+        self.submission.stake = 0;
+        self.submission.status = SubmissionStatus::Accepted;
+
+        // 2. updating reputation
+        self.reputation.record_success(
+            solver_amount,
+            self.bounty.reward_mint,
+            self.config.primary_mint,
+            self.bounty.bounty_type,
+            self.config.score_weight(self.bounty.reward),
+        )?;
+        self.leaderboard
+            .load_mut()?
+            .record(self.reputation.agent, self.reputation.score);
+
+        // 3. updating bounty status
+        self.bounty.mark_settled()?;
+
+        // 4. freeing the creator's open bounty slot
+        self.creator_profile
+            .record_settlement(payable, self.bounty.submitted_at_slot)?;
+        self.stats.load_mut()?.record_settled(payable)?;
+        self.creator_profile.emit_stats();
+
+        // 5. recording the settlement for the agent's history
+        let now = Clock::get()?.unix_timestamp;
+        self.settlement_record.set_inner(SettlementRecord {
+            agent: self.agent.key(),
+            bounty: self.bounty.key(),
+            bounty_id: self.bounty.id,
+            bounty_type: self.bounty.bounty_type,
+            amount_earned: solver_amount,
+            score_delta: self.config.score_weight(self.bounty.reward),
+            timestamp: now,
+            bump: bumps.settlement_record,
+        });
+
+        // 6. minting the solver's badge, in this transaction so a failed mint undoes the payout
+        if self.bounty.mint_badge {
+            mint_badge(
+                &self.bounty,
+                &self.agent.to_account_info(),
+                &self.agent.to_account_info(),
+                self.badge_mint.as_ref(),
+                bumps.badge_mint,
+                self.badge_authority.as_ref(),
+                bumps.badge_authority,
+                self.agent_badge_account.as_ref(),
+                self.badge_token_program.as_ref(),
+                &self.associated_token_program,
+                &self.system_program,
+                self.bounty.claims_made,
+                payable,
+                now,
+            )?;
+        }
+
+        emit!(BountySettled {
+            bounty: self.bounty.key(),
+            solver: self.agent.key(),
+            solver_amount,
+            fee_amount,
+            withheld,
+            insurance_topup,
+            streak: Some(self.reputation.current_streak),
+            reward_mint: self.bounty.reward_mint,
+            mint_earned: Some(self.reputation.earned_in(self.bounty.reward_mint)),
+            payment_amount: None,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
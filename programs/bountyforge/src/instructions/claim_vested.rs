@@ -0,0 +1,81 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::events::VestedClaimed;
+use crate::state::{Bounty, BountyStatus};
+use crate::utils::pay_from_escrow;
+
+#[derive(Accounts)]
+pub struct ClaimVested<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.status == BountyStatus::Settled @ BountyForgeError::BountyNotSettled,
+        constraint = bounty.vesting_duration_seconds > 0 @ BountyForgeError::NotVesting,
+        constraint = bounty.solver == Some(agent.key()) @ BountyForgeError::UnauthorizedClaim
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        mut,
+        constraint = agent_token_account.owner == agent.key(),
+        constraint = agent_token_account.mint == bounty.reward_mint
+    )]
+    pub agent_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = bounty_token_account.owner == bounty.key(),
+        constraint = bounty_token_account.mint == bounty.reward_mint
+    )]
+    pub bounty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ClaimVested<'info> {
+    pub fn claim_vested(&mut self) -> Result<()> {
+        // 1. working out what has unlocked since the last claim
+        let now = Clock::get()?.unix_timestamp;
+        let amount = self
+            .bounty
+            .vested_at(now)?
+            .saturating_sub(self.bounty.vested_claimed);
+        require!(amount > 0, BountyForgeError::NothingVested);
+
+        // 2. paying it out of escrow, signing as the bounty PDA
+        pay_from_escrow(
+            &self.bounty,
+            self.bounty_token_account.as_ref(),
+            self.reward_mint.as_ref(),
+            &self.agent.to_account_info(),
+            self.agent_token_account.as_ref(),
+            &self.token_program,
+            amount,
+        )?;
+        self.bounty.vested_claimed = self
+            .bounty
+            .vested_claimed
+            .checked_add(amount)
+            .ok_or(BountyForgeError::RewardOverflow)?;
+
+        emit!(VestedClaimed {
+            bounty: self.bounty.key(),
+            solver: self.agent.key(),
+            amount,
+            total_claimed: self.bounty.vested_claimed,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BountyForgeError;
+use crate::state::{Application, ApplicationStatus, BountyStatus};
+use crate::utils::load_bounty;
+
+#[derive(Accounts)]
+pub struct CloseApplication<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    /// CHECK: The application's bounty, possibly closed since; read in the handler
+    pub bounty: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = agent,
+        seeds = [b"application", bounty.key().as_ref(), agent.key().as_ref()],
+        bump = application.bump
+    )]
+    pub application: Account<'info, Application>,
+}
+
+impl<'info> CloseApplication<'info> {
+    pub fn close_application(&mut self) -> Result<()> {
+        let Some(bounty) = load_bounty(&self.bounty)? else {
+            return Ok(());
+        };
+
+        // an accepted applicant keeps theirs while they hold the bounty
+        let holds_bounty = self.application.status == ApplicationStatus::Accepted
+            && bounty.assignee == Some(self.agent.key())
+            && matches!(
+                bounty.status,
+                BountyStatus::Open
+                    | BountyStatus::Submitted
+                    | BountyStatus::Approved
+                    | BountyStatus::Challenged
+                    | BountyStatus::Disputed
+            );
+        require!(!holds_bounty, BountyForgeError::ApplicationStillActive);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::state::{Bounty, BountyStatus, CreatorBond, CreatorIndex, RewardCurrency};
+use crate::utils::{escrow_balance, pay_from_escrow, refund_rent};
+
+#[derive(Accounts)]
+pub struct CloseBounty<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = creator.key() == bounty.creator @ BountyForgeError::UnauthorizedCancellation,
+        constraint = matches!(
+            bounty.status,
+            BountyStatus::Settled
+                | BountyStatus::Cancelled
+                | BountyStatus::Expired
+                | BountyStatus::Swept
+        ) @ BountyForgeError::BountyNotFinished
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    /// The creator's bond, required if one is still held
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"bond", bounty.key().as_ref()],
+        bump = creator_bond.bump
+    )]
+    pub creator_bond: Option<Account<'info, CreatorBond>>,
+
+    /// Index the bounty was listed in when posted, kept by its original creator
+    #[account(
+        mut,
+        seeds = [b"index", bounty.original_creator.as_ref()],
+        bump = creator_index.bump
+    )]
+    pub creator_index: Account<'info, CreatorIndex>,
+
+    /// Escrow ATA, closed alongside the bounty, omitted for Sol bounties
+    #[account(
+        mut,
+        constraint = bounty_token_account.owner == bounty.key(),
+        constraint = bounty_token_account.mint == bounty.reward_mint
+    )]
+    pub bounty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Receives any surplus left in a settled bounty's escrow ATA, omitted otherwise
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == creator.key(),
+        constraint = creator_token_account.mint == bounty.reward_mint
+    )]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward mint recorded on the bounty, only needed to return a surplus
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// CHECK: Sponsor that paid the bounty's and escrow's rent, only required
+    /// if that wasn't the creator
+    #[account(mut, address = bounty.rent_payer @ BountyForgeError::RentPayerMismatch)]
+    pub rent_payer: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CloseBounty<'info> {
+    /// Closes a finished bounty and its escrow ATA, returning any creator bond
+    /// and any surplus left after settlement to the bounty's current creator.
+    /// Both rents go back to whoever paid them, the creator unless sponsored
+    pub fn close_bounty(&mut self) -> Result<()> {
+        // 1. never closing over funds someone is still owed
+        require!(
+            self.bounty.contributed == 0,
+            BountyForgeError::ContributionsOutstanding
+        );
+        require!(
+            self.bounty.creator_bond == 0 || self.creator_bond.is_some(),
+            BountyForgeError::MissingCreatorBond
+        );
+        require!(
+            self.bounty.vested_claimed == self.bounty.vesting_amount,
+            BountyForgeError::VestingIncomplete
+        );
+        let surplus = escrow_balance(&self.bounty, self.bounty_token_account.as_ref())?;
+        if surplus > 0 {
+            // once settled nobody else is owed, so whatever is left belongs to the creator
+            require!(
+                self.bounty.status == BountyStatus::Settled,
+                BountyForgeError::EscrowNotEmpty
+            );
+            // Sol surplus leaves with the bounty's lamports when it closes
+            if self.bounty.reward_currency == RewardCurrency::Spl {
+                pay_from_escrow(
+                    &self.bounty,
+                    self.bounty_token_account.as_ref(),
+                    self.reward_mint.as_ref(),
+                    &self.creator.to_account_info(),
+                    self.creator_token_account.as_ref(),
+                    &self.token_program,
+                    surplus,
+                )?;
+            }
+        }
+
+        // 2. closing the escrow ATA, signing as the bounty PDA
+        let creator = self.creator.key();
+        let sponsored = self.bounty.rent_payer != creator;
+        if self.bounty.reward_currency == RewardCurrency::Spl {
+            let bounty_token_account = self
+                .bounty_token_account
+                .as_ref()
+                .ok_or(BountyForgeError::MissingTokenAccount)?;
+
+            let bounty_id_bytes = self.bounty.id.to_le_bytes();
+            let bounty_seeds = &[
+                b"bounty",
+                self.bounty.original_creator.as_ref(),
+                bounty_id_bytes.as_ref(),
+                &[self.bounty.bump],
+            ];
+            let bounty_signer = &[&bounty_seeds[..]];
+
+            let cpi_accounts = CloseAccount {
+                account: bounty_token_account.to_account_info(),
+                destination: match &self.rent_payer {
+                    Some(rent_payer) if sponsored => rent_payer.to_account_info(),
+                    _ => self.creator.to_account_info(),
+                },
+                authority: self.bounty.to_account_info(),
+            };
+            close_account(CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                cpi_accounts,
+                bounty_signer,
+            ))?;
+        }
+
+        // 3. handing the bounty's rent to its sponsor, close sending the rest to the creator
+        refund_rent(
+            &self.bounty.to_account_info(),
+            self.bounty.rent_payer,
+            &creator,
+            self.rent_payer.as_ref(),
+        )?;
+
+        // 4. dropping it from the creator's index
+        let bounty_id = self.bounty.id;
+        self.creator_index.bounty_ids.retain(|id| *id != bounty_id);
+
+        Ok(())
+    }
+}
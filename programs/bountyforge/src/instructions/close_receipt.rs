@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BountyForgeError;
+use crate::state::{Config, SettlementReceipt};
+
+#[derive(Accounts)]
+pub struct CloseReceipt<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [
+            b"receipt",
+            settlement_receipt.bounty.as_ref(),
+            settlement_receipt.claim.to_le_bytes().as_ref()
+        ],
+        bump = settlement_receipt.bump,
+        constraint = settlement_receipt.creator == creator.key() @ BountyForgeError::UnauthorizedReceiptClose
+    )]
+    pub settlement_receipt: Account<'info, SettlementReceipt>,
+
+    /// Config of the settled bounty's market
+    #[account(seeds = [b"config", settlement_receipt.market.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> CloseReceipt<'info> {
+    /// Returns the receipt's rent to the creator once it has been kept for the
+    /// retention period of the bounty's market.
+    pub fn close_receipt(&mut self) -> Result<()> {
+        let retention = i64::try_from(self.config.receipt_retention_seconds).unwrap_or(i64::MAX);
+        require!(
+            Clock::get()?.unix_timestamp
+                >= self.settlement_receipt.settled_at.saturating_add(retention),
+            BountyForgeError::ReceiptRetentionActive
+        );
+
+        Ok(())
+    }
+}
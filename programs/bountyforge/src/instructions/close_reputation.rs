@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BountyForgeError;
+use crate::state::Reputation;
+use crate::utils::refund_rent;
+
+#[derive(Accounts)]
+pub struct CloseReputation<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    #[account(
+        mut,
+        close = agent,
+        seeds = [b"rep", agent.key().as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.agent == agent.key() @ BountyForgeError::ReputationOwnerMismatch,
+        constraint = reputation.open_submissions == 0 @ BountyForgeError::ReputationInUse,
+        constraint = reputation.failed_bounties == 0 @ BountyForgeError::ReputationHasFailures
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    /// CHECK: Sponsor that paid the reputation's rent, only required if one did
+    #[account(mut, address = reputation.rent_payer @ BountyForgeError::RentPayerMismatch)]
+    pub rent_payer: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> CloseReputation<'info> {
+    /// Returns the reputation's rent to whoever paid it when an agent leaves
+    /// the platform. Only a clean record can be closed, so starting over never
+    /// hides a failure.
+    pub fn close_reputation(&mut self) -> Result<()> {
+        refund_rent(
+            &self.reputation.to_account_info(),
+            self.reputation.rent_payer,
+            &self.agent.key(),
+            self.rent_payer.as_ref(),
+        )
+    }
+}
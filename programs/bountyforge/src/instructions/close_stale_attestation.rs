@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BountyForgeError;
+use crate::events::AttestationClosed;
+use crate::state::{Attestation, Bounty, BountyStatus, Config};
+use crate::utils::transfer_stake;
+
+#[derive(Accounts)]
+pub struct CloseStaleAttestation<'info> {
+    /// Anyone, paid the Config `attestation_close_tip_lamports`
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        close = agent,
+        constraint = !attestation.consumed @ BountyForgeError::AttestationAlreadyConsumed
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    /// CHECK: The agent who paid for the attestation, gets its rent back
+    #[account(mut, address = attestation.agent @ BountyForgeError::AttestationOwnerMismatch)]
+    pub agent: AccountInfo<'info>,
+
+    /// CHECK: The attestation's bounty, which may already be closed
+    /// Read in the handler to make sure it isn't waiting on this agent
+    #[account(address = attestation.bounty @ BountyForgeError::AttestationBountyMismatch)]
+    pub bounty: UncheckedAccount<'info>,
+
+    /// Config of the bounty's market, whose TTL and tip apply
+    #[account(seeds = [b"config", attestation.market.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> CloseStaleAttestation<'info> {
+    /// Closes an attestation that went unused for its market's
+    /// `attestation_ttl_seconds`, returning its rent to the agent less the
+    /// cranker's tip. Only attestations that never could have backed a
+    /// submission qualify: not consumed, not verified and not revoked, which
+    /// stays visible as an audit trail.
+    pub fn close_stale_attestation(&mut self) -> Result<()> {
+        let ttl = self.config.attestation_ttl_seconds;
+        require!(ttl > 0, BountyForgeError::AttestationCleanupDisabled);
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now.saturating_sub(self.attestation.timestamp) >= ttl as i64,
+            BountyForgeError::AttestationNotStale
+        );
+        require!(
+            !self.attestation.verified && !self.attestation.revoked,
+            BountyForgeError::AttestationNotCloseable
+        );
+
+        // never while the bounty is waiting on a solution from this agent
+        if !self.bounty.data_is_empty() && *self.bounty.owner == crate::ID {
+            let bounty = Bounty::try_deserialize(&mut &self.bounty.try_borrow_data()?[..])?;
+            require!(
+                bounty.status != BountyStatus::Submitted
+                    || bounty.solver != Some(self.attestation.agent),
+                BountyForgeError::AttestationNotCloseable
+            );
+        }
+
+        // the tip comes out of the rent, Anchor returns the rest to the agent on close
+        let tip = self
+            .config
+            .attestation_close_tip_lamports
+            .min(self.attestation.to_account_info().lamports());
+        if tip > 0 {
+            transfer_stake(
+                &self.attestation.to_account_info(),
+                &self.cranker.to_account_info(),
+                tip,
+            )?;
+        }
+
+        emit!(AttestationClosed {
+            attestation: self.attestation.key(),
+            bounty: self.attestation.bounty,
+            agent: self.attestation.agent,
+            cranker: self.cranker.key(),
+            tip,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
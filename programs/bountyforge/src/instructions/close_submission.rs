@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BountyForgeError;
+use crate::state::{BountyStatus, Reputation, Submission, SubmissionStatus};
+use crate::utils::{load_bounty, refund_rent};
+
+#[derive(Accounts)]
+pub struct CloseSubmission<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    /// CHECK: The submission's bounty, possibly closed since; read in the handler
+    pub bounty: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = agent,
+        seeds = [b"submission", bounty.key().as_ref(), agent.key().as_ref()],
+        bump = submission.bump
+    )]
+    pub submission: Account<'info, Submission>,
+
+    #[account(
+        mut,
+        seeds = [b"rep", agent.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    /// CHECK: Sponsor that paid the submission's rent, only required if one did
+    #[account(mut, address = submission.rent_payer @ BountyForgeError::RentPayerMismatch)]
+    pub rent_payer: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> CloseSubmission<'info> {
+    pub fn close_submission(&mut self) -> Result<()> {
+        // any submission once the bounty is over, closed or not, otherwise only
+        // losing ones: rejected, or pending once the bounty has moved on without them.
+        // Closing returns any stake, and the rent unless a sponsor paid it.
+        if let Some(bounty) = load_bounty(&self.bounty)? {
+            let finished = matches!(
+                bounty.status,
+                BountyStatus::Settled
+                    | BountyStatus::Cancelled
+                    | BountyStatus::Expired
+                    | BountyStatus::Swept
+            );
+            let is_solver = bounty.solver == Some(self.agent.key());
+            let lost = self.submission.status == SubmissionStatus::Rejected
+                || (self.submission.status == SubmissionStatus::Pending
+                    && bounty.status != BountyStatus::Open
+                    && !is_solver);
+            require!(finished || lost, BountyForgeError::SubmissionStillActive);
+        }
+
+        self.reputation.open_submissions = self.reputation.open_submissions.saturating_sub(1);
+        refund_rent(
+            &self.submission.to_account_info(),
+            self.submission.rent_payer,
+            &self.agent.key(),
+            self.rent_payer.as_ref(),
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::constants::{ANCHOR_DISCRIMINATOR, CURRENT_BOUNTY_VERSION};
+use crate::errors::BountyForgeError;
+use crate::events::ContributionReceived;
+use crate::state::{Bounty, BountyStatus, Config, Contribution, RewardCurrency};
+
+#[derive(Accounts)]
+pub struct ContributeToBounty<'info> {
+    #[account(mut)]
+    pub contributor: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen,
+        constraint = bounty.solution_hash.is_none() && bounty.submission_count == 0 @ BountyForgeError::RewardLocked,
+        constraint = bounty.prizes.is_empty() @ BountyForgeError::BountyHasPrizeSchedule
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        init_if_needed,
+        payer = contributor,
+        space = ANCHOR_DISCRIMINATOR + Contribution::INIT_SPACE,
+        seeds = [b"contrib", bounty.key().as_ref(), contributor.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(
+        seeds = [b"config", bounty.market.as_bytes()],
+        bump = config.bump,
+        constraint = !config.paused @ BountyForgeError::ProgramPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = contributor_token_account.owner == contributor.key(),
+        constraint = contributor_token_account.mint == bounty.reward_mint
+    )]
+    pub contributor_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = bounty_token_account.owner == bounty.key(),
+        constraint = bounty_token_account.mint == bounty.reward_mint
+    )]
+    pub bounty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ContributeToBounty<'info> {
+    pub fn contribute_to_bounty(
+        &mut self,
+        amount: u64,
+        bumps: &ContributeToBountyBumps,
+    ) -> Result<()> {
+        require!(amount > 0, BountyForgeError::ZeroTopUp);
+        let new_reward = self
+            .bounty
+            .reward
+            .checked_add(amount)
+            .ok_or(BountyForgeError::RewardOverflow)?;
+        require!(
+            new_reward <= self.config.max_reward,
+            BountyForgeError::RewardTooLarge
+        );
+
+        // 1. escrowing the contribution alongside the creator's reward
+        let escrowed = match self.bounty.reward_currency {
+            RewardCurrency::Spl => self.escrow_tokens(amount)?,
+            RewardCurrency::Sol => self.escrow_lamports(amount)?,
+        };
+
+        // 2. recording what this contributor has put in, the basis for any refund
+        if self.contribution.contributor == Pubkey::default() {
+            self.contribution.set_inner(Contribution {
+                bounty: self.bounty.key(),
+                contributor: self.contributor.key(),
+                amount: 0,
+                bump: bumps.contribution,
+            });
+        }
+        self.contribution.amount = self
+            .contribution
+            .amount
+            .checked_add(escrowed)
+            .ok_or(BountyForgeError::RewardOverflow)?;
+
+        // 3. updating the advertised reward and what escrow holds
+        self.bounty.reward = new_reward;
+        self.bounty.escrow_amount = self
+            .bounty
+            .escrow_amount
+            .checked_add(escrowed)
+            .ok_or(BountyForgeError::RewardOverflow)?;
+        self.bounty.contributed = self
+            .bounty
+            .contributed
+            .checked_add(escrowed)
+            .ok_or(BountyForgeError::RewardOverflow)?;
+
+        emit!(ContributionReceived {
+            bounty: self.bounty.key(),
+            contributor: self.contributor.key(),
+            amount: escrowed,
+            total: self.contribution.amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    fn escrow_tokens(&mut self, amount: u64) -> Result<u64> {
+        let reward_mint = self
+            .reward_mint
+            .as_ref()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+        let contributor_token_account = self
+            .contributor_token_account
+            .as_ref()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+        let bounty_token_account = self
+            .bounty_token_account
+            .as_mut()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+
+        let balance_before = bounty_token_account.amount;
+        let cpi_accounts = TransferChecked {
+            from: contributor_token_account.to_account_info(),
+            mint: reward_mint.to_account_info(),
+            to: bounty_token_account.to_account_info(),
+            authority: self.contributor.to_account_info(),
+        };
+        transfer_checked(
+            CpiContext::new(self.token_program.to_account_info(), cpi_accounts),
+            amount,
+            reward_mint.decimals,
+        )?;
+
+        // a transfer-fee mint withholds part of the contribution
+        bounty_token_account.reload()?;
+        Ok(bounty_token_account.amount.saturating_sub(balance_before))
+    }
+
+    fn escrow_lamports(&self, amount: u64) -> Result<u64> {
+        let cpi_accounts = system_program::Transfer {
+            from: self.contributor.to_account_info(),
+            to: self.bounty.to_account_info(),
+        };
+        system_program::transfer(
+            CpiContext::new(self.system_program.to_account_info(), cpi_accounts),
+            amount,
+        )?;
+
+        Ok(amount)
+    }
+}
@@ -0,0 +1,127 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::events::BountyCranked;
+use crate::state::{Bounty, BountyStatus, Config, CreatorProfile};
+use crate::utils::{escrow_balance, pay_from_escrow, split_fee};
+
+#[derive(Accounts)]
+pub struct CrankExpireBounty<'info> {
+    /// Anyone, paid the Config `crank_fee_bps` of the creator's refund
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen,
+        constraint = bounty.solution_hash.is_none() @ BountyForgeError::BountyAlreadySubmitted,
+        constraint = !bounty.has_submitted_milestone() @ BountyForgeError::BountyAlreadySubmitted
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    /// CHECK: The bounty's creator, refunded in lamports for Sol bounties
+    #[account(mut, address = bounty.creator @ BountyForgeError::UnauthorizedCancellation)]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"creator", bounty.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    #[account(seeds = [b"config", bounty.market.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// Omitted for Sol bounties
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == bounty.creator,
+        constraint = creator_token_account.mint == bounty.reward_mint
+    )]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = bounty_token_account.owner == bounty.key(),
+        constraint = bounty_token_account.mint == bounty.reward_mint
+    )]
+    pub bounty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Receives the incentive on token bounties, omitted for Sol bounties
+    #[account(
+        mut,
+        constraint = cranker_token_account.owner == cranker.key(),
+        constraint = cranker_token_account.mint == bounty.reward_mint
+    )]
+    pub cranker_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> CrankExpireBounty<'info> {
+    /// `expire_bounty` for anyone once the deadline has passed, so abandoned
+    /// bounties don't stay open forever. The cranker's cut comes out of the
+    /// creator's refund, contributors are refunded in full.
+    pub fn crank_expire_bounty(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now > self.bounty.deadline,
+            BountyForgeError::BountyDeadlineNotReached
+        );
+
+        // 1. splitting the escrow between contributors, the cranker and the creator
+        let balance = escrow_balance(&self.bounty, self.bounty_token_account.as_ref())?;
+        let contributors_share = self.bounty.contributors_share(balance)?;
+        let (refund, incentive) = split_fee(
+            balance.saturating_sub(contributors_share),
+            self.config.crank_fee_bps,
+        )?;
+
+        if incentive > 0 {
+            pay_from_escrow(
+                &self.bounty,
+                self.bounty_token_account.as_ref(),
+                self.reward_mint.as_ref(),
+                &self.cranker.to_account_info(),
+                self.cranker_token_account.as_ref(),
+                &self.token_program,
+                incentive,
+            )?;
+        }
+        pay_from_escrow(
+            &self.bounty,
+            self.bounty_token_account.as_ref(),
+            self.reward_mint.as_ref(),
+            &self.creator.to_account_info(),
+            self.creator_token_account.as_ref(),
+            &self.token_program,
+            refund,
+        )?;
+
+        // 2. updating bounty status, which also stops a second crank
+        self.bounty.contributor_pool = contributors_share;
+        self.bounty.status = BountyStatus::Expired;
+
+        // 3. freeing the creator's open bounty slot
+        self.creator_profile.record_cancellation();
+        self.creator_profile.emit_stats();
+
+        emit!(BountyCranked {
+            bounty: self.bounty.key(),
+            cranker: self.cranker.key(),
+            refund,
+            incentive,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
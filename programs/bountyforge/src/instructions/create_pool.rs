@@ -0,0 +1,101 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::constants::ANCHOR_DISCRIMINATOR;
+use crate::errors::BountyForgeError;
+use crate::state::{Config, FundingPool};
+use crate::utils::{check_creator_balance, check_reward_mint};
+
+#[derive(Accounts)]
+#[instruction(market: String)]
+pub struct CreatePool<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// One pool per creator and mint, topped up by calling create_pool again
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = ANCHOR_DISCRIMINATOR + FundingPool::INIT_SPACE,
+        seeds = [b"pool", creator.key().as_ref(), reward_mint.key().as_ref()],
+        bump
+    )]
+    pub funding_pool: Box<Account<'info, FundingPool>>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = reward_mint,
+        associated_token::authority = funding_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == creator.key(),
+        constraint = creator_token_account.mint == reward_mint.key() @ BountyForgeError::RewardMintMismatch
+    )]
+    pub creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        seeds = [b"config", market.as_bytes()],
+        bump = config.bump,
+        constraint = !config.paused @ BountyForgeError::ProgramPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreatePool<'info> {
+    /// Deposits `amount` into the creator's pool for `reward_mint`, for
+    /// post_bounty_from_pool to fund bounties from later.
+    pub fn create_pool(&mut self, amount: u64, bumps: &CreatePoolBumps) -> Result<()> {
+        // the pool only ever pays bounty escrows, so it takes the mints they do
+        check_reward_mint(&self.reward_mint, &self.creator.key(), &self.config)?;
+        check_creator_balance(&self.creator_token_account, amount)?;
+
+        if self.funding_pool.creator == Pubkey::default() {
+            self.funding_pool.set_inner(FundingPool {
+                creator: self.creator.key(),
+                mint: self.reward_mint.key(),
+                deposited: 0,
+                allocated: 0,
+                bump: bumps.funding_pool,
+            });
+        }
+
+        let before = self.pool_token_account.amount;
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.creator_token_account.to_account_info(),
+                    mint: self.reward_mint.to_account_info(),
+                    to: self.pool_token_account.to_account_info(),
+                    authority: self.creator.to_account_info(),
+                },
+            ),
+            amount,
+            self.reward_mint.decimals,
+        )?;
+        // a transfer-fee mint withholds part of the deposit, so count what landed
+        self.pool_token_account.reload()?;
+        let landed = self.pool_token_account.amount.saturating_sub(before);
+        self.funding_pool.deposited = self
+            .funding_pool
+            .deposited
+            .checked_add(landed)
+            .ok_or(BountyForgeError::RewardOverflow)?;
+
+        self.funding_pool.emit_update(self.funding_pool.key())
+    }
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::events::ReputationDecayed;
+use crate::state::{Config, Leaderboard, Reputation};
+
+#[derive(Accounts)]
+pub struct DecayReputation<'info> {
+    #[account(
+        mut,
+        seeds = [b"rep", reputation.agent.as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    /// Top agents by score
+    #[account(mut, seeds = [b"leaderboard"], bump = leaderboard.load()?.bump)]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+
+    /// The default market's Config. Reputation is shared by every market, so
+    /// one decay period governs it, never one a caller picks
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> DecayReputation<'info> {
+    /// Permissionless crank halving an inactive agent's score. Does nothing
+    /// when no whole period has passed, so bots can call it blindly.
+    pub fn decay_reputation(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let halvings = self.reputation.decay(now, self.config.decay_period_seconds);
+        if halvings == 0 {
+            return Ok(());
+        }
+        self.leaderboard
+            .load_mut()?
+            .record(self.reputation.agent, self.reputation.score);
+
+        emit!(ReputationDecayed {
+            agent: self.reputation.agent,
+            halvings,
+            new_score: self.reputation.score,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
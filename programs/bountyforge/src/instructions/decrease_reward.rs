@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::events::RewardDecreased;
+use crate::state::{Bounty, BountyStatus, Config};
+use crate::utils::pay_from_escrow;
+
+#[derive(Accounts)]
+pub struct DecreaseReward<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = creator.key() == bounty.creator @ BountyForgeError::UnauthorizedRewardChange,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen,
+        constraint = bounty.solution_hash.is_none() && bounty.submission_count == 0 @ BountyForgeError::RewardLocked,
+        constraint = bounty.prizes.is_empty() @ BountyForgeError::BountyHasPrizeSchedule,
+        constraint = bounty.milestones.is_empty() @ BountyForgeError::MilestoneScheduleMismatch,
+        constraint = !bounty.is_repeatable() @ BountyForgeError::RepeatableBounty
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(seeds = [b"config", bounty.market.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == creator.key(),
+        constraint = creator_token_account.mint == bounty.reward_mint
+    )]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = bounty_token_account.owner == bounty.key(),
+        constraint = bounty_token_account.mint == bounty.reward_mint
+    )]
+    pub bounty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> DecreaseReward<'info> {
+    /// Lowers the reward by `amount` and refunds that much of the creator's own
+    /// deposit from escrow, leaving the bounty open in its place.
+    pub fn decrease_reward(&mut self, amount: u64) -> Result<()> {
+        require!(amount > 0, BountyForgeError::ZeroRewardDecrease);
+        let old_reward = self.bounty.reward;
+        let new_reward = old_reward
+            .checked_sub(amount)
+            .ok_or(BountyForgeError::RewardTooSmall)?;
+        self.config.check_reward(new_reward)?;
+
+        // contributors' funds stay in escrow and keep backing the reward
+        require!(
+            amount <= self.bounty.creator_deposit && new_reward >= self.bounty.contributed,
+            BountyForgeError::RewardBelowContributions
+        );
+
+        // 1. refunding the difference to the creator
+        pay_from_escrow(
+            &self.bounty,
+            self.bounty_token_account.as_ref(),
+            self.reward_mint.as_ref(),
+            &self.creator.to_account_info(),
+            self.creator_token_account.as_ref(),
+            &self.token_program,
+            amount,
+        )?;
+
+        // 2. updating the advertised reward and what escrow holds
+        self.bounty.reward = new_reward;
+        self.bounty.escrow_amount = self
+            .bounty
+            .escrow_amount
+            .checked_sub(amount)
+            .ok_or(BountyForgeError::EscrowUnderfunded)?;
+        self.bounty.creator_deposit -= amount;
+
+        emit!(RewardDecreased {
+            bounty: self.bounty.key(),
+            old_reward,
+            new_reward,
+            refund: amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
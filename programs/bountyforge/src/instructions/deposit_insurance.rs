@@ -0,0 +1,97 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::constants::ANCHOR_DISCRIMINATOR;
+use crate::errors::BountyForgeError;
+use crate::state::{Config, InsuranceFund};
+use crate::utils::check_creator_balance;
+
+#[derive(Accounts)]
+#[instruction(market: String)]
+pub struct DepositInsurance<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", market.as_bytes()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ BountyForgeError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+
+    /// One fund per market and mint, created on the first deposit
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = ANCHOR_DISCRIMINATOR + InsuranceFund::INIT_SPACE,
+        seeds = [b"insurance", market.as_bytes(), reward_mint.key().as_ref()],
+        bump
+    )]
+    pub insurance_fund: Box<Account<'info, InsuranceFund>>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = reward_mint,
+        associated_token::authority = insurance_fund,
+        associated_token::token_program = token_program
+    )]
+    pub insurance_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = admin_token_account.owner == admin.key(),
+        constraint = admin_token_account.mint == reward_mint.key() @ BountyForgeError::RewardMintMismatch
+    )]
+    pub admin_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DepositInsurance<'info> {
+    /// Adds `amount` to the insurance fund for `reward_mint`, which
+    /// settle_bounty draws on to make up short escrows.
+    pub fn deposit_insurance(&mut self, amount: u64, bumps: &DepositInsuranceBumps) -> Result<()> {
+        check_creator_balance(&self.admin_token_account, amount)?;
+
+        if self.insurance_fund.mint == Pubkey::default() {
+            self.insurance_fund.set_inner(InsuranceFund {
+                mint: self.reward_mint.key(),
+                balance: 0,
+                total_deposited: 0,
+                total_fees: 0,
+                total_topups: 0,
+                total_withdrawn: 0,
+                bump: bumps.insurance_fund,
+            });
+        }
+
+        let before = self.insurance_token_account.amount;
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.admin_token_account.to_account_info(),
+                    mint: self.reward_mint.to_account_info(),
+                    to: self.insurance_token_account.to_account_info(),
+                    authority: self.admin.to_account_info(),
+                },
+            ),
+            amount,
+            self.reward_mint.decimals,
+        )?;
+        // a transfer-fee mint withholds part of the deposit, so count what landed
+        self.insurance_token_account.reload()?;
+        let landed = self.insurance_token_account.amount.saturating_sub(before);
+        self.insurance_fund.record_deposit(landed)?;
+
+        self.insurance_fund.emit_update(self.insurance_fund.key())
+    }
+}
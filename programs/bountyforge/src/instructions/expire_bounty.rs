@@ -0,0 +1,125 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::state::{Bounty, BountyStatus, CreatorProfile};
+use crate::utils::{escrow_balance, is_native_mint, pay_from_escrow, unwrap_from_escrow};
+
+#[derive(Accounts)]
+pub struct ExpireBounty<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = creator.key() == bounty.creator @ BountyForgeError::UnauthorizedCancellation,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen,
+        constraint = bounty.solution_hash.is_none() @ BountyForgeError::BountyAlreadySubmitted,
+        constraint = !bounty.has_submitted_milestone() @ BountyForgeError::BountyAlreadySubmitted
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator", creator.key().as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == creator.key(),
+        constraint = creator_token_account.mint == bounty.reward_mint
+    )]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = bounty_token_account.owner == bounty.key(),
+        constraint = bounty_token_account.mint == bounty.reward_mint
+    )]
+    pub bounty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    /// Temporary wSOL account, only needed to refund a wrapped SOL bounty as
+    /// lamports, in which case `creator_token_account` is omitted
+    #[account(
+        init,
+        payer = creator,
+        seeds = [b"unwrap", bounty.key().as_ref()],
+        bump,
+        token::mint = reward_mint,
+        token::authority = bounty,
+        token::token_program = token_program
+    )]
+    pub unwrap_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ExpireBounty<'info> {
+    pub fn expire_bounty(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp > self.bounty.deadline,
+            BountyForgeError::BountyDeadlineNotReached
+        );
+
+        // 1. refunding the escrow to the creator, less what contributors get back
+        let balance = escrow_balance(&self.bounty, self.bounty_token_account.as_ref())?;
+        let contributors_share = self.bounty.contributors_share(balance)?;
+        let refund = balance.saturating_sub(contributors_share);
+        self.refund_creator(refund)?;
+
+        // 2. updating bounty status
+        self.bounty.contributor_pool = contributors_share;
+        self.bounty.status = BountyStatus::Expired;
+
+        // 3. freeing the creator's open bounty slot
+        self.creator_profile.record_cancellation();
+        self.creator_profile.emit_stats();
+
+        Ok(())
+    }
+
+    fn refund_creator(&self, refund: u64) -> Result<()> {
+        // a wrapped SOL bounty goes back as lamports unless a wSOL account is named
+        let unwrap = self.creator_token_account.is_none()
+            && self
+                .reward_mint
+                .as_ref()
+                .is_some_and(|mint| is_native_mint(&mint.key()));
+        if !unwrap {
+            return pay_from_escrow(
+                &self.bounty,
+                self.bounty_token_account.as_ref(),
+                self.reward_mint.as_ref(),
+                &self.creator.to_account_info(),
+                self.creator_token_account.as_ref(),
+                &self.token_program,
+                refund,
+            );
+        }
+
+        unwrap_from_escrow(
+            &self.bounty,
+            self.bounty_token_account
+                .as_ref()
+                .ok_or(BountyForgeError::MissingTokenAccount)?,
+            self.reward_mint
+                .as_ref()
+                .ok_or(BountyForgeError::MissingTokenAccount)?,
+            self.unwrap_account
+                .as_ref()
+                .ok_or(BountyForgeError::MissingUnwrapAccount)?,
+            self.creator.to_account_info(),
+            &self.token_program,
+            refund,
+        )
+    }
+}
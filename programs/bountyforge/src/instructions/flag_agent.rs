@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::ANCHOR_DISCRIMINATOR;
+use crate::errors::BountyForgeError;
+use crate::state::{AgentFlag, Config};
+
+#[derive(Accounts)]
+#[instruction(market: String, agent: Pubkey)]
+pub struct FlagAgent<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", market.as_bytes()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ BountyForgeError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = ANCHOR_DISCRIMINATOR + AgentFlag::INIT_SPACE,
+        seeds = [b"flag", market.as_bytes(), agent.as_ref()],
+        bump
+    )]
+    pub agent_flag: Account<'info, AgentFlag>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FlagAgent<'info> {
+    pub fn flag_agent(
+        &mut self,
+        agent: Pubkey,
+        banned: bool,
+        bumps: &FlagAgentBumps,
+    ) -> Result<()> {
+        // unflagging keeps the account with the ban cleared, like remove_attestor
+        self.agent_flag.set_inner(AgentFlag {
+            agent,
+            banned,
+            updated_at: Clock::get()?.unix_timestamp,
+            bump: bumps.agent_flag,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,143 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::events::RewardIncreased;
+use crate::state::{Bounty, BountyStatus, Config, RewardCurrency};
+
+#[derive(Accounts)]
+pub struct IncreaseReward<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = creator.key() == bounty.creator @ BountyForgeError::UnauthorizedRewardChange,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen,
+        constraint = bounty.solution_hash.is_none() && bounty.submission_count == 0 @ BountyForgeError::RewardLocked,
+        constraint = bounty.prizes.is_empty() @ BountyForgeError::BountyHasPrizeSchedule,
+        constraint = !bounty.is_repeatable() @ BountyForgeError::RepeatableBounty
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        seeds = [b"config", bounty.market.as_bytes()],
+        bump = config.bump,
+        constraint = !config.paused @ BountyForgeError::ProgramPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == creator.key(),
+        constraint = creator_token_account.mint == bounty.reward_mint
+    )]
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = bounty_token_account.owner == bounty.key(),
+        constraint = bounty_token_account.mint == bounty.reward_mint
+    )]
+    pub bounty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> IncreaseReward<'info> {
+    pub fn increase_reward(&mut self, additional: u64) -> Result<()> {
+        require!(additional > 0, BountyForgeError::ZeroTopUp);
+        let old_reward = self.bounty.reward;
+        let new_reward = old_reward
+            .checked_add(additional)
+            .ok_or(BountyForgeError::RewardOverflow)?;
+        // otherwise posting small and topping up would get round max_reward
+        require!(
+            new_reward <= self.config.max_reward,
+            BountyForgeError::RewardTooLarge
+        );
+
+        // 1. escrowing the top-up alongside the original reward
+        let escrowed = match self.bounty.reward_currency {
+            RewardCurrency::Spl => self.escrow_tokens(additional)?,
+            RewardCurrency::Sol => self.escrow_lamports(additional)?,
+        };
+
+        // 2. updating the advertised reward and what escrow holds
+        self.bounty.reward = new_reward;
+        self.bounty.escrow_amount = self
+            .bounty
+            .escrow_amount
+            .checked_add(escrowed)
+            .ok_or(BountyForgeError::RewardOverflow)?;
+        self.bounty.creator_deposit = self
+            .bounty
+            .creator_deposit
+            .checked_add(escrowed)
+            .ok_or(BountyForgeError::RewardOverflow)?;
+
+        emit!(RewardIncreased {
+            bounty: self.bounty.key(),
+            old_reward,
+            new_reward: self.bounty.reward,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    fn escrow_tokens(&mut self, amount: u64) -> Result<u64> {
+        let reward_mint = self
+            .reward_mint
+            .as_ref()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+        let creator_token_account = self
+            .creator_token_account
+            .as_ref()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+        let bounty_token_account = self
+            .bounty_token_account
+            .as_mut()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+
+        let balance_before = bounty_token_account.amount;
+        let cpi_accounts = TransferChecked {
+            from: creator_token_account.to_account_info(),
+            mint: reward_mint.to_account_info(),
+            to: bounty_token_account.to_account_info(),
+            authority: self.creator.to_account_info(),
+        };
+        transfer_checked(
+            CpiContext::new(self.token_program.to_account_info(), cpi_accounts),
+            amount,
+            reward_mint.decimals,
+        )?;
+
+        // a transfer-fee mint withholds part of the top-up
+        bounty_token_account.reload()?;
+        Ok(bounty_token_account.amount.saturating_sub(balance_before))
+    }
+
+    fn escrow_lamports(&self, amount: u64) -> Result<u64> {
+        let cpi_accounts = system_program::Transfer {
+            from: self.creator.to_account_info(),
+            to: self.bounty.to_account_info(),
+        };
+        system_program::transfer(
+            CpiContext::new(self.system_program.to_account_info(), cpi_accounts),
+            amount,
+        )?;
+
+        Ok(amount)
+    }
+}
@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{ANCHOR_DISCRIMINATOR, BOUNTY_TYPE_SLOTS, EARNING_MINT_SLOTS};
+use crate::errors::BountyForgeError;
+use crate::state::{MintEarnings, Reputation};
+
+#[derive(Accounts)]
+pub struct InitReputation<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    /// Pays the reputation's rent in the agent's place and gets it back on
+    /// close_reputation. Omitted for the agent to pay it
+    #[account(mut)]
+    pub sponsor: Option<Signer<'info>>,
+
+    #[account(
+        init_if_needed,
+        payer = sponsor.as_ref().unwrap_or(&agent),
+        space = ANCHOR_DISCRIMINATOR + Reputation::INIT_SPACE,
+        seeds = [b"rep", agent.key().as_ref()],
+        bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitReputation<'info> {
+    /// Creates the agent's reputation with zeroed stats. Agents need one
+    /// before their first submission.
+    pub fn init_reputation(&mut self, bumps: &InitReputationBumps) -> Result<()> {
+        // init_if_needed so a second call fails with our own error instead of a system one
+        require!(
+            self.reputation.agent == Pubkey::default(),
+            BountyForgeError::ReputationAlreadyInitialized
+        );
+
+        self.reputation.set_inner(Reputation {
+            agent: self.agent.key(),
+            score: 0,
+            successful_bounties: 0,
+            failed_bounties: 0,
+            total_earned: 0,
+            open_submissions: 0,
+            bump: bumps.reputation,
+            completed_by_type: [0; BOUNTY_TYPE_SLOTS],
+            tier: 0,
+            current_streak: 0,
+            longest_streak: 0,
+            last_active_ts: Clock::get()?.unix_timestamp,
+            last_decayed_ts: 0,
+            rating_sum: 0,
+            rating_count: 0,
+            withdrawals: 0,
+            earned_by_mint: [MintEarnings::default(); EARNING_MINT_SLOTS],
+            other_earned_count: 0,
+            next_solution_id: 0,
+            rent_payer: self
+                .sponsor
+                .as_ref()
+                .map_or(self.agent.key(), |sponsor| sponsor.key()),
+            rejections: 0,
+            plagiarized: 0,
+        });
+
+        Ok(())
+    }
+}
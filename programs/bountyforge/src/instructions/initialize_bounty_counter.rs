@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::ANCHOR_DISCRIMINATOR;
+use crate::errors::BountyForgeError;
+use crate::state::BountyCounter;
+
+#[derive(Accounts)]
+pub struct InitializeBountyCounter<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = ANCHOR_DISCRIMINATOR + BountyCounter::INIT_SPACE,
+        seeds = [b"counter", creator.key().as_ref()],
+        bump
+    )]
+    pub bounty_counter: Account<'info, BountyCounter>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeBountyCounter<'info> {
+    pub fn initialize_bounty_counter(
+        &mut self,
+        bumps: &InitializeBountyCounterBumps,
+    ) -> Result<()> {
+        // init_if_needed so a second call fails with our own error instead of a system one
+        require!(
+            self.bounty_counter.creator == Pubkey::default(),
+            BountyForgeError::BountyCounterAlreadyInitialized
+        );
+
+        self.bounty_counter.set_inner(BountyCounter {
+            creator: self.creator.key(),
+            next_id: 1,
+            bump: bumps.bounty_counter,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{ANCHOR_DISCRIMINATOR, MAX_ATTESTORS, MAX_FEE_BPS, MAX_TRUSTED_MINTS};
+use crate::errors::BountyForgeError;
+use crate::state::Config;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitializeConfigParams {
+    pub fee_bps: u16,
+    pub treasury: Pubkey,
+    pub verifier: Pubkey,
+    pub trusted_attestor: Option<Pubkey>,
+    pub attestors: Vec<Pubkey>,
+    pub default_challenge_period_slots: u64,
+    pub challenge_bond_lamports: u64,
+    pub challenge_reward_bps: u16,
+    pub stake_slash_treasury_bps: u16,
+    pub default_settle_window_slots: u64,
+    pub max_open_per_creator: u32,
+    pub min_reward: u64,
+    pub max_reward: u64,
+    pub receipt_retention_seconds: u64,
+    pub attestor_fee_bps: u16,
+    pub score_unit: u64,
+    pub max_score_per_bounty: u64,
+    pub decay_period_seconds: u64,
+    pub allow_self_settlement: bool,
+    pub crank_fee_bps: u16,
+    pub payment_gateway: Option<Pubkey>,
+    pub default_resubmission_cooldown_seconds: u64,
+    pub abandonment_seconds: u64,
+    pub trusted_mints: Vec<Pubkey>,
+    pub boost_fee_bps: u16,
+    pub boost_duration_seconds: u64,
+    pub attestation_ttl_seconds: u64,
+    pub attestation_close_tip_lamports: u64,
+    pub insurance_fee_bps: u16,
+    pub max_insurance_topup: u64,
+    pub primary_mint: Option<Pubkey>,
+    pub appeal_window_seconds: u64,
+    pub challenge_forfeit_bps: u16,
+    pub min_verification_fee: u64,
+}
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = ANCHOR_DISCRIMINATOR + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl InitializeConfigParams {
+    /// Checks the settings and builds the Config for `market`, shared by
+    /// initialize_config and initialize_market.
+    pub fn into_config(self, admin: Pubkey, market: String, bump: u8) -> Result<Config> {
+        Config::check_fees(self.fee_bps, self.attestor_fee_bps)?;
+        require!(
+            self.challenge_reward_bps <= MAX_FEE_BPS
+                && self.stake_slash_treasury_bps <= MAX_FEE_BPS
+                && self.crank_fee_bps <= MAX_FEE_BPS
+                && self.boost_fee_bps <= MAX_FEE_BPS
+                && self.insurance_fee_bps <= MAX_FEE_BPS
+                && self.challenge_forfeit_bps <= MAX_FEE_BPS,
+            BountyForgeError::InvalidFeeBps
+        );
+        Config::check_reward_bounds(self.min_reward, self.max_reward)?;
+        Config::check_score_cap(self.max_score_per_bounty)?;
+        require!(
+            self.abandonment_seconds > 0,
+            BountyForgeError::InvalidAbandonmentPeriod
+        );
+        require!(
+            self.attestors.len() <= MAX_ATTESTORS,
+            BountyForgeError::TooManyAttestors
+        );
+        require!(
+            self.trusted_mints.len() <= MAX_TRUSTED_MINTS,
+            BountyForgeError::TooManyTrustedMints
+        );
+
+        Ok(Config {
+            admin,
+            pending_admin: None,
+            fee_bps: self.fee_bps,
+            treasury: self.treasury,
+            paused: false,
+            verifier: self.verifier,
+            trusted_attestor: self.trusted_attestor,
+            attestors: self.attestors,
+            default_challenge_period_slots: self.default_challenge_period_slots,
+            challenge_bond_lamports: self.challenge_bond_lamports,
+            challenge_reward_bps: self.challenge_reward_bps,
+            stake_slash_treasury_bps: self.stake_slash_treasury_bps,
+            default_settle_window_slots: self.default_settle_window_slots,
+            max_open_per_creator: self.max_open_per_creator,
+            min_reward: self.min_reward,
+            max_reward: self.max_reward,
+            receipt_retention_seconds: self.receipt_retention_seconds,
+            attestor_fee_bps: self.attestor_fee_bps,
+            score_unit: self.score_unit,
+            max_score_per_bounty: self.max_score_per_bounty,
+            decay_period_seconds: self.decay_period_seconds,
+            allow_self_settlement: self.allow_self_settlement,
+            crank_fee_bps: self.crank_fee_bps,
+            payment_gateway: self.payment_gateway,
+            default_resubmission_cooldown_seconds: self.default_resubmission_cooldown_seconds,
+            abandonment_seconds: self.abandonment_seconds,
+            trusted_mints: self.trusted_mints,
+            boost_fee_bps: self.boost_fee_bps,
+            boost_duration_seconds: self.boost_duration_seconds,
+            attestation_ttl_seconds: self.attestation_ttl_seconds,
+            attestation_close_tip_lamports: self.attestation_close_tip_lamports,
+            insurance_fee_bps: self.insurance_fee_bps,
+            max_insurance_topup: self.max_insurance_topup,
+            primary_mint: self.primary_mint,
+            appeal_window_seconds: self.appeal_window_seconds,
+            challenge_forfeit_bps: self.challenge_forfeit_bps,
+            min_verification_fee: self.min_verification_fee,
+            market,
+            bump,
+        })
+    }
+}
+
+impl<'info> InitializeConfig<'info> {
+    pub fn initialize_config(
+        &mut self,
+        params: InitializeConfigParams,
+        bumps: &InitializeConfigBumps,
+    ) -> Result<()> {
+        // init_if_needed so a second call fails with our own error instead of a system one
+        require!(
+            self.config.admin == Pubkey::default(),
+            BountyForgeError::ConfigAlreadyInitialized
+        );
+        let config = params.into_config(self.admin.key(), String::new(), bumps.config)?;
+        self.config.set_inner(config);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{ANCHOR_DISCRIMINATOR, LEADERBOARD_SIZE};
+use crate::state::{Leaderboard, LeaderboardEntry};
+
+#[derive(Accounts)]
+pub struct InitializeLeaderboard<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ANCHOR_DISCRIMINATOR + Leaderboard::INIT_SPACE,
+        seeds = [b"leaderboard"],
+        bump
+    )]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeLeaderboard<'info> {
+    /// Creates the empty leaderboard once. Anyone may pay for it.
+    pub fn initialize_leaderboard(&mut self, bumps: &InitializeLeaderboardBumps) -> Result<()> {
+        *self.leaderboard.load_init()? = Leaderboard {
+            entries: [LeaderboardEntry::default(); LEADERBOARD_SIZE],
+            len: 0,
+            bump: bumps.leaderboard,
+            padding: [0; 6],
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{ANCHOR_DISCRIMINATOR, MAX_MARKET_LEN};
+use crate::errors::BountyForgeError;
+use crate::instructions::InitializeConfigParams;
+use crate::state::{BountyStats, Config};
+
+#[derive(Accounts)]
+#[instruction(market: String)]
+pub struct InitializeMarket<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// Only the default market's admin opens new ones
+    #[account(
+        seeds = [b"config"],
+        bump = default_config.bump,
+        constraint = default_config.admin == admin.key() @ BountyForgeError::UnauthorizedAdmin
+    )]
+    pub default_config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ANCHOR_DISCRIMINATOR + Config::INIT_SPACE,
+        seeds = [b"config", market.as_bytes()],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = ANCHOR_DISCRIMINATOR + BountyStats::INIT_SPACE,
+        seeds = [b"stats", market.as_bytes()],
+        bump
+    )]
+    pub stats: AccountLoader<'info, BountyStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeMarket<'info> {
+    /// Opens a market next to the default one, with its own Config and
+    /// counters. The signer becomes its admin and can hand it over with
+    /// update_config and accept_admin like any other.
+    pub fn initialize_market(
+        &mut self,
+        market: String,
+        params: InitializeConfigParams,
+        bumps: &InitializeMarketBumps,
+    ) -> Result<()> {
+        // the empty name is the default market's, whose accounts already exist
+        require!(
+            !market.is_empty() && market.len() <= MAX_MARKET_LEN,
+            BountyForgeError::InvalidMarketName
+        );
+
+        let config = params.into_config(self.admin.key(), market, bumps.config)?;
+        self.config.set_inner(config);
+        *self.stats.load_init()? = BountyStats {
+            bounties_posted: 0,
+            bounties_submitted: 0,
+            bounties_settled: 0,
+            bounties_cancelled: 0,
+            total_escrowed: 0,
+            total_paid: 0,
+            bump: bumps.stats,
+            padding: [0; 15],
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::ANCHOR_DISCRIMINATOR;
+use crate::state::BountyStats;
+
+#[derive(Accounts)]
+pub struct InitializeStats<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ANCHOR_DISCRIMINATOR + BountyStats::INIT_SPACE,
+        seeds = [b"stats"],
+        bump
+    )]
+    pub stats: AccountLoader<'info, BountyStats>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeStats<'info> {
+    /// Creates the protocol-wide counters once, from zero. Anyone may pay for it.
+    pub fn initialize_stats(&mut self, bumps: &InitializeStatsBumps) -> Result<()> {
+        *self.stats.load_init()? = BountyStats {
+            bounties_posted: 0,
+            bounties_submitted: 0,
+            bounties_settled: 0,
+            bounties_cancelled: 0,
+            total_escrowed: 0,
+            total_paid: 0,
+            bump: bumps.stats,
+            padding: [0; 15],
+        };
+
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CURRENT_BOUNTY_VERSION, MAX_RATING, MIN_RATING};
+use crate::errors::BountyForgeError;
+use crate::events::FeedbackLeft;
+use crate::state::{Bounty, BountyStatus, Reputation};
+
+#[derive(Accounts)]
+pub struct LeaveFeedback<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = creator.key() == bounty.creator @ BountyForgeError::UnauthorizedFeedback,
+        constraint = bounty.status == BountyStatus::Settled @ BountyForgeError::BountyNotSettled,
+        constraint = !bounty.feedback_given @ BountyForgeError::FeedbackAlreadyGiven
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    /// The solver's reputation
+    #[account(
+        mut,
+        seeds = [b"rep", reputation.agent.as_ref()],
+        bump = reputation.bump,
+        constraint = bounty.solver == Some(reputation.agent) @ BountyForgeError::SolverMismatch
+    )]
+    pub reputation: Account<'info, Reputation>,
+}
+
+impl<'info> LeaveFeedback<'info> {
+    /// The creator rates the solver of a settled bounty once, from 1 to 5.
+    pub fn leave_feedback(&mut self, rating: u8) -> Result<()> {
+        require!(
+            (MIN_RATING..=MAX_RATING).contains(&rating),
+            BountyForgeError::InvalidRating
+        );
+
+        self.bounty.feedback_given = true;
+        self.reputation.rating_sum = self.reputation.rating_sum.saturating_add(rating as u64);
+        self.reputation.rating_count = self.reputation.rating_count.saturating_add(1);
+
+        emit!(FeedbackLeft {
+            bounty: self.bounty.key(),
+            agent: self.reputation.agent,
+            rating,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,182 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+
+use crate::constants::{ANCHOR_DISCRIMINATOR, CURRENT_BOUNTY_VERSION};
+use crate::errors::BountyForgeError;
+use crate::events::BountyMigrated;
+use crate::state::{Bounty, BountyCounter, BountyV0};
+
+#[derive(Accounts)]
+pub struct MigrateBounty<'info> {
+    /// Anyone may migrate a bounty, paying the rent of its new accounts
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: A bounty on the 0.1.0 layout, which Anchor can no longer
+    /// deserialize. Owner, discriminator, length and address are checked in
+    /// the handler, and it is closed once moved
+    #[account(mut)]
+    pub legacy_bounty: UncheckedAccount<'info>,
+
+    /// CHECK: The legacy bounty's creator, checked against it in the handler.
+    /// Gets the old accounts' rent back
+    #[account(mut)]
+    pub creator: UncheckedAccount<'info>,
+
+    /// Assigns the migrated bounty its id, as post_bounty does
+    #[account(
+        mut,
+        seeds = [b"counter", creator.key().as_ref()],
+        bump = bounty_counter.bump
+    )]
+    pub bounty_counter: Account<'info, BountyCounter>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ANCHOR_DISCRIMINATOR + Bounty::INIT_SPACE,
+        seeds = [b"bounty", creator.key().as_ref(), bounty_counter.next_id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    /// The mint the legacy bounty escrowed, USDC on the 0.1.0 deployment
+    #[account(mint::token_program = token_program)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// The legacy bounty's escrow ATA, emptied into the new one and closed
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = legacy_bounty,
+        associated_token::token_program = token_program
+    )]
+    pub legacy_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = reward_mint,
+        associated_token::authority = bounty,
+        associated_token::token_program = token_program
+    )]
+    pub bounty_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateBounty<'info> {
+    /// Moves a bounty posted by 0.1.0 to the current layout. Its old address
+    /// can't sign for the current seeds, so it becomes a new bounty at the
+    /// creator's next id, taking the whole escrow with it. The old bounty and
+    /// escrow ATA are closed, their rent going back to the creator.
+    pub fn migrate_bounty(&mut self, bumps: &MigrateBountyBumps) -> Result<()> {
+        // 1. reading the old layout
+        let legacy = self.legacy_bounty.to_account_info();
+        require_keys_eq!(
+            *legacy.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        let old = {
+            let data = legacy.try_borrow_data()?;
+            require!(
+                data.get(..ANCHOR_DISCRIMINATOR) == Some(Bounty::DISCRIMINATOR),
+                ErrorCode::AccountDiscriminatorMismatch
+            );
+            if data.len() != ANCHOR_DISCRIMINATOR + BountyV0::SPACE {
+                return if data.get(ANCHOR_DISCRIMINATOR) == Some(&CURRENT_BOUNTY_VERSION) {
+                    err!(BountyForgeError::BountyAlreadyMigrated)
+                } else {
+                    err!(BountyForgeError::UnknownBountyLayout)
+                };
+            }
+            BountyV0::deserialize(&mut &data[ANCHOR_DISCRIMINATOR..])?
+        };
+        require_keys_eq!(
+            old.creator,
+            self.creator.key(),
+            BountyForgeError::LegacyCreatorMismatch
+        );
+        let legacy_id_bytes = old.id.to_le_bytes();
+        let legacy_seeds = &[b"bounty", legacy_id_bytes.as_ref(), &[old.bump]];
+        require_keys_eq!(
+            Pubkey::create_program_address(legacy_seeds, &crate::ID)
+                .map_err(|_| ErrorCode::ConstraintSeeds)?,
+            legacy.key(),
+            ErrorCode::ConstraintSeeds
+        );
+        let legacy_signer = &[&legacy_seeds[..]];
+
+        // 2. writing the new bounty at the next id, as post_bounty would
+        let bounty_id = self.bounty_counter.next_id;
+        self.bounty_counter.next_id = bounty_id
+            .checked_add(1)
+            .ok_or(BountyForgeError::BountyIdOverflow)?;
+        let escrowed = self.legacy_token_account.amount;
+        let now = Clock::get()?.unix_timestamp;
+        self.bounty.set_inner(old.into_bounty(
+            bounty_id,
+            bumps.bounty,
+            self.reward_mint.key(),
+            escrowed,
+            self.payer.key(),
+            now,
+        ));
+
+        // 3. moving the escrow across, signing as the legacy PDA
+        if escrowed > 0 {
+            let cpi_accounts = TransferChecked {
+                from: self.legacy_token_account.to_account_info(),
+                mint: self.reward_mint.to_account_info(),
+                to: self.bounty_token_account.to_account_info(),
+                authority: legacy.clone(),
+            };
+            transfer_checked(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    cpi_accounts,
+                    legacy_signer,
+                ),
+                escrowed,
+                self.reward_mint.decimals,
+            )?;
+        }
+
+        // 4. closing the old escrow ATA and bounty
+        let cpi_accounts = CloseAccount {
+            account: self.legacy_token_account.to_account_info(),
+            destination: self.creator.to_account_info(),
+            authority: legacy.clone(),
+        };
+        close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+            legacy_signer,
+        ))?;
+        let creator = self.creator.to_account_info();
+        **creator.try_borrow_mut_lamports()? = creator
+            .lamports()
+            .checked_add(legacy.lamports())
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        **legacy.try_borrow_mut_lamports()? = 0;
+        legacy.assign(&System::id());
+        legacy.realloc(0, false)?;
+
+        emit!(BountyMigrated {
+            bounty: self.bounty.key(),
+            legacy_bounty: legacy.key(),
+            bounty_id,
+            version: CURRENT_BOUNTY_VERSION,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
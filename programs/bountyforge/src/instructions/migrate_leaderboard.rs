@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::ANCHOR_DISCRIMINATOR;
+use crate::errors::BountyForgeError;
+use crate::state::Leaderboard;
+use crate::utils::grow_account;
+
+#[derive(Accounts)]
+pub struct MigrateLeaderboard<'info> {
+    /// Anyone may migrate the leaderboard, paying the extra rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: The leaderboard on the Borsh layout, too short to load as
+    /// zero-copy. Owner, discriminator and length are checked in the handler
+    #[account(mut, seeds = [b"leaderboard"], bump)]
+    pub leaderboard: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateLeaderboard<'info> {
+    /// Grows a leaderboard created before it was zero-copy by the padding the
+    /// new layout ends with. The entries already sit at their offsets, so the
+    /// ranking carries over untouched.
+    pub fn migrate_leaderboard(&mut self) -> Result<()> {
+        let leaderboard = self.leaderboard.to_account_info();
+        require_keys_eq!(
+            *leaderboard.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        require!(
+            leaderboard.try_borrow_data()?.get(..ANCHOR_DISCRIMINATOR)
+                == Some(Leaderboard::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let new_len = ANCHOR_DISCRIMINATOR + Leaderboard::INIT_SPACE;
+        require!(
+            leaderboard.data_len() != new_len,
+            BountyForgeError::AccountAlreadyMigrated
+        );
+        require!(
+            leaderboard.data_len() == ANCHOR_DISCRIMINATOR + Leaderboard::LEGACY_SPACE,
+            BountyForgeError::UnknownAccountLayout
+        );
+
+        grow_account(
+            &leaderboard,
+            new_len,
+            &self.payer.to_account_info(),
+            &self.system_program,
+        )
+    }
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+use crate::constants::ANCHOR_DISCRIMINATOR;
+use crate::errors::BountyForgeError;
+use crate::state::Reputation;
+
+#[derive(Accounts)]
+pub struct MigrateReputation<'info> {
+    /// Anyone may migrate a reputation, paying the extra rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Agent whose reputation is migrated, only used for the seeds
+    pub agent: UncheckedAccount<'info>,
+
+    /// CHECK: The agent's reputation on an older, shorter layout, which Anchor
+    /// can no longer deserialize. Owner and discriminator are checked in the handler
+    #[account(
+        mut,
+        seeds = [b"rep", agent.key().as_ref()],
+        bump
+    )]
+    pub reputation: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateReputation<'info> {
+    /// Grows a reputation created before some of the fields after `bump` were
+    /// added. New counters and streaks start at zero, as earlier wins weren't
+    /// recorded that way, the tier is worked out from the wins so far and the
+    /// agent counts as active now. Earnings by mint start empty too, with
+    /// `total_earned` left holding everything earned before. The agent paid
+    /// the original rent, so it is the one refunded on close.
+    pub fn migrate_reputation(&mut self) -> Result<()> {
+        let reputation = self.reputation.to_account_info();
+        require!(
+            reputation.owner == &crate::ID,
+            BountyForgeError::ReputationOwnerMismatch
+        );
+        let new_len = ANCHOR_DISCRIMINATOR + Reputation::INIT_SPACE;
+        require!(
+            reputation.data_len() < new_len,
+            BountyForgeError::ReputationAlreadyMigrated
+        );
+        require!(
+            reputation.data_len() >= ANCHOR_DISCRIMINATOR + Reputation::LEGACY_SPACE,
+            BountyForgeError::ReputationOwnerMismatch
+        );
+        require!(
+            reputation.try_borrow_data()?[..ANCHOR_DISCRIMINATOR] == *Reputation::DISCRIMINATOR,
+            BountyForgeError::ReputationOwnerMismatch
+        );
+
+        // 1. topping up rent for the larger account, then growing it with zeroed fields
+        let shortfall = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(reputation.lamports());
+        if shortfall > 0 {
+            let cpi_accounts = system_program::Transfer {
+                from: self.payer.to_account_info(),
+                to: reputation.clone(),
+            };
+            system_program::transfer(
+                CpiContext::new(self.system_program.to_account_info(), cpi_accounts),
+                shortfall,
+            )?;
+        }
+        reputation.realloc(new_len, true)?;
+
+        // 2. filling in the tier from the wins already counted
+        let mut migrated = Reputation::try_deserialize(&mut &reputation.try_borrow_data()?[..])?;
+        require!(
+            migrated.agent == self.agent.key(),
+            BountyForgeError::ReputationOwnerMismatch
+        );
+        migrated.update_tier();
+        if migrated.rent_payer == Pubkey::default() {
+            migrated.rent_payer = migrated.agent;
+        }
+        // the agent's history predates activity tracking, so decay starts from now
+        if migrated.last_active_ts == 0 {
+            migrated.last_active_ts = Clock::get()?.unix_timestamp;
+        }
+        migrated.try_serialize(&mut &mut reputation.try_borrow_mut_data()?[..])?;
+
+        Ok(())
+    }
+}
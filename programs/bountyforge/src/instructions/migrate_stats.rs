@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::ANCHOR_DISCRIMINATOR;
+use crate::errors::BountyForgeError;
+use crate::state::BountyStats;
+use crate::utils::grow_account;
+
+#[derive(Accounts)]
+#[instruction(market: String)]
+pub struct MigrateStats<'info> {
+    /// Anyone may migrate a market's counters, paying the extra rent
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: The market's counters on the Borsh layout, too short to load as
+    /// zero-copy. Owner, discriminator and length are checked in the handler
+    #[account(
+        mut,
+        seeds = [b"stats", market.as_bytes()],
+        bump
+    )]
+    pub stats: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateStats<'info> {
+    /// Grows counters created before `BountyStats` was zero-copy by the padding
+    /// the new layout ends with. Every counter already sits at its offset, so
+    /// nothing else is rewritten.
+    pub fn migrate_stats(&mut self) -> Result<()> {
+        let stats = self.stats.to_account_info();
+        require_keys_eq!(
+            *stats.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        require!(
+            stats.try_borrow_data()?.get(..ANCHOR_DISCRIMINATOR)
+                == Some(BountyStats::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let new_len = ANCHOR_DISCRIMINATOR + BountyStats::INIT_SPACE;
+        require!(
+            stats.data_len() != new_len,
+            BountyForgeError::AccountAlreadyMigrated
+        );
+        require!(
+            stats.data_len() == ANCHOR_DISCRIMINATOR + BountyStats::LEGACY_SPACE,
+            BountyForgeError::UnknownAccountLayout
+        );
+
+        grow_account(
+            &stats,
+            new_len,
+            &self.payer.to_account_info(),
+            &self.system_program,
+        )
+    }
+}
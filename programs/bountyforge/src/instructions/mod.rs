@@ -1,9 +1,153 @@
+pub mod accept_admin;
+pub mod accept_application;
+pub mod accept_bounty_ownership;
+pub mod add_allowlisted_agent;
+pub mod appeal_rejection;
+pub mod apply_to_bounty;
+pub mod approve_settlement;
+pub mod assign_bounty;
 pub mod attest_solution;
+pub mod boost_bounty;
+pub mod cancel_bounty;
+pub mod challenge_solution;
+pub mod claim_reward;
+pub mod claim_vested;
+pub mod close_application;
+pub mod close_bounty;
+pub mod close_receipt;
+pub mod close_reputation;
+pub mod close_stale_attestation;
+pub mod close_submission;
+pub mod contribute_to_bounty;
+pub mod crank_expire_bounty;
+pub mod create_pool;
+pub mod decay_reputation;
+pub mod decrease_reward;
+pub mod deposit_insurance;
+pub mod expire_bounty;
+pub mod flag_agent;
+pub mod force_settle;
+pub mod increase_reward;
+pub mod init_reputation;
+pub mod initialize_bounty_counter;
+pub mod initialize_config;
+pub mod initialize_leaderboard;
+pub mod initialize_market;
+pub mod initialize_stats;
+pub mod leave_feedback;
+pub mod migrate_bounty;
+pub mod migrate_leaderboard;
+pub mod migrate_reputation;
+pub mod migrate_stats;
+pub mod open_dispute;
+pub mod post_bounties;
 pub mod post_bounty;
+pub mod refund_contribution;
+pub mod register_attestor;
+pub mod register_operator;
+pub mod reject_solution;
+pub mod remove_allowlisted_agent;
+pub mod remove_attestor;
+pub mod resolve_appeal;
+pub mod resolve_challenge;
+pub mod resolve_dispute;
+pub mod reveal_criteria;
+pub mod reveal_solution;
+pub mod revoke_attestation;
+pub mod revoke_operator;
+pub mod select_winner;
+pub mod set_pause;
+pub mod set_settlement_authority;
+pub mod settle_bounties;
 pub mod settle_bounty;
+pub mod settle_milestone;
+pub mod settle_prizes;
+pub mod snapshot_reputation;
+pub mod submit_milestone;
 pub mod submit_solution;
+pub mod sweep_abandoned_bounty;
+pub mod transfer_bounty_ownership;
+pub mod update_bounty;
+pub mod update_config;
+pub mod verify_attestation;
+pub mod verify_reputation_proof;
+pub mod withdraw_from_pool;
+pub mod withdraw_insurance;
+pub mod withdraw_submission;
 
+pub use accept_admin::*;
+pub use accept_application::*;
+pub use accept_bounty_ownership::*;
+pub use add_allowlisted_agent::*;
+pub use appeal_rejection::*;
+pub use apply_to_bounty::*;
+pub use approve_settlement::*;
+pub use assign_bounty::*;
 pub use attest_solution::*;
+pub use boost_bounty::*;
+pub use cancel_bounty::*;
+pub use challenge_solution::*;
+pub use claim_reward::*;
+pub use claim_vested::*;
+pub use close_application::*;
+pub use close_bounty::*;
+pub use close_receipt::*;
+pub use close_reputation::*;
+pub use close_stale_attestation::*;
+pub use close_submission::*;
+pub use contribute_to_bounty::*;
+pub use crank_expire_bounty::*;
+pub use create_pool::*;
+pub use decay_reputation::*;
+pub use decrease_reward::*;
+pub use deposit_insurance::*;
+pub use expire_bounty::*;
+pub use flag_agent::*;
+pub use force_settle::*;
+pub use increase_reward::*;
+pub use init_reputation::*;
+pub use initialize_bounty_counter::*;
+pub use initialize_config::*;
+pub use initialize_leaderboard::*;
+pub use initialize_market::*;
+pub use initialize_stats::*;
+pub use leave_feedback::*;
+pub use migrate_bounty::*;
+pub use migrate_leaderboard::*;
+pub use migrate_reputation::*;
+pub use migrate_stats::*;
+pub use open_dispute::*;
+pub use post_bounties::*;
 pub use post_bounty::*;
+pub use refund_contribution::*;
+pub use register_attestor::*;
+pub use register_operator::*;
+pub use reject_solution::*;
+pub use remove_allowlisted_agent::*;
+pub use remove_attestor::*;
+pub use resolve_appeal::*;
+pub use resolve_challenge::*;
+pub use resolve_dispute::*;
+pub use reveal_criteria::*;
+pub use reveal_solution::*;
+pub use revoke_attestation::*;
+pub use revoke_operator::*;
+pub use select_winner::*;
+pub use set_pause::*;
+pub use set_settlement_authority::*;
+pub use settle_bounties::*;
 pub use settle_bounty::*;
+pub use settle_milestone::*;
+pub use settle_prizes::*;
+pub use snapshot_reputation::*;
+pub use submit_milestone::*;
 pub use submit_solution::*;
+pub use sweep_abandoned_bounty::*;
+pub use transfer_bounty_ownership::*;
+pub use update_bounty::*;
+pub use update_config::*;
+pub use verify_attestation::*;
+pub use verify_reputation_proof::*;
+pub use withdraw_from_pool::*;
+pub use withdraw_insurance::*;
+pub use withdraw_submission::*;
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::state::{Bounty, BountyStatus};
+
+#[derive(Accounts)]
+pub struct OpenDispute<'info> {
+    /// The bounty's creator or its solver
+    pub party: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.arbiter.is_some() @ BountyForgeError::NoArbiter,
+        constraint = bounty.status == BountyStatus::Submitted @ BountyForgeError::BountyNotSubmitted,
+        constraint = party.key() == bounty.creator || bounty.solver == Some(party.key()) @ BountyForgeError::UnauthorizedDisputant
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+}
+
+impl<'info> OpenDispute<'info> {
+    pub fn open_dispute(&mut self) -> Result<()> {
+        // settlement is frozen until the arbiter decides
+        self.bounty.status = BountyStatus::Disputed;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,337 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, AssociatedToken};
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::constants::{
+    ANCHOR_DISCRIMINATOR, CURRENT_BOUNTY_VERSION, MAX_ALLOWLIST, MAX_BATCH_POSTS,
+    MAX_DESCRIPTION_LEN, MAX_INDEXED_BOUNTIES, MIN_POST_INTERVAL_SECONDS,
+};
+use crate::errors::BountyForgeError;
+use crate::events::BountyPosted;
+use crate::state::{
+    AttestationSource, Bounty, BountyCounter, BountyStats, BountyStatus, BountyType, Config,
+    CreatorIndex, CreatorProfile, HashAlgo, OracleKind, RewardCurrency,
+};
+use crate::utils::{
+    check_creator_balance, check_reward_mint, create_pda_account, post_bounty_space,
+};
+
+/// One bounty of a `post_bounties` batch. Everything else takes the same
+/// defaults as a plain `post_bounty`: open to anyone, no oracle, no reveal, no
+/// target, the default market and its Config challenge and settle windows.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BatchBountyParams {
+    pub bounty_type: BountyType,
+    pub description: String,
+    pub reward: u64,
+    pub deadline: i64,
+}
+
+#[derive(Accounts)]
+pub struct PostBounties<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Assigns the ids, one after the other in batch order
+    #[account(
+        mut,
+        seeds = [b"counter", creator.key().as_ref()],
+        bump = bounty_counter.bump
+    )]
+    pub bounty_counter: Account<'info, BountyCounter>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = ANCHOR_DISCRIMINATOR + CreatorProfile::INIT_SPACE,
+        seeds = [b"creator", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    /// Counters of the default market, the only one batches work in
+    #[account(mut, seeds = [b"stats"], bump = stats.load()?.bump)]
+    pub stats: AccountLoader<'info, BountyStats>,
+
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = ANCHOR_DISCRIMINATOR + CreatorIndex::INIT_SPACE,
+        seeds = [b"index", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_index: Box<Account<'info, CreatorIndex>>,
+
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump,
+        constraint = !config.paused @ BountyForgeError::ProgramPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    /// Reward mint shared by the whole batch, owned by either token program
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == creator.key(),
+        constraint = creator_token_account.mint == reward_mint.key() @ BountyForgeError::RewardMintMismatch
+    )]
+    pub creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PostBounties<'info> {
+    /// Posts token bounties back to back, taking the bounty PDA and its escrow
+    /// ATA for each one as remaining accounts: `[bounty, bounty_token_account]`
+    /// per item, in batch order. Both are created here. A failing item aborts
+    /// the whole batch, and its index is logged before the error.
+    pub fn post_bounties(
+        &mut self,
+        items: Vec<BatchBountyParams>,
+        accounts: &'info [AccountInfo<'info>],
+        bumps: &PostBountiesBumps,
+    ) -> Result<()> {
+        require!(
+            !items.is_empty() && items.len() <= MAX_BATCH_POSTS,
+            BountyForgeError::InvalidBatchSize
+        );
+        require!(
+            accounts.len() == items.len() * 2,
+            BountyForgeError::BatchAccountMismatch
+        );
+        check_reward_mint(&self.reward_mint, &self.creator.key(), &self.config)?;
+
+        // the batch counts as a single post for the cooldown
+        let now = Clock::get()?.unix_timestamp;
+        if self.creator_profile.creator == Pubkey::default() {
+            self.creator_profile.set_inner(CreatorProfile {
+                creator: self.creator.key(),
+                open_bounty_count: 0,
+                last_post_ts: 0,
+                disputes_lost: 0,
+                total_posted: 0,
+                total_settled: 0,
+                total_cancelled: 0,
+                total_paid_out: 0,
+                total_settle_delay_slots: 0,
+                timed_settlements: 0,
+                bump: bumps.creator_profile,
+            });
+        } else {
+            require!(
+                now.saturating_sub(self.creator_profile.last_post_ts) >= MIN_POST_INTERVAL_SECONDS,
+                BountyForgeError::PostCooldownActive
+            );
+        }
+        if self.creator_index.creator == Pubkey::default() {
+            self.creator_index.creator = self.creator.key();
+            self.creator_index.bump = bumps.creator_index;
+        }
+
+        for (index, (item, item_accounts)) in items.into_iter().zip(accounts.chunks(2)).enumerate()
+        {
+            self.post_one(item, &item_accounts[0], &item_accounts[1], now)
+                .inspect_err(|_| msg!("post_bounties: item {} failed", index))?;
+        }
+
+        self.creator_profile.last_post_ts = now;
+        self.creator_profile.emit_stats();
+
+        Ok(())
+    }
+
+    fn post_one(
+        &mut self,
+        item: BatchBountyParams,
+        bounty_info: &'info AccountInfo<'info>,
+        escrow_info: &'info AccountInfo<'info>,
+        now: i64,
+    ) -> Result<()> {
+        let BatchBountyParams {
+            bounty_type,
+            description,
+            reward,
+            deadline,
+        } = item;
+
+        // 1. the same checks post_bounty makes on these fields
+        require!(!description.is_empty(), BountyForgeError::EmptyDescription);
+        require!(
+            description.len() <= MAX_DESCRIPTION_LEN,
+            BountyForgeError::DescriptionTooLong
+        );
+        self.config.check_reward(reward)?;
+        require!(deadline > now, BountyForgeError::InvalidDeadline);
+
+        require!(
+            self.creator_profile.open_bounty_count < self.config.max_open_per_creator,
+            BountyForgeError::TooManyOpenBounties
+        );
+        self.creator_profile.open_bounty_count = self
+            .creator_profile
+            .open_bounty_count
+            .checked_add(1)
+            .ok_or(BountyForgeError::TooManyOpenBounties)?;
+        self.creator_profile.total_posted = self.creator_profile.total_posted.saturating_add(1);
+
+        let bounty_id = self.bounty_counter.next_id;
+        self.bounty_counter.next_id = bounty_id
+            .checked_add(1)
+            .ok_or(BountyForgeError::BountyIdOverflow)?;
+        require!(
+            self.creator_index.bounty_ids.len() < MAX_INDEXED_BOUNTIES,
+            BountyForgeError::IndexFull
+        );
+        self.creator_index.bounty_ids.push(bounty_id);
+
+        // 2. creating the bounty PDA for the next id, as init would
+        let creator = self.creator.key();
+        let id_bytes = bounty_id.to_le_bytes();
+        let (_, bump) = Pubkey::find_program_address(
+            &[b"bounty", creator.as_ref(), id_bytes.as_ref()],
+            &crate::ID,
+        );
+        create_pda_account(
+            &self.creator.to_account_info(),
+            bounty_info,
+            &[b"bounty", creator.as_ref(), id_bytes.as_ref(), &[bump]],
+            post_bounty_space(&self.creator, Some(self.reward_mint.as_ref()), true)?,
+            &self.system_program,
+        )?;
+
+        // 3. creating the escrow ATA, which checks its own address, and funding it
+        // from a balance reloaded after the previous item's transfer
+        self.creator_token_account.reload()?;
+        check_creator_balance(&self.creator_token_account, reward)?;
+        associated_token::create(CpiContext::new(
+            self.associated_token_program.to_account_info(),
+            associated_token::Create {
+                payer: self.creator.to_account_info(),
+                associated_token: escrow_info.clone(),
+                authority: bounty_info.clone(),
+                mint: self.reward_mint.to_account_info(),
+                system_program: self.system_program.to_account_info(),
+                token_program: self.token_program.to_account_info(),
+            },
+        ))?;
+        transfer_checked(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.creator_token_account.to_account_info(),
+                    mint: self.reward_mint.to_account_info(),
+                    to: escrow_info.clone(),
+                    authority: self.creator.to_account_info(),
+                },
+            ),
+            reward,
+            self.reward_mint.decimals,
+        )?;
+        // a transfer-fee mint withholds part of the reward, so record what landed
+        let escrowed = InterfaceAccount::<TokenAccount>::try_from(escrow_info)?.amount;
+        self.stats.load_mut()?.record_posted(escrowed)?;
+
+        // 4. writing the bounty, discriminator included
+        let bounty = Bounty {
+            version: CURRENT_BOUNTY_VERSION,
+            id: bounty_id,
+            status: BountyStatus::Open,
+            creator,
+            original_creator: creator,
+            reward_mint: self.reward_mint.key(),
+            reward,
+            bump,
+            boosted_until: 0,
+            target: Pubkey::default(),
+            bounty_type,
+            escrow_amount: escrowed,
+            solution_hash: None,
+            solver: None,
+            reward_currency: RewardCurrency::Spl,
+            requires_oracle: false,
+            oracle_feeds: Vec::new(),
+            oracle_kind: OracleKind::Switchboard,
+            oracle_min_value: None,
+            oracle_max_value: None,
+            oracle_exponent: 0,
+            deadline,
+            max_attestation_age: None,
+            required_attestations: 0,
+            min_attestor_track_record: 0,
+            attestation_source: AttestationSource::Internal,
+            requires_reveal: false,
+            revealed: false,
+            requires_payment_receipt: false,
+            hash_algo: HashAlgo::Sha256,
+            encryption_pubkey: None,
+            criteria_hash: [0; 32],
+            criteria_revealed: false,
+            allow_cpi: false,
+            competitive: false,
+            submission_count: 0,
+            max_submissions: 0,
+            live_submissions: 0,
+            prizes: Vec::new(),
+            max_claims: 1,
+            claims_made: 0,
+            allow_repeat_solver: false,
+            mint_badge: false,
+            assignee: None,
+            requires_application: false,
+            challenge_period_slots: self.config.default_challenge_period_slots,
+            submitted_at_slot: 0,
+            arbiter: None,
+            required_stake: 0,
+            creator_bond: 0,
+            settle_window_slots: self.config.default_settle_window_slots,
+            resubmission_cooldown_seconds: self.config.default_resubmission_cooldown_seconds,
+            full_reward_until: 0,
+            decay_bps_per_hour: 0,
+            decay_floor_bps: 0,
+            creator_deposit: escrowed,
+            contributed: 0,
+            contributor_pool: 0,
+            total_boost_paid: 0,
+            pending_creator: None,
+            rent_payer: creator,
+            appellant: None,
+            appeal_ends_at: 0,
+            min_reputation: 0,
+            allowlist: [Pubkey::default(); MAX_ALLOWLIST],
+            allowlist_len: 0,
+            milestones: Vec::new(),
+            vesting_duration_seconds: 0,
+            vesting_start: 0,
+            vesting_amount: 0,
+            vested_claimed: 0,
+            feedback_given: false,
+            created_at: now,
+            submitted_at: 0,
+            settled_at: 0,
+            description,
+            metadata_uri: String::new(),
+            settlement_authority: None,
+            market: self.config.market.clone(),
+        };
+        bounty.try_serialize(&mut &mut bounty_info.try_borrow_mut_data()?[..])?;
+
+        emit!(BountyPosted {
+            bounty: bounty_info.key(),
+            bounty_id,
+            creator,
+            reward,
+            bounty_type,
+            target: Pubkey::default(),
+            metadata_uri: String::new(),
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
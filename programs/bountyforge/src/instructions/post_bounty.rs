@@ -1,100 +1,877 @@
-use crate::{constants::ANCHOR_DISCRIMINATOR};
-use crate::state::{Bounty, BountyStatus, BountyType};
+use crate::constants::{
+    ANCHOR_DISCRIMINATOR, CURRENT_BOUNTY_VERSION, MAX_ALLOWLIST, MAX_ATTESTORS,
+    MAX_DESCRIPTION_LEN, MAX_FEE_BPS, MAX_INDEXED_BOUNTIES, MAX_MILESTONES, MAX_ORACLE_EXPONENT,
+    MAX_ORACLE_FEEDS, MAX_PRIZES, MIN_POST_INTERVAL_SECONDS,
+};
+use crate::errors::BountyForgeError;
+use crate::events::BountyPosted;
+use crate::state::{
+    AttestationSource, Bounty, BountyCounter, BountyStats, BountyStatus, BountyType, Config,
+    CreatorBond, CreatorIndex, CreatorProfile, FundingPool, HashAlgo, Milestone, MilestoneStatus,
+    OracleKind, RewardCurrency,
+};
+use crate::utils::{
+    check_creator_balance, check_reward_mint, is_native_mint, post_bounty_space,
+    validate_metadata_uri,
+};
 use anchor_lang::prelude::*;
-use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
-use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+use anchor_lang::system_program;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    sync_native, transfer_checked, Mint, SyncNative, TokenAccount, TokenInterface, TransferChecked,
+};
+use anchor_spl::{token, token_2022};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PostBountyParams {
+    pub bounty_type: BountyType,
+    /// The wallet a WalletIntelligence bounty is about, or the mint a
+    /// TokenScreening one is, passed as `target_account`. Pubkey::default()
+    /// for none, which only WalletIntelligence refuses.
+    pub target: Pubkey,
+    pub description: String,
+    pub reward: u64,
+    pub reward_currency: RewardCurrency,
+    pub requires_oracle: bool,
+    /// Up to MAX_ORACLE_FEEDS distinct feeds, all of the same `oracle_kind`.
+    pub oracle_feeds: Vec<Pubkey>,
+    pub oracle_kind: OracleKind,
+    /// Band every feed's value must fall in at submission, in units of
+    /// 10^`oracle_exponent` (e.g. -2 for cents). `None` leaves that side open.
+    pub oracle_min_value: Option<i128>,
+    pub oracle_max_value: Option<i128>,
+    pub oracle_exponent: i32,
+    pub deadline: i64,
+    pub max_attestation_age: Option<i64>,
+    pub required_attestations: u8,
+    /// Consumed less disputed attestations the agent's Attestor PDA must show
+    /// for submissions to be accepted, 0 for anyone.
+    pub min_attestor_track_record: u64,
+    /// `Sas` takes a Solana Attestation Service attestation whose data is the solution hash.
+    pub attestation_source: AttestationSource,
+    pub requires_reveal: bool,
+    /// settle_bounty then needs an x402 receipt signed by the Config payment gateway.
+    pub requires_payment_receipt: bool,
+    pub hash_algo: HashAlgo,
+    /// The creator's x25519 public key. Agents then encrypt their solution to
+    /// it and commit to the ciphertext on submission. `None` for plaintext handoff.
+    pub encryption_pubkey: Option<[u8; 32]>,
+    /// `hash_algo` digest of the acceptance criteria, which must be revealed
+    /// with reveal_criteria before any rejection. Zeroed for no commitment.
+    pub criteria_hash: [u8; 32],
+    /// Lets programs submit solutions through CPI, which is otherwise refused.
+    pub allow_cpi: bool,
+    pub competitive: bool,
+    /// Submissions the bounty holds at once, rejected ones freeing their slot. 0 for unlimited.
+    pub max_submissions: u16,
+    pub prizes: Vec<u64>,
+    /// Times the bounty pays `reward`, escrowing `reward * max_claims` up front.
+    /// Above 1 it reopens after each settlement until the last claim is paid.
+    pub max_claims: u16,
+    /// Lets an agent who was already paid a claim submit for another one.
+    pub allow_repeat_solver: bool,
+    /// settle_bounty then mints the solver a soulbound Token-2022 badge.
+    pub mint_badge: bool,
+    pub assignee: Option<Pubkey>,
+    pub requires_application: bool,
+    /// `None` takes the Config default.
+    pub challenge_period_slots: Option<u64>,
+    pub arbiter: Option<Pubkey>,
+    /// Lamports, whatever the reward currency.
+    pub required_stake: u64,
+    /// Lamports held in the bond PDA, paid to the solver if they have to force settle.
+    pub bond: u64,
+    /// Slots the creator has to settle once the challenge period ends, 0 for no limit.
+    /// `None` takes the Config default.
+    pub settle_window_slots: Option<u64>,
+    /// Seconds a rejected agent waits before submitting again. `None` takes the Config default.
+    pub resubmission_cooldown_seconds: Option<u64>,
+    /// Unix timestamp after which a late solution is paid less, 0 pays the full
+    /// reward whenever it lands. Only for single-payout bounties.
+    pub full_reward_until: i64,
+    /// Share of the reward lost per hour past `full_reward_until`, pro rata.
+    pub decay_bps_per_hour: u16,
+    /// Share of the reward a late solution is always paid.
+    pub decay_floor_bps: u16,
+    /// Arweave, IPFS or https link to a JSON document describing the task, empty for none.
+    pub metadata_uri: String,
+    /// Settled bounties an agent must have won before submitting, 0 lets anyone in.
+    pub min_reputation: u64,
+    /// Only these agents may submit, up to MAX_ALLOWLIST of them. Empty lets anyone in.
+    pub allowlist: Vec<Pubkey>,
+    /// Up to MAX_MILESTONES deliverables paid separately, summing to `reward`.
+    /// Empty pays the whole reward on a single settlement.
+    pub milestones: Vec<MilestoneParams>,
+    /// Seconds over which settle_bounty streams the reward to the solver, 0 pays it at once.
+    pub vesting_duration_seconds: u64,
+    /// Key allowed to settle and reject alongside the creator, such as a
+    /// multisig's member key. It cannot cancel the bounty or change the reward.
+    pub settlement_authority: Option<Pubkey>,
+    /// Market the bounty is posted in, whose Config fees and limits it follows
+    /// for good. Empty for the default market.
+    pub market: String,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct MilestoneParams {
+    pub amount: u64,
+    pub description_hash: [u8; 32],
+}
 
 #[derive(Accounts)]
-#[instruction(bounty_id: u64)]
+#[instruction(params: PostBountyParams)]
 pub struct PostBounty<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
 
+    /// Pays the rent of the accounts created here in the creator's place, and
+    /// gets the bounty's and escrow's back on close. Omitted for the creator to pay
+    #[account(mut)]
+    pub sponsor: Option<Signer<'info>>,
+
+    /// Assigns the bounty id, created once per creator with initialize_bounty_counter
+    #[account(
+        mut,
+        seeds = [b"counter", creator.key().as_ref()],
+        bump = bounty_counter.bump
+    )]
+    pub bounty_counter: Account<'info, BountyCounter>,
+
+    /// The first account created, so its space is where the payer's rent is checked
+    #[account(
+        init,
+        payer = sponsor.as_ref().unwrap_or(&creator),
+        space = post_bounty_space(
+            sponsor.as_ref().unwrap_or(&creator),
+            reward_mint.as_ref(),
+            bounty_token_account.is_some()
+        )?,
+        seeds = [
+            b"bounty",
+            creator.key().as_ref(),
+            bounty_counter.next_id.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        init_if_needed,
+        payer = sponsor.as_ref().unwrap_or(&creator),
+        space = ANCHOR_DISCRIMINATOR + CreatorProfile::INIT_SPACE,
+        seeds = [b"creator", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    /// Counters of the market the bounty is posted to
+    #[account(mut, seeds = [b"stats", params.market.as_bytes()], bump = stats.load()?.bump)]
+    pub stats: AccountLoader<'info, BountyStats>,
+
+    /// Ids of the creator's live bounties, so clients can list them without a scan
+    #[account(
+        init_if_needed,
+        payer = sponsor.as_ref().unwrap_or(&creator),
+        space = ANCHOR_DISCRIMINATOR + CreatorIndex::INIT_SPACE,
+        seeds = [b"index", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_index: Box<Account<'info, CreatorIndex>>,
+
+    /// Holds the creator's bond, only required if the bounty is posted with one.
+    /// Its rent stays the creator's even when sponsored, leaving with the bond
     #[account(
         init,
         payer = creator,
-        space = 8 + // discriminator
-                8 + // id: u64
-                1 + // bounty_type: BountyType enum
-                4 + 50 + // description: String (4 byte length + 50 chars max)
-                8 + // reward: u64
-                1 + 32 + // solution_hash: Option<[u8; 32]> (1 byte Some/None tag + 32 bytes)
-                1 + // status: BountyStatus enum
-                32 + // creator: Pubkey
-                1 + // bump: u8
-                32, // extra padding to ensure enough space
-        seeds = [b"bounty", bounty_id.to_le_bytes().as_ref()],
+        space = ANCHOR_DISCRIMINATOR + CreatorBond::INIT_SPACE,
+        seeds = [b"bond", bounty.key().as_ref()],
         bump
     )]
-    pub bounty: Account<'info, Bounty>,
+    pub creator_bond: Option<Box<Account<'info, CreatorBond>>>,
+
+    #[account(
+        seeds = [b"config", params.market.as_bytes()],
+        bump = config.bump,
+        constraint = !config.paused @ BountyForgeError::ProgramPaused
+    )]
+    pub config: Account<'info, Config>,
 
-    /// CHECK: USDC mint address (validated by token account)
-    pub usdc_mint: AccountInfo<'info>,
+    /// Reward mint, owned by either token program, omitted for Sol bounties
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
 
+    /// CHECK: The mint a TokenScreening bounty is about, checked in the handler
+    pub target_account: Option<UncheckedAccount<'info>>,
+
+    /// Omitted for Sol bounties, and for wrapped SOL ones funded from the creator's lamports
     #[account(
         mut,
-        constraint = creator_token_account.mint == usdc_mint.key(),
         constraint = creator_token_account.owner == creator.key()
     )]
-    pub creator_token_account: Account<'info, TokenAccount>,
+    pub creator_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Escrow ATA for the reward mint, created here so posting is one instruction,
+    /// omitted for Sol bounties
+    #[account(
+        init,
+        payer = sponsor.as_ref().unwrap_or(&creator),
+        associated_token::mint = reward_mint,
+        associated_token::authority = bounty,
+        associated_token::token_program = token_program
+    )]
+    pub bounty_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The creator's pool the reward is drawn from, only for post_bounty_from_pool
+    #[account(
+        mut,
+        seeds = [b"pool", creator.key().as_ref(), funding_pool.mint.as_ref()],
+        bump = funding_pool.bump
+    )]
+    pub funding_pool: Option<Box<Account<'info, FundingPool>>>,
 
-    /// CHECK: Bounty token account - will be initialized by ATA program
-    /// Validated in instruction handler
+    /// The pool's token account, checked against the pool in the handler
     #[account(mut)]
-    pub bounty_token_account: AccountInfo<'info>,
+    pub pool_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> PostBounty<'info> {
-    pub fn post_bounty(
-        &mut self,
-        bounty_id: u64,
-        bounty_type: BountyType,
-        description: String,
-        reward: u64,
-        bumps: &PostBountyBumps,
-    ) -> Result<()> {
+    pub fn post_bounty(&mut self, params: PostBountyParams, bumps: &PostBountyBumps) -> Result<()> {
+        let PostBountyParams {
+            bounty_type,
+            target,
+            description,
+            reward,
+            reward_currency,
+            requires_oracle,
+            oracle_feeds,
+            oracle_kind,
+            oracle_min_value,
+            oracle_max_value,
+            oracle_exponent,
+            deadline,
+            max_attestation_age,
+            required_attestations,
+            min_attestor_track_record,
+            attestation_source,
+            requires_reveal,
+            requires_payment_receipt,
+            hash_algo,
+            encryption_pubkey,
+            criteria_hash,
+            allow_cpi,
+            competitive,
+            max_submissions,
+            prizes,
+            max_claims,
+            allow_repeat_solver,
+            mint_badge,
+            assignee,
+            requires_application,
+            challenge_period_slots,
+            arbiter,
+            required_stake,
+            bond,
+            settle_window_slots,
+            resubmission_cooldown_seconds,
+            full_reward_until,
+            decay_bps_per_hour,
+            decay_floor_bps,
+            metadata_uri,
+            min_reputation,
+            allowlist: allowlisted_agents,
+            milestones,
+            vesting_duration_seconds,
+            settlement_authority,
+            market,
+        } = params;
+
+        require!(!description.is_empty(), BountyForgeError::EmptyDescription);
+        require!(
+            description.len() <= MAX_DESCRIPTION_LEN,
+            BountyForgeError::DescriptionTooLong
+        );
+        validate_metadata_uri(&metadata_uri)?;
+        self.config.check_reward(reward)?;
+        self.check_target(bounty_type, &target)?;
+
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(deadline > now, BountyForgeError::InvalidDeadline);
+        require!(
+            !requires_oracle || !oracle_feeds.is_empty(),
+            BountyForgeError::OracleFeedRequired
+        );
+        require!(
+            oracle_feeds.len() <= MAX_ORACLE_FEEDS
+                && !oracle_feeds
+                    .iter()
+                    .enumerate()
+                    .any(|(i, feed)| oracle_feeds[..i].contains(feed)),
+            BountyForgeError::InvalidOracleFeeds
+        );
+        if oracle_min_value.is_some() || oracle_max_value.is_some() {
+            require!(
+                requires_oracle && oracle_exponent.unsigned_abs() <= MAX_ORACLE_EXPONENT,
+                BountyForgeError::InvalidOracleBounds
+            );
+        }
+        if let (Some(min), Some(max)) = (oracle_min_value, oracle_max_value) {
+            require!(min <= max, BountyForgeError::InvalidOracleBounds);
+        }
+        require!(
+            !requires_payment_receipt || self.config.payment_gateway.is_some(),
+            BountyForgeError::PaymentGatewayNotConfigured
+        );
+        if let Some(age) = max_attestation_age {
+            require!(age > 0, BountyForgeError::InvalidAttestationAge);
+        }
+        require!(
+            required_attestations as usize <= MAX_ATTESTORS,
+            BountyForgeError::InvalidAttestationThreshold
+        );
+        // only internal attestations name a registered attestor to count
+        require!(
+            attestation_source == AttestationSource::Internal || required_attestations == 0,
+            BountyForgeError::InvalidAttestationSource
+        );
+        if !prizes.is_empty() {
+            // places are paid to competing submissions, one payout per agent
+            require!(
+                prizes.len() <= MAX_PRIZES,
+                BountyForgeError::InvalidPrizeSchedule
+            );
+            require!(
+                competitive && !requires_reveal && !prizes.contains(&0),
+                BountyForgeError::InvalidPrizeSchedule
+            );
+            let total = prizes
+                .iter()
+                .try_fold(0u64, |sum, prize| sum.checked_add(*prize));
+            require!(
+                total == Some(reward),
+                BountyForgeError::PrizeScheduleMismatch
+            );
+        }
+
+        if !milestones.is_empty() {
+            // one solver works through the milestones, settled by the creator one at a time
+            require!(
+                milestones.len() <= MAX_MILESTONES,
+                BountyForgeError::InvalidMilestoneSchedule
+            );
+            require!(
+                !competitive
+                    && prizes.is_empty()
+                    && !requires_reveal
+                    && !requires_oracle
+                    && required_attestations == 0
+                    && attestation_source == AttestationSource::Internal
+                    && required_stake == 0
+                    && !milestones.iter().any(|milestone| milestone.amount == 0),
+                BountyForgeError::InvalidMilestoneSchedule
+            );
+            let total = milestones
+                .iter()
+                .try_fold(0u64, |sum, milestone| sum.checked_add(milestone.amount));
+            require!(
+                total == Some(reward),
+                BountyForgeError::MilestoneScheduleMismatch
+            );
+        }
+        // each claim is one plain settle_bounty payout, reopening the bounty for the next
+        require!(max_claims > 0, BountyForgeError::InvalidClaimSchedule);
+        if max_claims > 1 {
+            require!(
+                !competitive
+                    && prizes.is_empty()
+                    && milestones.is_empty()
+                    && vesting_duration_seconds == 0
+                    && arbiter.is_none()
+                    && bond == 0,
+                BountyForgeError::InvalidClaimSchedule
+            );
+        }
+        let escrow_total = reward
+            .checked_mul(max_claims as u64)
+            .ok_or(BountyForgeError::RewardOverflow)?;
+
+        // vesting holds back a single payout, not a set of them
+        require!(
+            vesting_duration_seconds == 0 || (prizes.is_empty() && milestones.is_empty()),
+            BountyForgeError::InvalidVestingSchedule
+        );
+        // a decaying reward grades one payout, the schedule set in full or not at all
+        if full_reward_until == 0 {
+            require!(
+                decay_bps_per_hour == 0 && decay_floor_bps == 0,
+                BountyForgeError::InvalidDecaySchedule
+            );
+        } else {
+            require!(
+                decay_bps_per_hour > 0
+                    && decay_floor_bps <= MAX_FEE_BPS
+                    && prizes.is_empty()
+                    && milestones.is_empty(),
+                BountyForgeError::InvalidDecaySchedule
+            );
+        }
+        let milestones = milestones
+            .into_iter()
+            .map(|milestone| Milestone {
+                amount: milestone.amount,
+                description_hash: milestone.description_hash,
+                solution_hash: None,
+                status: MilestoneStatus::Pending,
+            })
+            .collect();
+
+        if let Some(assignee) = assignee {
+            self.config
+                .check_not_self_dealing(&self.creator.key(), &assignee)?;
+        }
+
+        require!(
+            allowlisted_agents.len() <= MAX_ALLOWLIST,
+            BountyForgeError::AllowlistFull
+        );
+        let mut allowlist = [Pubkey::default(); MAX_ALLOWLIST];
+        for (i, agent) in allowlisted_agents.iter().enumerate() {
+            require!(
+                !allowlisted_agents[..i].contains(agent),
+                BountyForgeError::AgentAlreadyAllowlisted
+            );
+            allowlist[i] = *agent;
+        }
+
+        let settle_window_slots =
+            settle_window_slots.unwrap_or(self.config.default_settle_window_slots);
+
+        // a bond is only forfeited once the creator lets the settle window run out
+        require!(
+            bond == 0 || settle_window_slots > 0,
+            BountyForgeError::SettleWindowRequired
+        );
+
+        if self.creator_profile.creator == Pubkey::default() {
+            // New creator profile - initialize it
+            self.creator_profile.set_inner(CreatorProfile {
+                creator: self.creator.key(),
+                open_bounty_count: 0,
+                last_post_ts: 0,
+                disputes_lost: 0,
+                total_posted: 0,
+                total_settled: 0,
+                total_cancelled: 0,
+                total_paid_out: 0,
+                total_settle_delay_slots: 0,
+                timed_settlements: 0,
+                bump: bumps.creator_profile,
+            });
+        } else {
+            require!(
+                now.saturating_sub(self.creator_profile.last_post_ts) >= MIN_POST_INTERVAL_SECONDS,
+                BountyForgeError::PostCooldownActive
+            );
+        }
+
+        require!(
+            self.creator_profile.open_bounty_count < self.config.max_open_per_creator,
+            BountyForgeError::TooManyOpenBounties
+        );
+        self.creator_profile.open_bounty_count = self
+            .creator_profile
+            .open_bounty_count
+            .checked_add(1)
+            .ok_or(BountyForgeError::TooManyOpenBounties)?;
+        self.creator_profile.total_posted = self.creator_profile.total_posted.saturating_add(1);
+        self.creator_profile.last_post_ts = now;
+        self.creator_profile.emit_stats();
+
+        // the bounty PDA was derived from next_id, so it is this bounty's id
+        let bounty_id = self.bounty_counter.next_id;
+        self.bounty_counter.next_id = bounty_id
+            .checked_add(1)
+            .ok_or(BountyForgeError::BountyIdOverflow)?;
+
+        if self.creator_index.creator == Pubkey::default() {
+            self.creator_index.creator = self.creator.key();
+            self.creator_index.bump = bumps.creator_index;
+        }
+        require!(
+            self.creator_index.bounty_ids.len() < MAX_INDEXED_BOUNTIES,
+            BountyForgeError::IndexFull
+        );
+        self.creator_index.bounty_ids.push(bounty_id);
+
         // 1. Initialize bounty account - must be done first before any transfers
         // Using set_inner with init constraint - Anchor handles initialization
         self.bounty.set_inner(Bounty {
+            version: CURRENT_BOUNTY_VERSION,
             id: bounty_id,
             bounty_type,
             description,
             reward,
+            escrow_amount: 0,
             solution_hash: None,
+            solver: None,
             status: BountyStatus::Open,
             creator: self.creator.key(),
+            reward_currency,
+            reward_mint: self
+                .reward_mint
+                .as_ref()
+                .map_or(Pubkey::default(), |mint| mint.key()),
+            requires_oracle,
+            oracle_feeds,
+            oracle_kind,
+            oracle_min_value,
+            oracle_max_value,
+            oracle_exponent,
+            deadline,
+            max_attestation_age,
+            required_attestations,
+            min_attestor_track_record,
+            attestation_source,
+            requires_reveal,
+            revealed: false,
+            requires_payment_receipt,
+            hash_algo,
+            encryption_pubkey,
+            criteria_hash,
+            criteria_revealed: false,
+            allow_cpi,
+            competitive,
+            submission_count: 0,
+            max_submissions,
+            live_submissions: 0,
+            prizes,
+            max_claims,
+            claims_made: 0,
+            allow_repeat_solver,
+            mint_badge,
+            assignee,
+            requires_application,
+            challenge_period_slots: challenge_period_slots
+                .unwrap_or(self.config.default_challenge_period_slots),
+            submitted_at_slot: 0,
+            arbiter,
+            required_stake,
+            creator_bond: bond,
+            settle_window_slots,
+            resubmission_cooldown_seconds: resubmission_cooldown_seconds
+                .unwrap_or(self.config.default_resubmission_cooldown_seconds),
+            full_reward_until,
+            decay_bps_per_hour,
+            decay_floor_bps,
+            creator_deposit: 0,
+            contributed: 0,
+            contributor_pool: 0,
+            total_boost_paid: 0,
+            pending_creator: None,
+            rent_payer: self
+                .sponsor
+                .as_ref()
+                .map_or(self.creator.key(), |sponsor| sponsor.key()),
+            appellant: None,
+            appeal_ends_at: 0,
+            original_creator: self.creator.key(),
+            metadata_uri: metadata_uri.clone(),
+            min_reputation,
+            allowlist,
+            allowlist_len: allowlisted_agents.len() as u8,
+            milestones,
+            vesting_duration_seconds,
+            vesting_start: 0,
+            vesting_amount: 0,
+            vested_claimed: 0,
+            feedback_given: false,
+            created_at: now,
+            submitted_at: 0,
+            settled_at: 0,
             bump: bumps.bounty,
+            boosted_until: 0,
+            target,
+            settlement_authority,
+            market,
         });
 
-        // 2. Verify the bounty token account is correctly derived
-        let expected_ata = get_associated_token_address(&self.bounty.key(), &self.usdc_mint.key());
+        let escrowed = match reward_currency {
+            RewardCurrency::Spl => self.escrow_tokens(escrow_total)?,
+            RewardCurrency::Sol => self.escrow_lamports(escrow_total)?,
+        };
+        self.bounty.escrow_amount = escrowed;
+        self.bounty.creator_deposit = escrowed;
+        self.stats.load_mut()?.record_posted(escrowed)?;
+
+        if let Some(bump) = bumps.creator_bond {
+            self.escrow_bond(bond, bump)?;
+        } else {
+            require!(bond == 0, BountyForgeError::MissingCreatorBond);
+        }
+
+        emit!(BountyPosted {
+            bounty: self.bounty.key(),
+            bounty_id,
+            creator: self.creator.key(),
+            reward,
+            bounty_type,
+            target,
+            metadata_uri,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+
+    /// Posts like post_bounty, with the escrow drawn from the creator's funding
+    /// pool for the reward mint instead of their token account. Only token
+    /// bounties can be funded this way.
+    pub fn post_bounty_from_pool(
+        &mut self,
+        params: PostBountyParams,
+        bumps: &PostBountyBumps,
+    ) -> Result<()> {
         require!(
-            self.bounty_token_account.key() == expected_ata,
-            anchor_lang::error::ErrorCode::ConstraintTokenMint
+            params.reward_currency == RewardCurrency::Spl
+                && self.funding_pool.is_some()
+                && self.pool_token_account.is_some(),
+            BountyForgeError::MissingFundingPool
         );
+        self.post_bounty(params, bumps)
+    }
+
+    /// A WalletIntelligence bounty needs a wallet, and a TokenScreening one
+    /// that names a target needs it to be a mint of either token program. A
+    /// screen across tokens names none. Other types take any target.
+    fn check_target(&self, bounty_type: BountyType, target: &Pubkey) -> Result<()> {
+        match bounty_type {
+            BountyType::WalletIntelligence => {
+                require!(
+                    *target != Pubkey::default(),
+                    BountyForgeError::InvalidBountyTarget
+                );
+            }
+            BountyType::TokenScreening if *target != Pubkey::default() => {
+                let account = self
+                    .target_account
+                    .as_ref()
+                    .ok_or(BountyForgeError::InvalidBountyTarget)?;
+                require_keys_eq!(
+                    account.key(),
+                    *target,
+                    BountyForgeError::InvalidBountyTarget
+                );
+                require!(
+                    *account.owner == token::ID || *account.owner == token_2022::ID,
+                    BountyForgeError::InvalidBountyTarget
+                );
+                Mint::try_deserialize(&mut &account.try_borrow_data()?[..])
+                    .map_err(|_| error!(BountyForgeError::InvalidBountyTarget))?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn escrow_tokens(&mut self, reward: u64) -> Result<u64> {
+        let reward_mint = self
+            .reward_mint
+            .as_ref()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+        check_reward_mint(reward_mint, &self.creator.key(), &self.config)?;
+        if self.funding_pool.is_some() {
+            return self.escrow_from_pool(reward);
+        }
+        if is_native_mint(&reward_mint.key()) && self.creator_token_account.is_none() {
+            return self.wrap_lamports(reward);
+        }
+        let creator_token_account = self
+            .creator_token_account
+            .as_ref()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+        let bounty_token_account = self
+            .bounty_token_account
+            .as_ref()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
 
-        // 3. Verify the token account is initialized (has data)
         require!(
-            !self.bounty_token_account.data_is_empty(),
-            anchor_lang::error::ErrorCode::AccountNotInitialized
+            creator_token_account.mint == reward_mint.key(),
+            BountyForgeError::RewardMintMismatch
         );
+        check_creator_balance(creator_token_account, reward)?;
 
-        // 4. Transfer USDC from creator to bounty PDA token account (escrow)
+        // 2. Transfer reward tokens from creator to bounty PDA token account (escrow)
         let cpi_program = self.token_program.to_account_info();
-        let cpi_accounts = Transfer {
-            from: self.creator_token_account.to_account_info(),
-            to: self.bounty_token_account.clone(),
+        let cpi_accounts = TransferChecked {
+            from: creator_token_account.to_account_info(),
+            mint: reward_mint.to_account_info(),
+            to: bounty_token_account.to_account_info(),
             authority: self.creator.to_account_info(),
         };
 
         let cpi_context = CpiContext::new(cpi_program, cpi_accounts);
 
-        transfer(cpi_context, reward)?;
+        transfer_checked(cpi_context, reward, reward_mint.decimals)?;
+
+        // 3. Record what actually landed in the fresh ATA, a transfer-fee mint withholds part of it
+        let bounty_token_account = self
+            .bounty_token_account
+            .as_mut()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+        bounty_token_account.reload()?;
+
+        Ok(bounty_token_account.amount)
+    }
+
+    /// Funds the escrow from the creator's funding pool, signed by the pool PDA,
+    /// so the creator's token account isn't involved.
+    fn escrow_from_pool(&mut self, reward: u64) -> Result<u64> {
+        let reward_mint = self
+            .reward_mint
+            .as_ref()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+        let funding_pool = self
+            .funding_pool
+            .as_mut()
+            .ok_or(BountyForgeError::MissingFundingPool)?;
+        let pool_token_account = self
+            .pool_token_account
+            .as_ref()
+            .ok_or(BountyForgeError::MissingFundingPool)?;
+        let bounty_token_account = self
+            .bounty_token_account
+            .as_ref()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+
+        require_keys_eq!(
+            funding_pool.mint,
+            reward_mint.key(),
+            BountyForgeError::RewardMintMismatch
+        );
+        require_keys_eq!(
+            pool_token_account.owner,
+            funding_pool.key(),
+            BountyForgeError::PoolAccountMismatch
+        );
+        require_keys_eq!(
+            pool_token_account.mint,
+            funding_pool.mint,
+            BountyForgeError::PoolAccountMismatch
+        );
+        funding_pool.allocate(reward)?;
+
+        let creator = self.creator.key();
+        let seeds: &[&[u8]] = &[
+            b"pool",
+            creator.as_ref(),
+            funding_pool.mint.as_ref(),
+            &[funding_pool.bump],
+        ];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: pool_token_account.to_account_info(),
+                    mint: reward_mint.to_account_info(),
+                    to: bounty_token_account.to_account_info(),
+                    authority: funding_pool.to_account_info(),
+                },
+                &[seeds],
+            ),
+            reward,
+            reward_mint.decimals,
+        )?;
+        funding_pool.emit_update(funding_pool.key())?;
+
+        let bounty_token_account = self
+            .bounty_token_account
+            .as_mut()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+        bounty_token_account.reload()?;
+
+        Ok(bounty_token_account.amount)
+    }
+
+    fn escrow_bond(&mut self, bond: u64, bump: u8) -> Result<()> {
+        let bounty = self.bounty.key();
+        let creator = self.creator.key();
+        let creator_bond = self
+            .creator_bond
+            .as_mut()
+            .ok_or(BountyForgeError::MissingCreatorBond)?;
+
+        // the bond sits on its own PDA so it never mixes with the reward escrow
+        if bond > 0 {
+            let cpi_accounts = system_program::Transfer {
+                from: self.creator.to_account_info(),
+                to: creator_bond.to_account_info(),
+            };
+            system_program::transfer(
+                CpiContext::new(self.system_program.to_account_info(), cpi_accounts),
+                bond,
+            )?;
+        }
+        creator_bond.set_inner(CreatorBond {
+            bounty,
+            creator,
+            amount: bond,
+            bump,
+        });
 
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Funds a wrapped SOL escrow straight from the creator's lamports, so
+    /// they don't need a wSOL account of their own.
+    fn wrap_lamports(&mut self, reward: u64) -> Result<u64> {
+        // rent for every account created here has already been taken
+        require!(
+            self.creator.lamports() >= reward,
+            BountyForgeError::InsufficientLamports
+        );
+        let bounty_token_account = self
+            .bounty_token_account
+            .as_mut()
+            .ok_or(BountyForgeError::MissingTokenAccount)?;
+
+        let cpi_accounts = system_program::Transfer {
+            from: self.creator.to_account_info(),
+            to: bounty_token_account.to_account_info(),
+        };
+        system_program::transfer(
+            CpiContext::new(self.system_program.to_account_info(), cpi_accounts),
+            reward,
+        )?;
+
+        // the lamports only count as tokens once the account is synced
+        let cpi_accounts = SyncNative {
+            account: bounty_token_account.to_account_info(),
+        };
+        sync_native(CpiContext::new(
+            self.token_program.to_account_info(),
+            cpi_accounts,
+        ))?;
+
+        bounty_token_account.reload()?;
+        Ok(bounty_token_account.amount)
+    }
+
+    fn escrow_lamports(&self, reward: u64) -> Result<u64> {
+        // 2. Transfer lamports from creator straight onto the bounty PDA (escrow)
+        // the account was funded rent-exempt by init, so the reward sits on top of it
+        let cpi_program = self.system_program.to_account_info();
+        let cpi_accounts = system_program::Transfer {
+            from: self.creator.to_account_info(),
+            to: self.bounty.to_account_info(),
+        };
+
+        let cpi_context = CpiContext::new(cpi_program, cpi_accounts);
+
+        system_program::transfer(cpi_context, reward)?;
+
+        Ok(reward)
+    }
+}
@@ -0,0 +1,96 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::events::ContributionRefunded;
+use crate::state::{Bounty, BountyStatus, Contribution};
+use crate::utils::pay_from_escrow;
+
+#[derive(Accounts)]
+pub struct RefundContribution<'info> {
+    /// CHECK: Contributor being refunded, gets the contribution account's rent back too
+    #[account(mut)]
+    pub contributor: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = matches!(
+            bounty.status,
+            BountyStatus::Settled | BountyStatus::Cancelled | BountyStatus::Expired
+        ) @ BountyForgeError::BountyNotFinished
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        mut,
+        close = contributor,
+        seeds = [b"contrib", bounty.key().as_ref(), contributor.key().as_ref()],
+        bump = contribution.bump,
+        has_one = contributor
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(
+        mut,
+        constraint = contributor_token_account.owner == contributor.key(),
+        constraint = contributor_token_account.mint == bounty.reward_mint
+    )]
+    pub contributor_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = bounty_token_account.owner == bounty.key(),
+        constraint = bounty_token_account.mint == bounty.reward_mint
+    )]
+    pub bounty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> RefundContribution<'info> {
+    /// Pays a contributor their share of what was set aside when the bounty was
+    /// refunded, and closes their contribution. Anyone can crank it.
+    pub fn refund_contribution(&mut self) -> Result<()> {
+        // 1. working out this contribution's cut of the pool, the last one takes the remainder
+        let amount = self.contribution.amount;
+        let refund = if self.bounty.contributed == 0 {
+            0
+        } else {
+            (amount as u128)
+                .checked_mul(self.bounty.contributor_pool as u128)
+                .and_then(|scaled| scaled.checked_div(self.bounty.contributed as u128))
+                .and_then(|share| u64::try_from(share).ok())
+                .ok_or(BountyForgeError::FeeCalculationOverflow)?
+        };
+
+        // 2. paying it out of escrow, a settled bounty usually has nothing left to give
+        if refund > 0 {
+            pay_from_escrow(
+                &self.bounty,
+                self.bounty_token_account.as_ref(),
+                self.reward_mint.as_ref(),
+                &self.contributor,
+                self.contributor_token_account.as_ref(),
+                &self.token_program,
+                refund,
+            )?;
+        }
+        self.bounty.contributor_pool = self.bounty.contributor_pool.saturating_sub(refund);
+        self.bounty.contributed = self.bounty.contributed.saturating_sub(amount);
+
+        emit!(ContributionRefunded {
+            bounty: self.bounty.key(),
+            contributor: self.contributor.key(),
+            amount: refund,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::ANCHOR_DISCRIMINATOR;
+use crate::errors::BountyForgeError;
+use crate::state::{Attestor, Config};
+
+#[derive(Accounts)]
+#[instruction(market: String, attestor: Pubkey)]
+pub struct RegisterAttestor<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", market.as_bytes()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ BountyForgeError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = ANCHOR_DISCRIMINATOR + Attestor::INIT_SPACE,
+        seeds = [b"attestor", market.as_bytes(), attestor.as_ref()],
+        bump
+    )]
+    pub attestor_account: Account<'info, Attestor>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterAttestor<'info> {
+    pub fn register_attestor(
+        &mut self,
+        attestor: Pubkey,
+        name: String,
+        bumps: &RegisterAttestorBumps,
+    ) -> Result<()> {
+        require!(name.len() <= 32, BountyForgeError::AttestorNameTooLong);
+
+        // registering a removed attestor again just reactivates it, keeping its record
+        self.attestor_account.set_inner(Attestor {
+            authority: attestor,
+            name,
+            registered_at: Clock::get()?.unix_timestamp,
+            active: true,
+            attestations_issued: self.attestor_account.attestations_issued,
+            attestations_consumed: self.attestor_account.attestations_consumed,
+            attestations_disputed: self.attestor_account.attestations_disputed,
+            bump: bumps.attestor_account,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::ANCHOR_DISCRIMINATOR;
+use crate::errors::BountyForgeError;
+use crate::events::OperatorRegistered;
+use crate::state::Operator;
+
+#[derive(Accounts)]
+#[instruction(operator: Pubkey)]
+pub struct RegisterOperator<'info> {
+    /// Only the agent identity itself, so an operator can't add others
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    #[account(
+        init,
+        payer = agent,
+        space = ANCHOR_DISCRIMINATOR + Operator::INIT_SPACE,
+        seeds = [b"operator", agent.key().as_ref(), operator.as_ref()],
+        bump
+    )]
+    pub operator_record: Account<'info, Operator>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterOperator<'info> {
+    /// Lets `operator` sign `submit_solution` for the agent, whose reputation
+    /// and payouts the submissions still count towards
+    pub fn register_operator(
+        &mut self,
+        operator: Pubkey,
+        bumps: &RegisterOperatorBumps,
+    ) -> Result<()> {
+        require_keys_neq!(
+            operator,
+            self.agent.key(),
+            BountyForgeError::InvalidOperator
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        self.operator_record.set_inner(Operator {
+            agent: self.agent.key(),
+            operator,
+            registered_at: now,
+            bump: bumps.operator_record,
+        });
+
+        emit!(OperatorRegistered {
+            agent: self.agent.key(),
+            operator,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
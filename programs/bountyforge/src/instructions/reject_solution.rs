@@ -0,0 +1,136 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{ANCHOR_DISCRIMINATOR, CURRENT_BOUNTY_VERSION};
+use crate::errors::BountyForgeError;
+use crate::events::SolutionRejected;
+use crate::state::{
+    AppealStatus, Bounty, BountyStatus, Config, Rejection, RejectionReason, Reputation, Submission,
+    SubmissionStatus,
+};
+use crate::utils::slash_stake;
+
+#[derive(Accounts)]
+pub struct RejectSolution<'info> {
+    /// The creator, or the settlement authority they named on the bounty
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.status == BountyStatus::Submitted @ BountyForgeError::NotRejectable,
+        constraint = bounty.can_settle(&creator.key()) @ BountyForgeError::UnauthorizedSettlement,
+        constraint = bounty.solver == Some(agent.key()) @ BountyForgeError::SolverMismatch,
+        constraint = bounty.criteria_disclosed() @ BountyForgeError::CriteriaNotRevealed
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    /// CHECK: Agent whose solution is being rejected (validated against bounty.solver)
+    pub agent: AccountInfo<'info>,
+
+    /// CHECK: The bounty's creator, credited with the slashed stake. Only
+    /// needed when the settlement authority signs instead of the creator
+    #[account(mut, address = bounty.creator @ BountyForgeError::UnauthorizedSettlement)]
+    pub bounty_creator: Option<UncheckedAccount<'info>>,
+
+    #[account(
+        mut,
+        seeds = [b"rep", agent.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    #[account(
+        mut,
+        seeds = [b"submission", bounty.key().as_ref(), agent.key().as_ref()],
+        bump = submission.bump
+    )]
+    pub submission: Account<'info, Submission>,
+
+    /// Why the solution was turned down, for the agent to learn from or appeal
+    #[account(
+        init,
+        payer = creator,
+        space = ANCHOR_DISCRIMINATOR + Rejection::INIT_SPACE,
+        seeds = [
+            b"rejection",
+            bounty.key().as_ref(),
+            agent.key().as_ref(),
+            reputation.rejections.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub rejection: Account<'info, Rejection>,
+
+    #[account(seeds = [b"config", bounty.market.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Protocol treasury wallet, only needed when part of a stake is slashed to it
+    #[account(mut, address = config.treasury @ BountyForgeError::TreasuryMismatch)]
+    pub treasury: Option<AccountInfo<'info>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RejectSolution<'info> {
+    pub fn reject_solution(
+        &mut self,
+        reason: RejectionReason,
+        note_hash: Option<[u8; 32]>,
+        bumps: &RejectSolutionBumps,
+    ) -> Result<()> {
+        // 1. reopening the bounty for other agents
+        let now = Clock::get()?.unix_timestamp;
+        self.bounty.solution_hash = None;
+        self.bounty.solver = None;
+        self.bounty.revealed = false;
+        self.bounty.status = BountyStatus::Open;
+        self.submission.status = SubmissionStatus::Rejected;
+        self.bounty.release_submission_slot();
+        self.submission.rejected_at = now;
+
+        // 2. recording the failure against the submitting agent, with the reason
+        // kept where they can appeal it
+        let attempt_index = self.reputation.record_rejection(reason)?;
+        self.rejection.set_inner(Rejection {
+            bounty: self.bounty.key(),
+            agent: self.agent.key(),
+            attempt_index,
+            reason,
+            note_hash,
+            rejected_by: self.creator.key(),
+            rejected_at: now,
+            appeal: AppealStatus::Unappealed,
+            bump: bumps.rejection,
+        });
+        emit!(SolutionRejected {
+            bounty: self.bounty.key(),
+            agent: self.agent.key(),
+            rejection: self.rejection.key(),
+            attempt_index,
+            reason,
+            note_hash,
+            rejected_by: self.creator.key(),
+            timestamp: now,
+        });
+
+        // 3. slashing their stake, whose non-treasury part goes to the creator
+        // even if a delegate signed
+        let creator = if self.creator.key() == self.bounty.creator {
+            self.creator.to_account_info()
+        } else {
+            self.bounty_creator
+                .as_ref()
+                .ok_or(BountyForgeError::MissingBountyCreator)?
+                .to_account_info()
+        };
+        slash_stake(
+            &mut self.submission,
+            &creator,
+            self.treasury.as_ref(),
+            self.config.stake_slash_treasury_bps,
+        )?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::state::{Bounty, BountyStatus};
+
+#[derive(Accounts)]
+pub struct RemoveAllowlistedAgent<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = creator.key() == bounty.creator @ BountyForgeError::UnauthorizedAllowlistChange,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+}
+
+impl<'info> RemoveAllowlistedAgent<'info> {
+    /// Removing the last agent opens the bounty to anyone again
+    pub fn remove_allowlisted_agent(&mut self, agent: Pubkey) -> Result<()> {
+        let index = self
+            .bounty
+            .allowlisted_agents()
+            .iter()
+            .position(|listed| *listed == agent)
+            .ok_or(BountyForgeError::AgentNotAllowlisted)?;
+
+        // order doesn't matter, so the last entry fills the gap
+        let last = self.bounty.allowlist_len as usize - 1;
+        self.bounty.allowlist[index] = self.bounty.allowlist[last];
+        self.bounty.allowlist[last] = Pubkey::default();
+        self.bounty.allowlist_len -= 1;
+
+        Ok(())
+    }
+}
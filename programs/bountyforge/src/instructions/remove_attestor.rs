@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BountyForgeError;
+use crate::state::{Attestor, Config};
+
+#[derive(Accounts)]
+#[instruction(market: String)]
+pub struct RemoveAttestor<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", market.as_bytes()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ BountyForgeError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"attestor", market.as_bytes(), attestor_account.authority.as_ref()],
+        bump = attestor_account.bump
+    )]
+    pub attestor_account: Account<'info, Attestor>,
+}
+
+impl<'info> RemoveAttestor<'info> {
+    pub fn remove_attestor(&mut self) -> Result<()> {
+        // deactivated rather than closed, existing attestations keep pointing at it
+        self.attestor_account.active = false;
+
+        Ok(())
+    }
+}
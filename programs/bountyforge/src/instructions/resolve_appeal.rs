@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::events::AppealResolved;
+use crate::state::{AppealStatus, Bounty, Rejection, Reputation};
+
+#[derive(Accounts)]
+pub struct ResolveAppeal<'info> {
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.arbiter.is_some() @ BountyForgeError::NoArbiter,
+        constraint = bounty.arbiter == Some(arbiter.key()) @ BountyForgeError::UnauthorizedArbiter
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"rejection",
+            bounty.key().as_ref(),
+            rejection.agent.as_ref(),
+            rejection.attempt_index.to_le_bytes().as_ref()
+        ],
+        bump = rejection.bump,
+        constraint = rejection.appeal == AppealStatus::Contested @ BountyForgeError::RejectionNotContested
+    )]
+    pub rejection: Account<'info, Rejection>,
+
+    #[account(
+        mut,
+        seeds = [b"rep", rejection.agent.as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+}
+
+impl<'info> ResolveAppeal<'info> {
+    /// The bounty's arbiter rules on a contested rejection, even once its
+    /// window has passed. Upholding the appeal takes the failure, and any
+    /// plagiarism count, back off the agent's reputation; the stake slashed
+    /// with the rejection stays where it went. Either way the creator may
+    /// settle with anyone again.
+    pub fn resolve_appeal(&mut self, uphold: bool) -> Result<()> {
+        let agent = self.rejection.agent;
+        if uphold {
+            self.rejection.appeal = AppealStatus::Upheld;
+            self.reputation.overturn_rejection(self.rejection.reason);
+        } else {
+            self.rejection.appeal = AppealStatus::Dismissed;
+        }
+        if self.bounty.appellant == Some(agent) {
+            self.bounty.appellant = None;
+            self.bounty.appeal_ends_at = 0;
+        }
+
+        emit!(AppealResolved {
+            bounty: self.bounty.key(),
+            agent,
+            rejection: self.rejection.key(),
+            upheld: uphold,
+            arbiter: self.arbiter.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::state::{
+    Bounty, BountyStatus, Challenge, Config, Reputation, Submission, SubmissionStatus,
+};
+use crate::utils::{pay_from_escrow, slash_stake, split_fee, transfer_stake};
+
+#[derive(Accounts)]
+pub struct ResolveChallenge<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", bounty.market.as_bytes()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ BountyForgeError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.status == BountyStatus::Challenged @ BountyForgeError::BountyNotChallenged,
+        constraint = bounty.solver == Some(agent.key()) @ BountyForgeError::SolverMismatch
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    // closing hands the bond back to the challenger along with the rent, less
+    // whatever a dismissal forfeits to the solver first
+    #[account(
+        mut,
+        close = challenger,
+        seeds = [b"challenge", bounty.key().as_ref()],
+        bump = challenge.bump,
+        has_one = challenger
+    )]
+    pub challenge: Account<'info, Challenge>,
+
+    /// CHECK: Challenger receiving the bond (validated against challenge.challenger)
+    #[account(mut)]
+    pub challenger: AccountInfo<'info>,
+
+    /// CHECK: Creator receiving the slashed stake (validated against bounty.creator)
+    #[account(mut, address = bounty.creator @ BountyForgeError::UnauthorizedSettlement)]
+    pub creator: AccountInfo<'info>,
+
+    /// CHECK: Agent whose solution was challenged (validated against bounty.solver),
+    /// paid the forfeited part of the bond when the challenge is dismissed
+    #[account(mut)]
+    pub agent: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rep", agent.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    #[account(
+        mut,
+        seeds = [b"submission", bounty.key().as_ref(), agent.key().as_ref()],
+        bump = submission.bump
+    )]
+    pub submission: Account<'info, Submission>,
+
+    /// Only needed when an upheld challenge pays a reward from a token escrow
+    #[account(
+        mut,
+        constraint = challenger_token_account.owner == challenger.key(),
+        constraint = challenger_token_account.mint == bounty.reward_mint
+    )]
+    pub challenger_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = bounty_token_account.owner == bounty.key(),
+        constraint = bounty_token_account.mint == bounty.reward_mint
+    )]
+    pub bounty_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// CHECK: Protocol treasury wallet, only needed when part of a stake is slashed to it
+    #[account(mut, address = config.treasury @ BountyForgeError::TreasuryMismatch)]
+    pub treasury: Option<AccountInfo<'info>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> ResolveChallenge<'info> {
+    pub fn resolve_challenge(&mut self, uphold: bool) -> Result<()> {
+        if !uphold {
+            // a frivolous challenge costs the challenger the Config share of the bond
+            let (_, forfeit) = split_fee(self.challenge.bond, self.config.challenge_forfeit_bps)?;
+            if forfeit > 0 {
+                transfer_stake(&self.challenge.to_account_info(), &self.agent, forfeit)?;
+            }
+
+            // the solution stands and settles once the challenge period is over
+            self.bounty.status = BountyStatus::Submitted;
+            return Ok(());
+        }
+
+        // 1. rewarding the challenger with a slice of the escrow
+        let (_, reward) = split_fee(self.bounty.escrow_amount, self.config.challenge_reward_bps)?;
+        if reward > 0 {
+            pay_from_escrow(
+                &self.bounty,
+                self.bounty_token_account.as_deref(),
+                self.reward_mint.as_deref(),
+                &self.challenger,
+                self.challenger_token_account.as_deref(),
+                &self.token_program,
+                reward,
+            )?;
+            self.bounty.escrow_amount = self.bounty.escrow_amount.saturating_sub(reward);
+        }
+
+        // 2. reopening the bounty as reject_solution would
+        self.bounty.solution_hash = None;
+        self.bounty.solver = None;
+        self.bounty.revealed = false;
+        self.bounty.status = BountyStatus::Open;
+        self.submission.status = SubmissionStatus::Rejected;
+        self.bounty.release_submission_slot();
+        self.submission.rejected_at = Clock::get()?.unix_timestamp;
+        self.reputation.record_failure()?;
+        slash_stake(
+            &mut self.submission,
+            &self.creator,
+            self.treasury.as_ref(),
+            self.config.stake_slash_treasury_bps,
+        )?;
+
+        Ok(())
+    }
+}
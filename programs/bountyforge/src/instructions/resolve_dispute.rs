@@ -0,0 +1,356 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::{CURRENT_BOUNTY_VERSION, MAX_FEE_BPS};
+use crate::errors::BountyForgeError;
+use crate::state::{
+    Attestor, Bounty, BountyStats, BountyStatus, Config, CreatorProfile, InsuranceFund,
+    Leaderboard, Reputation, Submission,
+};
+use crate::utils::{
+    cover_shortfall, mint_badge, pay_attestor_share, pay_from_escrow, pay_insurance, pay_solver,
+    slash_stake, transfer_stake,
+};
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum DisputeOutcome {
+    PaySolver,
+    RefundCreator,
+    Split(u16), // bps of the escrow going to the solver, the rest to the creator
+}
+
+#[derive(Accounts)]
+pub struct ResolveDispute<'info> {
+    /// Pays for the solver's badge when the bounty mints one
+    #[account(mut)]
+    pub arbiter: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.status == BountyStatus::Disputed @ BountyForgeError::BountyNotDisputed,
+        constraint = bounty.arbiter == Some(arbiter.key()) @ BountyForgeError::UnauthorizedArbiter,
+        constraint = bounty.solver == Some(agent.key()) @ BountyForgeError::SolverMismatch,
+        constraint = bounty.criteria_disclosed() @ BountyForgeError::CriteriaNotRevealed
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    /// CHECK: Creator receiving any refund (validated against bounty.creator)
+    #[account(mut, address = bounty.creator @ BountyForgeError::UnauthorizedSettlement)]
+    pub creator: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"creator", bounty.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    /// Counters of the bounty's market
+    #[account(mut, seeds = [b"stats", bounty.market.as_bytes()], bump = stats.load()?.bump)]
+    pub stats: AccountLoader<'info, BountyStats>,
+
+    /// Top agents by score
+    #[account(mut, seeds = [b"leaderboard"], bump = leaderboard.load()?.bump)]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+
+    /// CHECK: Solver receiving any payout (validated against bounty.solver)
+    #[account(mut)]
+    pub agent: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"rep", agent.key().as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.agent == agent.key() @ BountyForgeError::ReputationOwnerMismatch
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    #[account(
+        mut,
+        seeds = [b"submission", bounty.key().as_ref(), agent.key().as_ref()],
+        bump = submission.bump
+    )]
+    pub submission: Box<Account<'info, Submission>>,
+
+    /// CHECK: The solver's Attestor PDA, which a Sas bounty's solver may not
+    /// have. Counts the dispute in the handler when the solver isn't paid in full
+    #[account(
+        mut,
+        seeds = [b"attestor", bounty.market.as_bytes(), agent.key().as_ref()],
+        bump
+    )]
+    pub attestor_account: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = agent_token_account.owner == agent.key(),
+        constraint = agent_token_account.mint == bounty.reward_mint
+    )]
+    pub agent_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == bounty.creator,
+        constraint = creator_token_account.mint == bounty.reward_mint
+    )]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = bounty_token_account.owner == bounty.key(),
+        constraint = bounty_token_account.mint == bounty.reward_mint
+    )]
+    pub bounty_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(seeds = [b"config", bounty.market.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Protocol treasury wallet, only needed when a fee is charged
+    #[account(mut, address = config.treasury @ BountyForgeError::TreasuryMismatch)]
+    pub treasury: Option<AccountInfo<'info>>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == config.treasury @ BountyForgeError::TreasuryMismatch,
+        constraint = treasury_token_account.mint == bounty.reward_mint
+    )]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Attestor recorded on the submission, paid on Sol bounties, omitted to
+    /// send their share to the treasury
+    #[account(mut, address = submission.attestor @ BountyForgeError::NotSubmissionAttestor)]
+    pub attestor: Option<AccountInfo<'info>>,
+
+    /// The attestor's token account, paid on token bounties, omitted to send
+    /// their share to the treasury
+    #[account(
+        mut,
+        constraint = attestor_token_account.owner == submission.attestor @ BountyForgeError::NotSubmissionAttestor,
+        constraint = attestor_token_account.mint == bounty.reward_mint
+    )]
+    pub attestor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The market's insurance fund for the reward mint, covers a slightly short
+    /// escrow and takes its share of the fee, omitted to do neither
+    #[account(
+        mut,
+        seeds = [b"insurance", bounty.market.as_bytes(), bounty.reward_mint.as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Option<Box<Account<'info, InsuranceFund>>>,
+
+    /// The fund's token account, checked against the fund in the handler
+    #[account(mut)]
+    pub insurance_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// CHECK: Badge mint, created in the handler, only needed when the bounty
+    /// mints badges and the solver is paid
+    #[account(
+        mut,
+        seeds = [b"badge", bounty.key().as_ref(), bounty.claims_made.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub badge_mint: Option<AccountInfo<'info>>,
+
+    /// CHECK: Mint and metadata authority of every badge
+    #[account(seeds = [b"badge_authority"], bump)]
+    pub badge_authority: Option<AccountInfo<'info>>,
+
+    /// CHECK: The agent's Token-2022 ATA for the badge, created in the handler
+    #[account(mut)]
+    pub agent_badge_account: Option<AccountInfo<'info>>,
+
+    pub badge_token_program: Option<Program<'info, Token2022>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ResolveDispute<'info> {
+    /// Pays out a disputed bounty as the arbiter rules. The solver's share is
+    /// split by the submission's team table, `member_accounts` holding each
+    /// member's payout account as for `settle_bounty`.
+    pub fn resolve_dispute(
+        &mut self,
+        outcome: DisputeOutcome,
+        bumps: &ResolveDisputeBumps,
+        member_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        // the vault must really hold what the bounty records, any surplus stays for close_bounty
+        cover_shortfall(
+            &self.bounty,
+            &self.config,
+            self.insurance_fund.as_deref_mut(),
+            self.insurance_token_account.as_deref(),
+            self.bounty_token_account.as_deref_mut(),
+            self.reward_mint.as_deref(),
+            &self.token_program,
+        )?;
+
+        // 1. working out the solver's share, the creator gets the rest back
+        let escrow = self.bounty.escrow_amount;
+        let solver_share = match outcome {
+            DisputeOutcome::PaySolver => escrow,
+            DisputeOutcome::RefundCreator => 0,
+            DisputeOutcome::Split(bps) => {
+                require!(bps <= MAX_FEE_BPS, BountyForgeError::InvalidSplitBps);
+                (escrow as u128)
+                    .checked_mul(bps as u128)
+                    .and_then(|scaled| scaled.checked_div(MAX_FEE_BPS as u128))
+                    .and_then(|share| u64::try_from(share).ok())
+                    .ok_or(BountyForgeError::FeeCalculationOverflow)?
+            }
+        };
+        // contributors share in whatever is refunded, pro rata, through refund_contribution
+        let contributors_share = self
+            .bounty
+            .contributors_share(escrow.saturating_sub(solver_share))?;
+        let refund = escrow
+            .saturating_sub(solver_share)
+            .saturating_sub(contributors_share);
+        let (solver_amount, _, fee_amount) = pay_attestor_share(
+            &self.bounty,
+            &self.submission,
+            &self.config,
+            self.bounty_token_account.as_deref(),
+            self.reward_mint.as_deref(),
+            self.attestor.as_ref(),
+            self.attestor_token_account.as_deref(),
+            &self.token_program,
+            solver_share,
+        )?;
+
+        // 2. paying out, the attestor's share and the protocol fee only apply to
+        // what the solver gets, which is split by the submission's team table
+        if solver_amount > 0 {
+            pay_solver(
+                &self.bounty,
+                &self.submission.team,
+                self.bounty_token_account.as_deref(),
+                self.reward_mint.as_deref(),
+                &self.agent,
+                self.agent_token_account.as_deref(),
+                member_accounts,
+                &self.token_program,
+                solver_amount,
+            )?;
+        }
+
+        // the insurance fund's cut comes out of the protocol fee, never the solver's share
+        let fee_amount = pay_insurance(
+            &self.bounty,
+            &self.config,
+            self.insurance_fund.as_deref_mut(),
+            self.insurance_token_account.as_deref_mut(),
+            self.bounty_token_account.as_deref(),
+            self.reward_mint.as_deref(),
+            &self.token_program,
+            fee_amount,
+        )?;
+        if fee_amount > 0 {
+            let treasury = self
+                .treasury
+                .as_ref()
+                .ok_or(BountyForgeError::MissingTreasury)?;
+            pay_from_escrow(
+                &self.bounty,
+                self.bounty_token_account.as_deref(),
+                self.reward_mint.as_deref(),
+                treasury,
+                self.treasury_token_account.as_deref(),
+                &self.token_program,
+                fee_amount,
+            )?;
+        }
+        if refund > 0 {
+            pay_from_escrow(
+                &self.bounty,
+                self.bounty_token_account.as_deref(),
+                self.reward_mint.as_deref(),
+                &self.creator,
+                self.creator_token_account.as_deref(),
+                &self.token_program,
+                refund,
+            )?;
+        }
+
+        // 3. updating both parties' records, a solver who lost outright forfeits their stake
+        if solver_share > 0 {
+            self.reputation.record_success(
+                solver_amount,
+                self.bounty.reward_mint,
+                self.config.primary_mint,
+                self.bounty.bounty_type,
+                self.config.score_weight(self.bounty.reward),
+            )?;
+            self.leaderboard
+                .load_mut()?
+                .record(self.reputation.agent, self.reputation.score);
+            transfer_stake(
+                &self.submission.to_account_info(),
+                &self.agent,
+                self.submission.stake,
+            )?;
+            self.submission.stake = 0;
+        } else {
+            self.reputation.record_failure()?;
+            slash_stake(
+                &mut self.submission,
+                &self.creator,
+                self.treasury.as_ref(),
+                self.config.stake_slash_treasury_bps,
+            )?;
+        }
+        if outcome == DisputeOutcome::PaySolver {
+            self.creator_profile.disputes_lost =
+                self.creator_profile.disputes_lost.saturating_add(1);
+        } else {
+            Attestor::update(&self.attestor_account, |attestor| {
+                attestor.attestations_disputed = attestor.attestations_disputed.saturating_add(1);
+            })?;
+        }
+
+        // 4. closing out the bounty
+        self.bounty.contributor_pool = contributors_share;
+        if solver_share > 0 {
+            self.bounty.mark_settled()?;
+            self.creator_profile
+                .record_settlement(solver_share, self.bounty.submitted_at_slot)?;
+            self.stats.load_mut()?.record_settled(solver_share)?;
+            // a solver paid anything earned the work, and the badge with it
+            if self.bounty.mint_badge {
+                mint_badge(
+                    &self.bounty,
+                    &self.agent,
+                    &self.arbiter.to_account_info(),
+                    self.badge_mint.as_ref(),
+                    bumps.badge_mint,
+                    self.badge_authority.as_ref(),
+                    bumps.badge_authority,
+                    self.agent_badge_account.as_ref(),
+                    self.badge_token_program.as_ref(),
+                    &self.associated_token_program,
+                    &self.system_program,
+                    self.bounty.claims_made,
+                    solver_share,
+                    self.bounty.settled_at,
+                )?;
+            }
+        } else {
+            self.bounty.status = BountyStatus::Cancelled;
+            self.creator_profile.release_open_slot();
+            self.stats.load_mut()?.record_cancelled()?;
+        }
+        self.creator_profile.emit_stats();
+
+        Ok(())
+    }
+}
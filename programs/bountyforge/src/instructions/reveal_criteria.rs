@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CURRENT_BOUNTY_VERSION, MAX_CRITERIA_LEN};
+use crate::errors::BountyForgeError;
+use crate::events::CriteriaRevealed;
+use crate::state::Bounty;
+
+#[derive(Accounts)]
+pub struct RevealCriteria<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = creator.key() == bounty.creator @ BountyForgeError::UnauthorizedCriteriaReveal
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+}
+
+impl<'info> RevealCriteria<'info> {
+    /// Shows the acceptance criteria the bounty committed to at posting, which
+    /// unlocks reject_solution and resolve_dispute. Revealing again is harmless.
+    pub fn reveal_criteria(&mut self, criteria: Vec<u8>) -> Result<()> {
+        require!(
+            self.bounty.criteria_hash != [0; 32],
+            BountyForgeError::NoCriteriaCommitment
+        );
+        require!(
+            criteria.len() <= MAX_CRITERIA_LEN,
+            BountyForgeError::CriteriaTooLarge
+        );
+
+        // the commitment is over the raw bytes, hashed like solutions are
+        require!(
+            self.bounty.hash_algo.digest(&criteria) == self.bounty.criteria_hash,
+            BountyForgeError::CriteriaHashMismatch
+        );
+
+        self.bounty.criteria_revealed = true;
+
+        emit!(CriteriaRevealed {
+            bounty: self.bounty.key(),
+            criteria_hash: self.bounty.criteria_hash,
+            criteria,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
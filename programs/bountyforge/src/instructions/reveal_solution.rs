@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CURRENT_BOUNTY_VERSION, MAX_SOLUTION_PAYLOAD_LEN};
+use crate::errors::BountyForgeError;
+use crate::state::{Bounty, BountyStatus, Submission};
+
+#[derive(Accounts)]
+pub struct RevealSolution<'info> {
+    pub agent: Signer<'info>,
+
+    // an encrypted solution can also be shown delivered to the arbiter of a dispute
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.status == BountyStatus::Submitted
+            || (bounty.status == BountyStatus::Disputed && bounty.encryption_pubkey.is_some())
+            @ BountyForgeError::BountyNotSubmitted,
+        constraint = bounty.solver == Some(agent.key()) @ BountyForgeError::SolverMismatch
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    /// The agent's submission, only needed when the bounty has an encryption key
+    #[account(
+        seeds = [b"submission", bounty.key().as_ref(), agent.key().as_ref()],
+        bump = submission.bump
+    )]
+    pub submission: Option<Account<'info, Submission>>,
+}
+
+impl<'info> RevealSolution<'info> {
+    pub fn reveal_solution(&mut self, payload: Vec<u8>) -> Result<()> {
+        require!(
+            payload.len() <= MAX_SOLUTION_PAYLOAD_LEN,
+            BountyForgeError::SolutionPayloadTooLarge
+        );
+
+        if self.bounty.encryption_pubkey.is_some() {
+            // the payload is the ciphertext handed to the creator, which only they can read
+            let submission = self
+                .submission
+                .as_ref()
+                .ok_or(BountyForgeError::CiphertextHashMismatch)?;
+            require!(
+                submission.ciphertext_hash == self.bounty.hash_algo.digest(&payload),
+                BountyForgeError::CiphertextHashMismatch
+            );
+        } else {
+            // the submitted hash commits to the payload's raw bytes
+            require!(
+                self.bounty.solution_hash == Some(self.bounty.hash_algo.digest(&payload)),
+                BountyForgeError::SolutionHashMismatch
+            );
+        }
+
+        self.bounty.revealed = true;
+
+        Ok(())
+    }
+}
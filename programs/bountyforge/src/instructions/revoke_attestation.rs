@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BountyForgeError;
+use crate::state::{Attestation, Config};
+
+#[derive(Accounts)]
+pub struct RevokeAttestation<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", attestation.market.as_bytes()],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = attestation.agent == authority.key()
+            || config.verifier == authority.key() @ BountyForgeError::UnauthorizedRevoker
+    )]
+    pub attestation: Account<'info, Attestation>,
+}
+
+impl<'info> RevokeAttestation<'info> {
+    pub fn revoke_attestation(&mut self) -> Result<()> {
+        // the account stays open so the revocation remains visible on-chain;
+        // once a submission has used it, the bounty's own review flow takes over
+        require!(
+            !self.attestation.consumed,
+            BountyForgeError::AttestationAlreadyConsumed
+        );
+
+        self.attestation.revoked = true;
+
+        Ok(())
+    }
+}
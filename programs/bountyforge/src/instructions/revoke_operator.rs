@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::events::OperatorRevoked;
+use crate::state::Operator;
+
+#[derive(Accounts)]
+#[instruction(operator: Pubkey)]
+pub struct RevokeOperator<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    #[account(
+        mut,
+        close = agent,
+        seeds = [b"operator", agent.key().as_ref(), operator.as_ref()],
+        bump = operator_record.bump
+    )]
+    pub operator_record: Account<'info, Operator>,
+}
+
+impl<'info> RevokeOperator<'info> {
+    /// Closes the operator record, refunding its rent to the agent. Submissions
+    /// the operator already made stay with the agent.
+    pub fn revoke_operator(&mut self, operator: Pubkey) -> Result<()> {
+        emit!(OperatorRevoked {
+            agent: self.agent.key(),
+            operator,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
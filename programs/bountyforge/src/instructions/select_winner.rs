@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::events::WinnerSelected;
+use crate::state::{Bounty, BountyStats, BountyStatus, Submission, SubmissionStatus};
+
+#[derive(Accounts)]
+pub struct SelectWinner<'info> {
+    /// The creator, or the settlement authority they named on the bounty
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.can_settle(&creator.key()) @ BountyForgeError::UnauthorizedSettlement,
+        constraint = bounty.competitive @ BountyForgeError::BountyNotCompetitive,
+        constraint = bounty.prizes.is_empty() @ BountyForgeError::BountyHasPrizeSchedule,
+        constraint = bounty.solver.is_none() @ BountyForgeError::WinnerAlreadySelected,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    /// Counters of the bounty's market
+    #[account(mut, seeds = [b"stats", bounty.market.as_bytes()], bump = stats.load()?.bump)]
+    pub stats: AccountLoader<'info, BountyStats>,
+
+    #[account(
+        mut,
+        constraint = submission.bounty == bounty.key() @ BountyForgeError::SubmissionBountyMismatch,
+        constraint = submission.status == SubmissionStatus::Pending @ BountyForgeError::SubmissionNotPending
+    )]
+    pub submission: Account<'info, Submission>,
+}
+
+impl<'info> SelectWinner<'info> {
+    pub fn select_winner(&mut self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        self.bounty.check_appeal(&self.submission.agent, now)?;
+
+        // the winner becomes the bounty's solver, so settlement pays exactly that agent
+        self.bounty.solution_hash = Some(self.submission.solution_hash);
+        self.bounty.solver = Some(self.submission.agent);
+        self.bounty.status = BountyStatus::Submitted;
+        if self.bounty.submitted_at == 0 {
+            self.stats.load_mut()?.record_submitted()?;
+        }
+        self.bounty.mark_submitted()?;
+        self.submission.status = SubmissionStatus::Accepted;
+
+        emit!(WinnerSelected {
+            bounty: self.bounty.key(),
+            agent: self.submission.agent,
+            solution_hash: self.submission.solution_hash,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
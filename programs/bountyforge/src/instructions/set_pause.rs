@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BountyForgeError;
+use crate::state::Config;
+
+#[derive(Accounts)]
+#[instruction(market: String)]
+pub struct SetPause<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", market.as_bytes()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ BountyForgeError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> SetPause<'info> {
+    pub fn set_pause(&mut self, paused: bool) -> Result<()> {
+        // settlement, cancellation and expiry ignore this so escrow can always exit
+        self.config.paused = paused;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::state::Bounty;
+
+#[derive(Accounts)]
+pub struct SetSettlementAuthority<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = creator.key() == bounty.creator @ BountyForgeError::UnauthorizedDelegation
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+}
+
+impl<'info> SetSettlementAuthority<'info> {
+    /// Lets `authority` settle and reject on the creator's behalf, replacing any
+    /// earlier one, or takes that right back with `None`
+    pub fn set_settlement_authority(&mut self, authority: Option<Pubkey>) -> Result<()> {
+        self.bounty.settlement_authority = authority;
+
+        Ok(())
+    }
+}
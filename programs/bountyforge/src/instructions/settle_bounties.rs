@@ -0,0 +1,385 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::{ANCHOR_DISCRIMINATOR, CURRENT_BOUNTY_VERSION, MAX_BATCH_SETTLEMENTS};
+use crate::errors::BountyForgeError;
+use crate::events::BountySettled;
+use crate::state::{
+    Bounty, BountyStats, BountyStatus, Config, CreatorProfile, InsuranceFund, Leaderboard,
+    Reputation, SettlementReceipt, SettlementRecord, Submission, SubmissionStatus,
+};
+use crate::utils::{
+    cover_shortfall, create_pda_account, pay_attestor_share, pay_from_escrow, pay_insurance,
+    transfer_stake,
+};
+
+/// Accounts each item of a batch passes, in this order.
+const ITEM_ACCOUNTS: usize = 9;
+
+#[derive(Accounts)]
+pub struct SettleBounties<'info> {
+    /// The creator, or a settlement authority every bounty in the batch names
+    #[account(mut)]
+    pub settler: Signer<'info>,
+
+    /// Profile of the creator whose bounties are settled
+    #[account(
+        mut,
+        seeds = [b"creator", creator_profile.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    /// Counters of the default market, the only one batches work in
+    #[account(mut, seeds = [b"stats"], bump = stats.load()?.bump)]
+    pub stats: AccountLoader<'info, BountyStats>,
+
+    /// Top agents by score
+    #[account(mut, seeds = [b"leaderboard"], bump = leaderboard.load()?.bump)]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+
+    #[account(seeds = [b"config"], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Protocol treasury wallet, only needed when a fee is charged
+    #[account(mut, address = config.treasury @ BountyForgeError::TreasuryMismatch)]
+    pub treasury: Option<AccountInfo<'info>>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == config.treasury @ BountyForgeError::TreasuryMismatch,
+        constraint = treasury_token_account.mint == reward_mint.key()
+    )]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// The default market's insurance fund for the reward mint, covers a
+    /// slightly short escrow and takes its share of each fee, omitted to do neither
+    #[account(
+        mut,
+        seeds = [b"insurance", config.market.as_bytes(), reward_mint.key().as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Option<Box<Account<'info, InsuranceFund>>>,
+
+    /// The fund's token account, checked against the fund in the handler
+    #[account(mut)]
+    pub insurance_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Reward mint shared by every bounty in the batch
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SettleBounties<'info> {
+    /// Settles submitted token bounties of one creator and one reward mint in
+    /// the default market, each exactly as `settle_bounty` would. Each item
+    /// passes nine remaining accounts, in order: `[bounty, submission,
+    /// reputation, agent, agent_token_account, bounty_token_account,
+    /// settlement_record, settlement_receipt, attestor_token_account]`, the
+    /// record and receipt being created here and the program id standing in
+    /// for an attestor token account left out, which sends their share to the
+    /// treasury. A failing item aborts the whole batch, and its index is
+    /// logged before the error.
+    pub fn settle_bounties(&mut self, accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        require!(
+            !accounts.is_empty() && accounts.len() <= MAX_BATCH_SETTLEMENTS * ITEM_ACCOUNTS,
+            BountyForgeError::InvalidBatchSize
+        );
+
+        // a short last chunk fails settle_one's destructuring
+        for (index, item_accounts) in accounts.chunks(ITEM_ACCOUNTS).enumerate() {
+            self.settle_one(item_accounts)
+                .inspect_err(|_| msg!("settle_bounties: item {} failed", index))?;
+        }
+        self.creator_profile.emit_stats();
+
+        Ok(())
+    }
+
+    fn settle_one(&mut self, accounts: &'info [AccountInfo<'info>]) -> Result<()> {
+        let [bounty_info, submission_info, reputation_info, agent, agent_token_info, escrow_info, record_info, receipt_info, attestor_token_info] =
+            accounts
+        else {
+            return err!(BountyForgeError::BatchAccountMismatch);
+        };
+
+        // 1. the checks SettleBounty makes with account constraints
+        let mut bounty = Account::<Bounty>::try_from(bounty_info)?;
+        require!(
+            bounty.version == CURRENT_BOUNTY_VERSION,
+            BountyForgeError::AccountNeedsMigration
+        );
+        require!(
+            bounty.market == self.config.market,
+            BountyForgeError::MarketMismatch
+        );
+        require!(
+            bounty.status == BountyStatus::Submitted,
+            BountyForgeError::BountyNotSubmitted
+        );
+        require!(
+            bounty.solution_hash.is_some(),
+            BountyForgeError::BountyAlreadySubmitted
+        );
+        require!(
+            bounty.can_settle(&self.settler.key()),
+            BountyForgeError::UnauthorizedSettlement
+        );
+        require_keys_eq!(
+            bounty.creator,
+            self.creator_profile.creator,
+            BountyForgeError::UnauthorizedSettlement
+        );
+        require!(
+            bounty.solver == Some(agent.key()),
+            BountyForgeError::SolverMismatch
+        );
+        require!(
+            !bounty.requires_reveal || bounty.revealed,
+            BountyForgeError::SolutionNotRevealed
+        );
+        // there is no room for a payment receipt per item, those go through settle_bounty
+        require!(
+            !bounty.requires_payment_receipt,
+            BountyForgeError::PaymentReceiptMissing
+        );
+        // nor for splitting the escrow over claims, which settle one at a time
+        require!(!bounty.is_repeatable(), BountyForgeError::RepeatableBounty);
+        // nor for the accounts of a badge, which settle_bounty mints
+        require!(!bounty.mint_badge, BountyForgeError::MissingBadgeAccounts);
+        // Sol bounties record the default mint, so they are turned away here too
+        require_keys_eq!(
+            bounty.reward_mint,
+            self.reward_mint.key(),
+            BountyForgeError::RewardMintMismatch
+        );
+
+        let mut submission = Account::<Submission>::try_from(submission_info)?;
+        require_keys_eq!(
+            submission.bounty,
+            bounty.key(),
+            BountyForgeError::SubmissionBountyMismatch
+        );
+        require_keys_eq!(
+            submission.agent,
+            agent.key(),
+            BountyForgeError::SolverMismatch
+        );
+        // the member accounts of a team would clash with the batch layout
+        require!(
+            submission.team.is_empty(),
+            BountyForgeError::TeamSettlementUnsupported
+        );
+
+        let mut reputation = Account::<Reputation>::try_from(reputation_info)?;
+        require_keys_eq!(
+            reputation.agent,
+            agent.key(),
+            BountyForgeError::ReputationOwnerMismatch
+        );
+
+        let agent_token_account = InterfaceAccount::<TokenAccount>::try_from(agent_token_info)?;
+        require_keys_eq!(agent_token_account.owner, agent.key());
+        require_keys_eq!(agent_token_account.mint, bounty.reward_mint);
+        let mut bounty_token_account = InterfaceAccount::<TokenAccount>::try_from(escrow_info)?;
+        require_keys_eq!(bounty_token_account.owner, bounty.key());
+        require_keys_eq!(bounty_token_account.mint, bounty.reward_mint);
+
+        require!(
+            Clock::get()?.slot >= bounty.challenge_ends_at_slot(),
+            BountyForgeError::ChallengePeriodActive
+        );
+        bounty.check_appeal(&agent.key(), Clock::get()?.unix_timestamp)?;
+        let insurance_topup = cover_shortfall(
+            &bounty,
+            &self.config,
+            self.insurance_fund.as_deref_mut(),
+            self.insurance_token_account.as_deref(),
+            Some(&mut bounty_token_account),
+            Some(&self.reward_mint),
+            &self.token_program,
+        )?;
+
+        let attestor_token_account = if attestor_token_info.key() == crate::ID {
+            None
+        } else {
+            Some(InterfaceAccount::<TokenAccount>::try_from(
+                attestor_token_info,
+            )?)
+        };
+
+        // 2. paying out, leaving what a late solution no longer earns as
+        // surplus for close_bounty
+        let payable = bounty.graded_amount(bounty.escrow_amount)?;
+        let withheld = bounty.escrow_amount.saturating_sub(payable);
+        let (solver_amount, attestor_amount, mut fee_amount) = pay_attestor_share(
+            &bounty,
+            &submission,
+            &self.config,
+            Some(&bounty_token_account),
+            Some(&self.reward_mint),
+            None,
+            attestor_token_account.as_ref(),
+            &self.token_program,
+            payable,
+        )?;
+
+        let now = Clock::get()?.unix_timestamp;
+        if bounty.vesting_duration_seconds > 0 {
+            bounty.vesting_start = now;
+            bounty.vesting_amount = solver_amount;
+        } else {
+            pay_from_escrow(
+                &bounty,
+                Some(&bounty_token_account),
+                Some(&self.reward_mint),
+                agent,
+                Some(&agent_token_account),
+                &self.token_program,
+                solver_amount,
+            )?;
+        }
+        fee_amount = pay_insurance(
+            &bounty,
+            &self.config,
+            self.insurance_fund.as_deref_mut(),
+            self.insurance_token_account.as_deref_mut(),
+            Some(&bounty_token_account),
+            Some(&self.reward_mint),
+            &self.token_program,
+            fee_amount,
+        )?;
+        if fee_amount > 0 {
+            let treasury = self
+                .treasury
+                .as_ref()
+                .ok_or(BountyForgeError::MissingTreasury)?;
+            pay_from_escrow(
+                &bounty,
+                Some(&bounty_token_account),
+                Some(&self.reward_mint),
+                treasury,
+                self.treasury_token_account.as_deref(),
+                &self.token_program,
+                fee_amount,
+            )?;
+        }
+
+        transfer_stake(submission_info, agent, submission.stake)?;
+        submission.stake = 0;
+        submission.status = SubmissionStatus::Accepted;
+
+        // 3. reputation, bounty status and the creator's stats
+        let score_weight = self.config.score_weight(bounty.reward);
+        reputation.record_success(
+            solver_amount,
+            bounty.reward_mint,
+            self.config.primary_mint,
+            bounty.bounty_type,
+            score_weight,
+        )?;
+        self.leaderboard
+            .load_mut()?
+            .record(reputation.agent, reputation.score);
+        bounty.mark_settled()?;
+        self.creator_profile
+            .record_settlement(payable, bounty.submitted_at_slot)?;
+        self.stats.load_mut()?.record_settled(payable)?;
+
+        // 4. the record and receipt settle_bounty would init
+        let (bounty_key, agent_key) = (bounty.key(), agent.key());
+        let claim = bounty.claims_made.to_le_bytes();
+        let (_, record_bump) = Pubkey::find_program_address(
+            &[
+                b"settlement",
+                bounty_key.as_ref(),
+                agent_key.as_ref(),
+                claim.as_ref(),
+            ],
+            &crate::ID,
+        );
+        create_pda_account(
+            &self.settler.to_account_info(),
+            record_info,
+            &[
+                b"settlement",
+                bounty_key.as_ref(),
+                agent_key.as_ref(),
+                claim.as_ref(),
+                &[record_bump],
+            ],
+            ANCHOR_DISCRIMINATOR + SettlementRecord::INIT_SPACE,
+            &self.system_program,
+        )?;
+        SettlementRecord {
+            agent: agent_key,
+            bounty: bounty_key,
+            bounty_id: bounty.id,
+            bounty_type: bounty.bounty_type,
+            amount_earned: solver_amount,
+            score_delta: score_weight,
+            timestamp: now,
+            bump: record_bump,
+        }
+        .try_serialize(&mut &mut record_info.try_borrow_mut_data()?[..])?;
+
+        let (_, receipt_bump) = Pubkey::find_program_address(
+            &[b"receipt", bounty_key.as_ref(), claim.as_ref()],
+            &crate::ID,
+        );
+        create_pda_account(
+            &self.settler.to_account_info(),
+            receipt_info,
+            &[
+                b"receipt",
+                bounty_key.as_ref(),
+                claim.as_ref(),
+                &[receipt_bump],
+            ],
+            ANCHOR_DISCRIMINATOR + SettlementReceipt::INIT_SPACE,
+            &self.system_program,
+        )?;
+        SettlementReceipt {
+            bounty: bounty_key,
+            bounty_id: bounty.id,
+            creator: bounty.creator,
+            solver: agent_key,
+            gross_reward: payable,
+            fee: fee_amount,
+            attestor_fee: attestor_amount,
+            net_paid: solver_amount,
+            mint: bounty.reward_mint,
+            settled_at: bounty.settled_at,
+            settled_by: self.settler.key(),
+            payment_receipt_hash: None,
+            payment_amount: None,
+            claim: bounty.claims_made,
+            market: bounty.market.clone(),
+            bump: receipt_bump,
+        }
+        .try_serialize(&mut &mut receipt_info.try_borrow_mut_data()?[..])?;
+
+        // 5. writing back what Anchor would on exit for declared accounts
+        bounty.exit(&crate::ID)?;
+        submission.exit(&crate::ID)?;
+        reputation.exit(&crate::ID)?;
+
+        emit!(BountySettled {
+            bounty: bounty_key,
+            solver: agent_key,
+            solver_amount,
+            fee_amount,
+            withheld,
+            insurance_topup,
+            streak: Some(reputation.current_streak),
+            reward_mint: bounty.reward_mint,
+            mint_earned: Some(reputation.earned_in(bounty.reward_mint)),
+            payment_amount: None,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
@@ -1,86 +1,454 @@
 use anchor_lang::prelude::*;
-use anchor_spl::token::{transfer, Token, TokenAccount, Transfer};
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_2022::Token2022;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
+use crate::constants::{ANCHOR_DISCRIMINATOR, CURRENT_BOUNTY_VERSION};
 use crate::errors::BountyForgeError;
-use crate::state::{Bounty, BountyStatus, Reputation};
+use crate::events::BountySettled;
+use crate::state::{
+    Bounty, BountyStats, BountyStatus, Config, CreatorProfile, InsuranceFund, Leaderboard,
+    Reputation, RewardCurrency, SettlementReceipt, SettlementRecord, Submission, SubmissionStatus,
+};
+use crate::utils::{
+    cover_shortfall, mint_badge, pay_attestor_share, pay_from_escrow, pay_insurance, pay_solver,
+    transfer_stake, verify_payment_receipt,
+};
 
 #[derive(Accounts)]
 pub struct SettleBounty<'info> {
+    /// The creator, or the settlement authority they named on the bounty
+    #[account(mut)]
     pub creator: Signer<'info>,
 
     #[account(
         mut,
+        seeds = [b"bounty", bounty.original_creator.as_ref(), bounty.id.to_le_bytes().as_ref()],
+        bump = bounty.bump,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
         constraint = bounty.status == BountyStatus::Submitted @ BountyForgeError::BountyNotSubmitted,
         constraint = bounty.solution_hash.is_some() @ BountyForgeError::BountyAlreadySubmitted,
-        constraint = creator.key() == bounty.creator @ BountyForgeError::UnauthorizedSettlement
+        constraint = bounty.can_settle(&creator.key()) @ BountyForgeError::UnauthorizedSettlement,
+        constraint = bounty.solver == Some(agent.key()) @ BountyForgeError::SolverMismatch,
+        constraint = !bounty.requires_reveal || bounty.revealed @ BountyForgeError::SolutionNotRevealed
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator", bounty.creator.as_ref()],
+        bump = creator_profile.bump
     )]
-    pub bounty: Account<'info, Bounty>,
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    /// Counters of the bounty's market
+    #[account(mut, seeds = [b"stats", bounty.market.as_bytes()], bump = stats.load()?.bump)]
+    pub stats: AccountLoader<'info, BountyStats>,
+
+    /// Top agents by score
+    #[account(mut, seeds = [b"leaderboard"], bump = leaderboard.load()?.bump)]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
 
     #[account(
         mut,
+        seeds = [b"rep", agent.key().as_ref()],
+        bump = reputation.bump,
         constraint = reputation.agent == agent.key() @ BountyForgeError::ReputationOwnerMismatch
     )]
     pub reputation: Account<'info, Reputation>,
 
-    /// CHECK: Agent receiving the reward
+    /// CHECK: Agent receiving the reward (must be the bounty's recorded solver)
     #[account(mut)]
     pub agent: AccountInfo<'info>,
 
+    #[account(
+        init,
+        payer = creator,
+        space = ANCHOR_DISCRIMINATOR + SettlementRecord::INIT_SPACE,
+        seeds = [
+            b"settlement",
+            bounty.key().as_ref(),
+            agent.key().as_ref(),
+            bounty.claims_made.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub settlement_record: Account<'info, SettlementRecord>,
+
+    /// Audit record of the payout, kept after the bounty is closed
+    #[account(
+        init,
+        payer = creator,
+        space = ANCHOR_DISCRIMINATOR + SettlementReceipt::INIT_SPACE,
+        seeds = [b"receipt", bounty.key().as_ref(), bounty.claims_made.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub settlement_receipt: Box<Account<'info, SettlementReceipt>>,
+
+    #[account(
+        mut,
+        seeds = [b"submission", bounty.key().as_ref(), agent.key().as_ref()],
+        bump = submission.bump
+    )]
+    pub submission: Box<Account<'info, Submission>>,
+
+    /// The solver's ATA, created if missing so settlement can't be held up, omitted for Sol bounties
+    #[account(
+        init_if_needed,
+        payer = creator,
+        associated_token::mint = reward_mint,
+        associated_token::authority = agent,
+        associated_token::token_program = token_program
+    )]
+    pub agent_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Escrow ATA, omitted for Sol bounties
     #[account(
         mut,
-        constraint = agent_token_account.owner == agent.key(),
-        constraint = agent_token_account.mint == usdc_mint.key()
+        associated_token::mint = reward_mint,
+        associated_token::authority = bounty,
+        associated_token::token_program = token_program
     )]
-    pub agent_token_account: Account<'info, TokenAccount>,
+    pub bounty_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(seeds = [b"config", bounty.market.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
 
+    /// Receives what a late solution no longer earns, omitted to leave it for close_bounty
     #[account(
         mut,
-        constraint = bounty_token_account.owner == bounty.key(),
-        constraint = bounty_token_account.mint == usdc_mint.key()
+        constraint = creator_token_account.owner == bounty.creator @ BountyForgeError::UnauthorizedSettlement,
+        constraint = creator_token_account.mint == bounty.reward_mint
     )]
-    pub bounty_token_account: Account<'info, TokenAccount>,
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    /// CHECK: USDC mint address (validated by token accounts)
-    pub usdc_mint: AccountInfo<'info>,
+    /// CHECK: Protocol treasury wallet, only needed when a fee is charged
+    #[account(mut, address = config.treasury @ BountyForgeError::TreasuryMismatch)]
+    pub treasury: Option<AccountInfo<'info>>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == config.treasury @ BountyForgeError::TreasuryMismatch,
+        constraint = treasury_token_account.mint == bounty.reward_mint
+    )]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// CHECK: Attestor recorded on the submission, paid on Sol bounties, omitted to
+    /// send their share to the treasury
+    #[account(mut, address = submission.attestor @ BountyForgeError::NotSubmissionAttestor)]
+    pub attestor: Option<AccountInfo<'info>>,
+
+    /// The attestor's token account, paid on token bounties, omitted to send
+    /// their share to the treasury
+    #[account(
+        mut,
+        constraint = attestor_token_account.owner == submission.attestor @ BountyForgeError::NotSubmissionAttestor,
+        constraint = attestor_token_account.mint == bounty.reward_mint
+    )]
+    pub attestor_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    pub token_program: Program<'info, Token>,
+    /// The market's insurance fund for the reward mint, covers a slightly short
+    /// escrow and takes its share of the fee, omitted to do neither
+    #[account(
+        mut,
+        seeds = [b"insurance", bounty.market.as_bytes(), bounty.reward_mint.as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Option<Box<Account<'info, InsuranceFund>>>,
+
+    /// The fund's token account, checked against the fund in the handler
+    #[account(mut)]
+    pub insurance_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// CHECK: Instructions sysvar, only needed when the bounty requires a payment receipt
+    /// The gateway's ed25519 instruction right before this one is read from it in the handler
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: Option<AccountInfo<'info>>,
+
+    /// CHECK: Badge mint, created in the handler, only needed when the bounty mints badges
+    #[account(
+        mut,
+        seeds = [b"badge", bounty.key().as_ref(), bounty.claims_made.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub badge_mint: Option<AccountInfo<'info>>,
+
+    /// CHECK: Mint and metadata authority of every badge
+    #[account(seeds = [b"badge_authority"], bump)]
+    pub badge_authority: Option<AccountInfo<'info>>,
+
+    /// CHECK: The agent's Token-2022 ATA for the badge, created in the handler
+    #[account(mut)]
+    pub agent_badge_account: Option<AccountInfo<'info>>,
+
+    pub badge_token_program: Option<Program<'info, Token2022>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+
+    pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> SettleBounty<'info> {
-    pub fn settle_bounty(&mut self) -> Result<()> {
-        // 1. transfering USDC from bounty PDA to agent token account
-        let bounty_id_bytes = self.bounty.id.to_le_bytes();
-        let bounty_seeds = &[b"bounty", bounty_id_bytes.as_ref(), &[self.bounty.bump]];
-        let bounty_signer = &[&bounty_seeds[..]];
-
-        let cpi_program = self.token_program.to_account_info();
-        let cpi_accounts = Transfer {
-            from: self.bounty_token_account.to_account_info(),
-            to: self.agent_token_account.to_account_info(),
-            authority: self.bounty.to_account_info(),
+    /// A team submission's members are paid from `member_accounts`, see `pay_solver`.
+    pub fn settle_bounty(
+        &mut self,
+        bumps: &SettleBountyBumps,
+        member_accounts: &'info [AccountInfo<'info>],
+    ) -> Result<()> {
+        // leaving time for anyone to flag a bogus solution before it is paid
+        require!(
+            Clock::get()?.slot >= self.bounty.challenge_ends_at_slot(),
+            BountyForgeError::ChallengePeriodActive
+        );
+        // nor paying anyone else while a rejected agent's appeal is pending
+        self.bounty
+            .check_appeal(&self.agent.key(), Clock::get()?.unix_timestamp)?;
+
+        // the vault must really hold what the bounty records, any surplus stays for close_bounty
+        let insurance_topup = cover_shortfall(
+            &self.bounty,
+            &self.config,
+            self.insurance_fund.as_deref_mut(),
+            self.insurance_token_account.as_deref(),
+            self.bounty_token_account.as_deref_mut(),
+            self.reward_mint.as_deref(),
+            &self.token_program,
+        )?;
+
+        // the creator's gateway must have been paid for verifying this solution
+        let payment_receipt = if self.bounty.requires_payment_receipt {
+            let solution_hash = self
+                .bounty
+                .solution_hash
+                .ok_or(BountyForgeError::BountyNotSubmitted)?;
+            Some(verify_payment_receipt(
+                self.instructions_sysvar.as_ref(),
+                self.config.payment_gateway,
+                self.config.min_verification_fee,
+                &self.bounty.key(),
+                &solution_hash,
+            )?)
+        } else {
+            None
         };
 
-        let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, bounty_signer);
+        // 1. grading a late solution, then splitting what it earns between the
+        // agent, their attestor and the protocol treasury
+        let claim_amount = self.bounty.claim_amount();
+        let gross_reward = self.bounty.graded_amount(claim_amount)?;
+        let withheld = claim_amount.saturating_sub(gross_reward);
+        let (solver_amount, attestor_amount, fee_amount) = pay_attestor_share(
+            &self.bounty,
+            &self.submission,
+            &self.config,
+            self.bounty_token_account.as_deref(),
+            self.reward_mint.as_deref(),
+            self.attestor.as_ref(),
+            self.attestor_token_account.as_deref(),
+            &self.token_program,
+            gross_reward,
+        )?;
+
+        // a vesting reward stays in escrow for the solver to pull with claim_vested
+        let now = Clock::get()?.unix_timestamp;
+        if self.bounty.vesting_duration_seconds > 0 {
+            self.bounty.vesting_start = now;
+            self.bounty.vesting_amount = solver_amount;
+        } else {
+            pay_solver(
+                &self.bounty,
+                &self.submission.team,
+                self.bounty_token_account.as_deref(),
+                self.reward_mint.as_deref(),
+                &self.agent,
+                self.agent_token_account.as_deref(),
+                member_accounts,
+                &self.token_program,
+                solver_amount,
+            )?;
+        }
+
+        // the insurance fund's cut comes out of the protocol fee, never the solver's share
+        let fee_amount = pay_insurance(
+            &self.bounty,
+            &self.config,
+            self.insurance_fund.as_deref_mut(),
+            self.insurance_token_account.as_deref_mut(),
+            self.bounty_token_account.as_deref(),
+            self.reward_mint.as_deref(),
+            &self.token_program,
+            fee_amount,
+        )?;
+
+        // a zero fee skips the second transfer entirely
+        if fee_amount > 0 {
+            let treasury = self
+                .treasury
+                .as_ref()
+                .ok_or(BountyForgeError::MissingTreasury)?;
+            pay_from_escrow(
+                &self.bounty,
+                self.bounty_token_account.as_deref(),
+                self.reward_mint.as_deref(),
+                treasury,
+                self.treasury_token_account.as_deref(),
+                &self.token_program,
+                fee_amount,
+            )?;
+        }
+
+        // what the late solution lost goes back to the creator now if it can,
+        // otherwise it stays in escrow as surplus for close_bounty
+        if withheld > 0 {
+            self.refund_withheld(withheld)?;
+        }
 
-        transfer(cpi_ctx, self.bounty.reward)?;
+        // the agent's stake goes back along with the reward
+        transfer_stake(
+            &self.submission.to_account_info(),
+            &self.agent,
+            self.submission.stake,
+        )?;
+        self.submission.stake = 0;
+        self.submission.status = SubmissionStatus::Accepted;
 
         // 2. updating reputation
-        self.reputation.successful_bounties = self
-            .reputation
-            .successful_bounties
+        self.reputation.record_success(
+            solver_amount,
+            self.bounty.reward_mint,
+            self.config.primary_mint,
+            self.bounty.bounty_type,
+            self.config.score_weight(self.bounty.reward),
+        )?;
+        self.leaderboard
+            .load_mut()?
+            .record(self.reputation.agent, self.reputation.score);
+
+        // 3. updating bounty status, a repeatable bounty reopens until its last claim
+        let claim = self.bounty.claims_made;
+        self.bounty.claims_made = claim
             .checked_add(1)
-            .ok_or(BountyForgeError::ReputationOverflow)?;
+            .ok_or(BountyForgeError::InvalidClaimSchedule)?;
+        let final_claim = self.bounty.claims_made >= self.bounty.max_claims;
+        if final_claim {
+            self.bounty.mark_settled()?;
+        } else {
+            self.bounty.escrow_amount = self
+                .bounty
+                .escrow_amount
+                .checked_sub(claim_amount)
+                .ok_or(BountyForgeError::EscrowUnderfunded)?;
+            self.bounty.status = BountyStatus::Open;
+            self.bounty.solution_hash = None;
+            self.bounty.solver = None;
+            self.bounty.revealed = false;
+        }
+
+        // 4. freeing the creator's open bounty slot once nothing is left to claim
+        if final_claim {
+            self.creator_profile
+                .record_settlement(gross_reward, self.bounty.submitted_at_slot)?;
+            self.stats.load_mut()?.record_settled(gross_reward)?;
+        } else {
+            self.creator_profile.record_payout(gross_reward);
+            self.stats.load_mut()?.record_payout(gross_reward)?;
+        }
+        self.creator_profile.emit_stats();
+
+        // 5. recording the settlement for the agent's history
+        // the record attributes the bounty's whole score weight to it, part of it credited at submission
+        self.settlement_record.set_inner(SettlementRecord {
+            agent: self.agent.key(),
+            bounty: self.bounty.key(),
+            bounty_id: self.bounty.id,
+            bounty_type: self.bounty.bounty_type,
+            amount_earned: solver_amount,
+            score_delta: self.config.score_weight(self.bounty.reward),
+            timestamp: now,
+            bump: bumps.settlement_record,
+        });
+
+        // 6. writing the receipt, which outlives the bounty for audits
+        self.settlement_receipt.set_inner(SettlementReceipt {
+            bounty: self.bounty.key(),
+            bounty_id: self.bounty.id,
+            creator: self.bounty.creator,
+            solver: self.agent.key(),
+            gross_reward,
+            fee: fee_amount,
+            attestor_fee: attestor_amount,
+            net_paid: solver_amount,
+            mint: self.bounty.reward_mint,
+            settled_at: now,
+            settled_by: self.creator.key(),
+            payment_receipt_hash: payment_receipt.map(|(hash, _)| hash),
+            payment_amount: payment_receipt.map(|(_, amount)| amount),
+            claim,
+            market: self.bounty.market.clone(),
+            bump: bumps.settlement_receipt,
+        });
 
-        self.reputation.total_earned = self
-            .reputation
-            .total_earned
-            .checked_add(self.bounty.reward)
-            .ok_or(BountyForgeError::ReputationOverflow)?;
+        // 7. minting the solver's badge, in this transaction so a failed mint undoes the payout
+        if self.bounty.mint_badge {
+            mint_badge(
+                &self.bounty,
+                &self.agent,
+                &self.creator.to_account_info(),
+                self.badge_mint.as_ref(),
+                bumps.badge_mint,
+                self.badge_authority.as_ref(),
+                bumps.badge_authority,
+                self.agent_badge_account.as_ref(),
+                self.badge_token_program.as_ref(),
+                &self.associated_token_program,
+                &self.system_program,
+                claim,
+                gross_reward,
+                now,
+            )?;
+        }
 
-        // 3. updating bounty status
-        self.bounty.status = BountyStatus::Settled;
+        emit!(BountySettled {
+            bounty: self.bounty.key(),
+            solver: self.agent.key(),
+            solver_amount,
+            fee_amount,
+            withheld,
+            insurance_topup,
+            streak: Some(self.reputation.current_streak),
+            reward_mint: self.bounty.reward_mint,
+            mint_earned: Some(self.reputation.earned_in(self.bounty.reward_mint)),
+            payment_amount: payment_receipt.map(|(_, amount)| amount),
+            timestamp: now,
+        });
 
         Ok(())
     }
+
+    /// Refunds the part of a claim a late solution no longer earns, to the
+    /// creator's token account if passed, or for Sol bounties to the creator
+    /// when they are the one settling.
+    fn refund_withheld(&self, amount: u64) -> Result<()> {
+        let refund_now = match self.bounty.reward_currency {
+            RewardCurrency::Spl => self.creator_token_account.is_some(),
+            RewardCurrency::Sol => self.creator.key() == self.bounty.creator,
+        };
+        if !refund_now {
+            return Ok(());
+        }
+        pay_from_escrow(
+            &self.bounty,
+            self.bounty_token_account.as_deref(),
+            self.reward_mint.as_deref(),
+            &self.creator.to_account_info(),
+            self.creator_token_account.as_deref(),
+            &self.token_program,
+            amount,
+        )
+    }
 }
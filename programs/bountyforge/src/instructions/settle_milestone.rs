@@ -0,0 +1,200 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::events::MilestoneSettled;
+use crate::state::{
+    Bounty, BountyStats, BountyStatus, Config, CreatorProfile, Leaderboard, MilestoneStatus,
+    Reputation,
+};
+use crate::utils::{escrow_balance, pay_from_escrow, split_fee};
+
+#[derive(Accounts)]
+pub struct SettleMilestone<'info> {
+    /// The creator, or the settlement authority they named on the bounty
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = !bounty.milestones.is_empty() @ BountyForgeError::NotMilestoneBounty,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen,
+        constraint = bounty.can_settle(&creator.key()) @ BountyForgeError::UnauthorizedSettlement,
+        constraint = bounty.solver == Some(agent.key()) @ BountyForgeError::SolverMismatch
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator", bounty.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    /// Counters of the bounty's market
+    #[account(mut, seeds = [b"stats", bounty.market.as_bytes()], bump = stats.load()?.bump)]
+    pub stats: AccountLoader<'info, BountyStats>,
+
+    /// Top agents by score
+    #[account(mut, seeds = [b"leaderboard"], bump = leaderboard.load()?.bump)]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+
+    #[account(
+        mut,
+        seeds = [b"rep", agent.key().as_ref()],
+        bump = reputation.bump,
+        constraint = reputation.agent == agent.key() @ BountyForgeError::ReputationOwnerMismatch
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    /// CHECK: Agent receiving the payout (must be the bounty's recorded solver)
+    #[account(mut)]
+    pub agent: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = agent_token_account.owner == agent.key(),
+        constraint = agent_token_account.mint == bounty.reward_mint
+    )]
+    pub agent_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(
+        mut,
+        constraint = bounty_token_account.owner == bounty.key(),
+        constraint = bounty_token_account.mint == bounty.reward_mint
+    )]
+    pub bounty_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(seeds = [b"config", bounty.market.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Protocol treasury wallet, only needed when a fee is charged
+    #[account(mut, address = config.treasury @ BountyForgeError::TreasuryMismatch)]
+    pub treasury: Option<AccountInfo<'info>>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == config.treasury @ BountyForgeError::TreasuryMismatch,
+        constraint = treasury_token_account.mint == bounty.reward_mint
+    )]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SettleMilestone<'info> {
+    pub fn settle_milestone(&mut self, index: u8) -> Result<()> {
+        let index = index as usize;
+        let milestone = *self
+            .bounty
+            .milestones
+            .get(index)
+            .ok_or(BountyForgeError::InvalidMilestoneIndex)?;
+        require!(
+            milestone.status != MilestoneStatus::Paid,
+            BountyForgeError::MilestoneAlreadyPaid
+        );
+        require!(
+            self.bounty.milestones[..index]
+                .iter()
+                .all(|earlier| earlier.status == MilestoneStatus::Paid),
+            BountyForgeError::MilestoneOutOfOrder
+        );
+        require!(
+            milestone.status == MilestoneStatus::Submitted,
+            BountyForgeError::MilestoneNotSubmitted
+        );
+        require!(
+            Clock::get()?.slot >= self.bounty.challenge_ends_at_slot(),
+            BountyForgeError::ChallengePeriodActive
+        );
+
+        // the last milestone sweeps whatever is left, covering increases and any transfer fee
+        let last = index + 1 == self.bounty.milestones.len();
+        let amount = if last {
+            self.bounty.escrow_amount
+        } else {
+            milestone.amount.min(self.bounty.escrow_amount)
+        };
+        require!(
+            escrow_balance(&self.bounty, self.bounty_token_account.as_deref())? >= amount,
+            BountyForgeError::EscrowUnderfunded
+        );
+
+        // 1. splitting this milestone's payout between the agent and the protocol treasury
+        let (solver_amount, fee_amount) = split_fee(amount, self.config.fee_bps)?;
+
+        pay_from_escrow(
+            &self.bounty,
+            self.bounty_token_account.as_deref(),
+            self.reward_mint.as_deref(),
+            &self.agent,
+            self.agent_token_account.as_deref(),
+            &self.token_program,
+            solver_amount,
+        )?;
+
+        if fee_amount > 0 {
+            let treasury = self
+                .treasury
+                .as_ref()
+                .ok_or(BountyForgeError::MissingTreasury)?;
+            pay_from_escrow(
+                &self.bounty,
+                self.bounty_token_account.as_deref(),
+                self.reward_mint.as_deref(),
+                treasury,
+                self.treasury_token_account.as_deref(),
+                &self.token_program,
+                fee_amount,
+            )?;
+        }
+
+        // 2. marking the milestone paid, the bounty settles with its last one
+        self.bounty.escrow_amount = self.bounty.escrow_amount.saturating_sub(amount);
+        self.bounty.milestones[index].status = MilestoneStatus::Paid;
+        if last {
+            self.reputation.record_success(
+                solver_amount,
+                self.bounty.reward_mint,
+                self.config.primary_mint,
+                self.bounty.bounty_type,
+                self.config.score_weight(self.bounty.reward),
+            )?;
+            self.leaderboard
+                .load_mut()?
+                .record(self.reputation.agent, self.reputation.score);
+            self.bounty.mark_settled()?;
+            self.creator_profile
+                .record_settlement(amount, self.bounty.submitted_at_slot)?;
+            self.stats.load_mut()?.record_settled(amount)?;
+        } else {
+            self.reputation.record_earnings(
+                solver_amount,
+                self.bounty.reward_mint,
+                self.config.primary_mint,
+            )?;
+            self.creator_profile.record_payout(amount);
+            self.stats.load_mut()?.record_payout(amount)?;
+        }
+        self.creator_profile.emit_stats();
+
+        emit!(MilestoneSettled {
+            bounty: self.bounty.key(),
+            solver: self.agent.key(),
+            index: index as u8,
+            solver_amount,
+            fee_amount,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::events::BountySettled;
+use crate::state::{
+    Bounty, BountyStats, BountyStatus, Config, CreatorProfile, RewardCurrency, Submission,
+    SubmissionStatus,
+};
+use crate::utils::{pay_from_escrow, split_fee, transfer_stake};
+
+#[derive(Accounts)]
+pub struct SettlePrizes<'info> {
+    /// The creator, or the settlement authority they named on the bounty
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = bounty.can_settle(&creator.key()) @ BountyForgeError::UnauthorizedSettlement,
+        constraint = !bounty.prizes.is_empty() @ BountyForgeError::NoPrizeSchedule,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        mut,
+        seeds = [b"creator", bounty.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, CreatorProfile>,
+
+    /// Counters of the bounty's market
+    #[account(mut, seeds = [b"stats", bounty.market.as_bytes()], bump = stats.load()?.bump)]
+    pub stats: AccountLoader<'info, BountyStats>,
+
+    #[account(
+        mut,
+        constraint = bounty_token_account.owner == bounty.key(),
+        constraint = bounty_token_account.mint == bounty.reward_mint
+    )]
+    pub bounty_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(seeds = [b"config", bounty.market.as_bytes()], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Protocol treasury wallet, only needed when a fee is charged
+    #[account(mut, address = config.treasury @ BountyForgeError::TreasuryMismatch)]
+    pub treasury: Option<AccountInfo<'info>>,
+
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == config.treasury @ BountyForgeError::TreasuryMismatch,
+        constraint = treasury_token_account.mint == bounty.reward_mint
+    )]
+    pub treasury_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SettlePrizes<'info> {
+    /// Pays each place of the prize schedule, in order, to the winners passed
+    /// as remaining accounts: `[submission, agent]` per place, followed by the
+    /// agent's token account for token bounties.
+    pub fn settle_prizes(&mut self, winners: &'info [AccountInfo<'info>]) -> Result<()> {
+        let stride = match self.bounty.reward_currency {
+            RewardCurrency::Spl => 3,
+            RewardCurrency::Sol => 2,
+        };
+        require!(
+            winners.len() == self.bounty.prizes.len() * stride,
+            BountyForgeError::WinnerCountMismatch
+        );
+
+        let escrow = self.bounty.escrow_amount;
+        let now = Clock::get()?.unix_timestamp;
+        let mut paid_agents: Vec<Pubkey> = Vec::with_capacity(self.bounty.prizes.len());
+        let mut paid = 0u64;
+        let mut total_fee = 0u64;
+
+        for (place, accounts) in winners.chunks(stride).enumerate() {
+            let (submission_info, agent) = (&accounts[0], &accounts[1]);
+
+            // 1. the place goes to a pending submission on this bounty
+            require_keys_eq!(
+                *submission_info.owner,
+                crate::ID,
+                BountyForgeError::SubmissionBountyMismatch
+            );
+            let mut submission = {
+                let data = submission_info.try_borrow_data()?;
+                Submission::try_deserialize(&mut &data[..])?
+            };
+            require_keys_eq!(
+                submission.bounty,
+                self.bounty.key(),
+                BountyForgeError::SubmissionBountyMismatch
+            );
+            require!(
+                submission.status == SubmissionStatus::Pending,
+                BountyForgeError::SubmissionNotPending
+            );
+            require_keys_eq!(
+                submission.agent,
+                agent.key(),
+                BountyForgeError::SolverMismatch
+            );
+            require!(
+                !paid_agents.contains(&submission.agent),
+                BountyForgeError::DuplicateWinner
+            );
+            paid_agents.push(submission.agent);
+            self.bounty.check_appeal(&submission.agent, now)?;
+
+            // 2. scaling the place to what escrow holds, the last place takes the remainder
+            let amount = if place + 1 == self.bounty.prizes.len() {
+                escrow.saturating_sub(paid)
+            } else {
+                (self.bounty.prizes[place] as u128)
+                    .checked_mul(escrow as u128)
+                    .and_then(|scaled| scaled.checked_div(self.bounty.reward as u128))
+                    .and_then(|share| u64::try_from(share).ok())
+                    .ok_or(BountyForgeError::FeeCalculationOverflow)?
+            };
+            paid = paid
+                .checked_add(amount)
+                .ok_or(BountyForgeError::FeeCalculationOverflow)?;
+
+            let (solver_amount, fee_amount) = split_fee(amount, self.config.fee_bps)?;
+            total_fee = total_fee.saturating_add(fee_amount);
+
+            let agent_token_account = match self.bounty.reward_currency {
+                RewardCurrency::Spl => {
+                    let token_account = InterfaceAccount::<TokenAccount>::try_from(&accounts[2])?;
+                    require_keys_eq!(token_account.owner, agent.key());
+                    require_keys_eq!(token_account.mint, self.bounty.reward_mint);
+                    Some(token_account)
+                }
+                RewardCurrency::Sol => None,
+            };
+            pay_from_escrow(
+                &self.bounty,
+                self.bounty_token_account.as_deref(),
+                self.reward_mint.as_deref(),
+                agent,
+                agent_token_account.as_ref(),
+                &self.token_program,
+                solver_amount,
+            )?;
+
+            transfer_stake(submission_info, agent, submission.stake)?;
+            submission.stake = 0;
+            submission.status = SubmissionStatus::Accepted;
+            submission.try_serialize(&mut &mut submission_info.try_borrow_mut_data()?[..])?;
+
+            emit!(BountySettled {
+                bounty: self.bounty.key(),
+                solver: agent.key(),
+                solver_amount,
+                fee_amount,
+                withheld: 0,
+                insurance_topup: 0,
+                streak: None,
+                reward_mint: self.bounty.reward_mint,
+                mint_earned: None,
+                payment_amount: None,
+                timestamp: now,
+            });
+        }
+
+        // 3. the protocol fee across all places goes out in one transfer
+        if total_fee > 0 {
+            let treasury = self
+                .treasury
+                .as_ref()
+                .ok_or(BountyForgeError::MissingTreasury)?;
+            pay_from_escrow(
+                &self.bounty,
+                self.bounty_token_account.as_deref(),
+                self.reward_mint.as_deref(),
+                treasury,
+                self.treasury_token_account.as_deref(),
+                &self.token_program,
+                total_fee,
+            )?;
+        }
+
+        // 4. closing out the bounty
+        self.bounty.mark_settled()?;
+        self.creator_profile
+            .record_settlement(paid, self.bounty.submitted_at_slot)?;
+        self.stats.load_mut()?.record_settled(paid)?;
+        self.creator_profile.emit_stats();
+
+        Ok(())
+    }
+}
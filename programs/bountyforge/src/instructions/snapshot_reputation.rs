@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::ANCHOR_DISCRIMINATOR;
+use crate::errors::BountyForgeError;
+use crate::events::ReputationSnapshotted;
+use crate::state::{Config, ReputationSnapshot};
+
+#[derive(Accounts)]
+#[instruction(market: String, epoch: u64)]
+pub struct SnapshotReputation<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", market.as_bytes()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ BountyForgeError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = ANCHOR_DISCRIMINATOR + ReputationSnapshot::INIT_SPACE,
+        seeds = [b"snapshot", market.as_bytes(), epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub snapshot: Account<'info, ReputationSnapshot>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SnapshotReputation<'info> {
+    /// Publishes an epoch's Merkle root. Each epoch is written once, so a
+    /// root other programs already rely on can't be swapped out.
+    pub fn snapshot_reputation(
+        &mut self,
+        epoch: u64,
+        root: [u8; 32],
+        leaf_count: u32,
+        bumps: &SnapshotReputationBumps,
+    ) -> Result<()> {
+        require!(
+            root != [0; 32] && leaf_count > 0,
+            BountyForgeError::InvalidSnapshot
+        );
+        // init_if_needed so a second call fails with our own error instead of a system one
+        require!(
+            self.snapshot.root == [0; 32],
+            BountyForgeError::SnapshotAlreadyExists
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        self.snapshot.set_inner(ReputationSnapshot {
+            epoch,
+            root,
+            leaf_count,
+            created_at: now,
+            bump: bumps.snapshot,
+        });
+
+        emit!(ReputationSnapshotted {
+            epoch,
+            root,
+            leaf_count,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
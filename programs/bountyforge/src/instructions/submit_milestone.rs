@@ -0,0 +1,198 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{CURRENT_BOUNTY_VERSION, MAX_ATTESTATION_AGE_SECONDS, SCORE_PER_SUBMISSION},
+    errors::BountyForgeError,
+    events::MilestoneSubmitted,
+    state::{
+        AgentFlag, Application, ApplicationStatus, Attestation, Attestor, Bounty, BountyStats,
+        BountyStatus, Config, Leaderboard, MilestoneStatus, Reputation,
+    },
+};
+
+#[derive(Accounts)]
+pub struct SubmitMilestone<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = !bounty.milestones.is_empty() @ BountyForgeError::NotMilestoneBounty,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen,
+        constraint = bounty.solver.is_none() || bounty.solver == Some(agent.key()) @ BountyForgeError::SolverMismatch,
+        constraint = bounty.assignee.is_none() || bounty.assignee == Some(agent.key()) @ BountyForgeError::NotAssignedAgent,
+        constraint = bounty.is_allowlisted(&agent.key()) @ BountyForgeError::AgentNotAllowlisted
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"attestation",
+            agent.key().as_ref(),
+            bounty.key().as_ref(),
+            attestation.solution_id.to_le_bytes().as_ref()
+        ],
+        bump = attestation.bump,
+        constraint = attestation.agent == agent.key() @ BountyForgeError::AttestationOwnerMismatch,
+        constraint = !attestation.consumed @ BountyForgeError::AttestationAlreadyConsumed,
+        constraint = attestation.bounty == bounty.key() @ BountyForgeError::AttestationBountyMismatch,
+        constraint = attestation.verified @ BountyForgeError::AttestationNotVerified,
+        constraint = !attestation.revoked @ BountyForgeError::AttestationRevoked,
+        constraint = attestation.hash_algo == bounty.hash_algo @ BountyForgeError::HashAlgoMismatch
+    )]
+    pub attestation: Account<'info, Attestation>,
+
+    /// Created beforehand with init_reputation
+    #[account(
+        mut,
+        seeds = [b"rep", agent.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    /// Counters of the bounty's market
+    #[account(mut, seeds = [b"stats", bounty.market.as_bytes()], bump = stats.load()?.bump)]
+    pub stats: AccountLoader<'info, BountyStats>,
+
+    /// Top agents by score
+    #[account(mut, seeds = [b"leaderboard"], bump = leaderboard.load()?.bump)]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+
+    /// CHECK: The agent's AgentFlag PDA, which usually doesn't exist
+    /// Not optional so a banned agent can't leave it out, checked in the handler
+    #[account(
+        seeds = [b"flag", bounty.market.as_bytes(), agent.key().as_ref()],
+        bump
+    )]
+    pub agent_flag: UncheckedAccount<'info>,
+
+    /// CHECK: The agent's Attestor PDA, which attest_solution required. Checked and counted in the handler
+    #[account(
+        mut,
+        seeds = [b"attestor", bounty.market.as_bytes(), agent.key().as_ref()],
+        bump
+    )]
+    pub attestor_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config", bounty.market.as_bytes()],
+        bump = config.bump,
+        constraint = !config.paused @ BountyForgeError::ProgramPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The agent's application, only required if the bounty was posted with
+    /// `requires_application`, in which case it must have been accepted
+    #[account(
+        constraint = application.bounty == bounty.key() @ BountyForgeError::ApplicationBountyMismatch,
+        constraint = application.agent == agent.key() @ BountyForgeError::ApplicationNotAccepted
+    )]
+    pub application: Option<Box<Account<'info, Application>>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitMilestone<'info> {
+    pub fn submit_milestone(&mut self, index: u8, solution_hash: [u8; 32]) -> Result<()> {
+        require!(
+            self.attestation.solution_hash == solution_hash,
+            BountyForgeError::SolutionHashMismatch
+        );
+        require!(
+            !AgentFlag::is_banned(&self.agent_flag)?,
+            BountyForgeError::AgentBanned
+        );
+        self.config
+            .check_not_self_dealing(&self.bounty.creator, &self.agent.key())?;
+
+        if self.bounty.requires_application {
+            let accepted = self
+                .application
+                .as_ref()
+                .is_some_and(|application| application.status == ApplicationStatus::Accepted);
+            require!(accepted, BountyForgeError::ApplicationNotAccepted);
+        }
+        require!(
+            self.reputation.successful_bounties >= self.bounty.min_reputation,
+            BountyForgeError::InsufficientReputation
+        );
+        // the agent's own registration issued the attestation, so its record is the one judged
+        if self.bounty.min_attestor_track_record > 0 {
+            let track_record = Attestor::load(&self.attestor_account)?
+                .map_or(0, |attestor| attestor.track_record());
+            require!(
+                track_record >= self.bounty.min_attestor_track_record,
+                BountyForgeError::AttestorTrackRecordTooLow
+            );
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now <= self.bounty.deadline,
+            BountyForgeError::BountyDeadlinePassed
+        );
+        require!(
+            self.attestation.timestamp <= now,
+            BountyForgeError::AttestationFromFuture
+        );
+        let max_attestation_age = self
+            .bounty
+            .max_attestation_age
+            .unwrap_or(MAX_ATTESTATION_AGE_SECONDS);
+        require!(
+            now.saturating_sub(self.attestation.timestamp) <= max_attestation_age,
+            BountyForgeError::AttestationExpired
+        );
+
+        // 1. recording the deliverable against its milestone, the bounty stays open for the rest
+        let milestone = self
+            .bounty
+            .milestones
+            .get_mut(index as usize)
+            .ok_or(BountyForgeError::InvalidMilestoneIndex)?;
+        match milestone.status {
+            MilestoneStatus::Pending => {}
+            MilestoneStatus::Submitted => return err!(BountyForgeError::BountyAlreadySubmitted),
+            MilestoneStatus::Paid => return err!(BountyForgeError::MilestoneCompleted),
+        }
+        milestone.solution_hash = Some(solution_hash);
+        milestone.status = MilestoneStatus::Submitted;
+        self.attestation.consumed = true;
+        Attestor::update(&self.attestor_account, |attestor| {
+            attestor.attestations_consumed = attestor.attestations_consumed.saturating_add(1);
+        })?;
+
+        // 2. the first submission makes this agent the bounty's only solver
+        let first_milestone = self.bounty.solver.is_none();
+        self.bounty.solver = Some(self.agent.key());
+        if first_milestone {
+            self.stats.load_mut()?.record_submitted()?;
+        }
+        self.bounty.mark_submitted()?;
+
+        // 3. updating reputation, scored once per bounty rather than per milestone
+        if first_milestone {
+            self.reputation.score = self
+                .reputation
+                .score
+                .checked_add(SCORE_PER_SUBMISSION)
+                .ok_or(BountyForgeError::ReputationScoreOverflow)?;
+            self.leaderboard
+                .load_mut()?
+                .record(self.reputation.agent, self.reputation.score);
+        }
+        self.reputation.last_active_ts = now;
+
+        emit!(MilestoneSubmitted {
+            bounty: self.bounty.key(),
+            agent: self.agent.key(),
+            index,
+            solution_hash,
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
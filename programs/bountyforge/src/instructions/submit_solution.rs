@@ -1,41 +1,144 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+use anchor_lang::system_program;
 
 use crate::{
-    constants::ANCHOR_DISCRIMINATOR,
+    constants::{
+        ANCHOR_DISCRIMINATOR, CURRENT_BOUNTY_VERSION, MAX_ATTESTATION_AGE_SECONDS,
+        MAX_ORACLE_FEEDS, SCORE_PER_SUBMISSION,
+    },
     errors::BountyForgeError,
-    state::{Attestation, Bounty, BountyStatus, Reputation},
+    events::SolutionSubmitted,
+    sas::SasAttestation,
+    state::{
+        AgentFlag, Application, ApplicationStatus, Attestation, AttestationSource, Attestor,
+        Bounty, BountyStats, BountyStatus, Config, Leaderboard, Operator, OracleKind, Reputation,
+        Submission, SubmissionStatus, TeamShare,
+    },
+    utils::{
+        require_top_level, validate_metadata_uri, validate_team, verify_pyth_price,
+        verify_switchboard_feed,
+    },
 };
 
 #[derive(Accounts)]
 pub struct SubmitSolution<'info> {
+    /// The agent itself or one of its operators, paying for the submission and any stake
     #[account(mut)]
-    pub agent: Signer<'info>,
+    pub submitter: Signer<'info>,
+
+    /// Pays the submission's rent in the submitter's place and gets it back on
+    /// close. Omitted for the submitter to pay it, the agent then being refunded
+    #[account(mut)]
+    pub sponsor: Option<Signer<'info>>,
+
+    /// CHECK: Agent identity the submission, reputation and payout belong to.
+    /// Must be the submitter unless `operator_record` vouches for them
+    pub agent: UncheckedAccount<'info>,
+
+    /// Only needed when an operator submits for the agent
+    #[account(
+        seeds = [b"operator", agent.key().as_ref(), submitter.key().as_ref()],
+        bump = operator_record.bump
+    )]
+    pub operator_record: Option<Account<'info, Operator>>,
 
     #[account(
         mut,
+        seeds = [b"bounty", bounty.original_creator.as_ref(), bounty.id.to_le_bytes().as_ref()],
+        bump = bounty.bump,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
         constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen,
-        constraint = bounty.solution_hash.is_none() @ BountyForgeError::BountyAlreadySubmitted
+        constraint = bounty.solution_hash.is_none() @ BountyForgeError::BountyAlreadySubmitted,
+        constraint = bounty.assignee.is_none() || bounty.assignee == Some(agent.key()) @ BountyForgeError::NotAssignedAgent,
+        constraint = bounty.is_allowlisted(&agent.key()) @ BountyForgeError::AgentNotAllowlisted,
+        constraint = bounty.milestones.is_empty() @ BountyForgeError::MilestoneBounty
     )]
-    pub bounty: Account<'info, Bounty>,
+    pub bounty: Box<Account<'info, Bounty>>,
 
+    /// Required unless the bounty takes SAS attestations
     #[account(
-        constraint = attestation.agent == agent.key() @ BountyForgeError::AttestationOwnerMismatch
+        mut,
+        seeds = [
+            b"attestation",
+            agent.key().as_ref(),
+            bounty.key().as_ref(),
+            attestation.solution_id.to_le_bytes().as_ref()
+        ],
+        bump = attestation.bump,
+        constraint = attestation.agent == agent.key() @ BountyForgeError::AttestationOwnerMismatch,
+        constraint = !attestation.consumed @ BountyForgeError::AttestationAlreadyConsumed,
+        constraint = attestation.bounty == bounty.key() @ BountyForgeError::AttestationBountyMismatch,
+        constraint = attestation.verified @ BountyForgeError::AttestationNotVerified,
+        constraint = !attestation.revoked @ BountyForgeError::AttestationRevoked,
+        constraint = attestation.hash_algo == bounty.hash_algo @ BountyForgeError::HashAlgoMismatch
     )]
-    pub attestation: Account<'info, Attestation>,
+    pub attestation: Option<Account<'info, Attestation>>,
+
+    /// CHECK: Solana Attestation Service attestation, only for bounties posted
+    /// with a `Sas` attestation source. Owner and layout are checked in the handler
+    pub sas_attestation: Option<UncheckedAccount<'info>>,
 
+    /// Created beforehand with init_reputation
     #[account(
-        init_if_needed,
-        payer = agent,
-        space = ANCHOR_DISCRIMINATOR + Reputation::INIT_SPACE,
+        mut,
         seeds = [b"rep", agent.key().as_ref()],
-        bump
+        bump = reputation.bump
     )]
     pub reputation: Account<'info, Reputation>,
 
-    /// CHECK: Optional Switchboard oracle account for price verification
-    /// Only required if bounty description mentions oracle/price
-    /// This is a generic account info - verification happens off-chain
-    pub oracle: Option<AccountInfo<'info>>,
+    /// Counters of the bounty's market
+    #[account(mut, seeds = [b"stats", bounty.market.as_bytes()], bump = stats.load()?.bump)]
+    pub stats: AccountLoader<'info, BountyStats>,
+
+    /// Top agents by score
+    #[account(mut, seeds = [b"leaderboard"], bump = leaderboard.load()?.bump)]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+
+    #[account(
+        init_if_needed,
+        payer = sponsor.as_ref().unwrap_or(&submitter),
+        space = ANCHOR_DISCRIMINATOR + Submission::INIT_SPACE,
+        seeds = [b"submission", bounty.key().as_ref(), agent.key().as_ref()],
+        bump
+    )]
+    pub submission: Box<Account<'info, Submission>>,
+
+    /// CHECK: The agent's AgentFlag PDA, which usually doesn't exist
+    /// Not optional so a banned agent can't leave it out, checked in the handler
+    #[account(
+        seeds = [b"flag", bounty.market.as_bytes(), agent.key().as_ref()],
+        bump
+    )]
+    pub agent_flag: UncheckedAccount<'info>,
+
+    /// CHECK: The agent's Attestor PDA, which attest_solution required and a
+    /// Sas bounty's agent may not have. Checked and counted in the handler
+    #[account(
+        mut,
+        seeds = [b"attestor", bounty.market.as_bytes(), agent.key().as_ref()],
+        bump
+    )]
+    pub attestor_account: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [b"config", bounty.market.as_bytes()],
+        bump = config.bump,
+        constraint = !config.paused @ BountyForgeError::ProgramPaused
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The agent's application, only required if the bounty was posted with
+    /// `requires_application`, in which case it must have been accepted
+    #[account(
+        constraint = application.bounty == bounty.key() @ BountyForgeError::ApplicationBountyMismatch,
+        constraint = application.agent == agent.key() @ BountyForgeError::ApplicationNotAccepted
+    )]
+    pub application: Option<Box<Account<'info, Application>>>,
+
+    /// CHECK: Instructions sysvar, read to refuse CPI unless the bounty allows it
+    #[account(address = sysvar_instructions::ID)]
+    pub instructions_sysvar: AccountInfo<'info>,
 
     pub system_program: Program<'info, System>,
 }
@@ -44,64 +147,385 @@ impl<'info> SubmitSolution<'info> {
     pub fn submit_solution(
         &mut self,
         solution_hash: [u8; 32],
+        ciphertext_hash: [u8; 32],
+        solution_uri: String,
+        team: Vec<TeamShare>,
+        remaining_accounts: &[AccountInfo<'info>],
         bumps: &SubmitSolutionBumps,
     ) -> Result<()> {
-        // 1. validating attestation solution hash matches
+        // 1. validating the signer and that the attestation's solution hash matches
+        if !self.bounty.allow_cpi {
+            require_top_level(&self.instructions_sysvar)?;
+        }
         require!(
-            self.attestation.solution_hash == solution_hash,
-            BountyForgeError::SolutionHashMismatch
+            self.submitter.key() == self.agent.key() || self.operator_record.is_some(),
+            BountyForgeError::UnauthorizedOperator
+        );
+        require!(
+            !AgentFlag::is_banned(&self.agent_flag)?,
+            BountyForgeError::AgentBanned
         );
+        self.config
+            .check_not_self_dealing(&self.bounty.creator, &self.agent.key())?;
+
+        if self.bounty.requires_application {
+            let accepted = self
+                .application
+                .as_ref()
+                .is_some_and(|application| application.status == ApplicationStatus::Accepted);
+            require!(accepted, BountyForgeError::ApplicationNotAccepted);
+        }
 
-        let description_lower = self.bounty.description.to_lowercase();
-        let requires_oracle = description_lower.contains("oracle") 
-            || description_lower.contains("switchboard") 
-            || description_lower.contains("price");
-        
-        if requires_oracle {
-            // Oracle verification: require oracle account to be provided
-            // Full verification happens off-chain via x402 gateway
-            // On-chain we just verify the account exists and is not empty
+        // counted in settled wins, which unlike score can't be farmed by submitting
+        require!(
+            self.reputation.successful_bounties >= self.bounty.min_reputation,
+            BountyForgeError::InsufficientReputation
+        );
+        // the agent's own registration issued the attestation, so its record is the one judged
+        if self.bounty.min_attestor_track_record > 0 {
+            let track_record = Attestor::load(&self.attestor_account)?
+                .map_or(0, |attestor| attestor.track_record());
             require!(
-                self.oracle.is_some(),
-                BountyForgeError::OracleVerificationFailed
+                track_record >= self.bounty.min_attestor_track_record,
+                BountyForgeError::AttestorTrackRecordTooLow
             );
-            
-            if let Some(ref oracle_account) = self.oracle {
-                // Basic check: oracle account must exist and have data
-                require!(
-                    !oracle_account.data_is_empty(),
-                    BountyForgeError::OracleVerificationFailed
-                );
-            }
         }
 
-        // 3. updating bounty
-        self.bounty.solution_hash = Some(solution_hash);
-        self.bounty.status = BountyStatus::Submitted;
-
-        // 4. updating reputation
-        if self.reputation.agent == Pubkey::default() {
-            // New reputation account - initialize it
-            self.reputation.set_inner(Reputation {
-                agent: self.agent.key(),
-                score: 1,
-                successful_bounties: 0,
-                failed_bounties: 0,
-                total_earned: 0,
-                bump: bumps.reputation,
-            });
+        // a bounty with an encryption key takes the solution encrypted to it, committed
+        // to alongside the plaintext hash, and other bounties take no commitment
+        require!(
+            (ciphertext_hash != [0; 32]) == self.bounty.encryption_pubkey.is_some(),
+            BountyForgeError::InvalidCiphertextHash
+        );
+
+        // only ever set here, so a rejected agent replaces it by submitting again
+        validate_metadata_uri(&solution_uri)?;
+        // a team splits one payout made at once, not prize places or a vesting stream
+        validate_team(&team)?;
+        require!(
+            team.is_empty()
+                || (self.bounty.prizes.is_empty() && self.bounty.vesting_duration_seconds == 0),
+            BountyForgeError::InvalidTeamSplit
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(
+            now <= self.bounty.deadline,
+            BountyForgeError::BountyDeadlinePassed
+        );
+
+        let attestor = match self.bounty.attestation_source {
+            AttestationSource::Internal => self.check_attestation(solution_hash, now)?,
+            AttestationSource::Sas { schema } => {
+                self.check_sas_attestation(&schema, solution_hash, now)?
+            }
+        };
+
+        // 2. oracle-backed bounties must be submitted alongside every feed named at posting
+        let other_attestations = self.check_oracle_feeds(remaining_accounts, now)?;
+
+        // high-value bounties need several distinct registered attestors to agree
+        let co_attestations = if self.bounty.required_attestations > 0 {
+            let (attestors, co_attestations) = self.count_attestors(&other_attestations)?;
+            require!(
+                attestors >= self.bounty.required_attestations as usize,
+                BountyForgeError::InsufficientAttestations
+            );
+            co_attestations
+        } else {
+            Vec::new()
+        };
+
+        // 3. recording the submission, a rejected one may be replaced but a pending one not
+        let new_submission = self.submission.agent == Pubkey::default();
+        if !new_submission
+            && self.bounty.is_repeatable()
+            && self.submission.status == SubmissionStatus::Accepted
+        {
+            // the agent was paid an earlier claim of this bounty
+            require!(
+                self.bounty.allow_repeat_solver,
+                BountyForgeError::AlreadyClaimed
+            );
         } else {
             require!(
-                self.reputation.agent == self.agent.key(),
-                BountyForgeError::ReputationOwnerMismatch
+                new_submission || self.submission.status == SubmissionStatus::Rejected,
+                BountyForgeError::SubmissionAlreadyExists
+            );
+        }
+        // a rejected agent can't flood the creator with fresh attempts, others are unaffected
+        if self.submission.status == SubmissionStatus::Rejected {
+            require!(
+                now >= self
+                    .submission
+                    .rejected_at
+                    .saturating_add_unsigned(self.bounty.resubmission_cooldown_seconds),
+                BountyForgeError::CooldownActive
             );
-            self.reputation.score = self
+        }
+        // each live submission holds one of the bounty's slots until it is rejected
+        let remaining_submissions = self.bounty.take_submission_slot()?;
+        // the stake sits on the submission until it is paid, slashed or the bounty goes away
+        let stake = self.bounty.required_stake;
+        if stake > 0 {
+            let cpi_accounts = system_program::Transfer {
+                from: self.submitter.to_account_info(),
+                to: self.submission.to_account_info(),
+            };
+            system_program::transfer(
+                CpiContext::new(self.system_program.to_account_info(), cpi_accounts),
+                stake,
+            )?;
+        }
+        // a resubmission reuses the account, whose rent is still owed to the first payer
+        let rent_payer = if new_submission {
+            self.sponsor
+                .as_ref()
+                .map_or(self.agent.key(), |sponsor| sponsor.key())
+        } else {
+            self.submission.rent_payer
+        };
+        self.submission.set_inner(Submission {
+            bounty: self.bounty.key(),
+            agent: self.agent.key(),
+            solution_hash,
+            ciphertext_hash,
+            solution_uri: solution_uri.clone(),
+            timestamp: now,
+            status: SubmissionStatus::Pending,
+            stake,
+            attestor,
+            rejected_at: self.submission.rejected_at,
+            rent_payer,
+            team,
+            bump: bumps.submission,
+        });
+        if let Some(attestation) = self.attestation.as_mut() {
+            attestation.consumed = true;
+            // the co-attestations counted towards the threshold can't back another submission either
+            let consumed = 1 + co_attestations.len() as u64;
+            for (account, mut co_attestation) in co_attestations {
+                co_attestation.consumed = true;
+                co_attestation.try_serialize(&mut &mut account.try_borrow_mut_data()?[..])?;
+            }
+            Attestor::update(&self.attestor_account, |attestor| {
+                attestor.attestations_consumed =
+                    attestor.attestations_consumed.saturating_add(consumed);
+            })?;
+        }
+
+        // 4. updating bounty, competitive bounties stay open for other agents
+        self.bounty.submission_count = self
+            .bounty
+            .submission_count
+            .checked_add(1)
+            .ok_or(BountyForgeError::SubmissionCountOverflow)?;
+        if !self.bounty.competitive {
+            self.bounty.solution_hash = Some(solution_hash);
+            self.bounty.solver = Some(self.agent.key());
+            self.bounty.status = BountyStatus::Submitted;
+            if self.bounty.submitted_at == 0 {
+                self.stats.load_mut()?.record_submitted()?;
+            }
+            self.bounty.mark_submitted()?;
+        }
+
+        // 5. updating reputation
+        self.reputation.score = self
+            .reputation
+            .score
+            .checked_add(SCORE_PER_SUBMISSION)
+            .ok_or(BountyForgeError::ReputationScoreOverflow)?;
+        self.leaderboard
+            .load_mut()?
+            .record(self.reputation.agent, self.reputation.score);
+        self.reputation.last_active_ts = now;
+        // the reputation can't be closed while any submission account is left
+        if new_submission {
+            self.reputation.open_submissions = self
                 .reputation
-                .score
+                .open_submissions
                 .checked_add(1)
-                .ok_or(BountyForgeError::ReputationScoreOverflow)?;
+                .ok_or(BountyForgeError::ReputationOverflow)?;
         }
 
+        emit!(SolutionSubmitted {
+            bounty: self.bounty.key(),
+            agent: self.agent.key(),
+            solution_hash,
+            solution_uri,
+            remaining_submissions,
+            timestamp: now,
+        });
+
         Ok(())
     }
+
+    /// Checks the bounty's own attestation vouches for this solution and is
+    /// recent enough, returning the attestor to record on the submission.
+    fn check_attestation(&self, solution_hash: [u8; 32], now: i64) -> Result<Pubkey> {
+        let attestation = self
+            .attestation
+            .as_ref()
+            .ok_or(BountyForgeError::MissingAttestation)?;
+        require!(
+            attestation.solution_hash == solution_hash,
+            BountyForgeError::SolutionHashMismatch
+        );
+
+        // an attestation only vouches for a solution for a limited time
+        require!(
+            attestation.timestamp <= now,
+            BountyForgeError::AttestationFromFuture
+        );
+        let max_attestation_age = self
+            .bounty
+            .max_attestation_age
+            .unwrap_or(MAX_ATTESTATION_AGE_SECONDS);
+        require!(
+            now.saturating_sub(attestation.timestamp) <= max_attestation_age,
+            BountyForgeError::AttestationExpired
+        );
+
+        Ok(attestation.attestor.unwrap_or(self.agent.key()))
+    }
+
+    /// Checks a Solana Attestation Service attestation under `schema` was
+    /// issued to the agent for this solution and hasn't expired, returning
+    /// its signer to record as the attestor.
+    fn check_sas_attestation(
+        &self,
+        schema: &Pubkey,
+        solution_hash: [u8; 32],
+        now: i64,
+    ) -> Result<Pubkey> {
+        let account = self
+            .sas_attestation
+            .as_ref()
+            .ok_or(BountyForgeError::MissingAttestation)?;
+        let attestation = SasAttestation::from_account(account)?;
+
+        require_keys_eq!(
+            attestation.schema,
+            *schema,
+            BountyForgeError::SasAttestationInvalid
+        );
+        require_keys_eq!(
+            *attestation.holder(),
+            self.agent.key(),
+            BountyForgeError::SasHolderMismatch
+        );
+        require!(
+            attestation.solution_hash() == Some(solution_hash),
+            BountyForgeError::SolutionHashMismatch
+        );
+        require!(
+            !attestation.is_expired(now),
+            BountyForgeError::SasAttestationExpired
+        );
+
+        Ok(attestation.signer)
+    }
+
+    /// Checks that the remaining accounts hold every feed the bounty names, in
+    /// any order, each live for its oracle kind and within the bounty's bounds,
+    /// and returns the others, which may only be this program's accounts (extra
+    /// attestations). The error can't carry the index of a bad or unexpected
+    /// account, so it is logged.
+    fn check_oracle_feeds<'a>(
+        &self,
+        remaining_accounts: &'a [AccountInfo<'info>],
+        now: i64,
+    ) -> Result<Vec<&'a AccountInfo<'info>>> {
+        let feeds: &[Pubkey] = if self.bounty.requires_oracle {
+            &self.bounty.oracle_feeds
+        } else {
+            &[]
+        };
+        let mut seen = [false; MAX_ORACLE_FEEDS];
+        let mut others = Vec::new();
+
+        for (index, account) in remaining_accounts.iter().enumerate() {
+            match feeds.iter().position(|feed| feed == account.key) {
+                Some(position) => {
+                    let verified = if seen[position] {
+                        err!(BountyForgeError::OracleVerificationFailed)
+                    } else {
+                        match self.bounty.oracle_kind {
+                            OracleKind::Switchboard => verify_switchboard_feed(account, now),
+                            OracleKind::Pyth => verify_pyth_price(account, now),
+                        }
+                        .and_then(|(value, exponent)| {
+                            self.bounty.check_oracle_value(value, exponent)
+                        })
+                    };
+                    verified.inspect_err(|_| {
+                        msg!("submit_solution: oracle feed at {} failed", index)
+                    })?;
+                    seen[position] = true;
+                }
+                None if account.owner == &crate::ID => others.push(account),
+                None => {
+                    msg!("submit_solution: unexpected account at {}", index);
+                    return err!(BountyForgeError::OracleVerificationFailed);
+                }
+            }
+        }
+
+        if let Some(missing) = seen[..feeds.len()].iter().position(|seen| !seen) {
+            msg!("submit_solution: oracle feed {} missing", missing);
+            return err!(BountyForgeError::OracleVerificationFailed);
+        }
+
+        Ok(others)
+    }
+
+    /// Distinct registered attestors behind this attestation and any extra
+    /// ones passed as remaining accounts, along with the extras that added an
+    /// attestor, which the submission consumes. Extras that are not usable
+    /// attestations of the same solution for this bounty are ignored.
+    fn count_attestors<'a>(
+        &self,
+        other_attestations: &[&'a AccountInfo<'info>],
+    ) -> Result<(usize, Vec<(&'a AccountInfo<'info>, Attestation)>)> {
+        let Some(own) = self.attestation.as_ref() else {
+            return Ok((0, Vec::new()));
+        };
+        let mut attestors: Vec<Pubkey> = Vec::new();
+
+        let mut consider = |attestation: &Attestation| {
+            let vouches = attestation.bounty == self.bounty.key()
+                && attestation.agent == self.agent.key()
+                && attestation.solution_hash == own.solution_hash
+                && attestation.hash_algo == self.bounty.hash_algo
+                && attestation.verified
+                && !attestation.revoked
+                && !attestation.consumed;
+
+            match attestation.attestor {
+                Some(attestor)
+                    if vouches
+                        && self.config.is_registered_attestor(&attestor)
+                        && !attestors.contains(&attestor) =>
+                {
+                    attestors.push(attestor);
+                    true
+                }
+                _ => false,
+            }
+        };
+
+        consider(own);
+        let mut co_attestations = Vec::new();
+        for account in other_attestations {
+            let data = account.try_borrow_data()?;
+            if let Ok(attestation) = Attestation::try_deserialize(&mut &data[..]) {
+                if consider(&attestation) {
+                    co_attestations.push((*account, attestation));
+                }
+            }
+        }
+
+        Ok((attestors.len(), co_attestations))
+    }
 }
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::events::BountySwept;
+use crate::state::{Bounty, BountyStatus, Config};
+use crate::utils::{escrow_balance, pay_from_escrow};
+
+#[derive(Accounts)]
+pub struct SweepAbandonedBounty<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", bounty.market.as_bytes()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ BountyForgeError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    /// Omitted for Sol bounties
+    #[account(
+        mut,
+        constraint = bounty_token_account.owner == bounty.key(),
+        constraint = bounty_token_account.mint == bounty.reward_mint
+    )]
+    pub bounty_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Protocol treasury wallet, receives the escrow of Sol bounties
+    #[account(mut, address = config.treasury @ BountyForgeError::TreasuryMismatch)]
+    pub treasury: AccountInfo<'info>,
+
+    /// Receives the escrow of token bounties, omitted for Sol bounties
+    #[account(
+        mut,
+        constraint = treasury_token_account.owner == config.treasury @ BountyForgeError::TreasuryMismatch,
+        constraint = treasury_token_account.mint == bounty.reward_mint
+    )]
+    pub treasury_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Reward mint recorded on the bounty, omitted for Sol bounties
+    #[account(address = bounty.reward_mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Option<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> SweepAbandonedBounty<'info> {
+    /// Moves the escrow of a bounty nobody has touched for the Config
+    /// `abandonment_seconds` to the treasury. Never while a solution waits on
+    /// the creator, nor while any agent or contributor can still claim from it.
+    pub fn sweep_abandoned_bounty(&mut self) -> Result<()> {
+        // 1. only bounties that owe nothing to anyone
+        let bounty = &self.bounty;
+        let sweepable = matches!(
+            bounty.status,
+            BountyStatus::Open
+                | BountyStatus::Settled
+                | BountyStatus::Cancelled
+                | BountyStatus::Expired
+        ) && !bounty.has_submitted_milestone()
+            && bounty.contributor_pool == 0
+            && bounty.vested_claimed >= bounty.vesting_amount;
+        require!(sweepable, BountyForgeError::BountyNotSweepable);
+
+        let now = Clock::get()?.unix_timestamp;
+        let last_activity = bounty.last_activity_at();
+        require!(
+            now.saturating_sub(last_activity) >= self.config.abandonment_seconds as i64,
+            BountyForgeError::BountyNotAbandoned
+        );
+
+        // 2. sweeping whatever escrow holds, surplus included
+        let status = bounty.status;
+        let amount = escrow_balance(bounty, self.bounty_token_account.as_ref())?;
+        if amount > 0 {
+            pay_from_escrow(
+                &self.bounty,
+                self.bounty_token_account.as_ref(),
+                self.reward_mint.as_ref(),
+                &self.treasury,
+                self.treasury_token_account.as_ref(),
+                &self.token_program,
+                amount,
+            )?;
+        }
+
+        // 3. retiring the bounty so nothing else can act on it
+        self.bounty.escrow_amount = 0;
+        self.bounty.status = BountyStatus::Swept;
+
+        msg!(
+            "sweep_abandoned_bounty: swept {} of bounty {} (id {}, creator {}) to the treasury",
+            amount,
+            self.bounty.key(),
+            self.bounty.id,
+            self.bounty.creator
+        );
+        emit!(BountySwept {
+            bounty: self.bounty.key(),
+            bounty_id: self.bounty.id,
+            creator: self.bounty.creator,
+            status,
+            mint: self.bounty.reward_mint,
+            amount,
+            treasury: self.config.treasury,
+            last_activity_at: last_activity,
+            swept_by: self.admin.key(),
+            timestamp: now,
+        });
+
+        Ok(())
+    }
+}
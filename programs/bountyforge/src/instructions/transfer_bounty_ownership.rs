@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CURRENT_BOUNTY_VERSION;
+use crate::errors::BountyForgeError;
+use crate::state::{Bounty, BountyStatus};
+
+#[derive(Accounts)]
+pub struct TransferBountyOwnership<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = creator.key() == bounty.creator @ BountyForgeError::UnauthorizedOwnershipTransfer,
+        constraint = matches!(bounty.status, BountyStatus::Open | BountyStatus::Submitted) @ BountyForgeError::BountyNotTransferable
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+}
+
+impl<'info> TransferBountyOwnership<'info> {
+    /// Proposes `new_creator` as the bounty's owner, who takes over once they
+    /// sign `accept_bounty_ownership`. Proposing again replaces the pending owner.
+    pub fn transfer_bounty_ownership(&mut self, new_creator: Pubkey) -> Result<()> {
+        require_keys_neq!(
+            new_creator,
+            self.bounty.creator,
+            BountyForgeError::InvalidNewCreator
+        );
+        self.bounty.pending_creator = Some(new_creator);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CURRENT_BOUNTY_VERSION, MAX_DESCRIPTION_LEN};
+use crate::errors::BountyForgeError;
+use crate::events::BountyUpdated;
+use crate::state::{Bounty, BountyStatus};
+use crate::utils::validate_metadata_uri;
+
+#[derive(Accounts)]
+pub struct UpdateBounty<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration,
+        constraint = creator.key() == bounty.creator @ BountyForgeError::UnauthorizedUpdate,
+        constraint = bounty.status == BountyStatus::Open @ BountyForgeError::BountyNotOpen,
+        constraint = bounty.solution_hash.is_none() && bounty.submission_count == 0 @ BountyForgeError::BountyAlreadySubmitted
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+}
+
+impl<'info> UpdateBounty<'info> {
+    /// Edits the description and metadata URI and pushes back the deadline of a bounty
+    /// nobody has submitted to yet. `None` leaves a field as it is; the reward has its
+    /// own instructions.
+    pub fn update_bounty(
+        &mut self,
+        description: Option<String>,
+        deadline: Option<i64>,
+        metadata_uri: Option<String>,
+    ) -> Result<()> {
+        let old_description = self.bounty.description.clone();
+        let old_deadline = self.bounty.deadline;
+        let old_metadata_uri = self.bounty.metadata_uri.clone();
+
+        if let Some(description) = description {
+            require!(!description.is_empty(), BountyForgeError::EmptyDescription);
+            require!(
+                description.len() <= MAX_DESCRIPTION_LEN,
+                BountyForgeError::DescriptionTooLong
+            );
+            self.bounty.description = description;
+        }
+
+        if let Some(metadata_uri) = metadata_uri {
+            validate_metadata_uri(&metadata_uri)?;
+            self.bounty.metadata_uri = metadata_uri;
+        }
+
+        // agents may already be working to the current deadline, so it only moves out
+        if let Some(deadline) = deadline {
+            require!(
+                deadline >= old_deadline,
+                BountyForgeError::DeadlineShortened
+            );
+            self.bounty.deadline = deadline;
+        }
+
+        emit!(BountyUpdated {
+            bounty: self.bounty.key(),
+            old_description,
+            new_description: self.bounty.description.clone(),
+            old_deadline,
+            new_deadline: self.bounty.deadline,
+            old_metadata_uri,
+            new_metadata_uri: self.bounty.metadata_uri.clone(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
@@ -0,0 +1,261 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_ATTESTORS, MAX_FEE_BPS, MAX_TRUSTED_MINTS};
+use crate::errors::BountyForgeError;
+use crate::state::Config;
+
+/// Each field left as `None` keeps its current value.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct UpdateConfigParams {
+    pub fee_bps: Option<u16>,
+    pub treasury: Option<Pubkey>,
+    pub paused: Option<bool>,
+    pub pending_admin: Option<Pubkey>,
+    pub verifier: Option<Pubkey>,
+    /// `Pubkey::default()` stops requiring an attestor signature.
+    pub trusted_attestor: Option<Pubkey>,
+    /// Replaces the whole list.
+    pub attestors: Option<Vec<Pubkey>>,
+    /// Only applies to bounties posted afterwards.
+    pub default_challenge_period_slots: Option<u64>,
+    pub challenge_bond_lamports: Option<u64>,
+    pub challenge_reward_bps: Option<u16>,
+    pub stake_slash_treasury_bps: Option<u16>,
+    /// Only applies to bounties posted afterwards.
+    pub default_settle_window_slots: Option<u64>,
+    /// Lowering it doesn't touch bounties already open, only blocks new ones.
+    pub max_open_per_creator: Option<u32>,
+    /// Only checked against new posts and top-ups, never existing rewards.
+    pub min_reward: Option<u64>,
+    pub max_reward: Option<u64>,
+    /// Also applies to receipts already written.
+    pub receipt_retention_seconds: Option<u64>,
+    /// Checked together with `fee_bps`, the two can't exceed 100% between them.
+    pub attestor_fee_bps: Option<u16>,
+    /// 0 weights every settled bounty the same.
+    pub score_unit: Option<u64>,
+    pub max_score_per_bounty: Option<u64>,
+    /// 0 stops decay_reputation from doing anything. Only the default
+    /// market's applies, reputation being shared by every market.
+    pub decay_period_seconds: Option<u64>,
+    pub allow_self_settlement: Option<bool>,
+    pub crank_fee_bps: Option<u16>,
+    /// `Pubkey::default()` removes the gateway, after which no receipt verifies.
+    pub payment_gateway: Option<Pubkey>,
+    /// Only applies to bounties posted afterwards.
+    pub default_resubmission_cooldown_seconds: Option<u64>,
+    /// How long a bounty must sit untouched before sweep_abandoned_bounty, never 0.
+    pub abandonment_seconds: Option<u64>,
+    /// Replaces the whole list. Only checked when a bounty is posted.
+    pub trusted_mints: Option<Vec<Pubkey>>,
+    /// Charged on the bounty's current reward at each boost.
+    pub boost_fee_bps: Option<u16>,
+    /// 0 disables boost_bounty; windows already bought run out as they were.
+    pub boost_duration_seconds: Option<u64>,
+    /// 0 stops close_stale_attestation.
+    pub attestation_ttl_seconds: Option<u64>,
+    /// Capped at the attestation's rent when paid.
+    pub attestation_close_tip_lamports: Option<u64>,
+    /// Of the protocol fee, the rest still going to the treasury.
+    pub insurance_fee_bps: Option<u16>,
+    /// In the reward's base units whatever the mint, 0 stops covering shortfalls.
+    pub max_insurance_topup: Option<u64>,
+    /// `Pubkey::default()` counts every mint into total_earned again. Earnings
+    /// already counted stay as they are.
+    pub primary_mint: Option<Pubkey>,
+    /// 0 refuses new appeals, ones already lodged keep the window they had.
+    pub appeal_window_seconds: Option<u64>,
+    /// Applies to challenges dismissed afterwards, whenever they were raised.
+    pub challenge_forfeit_bps: Option<u16>,
+    /// Applies to receipts checked afterwards. A receipt for nothing never
+    /// verifies, whatever this is.
+    pub min_verification_fee: Option<u64>,
+}
+
+#[derive(Accounts)]
+#[instruction(market: String)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"config", market.as_bytes()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ BountyForgeError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn update_config(&mut self, params: UpdateConfigParams) -> Result<()> {
+        if params.fee_bps.is_some() || params.attestor_fee_bps.is_some() {
+            let fee_bps = params.fee_bps.unwrap_or(self.config.fee_bps);
+            let attestor_fee_bps = params
+                .attestor_fee_bps
+                .unwrap_or(self.config.attestor_fee_bps);
+            Config::check_fees(fee_bps, attestor_fee_bps)?;
+            self.config.fee_bps = fee_bps;
+            self.config.attestor_fee_bps = attestor_fee_bps;
+        }
+
+        if let Some(treasury) = params.treasury {
+            self.config.treasury = treasury;
+        }
+
+        if let Some(paused) = params.paused {
+            self.config.paused = paused;
+        }
+
+        if let Some(verifier) = params.verifier {
+            self.config.verifier = verifier;
+        }
+
+        if let Some(trusted_attestor) = params.trusted_attestor {
+            self.config.trusted_attestor =
+                (trusted_attestor != Pubkey::default()).then_some(trusted_attestor);
+        }
+
+        if let Some(attestors) = params.attestors {
+            require!(
+                attestors.len() <= MAX_ATTESTORS,
+                BountyForgeError::TooManyAttestors
+            );
+            self.config.attestors = attestors;
+        }
+
+        if let Some(slots) = params.default_challenge_period_slots {
+            self.config.default_challenge_period_slots = slots;
+        }
+
+        if let Some(bond) = params.challenge_bond_lamports {
+            self.config.challenge_bond_lamports = bond;
+        }
+
+        if let Some(reward_bps) = params.challenge_reward_bps {
+            require!(reward_bps <= MAX_FEE_BPS, BountyForgeError::InvalidFeeBps);
+            self.config.challenge_reward_bps = reward_bps;
+        }
+
+        if let Some(slash_bps) = params.stake_slash_treasury_bps {
+            require!(slash_bps <= MAX_FEE_BPS, BountyForgeError::InvalidFeeBps);
+            self.config.stake_slash_treasury_bps = slash_bps;
+        }
+
+        if let Some(slots) = params.default_settle_window_slots {
+            self.config.default_settle_window_slots = slots;
+        }
+
+        if let Some(max_open) = params.max_open_per_creator {
+            self.config.max_open_per_creator = max_open;
+        }
+
+        if params.min_reward.is_some() || params.max_reward.is_some() {
+            let min_reward = params.min_reward.unwrap_or(self.config.min_reward);
+            let max_reward = params.max_reward.unwrap_or(self.config.max_reward);
+            Config::check_reward_bounds(min_reward, max_reward)?;
+            self.config.min_reward = min_reward;
+            self.config.max_reward = max_reward;
+        }
+
+        if let Some(retention) = params.receipt_retention_seconds {
+            self.config.receipt_retention_seconds = retention;
+        }
+
+        if let Some(score_unit) = params.score_unit {
+            self.config.score_unit = score_unit;
+        }
+
+        if let Some(max_score) = params.max_score_per_bounty {
+            Config::check_score_cap(max_score)?;
+            self.config.max_score_per_bounty = max_score;
+        }
+
+        if let Some(period) = params.decay_period_seconds {
+            self.config.decay_period_seconds = period;
+        }
+
+        if let Some(allow) = params.allow_self_settlement {
+            self.config.allow_self_settlement = allow;
+        }
+
+        if let Some(crank_bps) = params.crank_fee_bps {
+            require!(crank_bps <= MAX_FEE_BPS, BountyForgeError::InvalidFeeBps);
+            self.config.crank_fee_bps = crank_bps;
+        }
+
+        if let Some(payment_gateway) = params.payment_gateway {
+            self.config.payment_gateway =
+                (payment_gateway != Pubkey::default()).then_some(payment_gateway);
+        }
+
+        if let Some(seconds) = params.default_resubmission_cooldown_seconds {
+            self.config.default_resubmission_cooldown_seconds = seconds;
+        }
+
+        if let Some(seconds) = params.abandonment_seconds {
+            require!(seconds > 0, BountyForgeError::InvalidAbandonmentPeriod);
+            self.config.abandonment_seconds = seconds;
+        }
+
+        if let Some(trusted_mints) = params.trusted_mints {
+            require!(
+                trusted_mints.len() <= MAX_TRUSTED_MINTS,
+                BountyForgeError::TooManyTrustedMints
+            );
+            self.config.trusted_mints = trusted_mints;
+        }
+
+        if let Some(boost_bps) = params.boost_fee_bps {
+            require!(boost_bps <= MAX_FEE_BPS, BountyForgeError::InvalidFeeBps);
+            self.config.boost_fee_bps = boost_bps;
+        }
+
+        if let Some(seconds) = params.boost_duration_seconds {
+            self.config.boost_duration_seconds = seconds;
+        }
+
+        if let Some(seconds) = params.attestation_ttl_seconds {
+            self.config.attestation_ttl_seconds = seconds;
+        }
+
+        if let Some(lamports) = params.attestation_close_tip_lamports {
+            self.config.attestation_close_tip_lamports = lamports;
+        }
+
+        if let Some(insurance_bps) = params.insurance_fee_bps {
+            require!(
+                insurance_bps <= MAX_FEE_BPS,
+                BountyForgeError::InvalidFeeBps
+            );
+            self.config.insurance_fee_bps = insurance_bps;
+        }
+
+        if let Some(topup) = params.max_insurance_topup {
+            self.config.max_insurance_topup = topup;
+        }
+
+        if let Some(primary_mint) = params.primary_mint {
+            self.config.primary_mint = (primary_mint != Pubkey::default()).then_some(primary_mint);
+        }
+
+        if let Some(window) = params.appeal_window_seconds {
+            self.config.appeal_window_seconds = window;
+        }
+
+        if let Some(forfeit_bps) = params.challenge_forfeit_bps {
+            require!(forfeit_bps <= MAX_FEE_BPS, BountyForgeError::InvalidFeeBps);
+            self.config.challenge_forfeit_bps = forfeit_bps;
+        }
+
+        if let Some(fee) = params.min_verification_fee {
+            self.config.min_verification_fee = fee;
+        }
+
+        // the new admin only takes over once they accept
+        if let Some(pending_admin) = params.pending_admin {
+            self.config.pending_admin = Some(pending_admin);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_PROOF_LEN;
+use crate::errors::BountyForgeError;
+use crate::state::ReputationSnapshot;
+
+#[derive(Accounts)]
+#[instruction(market: String, epoch: u64)]
+pub struct VerifyReputationProof<'info> {
+    #[account(
+        seeds = [b"snapshot", market.as_bytes(), epoch.to_le_bytes().as_ref()],
+        bump = snapshot.bump
+    )]
+    pub snapshot: Account<'info, ReputationSnapshot>,
+}
+
+impl<'info> VerifyReputationProof<'info> {
+    /// Succeeds only if the agent's stats are a leaf of the epoch's root, so
+    /// other programs can CPI into it and let a failure abort their transaction.
+    pub fn verify_reputation_proof(
+        &self,
+        agent: Pubkey,
+        score: u64,
+        successful_bounties: u64,
+        proof: &[[u8; 32]],
+    ) -> Result<()> {
+        require!(
+            proof.len() <= MAX_PROOF_LEN,
+            BountyForgeError::InvalidReputationProof
+        );
+        let leaf = ReputationSnapshot::leaf(&agent, score, successful_bounties);
+        require!(
+            self.snapshot.verify(leaf, proof),
+            BountyForgeError::InvalidReputationProof
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::errors::BountyForgeError;
+use crate::state::FundingPool;
+
+#[derive(Accounts)]
+pub struct WithdrawFromPool<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"pool", creator.key().as_ref(), funding_pool.mint.as_ref()],
+        bump = funding_pool.bump
+    )]
+    pub funding_pool: Box<Account<'info, FundingPool>>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = funding_pool,
+        associated_token::token_program = token_program
+    )]
+    pub pool_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = creator_token_account.owner == creator.key(),
+        constraint = creator_token_account.mint == reward_mint.key() @ BountyForgeError::RewardMintMismatch
+    )]
+    pub creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = funding_pool.mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawFromPool<'info> {
+    /// Returns `amount` of the pool's unallocated funds to the creator. What
+    /// bounties were funded with stays in their escrows.
+    pub fn withdraw_from_pool(&mut self, amount: u64) -> Result<()> {
+        self.funding_pool.withdraw(amount)?;
+
+        let creator = self.creator.key();
+        let mint = self.funding_pool.mint;
+        let seeds: &[&[u8]] = &[
+            b"pool",
+            creator.as_ref(),
+            mint.as_ref(),
+            &[self.funding_pool.bump],
+        ];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.pool_token_account.to_account_info(),
+                    mint: self.reward_mint.to_account_info(),
+                    to: self.creator_token_account.to_account_info(),
+                    authority: self.funding_pool.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            self.reward_mint.decimals,
+        )?;
+
+        self.funding_pool.emit_update(self.funding_pool.key())
+    }
+}
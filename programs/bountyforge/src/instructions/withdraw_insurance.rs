@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::errors::BountyForgeError;
+use crate::state::{Config, InsuranceFund};
+
+#[derive(Accounts)]
+#[instruction(market: String)]
+pub struct WithdrawInsurance<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(
+        seeds = [b"config", market.as_bytes()],
+        bump = config.bump,
+        constraint = config.admin == admin.key() @ BountyForgeError::UnauthorizedAdmin
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [b"insurance", market.as_bytes(), insurance_fund.mint.as_ref()],
+        bump = insurance_fund.bump
+    )]
+    pub insurance_fund: Box<Account<'info, InsuranceFund>>,
+
+    #[account(
+        mut,
+        associated_token::mint = reward_mint,
+        associated_token::authority = insurance_fund,
+        associated_token::token_program = token_program
+    )]
+    pub insurance_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = admin_token_account.mint == reward_mint.key() @ BountyForgeError::RewardMintMismatch
+    )]
+    pub admin_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = insurance_fund.mint @ BountyForgeError::RewardMintMismatch)]
+    pub reward_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawInsurance<'info> {
+    /// Takes `amount` out of the insurance fund into `admin_token_account`,
+    /// which needn't be the admin's own, e.g. to move it to the treasury.
+    pub fn withdraw_insurance(&mut self, market: &str, amount: u64) -> Result<()> {
+        self.insurance_fund.withdraw(amount)?;
+
+        let mint = self.insurance_fund.mint;
+        let seeds: &[&[u8]] = &[
+            b"insurance",
+            market.as_bytes(),
+            mint.as_ref(),
+            &[self.insurance_fund.bump],
+        ];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                TransferChecked {
+                    from: self.insurance_token_account.to_account_info(),
+                    mint: self.reward_mint.to_account_info(),
+                    to: self.admin_token_account.to_account_info(),
+                    authority: self.insurance_fund.to_account_info(),
+                },
+                &[seeds],
+            ),
+            amount,
+            self.reward_mint.decimals,
+        )?;
+
+        self.insurance_fund.emit_update(self.insurance_fund.key())
+    }
+}
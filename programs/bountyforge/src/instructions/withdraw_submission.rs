@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CURRENT_BOUNTY_VERSION, SCORE_PER_SUBMISSION};
+use crate::errors::BountyForgeError;
+use crate::events::SubmissionWithdrawn;
+use crate::state::{Bounty, BountyStatus, Leaderboard, Reputation, Submission, SubmissionStatus};
+use crate::utils::refund_rent;
+
+#[derive(Accounts)]
+pub struct WithdrawSubmission<'info> {
+    #[account(mut)]
+    pub agent: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = bounty.version == CURRENT_BOUNTY_VERSION @ BountyForgeError::AccountNeedsMigration
+    )]
+    pub bounty: Box<Account<'info, Bounty>>,
+
+    #[account(
+        mut,
+        close = agent,
+        seeds = [b"submission", bounty.key().as_ref(), agent.key().as_ref()],
+        bump = submission.bump,
+        constraint = submission.status == SubmissionStatus::Pending @ BountyForgeError::SubmissionNotPending
+    )]
+    pub submission: Box<Account<'info, Submission>>,
+
+    #[account(
+        mut,
+        seeds = [b"rep", agent.key().as_ref()],
+        bump = reputation.bump
+    )]
+    pub reputation: Account<'info, Reputation>,
+
+    /// Top agents by score
+    #[account(mut, seeds = [b"leaderboard"], bump = leaderboard.load()?.bump)]
+    pub leaderboard: AccountLoader<'info, Leaderboard>,
+
+    /// CHECK: Sponsor that paid the submission's rent, only required if one did
+    #[account(mut, address = submission.rent_payer @ BountyForgeError::RentPayerMismatch)]
+    pub rent_payer: Option<UncheckedAccount<'info>>,
+}
+
+impl<'info> WithdrawSubmission<'info> {
+    /// Retracts the agent's pending submission before the creator acts on it:
+    /// while a competitive bounty is still open, or while another bounty sits
+    /// in `Submitted` with the agent as solver. Closing the submission returns
+    /// any stake to the agent, and its rent to whoever paid it.
+    pub fn withdraw_submission(&mut self) -> Result<()> {
+        let agent = self.agent.key();
+        let bounty = &mut self.bounty;
+        let withdrawable = if bounty.competitive {
+            bounty.status == BountyStatus::Open
+        } else {
+            bounty.status == BountyStatus::Submitted && bounty.solver == Some(agent)
+        };
+        require!(withdrawable, BountyForgeError::SubmissionNotWithdrawable);
+
+        // 1. reopening the bounty as reject_solution would, without the failure
+        if !bounty.competitive {
+            bounty.solution_hash = None;
+            bounty.solver = None;
+            bounty.revealed = false;
+            bounty.status = BountyStatus::Open;
+        }
+        bounty.release_submission_slot();
+
+        // 2. taking back the submission's score, so withdrawing and submitting
+        // again can't farm it, and counting the withdrawal instead
+        let reputation = &mut self.reputation;
+        reputation.score = reputation.score.saturating_sub(SCORE_PER_SUBMISSION);
+        self.leaderboard
+            .load_mut()?
+            .record(reputation.agent, reputation.score);
+        reputation.withdrawals = reputation
+            .withdrawals
+            .checked_add(1)
+            .ok_or(BountyForgeError::ReputationOverflow)?;
+        reputation.open_submissions = reputation.open_submissions.saturating_sub(1);
+        refund_rent(
+            &self.submission.to_account_info(),
+            self.submission.rent_payer,
+            &agent,
+            self.rent_payer.as_ref(),
+        )?;
+
+        emit!(SubmissionWithdrawn {
+            bounty: bounty.key(),
+            agent,
+            stake: self.submission.stake,
+            withdrawals: reputation.withdrawals,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+}
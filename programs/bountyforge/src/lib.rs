@@ -2,41 +2,432 @@
 
 use anchor_lang::prelude::*;
 use instructions::*;
-mod constants;
+use state::{HashAlgo, RejectionReason, TeamShare};
+pub mod constants;
 mod errors;
+mod events;
 mod instructions;
+mod sas;
 mod state;
+mod utils;
 declare_id!("9Y6Z41eWLsfc8kY73WLBNeRN1NuiTBuMoADEecXGKnpZ");
 
 #[program]
 pub mod bountyforge {
     use super::*;
 
-    pub fn post_bounty(
-        ctx: Context<PostBounty>,
-        bounty_id: u64,
-        bounty_type: state::BountyType,
-        description: String,
-        reward: u64,
+    pub fn initialize_bounty_counter(ctx: Context<InitializeBountyCounter>) -> Result<()> {
+        ctx.accounts.initialize_bounty_counter(&ctx.bumps)
+    }
+
+    pub fn post_bounty(ctx: Context<PostBounty>, params: PostBountyParams) -> Result<()> {
+        ctx.accounts.post_bounty(params, &ctx.bumps)
+    }
+
+    pub fn post_bounty_from_pool(ctx: Context<PostBounty>, params: PostBountyParams) -> Result<()> {
+        ctx.accounts.post_bounty_from_pool(params, &ctx.bumps)
+    }
+
+    pub fn deposit_insurance(
+        ctx: Context<DepositInsurance>,
+        _market: String,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.deposit_insurance(amount, &ctx.bumps)
+    }
+
+    pub fn withdraw_insurance(
+        ctx: Context<WithdrawInsurance>,
+        market: String,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.withdraw_insurance(&market, amount)
+    }
+
+    pub fn create_pool(ctx: Context<CreatePool>, _market: String, amount: u64) -> Result<()> {
+        ctx.accounts.create_pool(amount, &ctx.bumps)
+    }
+
+    pub fn withdraw_from_pool(ctx: Context<WithdrawFromPool>, amount: u64) -> Result<()> {
+        ctx.accounts.withdraw_from_pool(amount)
+    }
+
+    pub fn post_bounties<'info>(
+        ctx: Context<'_, '_, 'info, 'info, PostBounties<'info>>,
+        items: Vec<BatchBountyParams>,
     ) -> Result<()> {
         ctx.accounts
-            .post_bounty(bounty_id, bounty_type, description, reward, &ctx.bumps)
+            .post_bounties(items, ctx.remaining_accounts, &ctx.bumps)
+    }
+
+    pub fn update_bounty(
+        ctx: Context<UpdateBounty>,
+        description: Option<String>,
+        deadline: Option<i64>,
+        metadata_uri: Option<String>,
+    ) -> Result<()> {
+        ctx.accounts
+            .update_bounty(description, deadline, metadata_uri)
+    }
+
+    pub fn transfer_bounty_ownership(
+        ctx: Context<TransferBountyOwnership>,
+        new_creator: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.transfer_bounty_ownership(new_creator)
+    }
+
+    pub fn accept_bounty_ownership(ctx: Context<AcceptBountyOwnership>) -> Result<()> {
+        ctx.accounts.accept_bounty_ownership(&ctx.bumps)
+    }
+
+    pub fn increase_reward(ctx: Context<IncreaseReward>, additional: u64) -> Result<()> {
+        ctx.accounts.increase_reward(additional)
+    }
+
+    pub fn decrease_reward(ctx: Context<DecreaseReward>, amount: u64) -> Result<()> {
+        ctx.accounts.decrease_reward(amount)
+    }
+
+    pub fn contribute_to_bounty(ctx: Context<ContributeToBounty>, amount: u64) -> Result<()> {
+        ctx.accounts.contribute_to_bounty(amount, &ctx.bumps)
+    }
+
+    pub fn refund_contribution(ctx: Context<RefundContribution>) -> Result<()> {
+        ctx.accounts.refund_contribution()
+    }
+
+    pub fn boost_bounty(ctx: Context<BoostBounty>) -> Result<()> {
+        ctx.accounts.boost_bounty()
+    }
+
+    pub fn assign_bounty(ctx: Context<AssignBounty>, assignee: Option<Pubkey>) -> Result<()> {
+        ctx.accounts.assign_bounty(assignee)
+    }
+
+    pub fn set_settlement_authority(
+        ctx: Context<SetSettlementAuthority>,
+        authority: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.set_settlement_authority(authority)
+    }
+
+    pub fn add_allowlisted_agent(ctx: Context<AddAllowlistedAgent>, agent: Pubkey) -> Result<()> {
+        ctx.accounts.add_allowlisted_agent(agent)
+    }
+
+    pub fn remove_allowlisted_agent(
+        ctx: Context<RemoveAllowlistedAgent>,
+        agent: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.remove_allowlisted_agent(agent)
+    }
+
+    pub fn apply_to_bounty(ctx: Context<ApplyToBounty>, note_hash: [u8; 32]) -> Result<()> {
+        ctx.accounts.apply_to_bounty(note_hash, &ctx.bumps)
+    }
+
+    pub fn accept_application(ctx: Context<AcceptApplication>) -> Result<()> {
+        ctx.accounts.accept_application()
+    }
+
+    pub fn close_application(ctx: Context<CloseApplication>) -> Result<()> {
+        ctx.accounts.close_application()
     }
 
     pub fn attest_solution(
         ctx: Context<AttestSolution>,
-        solution_id: u64,
         solution_hash: [u8; 32],
+        hash_algo: HashAlgo,
     ) -> Result<()> {
         ctx.accounts
-            .attest_solution(solution_id, solution_hash, &ctx.bumps)
+            .attest_solution(solution_hash, hash_algo, &ctx.bumps)
+    }
+
+    pub fn init_reputation(ctx: Context<InitReputation>) -> Result<()> {
+        ctx.accounts.init_reputation(&ctx.bumps)
+    }
+
+    pub fn register_operator(ctx: Context<RegisterOperator>, operator: Pubkey) -> Result<()> {
+        ctx.accounts.register_operator(operator, &ctx.bumps)
+    }
+
+    pub fn revoke_operator(ctx: Context<RevokeOperator>, operator: Pubkey) -> Result<()> {
+        ctx.accounts.revoke_operator(operator)
+    }
+
+    pub fn submit_solution<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SubmitSolution<'info>>,
+        solution_hash: [u8; 32],
+        ciphertext_hash: [u8; 32],
+        solution_uri: String,
+        team: Vec<TeamShare>,
+    ) -> Result<()> {
+        ctx.accounts.submit_solution(
+            solution_hash,
+            ciphertext_hash,
+            solution_uri,
+            team,
+            ctx.remaining_accounts,
+            &ctx.bumps,
+        )
+    }
+
+    pub fn submit_milestone(
+        ctx: Context<SubmitMilestone>,
+        index: u8,
+        solution_hash: [u8; 32],
+    ) -> Result<()> {
+        ctx.accounts.submit_milestone(index, solution_hash)
+    }
+
+    pub fn reveal_solution(ctx: Context<RevealSolution>, payload: Vec<u8>) -> Result<()> {
+        ctx.accounts.reveal_solution(payload)
     }
 
-    pub fn submit_solution(ctx: Context<SubmitSolution>, solution_hash: [u8; 32]) -> Result<()> {
-        ctx.accounts.submit_solution(solution_hash, &ctx.bumps)
+    pub fn reveal_criteria(ctx: Context<RevealCriteria>, criteria: Vec<u8>) -> Result<()> {
+        ctx.accounts.reveal_criteria(criteria)
     }
 
-    pub fn settle_bounty(ctx: Context<SettleBounty>) -> Result<()> {
-        ctx.accounts.settle_bounty()
+    pub fn select_winner(ctx: Context<SelectWinner>) -> Result<()> {
+        ctx.accounts.select_winner()
+    }
+
+    pub fn close_submission(ctx: Context<CloseSubmission>) -> Result<()> {
+        ctx.accounts.close_submission()
+    }
+
+    pub fn settle_bounties<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleBounties<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.settle_bounties(ctx.remaining_accounts)
+    }
+
+    pub fn settle_prizes<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettlePrizes<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.settle_prizes(ctx.remaining_accounts)
+    }
+
+    pub fn challenge_solution(ctx: Context<ChallengeSolution>) -> Result<()> {
+        ctx.accounts.challenge_solution(&ctx.bumps)
+    }
+
+    pub fn resolve_challenge(ctx: Context<ResolveChallenge>, uphold: bool) -> Result<()> {
+        ctx.accounts.resolve_challenge(uphold)
+    }
+
+    pub fn open_dispute(ctx: Context<OpenDispute>) -> Result<()> {
+        ctx.accounts.open_dispute()
+    }
+
+    pub fn resolve_dispute<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ResolveDispute<'info>>,
+        outcome: DisputeOutcome,
+    ) -> Result<()> {
+        ctx.accounts
+            .resolve_dispute(outcome, &ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn settle_bounty<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SettleBounty<'info>>,
+    ) -> Result<()> {
+        ctx.accounts
+            .settle_bounty(&ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn settle_milestone(ctx: Context<SettleMilestone>, index: u8) -> Result<()> {
+        ctx.accounts.settle_milestone(index)
+    }
+
+    pub fn claim_vested(ctx: Context<ClaimVested>) -> Result<()> {
+        ctx.accounts.claim_vested()
+    }
+
+    pub fn leave_feedback(ctx: Context<LeaveFeedback>, rating: u8) -> Result<()> {
+        ctx.accounts.leave_feedback(rating)
+    }
+
+    pub fn close_receipt(ctx: Context<CloseReceipt>) -> Result<()> {
+        ctx.accounts.close_receipt()
+    }
+
+    pub fn cancel_bounty(ctx: Context<CancelBounty>) -> Result<()> {
+        ctx.accounts.cancel_bounty()
+    }
+
+    pub fn expire_bounty(ctx: Context<ExpireBounty>) -> Result<()> {
+        ctx.accounts.expire_bounty()
+    }
+
+    pub fn crank_expire_bounty(ctx: Context<CrankExpireBounty>) -> Result<()> {
+        ctx.accounts.crank_expire_bounty()
+    }
+
+    pub fn sweep_abandoned_bounty(ctx: Context<SweepAbandonedBounty>) -> Result<()> {
+        ctx.accounts.sweep_abandoned_bounty()
+    }
+
+    pub fn approve_settlement(ctx: Context<ApproveSettlement>) -> Result<()> {
+        ctx.accounts.approve_settlement()
+    }
+
+    pub fn claim_reward<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimReward<'info>>,
+    ) -> Result<()> {
+        ctx.accounts
+            .claim_reward(&ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn reject_solution(
+        ctx: Context<RejectSolution>,
+        reason: RejectionReason,
+        note_hash: Option<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.reject_solution(reason, note_hash, &ctx.bumps)
+    }
+
+    pub fn appeal_rejection(ctx: Context<AppealRejection>) -> Result<()> {
+        ctx.accounts.appeal_rejection()
+    }
+
+    pub fn resolve_appeal(ctx: Context<ResolveAppeal>, uphold: bool) -> Result<()> {
+        ctx.accounts.resolve_appeal(uphold)
+    }
+
+    pub fn withdraw_submission(ctx: Context<WithdrawSubmission>) -> Result<()> {
+        ctx.accounts.withdraw_submission()
+    }
+
+    pub fn force_settle<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ForceSettle<'info>>,
+    ) -> Result<()> {
+        ctx.accounts
+            .force_settle(&ctx.bumps, ctx.remaining_accounts)
+    }
+
+    pub fn close_stale_attestation(ctx: Context<CloseStaleAttestation>) -> Result<()> {
+        ctx.accounts.close_stale_attestation()
+    }
+
+    pub fn close_bounty(ctx: Context<CloseBounty>) -> Result<()> {
+        ctx.accounts.close_bounty()
+    }
+
+    pub fn close_reputation(ctx: Context<CloseReputation>) -> Result<()> {
+        ctx.accounts.close_reputation()
+    }
+
+    pub fn migrate_reputation(ctx: Context<MigrateReputation>) -> Result<()> {
+        ctx.accounts.migrate_reputation()
+    }
+
+    pub fn migrate_bounty(ctx: Context<MigrateBounty>) -> Result<()> {
+        ctx.accounts.migrate_bounty(&ctx.bumps)
+    }
+
+    pub fn migrate_stats(ctx: Context<MigrateStats>, _market: String) -> Result<()> {
+        ctx.accounts.migrate_stats()
+    }
+
+    pub fn migrate_leaderboard(ctx: Context<MigrateLeaderboard>) -> Result<()> {
+        ctx.accounts.migrate_leaderboard()
+    }
+
+    pub fn snapshot_reputation(
+        ctx: Context<SnapshotReputation>,
+        _market: String,
+        epoch: u64,
+        root: [u8; 32],
+        leaf_count: u32,
+    ) -> Result<()> {
+        ctx.accounts
+            .snapshot_reputation(epoch, root, leaf_count, &ctx.bumps)
+    }
+
+    pub fn verify_reputation_proof(
+        ctx: Context<VerifyReputationProof>,
+        _market: String,
+        _epoch: u64,
+        agent: Pubkey,
+        score: u64,
+        successful_bounties: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts
+            .verify_reputation_proof(agent, score, successful_bounties, &proof)
+    }
+
+    pub fn decay_reputation(ctx: Context<DecayReputation>) -> Result<()> {
+        ctx.accounts.decay_reputation()
+    }
+
+    pub fn initialize_config(
+        ctx: Context<InitializeConfig>,
+        params: InitializeConfigParams,
+    ) -> Result<()> {
+        ctx.accounts.initialize_config(params, &ctx.bumps)
+    }
+
+    pub fn initialize_market(
+        ctx: Context<InitializeMarket>,
+        market: String,
+        params: InitializeConfigParams,
+    ) -> Result<()> {
+        ctx.accounts.initialize_market(market, params, &ctx.bumps)
+    }
+
+    pub fn initialize_leaderboard(ctx: Context<InitializeLeaderboard>) -> Result<()> {
+        ctx.accounts.initialize_leaderboard(&ctx.bumps)
+    }
+
+    pub fn initialize_stats(ctx: Context<InitializeStats>) -> Result<()> {
+        ctx.accounts.initialize_stats(&ctx.bumps)
+    }
+
+    pub fn update_config(
+        ctx: Context<UpdateConfig>,
+        _market: String,
+        params: UpdateConfigParams,
+    ) -> Result<()> {
+        ctx.accounts.update_config(params)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>, _market: String) -> Result<()> {
+        ctx.accounts.accept_admin()
+    }
+
+    pub fn set_pause(ctx: Context<SetPause>, _market: String, paused: bool) -> Result<()> {
+        ctx.accounts.set_pause(paused)
+    }
+
+    pub fn verify_attestation(ctx: Context<VerifyAttestation>) -> Result<()> {
+        ctx.accounts.verify_attestation()
+    }
+
+    pub fn revoke_attestation(ctx: Context<RevokeAttestation>) -> Result<()> {
+        ctx.accounts.revoke_attestation()
+    }
+
+    pub fn register_attestor(
+        ctx: Context<RegisterAttestor>,
+        _market: String,
+        attestor: Pubkey,
+        name: String,
+    ) -> Result<()> {
+        ctx.accounts.register_attestor(attestor, name, &ctx.bumps)
+    }
+
+    pub fn remove_attestor(ctx: Context<RemoveAttestor>, _market: String) -> Result<()> {
+        ctx.accounts.remove_attestor()
+    }
+
+    pub fn flag_agent(
+        ctx: Context<FlagAgent>,
+        _market: String,
+        agent: Pubkey,
+        banned: bool,
+    ) -> Result<()> {
+        ctx.accounts.flag_agent(agent, banned, &ctx.bumps)
     }
 }
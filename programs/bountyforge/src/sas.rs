@@ -0,0 +1,63 @@
+// Just enough of the Solana Attestation Service account layout to accept its
+// attestations in place of BountyForge's own. Parsing works on raw bytes so
+// it can be checked against fixture accounts without the live program.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::pubkey;
+
+use crate::errors::BountyForgeError;
+
+pub const SAS_PROGRAM_ID: Pubkey = pubkey!("22zoJMtdu4tQc2PzL74ZUT7FrwgB1Udec8DdW4yw4BdG");
+
+// first byte of every SAS account: 0 credential, 1 schema, 2 attestation
+const ATTESTATION_DISCRIMINATOR: u8 = 2;
+
+/// PDA of the SAS program: `["attestation", credential, schema, nonce]`,
+/// where the nonce is the key the credential was issued to. Only the fields
+/// up to `expiry` are read; a tokenized attestation's token account follows.
+#[derive(AnchorDeserialize)]
+pub struct SasAttestation {
+    pub discriminator: u8,
+    pub nonce: Pubkey, // the holder
+    _credential: Pubkey,
+    pub schema: Pubkey,
+    pub data: Vec<u8>,  // laid out by the schema, a bare 32-byte hash for bounties
+    pub signer: Pubkey, // the credential's authorized signer who issued it
+    pub expiry: i64,    // unix timestamp, 0 never expires
+}
+
+impl SasAttestation {
+    /// Reads an attestation from an account's data, ignoring any trailing bytes.
+    pub fn parse(data: &[u8]) -> Result<Self> {
+        let attestation = Self::deserialize(&mut &data[..])
+            .map_err(|_| error!(BountyForgeError::SasAttestationInvalid))?;
+        require!(
+            attestation.discriminator == ATTESTATION_DISCRIMINATOR,
+            BountyForgeError::SasAttestationInvalid
+        );
+        Ok(attestation)
+    }
+
+    /// Reads an attestation from an account the SAS program owns.
+    pub fn from_account(account: &AccountInfo) -> Result<Self> {
+        require_keys_eq!(
+            *account.owner,
+            SAS_PROGRAM_ID,
+            BountyForgeError::SasAttestationInvalid
+        );
+        Self::parse(&account.try_borrow_data()?)
+    }
+
+    pub fn holder(&self) -> &Pubkey {
+        &self.nonce
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.expiry != 0 && self.expiry <= now
+    }
+
+    /// The solution hash this attestation vouches for, if its data is one.
+    pub fn solution_hash(&self) -> Option<[u8; 32]> {
+        self.data.as_slice().try_into().ok()
+    }
+}
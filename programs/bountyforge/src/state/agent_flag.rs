@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+/// PDA: `["flag", market, agent]`.
+#[account]
+#[derive(InitSpace)]
+pub struct AgentFlag {
+    pub agent: Pubkey,
+    pub banned: bool,    // cleared again when the admin unflags the agent
+    pub updated_at: i64, // unix timestamp of the latest flag_agent
+    pub bump: u8,
+}
+
+impl AgentFlag {
+    /// Whether the agent's flag PDA holds an active ban. The PDA usually
+    /// doesn't exist, which counts as not banned.
+    pub fn is_banned(flag_account: &AccountInfo) -> Result<bool> {
+        if flag_account.owner != &crate::ID {
+            return Ok(false);
+        }
+        let data = flag_account.try_borrow_data()?;
+        Ok(AgentFlag::try_deserialize(&mut &data[..]).is_ok_and(|flag| flag.banned))
+    }
+}
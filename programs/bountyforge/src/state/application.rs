@@ -0,0 +1,19 @@
+use anchor_lang::prelude::*;
+
+/// PDA: `["application", bounty, agent]`.
+#[account]
+#[derive(InitSpace)]
+pub struct Application {
+    pub bounty: Pubkey,
+    pub agent: Pubkey,
+    pub note_hash: [u8; 32], // hash of the pitch shared with the creator off-chain
+    pub timestamp: i64,
+    pub status: ApplicationStatus,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum ApplicationStatus {
+    Pending,
+    Accepted, // the bounty is assigned to this agent
+}
@@ -1,12 +1,23 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::MAX_MARKET_LEN;
+use crate::state::HashAlgo;
+
+/// PDA: `["attestation", agent, bounty, solution_id as u64 LE]`.
 #[account]
 #[derive(InitSpace)]
 pub struct Attestation {
-    pub solution_id: u64,
+    pub solution_id: u64, // assigned from the agent's Reputation::next_solution_id
     pub solution_hash: [u8; 32],
+    pub hash_algo: HashAlgo,
     pub timestamp: i64,
     pub agent: Pubkey,
+    pub bounty: Pubkey,
     pub verified: bool,
+    pub attestor: Option<Pubkey>, // ed25519 countersigner, if the config required one
+    pub revoked: bool,            // set by revoke_attestation, kept for the audit trail
+    pub consumed: bool,           // backs one submission only, even if that one is rejected
+    #[max_len(MAX_MARKET_LEN)]
+    pub market: String, // the bounty's, kept so its Config can be found once the bounty is closed
     pub bump: u8,
 }
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::events::AttestorStatsUpdated;
+
+/// PDA: `["attestor", market, authority]`.
+#[account]
+#[derive(InitSpace)]
+pub struct Attestor {
+    pub authority: Pubkey, // the key allowed to sign attest_solution
+    #[max_len(32)]
+    pub name: String,
+    pub registered_at: i64, // unix timestamp of the latest (re-)registration
+    pub active: bool,       // cleared by remove_attestor
+    pub attestations_issued: u64, // created with attest_solution
+    pub attestations_consumed: u64, // backed a submission
+    pub attestations_disputed: u64, // backed a solution an arbiter didn't pay in full
+    pub bump: u8,
+}
+
+impl Attestor {
+    /// Consumed attestations less disputed ones, what a bounty's
+    /// `min_attestor_track_record` is compared against.
+    pub fn track_record(&self) -> u64 {
+        self.attestations_consumed
+            .saturating_sub(self.attestations_disputed)
+    }
+
+    /// Reads the Attestor PDA an instruction takes unchecked, returning `None`
+    /// when it doesn't exist, which counts as no track record.
+    pub fn load(attestor_account: &AccountInfo) -> Result<Option<Attestor>> {
+        if attestor_account.owner != &crate::ID {
+            return Ok(None);
+        }
+        let data = attestor_account.try_borrow_data()?;
+        Ok(Attestor::try_deserialize(&mut &data[..]).ok())
+    }
+
+    /// Applies `update` to the Attestor PDA, if it exists, and writes it back
+    /// with its counters published.
+    pub fn update(
+        attestor_account: &AccountInfo,
+        update: impl FnOnce(&mut Attestor),
+    ) -> Result<()> {
+        let Some(mut attestor) = Attestor::load(attestor_account)? else {
+            return Ok(());
+        };
+        update(&mut attestor);
+        attestor.try_serialize(&mut &mut attestor_account.try_borrow_mut_data()?[..])?;
+        attestor.emit_stats();
+        Ok(())
+    }
+
+    /// Publishes the counters for explorers to render an attestor's track record.
+    pub fn emit_stats(&self) {
+        emit!(AttestorStatsUpdated {
+            attestor: self.authority,
+            attestations_issued: self.attestations_issued,
+            attestations_consumed: self.attestations_consumed,
+            attestations_disputed: self.attestations_disputed,
+            track_record: self.track_record(),
+        });
+    }
+}
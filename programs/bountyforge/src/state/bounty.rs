@@ -1,28 +1,517 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{blake3, hash, keccak};
+
+use crate::constants::{
+    CURRENT_BOUNTY_VERSION, MAX_ALLOWLIST, MAX_DESCRIPTION_LEN, MAX_FEE_BPS, MAX_MARKET_LEN,
+    MAX_METADATA_URI_LEN, MAX_MILESTONES, MAX_ORACLE_FEEDS, MAX_PRIZES, SECONDS_PER_HOUR,
+};
+use crate::errors::BountyForgeError;
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum BountyType {
     WalletIntelligence,
     TokenScreening,
+    SmartContractAudit,
+    DataLabeling,
+    Custom(u16), // integrator-defined code, meaning agreed off-chain
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum RewardCurrency {
+    Spl, // escrowed in the bounty PDA's associated token account
+    Sol, // escrowed as lamports on the bounty PDA itself
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum OracleKind {
+    Switchboard, // on-demand pull feed
+    Pyth,        // pull oracle PriceUpdateV2 account
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AttestationSource {
+    Internal,               // BountyForge's own Attestation accounts
+    Sas { schema: Pubkey }, // Solana Attestation Service attestations under this schema
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum HashAlgo {
+    Sha256,
+    Keccak256,
+    Blake3,
+}
+
+impl BountyType {
+    /// Slot in `Reputation::completed_by_type`, custom codes share the last one.
+    pub fn stats_index(&self) -> usize {
+        match self {
+            BountyType::WalletIntelligence => 0,
+            BountyType::TokenScreening => 1,
+            BountyType::SmartContractAudit => 2,
+            BountyType::DataLabeling => 3,
+            BountyType::Custom(_) => 4,
+        }
+    }
+
+    /// Human-readable name, as written into badge metadata.
+    pub fn label(&self) -> String {
+        match self {
+            BountyType::WalletIntelligence => "WalletIntelligence".to_string(),
+            BountyType::TokenScreening => "TokenScreening".to_string(),
+            BountyType::SmartContractAudit => "SmartContractAudit".to_string(),
+            BountyType::DataLabeling => "DataLabeling".to_string(),
+            BountyType::Custom(code) => format!("Custom({code})"),
+        }
+    }
+}
+
+impl HashAlgo {
+    pub fn digest(&self, data: &[u8]) -> [u8; 32] {
+        match self {
+            HashAlgo::Sha256 => hash::hash(data).to_bytes(),
+            HashAlgo::Keccak256 => keccak::hash(data).to_bytes(),
+            HashAlgo::Blake3 => blake3::hash(data).to_bytes(),
+        }
+    }
 }
 
+/// PDA: `["bounty", original_creator, id as u64 LE]`, so each creator has
+/// their own id namespace. Before 0.2.0 the seeds were `["bounty", id as u64 LE]`.
+/// `version` goes up with every change to this layout, the previous one kept
+/// for `migrate_bounty`. Bounties 0.1.0 wrote are `BountyV0`.
 #[account]
 #[derive(InitSpace)]
 pub struct Bounty {
+    // fixed-size fields first, so they sit at the BOUNTY_*_OFFSET constants for memcmp filters
+    pub version: u8, // CURRENT_BOUNTY_VERSION once posted or migrated
     pub id: u64,
+    pub status: BountyStatus,
+    pub creator: Pubkey,
+    pub original_creator: Pubkey, // PDA seed, kept when ownership changes hands
+    pub reward_mint: Pubkey,      // Pubkey::default() for Sol bounties
+    pub reward: u64,              // lamports
+    pub bump: u8,
+    pub boosted_until: i64, // unix timestamp the bounty is featured until, 0 if never boosted
+    pub target: Pubkey,     // the wallet or mint the bounty is about, Pubkey::default() for none
+    // variable-size from here on, only this first one's variant tag has a fixed offset
     pub bounty_type: BountyType,
+    pub escrow_amount: u64, // actually held in escrow, net of any transfer fee
+    pub solution_hash: Option<[u8; 32]>,
+    pub solver: Option<Pubkey>,
+    pub reward_currency: RewardCurrency,
+    pub requires_oracle: bool,
+    #[max_len(MAX_ORACLE_FEEDS)]
+    pub oracle_feeds: Vec<Pubkey>, // all passed to submit_solution, non-empty whenever requires_oracle is
+    pub oracle_kind: OracleKind,
+    pub oracle_min_value: Option<i128>, // lowest feed value accepted, in units of 10^oracle_exponent
+    pub oracle_max_value: Option<i128>, // highest feed value accepted, likewise
+    pub oracle_exponent: i32,
+    pub deadline: i64,                         // unix timestamp
+    pub max_attestation_age: Option<i64>,      // overrides MAX_ATTESTATION_AGE_SECONDS
+    pub required_attestations: u8,             // distinct registered attestors, 0 for none
+    pub min_attestor_track_record: u64,        // Attestor::track_record the agent's attestor needs
+    pub attestation_source: AttestationSource, // what submit_solution accepts as the attestation
+    pub requires_reveal: bool,                 // settlement waits for reveal_solution
+    pub revealed: bool,                        // payload was shown to hash to solution_hash
+    pub requires_payment_receipt: bool,        // settle_bounty needs the gateway's signed receipt
+    pub hash_algo: HashAlgo,                   // how solution_hash is computed
+    pub encryption_pubkey: Option<[u8; 32]>,   // creator's x25519 key solutions are encrypted to
+    pub criteria_hash: [u8; 32], // commits to the acceptance criteria, zeroed for none
+    pub criteria_revealed: bool, // reveal_criteria showed the criteria hash to criteria_hash
+    pub allow_cpi: bool,         // submit_solution may be reached through another program's CPI
+    pub competitive: bool,       // stays Open while agents keep submitting
+    pub submission_count: u32,
+    pub max_submissions: u16, // submissions holding a slot at once, 0 for unlimited
+    pub live_submissions: u16, // submissions holding a slot, freed when one is rejected or withdrawn
+    #[max_len(MAX_PRIZES)]
+    pub prizes: Vec<u64>, // per-place payouts summing to reward, empty for a single winner
+    pub max_claims: u16, // settlements the escrow funds, one reward each, 1 for a single payout
+    pub claims_made: u16, // settlements paid so far, the bounty reopens after each until the last
+    pub allow_repeat_solver: bool, // one agent may be paid for several claims
+    pub mint_badge: bool, // settle_bounty mints the solver a soulbound badge
+    pub assignee: Option<Pubkey>, // only agent allowed to submit, None for anyone
+    pub requires_application: bool, // agents apply and the creator accepts one first
+    pub challenge_period_slots: u64, // fixed at posting, 0 lets settlement follow at once
+    pub submitted_at_slot: u64,
+    pub arbiter: Option<Pubkey>, // settles disputes between creator and solver
+    pub required_stake: u64,     // lamports each submission locks up, 0 for none
+    pub creator_bond: u64,       // lamports forfeited to the solver on force_settle, 0 for none
+    pub settle_window_slots: u64, // after the challenge period, 0 disables force_settle
+    pub resubmission_cooldown_seconds: u64, // a rejected agent waits this long to submit again
+    pub full_reward_until: i64,  // unix timestamp after which a solution pays less, 0 for no decay
+    pub decay_bps_per_hour: u16, // of the reward lost per hour a solution lands past full_reward_until
+    pub decay_floor_bps: u16,    // of the reward a late solution is always worth
+    pub creator_deposit: u64,    // escrowed by the creator, net of any transfer fee
+    pub contributed: u64,        // escrowed by contributors and not yet refunded to them
+    pub contributor_pool: u64,   // refunded escrow held back for refund_contribution
+    pub total_boost_paid: u64,   // boost fees paid to the treasury, in the reward's base units
+    pub pending_creator: Option<Pubkey>, // set by transfer_bounty_ownership, cleared on accept
+    pub rent_payer: Pubkey,      // paid the bounty's and escrow's rent, refunded on close
+    pub appellant: Option<Pubkey>, // agent contesting a rejection, the only one payable meanwhile
+    pub appeal_ends_at: i64,     // unix timestamp the appeal stops holding up settlement
+    pub min_reputation: u64,     // successful_bounties an agent needs to submit, 0 for anyone
+    pub allowlist: [Pubkey; MAX_ALLOWLIST], // only the first allowlist_len entries are used
+    pub allowlist_len: u8,       // 0 lets any agent submit
+    #[max_len(MAX_MILESTONES)]
+    pub milestones: Vec<Milestone>, // paid one by one in order, empty for a single payout
+    pub vesting_duration_seconds: u64, // reward streams to the solver after settlement, 0 pays at once
+    pub vesting_start: i64,            // unix timestamp of the settlement that started vesting
+    pub vesting_amount: u64,           // solver's share being vested, net of the protocol fee
+    pub vested_claimed: u64,           // already pulled with claim_vested
+    pub feedback_given: bool,          // the creator has rated the solver with leave_feedback
+    pub created_at: i64,               // unix timestamp of post_bounty
+    pub submitted_at: i64, // unix timestamp of the latest solution taken on, 0 if none yet
+    pub settled_at: i64,   // unix timestamp of settlement, 0 if not settled
+    #[max_len(MAX_DESCRIPTION_LEN)]
+    pub description: String,
+    #[max_len(MAX_METADATA_URI_LEN)]
+    pub metadata_uri: String, // JSON document with the full task, empty for none
+    pub settlement_authority: Option<Pubkey>, // may settle and reject alongside the creator
+    #[max_len(MAX_MARKET_LEN)]
+    pub market: String, // seeds the Config and BountyStats it answers to, empty for the default market
+}
+
+impl Bounty {
+    /// First slot at which the submitted solution can be settled.
+    pub fn challenge_ends_at_slot(&self) -> u64 {
+        self.submitted_at_slot
+            .saturating_add(self.challenge_period_slots)
+    }
+
+    /// Part of a refund of `amount` owed to contributors, in proportion to
+    /// what they put in against the creator. Rounds down in the creator's favour.
+    pub fn contributors_share(&self, amount: u64) -> Result<u64> {
+        if self.contributed == 0 {
+            return Ok(0);
+        }
+        let funded = (self.creator_deposit as u128) + (self.contributed as u128);
+        (amount as u128)
+            .checked_mul(self.contributed as u128)
+            .and_then(|scaled| scaled.checked_div(funded))
+            .and_then(|share| u64::try_from(share).ok())
+            .ok_or(error!(BountyForgeError::FeeCalculationOverflow))
+    }
+
+    /// Refuses to pay anyone but the appellant while their appeal is within
+    /// its window and no arbiter has ruled on it.
+    pub fn check_appeal(&self, agent: &Pubkey, now: i64) -> Result<()> {
+        if let Some(appellant) = self.appellant {
+            require!(
+                appellant == *agent || now >= self.appeal_ends_at,
+                BountyForgeError::AppealPending
+            );
+        }
+        Ok(())
+    }
+
+    /// Takes a submission slot, failing once `max_submissions` are held.
+    /// Returns the slots left afterwards, `None` when the bounty is unlimited.
+    pub fn take_submission_slot(&mut self) -> Result<Option<u16>> {
+        require!(
+            self.max_submissions == 0 || self.live_submissions < self.max_submissions,
+            BountyForgeError::SubmissionLimitReached
+        );
+        self.live_submissions = self.live_submissions.saturating_add(1);
+        Ok((self.max_submissions > 0).then(|| self.max_submissions - self.live_submissions))
+    }
+
+    /// Frees the slot of a submission that was rejected or withdrawn.
+    pub fn release_submission_slot(&mut self) {
+        self.live_submissions = self.live_submissions.saturating_sub(1);
+    }
+
+    /// Whether the bounty pays out more than once, reopening after each settlement.
+    pub fn is_repeatable(&self) -> bool {
+        self.max_claims > 1
+    }
+
+    /// Escrow the next settlement releases: an even share of what is left over
+    /// the claims still to be paid, so the last one takes any remainder.
+    pub fn claim_amount(&self) -> u64 {
+        let claims_left = self.max_claims.saturating_sub(self.claims_made).max(1);
+        self.escrow_amount / claims_left as u64
+    }
+
+    /// Agents allowed to submit, empty if the bounty is open to all.
+    pub fn allowlisted_agents(&self) -> &[Pubkey] {
+        &self.allowlist[..self.allowlist_len as usize]
+    }
+
+    /// Whether `signer` may settle or reject: the creator, or the settlement
+    /// authority they delegated to. Cancelling and changing the reward stay with the creator.
+    pub fn can_settle(&self, signer: &Pubkey) -> bool {
+        *signer == self.creator || self.settlement_authority.as_ref() == Some(signer)
+    }
+
+    /// Checks that a feed's `value * 10^exponent`, rounded down to the
+    /// bounty's `oracle_exponent`, falls within its oracle bounds.
+    pub fn check_oracle_value(&self, value: i128, exponent: i32) -> Result<()> {
+        if self.oracle_min_value.is_none() && self.oracle_max_value.is_none() {
+            return Ok(());
+        }
+        let shift = exponent.abs_diff(self.oracle_exponent);
+        let scale = 10i128
+            .checked_pow(shift)
+            .ok_or(BountyForgeError::OracleValueOutOfRange)?;
+        let value = if exponent >= self.oracle_exponent {
+            value.checked_mul(scale)
+        } else {
+            value.checked_div_euclid(scale)
+        }
+        .ok_or(BountyForgeError::OracleValueOutOfRange)?;
+
+        require!(
+            self.oracle_min_value.is_none_or(|min| value >= min)
+                && self.oracle_max_value.is_none_or(|max| value <= max),
+            BountyForgeError::OracleValueOutOfRange
+        );
+        Ok(())
+    }
+
+    pub fn is_allowlisted(&self, agent: &Pubkey) -> bool {
+        self.allowlist_len == 0 || self.allowlisted_agents().contains(agent)
+    }
+
+    /// Whether a milestone is waiting on the creator to settle it.
+    pub fn has_submitted_milestone(&self) -> bool {
+        self.milestones
+            .iter()
+            .any(|milestone| milestone.status == MilestoneStatus::Submitted)
+    }
+
+    /// Part of the vesting amount unlocked by `now`, rounded down so the
+    /// total claimed can never exceed it.
+    pub fn vested_at(&self, now: i64) -> Result<u64> {
+        if self.vesting_duration_seconds == 0 {
+            return Ok(self.vesting_amount);
+        }
+        let elapsed = now.saturating_sub(self.vesting_start).max(0) as u64;
+        let elapsed = elapsed.min(self.vesting_duration_seconds);
+        (self.vesting_amount as u128)
+            .checked_mul(elapsed as u128)
+            .and_then(|scaled| scaled.checked_div(self.vesting_duration_seconds as u128))
+            .and_then(|vested| u64::try_from(vested).ok())
+            .ok_or(error!(BountyForgeError::RewardOverflow))
+    }
+
+    /// Stamps when the solution the bounty now waits on came in, which the
+    /// challenge and settle windows count from.
+    pub fn mark_submitted(&mut self) -> Result<()> {
+        let clock = Clock::get()?;
+        self.submitted_at_slot = clock.slot;
+        self.submitted_at = clock.unix_timestamp;
+        Ok(())
+    }
+
+    /// Part of `amount` a settlement pays for the solution taken on at
+    /// `submitted_at`: all of it up to `full_reward_until`, then
+    /// `decay_bps_per_hour` less for every hour past it, pro rata to the
+    /// second, but never below `decay_floor_bps`. Rounds down, so whatever
+    /// is held back goes back to the creator.
+    pub fn graded_amount(&self, amount: u64) -> Result<u64> {
+        if self.full_reward_until == 0 || self.submitted_at <= self.full_reward_until {
+            return Ok(amount);
+        }
+        let late_seconds = self.submitted_at.saturating_sub(self.full_reward_until) as u128;
+        let decay_bps = (self.decay_bps_per_hour as u128)
+            .saturating_mul(late_seconds)
+            .checked_div(SECONDS_PER_HOUR as u128)
+            .unwrap_or(0)
+            .min(MAX_FEE_BPS.saturating_sub(self.decay_floor_bps) as u128);
+        (amount as u128)
+            .checked_mul(MAX_FEE_BPS as u128 - decay_bps)
+            .and_then(|scaled| scaled.checked_div(MAX_FEE_BPS as u128))
+            .and_then(|graded| u64::try_from(graded).ok())
+            .ok_or(error!(BountyForgeError::RewardOverflow))
+    }
+
+    /// Whether the creator may reject or have a dispute resolved against
+    /// their acceptance criteria: they committed to none, or revealed them.
+    pub fn criteria_disclosed(&self) -> bool {
+        self.criteria_hash == [0; 32] || self.criteria_revealed
+    }
+
+    /// Latest lifecycle timestamp, which sweep_abandoned_bounty measures abandonment from.
+    pub fn last_activity_at(&self) -> i64 {
+        self.created_at.max(self.submitted_at).max(self.settled_at)
+    }
+
+    pub fn mark_settled(&mut self) -> Result<()> {
+        self.status = BountyStatus::Settled;
+        self.settled_at = Clock::get()?.unix_timestamp;
+        Ok(())
+    }
+
+    /// First slot at which the solver can settle without the creator.
+    pub fn force_settle_at_slot(&self) -> u64 {
+        self.challenge_ends_at_slot()
+            .saturating_add(self.settle_window_slots)
+    }
+}
+
+/// Bounty as 0.1.0 wrote it, at `["bounty", id as u64 LE]` with its USDC in
+/// that PDA's ATA. Only read by `migrate_bounty`, which moves it to a current
+/// `Bounty` at the creator's next id.
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct BountyV0 {
+    pub id: u64,
+    pub bounty_type: BountyTypeV0,
     #[max_len(50)]
     pub description: String,
-    pub reward: u64, // lamports
+    pub reward: u64,
     pub solution_hash: Option<[u8; 32]>,
-    pub status: BountyStatus,
+    pub status: BountyStatusV0,
     pub creator: Pubkey,
     pub bump: u8,
 }
 
+impl BountyV0 {
+    // post_bounty allocated 32 bytes of padding past the fields
+    pub const SPACE: usize = Self::INIT_SPACE + 32;
+
+    /// The bounty on the current layout, posted in the default market with
+    /// `escrow_amount` of `reward_mint` escrowed and no options set. 0.1.0
+    /// kept no record of who submitted, so a submitted bounty reopens and its
+    /// agent submits again. It never expires, as 0.1.0 had no deadlines, and
+    /// counts as created at `now` so it isn't swept as abandoned.
+    pub fn into_bounty(
+        self,
+        id: u64,
+        bump: u8,
+        reward_mint: Pubkey,
+        escrow_amount: u64,
+        rent_payer: Pubkey,
+        now: i64,
+    ) -> Bounty {
+        let settled = self.status == BountyStatusV0::Settled;
+        Bounty {
+            version: CURRENT_BOUNTY_VERSION,
+            id,
+            status: if settled {
+                BountyStatus::Settled
+            } else {
+                BountyStatus::Open
+            },
+            creator: self.creator,
+            original_creator: self.creator,
+            reward_mint,
+            reward: self.reward,
+            bump,
+            boosted_until: 0,
+            target: Pubkey::default(),
+            bounty_type: match self.bounty_type {
+                BountyTypeV0::WalletIntelligence => BountyType::WalletIntelligence,
+                BountyTypeV0::TokenScreening => BountyType::TokenScreening,
+            },
+            escrow_amount,
+            solution_hash: if settled { self.solution_hash } else { None },
+            solver: None,
+            reward_currency: RewardCurrency::Spl,
+            requires_oracle: false,
+            oracle_feeds: Vec::new(),
+            oracle_kind: OracleKind::Switchboard,
+            oracle_min_value: None,
+            oracle_max_value: None,
+            oracle_exponent: 0,
+            deadline: i64::MAX,
+            max_attestation_age: None,
+            required_attestations: 0,
+            min_attestor_track_record: 0,
+            attestation_source: AttestationSource::Internal,
+            requires_reveal: false,
+            revealed: false,
+            criteria_hash: [0; 32],
+            criteria_revealed: false,
+            allow_cpi: false,
+            requires_payment_receipt: false,
+            hash_algo: HashAlgo::Sha256,
+            encryption_pubkey: None,
+            competitive: false,
+            submission_count: 0,
+            max_submissions: 0,
+            live_submissions: 0,
+            prizes: Vec::new(),
+            max_claims: 1,
+            claims_made: u16::from(settled),
+            allow_repeat_solver: false,
+            mint_badge: false,
+            assignee: None,
+            requires_application: false,
+            challenge_period_slots: 0,
+            submitted_at_slot: 0,
+            arbiter: None,
+            required_stake: 0,
+            creator_bond: 0,
+            settle_window_slots: 0,
+            resubmission_cooldown_seconds: 0,
+            full_reward_until: 0,
+            decay_bps_per_hour: 0,
+            decay_floor_bps: 0,
+            creator_deposit: escrow_amount,
+            contributed: 0,
+            contributor_pool: 0,
+            total_boost_paid: 0,
+            pending_creator: None,
+            rent_payer,
+            appellant: None,
+            appeal_ends_at: 0,
+            min_reputation: 0,
+            allowlist: [Pubkey::default(); MAX_ALLOWLIST],
+            allowlist_len: 0,
+            milestones: Vec::new(),
+            vesting_duration_seconds: 0,
+            vesting_start: 0,
+            vesting_amount: 0,
+            vested_claimed: 0,
+            feedback_given: false,
+            created_at: now,
+            submitted_at: 0,
+            settled_at: 0,
+            description: self.description,
+            metadata_uri: String::new(),
+            settlement_authority: None,
+            market: String::new(),
+        }
+    }
+}
+
+#[derive(AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum BountyTypeV0 {
+    WalletIntelligence,
+    TokenScreening,
+}
+
+#[derive(AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum BountyStatusV0 {
+    Open,
+    Submitted,
+    Settled,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct Milestone {
+    pub amount: u64,
+    pub description_hash: [u8; 32], // hash of the deliverable, described off-chain
+    pub solution_hash: Option<[u8; 32]>,
+    pub status: MilestoneStatus,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum MilestoneStatus {
+    Pending,
+    Submitted,
+    Paid,
+}
+
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
 pub enum BountyStatus {
     Open,
     Submitted,
+    Approved,
     Settled,
+    Cancelled,
+    Expired,
+    Challenged, // settlement waits for resolve_challenge
+    Disputed,   // settlement waits for the arbiter's resolve_dispute
+    Swept,      // abandoned, its escrow moved to the treasury by sweep_abandoned_bounty
 }
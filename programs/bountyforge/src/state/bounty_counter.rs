@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+/// PDA: `["counter", creator]`.
+#[account]
+#[derive(InitSpace)]
+pub struct BountyCounter {
+    pub creator: Pubkey,
+    pub next_id: u64, // id post_bounty assigns to this creator's next bounty
+    pub bump: u8,
+}
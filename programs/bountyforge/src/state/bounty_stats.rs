@@ -0,0 +1,64 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BountyForgeError;
+
+/// PDA: `["stats", market]`. Market-wide totals for dashboards, only ever added to.
+/// Every posting and settlement writes it, so those transactions can't run in
+/// parallel with each other within a slot. Zero-copy like `Leaderboard`, with
+/// the fields up to `bump` where the Borsh layout had them.
+#[account(zero_copy)]
+#[derive(InitSpace)]
+pub struct BountyStats {
+    pub bounties_posted: u64,
+    pub bounties_submitted: u64, // bounties that took on their first solution
+    pub bounties_settled: u64,
+    pub bounties_cancelled: u64, // cancelled by the creator, or refunded in full by an arbiter
+    pub total_escrowed: u128,    // escrowed at posting, every mint's base units summed together
+    pub total_paid: u128,        // escrow released to solvers, fees included, same units
+    pub bump: u8,
+    pub padding: [u8; 15], // rounds the size up to the totals' alignment, always zero
+}
+
+impl BountyStats {
+    /// Length of the Borsh layout before the padding, grown by `migrate_stats`.
+    pub const LEGACY_SPACE: usize = Self::INIT_SPACE - 15;
+
+    pub fn record_posted(&mut self, escrowed: u64) -> Result<()> {
+        self.bounties_posted = Self::increment(self.bounties_posted)?;
+        self.total_escrowed = self
+            .total_escrowed
+            .checked_add(escrowed as u128)
+            .ok_or(BountyForgeError::StatsOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_submitted(&mut self) -> Result<()> {
+        self.bounties_submitted = Self::increment(self.bounties_submitted)?;
+        Ok(())
+    }
+
+    pub fn record_settled(&mut self, paid: u64) -> Result<()> {
+        self.bounties_settled = Self::increment(self.bounties_settled)?;
+        self.record_payout(paid)
+    }
+
+    /// Counts a payout made before the bounty is settled, such as an earlier claim or milestone.
+    pub fn record_payout(&mut self, paid: u64) -> Result<()> {
+        self.total_paid = self
+            .total_paid
+            .checked_add(paid as u128)
+            .ok_or(BountyForgeError::StatsOverflow)?;
+        Ok(())
+    }
+
+    pub fn record_cancelled(&mut self) -> Result<()> {
+        self.bounties_cancelled = Self::increment(self.bounties_cancelled)?;
+        Ok(())
+    }
+
+    fn increment(count: u64) -> Result<u64> {
+        count
+            .checked_add(1)
+            .ok_or(error!(BountyForgeError::StatsOverflow))
+    }
+}
@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// PDA: `["challenge", bounty]`.
+#[account]
+#[derive(InitSpace)]
+pub struct Challenge {
+    pub bounty: Pubkey,
+    pub challenger: Pubkey,
+    pub bond: u64, // lamports held on this account on top of its rent
+    pub created_at: i64,
+    pub bump: u8,
+}
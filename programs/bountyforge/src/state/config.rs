@@ -0,0 +1,117 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    MAX_ATTESTORS, MAX_FEE_BPS, MAX_MARKET_LEN, MAX_TRUSTED_MINTS, SCORE_PER_SUBMISSION,
+};
+use crate::errors::BountyForgeError;
+
+/// PDA: `["config", market]`, the default market's name being empty so its
+/// address is that of the original `["config"]`.
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>, // set by update_config, cleared by accept_admin
+    pub fee_bps: u16,
+    pub treasury: Pubkey,
+    pub paused: bool,
+    pub verifier: Pubkey, // the only signer allowed to verify attestations
+    pub trusted_attestor: Option<Pubkey>, // must countersign every attestation when set
+    #[max_len(MAX_ATTESTORS)]
+    pub attestors: Vec<Pubkey>, // may countersign, counted towards bounty thresholds
+    pub default_challenge_period_slots: u64, // for bounties posted without their own
+    pub challenge_bond_lamports: u64,
+    pub challenge_reward_bps: u16, // share of the escrow paid for an upheld challenge
+    pub stake_slash_treasury_bps: u16, // share of a slashed stake for the treasury, the rest to the creator
+    pub default_settle_window_slots: u64, // for bounties posted without their own, 0 disables force_settle
+    pub max_open_per_creator: u32,        // open bounties a creator may hold at once
+    pub min_reward: u64,                  // in the reward's base units, never 0
+    pub max_reward: u64,                  // also caps what top-ups can grow a reward to
+    pub receipt_retention_seconds: u64,   // how long a settlement receipt must be kept
+    pub attestor_fee_bps: u16, // share of a settle_bounty payout for the backing attestor
+    pub score_unit: u64,       // reward that earns a second point of score, 0 gives every bounty 1
+    pub max_score_per_bounty: u64, // caps the weighted score, at least 1
+    pub decay_period_seconds: u64, // inactivity that halves an agent's score, 0 disables decay
+    pub allow_self_settlement: bool, // lets creators work their own bounties, for internal deployments
+    pub crank_fee_bps: u16, // share of an expired bounty's refund paid to whoever cranks it
+    pub payment_gateway: Option<Pubkey>, // signs x402 receipts for bounties that require them
+    pub default_resubmission_cooldown_seconds: u64, // for bounties posted without their own
+    pub abandonment_seconds: u64, // inactivity after which the admin may sweep a bounty's escrow, never 0
+    #[max_len(MAX_TRUSTED_MINTS)]
+    pub trusted_mints: Vec<Pubkey>, // reward mints exempt from the escrow safety checks
+    pub boost_fee_bps: u16, // share of a bounty's reward charged by boost_bounty, paid to the treasury
+    pub boost_duration_seconds: u64, // how long each boost features a bounty, 0 disables boosting
+    pub attestation_ttl_seconds: u64, // age at which an unused attestation can be closed, 0 never
+    pub attestation_close_tip_lamports: u64, // of the closed attestation's rent for the cranker
+    pub insurance_fee_bps: u16, // share of settle_bounty's protocol fee paid into the mint's insurance fund
+    pub max_insurance_topup: u64, // largest escrow shortfall the insurance fund covers, 0 disables it
+    pub primary_mint: Option<Pubkey>, // the only mint counted in Reputation::total_earned, every one when unset
+    pub appeal_window_seconds: u64, // how long an agent has to appeal a rejection, 0 disables appeals
+    pub challenge_forfeit_bps: u16, // share of a dismissed challenge's bond paid to the solver
+    pub min_verification_fee: u64, // least a gateway receipt must show was paid, in the gateway's units
+    #[max_len(MAX_MARKET_LEN)]
+    pub market: String, // the market this config governs, empty for the default one
+    pub bump: u8,
+}
+
+impl Config {
+    pub fn is_registered_attestor(&self, key: &Pubkey) -> bool {
+        self.trusted_attestor.as_ref() == Some(key) || self.attestors.contains(key)
+    }
+
+    /// Checked when a bounty is posted and whenever its reward grows, so a
+    /// bounty posted under older bounds stays valid until it is topped up.
+    pub fn check_reward(&self, reward: u64) -> Result<()> {
+        require!(reward >= self.min_reward, BountyForgeError::RewardTooSmall);
+        require!(reward <= self.max_reward, BountyForgeError::RewardTooLarge);
+        Ok(())
+    }
+
+    /// A creator solving their own bounty would earn reputation for nothing
+    /// but rent and fees, so only deployments that opt in allow it.
+    pub fn check_not_self_dealing(&self, creator: &Pubkey, agent: &Pubkey) -> Result<()> {
+        require!(
+            self.allow_self_settlement || creator != agent,
+            BountyForgeError::SelfDealingNotAllowed
+        );
+        Ok(())
+    }
+
+    /// The protocol and attestor fees come out of the same escrow, so
+    /// together they can take at most all of it.
+    pub fn check_fees(fee_bps: u16, attestor_fee_bps: u16) -> Result<()> {
+        require!(
+            (fee_bps as u32) + (attestor_fee_bps as u32) <= MAX_FEE_BPS as u32,
+            BountyForgeError::InvalidFeeBps
+        );
+        Ok(())
+    }
+
+    /// Score a settled bounty is worth: `1 + floor(log2(reward / score_unit))`,
+    /// with the division rounding down too, so each doubling of the reward
+    /// past one unit adds a point. Rewards below one unit, and every reward
+    /// when `score_unit` is 0, are worth 1. Never more than `max_score_per_bounty`.
+    pub fn score_weight(&self, reward: u64) -> u64 {
+        let units = reward.checked_div(self.score_unit).unwrap_or(0);
+        let doublings = units.checked_ilog2().map_or(0, u64::from);
+        SCORE_PER_SUBMISSION
+            .saturating_add(doublings)
+            .min(self.max_score_per_bounty)
+    }
+
+    pub fn check_score_cap(max_score_per_bounty: u64) -> Result<()> {
+        require!(
+            max_score_per_bounty >= SCORE_PER_SUBMISSION,
+            BountyForgeError::InvalidScoreCap
+        );
+        Ok(())
+    }
+
+    pub fn check_reward_bounds(min_reward: u64, max_reward: u64) -> Result<()> {
+        require!(
+            min_reward > 0 && min_reward <= max_reward,
+            BountyForgeError::InvalidRewardBounds
+        );
+        Ok(())
+    }
+}
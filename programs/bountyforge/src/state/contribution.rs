@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+/// PDA: `["contrib", bounty, contributor]`.
+#[account]
+#[derive(InitSpace)]
+pub struct Contribution {
+    pub bounty: Pubkey,
+    pub contributor: Pubkey,
+    pub amount: u64, // total that landed in escrow, net of any transfer fee
+    pub bump: u8,
+}
@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+/// PDA: `["bond", bounty]`.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorBond {
+    pub bounty: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64, // lamports held on this account on top of its rent
+    pub bump: u8,
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_INDEXED_BOUNTIES;
+
+/// PDA: `["index", creator]`.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorIndex {
+    pub creator: Pubkey,
+    #[max_len(MAX_INDEXED_BOUNTIES)]
+    pub bounty_ids: Vec<u64>, // posted and not yet closed, oldest first
+    pub bump: u8,
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::events::CreatorStatsUpdated;
+
+/// PDA: `["creator", creator]`.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorProfile {
+    pub creator: Pubkey,
+    pub open_bounty_count: u32,
+    pub last_post_ts: i64,
+    pub disputes_lost: u32, // arbiter found the solver should have been paid
+    pub total_posted: u64,
+    pub total_settled: u64,   // bounties that ended with a solver paid
+    pub total_cancelled: u64, // cancelled or expired by the creator with nobody paid
+    pub total_paid_out: u64, // escrow released on settlement, fees included, in each bounty's base units
+    pub total_settle_delay_slots: u64, // summed slots from submission to settlement
+    pub timed_settlements: u64, // settlements counted in total_settle_delay_slots
+    pub bump: u8,
+}
+
+impl CreatorProfile {
+    pub fn release_open_slot(&mut self) {
+        self.open_bounty_count = self.open_bounty_count.saturating_sub(1);
+    }
+
+    /// Frees the slot of a bounty that ended with a solver paid, and times
+    /// how long the creator took to pay from when the solution came in.
+    pub fn record_settlement(&mut self, paid_out: u64, submitted_at_slot: u64) -> Result<()> {
+        self.release_open_slot();
+        self.total_settled = self.total_settled.saturating_add(1);
+        self.record_payout(paid_out);
+
+        // competitive bounties settled straight from their submissions never set it
+        if submitted_at_slot > 0 {
+            let delay = Clock::get()?.slot.saturating_sub(submitted_at_slot);
+            self.total_settle_delay_slots = self.total_settle_delay_slots.saturating_add(delay);
+            self.timed_settlements = self.timed_settlements.saturating_add(1);
+        }
+        Ok(())
+    }
+
+    /// Counts a payout made before the bounty is settled, such as an earlier milestone.
+    pub fn record_payout(&mut self, paid_out: u64) {
+        self.total_paid_out = self.total_paid_out.saturating_add(paid_out);
+    }
+
+    /// Frees the slot of a bounty the creator took back without paying anyone.
+    pub fn record_cancellation(&mut self) {
+        self.release_open_slot();
+        self.total_cancelled = self.total_cancelled.saturating_add(1);
+    }
+
+    pub fn avg_settle_delay_slots(&self) -> u64 {
+        self.total_settle_delay_slots
+            .checked_div(self.timed_settlements)
+            .unwrap_or(0)
+    }
+
+    /// Publishes the counters for explorers to render a creator's track record.
+    pub fn emit_stats(&self) {
+        emit!(CreatorStatsUpdated {
+            creator: self.creator,
+            total_posted: self.total_posted,
+            total_settled: self.total_settled,
+            total_cancelled: self.total_cancelled,
+            total_paid_out: self.total_paid_out,
+            avg_settle_delay_slots: self.avg_settle_delay_slots(),
+            disputes_lost: self.disputes_lost,
+        });
+    }
+}
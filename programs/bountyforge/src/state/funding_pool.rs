@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BountyForgeError;
+use crate::events::FundingPoolUpdated;
+
+/// PDA: `["pool", creator, mint]`. Its ATA for `mint` holds the deposits.
+#[account]
+#[derive(InitSpace)]
+pub struct FundingPool {
+    pub creator: Pubkey,
+    pub mint: Pubkey,
+    pub deposited: u64, // landed in the pool's ATA, less withdrawals
+    pub allocated: u64, // moved on to the escrows of bounties posted from the pool
+    pub bump: u8,
+}
+
+impl FundingPool {
+    pub fn available(&self) -> u64 {
+        self.deposited.saturating_sub(self.allocated)
+    }
+
+    /// Earmarks `amount` for a bounty, refusing more than the pool has left.
+    pub fn allocate(&mut self, amount: u64) -> Result<()> {
+        let allocated = self
+            .allocated
+            .checked_add(amount)
+            .ok_or(BountyForgeError::PoolInsufficient)?;
+        require!(
+            allocated <= self.deposited,
+            BountyForgeError::PoolInsufficient
+        );
+        self.allocated = allocated;
+        Ok(())
+    }
+
+    /// Takes back `amount` of what hasn't been allocated.
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        require!(
+            amount <= self.available(),
+            BountyForgeError::PoolInsufficient
+        );
+        self.deposited -= amount;
+        Ok(())
+    }
+
+    pub fn emit_update(&self, pool: Pubkey) -> Result<()> {
+        emit!(FundingPoolUpdated {
+            pool,
+            creator: self.creator,
+            mint: self.mint,
+            deposited: self.deposited,
+            allocated: self.allocated,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+}
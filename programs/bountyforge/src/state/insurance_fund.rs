@@ -0,0 +1,73 @@
+use anchor_lang::prelude::*;
+
+use crate::errors::BountyForgeError;
+use crate::events::InsuranceFundUpdated;
+
+/// PDA: `["insurance", market, mint]`. Its ATA for `mint` holds the fund, which
+/// settle_bounty draws on when an escrow comes up slightly short.
+#[account]
+#[derive(InitSpace)]
+pub struct InsuranceFund {
+    pub mint: Pubkey,
+    pub balance: u64,         // deposits and fees in, less top-ups and withdrawals
+    pub total_deposited: u64, // landed from the admin's deposits
+    pub total_fees: u64,      // landed from settle_bounty's fee share
+    pub total_topups: u64,    // paid into short escrows
+    pub total_withdrawn: u64, // taken back by the admin
+    pub bump: u8,
+}
+
+impl InsuranceFund {
+    pub fn record_deposit(&mut self, landed: u64) -> Result<()> {
+        self.balance = Self::add(self.balance, landed)?;
+        self.total_deposited = Self::add(self.total_deposited, landed)?;
+        Ok(())
+    }
+
+    pub fn record_fee(&mut self, landed: u64) -> Result<()> {
+        self.balance = Self::add(self.balance, landed)?;
+        self.total_fees = Self::add(self.total_fees, landed)?;
+        Ok(())
+    }
+
+    /// Covers a shortfall, refusing more than the fund holds.
+    pub fn draw_topup(&mut self, amount: u64) -> Result<()> {
+        self.take(amount)?;
+        self.total_topups = Self::add(self.total_topups, amount)?;
+        Ok(())
+    }
+
+    pub fn withdraw(&mut self, amount: u64) -> Result<()> {
+        self.take(amount)?;
+        self.total_withdrawn = Self::add(self.total_withdrawn, amount)?;
+        Ok(())
+    }
+
+    pub fn emit_update(&self, fund: Pubkey) -> Result<()> {
+        emit!(InsuranceFundUpdated {
+            fund,
+            mint: self.mint,
+            balance: self.balance,
+            total_deposited: self.total_deposited,
+            total_fees: self.total_fees,
+            total_topups: self.total_topups,
+            total_withdrawn: self.total_withdrawn,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+        Ok(())
+    }
+
+    fn take(&mut self, amount: u64) -> Result<()> {
+        self.balance = self
+            .balance
+            .checked_sub(amount)
+            .ok_or(BountyForgeError::InsuranceFundInsufficient)?;
+        Ok(())
+    }
+
+    fn add(total: u64, amount: u64) -> Result<u64> {
+        total
+            .checked_add(amount)
+            .ok_or(error!(BountyForgeError::RewardOverflow))
+    }
+}
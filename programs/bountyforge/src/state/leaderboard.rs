@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::LEADERBOARD_SIZE;
+
+#[zero_copy]
+#[derive(Default, InitSpace)]
+pub struct LeaderboardEntry {
+    pub agent: Pubkey,
+    pub score: u64,
+}
+
+/// PDA: `["leaderboard"]`. The highest reputation scores, so clients can show
+/// a ranking without fetching every `Reputation`. Like `BountyStats`, every
+/// score change writes it, so it is zero-copy: a score change touches the
+/// entries it moves instead of deserializing and writing back all of them.
+/// The fields up to `bump` sit where the Borsh layout had them, so
+/// `migrate_leaderboard` only has to add the padding.
+#[account(zero_copy)]
+#[derive(InitSpace)]
+pub struct Leaderboard {
+    pub entries: [LeaderboardEntry; LEADERBOARD_SIZE], // highest score first, ties in order of arrival
+    pub len: u8,                                       // entries in use, the rest are default
+    pub bump: u8,
+    pub padding: [u8; 6], // rounds the size up to the entries' alignment, always zero
+}
+
+impl Leaderboard {
+    /// Length of the Borsh layout before the padding, grown by `migrate_leaderboard`.
+    pub const LEGACY_SPACE: usize = Self::INIT_SPACE - 6;
+
+    /// Records an agent's new score: updates its entry if it has one, or takes
+    /// a free slot, or replaces the lowest entry if it beats it, then moves it
+    /// into place. Bounded by LEADERBOARD_SIZE and never allocates.
+    ///
+    /// An entry whose score dropped stays on the board even if an agent not on
+    /// it now scores higher; that agent takes its place at its next score change.
+    pub fn record(&mut self, agent: Pubkey, score: u64) {
+        let len = self.len as usize;
+        let slot = match self.entries[..len].iter().position(|e| e.agent == agent) {
+            Some(index) => index,
+            None if score == 0 => return,
+            None if len < LEADERBOARD_SIZE => {
+                self.len += 1;
+                len
+            }
+            None if score > self.entries[len - 1].score => len - 1,
+            None => return,
+        };
+        self.entries[slot] = LeaderboardEntry { agent, score };
+
+        // only this entry can be out of order, so a single pass either way sorts it
+        let len = self.len as usize;
+        let mut index = slot;
+        while index > 0 && self.entries[index - 1].score < score {
+            self.entries.swap(index - 1, index);
+            index -= 1;
+        }
+        while index + 1 < len && self.entries[index + 1].score > score {
+            self.entries.swap(index, index + 1);
+            index += 1;
+        }
+    }
+}
@@ -1,7 +1,47 @@
+pub mod agent_flag;
+pub mod application;
 pub mod attestation;
+pub mod attestor;
 pub mod bounty;
+pub mod bounty_counter;
+pub mod bounty_stats;
+pub mod challenge;
+pub mod config;
+pub mod contribution;
+pub mod creator_bond;
+pub mod creator_index;
+pub mod creator_profile;
+pub mod funding_pool;
+pub mod insurance_fund;
+pub mod leaderboard;
+pub mod operator;
+pub mod rejection;
 pub mod reputation;
+pub mod reputation_snapshot;
+pub mod settlement_receipt;
+pub mod settlement_record;
+pub mod submission;
 
+pub use agent_flag::*;
+pub use application::*;
 pub use attestation::*;
+pub use attestor::*;
 pub use bounty::*;
-pub use reputation::*;
\ No newline at end of file
+pub use bounty_counter::*;
+pub use bounty_stats::*;
+pub use challenge::*;
+pub use config::*;
+pub use contribution::*;
+pub use creator_bond::*;
+pub use creator_index::*;
+pub use creator_profile::*;
+pub use funding_pool::*;
+pub use insurance_fund::*;
+pub use leaderboard::*;
+pub use operator::*;
+pub use rejection::*;
+pub use reputation::*;
+pub use reputation_snapshot::*;
+pub use settlement_receipt::*;
+pub use settlement_record::*;
+pub use submission::*;
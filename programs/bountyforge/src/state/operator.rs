@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// PDA: `["operator", agent, operator]`. Lets `operator` submit solutions on
+/// the agent's behalf, closed again by `revoke_operator`.
+#[account]
+#[derive(InitSpace)]
+pub struct Operator {
+    pub agent: Pubkey,
+    pub operator: Pubkey,
+    pub registered_at: i64, // unix timestamp of register_operator
+    pub bump: u8,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+/// PDA: `["rejection", bounty, agent, attempt_index as u32 LE]`. The index
+/// counts every rejection the agent has had, so it never repeats even once
+/// a submission is closed and made again.
+#[account]
+#[derive(InitSpace)]
+pub struct Rejection {
+    pub bounty: Pubkey,
+    pub agent: Pubkey,
+    pub attempt_index: u32,
+    pub reason: RejectionReason,
+    pub note_hash: Option<[u8; 32]>, // of the creator's explanation, kept off-chain
+    pub rejected_by: Pubkey,         // the creator or their settlement authority
+    pub rejected_at: i64,
+    pub appeal: AppealStatus,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum RejectionReason {
+    DoesNotMeetCriteria,
+    Plagiarized, // also counted in Reputation::plagiarized
+    IncompleteData,
+    Other(u16), // a code of the creator's or integrator's own
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum AppealStatus {
+    Unappealed,
+    Contested, // settling with another agent waits for the window or the arbiter
+    Upheld,    // the arbiter overturned the rejection, taking back its failure
+    Dismissed, // the arbiter let the rejection stand
+}
@@ -1,12 +1,216 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::{
+    BOUNTY_TYPE_SLOTS, BRONZE_TIER_SUCCESSES, EARNING_MINT_SLOTS, GOLD_TIER_SUCCESSES,
+    SCORE_PER_SUBMISSION, SILVER_TIER_SUCCESSES,
+};
+use crate::errors::BountyForgeError;
+use crate::state::{BountyType, RejectionReason};
+
+/// PDA: `["rep", agent]`.
 #[account]
 #[derive(InitSpace)]
 pub struct Reputation {
     pub agent: Pubkey,
-    pub score: u64, // +1 per successful bounty
+    pub score: u64, // +1 per submission, topped up to Config::score_weight when it settles
     pub successful_bounties: u64,
     pub failed_bounties: u64,
-    pub total_earned: u64, // lamports
+    pub total_earned: u64, // in Config::primary_mint, or across every mint when unset
+    pub open_submissions: u32, // submission accounts not yet closed with close_submission
     pub bump: u8,
+    // appended after bump, so reputations created before they existed only need to grow
+    pub completed_by_type: [u32; BOUNTY_TYPE_SLOTS], // indexed by BountyType::stats_index
+    pub tier: u8, // 0 none, 1 bronze, 2 silver, 3 gold, from successful_bounties
+    pub current_streak: u32, // successes since the last failure
+    pub longest_streak: u32,
+    pub last_active_ts: i64,  // last submission or settlement
+    pub last_decayed_ts: i64, // decay_reputation has accounted for inactivity up to here
+    pub rating_sum: u64,      // creators' leave_feedback ratings, averaged client-side
+    pub rating_count: u32,
+    pub withdrawals: u32, // submissions retracted with withdraw_submission, not failures
+    pub earned_by_mint: [MintEarnings; EARNING_MINT_SLOTS], // first mints paid in, in order
+    pub other_earned_count: u32, // payouts in mints that found every slot taken
+    pub next_solution_id: u64, // handed to the agent's next attestation by attest_solution
+    pub rent_payer: Pubkey, // sponsor refunded the rent on close, else the agent
+    pub rejections: u32,  // by reject_solution, the next one's Rejection::attempt_index
+    pub plagiarized: u32, // rejections for plagiarism, less any overturned on appeal
+}
+
+/// An agent's lifetime earnings in one reward mint, `Pubkey::default()` for
+/// Sol. A slot with nothing earned is free.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct MintEarnings {
+    pub mint: Pubkey,
+    pub amount: u64,
+}
+
+impl Reputation {
+    /// Size of the oldest layout, from before any fields were appended after `bump`.
+    pub const LEGACY_SPACE: usize = 32 + 8 + 8 + 8 + 8 + 4 + 1;
+
+    /// `score_weight` is what the bounty is worth in total, its first
+    /// SCORE_PER_SUBMISSION was already credited when the solution was submitted.
+    pub fn record_success(
+        &mut self,
+        earned: u64,
+        mint: Pubkey,
+        primary_mint: Option<Pubkey>,
+        bounty_type: BountyType,
+        score_weight: u64,
+    ) -> Result<()> {
+        self.score = self
+            .score
+            .checked_add(score_weight.saturating_sub(SCORE_PER_SUBMISSION))
+            .ok_or(BountyForgeError::ReputationScoreOverflow)?;
+        self.successful_bounties = self
+            .successful_bounties
+            .checked_add(1)
+            .ok_or(BountyForgeError::ReputationOverflow)?;
+
+        let completed = &mut self.completed_by_type[bounty_type.stats_index()];
+        *completed = completed
+            .checked_add(1)
+            .ok_or(BountyForgeError::ReputationOverflow)?;
+        self.update_tier();
+
+        self.current_streak = self
+            .current_streak
+            .checked_add(1)
+            .ok_or(BountyForgeError::ReputationOverflow)?;
+        self.longest_streak = self.longest_streak.max(self.current_streak);
+        self.last_active_ts = Clock::get()?.unix_timestamp;
+
+        self.record_earnings(earned, mint, primary_mint)
+    }
+
+    /// Counts a payout that doesn't complete a bounty, such as an early milestone.
+    /// `total_earned` only takes payouts in `primary_mint`, or every one when unset.
+    pub fn record_earnings(
+        &mut self,
+        earned: u64,
+        mint: Pubkey,
+        primary_mint: Option<Pubkey>,
+    ) -> Result<()> {
+        if earned == 0 {
+            return Ok(());
+        }
+        if primary_mint.is_none_or(|primary| primary == mint) {
+            self.total_earned = self
+                .total_earned
+                .checked_add(earned)
+                .ok_or(BountyForgeError::ReputationOverflow)?;
+        }
+
+        let slot = self
+            .earned_by_mint
+            .iter()
+            .position(|entry| entry.amount > 0 && entry.mint == mint)
+            .or_else(|| {
+                self.earned_by_mint
+                    .iter()
+                    .position(|entry| entry.amount == 0)
+            });
+        match slot {
+            Some(index) => {
+                let entry = &mut self.earned_by_mint[index];
+                entry.mint = mint;
+                entry.amount = entry
+                    .amount
+                    .checked_add(earned)
+                    .ok_or(BountyForgeError::ReputationOverflow)?;
+            }
+            None => {
+                self.other_earned_count = self
+                    .other_earned_count
+                    .checked_add(1)
+                    .ok_or(BountyForgeError::ReputationOverflow)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Hands out the id for a new attestation, each agent's counting up from 0.
+    pub fn take_solution_id(&mut self) -> Result<u64> {
+        let solution_id = self.next_solution_id;
+        self.next_solution_id = solution_id
+            .checked_add(1)
+            .ok_or(BountyForgeError::ReputationOverflow)?;
+        Ok(solution_id)
+    }
+
+    /// Lifetime earnings in `mint`, 0 when it never took a slot.
+    pub fn earned_in(&self, mint: Pubkey) -> u64 {
+        self.earned_by_mint
+            .iter()
+            .find(|entry| entry.amount > 0 && entry.mint == mint)
+            .map_or(0, |entry| entry.amount)
+    }
+
+    pub fn record_failure(&mut self) -> Result<()> {
+        self.failed_bounties = self
+            .failed_bounties
+            .checked_add(1)
+            .ok_or(BountyForgeError::ReputationOverflow)?;
+        self.current_streak = 0;
+
+        Ok(())
+    }
+
+    /// Counts a rejection on top of the failure, returning its attempt index.
+    pub fn record_rejection(&mut self, reason: RejectionReason) -> Result<u32> {
+        self.record_failure()?;
+        let attempt_index = self.rejections;
+        self.rejections = attempt_index
+            .checked_add(1)
+            .ok_or(BountyForgeError::ReputationOverflow)?;
+        if reason == RejectionReason::Plagiarized {
+            self.plagiarized = self
+                .plagiarized
+                .checked_add(1)
+                .ok_or(BountyForgeError::ReputationOverflow)?;
+        }
+        Ok(attempt_index)
+    }
+
+    /// Takes back the failure of a rejection overturned on appeal. The streak
+    /// it broke stays broken.
+    pub fn overturn_rejection(&mut self, reason: RejectionReason) {
+        self.failed_bounties = self.failed_bounties.saturating_sub(1);
+        if reason == RejectionReason::Plagiarized {
+            self.plagiarized = self.plagiarized.saturating_sub(1);
+        }
+    }
+
+    /// Halves the score once for every whole `period` without activity since
+    /// the later of the last activity and the last decay, carrying any
+    /// partial period over. Returns the number of halvings applied.
+    pub fn decay(&mut self, now: i64, period: u64) -> u32 {
+        let Ok(period) = i64::try_from(period) else {
+            return 0;
+        };
+        if period == 0 {
+            return 0;
+        }
+        let since = self.last_active_ts.max(self.last_decayed_ts);
+        let periods = now.saturating_sub(since).max(0) / period;
+        if periods == 0 {
+            return 0;
+        }
+
+        // a u64 is gone after 64 halvings, checked_shr would refuse those
+        let halvings = u32::try_from(periods).unwrap_or(u32::MAX);
+        self.score = self.score.checked_shr(halvings).unwrap_or(0);
+        self.last_decayed_ts = since.saturating_add(periods.saturating_mul(period));
+        halvings
+    }
+
+    pub fn update_tier(&mut self) {
+        self.tier = match self.successful_bounties {
+            n if n >= GOLD_TIER_SUCCESSES => 3,
+            n if n >= SILVER_TIER_SUCCESSES => 2,
+            n if n >= BRONZE_TIER_SUCCESSES => 1,
+            _ => 0,
+        };
+    }
 }
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+
+// domain prefixes so an inner node can never be passed off as a leaf
+const LEAF_PREFIX: &[u8] = &[0];
+const NODE_PREFIX: &[u8] = &[1];
+
+/// PDA: `["snapshot", market, epoch as u64 LE]`. Merkle root over every agent's
+/// `(agent, score, successful_bounties)` at the end of an epoch, built off-chain.
+#[account]
+#[derive(InitSpace)]
+pub struct ReputationSnapshot {
+    pub epoch: u64,
+    pub root: [u8; 32],
+    pub leaf_count: u32,
+    pub created_at: i64, // unix timestamp
+    pub bump: u8,
+}
+
+impl ReputationSnapshot {
+    /// `keccak(0x00 || agent || score as u64 LE || successful_bounties as u64 LE)`
+    pub fn leaf(agent: &Pubkey, score: u64, successful_bounties: u64) -> [u8; 32] {
+        keccak::hashv(&[
+            LEAF_PREFIX,
+            agent.as_ref(),
+            &score.to_le_bytes(),
+            &successful_bounties.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+
+    /// Walks `proof` up from `leaf`. Each pair is hashed as
+    /// `keccak(0x01 || min || max)`, so proofs don't need to carry positions.
+    pub fn verify(&self, leaf: [u8; 32], proof: &[[u8; 32]]) -> bool {
+        let computed = proof.iter().fold(leaf, |node, sibling| {
+            let (left, right) = if node <= *sibling {
+                (node, *sibling)
+            } else {
+                (*sibling, node)
+            };
+            keccak::hashv(&[NODE_PREFIX, &left, &right]).to_bytes()
+        });
+        computed == self.root
+    }
+}
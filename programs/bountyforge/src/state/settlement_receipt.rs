@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAX_MARKET_LEN;
+
+/// PDA: `["receipt", bounty, claim as u16 LE]`. Written once per settlement
+/// and left in place when the bounty is closed, until close_receipt after the
+/// retention period.
+#[account]
+#[derive(InitSpace)]
+pub struct SettlementReceipt {
+    pub bounty: Pubkey,
+    pub bounty_id: u64,
+    pub creator: Pubkey,
+    pub solver: Pubkey,
+    pub gross_reward: u64, // escrow released, before fees
+    pub fee: u64,          // paid to the treasury
+    pub attestor_fee: u64, // paid to the backing attestor, 0 if their share went to the treasury
+    pub net_paid: u64,     // solver's share, streamed by claim_vested if the bounty vests
+    pub mint: Pubkey,      // Pubkey::default() for Sol bounties
+    pub settled_at: i64,   // unix timestamp
+    pub settled_by: Pubkey,
+    pub payment_receipt_hash: Option<[u8; 32]>, // sha256 of the gateway's signed x402 receipt, if required
+    pub payment_amount: Option<u64>, // verification fee the receipt shows was paid, alongside its hash
+    pub claim: u16, // which claim of a repeatable bounty this paid, 0 for the first or only one
+    #[max_len(MAX_MARKET_LEN)]
+    pub market: String, // the bounty's, whose Config sets the retention period
+    pub bump: u8,
+}
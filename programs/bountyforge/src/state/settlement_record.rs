@@ -0,0 +1,18 @@
+use anchor_lang::prelude::*;
+
+use crate::state::BountyType;
+
+/// PDA: `["settlement", bounty, agent, claim as u16 LE]`, where the claim is
+/// the bounty's `claims_made` before the settlement, 0 unless it is repeatable.
+#[account]
+#[derive(InitSpace)]
+pub struct SettlementRecord {
+    pub agent: Pubkey,
+    pub bounty: Pubkey,
+    pub bounty_id: u64,
+    pub bounty_type: BountyType,
+    pub amount_earned: u64,
+    pub score_delta: u64,
+    pub timestamp: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_METADATA_URI_LEN, MAX_TEAM_MEMBERS};
+
+/// PDA: `["submission", bounty, agent]`.
+#[account]
+#[derive(InitSpace)]
+pub struct Submission {
+    pub bounty: Pubkey,
+    pub agent: Pubkey,
+    pub solution_hash: [u8; 32],
+    pub ciphertext_hash: [u8; 32], // commits to the solution encrypted to the bounty's key, else zeroed
+    #[max_len(MAX_METADATA_URI_LEN)]
+    pub solution_uri: String, // where the creator fetches the deliverable, empty for none
+    pub timestamp: i64,
+    pub status: SubmissionStatus,
+    pub stake: u64,         // lamports held on this account on top of its rent
+    pub attestor: Pubkey,   // countersigner of the backing attestation, else the agent itself
+    pub rejected_at: i64,   // unix timestamp of the latest rejection, 0 if never rejected
+    pub rent_payer: Pubkey, // sponsor refunded the rent on close, else the agent
+    #[max_len(MAX_TEAM_MEMBERS)]
+    pub team: Vec<TeamShare>, // how the payout is split, empty pays it all to the agent
+    pub bump: u8,
+}
+
+/// One member's cut of a team submission's payout. Reputation is only ever
+/// credited to the submitting agent.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub struct TeamShare {
+    pub member: Pubkey,
+    pub share_bps: u16,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, InitSpace)]
+pub enum SubmissionStatus {
+    Pending,  // live, one per agent and bounty
+    Accepted, // picked to be paid
+    Rejected, // the agent may submit again
+}
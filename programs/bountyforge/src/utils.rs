@@ -0,0 +1,1166 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::solana_program::sysvar::instructions::{
+    load_current_index_checked, load_instruction_at_checked,
+};
+use anchor_lang::solana_program::{ed25519_program, hash};
+use anchor_lang::system_program::{self, create_account, CreateAccount};
+use anchor_spl::associated_token::{
+    self, get_associated_token_address_with_program_id, AssociatedToken,
+};
+use anchor_spl::token::spl_token;
+use anchor_spl::token_2022::spl_token_2022;
+use anchor_spl::token_2022::spl_token_2022::extension::default_account_state::DefaultAccountState;
+use anchor_spl::token_2022::spl_token_2022::extension::permanent_delegate::PermanentDelegate;
+use anchor_spl::token_2022::spl_token_2022::extension::transfer_hook::TransferHook;
+use anchor_spl::token_2022::spl_token_2022::extension::{
+    BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+};
+use anchor_spl::token_2022::spl_token_2022::instruction::AuthorityType;
+use anchor_spl::token_2022::spl_token_2022::state::{
+    Account as TokenAccountState, AccountState, Mint as MintState,
+};
+use anchor_spl::token_2022::{self, Token2022};
+use anchor_spl::token_interface::spl_pod::optional_keys::OptionalNonZeroPubkey;
+use anchor_spl::token_interface::spl_token_metadata_interface::state::{Field, TokenMetadata};
+use anchor_spl::token_interface::{
+    close_account, metadata_pointer_initialize, non_transferable_mint_initialize,
+    token_metadata_initialize, token_metadata_update_field, transfer_checked, CloseAccount,
+    MetadataPointerInitialize, Mint, NonTransferableMintInitialize, TokenAccount, TokenInterface,
+    TokenMetadataInitialize, TokenMetadataUpdateField, TransferChecked,
+};
+
+use pyth_solana_receiver_sdk::price_update::{PriceUpdateV2, VerificationLevel};
+use switchboard_on_demand::{PullFeedAccountData, PRECISION, SWITCHBOARD_ON_DEMAND_PROGRAM_ID};
+
+use crate::constants::{
+    ANCHOR_DISCRIMINATOR, BADGE_NAME_PREFIX, BADGE_SYMBOL, MAX_FEE_BPS, MAX_METADATA_URI_LEN,
+    MAX_ORACLE_CONFIDENCE_BPS, MAX_ORACLE_STALENESS_SECONDS, MAX_TEAM_MEMBERS,
+};
+use crate::errors::BountyForgeError;
+use crate::events::BadgeMinted;
+use crate::state::{Bounty, Config, InsuranceFund, RewardCurrency, Submission, TeamShare};
+
+/// Moves `amount` out of the bounty escrow ATA, signing as the bounty PDA.
+pub fn transfer_from_escrow<'info>(
+    bounty: &Account<'info, Bounty>,
+    bounty_token_account: &InterfaceAccount<'info, TokenAccount>,
+    reward_mint: &InterfaceAccount<'info, Mint>,
+    to: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    let bounty_id_bytes = bounty.id.to_le_bytes();
+    let bounty_seeds = &[
+        b"bounty",
+        bounty.original_creator.as_ref(),
+        bounty_id_bytes.as_ref(),
+        &[bounty.bump],
+    ];
+    let bounty_signer = &[&bounty_seeds[..]];
+
+    let cpi_program = token_program.to_account_info();
+    let cpi_accounts = TransferChecked {
+        from: bounty_token_account.to_account_info(),
+        mint: reward_mint.to_account_info(),
+        to,
+        authority: bounty.to_account_info(),
+    };
+
+    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, bounty_signer);
+
+    transfer_checked(cpi_ctx, amount, reward_mint.decimals)
+}
+
+/// Checks the creator's token account can fund `reward` before the transfer, so
+/// the creator gets our error rather than the token program's. A delegate whose
+/// allowance exceeds what's left doesn't stop the transfer and is only logged.
+pub fn check_creator_balance(
+    creator_token_account: &InterfaceAccount<TokenAccount>,
+    reward: u64,
+) -> Result<()> {
+    require!(
+        creator_token_account.state != AccountState::Frozen,
+        BountyForgeError::TokenAccountFrozen
+    );
+    require!(
+        creator_token_account.amount >= reward,
+        BountyForgeError::InsufficientRewardBalance
+    );
+    let left = creator_token_account.amount - reward;
+    if creator_token_account.delegate.is_some() && creator_token_account.delegated_amount > left {
+        msg!(
+            "post_bounty: delegate may spend {} but only {} remains after escrow",
+            creator_token_account.delegated_amount,
+            left
+        );
+    }
+    Ok(())
+}
+
+/// Space of a bounty `post_bounty` creates, once `payer` is known to cover its
+/// rent and, when `with_escrow`, the escrow ATA's, sized for the reward mint's
+/// extensions as the associated token program sizes it. Anchor creates `init`
+/// accounts before checking any constraint, so the bounty's space is the last
+/// point where a short payer can still get our error instead of the system
+/// program's.
+pub fn post_bounty_space(
+    payer: &AccountInfo,
+    reward_mint: Option<&InterfaceAccount<Mint>>,
+    with_escrow: bool,
+) -> Result<usize> {
+    let space = ANCHOR_DISCRIMINATOR + Bounty::INIT_SPACE;
+    let rent = Rent::get()?;
+    let mut lamports = rent.minimum_balance(space);
+    if let (Some(reward_mint), true) = (reward_mint, with_escrow) {
+        let mint_info = reward_mint.to_account_info();
+        let escrow_len = if *mint_info.owner == spl_token_2022::ID {
+            let data = mint_info.try_borrow_data()?;
+            let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+            let mut extensions =
+                ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+            extensions.push(ExtensionType::ImmutableOwner);
+            ExtensionType::try_calculate_account_len::<TokenAccountState>(&extensions)?
+        } else {
+            spl_token::state::Account::LEN
+        };
+        lamports = lamports.saturating_add(rent.minimum_balance(escrow_len));
+    }
+    require!(
+        payer.lamports() >= lamports,
+        BountyForgeError::InsufficientRentFunds
+    );
+    Ok(space)
+}
+
+/// Refuses reward mints that would let someone other than the program move or
+/// lock the escrow: a permanent delegate, a transfer hook, accounts frozen by
+/// default, or a freeze authority held by the creator. Mints on the Config
+/// `trusted_mints` list skip the checks.
+pub fn check_reward_mint(
+    reward_mint: &InterfaceAccount<Mint>,
+    creator: &Pubkey,
+    config: &Config,
+) -> Result<()> {
+    if config.trusted_mints.contains(&reward_mint.key()) {
+        return Ok(());
+    }
+    require!(
+        Option::<Pubkey>::from(reward_mint.freeze_authority) != Some(*creator),
+        BountyForgeError::UnsafeRewardMintFreezeAuthority
+    );
+
+    // legacy token mints carry no extensions
+    let mint_info = reward_mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+    let data = mint_info.try_borrow_data()?;
+    let mint = StateWithExtensions::<MintState>::unpack(&data)?;
+    if let Ok(extension) = mint.get_extension::<PermanentDelegate>() {
+        require!(
+            Option::<Pubkey>::from(extension.delegate).is_none(),
+            BountyForgeError::UnsafeRewardMintPermanentDelegate
+        );
+    }
+    if let Ok(extension) = mint.get_extension::<TransferHook>() {
+        require!(
+            Option::<Pubkey>::from(extension.program_id).is_none(),
+            BountyForgeError::UnsafeRewardMintTransferHook
+        );
+    }
+    if let Ok(extension) = mint.get_extension::<DefaultAccountState>() {
+        require!(
+            extension.state != AccountState::Frozen as u8,
+            BountyForgeError::UnsafeRewardMintDefaultFrozen
+        );
+    }
+    Ok(())
+}
+
+/// Whether `mint` is wrapped SOL, under either token program.
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    *mint == spl_token::native_mint::ID || *mint == spl_token_2022::native_mint::ID
+}
+
+/// Pays `amount` of a wrapped SOL escrow out as plain lamports: the tokens move
+/// into `unwrap_account`, a fresh token account owned by the bounty PDA, which
+/// is then closed to `recipient` along with its rent.
+pub fn unwrap_from_escrow<'info>(
+    bounty: &Account<'info, Bounty>,
+    bounty_token_account: &InterfaceAccount<'info, TokenAccount>,
+    reward_mint: &InterfaceAccount<'info, Mint>,
+    unwrap_account: &InterfaceAccount<'info, TokenAccount>,
+    recipient: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    transfer_from_escrow(
+        bounty,
+        bounty_token_account,
+        reward_mint,
+        unwrap_account.to_account_info(),
+        token_program,
+        amount,
+    )?;
+
+    let bounty_id_bytes = bounty.id.to_le_bytes();
+    let bounty_seeds = &[
+        b"bounty",
+        bounty.original_creator.as_ref(),
+        bounty_id_bytes.as_ref(),
+        &[bounty.bump],
+    ];
+    let bounty_signer = &[&bounty_seeds[..]];
+
+    let cpi_accounts = CloseAccount {
+        account: unwrap_account.to_account_info(),
+        destination: recipient,
+        authority: bounty.to_account_info(),
+    };
+    close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        cpi_accounts,
+        bounty_signer,
+    ))
+}
+
+/// Moves `amount` lamports held on the bounty PDA, never dipping it below rent exemption.
+pub fn transfer_lamports_from_bounty<'info>(
+    bounty: &Account<'info, Bounty>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let bounty_info = bounty.to_account_info();
+    let rent_floor = Rent::get()?.minimum_balance(bounty_info.data_len());
+
+    let remaining = bounty_info
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(BountyForgeError::BelowRentExemption)?;
+    require!(
+        remaining >= rent_floor,
+        BountyForgeError::BelowRentExemption
+    );
+
+    let credited = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(BountyForgeError::BelowRentExemption)?;
+
+    **bounty_info.try_borrow_mut_lamports()? = remaining;
+    **to.try_borrow_mut_lamports()? = credited;
+
+    Ok(())
+}
+
+/// Moves `amount` lamports staked on a submission, which sit on top of its
+/// rent. Also moves a challenge's bond, held the same way.
+pub fn transfer_stake<'info>(
+    submission: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let remaining = submission
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(BountyForgeError::BelowRentExemption)?;
+    let credited = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(BountyForgeError::BelowRentExemption)?;
+
+    **submission.try_borrow_mut_lamports()? = remaining;
+    **to.try_borrow_mut_lamports()? = credited;
+
+    Ok(())
+}
+
+/// Hands a sponsored account's rent back to whoever paid it ahead of its
+/// `close`, which then sends what's left, such as a stake, to `owner`.
+/// Nothing moves when `owner` paid the rent themselves.
+pub fn refund_rent<'info>(
+    account: &AccountInfo<'info>,
+    recorded_payer: Pubkey,
+    owner: &Pubkey,
+    rent_payer: Option<&UncheckedAccount<'info>>,
+) -> Result<()> {
+    if recorded_payer == *owner {
+        return Ok(());
+    }
+    let rent_payer = rent_payer.ok_or(BountyForgeError::MissingRentPayer)?;
+    let rent = Rent::get()?
+        .minimum_balance(account.data_len())
+        .min(account.lamports());
+    let credited = rent_payer
+        .lamports()
+        .checked_add(rent)
+        .ok_or(BountyForgeError::BelowRentExemption)?;
+
+    **account.try_borrow_mut_lamports()? -= rent;
+    **rent_payer.try_borrow_mut_lamports()? = credited;
+
+    Ok(())
+}
+
+/// Creates a program-owned PDA of `space` bytes, rent paid by `payer`, the
+/// way `init` would for an account that can't be declared up front.
+/// `seeds` must end with the bump and derive `account`'s address.
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    seeds: &[&[u8]],
+    space: usize,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let expected = Pubkey::create_program_address(seeds, &crate::ID)
+        .map_err(|_| error!(BountyForgeError::BatchAccountMismatch))?;
+    require_keys_eq!(
+        account.key(),
+        expected,
+        BountyForgeError::BatchAccountMismatch
+    );
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            system_program::CreateAccount {
+                from: payer.clone(),
+                to: account.clone(),
+            },
+            &[seeds],
+        ),
+        Rent::get()?.minimum_balance(space),
+        space as u64,
+        &crate::ID,
+    )
+}
+
+/// Grows a program-owned account to `new_len` with zeroed bytes, `payer`
+/// topping up the rent first.
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    new_len: usize,
+    payer: &AccountInfo<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    let shortfall = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if shortfall > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: payer.clone(),
+            to: account.clone(),
+        };
+        system_program::transfer(
+            CpiContext::new(system_program.to_account_info(), cpi_accounts),
+            shortfall,
+        )?;
+    }
+    account.realloc(new_len, true)?;
+    Ok(())
+}
+
+/// Hands a failed submission's stake to the creator, less `treasury_bps`
+/// which goes to the treasury.
+pub fn slash_stake<'info>(
+    submission: &mut Account<'info, Submission>,
+    creator: &AccountInfo<'info>,
+    treasury: Option<&AccountInfo<'info>>,
+    treasury_bps: u16,
+) -> Result<()> {
+    let (creator_share, treasury_share) = split_fee(submission.stake, treasury_bps)?;
+    let submission_info = submission.to_account_info();
+
+    transfer_stake(&submission_info, creator, creator_share)?;
+    if treasury_share > 0 {
+        let treasury = treasury.ok_or(BountyForgeError::MissingTreasury)?;
+        transfer_stake(&submission_info, treasury, treasury_share)?;
+    }
+    submission.stake = 0;
+
+    Ok(())
+}
+
+/// Reads a bounty that may already have been closed by `close_bounty`, in
+/// which case `None` is returned and whatever hung off it is free to go.
+pub fn load_bounty(bounty: &AccountInfo) -> Result<Option<Bounty>> {
+    if bounty.owner != &crate::ID || bounty.data_is_empty() {
+        return Ok(None);
+    }
+    let data = bounty.try_borrow_data()?;
+    Ok(Some(Bounty::try_deserialize(&mut &data[..])?))
+}
+
+/// Amount currently held in escrow for the bounty, whatever its currency.
+pub fn escrow_balance<'info>(
+    bounty: &Account<'info, Bounty>,
+    bounty_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+) -> Result<u64> {
+    match bounty.reward_currency {
+        RewardCurrency::Spl => Ok(bounty_token_account
+            .ok_or(BountyForgeError::MissingTokenAccount)?
+            .amount),
+        RewardCurrency::Sol => {
+            let bounty_info = bounty.to_account_info();
+            let rent_floor = Rent::get()?.minimum_balance(bounty_info.data_len());
+            Ok(bounty_info.lamports().saturating_sub(rent_floor))
+        }
+    }
+}
+
+/// Pays `amount` out of the bounty escrow: a token transfer into `to_token_account`
+/// for SPL bounties, a lamport transfer into `to_wallet` for SOL bounties.
+pub fn pay_from_escrow<'info>(
+    bounty: &Account<'info, Bounty>,
+    bounty_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    reward_mint: Option<&InterfaceAccount<'info, Mint>>,
+    to_wallet: &AccountInfo<'info>,
+    to_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    match bounty.reward_currency {
+        RewardCurrency::Spl => {
+            let from = bounty_token_account.ok_or(BountyForgeError::MissingTokenAccount)?;
+            let mint = reward_mint.ok_or(BountyForgeError::MissingTokenAccount)?;
+            let to = to_token_account.ok_or(BountyForgeError::MissingTokenAccount)?;
+            transfer_from_escrow(
+                bounty,
+                from,
+                mint,
+                to.to_account_info(),
+                token_program,
+                amount,
+            )
+        }
+        RewardCurrency::Sol => transfer_lamports_from_bounty(bounty, to_wallet, amount),
+    }
+}
+
+/// Pays the solver's share of a settlement: all of it to the agent, or split
+/// by the submission's team table, each member's cut rounding down and the
+/// dust going to the agent. `member_accounts` holds one account per member in
+/// table order: their associated token account for the reward mint on token
+/// bounties, their wallet on Sol ones.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_solver<'info>(
+    bounty: &Account<'info, Bounty>,
+    team: &[TeamShare],
+    bounty_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    reward_mint: Option<&InterfaceAccount<'info, Mint>>,
+    agent: &AccountInfo<'info>,
+    agent_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    member_accounts: &'info [AccountInfo<'info>],
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    require!(
+        member_accounts.len() >= team.len(),
+        BountyForgeError::MissingTeamMemberAccount
+    );
+
+    let mut paid = 0u64;
+    for (share, account) in team.iter().zip(member_accounts) {
+        let cut = (amount as u128)
+            .checked_mul(share.share_bps as u128)
+            .and_then(|scaled| scaled.checked_div(MAX_FEE_BPS as u128))
+            .and_then(|cut| u64::try_from(cut).ok())
+            .ok_or(BountyForgeError::FeeCalculationOverflow)?;
+        match bounty.reward_currency {
+            RewardCurrency::Spl => {
+                let mint = reward_mint.ok_or(BountyForgeError::MissingTokenAccount)?;
+                require_keys_eq!(
+                    account.key(),
+                    get_associated_token_address_with_program_id(
+                        &share.member,
+                        &mint.key(),
+                        &token_program.key()
+                    ),
+                    BountyForgeError::TeamMemberAccountMismatch
+                );
+                let member_token_account = InterfaceAccount::<TokenAccount>::try_from(account)?;
+                pay_from_escrow(
+                    bounty,
+                    bounty_token_account,
+                    reward_mint,
+                    account,
+                    Some(&member_token_account),
+                    token_program,
+                    cut,
+                )?;
+            }
+            RewardCurrency::Sol => {
+                require_keys_eq!(
+                    account.key(),
+                    share.member,
+                    BountyForgeError::TeamMemberAccountMismatch
+                );
+                pay_from_escrow(bounty, None, None, account, None, token_program, cut)?;
+            }
+        }
+        paid = paid
+            .checked_add(cut)
+            .ok_or(BountyForgeError::FeeCalculationOverflow)?;
+    }
+
+    let rest = amount.saturating_sub(paid);
+    if rest > 0 {
+        pay_from_escrow(
+            bounty,
+            bounty_token_account,
+            reward_mint,
+            agent,
+            agent_token_account,
+            token_program,
+            rest,
+        )?;
+    }
+    Ok(())
+}
+
+/// Splits a settlement's `amount` as `split_fees` does and pays the attestor's
+/// share the way every settlement path does: folded into the solver's share
+/// when the agent attested their own solution, to the submission's attestor
+/// when their wallet (Sol bounties) or token account (token bounties) is
+/// passed, and into the protocol fee otherwise. Returns `(solver_amount,
+/// attestor_amount, fee_amount)`, `attestor_amount` being what the attestor
+/// was actually paid.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_attestor_share<'info>(
+    bounty: &Account<'info, Bounty>,
+    submission: &Submission,
+    config: &Config,
+    bounty_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    reward_mint: Option<&InterfaceAccount<'info, Mint>>,
+    attestor: Option<&AccountInfo<'info>>,
+    attestor_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<(u64, u64, u64)> {
+    let (mut solver_amount, attestor_amount, mut fee_amount) =
+        split_fees(amount, config.fee_bps, config.attestor_fee_bps)?;
+    if submission.attestor == submission.agent {
+        // a self-attested solution earns both shares in one transfer
+        solver_amount = solver_amount
+            .checked_add(attestor_amount)
+            .ok_or(BountyForgeError::FeeCalculationOverflow)?;
+        return Ok((solver_amount, 0, fee_amount));
+    }
+
+    let paid_to = match bounty.reward_currency {
+        RewardCurrency::Spl => match attestor_token_account {
+            Some(token_account) => {
+                require!(
+                    token_account.owner == submission.attestor
+                        && token_account.mint == bounty.reward_mint,
+                    BountyForgeError::NotSubmissionAttestor
+                );
+                Some(token_account.to_account_info())
+            }
+            None => None,
+        },
+        RewardCurrency::Sol => match attestor {
+            Some(wallet) => {
+                require_keys_eq!(
+                    wallet.key(),
+                    submission.attestor,
+                    BountyForgeError::NotSubmissionAttestor
+                );
+                Some(wallet.clone())
+            }
+            None => None,
+        },
+    };
+    let (Some(to_wallet), true) = (paid_to, attestor_amount > 0) else {
+        fee_amount = fee_amount
+            .checked_add(attestor_amount)
+            .ok_or(BountyForgeError::FeeCalculationOverflow)?;
+        return Ok((solver_amount, 0, fee_amount));
+    };
+    pay_from_escrow(
+        bounty,
+        bounty_token_account,
+        reward_mint,
+        &to_wallet,
+        attestor_token_account,
+        token_program,
+        attestor_amount,
+    )?;
+
+    Ok((solver_amount, attestor_amount, fee_amount))
+}
+
+/// Mints a 1-of-1 Token-2022 badge to the agent for claim `claim` of the
+/// bounty, `payer` funding the mint and the agent's badge account. The mint is
+/// non-transferable and holds its own metadata, and its mint authority is
+/// dropped once the badge is out so no second one can follow.
+#[allow(clippy::too_many_arguments)]
+pub fn mint_badge<'info>(
+    bounty: &Account<'info, Bounty>,
+    agent: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    badge_mint: Option<&AccountInfo<'info>>,
+    badge_mint_bump: Option<u8>,
+    badge_authority: Option<&AccountInfo<'info>>,
+    badge_authority_bump: Option<u8>,
+    agent_badge_account: Option<&AccountInfo<'info>>,
+    badge_token_program: Option<&Program<'info, Token2022>>,
+    associated_token_program: &Program<'info, AssociatedToken>,
+    system_program: &Program<'info, System>,
+    claim: u16,
+    reward: u64,
+    settled_at: i64,
+) -> Result<()> {
+    let (
+        Some(mint),
+        Some(mint_bump),
+        Some(authority),
+        Some(authority_bump),
+        Some(agent_badge_account),
+        Some(badge_token_program),
+    ) = (
+        badge_mint,
+        badge_mint_bump,
+        badge_authority,
+        badge_authority_bump,
+        agent_badge_account,
+        badge_token_program,
+    )
+    else {
+        return err!(BountyForgeError::MissingBadgeAccounts);
+    };
+    let token_program = badge_token_program.to_account_info();
+    let bounty_key = bounty.key();
+    let claim_bytes = claim.to_le_bytes();
+    let mint_seeds: &[&[u8]] = &[b"badge", bounty_key.as_ref(), &claim_bytes, &[mint_bump]];
+    let authority_seeds: &[&[u8]] = &[b"badge_authority", &[authority_bump]];
+
+    let metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(authority.key()))?,
+        mint: mint.key(),
+        name: format!("{BADGE_NAME_PREFIX}{}", bounty.id),
+        symbol: BADGE_SYMBOL.to_string(),
+        uri: bounty.metadata_uri.clone(),
+        additional_metadata: vec![
+            ("bounty".to_string(), bounty_key.to_string()),
+            ("bounty_id".to_string(), bounty.id.to_string()),
+            ("bounty_type".to_string(), bounty.bounty_type.label()),
+            ("reward".to_string(), reward.to_string()),
+            ("settled_at".to_string(), settled_at.to_string()),
+        ],
+    };
+
+    // 1. creating the mint with room for its extensions, funded for the metadata too
+    let mint_len = ExtensionType::try_calculate_account_len::<MintState>(&[
+        ExtensionType::NonTransferable,
+        ExtensionType::MetadataPointer,
+    ])?;
+    let lamports = Rent::get()?.minimum_balance(mint_len + metadata.tlv_size_of()?);
+    create_account(
+        CpiContext::new_with_signer(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: payer.clone(),
+                to: mint.clone(),
+            },
+            &[mint_seeds],
+        ),
+        lamports,
+        mint_len as u64,
+        &token_2022::ID,
+    )?;
+
+    // 2. extensions first, as Token-2022 requires, then the mint itself
+    non_transferable_mint_initialize(CpiContext::new(
+        token_program.clone(),
+        NonTransferableMintInitialize {
+            token_program_id: token_program.clone(),
+            mint: mint.clone(),
+        },
+    ))?;
+    metadata_pointer_initialize(
+        CpiContext::new(
+            token_program.clone(),
+            MetadataPointerInitialize {
+                token_program_id: token_program.clone(),
+                mint: mint.clone(),
+            },
+        ),
+        Some(authority.key()),
+        Some(mint.key()),
+    )?;
+    token_2022::initialize_mint2(
+        CpiContext::new(
+            token_program.clone(),
+            token_2022::InitializeMint2 { mint: mint.clone() },
+        ),
+        0,
+        &authority.key(),
+        None,
+    )?;
+
+    // 3. writing the metadata into the mint
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            TokenMetadataInitialize {
+                program_id: token_program.clone(),
+                metadata: mint.clone(),
+                update_authority: authority.clone(),
+                mint_authority: authority.clone(),
+                mint: mint.clone(),
+            },
+            &[authority_seeds],
+        ),
+        metadata.name.clone(),
+        metadata.symbol.clone(),
+        metadata.uri.clone(),
+    )?;
+    for (key, value) in metadata.additional_metadata {
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                token_program.clone(),
+                TokenMetadataUpdateField {
+                    program_id: token_program.clone(),
+                    metadata: mint.clone(),
+                    update_authority: authority.clone(),
+                },
+                &[authority_seeds],
+            ),
+            Field::Key(key),
+            value,
+        )?;
+    }
+
+    // 4. minting the one badge to the agent and retiring the mint authority
+    associated_token::create(CpiContext::new(
+        associated_token_program.to_account_info(),
+        associated_token::Create {
+            payer: payer.clone(),
+            associated_token: agent_badge_account.clone(),
+            authority: agent.clone(),
+            mint: mint.clone(),
+            system_program: system_program.to_account_info(),
+            token_program: token_program.clone(),
+        },
+    ))?;
+    token_2022::mint_to(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            token_2022::MintTo {
+                mint: mint.clone(),
+                to: agent_badge_account.clone(),
+                authority: authority.clone(),
+            },
+            &[authority_seeds],
+        ),
+        1,
+    )?;
+    token_2022::set_authority(
+        CpiContext::new_with_signer(
+            token_program,
+            token_2022::SetAuthority {
+                current_authority: authority.clone(),
+                account_or_mint: mint.clone(),
+            },
+            &[authority_seeds],
+        ),
+        AuthorityType::MintTokens,
+        None,
+    )?;
+
+    emit!(BadgeMinted {
+        bounty: bounty_key,
+        solver: agent.key(),
+        mint: mint.key(),
+        claim,
+        timestamp: settled_at,
+    });
+
+    Ok(())
+}
+
+/// Makes up a token escrow that holds less than the bounty records, as a
+/// transfer-fee mint or rounding can leave it, from the market's insurance
+/// fund for the reward mint, returning the top-up. Shortfalls above the Config
+/// `max_insurance_topup`, or that no fund was passed for or it can't cover,
+/// fail with `EscrowUnderfunded` as they would without insurance.
+#[allow(clippy::too_many_arguments)]
+pub fn cover_shortfall<'info>(
+    bounty: &Account<'info, Bounty>,
+    config: &Config,
+    insurance_fund: Option<&mut Account<'info, InsuranceFund>>,
+    insurance_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    bounty_token_account: Option<&mut InterfaceAccount<'info, TokenAccount>>,
+    reward_mint: Option<&InterfaceAccount<'info, Mint>>,
+    token_program: &Interface<'info, TokenInterface>,
+) -> Result<u64> {
+    let balance = escrow_balance(bounty, bounty_token_account.as_deref())?;
+    let shortfall = bounty.escrow_amount.saturating_sub(balance);
+    if shortfall == 0 {
+        return Ok(0);
+    }
+    require!(
+        bounty.reward_currency == RewardCurrency::Spl
+            && shortfall <= config.max_insurance_topup
+            && insurance_fund.is_some(),
+        BountyForgeError::EscrowUnderfunded
+    );
+    let (Some(fund), Some(fund_token_account), Some(bounty_token_account), Some(reward_mint)) = (
+        insurance_fund,
+        insurance_token_account,
+        bounty_token_account,
+        reward_mint,
+    ) else {
+        return err!(BountyForgeError::MissingTokenAccount);
+    };
+    check_insurance_accounts(fund, fund_token_account)?;
+    fund.draw_topup(shortfall)
+        .map_err(|_| error!(BountyForgeError::EscrowUnderfunded))?;
+
+    let mint = fund.mint;
+    let seeds: &[&[u8]] = &[
+        b"insurance",
+        bounty.market.as_bytes(),
+        mint.as_ref(),
+        &[fund.bump],
+    ];
+    transfer_checked(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            TransferChecked {
+                from: fund_token_account.to_account_info(),
+                mint: reward_mint.to_account_info(),
+                to: bounty_token_account.to_account_info(),
+                authority: fund.to_account_info(),
+            },
+            &[seeds],
+        ),
+        shortfall,
+        reward_mint.decimals,
+    )?;
+    // a transfer fee on the top-up itself would leave the escrow short still
+    bounty_token_account.reload()?;
+    require!(
+        bounty_token_account.amount >= bounty.escrow_amount,
+        BountyForgeError::EscrowUnderfunded
+    );
+    msg!("escrow topped up by {} from insurance", shortfall);
+
+    fund.emit_update(fund.key())?;
+    Ok(shortfall)
+}
+
+/// Takes the insurance fund's cut of a settlement's protocol fee,
+/// `insurance_fee_bps` from the Config, into the fund's token account and
+/// returns what is left of the fee for the treasury. Without a fund the whole
+/// fee is left.
+#[allow(clippy::too_many_arguments)]
+pub fn pay_insurance<'info>(
+    bounty: &Account<'info, Bounty>,
+    config: &Config,
+    insurance_fund: Option<&mut Account<'info, InsuranceFund>>,
+    insurance_token_account: Option<&mut InterfaceAccount<'info, TokenAccount>>,
+    bounty_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    reward_mint: Option<&InterfaceAccount<'info, Mint>>,
+    token_program: &Interface<'info, TokenInterface>,
+    fee_amount: u64,
+) -> Result<u64> {
+    let Some(fund) = insurance_fund else {
+        return Ok(fee_amount);
+    };
+    let (treasury_amount, insurance_amount) = split_fee(fee_amount, config.insurance_fee_bps)?;
+    if insurance_amount == 0 {
+        return Ok(fee_amount);
+    }
+    let fund_token_account =
+        insurance_token_account.ok_or(BountyForgeError::MissingTokenAccount)?;
+    check_insurance_accounts(fund, fund_token_account)?;
+
+    let before = fund_token_account.amount;
+    pay_from_escrow(
+        bounty,
+        bounty_token_account,
+        reward_mint,
+        &fund.to_account_info(),
+        Some(fund_token_account),
+        token_program,
+        insurance_amount,
+    )?;
+    fund_token_account.reload()?;
+    fund.record_fee(fund_token_account.amount.saturating_sub(before))?;
+    fund.emit_update(fund.key())?;
+
+    Ok(treasury_amount)
+}
+
+fn check_insurance_accounts(
+    fund: &Account<InsuranceFund>,
+    fund_token_account: &InterfaceAccount<TokenAccount>,
+) -> Result<()> {
+    require!(
+        fund_token_account.owner == fund.key() && fund_token_account.mint == fund.mint,
+        BountyForgeError::InsuranceAccountMismatch
+    );
+    Ok(())
+}
+
+/// Checks a team table: up to MAX_TEAM_MEMBERS distinct members with
+/// non-zero shares summing to exactly MAX_FEE_BPS. Empty means no team.
+pub fn validate_team(team: &[TeamShare]) -> Result<()> {
+    if team.is_empty() {
+        return Ok(());
+    }
+    require!(
+        team.len() <= MAX_TEAM_MEMBERS,
+        BountyForgeError::InvalidTeamSplit
+    );
+    let mut total = 0u32;
+    for (i, share) in team.iter().enumerate() {
+        require!(
+            share.share_bps > 0 && !team[..i].iter().any(|other| other.member == share.member),
+            BountyForgeError::InvalidTeamSplit
+        );
+        total += share.share_bps as u32;
+    }
+    require!(
+        total == MAX_FEE_BPS as u32,
+        BountyForgeError::InvalidTeamSplit
+    );
+    Ok(())
+}
+
+/// Splits `amount` into the solver's share and the protocol fee. The solver's
+/// share rounds down, so any remainder goes to the treasury.
+pub fn split_fee(amount: u64, fee_bps: u16) -> Result<(u64, u64)> {
+    let solver_bps = MAX_FEE_BPS.saturating_sub(fee_bps);
+    let solver_amount = (amount as u128)
+        .checked_mul(solver_bps as u128)
+        .and_then(|scaled| scaled.checked_div(MAX_FEE_BPS as u128))
+        .and_then(|share| u64::try_from(share).ok())
+        .ok_or(BountyForgeError::FeeCalculationOverflow)?;
+
+    Ok((solver_amount, amount.saturating_sub(solver_amount)))
+}
+
+/// Splits `amount` three ways: the solver's share, the attestor's share and
+/// the protocol fee. Both shares round down, so the three always sum to
+/// `amount` with any remainder going to the treasury.
+pub fn split_fees(amount: u64, fee_bps: u16, attestor_fee_bps: u16) -> Result<(u64, u64, u64)> {
+    let (solver_amount, fees) = split_fee(amount, fee_bps.saturating_add(attestor_fee_bps))?;
+    let attestor_amount = (amount as u128)
+        .checked_mul(attestor_fee_bps as u128)
+        .and_then(|scaled| scaled.checked_div(MAX_FEE_BPS as u128))
+        .and_then(|share| u64::try_from(share).ok())
+        .ok_or(BountyForgeError::FeeCalculationOverflow)?;
+    let fee_amount = fees
+        .checked_sub(attestor_amount)
+        .ok_or(BountyForgeError::FeeCalculationOverflow)?;
+
+    Ok((solver_amount, attestor_amount, fee_amount))
+}
+
+/// Checks that `oracle` is a Switchboard on-demand pull feed updated within
+/// `MAX_ORACLE_STALENESS_SECONDS` of `now`, and returns its value and exponent.
+pub fn verify_switchboard_feed(oracle: &AccountInfo, now: i64) -> Result<(i128, i32)> {
+    require_keys_eq!(
+        *oracle.owner,
+        SWITCHBOARD_ON_DEMAND_PROGRAM_ID,
+        BountyForgeError::OracleVerificationFailed
+    );
+
+    let data = oracle.try_borrow_data()?;
+    let feed = PullFeedAccountData::parse(data)
+        .map_err(|_| error!(BountyForgeError::OracleVerificationFailed))?;
+
+    require!(
+        now.saturating_sub(feed.last_update_timestamp) <= MAX_ORACLE_STALENESS_SECONDS,
+        BountyForgeError::OracleDataStale
+    );
+
+    Ok((feed.result.value, -(PRECISION as i32)))
+}
+
+/// Checks that `oracle` is a fully verified Pyth price update published within
+/// `MAX_ORACLE_STALENESS_SECONDS` of `now`, with a confidence interval no wider
+/// than `MAX_ORACLE_CONFIDENCE_BPS` of the price. Returns the price and its exponent.
+pub fn verify_pyth_price(oracle: &AccountInfo, now: i64) -> Result<(i128, i32)> {
+    require_keys_eq!(
+        *oracle.owner,
+        pyth_solana_receiver_sdk::ID,
+        BountyForgeError::OracleVerificationFailed
+    );
+
+    let data = oracle.try_borrow_data()?;
+    let price_update = PriceUpdateV2::try_deserialize(&mut &data[..])
+        .map_err(|_| error!(BountyForgeError::OracleVerificationFailed))?;
+
+    require!(
+        price_update.verification_level.gte(VerificationLevel::Full),
+        BountyForgeError::OracleVerificationFailed
+    );
+
+    let message = &price_update.price_message;
+    require!(
+        now.saturating_sub(message.publish_time) <= MAX_ORACLE_STALENESS_SECONDS,
+        BountyForgeError::OracleDataStale
+    );
+
+    let price = message.price.unsigned_abs();
+    require!(
+        price > 0
+            && (message.conf as u128) * 10_000
+                <= (price as u128) * (MAX_ORACLE_CONFIDENCE_BPS as u128),
+        BountyForgeError::OracleVerificationFailed
+    );
+
+    Ok((message.price as i128, message.exponent))
+}
+
+/// Checks a bounty's metadata URI or a submission's solution URI fits its
+/// space and is plain ASCII. An empty URI is allowed and means there is none.
+pub fn validate_metadata_uri(uri: &str) -> Result<()> {
+    require!(
+        uri.len() <= MAX_METADATA_URI_LEN,
+        BountyForgeError::UriTooLong
+    );
+    require!(uri.is_ascii(), BountyForgeError::UriNotAscii);
+    Ok(())
+}
+
+/// Bytes an attestor signs for an attestation: agent, then the little-endian
+/// solution id, then the solution hash.
+pub fn attestation_message(agent: &Pubkey, solution_id: u64, solution_hash: &[u8; 32]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(32 + 8 + 32);
+    message.extend_from_slice(agent.as_ref());
+    message.extend_from_slice(&solution_id.to_le_bytes());
+    message.extend_from_slice(solution_hash);
+    message
+}
+
+/// Returns the key that signed `message` in the ed25519 program instruction
+/// right before the current one, or `None` if that instruction is not one.
+pub fn signing_attestor(
+    instructions_sysvar: &AccountInfo,
+    message: &[u8],
+) -> Result<Option<Pubkey>> {
+    let Some((signer, signed_message)) = preceding_ed25519_signature(instructions_sysvar)? else {
+        return Ok(None);
+    };
+    require!(
+        signed_message == message,
+        BountyForgeError::AttestorMessageMismatch
+    );
+    Ok(Some(signer))
+}
+
+/// Bytes the payment gateway signs once a verification has been paid for
+/// over x402: bounty, then the solution hash, then the little-endian amount paid.
+pub fn payment_receipt_message(
+    bounty: &Pubkey,
+    solution_hash: &[u8; 32],
+    amount_paid: u64,
+) -> Vec<u8> {
+    let mut message = Vec::with_capacity(32 + 32 + 8);
+    message.extend_from_slice(bounty.as_ref());
+    message.extend_from_slice(solution_hash);
+    message.extend_from_slice(&amount_paid.to_le_bytes());
+    message
+}
+
+/// Checks that the ed25519 program instruction right before the current one
+/// is `gateway`'s receipt for this bounty's solution, paying at least
+/// `min_fee` and never nothing, and returns the hash of the signed message
+/// and the amount paid for the settlement receipt.
+pub fn verify_payment_receipt(
+    instructions_sysvar: Option<&AccountInfo>,
+    gateway: Option<Pubkey>,
+    min_fee: u64,
+    bounty: &Pubkey,
+    solution_hash: &[u8; 32],
+) -> Result<([u8; 32], u64)> {
+    let (signer, message) = instructions_sysvar
+        .map(preceding_ed25519_signature)
+        .transpose()?
+        .flatten()
+        .ok_or(BountyForgeError::PaymentReceiptMissing)?;
+    require!(
+        gateway == Some(signer),
+        BountyForgeError::PaymentReceiptSignerMismatch
+    );
+
+    let expected = payment_receipt_message(bounty, solution_hash, 0);
+    require!(
+        message.len() == expected.len() && message[..64] == expected[..64],
+        BountyForgeError::PaymentReceiptMismatch
+    );
+    let mut amount_bytes = [0u8; 8];
+    amount_bytes.copy_from_slice(&message[64..]);
+    let amount_paid = u64::from_le_bytes(amount_bytes);
+    require!(
+        amount_paid > 0 && amount_paid >= min_fee,
+        BountyForgeError::PaymentReceiptUnderpaid
+    );
+
+    Ok((hash::hash(&message).to_bytes(), amount_paid))
+}
+
+/// Fails unless the current instruction is a top-level one of this program,
+/// rather than reached through another program's CPI.
+pub fn require_top_level(instructions_sysvar: &AccountInfo) -> Result<()> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let current = load_instruction_at_checked(current_index as usize, instructions_sysvar)?;
+    require_keys_eq!(
+        current.program_id,
+        crate::ID,
+        BountyForgeError::CpiNotAllowed
+    );
+    Ok(())
+}
+
+/// Returns the key and message of the single signature checked by the
+/// ed25519 program instruction right before the current one, or `None` if
+/// that instruction is not one.
+fn preceding_ed25519_signature(
+    instructions_sysvar: &AccountInfo,
+) -> Result<Option<(Pubkey, Vec<u8>)>> {
+    let current_index = load_current_index_checked(instructions_sysvar)?;
+    let Some(ed25519_ix) = current_index
+        .checked_sub(1)
+        .map(|index| load_instruction_at_checked(index as usize, instructions_sysvar))
+        .transpose()?
+        .filter(|ix| ix.program_id == ed25519_program::ID)
+    else {
+        return Ok(None);
+    };
+
+    // header: signature count and padding, then one 14 byte offsets struct
+    let data = &ed25519_ix.data;
+    require!(
+        data.len() >= 16 && data[0] == 1,
+        BountyForgeError::InvalidEd25519Instruction
+    );
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let public_key_offset = read_u16(6) as usize;
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+
+    // every offset must point into this same instruction's data
+    require!(
+        [read_u16(4), read_u16(8), read_u16(14)]
+            .iter()
+            .all(|&index| index == u16::MAX),
+        BountyForgeError::InvalidEd25519Instruction
+    );
+
+    let signed_key = data
+        .get(public_key_offset..public_key_offset + 32)
+        .ok_or(BountyForgeError::InvalidEd25519Instruction)?;
+    let signed_message = data
+        .get(message_offset..message_offset + message_size)
+        .ok_or(BountyForgeError::InvalidEd25519Instruction)?;
+
+    let signer =
+        Pubkey::try_from(signed_key).map_err(|_| BountyForgeError::InvalidEd25519Instruction)?;
+    Ok(Some((signer, signed_message.to_vec())))
+}
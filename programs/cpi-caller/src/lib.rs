@@ -0,0 +1,48 @@
+#![allow(unexpected_cfgs, deprecated)]
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+
+declare_id!("9LSU4qsQDxWrzUTbhNjfKs92qH6m5LFrEdP4Gppehw6N");
+
+// Only deployed by the tests, to check which bountyforge instructions refuse
+// to be reached through another program.
+#[program]
+pub mod cpi_caller {
+    use super::*;
+
+    /// Invokes `target` with `data` and the remaining accounts as they were
+    /// passed, signers included.
+    pub fn forward<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Forward<'info>>,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let accounts = ctx
+            .remaining_accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: account.key(),
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect();
+        let instruction = Instruction {
+            program_id: ctx.accounts.target.key(),
+            accounts,
+            data,
+        };
+
+        let mut account_infos = ctx.remaining_accounts.to_vec();
+        account_infos.push(ctx.accounts.target.to_account_info());
+        invoke(&instruction, &account_infos)?;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Forward<'info> {
+    /// CHECK: Program being called
+    #[account(executable)]
+    pub target: UncheckedAccount<'info>,
+}
@@ -5,70 +5,145 @@ import {
     TOKEN_PROGRAM_ID,
     getAssociatedTokenAddressSync,
     getOrCreateAssociatedTokenAccount,
-    createAssociatedTokenAccountInstruction,
 } from "@solana/spl-token";
 import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import * as fs from "fs";
 import * as path from "path";
 
-function deriveBountyPda(programId: PublicKey, bountyId: number): [PublicKey, number] {
+function deriveBountyPda(
+    programId: PublicKey,
+    creator: PublicKey,
+    bountyId: number
+): [PublicKey, number] {
     return PublicKey.findProgramAddressSync(
-        [Buffer.from("bounty"), new anchor.BN(bountyId).toBuffer("le", 8)],
+        [Buffer.from("bounty"), creator.toBuffer(), new anchor.BN(bountyId).toBuffer("le", 8)],
         programId
     );
 }
 
+function deriveBountyCounterPda(programId: PublicKey, creator: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("counter"), creator.toBuffer()],
+        programId
+    );
+}
+
+function deriveCreatorIndexPda(programId: PublicKey, creator: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("index"), creator.toBuffer()],
+        programId
+    );
+}
+
+function deriveCreatorProfilePda(programId: PublicKey, creator: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("creator"), creator.toBuffer()],
+        programId
+    );
+}
+
+function deriveConfigPda(programId: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync([Buffer.from("config")], programId);
+}
+
+// post_bounty enforces MIN_POST_INTERVAL_SECONDS between posts from one creator
+const POST_COOLDOWN_MS = 3000;
+const BOUNTY_LIFETIME_SECONDS = 7 * 24 * 60 * 60;
+
 async function postBounty(
     program: Program<Bountyforge>,
     connection: anchor.web3.Connection,
     creator: Keypair,
     usdcMint: PublicKey,
     creatorTokenAccount: PublicKey,
-    bountyId: number,
     bountyType: any,
+    target: PublicKey,
     description: string,
     reward: number
 ): Promise<void> {
-    const [bountyPda] = deriveBountyPda(program.programId, bountyId);
-    
-    if (await connection.getAccountInfo(bountyPda)) {
-        console.log(`Bounty #${bountyId} exists, skipping`);
-        return;
-    }
-
+    // post_bounty assigns the creator's next id from their counter
+    const [bountyCounter] = deriveBountyCounterPda(program.programId, creator.publicKey);
+    const bountyId = (await program.account.bountyCounter.fetch(bountyCounter)).nextId.toNumber();
+    const [bountyPda] = deriveBountyPda(program.programId, creator.publicKey, bountyId);
+
+    const [creatorProfile] = deriveCreatorProfilePda(program.programId, creator.publicKey);
+    const [creatorIndex] = deriveCreatorIndexPda(program.programId, creator.publicKey);
+    const [config] = deriveConfigPda(program.programId);
+    // post_bounty creates the escrow ATA itself
     const bountyTokenAccount = getAssociatedTokenAddressSync(usdcMint, bountyPda, true);
-    const instructions = [];
-
-    if (!await connection.getAccountInfo(bountyTokenAccount)) {
-        instructions.push(
-            createAssociatedTokenAccountInstruction(
-                creator.publicKey,
-                bountyTokenAccount,
-                bountyPda,
-                usdcMint,
-                TOKEN_PROGRAM_ID,
-                new PublicKey("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL")
-            )
-        );
-    }
 
     const tx = await program.methods
-        .postBounty(new anchor.BN(bountyId), bountyType, description, new anchor.BN(reward))
+        .postBounty({
+            bountyType,
+            target,
+            description,
+            reward: new anchor.BN(reward),
+            rewardCurrency: { spl: {} },
+            requiresOracle: false,
+            oracleFeeds: [],
+            oracleKind: { switchboard: {} },
+            oracleMinValue: null,
+            oracleMaxValue: null,
+            oracleExponent: 0,
+            deadline: new anchor.BN(Math.floor(Date.now() / 1000) + BOUNTY_LIFETIME_SECONDS),
+            maxAttestationAge: null,
+            requiredAttestations: 0,
+            minAttestorTrackRecord: new anchor.BN(0),
+            attestationSource: { internal: {} },
+            requiresReveal: false,
+            requiresPaymentReceipt: false,
+            hashAlgo: { sha256: {} },
+            encryptionPubkey: null,
+            criteriaHash: Array(32).fill(0),
+            allowCpi: false,
+            competitive: false,
+            maxSubmissions: 0,
+            prizes: [],
+            maxClaims: 1,
+            allowRepeatSolver: false,
+            mintBadge: false,
+            assignee: null,
+            requiresApplication: false,
+            challengePeriodSlots: null,
+            arbiter: null,
+            requiredStake: new anchor.BN(0),
+            bond: new anchor.BN(0),
+            settleWindowSlots: null,
+            resubmissionCooldownSeconds: null,
+            fullRewardUntil: new anchor.BN(0),
+            decayBpsPerHour: 0,
+            decayFloorBps: 0,
+            metadataUri: "",
+            minReputation: new anchor.BN(0),
+            allowlist: [],
+            milestones: [],
+            vestingDurationSeconds: new anchor.BN(0),
+            settlementAuthority: null,
+            market: "",
+        })
         .accountsStrict({
             creator: creator.publicKey,
+            bountyCounter,
             bounty: bountyPda,
-            usdcMint,
+            creatorProfile,
+            creatorIndex,
+            creatorBond: null,
+            config,
+            rewardMint: usdcMint,
+            targetAccount: null,
             creatorTokenAccount,
             bountyTokenAccount,
+            fundingPool: null,
+            poolTokenAccount: null,
             tokenProgram: TOKEN_PROGRAM_ID,
             associatedTokenProgram: new PublicKey("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"),
             systemProgram: SystemProgram.programId,
         })
-        .preInstructions(instructions)
         .signers([creator])
         .rpc();
 
     console.log(`✓ Bounty #${bountyId}: ${tx.slice(0, 8)}...`);
+    await new Promise((resolve) => setTimeout(resolve, POST_COOLDOWN_MS));
 }
 
 async function main() {
@@ -120,29 +195,44 @@ async function main() {
         process.exit(1);
     }
 
+    const [bountyCounter] = deriveBountyCounterPda(program.programId, creator.publicKey);
+    if (!await connection.getAccountInfo(bountyCounter)) {
+        await program.methods
+            .initializeBountyCounter()
+            .accountsStrict({
+                creator: creator.publicKey,
+                bountyCounter,
+                systemProgram: SystemProgram.programId,
+            })
+            .signers([creator])
+            .rpc();
+    }
+
+    // post_bounty rejects descriptions over 50 bytes
     const bounties = [
         {
-            id: 100,
             type: { walletIntelligence: {} },
-            desc: "Analyze wallet 7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU - determine if it's smart money",
+            target: new PublicKey("7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU"),
+            desc: "Check wallet activity",
             reward: 1e6,
         },
         {
-            id: 101,
             type: { walletIntelligence: {} },
-            desc: "Analyze wallet 9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM - check PnL and status",
+            target: new PublicKey("9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM"),
+            desc: "PnL of the target wallet",
             reward: 2e6,
         },
         {
-            id: 102,
+            // a screen across tokens, so no single mint to target
             type: { tokenScreening: {} },
-            desc: "Screen Solana tokens: >$100k volume, >10% holder growth",
+            target: PublicKey.default,
+            desc: "Tokens: >$100k volume, >10% holder growth",
             reward: 1.5e6,
         },
     ];
 
     for (const b of bounties) {
-        await postBounty(program, connection, creator, usdcMint, creatorTokenAccount, b.id, b.type, b.desc, b.reward);
+        await postBounty(program, connection, creator, usdcMint, creatorTokenAccount, b.type, b.target, b.desc, b.reward);
     }
 
     console.log("Done");
@@ -0,0 +1,143 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("allowlist", () => {
+  let ctx: TestContext;
+  let vetted: Keypair;
+  let alsoVetted: Keypair;
+  let outsider: Keypair;
+  let bountyPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    vetted = Keypair.generate();
+    alsoVetted = Keypair.generate();
+    outsider = Keypair.generate();
+    await airdropSol(ctx.connection, vetted.publicKey);
+    await airdropSol(ctx.connection, alsoVetted.publicKey);
+    await airdropSol(ctx.connection, outsider.publicKey);
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    bountyPda = await postBounty(ctx, "KYC'd agents only", 10 * 10 ** 6, {
+      allowlist: [vetted.publicKey, alsoVetted.publicKey],
+    });
+  });
+
+  async function submitAs(agent: Keypair, value: number): Promise<void> {
+    const hash = generateSolutionHashWithValue(value);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+  }
+
+  async function addAgent(agent: PublicKey, signer: Keypair = ctx.creator) {
+    await ctx.program.methods
+      .addAllowlistedAgent(agent)
+      .accountsPartial({ creator: signer.publicKey, bounty: bountyPda })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function removeAgent(agent: PublicKey) {
+    await ctx.program.methods
+      .removeAllowlistedAgent(agent)
+      .accountsPartial({ creator: ctx.creator.publicKey, bounty: bountyPda })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function allowlisted(): Promise<string[]> {
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    return bountyAccount.allowlist
+      .slice(0, bountyAccount.allowlistLen)
+      .map((agent) => agent.toString());
+  }
+
+  it("Accepts a submission from a listed agent", async () => {
+    expect(await allowlisted()).to.deep.equal([
+      vetted.publicKey.toString(),
+      alsoVetted.publicKey.toString(),
+    ]);
+
+    await submitAs(vetted, 0xb1);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.solver.toString()).to.equal(
+      vetted.publicKey.toString()
+    );
+  });
+
+  it("Fails when an unlisted agent submits", async () => {
+    try {
+      await submitAs(outsider, 0xb2);
+      expect.fail("Should have failed - agent not allowlisted");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AgentNotAllowlisted");
+    }
+  });
+
+  it("Stops a removed agent from submitting", async () => {
+    await removeAgent(vetted.publicKey);
+    expect(await allowlisted()).to.deep.equal([
+      alsoVetted.publicKey.toString(),
+    ]);
+
+    try {
+      await submitAs(vetted, 0xb3);
+      expect.fail("Should have failed - agent was removed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AgentNotAllowlisted");
+    }
+
+    await submitAs(alsoVetted, 0xb4);
+  });
+
+  it("Lets the creator add an agent while the bounty is open", async () => {
+    await addAgent(outsider.publicKey);
+    await submitAs(outsider, 0xb5);
+  });
+
+  it("Opens the bounty to anyone once the list is emptied", async () => {
+    await removeAgent(vetted.publicKey);
+    await removeAgent(alsoVetted.publicKey);
+    expect(await allowlisted()).to.be.empty;
+
+    await submitAs(outsider, 0xb6);
+  });
+
+  it("Fails to add an agent twice", async () => {
+    try {
+      await addAgent(vetted.publicKey);
+      expect.fail("Should have failed - already allowlisted");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AgentAlreadyAllowlisted");
+    }
+  });
+
+  it("Fails when someone other than the creator changes the list", async () => {
+    try {
+      await addAgent(outsider.publicKey, outsider);
+      expect.fail("Should have failed - not the creator");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedAllowlistChange");
+    }
+  });
+});
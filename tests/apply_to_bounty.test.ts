@@ -0,0 +1,168 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveApplicationPda,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("apply_to_bounty", () => {
+  let ctx: TestContext;
+  let applicant: Keypair;
+  let rival: Keypair;
+  let bountyPda: anchor.web3.PublicKey;
+  let applicationPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+
+    applicant = Keypair.generate();
+    rival = Keypair.generate();
+    await airdropSol(ctx.connection, applicant.publicKey);
+    await airdropSol(ctx.connection, rival.publicKey);
+
+    bountyPda = await postBounty(
+      ctx,
+      "Screened applicants only",
+      10 * 10 ** 6,
+      { requiresApplication: true }
+    );
+    applicationPda = await apply(applicant);
+  });
+
+  async function apply(agent: Keypair): Promise<anchor.web3.PublicKey> {
+    await ctx.program.methods
+      .applyToBounty(Array.from(generateSolutionHashWithValue(0xb0)))
+      .accountsPartial({ agent: agent.publicKey, bounty: bountyPda })
+      .signers([agent])
+      .rpc();
+    const [pda] = deriveApplicationPda(
+      ctx.program.programId,
+      bountyPda,
+      agent.publicKey
+    );
+    return pda;
+  }
+
+  async function accept(application: anchor.web3.PublicKey): Promise<void> {
+    await ctx.program.methods
+      .acceptApplication()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        application,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function submitAs(
+    agent: Keypair,
+    application: anchor.web3.PublicKey | null
+  ): Promise<void> {
+    const hash = generateSolutionHashWithValue(0xb1);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(
+      ctx,
+      agent,
+      bountyPda,
+      attestation,
+      hash,
+      [],
+      [],
+      application
+    );
+  }
+
+  async function closeApplication(agent: Keypair): Promise<void> {
+    await ctx.program.methods
+      .closeApplication()
+      .accountsPartial({ agent: agent.publicKey, bounty: bountyPda })
+      .signers([agent])
+      .rpc();
+  }
+
+  it("Submits after the creator accepts the application", async () => {
+    const pending = await ctx.program.account.application.fetch(applicationPda);
+    expect(pending.agent.toString()).to.equal(applicant.publicKey.toString());
+    expect(pending.status).to.deep.equal({ pending: {} });
+
+    await accept(applicationPda);
+
+    const accepted = await ctx.program.account.application.fetch(
+      applicationPda
+    );
+    expect(accepted.status).to.deep.equal({ accepted: {} });
+    const assigned = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(assigned.assignee.toString()).to.equal(
+      applicant.publicKey.toString()
+    );
+
+    await submitAs(applicant, applicationPda);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+  });
+
+  it("Fails to submit before the application is accepted", async () => {
+    try {
+      await submitAs(applicant, applicationPda);
+      expect.fail("Should have failed - application still pending");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ApplicationNotAccepted");
+    }
+  });
+
+  it("Fails to submit without an application", async () => {
+    try {
+      await submitAs(rival, null);
+      expect.fail("Should have failed - no application");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ApplicationNotAccepted");
+    }
+  });
+
+  it("Lets passed-over applicants close their application", async () => {
+    const rivalApplication = await apply(rival);
+    await accept(applicationPda);
+
+    await closeApplication(rival);
+    expect(await ctx.connection.getAccountInfo(rivalApplication)).to.be.null;
+
+    try {
+      await closeApplication(applicant);
+      expect.fail("Should have failed - applicant holds the bounty");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ApplicationStillActive");
+    }
+  });
+
+  it("Fails to accept a second application", async () => {
+    const rivalApplication = await apply(rival);
+    await accept(applicationPda);
+
+    try {
+      await accept(rivalApplication);
+      expect.fail("Should have failed - bounty already assigned");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyAlreadyAssigned");
+    }
+  });
+});
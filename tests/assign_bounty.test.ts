@@ -0,0 +1,118 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("assign_bounty", () => {
+  let ctx: TestContext;
+  let assignee: Keypair;
+  let outsider: Keypair;
+  let bountyPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    assignee = Keypair.generate();
+    outsider = Keypair.generate();
+    await airdropSol(ctx.connection, assignee.publicKey);
+    await airdropSol(ctx.connection, outsider.publicKey);
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    bountyPda = await postBounty(
+      ctx,
+      "Negotiated job",
+      10 * 10 ** 6,
+      { assignee: assignee.publicKey }
+    );
+  });
+
+  async function submitAs(agent: Keypair, value: number): Promise<void> {
+    const hash = generateSolutionHashWithValue(value);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+  }
+
+  async function assign(
+    to: PublicKey | null,
+    signer: Keypair = ctx.creator
+  ): Promise<void> {
+    await ctx.program.methods
+      .assignBounty(to)
+      .accountsPartial({ creator: signer.publicKey, bounty: bountyPda })
+      .signers([signer])
+      .rpc();
+  }
+
+  it("Accepts a submission from the assigned agent", async () => {
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.assignee.toString()).to.equal(
+      assignee.publicKey.toString()
+    );
+
+    await submitAs(assignee, 0xa1);
+
+    const submitted = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(submitted.status).to.deep.equal({ submitted: {} });
+    expect(submitted.solver.toString()).to.equal(assignee.publicKey.toString());
+  });
+
+  it("Fails when another agent submits", async () => {
+    try {
+      await submitAs(outsider, 0xa2);
+      expect.fail("Should have failed - not the assigned agent");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotAssignedAgent");
+    }
+  });
+
+  it("Lets the creator reassign the bounty", async () => {
+    await assign(outsider.publicKey);
+
+    try {
+      await submitAs(assignee, 0xa3);
+      expect.fail("Should have failed - bounty was reassigned");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotAssignedAgent");
+    }
+
+    await submitAs(outsider, 0xa4);
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.solver.toString()).to.equal(
+      outsider.publicKey.toString()
+    );
+  });
+
+  it("Opens the bounty to anyone once the assignment is cleared", async () => {
+    await assign(null);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.assignee).to.be.null;
+
+    await submitAs(outsider, 0xa5);
+  });
+
+  it("Fails when someone other than the creator assigns", async () => {
+    try {
+      await assign(outsider.publicKey, outsider);
+      expect.fail("Should have failed - not the creator");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedAssignment");
+    }
+  });
+});
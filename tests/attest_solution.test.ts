@@ -1,48 +1,102 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Keypair, SystemProgram } from "@solana/web3.js";
+import {
+  Ed25519Program,
+  Keypair,
+  PublicKey,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SystemProgram,
+} from "@solana/web3.js";
 import { expect } from "chai";
 import {
   setupTestContext,
+  DEFAULT_HASH_ALGO,
   deriveAttestationPda,
   airdropSol,
-  generateRandomId,
+  ensureCreatorBalance,
+  initReputation,
+  postBounty,
+  createAttestation,
+  nextSolutionId,
   generateSolutionHash,
   generateSolutionHashWithValue,
+  attestationMessage,
+  updateConfig,
+  registerAttestorIx,
+  fetchEvents,
+  findEvent,
   TestContext,
 } from "./helpers";
 
 describe("attest_solution", () => {
   let ctx: TestContext;
   let agent: Keypair;
+  let bountyPda: anchor.web3.PublicKey;
   let solutionId: number;
   let attestationPda: anchor.web3.PublicKey;
   let solutionHash: Buffer;
 
   before(async () => {
     ctx = await setupTestContext();
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    bountyPda = await postBounty(ctx, "Bounty to attest against", 10 * 10 ** 6);
   });
 
-  beforeEach(() => {
+  async function registerAttestor(attestor: anchor.web3.PublicKey) {
+    await ctx.provider.sendAndConfirm(
+      new anchor.web3.Transaction().add(await registerAttestorIx(ctx, attestor))
+    );
+  }
+
+  beforeEach(async () => {
     agent = Keypair.generate();
-    solutionId = generateRandomId();
-    [attestationPda] = deriveAttestationPda(ctx.program.programId, solutionId);
+    await airdropSol(ctx.connection, agent.publicKey);
+    await registerAttestor(agent.publicKey);
+    await initReputation(ctx, agent);
+    // a fresh agent's first attestation
+    solutionId = 0;
+    [attestationPda] = deriveAttestationPda(
+      ctx.program.programId,
+      agent.publicKey,
+      bountyPda,
+      solutionId
+    );
     solutionHash = generateSolutionHash();
   });
 
-  it("Creates an attestation successfully", async () => {
-    await airdropSol(ctx.connection, agent.publicKey);
-
-    const beforeTimestamp = Math.floor(Date.now() / 1000);
-
-    await ctx.program.methods
-      .attestSolution(new anchor.BN(solutionId), Array.from(solutionHash))
+  function attest(
+    signer: Keypair,
+    bounty: PublicKey,
+    hash: Buffer,
+    attestation: PublicKey
+  ) {
+    return ctx.program.methods
+      .attestSolution(Array.from(hash), DEFAULT_HASH_ALGO)
       .accountsPartial({
-        agent: agent.publicKey,
-        attestation: attestationPda,
+        agent: signer.publicKey,
+        bounty,
+        attestation,
+        instructionsSysvar: null,
         systemProgram: SystemProgram.programId,
       })
-      .signers([agent])
+      .signers([signer])
       .rpc();
+  }
+
+  it("Creates an attestation successfully", async () => {
+    const beforeTimestamp = Math.floor(Date.now() / 1000);
+
+    const signature = await attest(
+      agent,
+      bountyPda,
+      solutionHash,
+      attestationPda
+    );
 
     const afterTimestamp = Math.floor(Date.now() / 1000);
 
@@ -57,153 +111,196 @@ describe("attest_solution", () => {
     expect(attestationAccount.agent.toString()).to.equal(
       agent.publicKey.toString()
     );
+    expect(attestationAccount.bounty.toString()).to.equal(bountyPda.toString());
     expect(attestationAccount.verified).to.be.false;
+    expect(attestationAccount.attestor).to.be.null;
 
     const attestationTimestamp = attestationAccount.timestamp.toNumber();
     expect(attestationTimestamp).to.be.at.least(beforeTimestamp - 5);
     expect(attestationTimestamp).to.be.at.most(afterTimestamp + 5);
+
+    const event = findEvent(
+      await fetchEvents(ctx, signature),
+      "SolutionAttested"
+    );
+    expect(event.data.attestation.toString()).to.equal(
+      attestationPda.toString()
+    );
+    expect(event.data.agent.toString()).to.equal(agent.publicKey.toString());
+    expect(event.data.solutionId.toNumber()).to.equal(solutionId);
+    expect(Buffer.from(event.data.solutionHash)).to.deep.equal(solutionHash);
+    expect(event.data.timestamp.toNumber()).to.be.within(
+      beforeTimestamp - 5,
+      afterTimestamp + 5
+    );
   });
 
   it("Fails when trying to create duplicate attestation with same solution_id", async () => {
-    await airdropSol(ctx.connection, agent.publicKey);
-
-    await ctx.program.methods
-      .attestSolution(new anchor.BN(solutionId), Array.from(solutionHash))
-      .accountsPartial({
-        agent: agent.publicKey,
-        attestation: attestationPda,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([agent])
-      .rpc();
-
-    const differentHash = generateSolutionHash();
+    await attest(agent, bountyPda, solutionHash, attestationPda);
 
+    // the address for id 0 no longer matches the reputation's next id
     try {
-      await ctx.program.methods
-        .attestSolution(
-          new anchor.BN(solutionId),
-          Array.from(differentHash)
-        )
-        .accountsPartial({
-          agent: agent.publicKey,
-          attestation: attestationPda,
-          systemProgram: SystemProgram.programId,
-        })
-        .signers([agent])
-        .rpc();
-
-      expect.fail("Should have failed - attestation already exists");
+      await attest(agent, bountyPda, generateSolutionHash(), attestationPda);
+      expect.fail("Should have failed - solution id already used");
     } catch (err) {
-      expect(err).to.exist;
+      expect(err.error.errorCode.code).to.equal("ConstraintSeeds");
+      expect(err.error.origin).to.equal("attestation");
     }
+    expect(await nextSolutionId(ctx, agent.publicKey)).to.equal(1);
   });
 
-  it("Allows different agents to create attestations with different solution_ids", async () => {
-    const agent1 = Keypair.generate();
-    const agent2 = Keypair.generate();
-    const solutionId1 = 100;
-    const solutionId2 = 200;
-
-    await airdropSol(ctx.connection, agent1.publicKey);
-    await airdropSol(ctx.connection, agent2.publicKey);
-
-    const [attestationPda1] = deriveAttestationPda(
+  it("Fails at an address not derived from the next solution id", async () => {
+    const [chosenPda] = deriveAttestationPda(
       ctx.program.programId,
-      solutionId1
-    );
-    const [attestationPda2] = deriveAttestationPda(
-      ctx.program.programId,
-      solutionId2
-    );
-
-    const hash1 = generateSolutionHashWithValue(0x01);
-    const hash2 = generateSolutionHashWithValue(0x02);
-
-    await ctx.program.methods
-      .attestSolution(new anchor.BN(solutionId1), Array.from(hash1))
-      .accountsPartial({
-        agent: agent1.publicKey,
-        attestation: attestationPda1,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([agent1])
-      .rpc();
-
-    await ctx.program.methods
-      .attestSolution(new anchor.BN(solutionId2), Array.from(hash2))
-      .accountsPartial({
-        agent: agent2.publicKey,
-        attestation: attestationPda2,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([agent2])
-      .rpc();
-
-    const attestation1 = await ctx.program.account.attestation.fetch(
-      attestationPda1
-    );
-    const attestation2 = await ctx.program.account.attestation.fetch(
-      attestationPda2
+      agent.publicKey,
+      bountyPda,
+      7
     );
-
-    expect(attestation1.solutionId.toNumber()).to.equal(solutionId1);
-    expect(attestation2.solutionId.toNumber()).to.equal(solutionId2);
-    expect(attestation1.agent.toString()).to.equal(agent1.publicKey.toString());
-    expect(attestation2.agent.toString()).to.equal(agent2.publicKey.toString());
-    expect(Buffer.from(attestation1.solutionHash)).to.deep.equal(hash1);
-    expect(Buffer.from(attestation2.solutionHash)).to.deep.equal(hash2);
+    try {
+      await attest(agent, bountyPda, solutionHash, chosenPda);
+      expect.fail("Should have failed - not the next solution id");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ConstraintSeeds");
+    }
+    expect(await nextSolutionId(ctx, agent.publicKey)).to.equal(0);
   });
 
-  it("Allows same agent to create multiple attestations with different solution_ids", async () => {
-    await airdropSol(ctx.connection, agent.publicKey);
-
-    const solutionId1 = 300;
-    const solutionId2 = 400;
+  it("Counts solution ids up per agent, across bounties", async () => {
+    const otherBountyPda = await postBounty(
+      ctx,
+      "Another bounty to attest against",
+      10 * 10 ** 6
+    );
+    const bounties = [bountyPda, otherBountyPda, bountyPda];
+    const attestations: PublicKey[] = [];
+    for (const [index, bounty] of bounties.entries()) {
+      attestations.push(
+        await createAttestation(
+          ctx,
+          agent,
+          bounty,
+          generateSolutionHashWithValue(index),
+          false
+        )
+      );
+    }
 
-    const [attestationPda1] = deriveAttestationPda(
-      ctx.program.programId,
-      solutionId1
+    for (const [index, attestation] of attestations.entries()) {
+      const account = await ctx.program.account.attestation.fetch(attestation);
+      expect(account.solutionId.toNumber()).to.equal(index);
+    }
+    expect(await nextSolutionId(ctx, agent.publicKey)).to.equal(3);
+
+    // another agent starts from 0 on the same bounty, at its own address
+    const other = Keypair.generate();
+    await airdropSol(ctx.connection, other.publicKey);
+    const otherAttestation = await createAttestation(
+      ctx,
+      other,
+      bountyPda,
+      solutionHash,
+      false
     );
-    const [attestationPda2] = deriveAttestationPda(
-      ctx.program.programId,
-      solutionId2
+    const account = await ctx.program.account.attestation.fetch(
+      otherAttestation
     );
+    expect(account.solutionId.toNumber()).to.equal(0);
+    expect(otherAttestation.toString()).to.not.equal(
+      attestations[0].toString()
+    );
+  });
 
-    const hash1 = generateSolutionHashWithValue(0xaa);
-    const hash2 = generateSolutionHashWithValue(0xbb);
+  describe("trusted attestor", () => {
+    const attestor = Keypair.generate();
 
-    await ctx.program.methods
-      .attestSolution(new anchor.BN(solutionId1), Array.from(hash1))
-      .accountsPartial({
-        agent: agent.publicKey,
-        attestation: attestationPda1,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([agent])
-      .rpc();
+    async function setTrustedAttestor(trustedAttestor: PublicKey) {
+      await updateConfig(ctx, { trustedAttestor });
+    }
 
-    await ctx.program.methods
-      .attestSolution(new anchor.BN(solutionId2), Array.from(hash2))
-      .accountsPartial({
-        agent: agent.publicKey,
-        attestation: attestationPda2,
-        systemProgram: SystemProgram.programId,
-      })
-      .signers([agent])
-      .rpc();
+    async function attestWithSignature(
+      signer: Keypair | null,
+      message: Buffer
+    ): Promise<void> {
+      const preInstructions = signer
+        ? [
+            Ed25519Program.createInstructionWithPrivateKey({
+              privateKey: signer.secretKey,
+              message,
+            }),
+          ]
+        : [];
 
-    const attestation1 = await ctx.program.account.attestation.fetch(
-      attestationPda1
-    );
-    const attestation2 = await ctx.program.account.attestation.fetch(
-      attestationPda2
-    );
+      await ctx.program.methods
+        .attestSolution(Array.from(solutionHash), DEFAULT_HASH_ALGO)
+        .accountsPartial({
+          agent: agent.publicKey,
+          bounty: bountyPda,
+          attestation: attestationPda,
+          instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+          systemProgram: SystemProgram.programId,
+        })
+        .preInstructions(preInstructions)
+        .signers([agent])
+        .rpc();
+    }
 
-    expect(attestation1.agent.toString()).to.equal(agent.publicKey.toString());
-    expect(attestation2.agent.toString()).to.equal(agent.publicKey.toString());
-    expect(attestation1.solutionId.toNumber()).to.equal(solutionId1);
-    expect(attestation2.solutionId.toNumber()).to.equal(solutionId2);
+    before(async () => {
+      await setTrustedAttestor(attestor.publicKey);
+    });
+
+    after(async () => {
+      await setTrustedAttestor(PublicKey.default);
+    });
+
+    it("Records the attestor when its signature covers the attestation", async () => {
+      await attestWithSignature(
+        attestor,
+        attestationMessage(agent.publicKey, solutionId, solutionHash)
+      );
+
+      const attestationAccount = await ctx.program.account.attestation.fetch(
+        attestationPda
+      );
+      expect(attestationAccount.attestor.toString()).to.equal(
+        attestor.publicKey.toString()
+      );
+    });
+
+    it("Fails when the ed25519 instruction is missing", async () => {
+      try {
+        await attestWithSignature(null, Buffer.alloc(0));
+        expect.fail("Should have failed - no attestor signature");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("AttestorSignatureMissing");
+      }
+    });
+
+    it("Fails when a different key signed", async () => {
+      try {
+        await attestWithSignature(
+          Keypair.generate(),
+          attestationMessage(agent.publicKey, solutionId, solutionHash)
+        );
+        expect.fail("Should have failed - signed by an untrusted key");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("AttestorMismatch");
+      }
+    });
+
+    it("Fails when the signature covers different data", async () => {
+      try {
+        await attestWithSignature(
+          attestor,
+          attestationMessage(
+            agent.publicKey,
+            solutionId,
+            generateSolutionHashWithValue(0xee)
+          )
+        );
+        expect.fail("Should have failed - signed a different hash");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("AttestorMessageMismatch");
+      }
+    });
   });
 });
-
@@ -0,0 +1,299 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { ComputeBudgetProgram, Keypair, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveCreatorProfilePda,
+  deriveReputationPda,
+  deriveSettlementReceiptPda,
+  deriveSettlementRecordPda,
+  deriveSubmissionPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  updateConfig,
+  generateSolutionHashWithValue,
+  waitForSlot,
+  TestContext,
+} from "./helpers";
+
+describe("attestor_fee", () => {
+  const feeBps = 250;
+  const attestorFeeBps = 500;
+
+  let ctx: TestContext;
+  let attestor: Keypair;
+  let attestorTokenAccount: anchor.web3.PublicKey;
+  let treasuryTokenAccount: anchor.web3.PublicKey;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    attestor = Keypair.generate();
+    attestorTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        ctx.connection,
+        ctx.creator,
+        ctx.usdcMint,
+        attestor.publicKey
+      )
+    ).address;
+    treasuryTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        ctx.connection,
+        ctx.creator,
+        ctx.usdcMint,
+        ctx.provider.wallet.publicKey
+      )
+    ).address;
+    await updateConfig(ctx, {
+      feeBps,
+      attestorFeeBps,
+      attestors: [attestor.publicKey],
+    });
+  });
+
+  after(async () => {
+    await updateConfig(ctx, { feeBps: 0, attestorFeeBps: 0, attestors: [] });
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+  });
+
+  // posts and submits a solution, countersigned by `countersigner` if given
+  async function submitted(
+    reward: number,
+    countersigner: Keypair | null,
+    settleWindowSlots?: number
+  ): Promise<anchor.web3.PublicKey> {
+    const bountyPda = await postBounty(ctx, "Attested work", reward, {
+      challengePeriodSlots: 0,
+      settleWindowSlots,
+    });
+    const hash = generateSolutionHashWithValue(0x4a);
+    const attestation = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      hash,
+      true,
+      countersigner
+    );
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    return bountyPda;
+  }
+
+  async function settle(
+    bountyPda: anchor.web3.PublicKey,
+    withAttestor: boolean
+  ) {
+    await ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        agent: agent.publicKey,
+        agentTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        treasury: ctx.provider.wallet.publicKey,
+        treasuryTokenAccount,
+        attestor: withAttestor ? attestor.publicKey : null,
+        attestorTokenAccount: withAttestor ? attestorTokenAccount : null,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function balanceOf(account: anchor.web3.PublicKey) {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  it("Splits the escrow between the solver, the attestor and the treasury", async () => {
+    const reward = 10_000_001;
+    const bountyPda = await submitted(reward, attestor);
+
+    const submission = await ctx.program.account.submission.fetch(
+      deriveSubmissionPda(ctx.program.programId, bountyPda, agent.publicKey)[0]
+    );
+    expect(submission.attestor.toString()).to.equal(
+      attestor.publicKey.toString()
+    );
+
+    const attestorBefore = await balanceOf(attestorTokenAccount);
+    const treasuryBefore = await balanceOf(treasuryTokenAccount);
+    await settle(bountyPda, true);
+
+    // 9_250_000.925 and 500_000.05 round down, the remainder goes to the treasury
+    const solverPaid = await balanceOf(agentTokenAccount);
+    const attestorPaid = (await balanceOf(attestorTokenAccount)) - attestorBefore;
+    const treasuryPaid = (await balanceOf(treasuryTokenAccount)) - treasuryBefore;
+    expect(solverPaid).to.equal(9_250_000);
+    expect(attestorPaid).to.equal(500_000);
+    expect(treasuryPaid).to.equal(250_001);
+    expect(solverPaid + attestorPaid + treasuryPaid).to.equal(reward);
+
+    const receipt = await ctx.program.account.settlementReceipt.fetch(
+      deriveSettlementReceiptPda(ctx.program.programId, bountyPda)[0]
+    );
+    expect(receipt.netPaid.toNumber()).to.equal(solverPaid);
+    expect(receipt.attestorFee.toNumber()).to.equal(attestorPaid);
+    expect(receipt.fee.toNumber()).to.equal(treasuryPaid);
+  });
+
+  it("Sends the attestor's share to the treasury when their accounts are left out", async () => {
+    const reward = 10 * 10 ** 6;
+    const bountyPda = await submitted(reward, attestor);
+
+    const attestorBefore = await balanceOf(attestorTokenAccount);
+    const treasuryBefore = await balanceOf(treasuryTokenAccount);
+    await settle(bountyPda, false);
+
+    expect(await balanceOf(agentTokenAccount)).to.equal(9_250_000);
+    expect(await balanceOf(attestorTokenAccount)).to.equal(attestorBefore);
+    expect((await balanceOf(treasuryTokenAccount)) - treasuryBefore).to.equal(
+      750_000
+    );
+  });
+
+  it("Pays a self-attested solver both their share and the attestor's", async () => {
+    const reward = 10 * 10 ** 6;
+    const bountyPda = await submitted(reward, null);
+
+    const submission = await ctx.program.account.submission.fetch(
+      deriveSubmissionPda(ctx.program.programId, bountyPda, agent.publicKey)[0]
+    );
+    expect(submission.attestor.toString()).to.equal(agent.publicKey.toString());
+
+    const treasuryBefore = await balanceOf(treasuryTokenAccount);
+    await settle(bountyPda, false);
+
+    expect(await balanceOf(agentTokenAccount)).to.equal(9_750_000);
+    expect((await balanceOf(treasuryTokenAccount)) - treasuryBefore).to.equal(
+      250_000
+    );
+  });
+
+  it("Pays the attestor their share in a batch settlement", async () => {
+    const reward = 10 * 10 ** 6;
+    const bountyPda = await submitted(reward, attestor);
+    const programId = ctx.program.programId;
+
+    const attestorBefore = await balanceOf(attestorTokenAccount);
+    const treasuryBefore = await balanceOf(treasuryTokenAccount);
+    await ctx.program.methods
+      .settleBounties()
+      .accountsPartial({
+        settler: ctx.creator.publicKey,
+        creatorProfile: deriveCreatorProfilePda(
+          programId,
+          ctx.creator.publicKey
+        )[0],
+        treasury: ctx.provider.wallet.publicKey,
+        treasuryTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(
+        [
+          bountyPda,
+          deriveSubmissionPda(programId, bountyPda, agent.publicKey)[0],
+          deriveReputationPda(programId, agent.publicKey)[0],
+          agent.publicKey,
+          agentTokenAccount,
+          getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda),
+          deriveSettlementRecordPda(programId, bountyPda, agent.publicKey)[0],
+          deriveSettlementReceiptPda(programId, bountyPda)[0],
+          attestorTokenAccount,
+        ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+      )
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }),
+      ])
+      .signers([ctx.creator])
+      .rpc();
+
+    expect(await balanceOf(agentTokenAccount)).to.equal(9_250_000);
+    expect((await balanceOf(attestorTokenAccount)) - attestorBefore).to.equal(
+      500_000
+    );
+    expect((await balanceOf(treasuryTokenAccount)) - treasuryBefore).to.equal(
+      250_000
+    );
+
+    const receipt = await ctx.program.account.settlementReceipt.fetch(
+      deriveSettlementReceiptPda(programId, bountyPda)[0]
+    );
+    expect(receipt.attestorFee.toNumber()).to.equal(500_000);
+  });
+
+  it("Pays the attestor their share when the solver forces settlement", async () => {
+    const reward = 10 * 10 ** 6;
+    const bountyPda = await submitted(reward, attestor, 2);
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    await waitForSlot(
+      ctx.connection,
+      bounty.submittedAtSlot.toNumber() + bounty.settleWindowSlots.toNumber()
+    );
+
+    const attestorBefore = await balanceOf(attestorTokenAccount);
+    const treasuryBefore = await balanceOf(treasuryTokenAccount);
+    await ctx.program.methods
+      .forceSettle()
+      .accountsPartial({
+        agent: agent.publicKey,
+        bounty: bountyPda,
+        creatorBond: null,
+        agentTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        treasury: ctx.provider.wallet.publicKey,
+        treasuryTokenAccount,
+        attestor: null,
+        attestorTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([agent])
+      .rpc();
+
+    expect(await balanceOf(agentTokenAccount)).to.equal(9_250_000);
+    expect((await balanceOf(attestorTokenAccount)) - attestorBefore).to.equal(
+      500_000
+    );
+    expect((await balanceOf(treasuryTokenAccount)) - treasuryBefore).to.equal(
+      250_000
+    );
+  });
+});
@@ -0,0 +1,141 @@
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  DEFAULT_MARKET,
+  setupTestContext,
+  deriveAttestorPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("attestor_track_record", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let arbiter: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    arbiter = Keypair.generate();
+    await airdropSol(ctx.connection, arbiter.publicKey);
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+  });
+
+  function fetchAttestor() {
+    return ctx.program.account.attestor.fetch(
+      deriveAttestorPda(ctx.program.programId, agent.publicKey)[0]
+    );
+  }
+
+  async function attestAndSubmit(bountyPda: PublicKey): Promise<void> {
+    const hash = generateSolutionHashWithValue(0x7a);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+  }
+
+  it("Counts issued, consumed and disputed attestations", async () => {
+    const bountyPda = await postBounty(ctx, "Arbitrated bounty", reward, {
+      arbiter: arbiter.publicKey,
+    });
+    const hash = generateSolutionHashWithValue(0x7b);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+
+    let attestor = await fetchAttestor();
+    expect(attestor.attestationsIssued.toNumber()).to.equal(1);
+    expect(attestor.attestationsConsumed.toNumber()).to.equal(0);
+
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    attestor = await fetchAttestor();
+    expect(attestor.attestationsConsumed.toNumber()).to.equal(1);
+    expect(attestor.attestationsDisputed.toNumber()).to.equal(0);
+
+    await ctx.program.methods
+      .openDispute()
+      .accountsPartial({ party: ctx.creator.publicKey, bounty: bountyPda })
+      .signers([ctx.creator])
+      .rpc();
+    await ctx.program.methods
+      .resolveDispute({ refundCreator: {} })
+      .accountsPartial({
+        arbiter: arbiter.publicKey,
+        bounty: bountyPda,
+        creator: ctx.creator.publicKey,
+        agent: agent.publicKey,
+        agentTokenAccount: await createAgentTokenAccount(
+          ctx.connection,
+          agent,
+          ctx.usdcMint
+        ),
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([arbiter])
+      .rpc();
+
+    attestor = await fetchAttestor();
+    expect(attestor.attestationsIssued.toNumber()).to.equal(1);
+    expect(attestor.attestationsConsumed.toNumber()).to.equal(1);
+    expect(attestor.attestationsDisputed.toNumber()).to.equal(1);
+  });
+
+  it("Keeps the counters when the attestor registers again", async () => {
+    const bountyPda = await postBounty(ctx, "Repeat registration", reward);
+    await attestAndSubmit(bountyPda);
+
+    await ctx.program.methods
+      .registerAttestor(DEFAULT_MARKET, agent.publicKey, "renamed")
+      .accountsPartial({ admin: ctx.provider.wallet.publicKey })
+      .rpc();
+
+    const attestor = await fetchAttestor();
+    expect(attestor.name).to.equal("renamed");
+    expect(attestor.attestationsIssued.toNumber()).to.equal(1);
+    expect(attestor.attestationsConsumed.toNumber()).to.equal(1);
+  });
+
+  it("Blocks a fresh attestor on a gated bounty until it has a record", async () => {
+    const gatedPda = await postBounty(ctx, "High-value audit", reward, {
+      minAttestorTrackRecord: 1,
+    });
+
+    try {
+      await attestAndSubmit(gatedPda);
+      expect.fail("Should have failed - no track record yet");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AttestorTrackRecordTooLow");
+    }
+
+    const openPda = await postBounty(ctx, "Ungated audit", reward);
+    await attestAndSubmit(openPda);
+    await attestAndSubmit(gatedPda);
+
+    const bounty = await ctx.program.account.bounty.fetch(gatedPda);
+    expect(bounty.minAttestorTrackRecord.toNumber()).to.equal(1);
+    expect(bounty.solver.toString()).to.equal(agent.publicKey.toString());
+  });
+});
@@ -0,0 +1,344 @@
+import {
+  ASSOCIATED_TOKEN_PROGRAM_ID,
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccountIdempotent,
+  getAccount,
+  getMint,
+  getTokenMetadata,
+  transferChecked,
+} from "@solana/spl-token";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveBadgeAuthorityPda,
+  deriveBadgeMintPda,
+  deriveCreatorProfilePda,
+  deriveReputationPda,
+  deriveSettlementReceiptPda,
+  deriveSettlementRecordPda,
+  deriveSubmissionPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  generateSolutionHashWithValue,
+  waitForSlot,
+  TestContext,
+} from "./helpers";
+
+describe("badge", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  async function postAndSubmit(
+    mintBadge: boolean,
+    value: number,
+    settleWindowSlots?: number
+  ): Promise<[PublicKey, Keypair, PublicKey]> {
+    const bountyPda = await postBounty(ctx, "Audit with a badge", reward, {
+      challengePeriodSlots: 0,
+      mintBadge,
+      settleWindowSlots,
+    });
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    const hash = generateSolutionHashWithValue(value);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    return [bountyPda, agent, agentTokenAccount];
+  }
+
+  async function settleWithBadge(
+    bountyPda: PublicKey,
+    agent: PublicKey,
+    agentTokenAccount: PublicKey
+  ): Promise<PublicKey> {
+    const [badgeMint] = deriveBadgeMintPda(ctx.program.programId, bountyPda);
+    await ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        reputation: deriveReputationPda(ctx.program.programId, agent)[0],
+        agent,
+        agentTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        badgeMint,
+        badgeAuthority: deriveBadgeAuthorityPda(ctx.program.programId)[0],
+        agentBadgeAccount: getAssociatedTokenAddressSync(
+          badgeMint,
+          agent,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        badgeTokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+    return badgeMint;
+  }
+
+  it("Mints the solver a 1-of-1 badge carrying the bounty's details", async () => {
+    const [bountyPda, agent, agentTokenAccount] = await postAndSubmit(
+      true,
+      0x5b
+    );
+    const badgeMint = await settleWithBadge(
+      bountyPda,
+      agent.publicKey,
+      agentTokenAccount
+    );
+
+    const badgeAccount = await getAccount(
+      ctx.connection,
+      getAssociatedTokenAddressSync(
+        badgeMint,
+        agent.publicKey,
+        TOKEN_2022_PROGRAM_ID
+      ),
+      "confirmed",
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(badgeAccount.amount).to.equal(BigInt(1));
+
+    // nobody can mint a second one
+    const mint = await getMint(
+      ctx.connection,
+      badgeMint,
+      "confirmed",
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(mint.supply).to.equal(BigInt(1));
+    expect(mint.decimals).to.equal(0);
+    expect(mint.mintAuthority).to.be.null;
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    const metadata = await getTokenMetadata(ctx.connection, badgeMint);
+    expect(metadata.name).to.equal(`BountyForge Badge #${bounty.id}`);
+    expect(metadata.symbol).to.equal("BFBADGE");
+    const fields = new Map(metadata.additionalMetadata);
+    expect(fields.get("bounty")).to.equal(bountyPda.toString());
+    expect(fields.get("bounty_id")).to.equal(bounty.id.toString());
+    expect(fields.get("bounty_type")).to.equal("WalletIntelligence");
+    expect(fields.get("reward")).to.equal(reward.toString());
+    expect(fields.get("settled_at")).to.equal(bounty.settledAt.toString());
+  });
+
+  it("Fails to transfer the badge", async () => {
+    const [bountyPda, agent, agentTokenAccount] = await postAndSubmit(
+      true,
+      0x5c
+    );
+    const badgeMint = await settleWithBadge(
+      bountyPda,
+      agent.publicKey,
+      agentTokenAccount
+    );
+
+    const other = Keypair.generate();
+    const otherBadgeAccount = await createAssociatedTokenAccountIdempotent(
+      ctx.connection,
+      agent,
+      badgeMint,
+      other.publicKey,
+      {},
+      TOKEN_2022_PROGRAM_ID
+    );
+    try {
+      await transferChecked(
+        ctx.connection,
+        agent,
+        getAssociatedTokenAddressSync(
+          badgeMint,
+          agent.publicKey,
+          TOKEN_2022_PROGRAM_ID
+        ),
+        badgeMint,
+        otherBadgeAccount,
+        agent,
+        1,
+        0,
+        [],
+        {},
+        TOKEN_2022_PROGRAM_ID
+      );
+      expect.fail("Should have failed - badges are soulbound");
+    } catch (err) {
+      expect(err.logs.join("\n")).to.include("Transfer is disabled for this mint");
+    }
+  });
+
+  it("Fails to settle a badge bounty without the badge accounts", async () => {
+    const [bountyPda, agent, agentTokenAccount] = await postAndSubmit(
+      true,
+      0x5d
+    );
+    try {
+      await ctx.program.methods
+        .settleBounty()
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          reputation: deriveReputationPda(
+            ctx.program.programId,
+            agent.publicKey
+          )[0],
+          agent: agent.publicKey,
+          agentTokenAccount,
+          bountyTokenAccount: getAssociatedTokenAddressSync(
+            ctx.usdcMint,
+            bountyPda
+          ),
+          rewardMint: ctx.usdcMint,
+          agentBadgeAccount: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+      expect.fail("Should have failed - badge accounts missing");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MissingBadgeAccounts");
+    }
+
+    // nothing was paid out
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ submitted: {} });
+  });
+
+  it("Settles a bounty without the flag and mints nothing", async () => {
+    const [bountyPda, agent, agentTokenAccount] = await postAndSubmit(
+      false,
+      0x5e
+    );
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ settled: {} });
+    const [badgeMint] = deriveBadgeMintPda(ctx.program.programId, bountyPda);
+    expect(await ctx.connection.getAccountInfo(badgeMint)).to.be.null;
+  });
+
+  it("Mints the badge when the solver forces settlement", async () => {
+    const [bountyPda, agent, agentTokenAccount] = await postAndSubmit(
+      true,
+      0x5f,
+      2
+    );
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    await waitForSlot(
+      ctx.connection,
+      bounty.submittedAtSlot.toNumber() + bounty.settleWindowSlots.toNumber()
+    );
+
+    const [badgeMint] = deriveBadgeMintPda(ctx.program.programId, bountyPda);
+    const agentBadgeAccount = getAssociatedTokenAddressSync(
+      badgeMint,
+      agent.publicKey,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await ctx.program.methods
+      .forceSettle()
+      .accountsPartial({
+        agent: agent.publicKey,
+        bounty: bountyPda,
+        creatorBond: null,
+        agentTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        badgeMint,
+        badgeAuthority: deriveBadgeAuthorityPda(ctx.program.programId)[0],
+        agentBadgeAccount,
+        badgeTokenProgram: TOKEN_2022_PROGRAM_ID,
+        associatedTokenProgram: ASSOCIATED_TOKEN_PROGRAM_ID,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+
+    const badgeAccount = await getAccount(
+      ctx.connection,
+      agentBadgeAccount,
+      "confirmed",
+      TOKEN_2022_PROGRAM_ID
+    );
+    expect(badgeAccount.amount).to.equal(BigInt(1));
+  });
+
+  it("Refuses a badge bounty in a batch settlement", async () => {
+    const [bountyPda, agent, agentTokenAccount] = await postAndSubmit(
+      true,
+      0x60
+    );
+    const programId = ctx.program.programId;
+    try {
+      await ctx.program.methods
+        .settleBounties()
+        .accountsPartial({
+          settler: ctx.creator.publicKey,
+          creatorProfile: deriveCreatorProfilePda(
+            programId,
+            ctx.creator.publicKey
+          )[0],
+          treasury: null,
+          treasuryTokenAccount: null,
+          rewardMint: ctx.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts(
+          [
+            bountyPda,
+            deriveSubmissionPda(programId, bountyPda, agent.publicKey)[0],
+            deriveReputationPda(programId, agent.publicKey)[0],
+            agent.publicKey,
+            agentTokenAccount,
+            getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda),
+            deriveSettlementRecordPda(programId, bountyPda, agent.publicKey)[0],
+            deriveSettlementReceiptPda(programId, bountyPda)[0],
+            programId,
+          ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+        )
+        .signers([ctx.creator])
+        .rpc();
+      expect.fail("Should have failed - badges need settle_bounty");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MissingBadgeAccounts");
+    }
+  });
+});
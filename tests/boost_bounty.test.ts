@@ -0,0 +1,142 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  ensureCreatorBalance,
+  postBounty,
+  updateConfig,
+  getClockUnixTimestamp,
+  TestContext,
+} from "./helpers";
+
+describe("boost_bounty", () => {
+  const reward = 10 * 10 ** 6;
+  const boostFeeBps = 100;
+  const boostDurationSeconds = 60 * 60;
+  const fee = (reward * boostFeeBps) / 10_000;
+
+  let ctx: TestContext;
+  let treasuryTokenAccount: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    treasuryTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        ctx.connection,
+        ctx.creator,
+        ctx.usdcMint,
+        ctx.provider.wallet.publicKey
+      )
+    ).address;
+    await updateConfig(ctx, {
+      boostFeeBps,
+      boostDurationSeconds: new anchor.BN(boostDurationSeconds),
+    });
+  });
+
+  after(async () => {
+    await updateConfig(ctx, {
+      boostFeeBps: 0,
+      boostDurationSeconds: new anchor.BN(7 * 24 * 60 * 60),
+    });
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  async function boost(bountyPda: anchor.web3.PublicKey) {
+    return ctx.program.methods
+      .boostBounty()
+      .accountsPartial({
+        booster: ctx.creator.publicKey,
+        bounty: bountyPda,
+        treasury: ctx.provider.wallet.publicKey,
+        boosterTokenAccount: ctx.creatorTokenAccount,
+        treasuryTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function balanceOf(account: anchor.web3.PublicKey) {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  // the offset published in the IDL from constants.rs
+  function boostedUntilOffset(): number {
+    const constant = ctx.program.idl.constants.find(
+      (c) => c.name.toLowerCase().replace(/_/g, "") === "bountyboosteduntiloffset"
+    );
+    return Number(constant.value);
+  }
+
+  it("Features a bounty for the boost duration and pays the treasury", async () => {
+    const bountyPda = await postBounty(ctx, "Featured audit", reward);
+    const treasuryBefore = await balanceOf(treasuryTokenAccount);
+    const before = await getClockUnixTimestamp(ctx.connection);
+
+    await boost(bountyPda);
+
+    expect((await balanceOf(treasuryTokenAccount)) - treasuryBefore).to.equal(
+      fee
+    );
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.boostedUntil.toNumber()).to.be.at.least(
+      before + boostDurationSeconds
+    );
+    expect(bounty.totalBoostPaid.toNumber()).to.equal(fee);
+    // boosting never touches the escrow or the reward
+    expect(bounty.reward.toNumber()).to.equal(reward);
+    expect(bounty.escrowAmount.toNumber()).to.equal(reward);
+
+    // readable without decoding, so indexers can filter featured bounties
+    const info = await ctx.connection.getAccountInfo(bountyPda);
+    expect(
+      Number(info.data.readBigInt64LE(boostedUntilOffset()))
+    ).to.equal(bounty.boostedUntil.toNumber());
+  });
+
+  it("Extends the window when boosted again", async () => {
+    const bountyPda = await postBounty(ctx, "Twice-featured audit", reward);
+    await boost(bountyPda);
+    const first = await ctx.program.account.bounty.fetch(bountyPda);
+
+    await boost(bountyPda);
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.boostedUntil.toNumber()).to.equal(
+      first.boostedUntil.toNumber() + boostDurationSeconds
+    );
+    expect(bounty.totalBoostPaid.toNumber()).to.equal(2 * fee);
+  });
+
+  it("Fails when boosting is disabled", async () => {
+    const bountyPda = await postBounty(ctx, "Unfeatured audit", reward);
+    await updateConfig(ctx, { boostDurationSeconds: new anchor.BN(0) });
+
+    try {
+      await boost(bountyPda);
+      expect.fail("Should have failed - boosting disabled");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BoostingDisabled");
+    } finally {
+      await updateConfig(ctx, {
+        boostDurationSeconds: new anchor.BN(boostDurationSeconds),
+      });
+    }
+  });
+});
@@ -0,0 +1,102 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  postBounty,
+  TestContext,
+} from "./helpers";
+
+describe("bounty_layout", () => {
+  const reward = 3 * 10 ** 6;
+
+  let ctx: TestContext;
+  let otherCtx: TestContext;
+  let openPdas: anchor.web3.PublicKey[];
+  let cancelledPda: anchor.web3.PublicKey;
+  let otherCreatorPda: anchor.web3.PublicKey;
+
+  // the offsets published in the IDL from constants.rs
+  function offset(name: string): number {
+    const constant = ctx.program.idl.constants.find(
+      (c) => c.name.toLowerCase().replace(/_/g, "") === name.toLowerCase()
+    );
+    return Number(constant.value);
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+    otherCtx = await setupTestContext();
+
+    openPdas = [
+      await postBounty(ctx, "Open one", reward),
+      await postBounty(ctx, "Open two with a longer description", reward, {
+        bountyType: { custom: { 0: 7 } },
+      }),
+    ];
+    cancelledPda = await postBounty(ctx, "Withdrawn", reward);
+    await ctx.program.methods
+      .cancelBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: cancelledPda,
+        unwrapAccount: null,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          cancelledPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+    otherCreatorPda = await postBounty(otherCtx, "Someone else's", reward);
+  });
+
+  it("Keeps the fixed-size fields at the published offsets", async () => {
+    const info = await ctx.connection.getAccountInfo(openPdas[1]);
+    const bounty = await ctx.program.account.bounty.fetch(openPdas[1]);
+
+    expect(info.data[offset("BountyVersionOffset")]).to.equal(
+      bounty.version
+    );
+    expect(bounty.version).to.equal(1);
+    expect(
+      info.data.readBigUInt64LE(offset("BountyIdOffset")).toString()
+    ).to.equal(bounty.id.toString());
+    expect(info.data[offset("BountyStatusOffset")]).to.equal(0);
+    expect(
+      new anchor.web3.PublicKey(
+        info.data.subarray(
+          offset("BountyCreatorOffset"),
+          offset("BountyCreatorOffset") + 32
+        )
+      ).toString()
+    ).to.equal(ctx.creator.publicKey.toString());
+    expect(
+      info.data.readBigUInt64LE(offset("BountyRewardOffset")).toString()
+    ).to.equal(reward.toString());
+    // Custom is the fifth variant
+    expect(info.data[offset("BountyTypeOffset")]).to.equal(4);
+  });
+
+  it("Selects only a creator's open bounties with memcmp filters", async () => {
+    const open = anchor.utils.bytes.bs58.encode(Buffer.from([0]));
+    const matches = await ctx.program.account.bounty.all([
+      { memcmp: { offset: offset("BountyStatusOffset"), bytes: open } },
+      {
+        memcmp: {
+          offset: offset("BountyCreatorOffset"),
+          bytes: ctx.creator.publicKey.toBase58(),
+        },
+      },
+    ]);
+
+    const found = matches.map((m) => m.publicKey.toString()).sort();
+    expect(found).to.deep.equal(openPdas.map((pda) => pda.toString()).sort());
+    expect(found).to.not.include(cancelledPda.toString());
+    expect(found).to.not.include(otherCreatorPda.toString());
+  });
+});
@@ -0,0 +1,111 @@
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveStatsPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("bounty_stats", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+  let statsPda: PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    statsPda = deriveStatsPda(ctx.program.programId)[0];
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  // every test file shares the one stats account, so compare against a snapshot
+  async function snapshot() {
+    const stats = await ctx.program.account.bountyStats.fetch(statsPda);
+    return {
+      posted: stats.bountiesPosted.toNumber(),
+      submitted: stats.bountiesSubmitted.toNumber(),
+      settled: stats.bountiesSettled.toNumber(),
+      cancelled: stats.bountiesCancelled.toNumber(),
+      escrowed: BigInt(stats.totalEscrowed.toString()),
+      paid: BigInt(stats.totalPaid.toString()),
+    };
+  }
+
+  it("Counts every step of a settled and a cancelled bounty", async () => {
+    const before = await snapshot();
+
+    // one bounty posted, solved and settled
+    const settledPda = await postBounty(ctx, "Counted audit", reward, {
+      challengePeriodSlots: 0,
+    });
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    const hash = generateSolutionHashWithValue(0x5a);
+    const attestation = await createAttestation(ctx, agent, settledPda, hash);
+    await submitSolution(ctx, agent, settledPda, attestation, hash);
+    await settleBounty(ctx, agent.publicKey, settledPda, agentTokenAccount);
+
+    // and one posted then cancelled untouched
+    const cancelledPda = await postBounty(ctx, "Counted cancel", reward);
+    await ctx.program.methods
+      .cancelBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: cancelledPda,
+        unwrapAccount: null,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          cancelledPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+
+    const after = await snapshot();
+    expect(after.posted - before.posted).to.equal(2);
+    expect(after.submitted - before.submitted).to.equal(1);
+    expect(after.settled - before.settled).to.equal(1);
+    expect(after.cancelled - before.cancelled).to.equal(1);
+    expect(after.escrowed - before.escrowed).to.equal(BigInt(2 * reward));
+    expect(after.paid - before.paid).to.equal(BigInt(reward));
+  });
+
+  it("Fails to initialize the stats twice", async () => {
+    try {
+      await ctx.program.methods
+        .initializeStats()
+        .accountsPartial({ payer: ctx.provider.wallet.publicKey })
+        .rpc();
+      expect.fail("Should have failed - stats already exist");
+    } catch (err) {
+      expect(err.logs.join("\n")).to.include("already in use");
+    }
+  });
+});
@@ -0,0 +1,106 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  ensureCreatorBalance,
+  postBounty,
+  TestContext,
+} from "./helpers";
+
+describe("bounty types", () => {
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  // the offset published in the IDL from constants.rs
+  function targetOffset(): number {
+    const constant = ctx.program.idl.constants.find(
+      (c) => c.name.toLowerCase().replace(/_/g, "") === "bountytargetoffset"
+    );
+    return Number(constant.value);
+  }
+
+  it("Posts and reads back every bounty type, including custom codes", async () => {
+    const bountyTypes = [
+      { walletIntelligence: {} },
+      { tokenScreening: {} },
+      { smartContractAudit: {} },
+      { dataLabeling: {} },
+      { custom: { 0: 4_242 } },
+    ];
+
+    for (const bountyType of bountyTypes) {
+      const bountyPda = await postBounty(ctx, "Typed bounty", 1 * 10 ** 6, {
+        bountyType,
+      });
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bountyAccount.bountyType).to.deep.equal(bountyType);
+    }
+  });
+
+  it("Round-trips the wallet a WalletIntelligence bounty is about", async () => {
+    const wallet = Keypair.generate().publicKey;
+    const bountyPda = await postBounty(ctx, "Who funds this wallet", 10 ** 6, {
+      bountyType: { walletIntelligence: {} },
+      target: wallet,
+    });
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.target.toBase58()).to.equal(wallet.toBase58());
+
+    // indexers can group bounties by subject without decoding them
+    const matches = await ctx.program.account.bounty.all([
+      { memcmp: { offset: targetOffset(), bytes: wallet.toBase58() } },
+    ]);
+    expect(matches.map((m) => m.publicKey.toBase58())).to.deep.equal([
+      bountyPda.toBase58(),
+    ]);
+  });
+
+  it("Fails to post a WalletIntelligence bounty without a wallet", async () => {
+    try {
+      await postBounty(ctx, "Whose wallet?", 10 ** 6, {
+        bountyType: { walletIntelligence: {} },
+        target: PublicKey.default,
+      });
+      expect.fail("Should have failed - no target wallet");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidBountyTarget");
+    }
+  });
+
+  it("Posts a TokenScreening bounty about a mint", async () => {
+    const bountyPda = await postBounty(ctx, "Screen this token", 10 ** 6, {
+      bountyType: { tokenScreening: {} },
+      target: ctx.usdcMint,
+    });
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.target.toBase58()).to.equal(ctx.usdcMint.toBase58());
+  });
+
+  it("Fails to post a TokenScreening bounty whose target isn't a mint", async () => {
+    try {
+      await postBounty(ctx, "Screen this token", 10 ** 6, {
+        bountyType: { tokenScreening: {} },
+        // a token account, owned by the token program but not a mint
+        target: ctx.creatorTokenAccount,
+      });
+      expect.fail("Should have failed - target is not a mint");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidBountyTarget");
+    }
+  });
+});
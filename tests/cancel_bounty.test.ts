@@ -0,0 +1,196 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID, mintTo } from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("cancel_bounty", () => {
+  let ctx: TestContext;
+  let bountyPda: anchor.web3.PublicKey;
+  let bountyTokenAccount: anchor.web3.PublicKey;
+  const reward = 40 * 10 ** 6;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+
+    bountyPda = await postBounty(ctx, "Typo'd description", reward);
+    bountyTokenAccount = getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda);
+  });
+
+  async function cancel(signer: Keypair = ctx.creator): Promise<void> {
+    await ctx.program.methods
+      .cancelBounty()
+      .accountsPartial({
+        creator: signer.publicKey,
+        bounty: bountyPda,
+        unwrapAccount: null,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  it("Refunds the escrow and marks the bounty cancelled", async () => {
+    const creatorBalanceBefore = await ctx.connection.getTokenAccountBalance(
+      ctx.creatorTokenAccount
+    );
+
+    await cancel();
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ cancelled: {} });
+
+    const creatorBalanceAfter = await ctx.connection.getTokenAccountBalance(
+      ctx.creatorTokenAccount
+    );
+    const bountyBalance = await ctx.connection.getTokenAccountBalance(
+      bountyTokenAccount
+    );
+    expect(bountyBalance.value.amount).to.equal("0");
+    expect(creatorBalanceAfter.value.amount).to.equal(
+      (Number(creatorBalanceBefore.value.amount) + reward).toString()
+    );
+  });
+
+  it("Sweeps the full escrow balance when it exceeds the reward", async () => {
+    const extra = 7 * 10 ** 6;
+    await mintTo(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      bountyTokenAccount,
+      ctx.creator,
+      extra
+    );
+
+    const creatorBalanceBefore = await ctx.connection.getTokenAccountBalance(
+      ctx.creatorTokenAccount
+    );
+
+    await cancel();
+
+    const creatorBalanceAfter = await ctx.connection.getTokenAccountBalance(
+      ctx.creatorTokenAccount
+    );
+    const bountyBalance = await ctx.connection.getTokenAccountBalance(
+      bountyTokenAccount
+    );
+    expect(bountyBalance.value.amount).to.equal("0");
+    expect(creatorBalanceAfter.value.amount).to.equal(
+      (Number(creatorBalanceBefore.value.amount) + reward + extra).toString()
+    );
+  });
+
+  it("Fails when an agent has already submitted", async () => {
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+
+    const solutionHash = generateSolutionHashWithValue(0x5c);
+    const attestationPda = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      solutionHash
+    );
+    await submitSolution(ctx, agent, bountyPda, attestationPda, solutionHash);
+
+    try {
+      await cancel();
+      expect.fail("Should have failed - solution already submitted");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("CannotCancelSubmitted");
+    }
+  });
+
+  it("Fails when called by someone other than the creator", async () => {
+    const stranger = Keypair.generate();
+    await airdropSol(ctx.connection, stranger.publicKey);
+
+    try {
+      await cancel(stranger);
+      expect.fail("Should have failed - unauthorized cancellation");
+    } catch (err) {
+      expect(err).to.exist;
+    }
+  });
+
+  it("Fails when the bounty is already cancelled", async () => {
+    await cancel();
+
+    try {
+      await cancel();
+      expect.fail("Should have failed - bounty not open");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotOpen");
+    }
+  });
+
+  it("Refunds lamports to the creator for a SOL bounty", async () => {
+    const solReward = 0.5 * anchor.web3.LAMPORTS_PER_SOL;
+    const solBountyPda = await postBounty(
+      ctx,
+      "SOL bounty to cancel",
+      solReward,
+      { rewardCurrency: { sol: {} } }
+    );
+
+    const creatorLamportsBefore = await ctx.connection.getBalance(
+      ctx.creator.publicKey
+    );
+
+    await ctx.program.methods
+      .cancelBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: solBountyPda,
+        unwrapAccount: null,
+        creatorTokenAccount: null,
+        bountyTokenAccount: null,
+        rewardMint: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(solBountyPda);
+    expect(bountyAccount.status).to.deep.equal({ cancelled: {} });
+
+    const bountyInfo = await ctx.connection.getAccountInfo(solBountyPda);
+    const rentFloor =
+      await ctx.connection.getMinimumBalanceForRentExemption(
+        bountyInfo.data.length
+      );
+    expect(bountyInfo.lamports).to.equal(rentFloor);
+
+    const creatorLamportsAfter = await ctx.connection.getBalance(
+      ctx.creator.publicKey
+    );
+    expect(creatorLamportsAfter - creatorLamportsBefore).to.be.closeTo(
+      solReward,
+      10_000
+    );
+  });
+});
@@ -0,0 +1,272 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  updateConfig,
+  waitForSlot,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("challenge_solution", () => {
+  const bond = LAMPORTS_PER_SOL / 10;
+  const reward = 10 * 10 ** 6;
+  const challengeRewardBps = 1_000;
+  const challengeForfeitBps = 2_000;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let challenger: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let challengerTokenAccount: anchor.web3.PublicKey;
+  let bountyPda: anchor.web3.PublicKey;
+  let challengePda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await updateConfig(ctx, {
+      challengeBondLamports: new anchor.BN(bond),
+      challengeRewardBps,
+      challengeForfeitBps,
+    });
+  });
+
+  after(async () => {
+    await updateConfig(ctx, {
+      challengeBondLamports: new anchor.BN(0),
+      challengeRewardBps: 0,
+      challengeForfeitBps: 0,
+    });
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+
+    agent = Keypair.generate();
+    challenger = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    await airdropSol(ctx.connection, challenger.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    challengerTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      challenger,
+      ctx.usdcMint
+    );
+
+    bountyPda = await postBounty(
+      ctx,
+      "Crowdfunded bounty",
+      reward,
+      { challengePeriodSlots: 30 }
+    );
+    const hash = generateSolutionHashWithValue(0xd0);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+
+    [challengePda] = anchor.web3.PublicKey.findProgramAddressSync(
+      [Buffer.from("challenge"), bountyPda.toBuffer()],
+      ctx.program.programId
+    );
+  });
+
+  async function challenge(by: Keypair = challenger): Promise<void> {
+    await ctx.program.methods
+      .challengeSolution()
+      .accountsPartial({ challenger: by.publicKey, bounty: bountyPda })
+      .signers([by])
+      .rpc();
+  }
+
+  async function resolve(uphold: boolean): Promise<void> {
+    await ctx.program.methods
+      .resolveChallenge(uphold)
+      .accountsPartial({
+        admin: ctx.provider.wallet.publicKey,
+        bounty: bountyPda,
+        challenger: challenger.publicKey,
+        creator: ctx.creator.publicKey,
+        agent: agent.publicKey,
+        challengerTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  }
+
+  function cancel() {
+    return ctx.program.methods
+      .cancelBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        unwrapAccount: null,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function tokenBalance(account: anchor.web3.PublicKey): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  it("Escrows the bond and blocks settlement", async () => {
+    const rent = await ctx.connection.getMinimumBalanceForRentExemption(
+      ctx.program.account.challenge.size
+    );
+    await challenge();
+
+    const challengeAccount = await ctx.program.account.challenge.fetch(
+      challengePda
+    );
+    expect(challengeAccount.challenger.toString()).to.equal(
+      challenger.publicKey.toString()
+    );
+    expect(challengeAccount.bond.toNumber()).to.equal(bond);
+    expect(await ctx.connection.getBalance(challengePda)).to.equal(
+      rent + bond
+    );
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ challenged: {} });
+
+    try {
+      await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+      expect.fail("Should have failed - bounty challenged");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotSubmitted");
+    }
+  });
+
+  it("Fails to challenge the same bounty twice", async () => {
+    await challenge();
+
+    const second = Keypair.generate();
+    await airdropSol(ctx.connection, second.publicKey);
+    try {
+      await challenge(second);
+      expect.fail("Should have failed - already challenged");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyAlreadyChallenged");
+    }
+  });
+
+  it("Forfeits part of the bond to the solver and allows settlement when the challenge is dismissed", async () => {
+    await challenge();
+    const held = await ctx.connection.getBalance(challengePda);
+    const challengerBefore = await ctx.connection.getBalance(
+      challenger.publicKey
+    );
+    const agentBefore = await ctx.connection.getBalance(agent.publicKey);
+
+    await resolve(false);
+
+    const forfeit = (bond * challengeForfeitBps) / 10_000;
+    expect(await ctx.connection.getAccountInfo(challengePda)).to.be.null;
+    expect(await ctx.connection.getBalance(challenger.publicKey)).to.equal(
+      challengerBefore + held - forfeit
+    );
+    expect(await ctx.connection.getBalance(agent.publicKey)).to.equal(
+      agentBefore + forfeit
+    );
+    expect(await tokenBalance(challengerTokenAccount)).to.equal(0);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+
+    await waitForSlot(
+      ctx.connection,
+      bountyAccount.submittedAtSlot.toNumber() +
+        bountyAccount.challengePeriodSlots.toNumber()
+    );
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+    expect(await tokenBalance(agentTokenAccount)).to.equal(reward);
+  });
+
+  it("Reopens the bounty and pays the challenger when the challenge is upheld", async () => {
+    await challenge();
+    const held = await ctx.connection.getBalance(challengePda);
+    const challengerBefore = await ctx.connection.getBalance(
+      challenger.publicKey
+    );
+
+    await resolve(true);
+
+    const slice = (reward * challengeRewardBps) / 10_000;
+    expect(await ctx.connection.getBalance(challenger.publicKey)).to.equal(
+      challengerBefore + held
+    );
+    expect(await tokenBalance(challengerTokenAccount)).to.equal(slice);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ open: {} });
+    expect(bountyAccount.solver).to.be.null;
+    expect(bountyAccount.escrowAmount.toNumber()).to.equal(reward - slice);
+    expect(
+      await tokenBalance(getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda))
+    ).to.equal(reward - slice);
+  });
+
+  it("Refuses to cancel a challenged bounty until the challenge is upheld", async () => {
+    await challenge();
+    try {
+      await cancel();
+      expect.fail("Should have failed - solution still under challenge");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("CannotCancelSubmitted");
+    }
+
+    // upholding clears the solution, after which the creator may walk away
+    await resolve(true);
+    await cancel();
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ cancelled: {} });
+  });
+
+  it("Fails to challenge once the period is over", async () => {
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    await waitForSlot(
+      ctx.connection,
+      bountyAccount.submittedAtSlot.toNumber() +
+        bountyAccount.challengePeriodSlots.toNumber()
+    );
+
+    try {
+      await challenge();
+      expect.fail("Should have failed - challenge window closed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ChallengeWindowClosed");
+    }
+  });
+});
@@ -0,0 +1,159 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  deriveReputationPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("claim_reward", () => {
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let bountyPda: anchor.web3.PublicKey;
+  let bountyTokenAccount: anchor.web3.PublicKey;
+  const reward = 60 * 10 ** 6;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+
+    bountyPda = await postBounty(ctx, "Pull payout", reward);
+    bountyTokenAccount = getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda);
+
+    const solutionHash = generateSolutionHashWithValue(0x7a);
+    const attestationPda = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      solutionHash
+    );
+    await submitSolution(ctx, agent, bountyPda, attestationPda, solutionHash);
+  });
+
+  async function approve(): Promise<void> {
+    await ctx.program.methods
+      .approveSettlement()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function claim(claimer: Keypair): Promise<void> {
+    await ctx.program.methods
+      .claimReward()
+      .accountsPartial({
+        agent: claimer.publicKey,
+        bounty: bountyPda,
+        agentTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          claimer.publicKey
+        ),
+        bountyTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([claimer])
+      .rpc();
+  }
+
+  it("Approves then lets the solver claim into their own ATA", async () => {
+    await approve();
+
+    let bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ approved: {} });
+    expect(bountyAccount.solver.toString()).to.equal(agent.publicKey.toString());
+
+    await claim(agent);
+
+    bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ settled: {} });
+
+    const agentBalance = await ctx.connection.getTokenAccountBalance(
+      agentTokenAccount
+    );
+    const bountyBalance = await ctx.connection.getTokenAccountBalance(
+      bountyTokenAccount
+    );
+    expect(agentBalance.value.amount).to.equal(reward.toString());
+    expect(bountyBalance.value.amount).to.equal("0");
+
+    const [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    const reputation = await ctx.program.account.reputation.fetch(reputationPda);
+    expect(reputation.successfulBounties.toNumber()).to.equal(1);
+    expect(reputation.totalEarned.toNumber()).to.equal(reward);
+  });
+
+  it("Fails when someone other than the solver claims", async () => {
+    await approve();
+
+    const impostor = Keypair.generate();
+    await airdropSol(ctx.connection, impostor.publicKey);
+    await createAgentTokenAccount(ctx.connection, impostor, ctx.usdcMint);
+
+    try {
+      await claim(impostor);
+      expect.fail("Should have failed - unauthorized claim");
+    } catch (err) {
+      expect(err).to.exist;
+    }
+  });
+
+  it("Fails when the settlement has not been approved", async () => {
+    try {
+      await claim(agent);
+      expect.fail("Should have failed - not approved");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotApproved");
+    }
+  });
+
+  it("Fails when a non-creator approves", async () => {
+    const stranger = Keypair.generate();
+    await airdropSol(ctx.connection, stranger.publicKey);
+
+    try {
+      await ctx.program.methods
+        .approveSettlement()
+        .accountsPartial({ creator: stranger.publicKey, bounty: bountyPda })
+        .signers([stranger])
+        .rpc();
+      expect.fail("Should have failed - unauthorized approval");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedSettlement");
+    }
+  });
+});
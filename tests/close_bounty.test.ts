@@ -0,0 +1,115 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID, transfer } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  ensureCreatorBalance,
+  postBounty,
+  TestContext,
+} from "./helpers";
+
+describe("close_bounty", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+  let bountyPda: anchor.web3.PublicKey;
+  let bountyTokenAccount: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    bountyPda = await postBounty(ctx, "Short-lived", reward);
+    bountyTokenAccount = getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda);
+  });
+
+  async function cancel() {
+    await ctx.program.methods
+      .cancelBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        unwrapAccount: null,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function closeBounty() {
+    return ctx.program.methods
+      .closeBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        creatorBond: null,
+        bountyTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  it("Returns the bounty and escrow ATA rent to the creator", async () => {
+    await cancel();
+
+    const rent =
+      (await ctx.connection.getBalance(bountyPda)) +
+      (await ctx.connection.getBalance(bountyTokenAccount));
+    const creatorBefore = await ctx.connection.getBalance(
+      ctx.creator.publicKey
+    );
+
+    const signature = await closeBounty();
+    const tx = await ctx.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    expect(await ctx.connection.getAccountInfo(bountyPda)).to.be.null;
+    expect(await ctx.connection.getAccountInfo(bountyTokenAccount)).to.be.null;
+    expect(await ctx.connection.getBalance(ctx.creator.publicKey)).to.equal(
+      creatorBefore + rent - tx.meta.fee
+    );
+  });
+
+  it("Fails to close an open bounty", async () => {
+    try {
+      await closeBounty();
+      expect.fail("Should have failed - bounty still open");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotFinished");
+    }
+  });
+
+  it("Fails to close while the escrow ATA holds tokens", async () => {
+    await cancel();
+    await transfer(
+      ctx.connection,
+      ctx.creator,
+      ctx.creatorTokenAccount,
+      bountyTokenAccount,
+      ctx.creator,
+      1
+    );
+
+    try {
+      await closeBounty();
+      expect.fail("Should have failed - escrow not empty");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("EscrowNotEmpty");
+    }
+  });
+});
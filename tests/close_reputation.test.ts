@@ -0,0 +1,136 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  deriveSubmissionPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  rejectSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("close_reputation", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let reputationPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+  });
+
+  async function postAndSubmit(): Promise<anchor.web3.PublicKey> {
+    const bountyPda = await postBounty(ctx, "Rep", reward);
+    const hash = generateSolutionHashWithValue(0x2c);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    return bountyPda;
+  }
+
+  async function closeSubmission(bountyPda: anchor.web3.PublicKey) {
+    await ctx.program.methods
+      .closeSubmission()
+      .accountsPartial({
+        agent: agent.publicKey,
+        bounty: bountyPda,
+        submission: deriveSubmissionPda(
+          ctx.program.programId,
+          bountyPda,
+          agent.publicKey
+        )[0],
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  async function closeReputation() {
+    return ctx.program.methods
+      .closeReputation()
+      .accountsPartial({ agent: agent.publicKey })
+      .signers([agent])
+      .rpc();
+  }
+
+  it("Returns the rent once every submission is closed", async () => {
+    const bountyPda = await postAndSubmit();
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+
+    try {
+      await closeReputation();
+      expect.fail("Should have failed - submission still open");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ReputationInUse");
+    }
+
+    await closeSubmission(bountyPda);
+    const rent = await ctx.connection.getBalance(reputationPda);
+    const agentBefore = await ctx.connection.getBalance(agent.publicKey);
+
+    const signature = await closeReputation();
+    const tx = await ctx.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    expect(await ctx.connection.getAccountInfo(reputationPda)).to.be.null;
+    expect(await ctx.connection.getBalance(agent.publicKey)).to.equal(
+      agentBefore + rent - tx.meta.fee
+    );
+
+    // a rebuilt reputation starts from scratch, with nothing to hide
+    await postAndSubmit();
+    const rebuilt = await ctx.program.account.reputation.fetch(reputationPda);
+    expect(rebuilt.successfulBounties.toNumber()).to.equal(0);
+    expect(rebuilt.failedBounties.toNumber()).to.equal(0);
+    expect(rebuilt.openSubmissions).to.equal(1);
+  });
+
+  it("Refuses to close a record with failures, so it cannot be laundered", async () => {
+    const bountyPda = await postAndSubmit();
+    await rejectSolution(ctx, agent.publicKey, bountyPda);
+    await closeSubmission(bountyPda);
+
+    try {
+      await closeReputation();
+      expect.fail("Should have failed - record has failures");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ReputationHasFailures");
+    }
+
+    const reputation = await ctx.program.account.reputation.fetch(
+      reputationPda
+    );
+    expect(reputation.failedBounties.toNumber()).to.equal(1);
+  });
+});
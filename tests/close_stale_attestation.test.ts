@@ -0,0 +1,134 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  updateConfig,
+  waitForClock,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("close_stale_attestation", () => {
+  const reward = 5 * 10 ** 6;
+  const ttlSeconds = 3;
+  const tipLamports = 1_000;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let cranker: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await updateConfig(ctx, {
+      attestationTtlSeconds: new anchor.BN(ttlSeconds),
+      attestationCloseTipLamports: new anchor.BN(tipLamports),
+    });
+  });
+
+  after(async () => {
+    await updateConfig(ctx, {
+      attestationTtlSeconds: new anchor.BN(7 * 24 * 60 * 60),
+      attestationCloseTipLamports: new anchor.BN(0),
+    });
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    cranker = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    await airdropSol(ctx.connection, cranker.publicKey);
+  });
+
+  function closeStale(attestation: PublicKey, bountyPda: PublicKey) {
+    return ctx.program.methods
+      .closeStaleAttestation()
+      .accountsPartial({
+        cranker: cranker.publicKey,
+        attestation,
+        agent: agent.publicKey,
+        bounty: bountyPda,
+      })
+      .signers([cranker])
+      .rpc();
+  }
+
+  async function waitPastTtl(attestation: PublicKey) {
+    const { timestamp } = await ctx.program.account.attestation.fetch(
+      attestation
+    );
+    await waitForClock(ctx.connection, timestamp.toNumber() + ttlSeconds);
+  }
+
+  it("Refuses to close an attestation younger than the TTL", async () => {
+    const bountyPda = await postBounty(ctx, "Bridge exploit trace", reward);
+    const attestation = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      generateSolutionHashWithValue(0x41),
+      false
+    );
+
+    try {
+      await closeStale(attestation, bountyPda);
+      expect.fail("Should have failed - attestation is fresh");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AttestationNotStale");
+    }
+  });
+
+  it("Closes a stale attestation, its rent going to the agent less the tip", async () => {
+    const bountyPda = await postBounty(ctx, "Bridge exploit trace", reward);
+    const attestation = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      generateSolutionHashWithValue(0x42),
+      false
+    );
+    const rent = await ctx.connection.getBalance(attestation);
+    await waitPastTtl(attestation);
+    const agentBefore = await ctx.connection.getBalance(agent.publicKey);
+    const crankerBefore = await ctx.connection.getBalance(cranker.publicKey);
+
+    await closeStale(attestation, bountyPda);
+
+    expect(await ctx.connection.getAccountInfo(attestation)).to.be.null;
+    expect((await ctx.connection.getBalance(agent.publicKey)) - agentBefore).to.equal(
+      rent - tipLamports
+    );
+    // the cranker paid the transaction fee out of the same balance
+    expect(
+      (await ctx.connection.getBalance(cranker.publicKey)) - crankerBefore
+    ).to.equal(tipLamports - 5_000);
+  });
+
+  it("Refuses to close a consumed attestation however old", async () => {
+    const bountyPda = await postBounty(ctx, "Bridge exploit trace", reward);
+    const hash = generateSolutionHashWithValue(0x43);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    await waitPastTtl(attestation);
+
+    try {
+      await closeStale(attestation, bountyPda);
+      expect.fail("Should have failed - attestation backs a submission");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AttestationAlreadyConsumed");
+    }
+    expect(await ctx.connection.getAccountInfo(attestation)).to.not.be.null;
+  });
+});
@@ -0,0 +1,81 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  generateSolutionHashWithValue,
+  SWITCHBOARD_FEED_FRESH,
+  SWITCHBOARD_FEED_FRESH_2,
+  TestContext,
+} from "./helpers";
+
+// Ceilings for the hot paths, with headroom over what they use today. A
+// change that pushes past one should say why in its PR before raising it.
+const SUBMIT_WITH_ORACLES_BUDGET = 90_000;
+const SETTLE_BUDGET = 110_000;
+
+describe("compute_budget", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  async function unitsConsumed(signature: string): Promise<number> {
+    await ctx.connection.confirmTransaction(signature, "confirmed");
+    const tx = await ctx.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    return tx.meta.computeUnitsConsumed;
+  }
+
+  it("Submits against two oracle feeds and settles within budget", async () => {
+    const feeds = [SWITCHBOARD_FEED_FRESH, SWITCHBOARD_FEED_FRESH_2];
+    const bountyPda = await postBounty(ctx, "Two-feed price check", reward, {
+      bountyType: { tokenScreening: {} },
+      challengePeriodSlots: 0,
+      requiresOracle: true,
+      oracleFeeds: feeds,
+      oracleKind: { switchboard: {} },
+    });
+
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const agentTokenAccount: PublicKey = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    const hash = generateSolutionHashWithValue(0x58);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+
+    const submitUnits = await unitsConsumed(
+      await submitSolution(ctx, agent, bountyPda, attestation, hash, feeds)
+    );
+    expect(submitUnits).to.be.at.most(SUBMIT_WITH_ORACLES_BUDGET);
+
+    const settleUnits = await unitsConsumed(
+      await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount)
+    );
+    expect(settleUnits).to.be.at.most(SETTLE_BUDGET);
+  });
+});
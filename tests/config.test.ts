@@ -0,0 +1,177 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveConfigPda,
+  DEFAULT_MARKET,
+  airdropSol,
+  NO_CONFIG_CHANGES,
+  TestContext,
+} from "./helpers";
+
+describe("config", () => {
+  let ctx: TestContext;
+  let configPda: anchor.web3.PublicKey;
+  let admin: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    [configPda] = deriveConfigPda(ctx.program.programId);
+    admin = ctx.provider.wallet.publicKey;
+  });
+
+  async function updateConfig(
+    changes: Partial<typeof NO_CONFIG_CHANGES>,
+    signer?: Keypair
+  ): Promise<void> {
+    const builder = ctx.program.methods
+      .updateConfig(DEFAULT_MARKET, { ...NO_CONFIG_CHANGES, ...changes })
+      .accountsPartial({
+        admin: signer ? signer.publicKey : admin,
+        config: configPda,
+      });
+    await (signer ? builder.signers([signer]) : builder).rpc();
+  }
+
+  it("Makes the initializer the admin", async () => {
+    const config = await ctx.program.account.config.fetch(configPda);
+    expect(config.admin.toString()).to.equal(admin.toString());
+    expect(config.pendingAdmin).to.be.null;
+    expect(config.paused).to.be.false;
+  });
+
+  it("Fails when initialized a second time", async () => {
+    try {
+      await ctx.program.methods
+        .initializeConfig({
+          feeBps: 0,
+          treasury: admin,
+          verifier: admin,
+          trustedAttestor: null,
+          attestors: [],
+          defaultChallengePeriodSlots: new anchor.BN(0),
+          challengeBondLamports: new anchor.BN(0),
+          challengeRewardBps: 0,
+          stakeSlashTreasuryBps: 0,
+          defaultSettleWindowSlots: new anchor.BN(0),
+          maxOpenPerCreator: 10,
+          minReward: new anchor.BN(1),
+          maxReward: new anchor.BN(1),
+          receiptRetentionSeconds: new anchor.BN(0),
+          attestorFeeBps: 0,
+          scoreUnit: new anchor.BN(0),
+          maxScorePerBounty: new anchor.BN(1),
+          decayPeriodSeconds: new anchor.BN(0),
+          allowSelfSettlement: false,
+          crankFeeBps: 0,
+          paymentGateway: null,
+          defaultResubmissionCooldownSeconds: new anchor.BN(0),
+          abandonmentSeconds: new anchor.BN(1),
+          trustedMints: [],
+          boostFeeBps: 0,
+          boostDurationSeconds: new anchor.BN(0),
+          attestationTtlSeconds: new anchor.BN(0),
+          attestationCloseTipLamports: new anchor.BN(0),
+          insuranceFeeBps: 0,
+          maxInsuranceTopup: new anchor.BN(0),
+          primaryMint: null,
+          appealWindowSeconds: new anchor.BN(0),
+          challengeForfeitBps: 0,
+          minVerificationFee: new anchor.BN(0),
+        })
+        .accountsPartial({ admin })
+        .rpc();
+      expect.fail("Should have failed - config already initialized");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ConfigAlreadyInitialized");
+    }
+  });
+
+  it("Lets the admin update the fee and treasury", async () => {
+    const before = await ctx.program.account.config.fetch(configPda);
+    const treasury = Keypair.generate().publicKey;
+
+    await updateConfig({ feeBps: 250, treasury });
+
+    const after = await ctx.program.account.config.fetch(configPda);
+    expect(after.feeBps).to.equal(250);
+    expect(after.treasury.toString()).to.equal(treasury.toString());
+    expect(after.paused).to.equal(before.paused);
+
+    await updateConfig({ feeBps: before.feeBps, treasury: before.treasury });
+  });
+
+  it("Fails when the fee exceeds 10000 bps", async () => {
+    try {
+      await updateConfig({ feeBps: 10_001 });
+      expect.fail("Should have failed - fee too high");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidFeeBps");
+    }
+  });
+
+  it("Fails when the protocol and attestor fees add up to more than 10000 bps", async () => {
+    try {
+      await updateConfig({ feeBps: 6_000, attestorFeeBps: 4_001 });
+      expect.fail("Should have failed - fees too high together");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidFeeBps");
+    }
+  });
+
+  it("Fails when a non-admin updates the config", async () => {
+    const intruder = Keypair.generate();
+    await airdropSol(ctx.connection, intruder.publicKey);
+
+    try {
+      await updateConfig({ paused: true }, intruder);
+      expect.fail("Should have failed - not the admin");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedAdmin");
+    }
+  });
+
+  it("Hands the admin role over in two steps", async () => {
+    const newAdmin = Keypair.generate();
+    const stranger = Keypair.generate();
+    await airdropSol(ctx.connection, newAdmin.publicKey);
+    await airdropSol(ctx.connection, stranger.publicKey);
+
+    await updateConfig({ pendingAdmin: newAdmin.publicKey });
+
+    let config = await ctx.program.account.config.fetch(configPda);
+    expect(config.admin.toString()).to.equal(admin.toString());
+    expect(config.pendingAdmin.toString()).to.equal(
+      newAdmin.publicKey.toString()
+    );
+
+    try {
+      await ctx.program.methods
+        .acceptAdmin(DEFAULT_MARKET)
+        .accountsPartial({ newAdmin: stranger.publicKey, config: configPda })
+        .signers([stranger])
+        .rpc();
+      expect.fail("Should have failed - not the pending admin");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotPendingAdmin");
+    }
+
+    await ctx.program.methods
+      .acceptAdmin(DEFAULT_MARKET)
+      .accountsPartial({ newAdmin: newAdmin.publicKey, config: configPda })
+      .signers([newAdmin])
+      .rpc();
+
+    config = await ctx.program.account.config.fetch(configPda);
+    expect(config.admin.toString()).to.equal(newAdmin.publicKey.toString());
+    expect(config.pendingAdmin).to.be.null;
+
+    // hand it back so the other suites keep the provider wallet as admin
+    await updateConfig({ pendingAdmin: admin }, newAdmin);
+    await ctx.program.methods
+      .acceptAdmin(DEFAULT_MARKET)
+      .accountsPartial({ newAdmin: admin, config: configPda })
+      .rpc();
+  });
+});
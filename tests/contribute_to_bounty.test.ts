@@ -0,0 +1,156 @@
+import * as anchor from "@coral-xyz/anchor";
+import { mintTo } from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveContributionPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  contributeToBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("contribute_to_bounty", () => {
+  const reward = 10 * 10 ** 6;
+  const contributions = [3 * 10 ** 6, 4 * 10 ** 6, 5 * 10 ** 6];
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let bountyPda: anchor.web3.PublicKey;
+  let contributors: { keypair: Keypair; tokenAccount: anchor.web3.PublicKey }[];
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+
+    contributors = [];
+    for (let i = 0; i < contributions.length; i++) {
+      const keypair = Keypair.generate();
+      await airdropSol(ctx.connection, keypair.publicKey);
+      const tokenAccount = await createAgentTokenAccount(
+        ctx.connection,
+        keypair,
+        ctx.usdcMint
+      );
+      await mintTo(
+        ctx.connection,
+        ctx.creator,
+        ctx.usdcMint,
+        tokenAccount,
+        ctx.creator,
+        20 * 10 ** 6
+      );
+      contributors.push({ keypair, tokenAccount });
+    }
+
+    bountyPda = await postBounty(ctx, "Crowdfunded", reward);
+  });
+
+  async function tokenBalance(account: anchor.web3.PublicKey): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  async function submit() {
+    const hash = generateSolutionHashWithValue(0xc1);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+  }
+
+  it("Pools three contributions and pays the full amount on settlement", async () => {
+    for (let i = 0; i < contributors.length; i++) {
+      await contributeToBounty(
+        ctx,
+        contributors[i].keypair,
+        contributors[i].tokenAccount,
+        bountyPda,
+        contributions[i]
+      );
+    }
+
+    const pooled = reward + contributions.reduce((sum, c) => sum + c, 0);
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.reward.toNumber()).to.equal(pooled);
+    expect(bountyAccount.escrowAmount.toNumber()).to.equal(pooled);
+
+    for (let i = 0; i < contributors.length; i++) {
+      const [contributionPda] = deriveContributionPda(
+        ctx.program.programId,
+        bountyPda,
+        contributors[i].keypair.publicKey
+      );
+      const contribution = await ctx.program.account.contribution.fetch(
+        contributionPda
+      );
+      expect(contribution.amount.toNumber()).to.equal(contributions[i]);
+      expect(contribution.contributor.toString()).to.equal(
+        contributors[i].keypair.publicKey.toString()
+      );
+    }
+
+    await submit();
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+    expect(await tokenBalance(agentTokenAccount)).to.equal(pooled);
+  });
+
+  it("Adds up repeated contributions from the same wallet", async () => {
+    const { keypair, tokenAccount } = contributors[0];
+    await contributeToBounty(ctx, keypair, tokenAccount, bountyPda, 1_000_001);
+    await contributeToBounty(ctx, keypair, tokenAccount, bountyPda, 2_000_002);
+
+    const [contributionPda] = deriveContributionPda(
+      ctx.program.programId,
+      bountyPda,
+      keypair.publicKey
+    );
+    const contribution = await ctx.program.account.contribution.fetch(
+      contributionPda
+    );
+    expect(contribution.amount.toNumber()).to.equal(3_000_003);
+    expect(
+      await tokenBalance(getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda))
+    ).to.equal(reward + 3_000_003);
+  });
+
+  it("Fails to contribute after a solution is submitted", async () => {
+    await submit();
+
+    try {
+      await contributeToBounty(
+        ctx,
+        contributors[0].keypair,
+        contributors[0].tokenAccount,
+        bountyPda,
+        contributions[0]
+      );
+      expect.fail("Should have failed - reward locked");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RewardLocked");
+    }
+  });
+});
@@ -0,0 +1,118 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import { CpiCaller } from "../target/types/cpi_caller";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  airdropSol,
+  ensureCreatorBalance,
+  ensureReputation,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  generateSolutionHashWithValue,
+  NO_CIPHERTEXT_HASH,
+  TestContext,
+} from "./helpers";
+
+describe("cpi_guard", () => {
+  const reward = 5 * 10 ** 6;
+
+  let ctx: TestContext;
+  let caller: Program<CpiCaller>;
+  let agent: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    caller = anchor.workspace.CpiCaller as Program<CpiCaller>;
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    await ensureReputation(ctx, agent);
+  });
+
+  async function postAndAttest(
+    allowCpi: boolean
+  ): Promise<[PublicKey, PublicKey, Buffer]> {
+    const bountyPda = await postBounty(ctx, "Wrapped submission", reward, {
+      allowCpi,
+    });
+    const hash = generateSolutionHashWithValue(0x2f);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    return [bountyPda, attestation, hash];
+  }
+
+  // the same submit_solution the helper sends, passed on by the harness program
+  async function submitThroughCaller(
+    bountyPda: PublicKey,
+    attestation: PublicKey,
+    hash: Buffer
+  ) {
+    const ix = await ctx.program.methods
+      .submitSolution(Array.from(hash), NO_CIPHERTEXT_HASH, "", [])
+      .accountsPartial({
+        submitter: agent.publicKey,
+        agent: agent.publicKey,
+        operatorRecord: null,
+        bounty: bountyPda,
+        attestation,
+        reputation: deriveReputationPda(
+          ctx.program.programId,
+          agent.publicKey
+        )[0],
+        application: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .instruction();
+
+    return caller.methods
+      .forward(ix.data)
+      .accounts({ target: ctx.program.programId })
+      .remainingAccounts(ix.keys)
+      .signers([agent])
+      .rpc();
+  }
+
+  it("Accepts a direct submission", async () => {
+    const [bountyPda, attestation, hash] = await postAndAttest(false);
+
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ submitted: {} });
+  });
+
+  it("Refuses a submission made through another program", async () => {
+    const [bountyPda, attestation, hash] = await postAndAttest(false);
+
+    try {
+      await submitThroughCaller(bountyPda, attestation, hash);
+      expect.fail("Should have failed - submitted through CPI");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("CpiNotAllowed");
+    }
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ open: {} });
+  });
+
+  it("Accepts a submission through another program when the bounty allows it", async () => {
+    const [bountyPda, attestation, hash] = await postAndAttest(true);
+
+    await submitThroughCaller(bountyPda, attestation, hash);
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ submitted: {} });
+    expect(bounty.solver.toBase58()).to.equal(agent.publicKey.toBase58());
+  });
+});
@@ -0,0 +1,139 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  updateConfig,
+  waitForClock,
+  fetchEvents,
+  findEvent,
+  TestContext,
+} from "./helpers";
+
+describe("crank_expire_bounty", () => {
+  const reward = 20 * 10 ** 6;
+  const crankFeeBps = 50;
+  const incentive = (reward * crankFeeBps) / 10_000;
+
+  let ctx: TestContext;
+  let cranker: Keypair;
+  let crankerTokenAccount: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await updateConfig(ctx, { crankFeeBps });
+
+    cranker = Keypair.generate();
+    await airdropSol(ctx.connection, cranker.publicKey);
+    crankerTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      cranker,
+      ctx.usdcMint
+    );
+  });
+
+  after(async () => {
+    await updateConfig(ctx, { crankFeeBps: 0 });
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  async function crank(bountyPda: anchor.web3.PublicKey) {
+    return ctx.program.methods
+      .crankExpireBounty()
+      .accountsPartial({
+        cranker: cranker.publicKey,
+        bounty: bountyPda,
+        creator: ctx.creator.publicKey,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        crankerTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([cranker])
+      .rpc();
+  }
+
+  // posts a bounty whose deadline passes a few seconds from now
+  async function postExpiring(): Promise<[anchor.web3.PublicKey, number]> {
+    const deadline = Math.floor(Date.now() / 1000) + 3;
+    const bountyPda = await postBounty(ctx, "Left behind", reward, {
+      deadline,
+    });
+    return [bountyPda, deadline];
+  }
+
+  it("Expires an abandoned bounty and pays the cranker", async () => {
+    const [bountyPda, deadline] = await postExpiring();
+    await waitForClock(ctx.connection, deadline + 1);
+
+    const creatorBefore = await ctx.connection.getTokenAccountBalance(
+      ctx.creatorTokenAccount
+    );
+    const events = await fetchEvents(ctx, await crank(bountyPda));
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ expired: {} });
+
+    const crankerBalance = await ctx.connection.getTokenAccountBalance(
+      crankerTokenAccount
+    );
+    expect(Number(crankerBalance.value.amount)).to.equal(incentive);
+
+    const creatorAfter = await ctx.connection.getTokenAccountBalance(
+      ctx.creatorTokenAccount
+    );
+    expect(
+      Number(creatorAfter.value.amount) - Number(creatorBefore.value.amount)
+    ).to.equal(reward - incentive);
+
+    const event = findEvent(events, "BountyCranked");
+    expect(event.data.cranker.toString()).to.equal(
+      cranker.publicKey.toString()
+    );
+    expect(event.data.incentive.toNumber()).to.equal(incentive);
+    expect(event.data.refund.toNumber()).to.equal(reward - incentive);
+  });
+
+  it("Fails before the deadline", async () => {
+    const bountyPda = await postBounty(ctx, "Still running", reward);
+
+    try {
+      await crank(bountyPda);
+      expect.fail("Should have failed - deadline not reached");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyDeadlineNotReached");
+    }
+  });
+
+  it("Fails on a bounty that already expired", async () => {
+    const [bountyPda, deadline] = await postExpiring();
+    await waitForClock(ctx.connection, deadline + 1);
+    await crank(bountyPda);
+
+    try {
+      await crank(bountyPda);
+      expect.fail("Should have failed - already expired");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotOpen");
+    }
+  });
+});
@@ -0,0 +1,214 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveCreatorBondPda,
+  deriveSettlementRecordPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  waitForSlot,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("creator bond", () => {
+  const bond = LAMPORTS_PER_SOL / 2;
+  const settleWindowSlots = 4;
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let bountyPda: anchor.web3.PublicKey;
+  let bondPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+
+    bountyPda = await postBounty(ctx, "Bonded bounty", reward, {
+      bond,
+      settleWindowSlots,
+      challengePeriodSlots: 0,
+    });
+    [bondPda] = deriveCreatorBondPda(ctx.program.programId, bountyPda);
+
+    const hash = generateSolutionHashWithValue(0xb0);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+  });
+
+  async function tokenBalance(account: anchor.web3.PublicKey): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  async function forceSettle() {
+    return ctx.program.methods
+      .forceSettle()
+      .accountsPartial({
+        agent: agent.publicKey,
+        bounty: bountyPda,
+        creatorBond: bondPda,
+        agentTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  async function closeBounty() {
+    return ctx.program.methods
+      .closeBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        creatorBond: bondPda,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  it("Escrows the bond on its own PDA and records the terms on the bounty", async () => {
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.creatorBond.toNumber()).to.equal(bond);
+    expect(bountyAccount.settleWindowSlots.toNumber()).to.equal(
+      settleWindowSlots
+    );
+
+    const bondAccount = await ctx.program.account.creatorBond.fetch(bondPda);
+    expect(bondAccount.amount.toNumber()).to.equal(bond);
+    expect(bondAccount.creator.toString()).to.equal(
+      ctx.creator.publicKey.toString()
+    );
+
+    const info = await ctx.connection.getAccountInfo(bondPda);
+    const rent = await ctx.connection.getMinimumBalanceForRentExemption(
+      info.data.length
+    );
+    expect(info.lamports).to.equal(rent + bond);
+  });
+
+  it("Returns the bond at close_bounty after a normal settlement", async () => {
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+
+    // the bounty and its escrow ATA are closed to the creator as well
+    const held =
+      (await ctx.connection.getBalance(bondPda)) +
+      (await ctx.connection.getBalance(bountyPda)) +
+      (await ctx.connection.getBalance(
+        getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda)
+      ));
+    const creatorBefore = await ctx.connection.getBalance(
+      ctx.creator.publicKey
+    );
+
+    const signature = await closeBounty();
+    const tx = await ctx.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    expect(await ctx.connection.getAccountInfo(bondPda)).to.be.null;
+    expect(await ctx.connection.getBalance(ctx.creator.publicKey)).to.equal(
+      creatorBefore + held - tx.meta.fee
+    );
+  });
+
+  it("Fails to close the bounty while the solution awaits settlement", async () => {
+    try {
+      await closeBounty();
+      expect.fail("Should have failed - bounty not finished");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotFinished");
+    }
+  });
+
+  it("Fails to force settle before the settle window runs out", async () => {
+    try {
+      await forceSettle();
+      expect.fail("Should have failed - settle window active");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SettleWindowActive");
+    }
+  });
+
+  it("Forfeits the bond to the agent when they force settle", async () => {
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    await waitForSlot(
+      ctx.connection,
+      bountyAccount.submittedAtSlot.toNumber() +
+        bountyAccount.challengePeriodSlots.toNumber() +
+        bountyAccount.settleWindowSlots.toNumber()
+    );
+
+    const held = await ctx.connection.getBalance(bondPda);
+    const agentBefore = await ctx.connection.getBalance(agent.publicKey);
+
+    const signature = await forceSettle();
+    const tx = await ctx.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    expect(await tokenBalance(agentTokenAccount)).to.equal(reward);
+    expect(await ctx.connection.getAccountInfo(bondPda)).to.be.null;
+
+    // the agent also pays for their settlement record
+    const [settlementPda] = deriveSettlementRecordPda(
+      ctx.program.programId,
+      bountyPda,
+      agent.publicKey
+    );
+    const recordRent = await ctx.connection.getBalance(settlementPda);
+    expect(await ctx.connection.getBalance(agent.publicKey)).to.equal(
+      agentBefore + held - recordRent - tx.meta.fee
+    );
+
+    const settled = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(settled.status).to.deep.equal({ settled: {} });
+  });
+
+  it("Fails to post a bond without a settle window", async () => {
+    try {
+      await postBounty(ctx, "No window", reward, { bond });
+      expect.fail("Should have failed - no settle window");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SettleWindowRequired");
+    }
+  });
+});
@@ -0,0 +1,93 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveCreatorIndexPda,
+  getAssociatedTokenAddressSync,
+  ensureCreatorBalance,
+  postBounty,
+  TestContext,
+} from "./helpers";
+
+describe("creator_index", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+  let indexPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    [indexPda] = deriveCreatorIndexPda(
+      ctx.program.programId,
+      ctx.creator.publicKey
+    );
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  it("Lists every posted bounty and drops the closed ones", async () => {
+    const bountyPdas: anchor.web3.PublicKey[] = [];
+    for (let i = 0; i < 5; i++) {
+      bountyPdas.push(await postBounty(ctx, `Indexed ${i}`, reward));
+    }
+
+    const ids = [];
+    for (const bountyPda of bountyPdas) {
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      ids.push(bountyAccount.id.toNumber());
+    }
+    let index = await ctx.program.account.creatorIndex.fetch(indexPda);
+    expect(index.creator.toString()).to.equal(ctx.creator.publicKey.toString());
+    expect(index.bountyIds.map((id) => id.toNumber())).to.deep.equal(ids);
+
+    const bountyPda = bountyPdas[2];
+    const bountyTokenAccount = getAssociatedTokenAddressSync(
+      ctx.usdcMint,
+      bountyPda
+    );
+    await ctx.program.methods
+      .cancelBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        unwrapAccount: null,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+
+    // cancelling leaves the index alone, only closing prunes it
+    index = await ctx.program.account.creatorIndex.fetch(indexPda);
+    expect(index.bountyIds).to.have.length(5);
+
+    await ctx.program.methods
+      .closeBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        creatorBond: null,
+        creatorIndex: indexPda,
+        bountyTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+
+    index = await ctx.program.account.creatorIndex.fetch(indexPda);
+    expect(index.bountyIds.map((id) => id.toNumber())).to.deep.equal([
+      ids[0],
+      ids[1],
+      ids[3],
+      ids[4],
+    ]);
+  });
+});
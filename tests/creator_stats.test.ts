@@ -0,0 +1,170 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveCreatorProfilePda,
+  deriveReputationPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  fetchEvents,
+  findEvent,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("creator_stats", () => {
+  const reward = 4 * 10 ** 6;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let profilePda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    [profilePda] = deriveCreatorProfilePda(
+      ctx.program.programId,
+      ctx.creator.publicKey
+    );
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  function fetchProfile() {
+    return ctx.program.account.creatorProfile.fetch(profilePda);
+  }
+
+  it("Counts a settlement, what it paid out and how long it took", async () => {
+    const before = await fetchProfile();
+
+    const bountyPda = await postBounty(ctx, "Track record", reward, {
+      challengePeriodSlots: 0,
+    });
+    const hash = generateSolutionHashWithValue(0xc5);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    const { submittedAtSlot } = await ctx.program.account.bounty.fetch(
+      bountyPda
+    );
+
+    const signature = await ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        reputation: deriveReputationPda(
+          ctx.program.programId,
+          agent.publicKey
+        )[0],
+        agent: agent.publicKey,
+        agentTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+    const tx = await ctx.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const delay = tx.slot - submittedAtSlot.toNumber();
+
+    const after = await fetchProfile();
+    expect(after.totalPosted.toNumber()).to.equal(
+      before.totalPosted.toNumber() + 1
+    );
+    expect(after.totalSettled.toNumber()).to.equal(
+      before.totalSettled.toNumber() + 1
+    );
+    expect(after.totalCancelled.toNumber()).to.equal(
+      before.totalCancelled.toNumber()
+    );
+    expect(after.totalPaidOut.toNumber()).to.equal(
+      before.totalPaidOut.toNumber() + reward
+    );
+    expect(after.timedSettlements.toNumber()).to.equal(
+      before.timedSettlements.toNumber() + 1
+    );
+    expect(after.totalSettleDelaySlots.toNumber()).to.equal(
+      before.totalSettleDelaySlots.toNumber() + delay
+    );
+
+    const event = findEvent(
+      await fetchEvents(ctx, signature),
+      "CreatorStatsUpdated"
+    );
+    expect(event.data.totalSettled.toNumber()).to.equal(
+      after.totalSettled.toNumber()
+    );
+    expect(event.data.totalPaidOut.toNumber()).to.equal(
+      after.totalPaidOut.toNumber()
+    );
+    expect(event.data.avgSettleDelaySlots.toNumber()).to.equal(
+      Math.floor(
+        after.totalSettleDelaySlots.toNumber() /
+          after.timedSettlements.toNumber()
+      )
+    );
+  });
+
+  it("Counts a cancellation without touching the settlement figures", async () => {
+    const bountyPda = await postBounty(ctx, "Changed my mind", reward);
+    const before = await fetchProfile();
+
+    await ctx.program.methods
+      .cancelBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        unwrapAccount: null,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+
+    const after = await fetchProfile();
+    expect(after.totalCancelled.toNumber()).to.equal(
+      before.totalCancelled.toNumber() + 1
+    );
+    expect(after.totalSettled.toNumber()).to.equal(
+      before.totalSettled.toNumber()
+    );
+    expect(after.totalPaidOut.toNumber()).to.equal(
+      before.totalPaidOut.toNumber()
+    );
+    expect(after.openBountyCount).to.equal(before.openBountyCount - 1);
+  });
+});
@@ -0,0 +1,149 @@
+import * as anchor from "@coral-xyz/anchor";
+import { mintTo, TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  contributeToBounty,
+  fetchEvents,
+  findEvent,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("decrease_reward", () => {
+  const reward = 20 * 10 ** 6;
+
+  let ctx: TestContext;
+  let bountyPda: anchor.web3.PublicKey;
+  let bountyTokenAccount: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    bountyPda = await postBounty(ctx, "Over-funded bounty", reward);
+    bountyTokenAccount = getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda);
+  });
+
+  async function tokenBalance(account: anchor.web3.PublicKey): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  async function decreaseReward(amount: number) {
+    return ctx.program.methods
+      .decreaseReward(new anchor.BN(amount))
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  it("Refunds part of the escrow and lowers the reward", async () => {
+    const creatorBefore = await tokenBalance(ctx.creatorTokenAccount);
+
+    const events = await fetchEvents(ctx, await decreaseReward(reward / 2));
+
+    expect(await tokenBalance(bountyTokenAccount)).to.equal(reward / 2);
+    expect(await tokenBalance(ctx.creatorTokenAccount)).to.equal(
+      creatorBefore + reward / 2
+    );
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ open: {} });
+    expect(bounty.reward.toNumber()).to.equal(reward / 2);
+    expect(bounty.escrowAmount.toNumber()).to.equal(reward / 2);
+    expect(bounty.creatorDeposit.toNumber()).to.equal(reward / 2);
+
+    const event = findEvent(events, "RewardDecreased");
+    expect(event.data.oldReward.toNumber()).to.equal(reward);
+    expect(event.data.newReward.toNumber()).to.equal(reward / 2);
+    expect(event.data.refund.toNumber()).to.equal(reward / 2);
+  });
+
+  it("Fails once a solution has been submitted", async () => {
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const hash = generateSolutionHashWithValue(0x81);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+
+    try {
+      await decreaseReward(reward / 2);
+      expect.fail("Should have failed - reward locked");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RewardLocked");
+    }
+    expect(await tokenBalance(bountyTokenAccount)).to.equal(reward);
+  });
+
+  it("Fails to take back what contributors put in", async () => {
+    const contributor = Keypair.generate();
+    await airdropSol(ctx.connection, contributor.publicKey);
+    const contributorTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      contributor,
+      ctx.usdcMint
+    );
+    await mintTo(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      contributorTokenAccount,
+      ctx.creator,
+      10 * 10 ** 6
+    );
+    await contributeToBounty(
+      ctx,
+      contributor,
+      contributorTokenAccount,
+      bountyPda,
+      10 * 10 ** 6
+    );
+
+    // the creator's own 20 are refundable, the contributor's 10 are not
+    try {
+      await decreaseReward(reward + 1);
+      expect.fail("Should have failed - would refund contributed funds");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RewardBelowContributions");
+    }
+
+    await decreaseReward(reward);
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.reward.toNumber()).to.equal(10 * 10 ** 6);
+    expect(bounty.contributed.toNumber()).to.equal(10 * 10 ** 6);
+    expect(await tokenBalance(bountyTokenAccount)).to.equal(10 * 10 ** 6);
+  });
+
+  it("Fails below the configured minimum reward", async () => {
+    try {
+      await decreaseReward(reward);
+      expect.fail("Should have failed - reward would be zero");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RewardTooSmall");
+    }
+  });
+});
@@ -0,0 +1,145 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveConfigPda,
+  deriveReputationPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  updateConfig,
+  generateSolutionHashWithValue,
+  fetchEvents,
+  findEvent,
+  PostBountyOptions,
+  TestContext,
+} from "./helpers";
+
+describe("earnings_by_mint", () => {
+  const usdcReward = 10 * 10 ** 6;
+  const solReward = anchor.web3.LAMPORTS_PER_SOL / 4;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+  });
+
+  after(async () => {
+    await updateConfig(ctx, { primaryMint: PublicKey.default });
+  });
+
+  async function submitted(
+    options: PostBountyOptions = {}
+  ): Promise<PublicKey> {
+    const reward = options.rewardCurrency ? solReward : usdcReward;
+    const bountyPda = await postBounty(ctx, "Multi-mint audit", reward, {
+      challengePeriodSlots: 0,
+      ...options,
+    });
+    const hash = generateSolutionHashWithValue(0x4d);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    return bountyPda;
+  }
+
+  async function earnUsdc(): Promise<string> {
+    const bountyPda = await submitted();
+    const agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    return settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+  }
+
+  async function earnSol(): Promise<string> {
+    const bountyPda = await submitted({ rewardCurrency: { sol: {} } });
+    return ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        agent: agent.publicKey,
+        agentTokenAccount: null,
+        bountyTokenAccount: null,
+        rewardMint: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  function fetchReputation() {
+    return ctx.program.account.reputation.fetch(
+      deriveReputationPda(ctx.program.programId, agent.publicKey)[0]
+    );
+  }
+
+  it("Keeps each mint apart and only counts the primary one in total_earned", async () => {
+    await updateConfig(ctx, { primaryMint: ctx.usdcMint });
+
+    const usdcSig = await earnUsdc();
+    const solSig = await earnSol();
+    await earnUsdc();
+
+    const reputation = await fetchReputation();
+    const [usdc, sol, ...free] = reputation.earnedByMint;
+    expect(usdc.mint.toString()).to.equal(ctx.usdcMint.toString());
+    expect(usdc.amount.toNumber()).to.equal(2 * usdcReward);
+    expect(sol.mint.toString()).to.equal(PublicKey.default.toString());
+    expect(sol.amount.toNumber()).to.equal(solReward);
+    for (const slot of free) {
+      expect(slot.amount.toNumber()).to.equal(0);
+    }
+    expect(reputation.otherEarnedCount).to.equal(0);
+    expect(reputation.totalEarned.toNumber()).to.equal(2 * usdcReward);
+
+    const usdcEvent = findEvent(
+      await fetchEvents(ctx, usdcSig),
+      "BountySettled"
+    );
+    expect(usdcEvent.data.rewardMint.toString()).to.equal(
+      ctx.usdcMint.toString()
+    );
+    expect(usdcEvent.data.mintEarned.toNumber()).to.equal(usdcReward);
+    const solEvent = findEvent(await fetchEvents(ctx, solSig), "BountySettled");
+    expect(solEvent.data.mintEarned.toNumber()).to.equal(solReward);
+  });
+
+  it("Sums every mint into total_earned while no primary mint is set", async () => {
+    await updateConfig(ctx, { primaryMint: PublicKey.default });
+    const config = await ctx.program.account.config.fetch(
+      deriveConfigPda(ctx.program.programId)[0]
+    );
+    expect(config.primaryMint).to.be.null;
+
+    await earnUsdc();
+    await earnSol();
+
+    const reputation = await fetchReputation();
+    expect(reputation.totalEarned.toNumber()).to.equal(usdcReward + solReward);
+    expect(reputation.earnedByMint[0].amount.toNumber()).to.equal(usdcReward);
+    expect(reputation.earnedByMint[1].amount.toNumber()).to.equal(solReward);
+  });
+});
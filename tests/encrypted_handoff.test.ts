@@ -0,0 +1,173 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { createHash, generateKeyPairSync, randomBytes } from "crypto";
+import {
+  setupTestContext,
+  deriveSubmissionPda,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  TestContext,
+} from "./helpers";
+
+describe("encrypted_handoff", () => {
+  const reward = 10 * 10 ** 6;
+  const plaintext = Buffer.from(
+    JSON.stringify({
+      token: "So11111111111111111111111111111111111111112",
+      risk: "low",
+    })
+  );
+  const solutionHash = createHash("sha256").update(plaintext).digest();
+  // stands in for the solution sealed to the creator's key off-chain
+  const ciphertext = randomBytes(96);
+  const ciphertextHash = createHash("sha256").update(ciphertext).digest();
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let encryptionPubkey: Buffer;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    const { publicKey } = generateKeyPairSync("x25519");
+    encryptionPubkey = Buffer.from(
+      publicKey.export({ format: "jwk" }).x,
+      "base64url"
+    );
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+  });
+
+  async function submit(
+    bountyPda: PublicKey,
+    commitment: Buffer | null
+  ): Promise<void> {
+    const attestation = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      solutionHash
+    );
+    await submitSolution(
+      ctx,
+      agent,
+      bountyPda,
+      attestation,
+      solutionHash,
+      [],
+      [],
+      null,
+      "",
+      [],
+      commitment
+    );
+  }
+
+  function reveal(bountyPda: PublicKey, payload: Buffer) {
+    return ctx.program.methods
+      .revealSolution(payload)
+      .accountsPartial({
+        agent: agent.publicKey,
+        bounty: bountyPda,
+        submission: deriveSubmissionPda(
+          ctx.program.programId,
+          bountyPda,
+          agent.publicKey
+        )[0],
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  it("Records the key and commitment, and accepts the matching ciphertext", async () => {
+    const bountyPda = await postBounty(ctx, "Sealed screening", reward, {
+      requiresReveal: true,
+      encryptionPubkey,
+    });
+    await submit(bountyPda, ciphertextHash);
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(Buffer.from(bounty.encryptionPubkey)).to.deep.equal(encryptionPubkey);
+    const submission = await ctx.program.account.submission.fetch(
+      deriveSubmissionPda(ctx.program.programId, bountyPda, agent.publicKey)[0]
+    );
+    expect(Buffer.from(submission.ciphertextHash)).to.deep.equal(ciphertextHash);
+    expect(Buffer.from(submission.solutionHash)).to.deep.equal(solutionHash);
+
+    await reveal(bountyPda, ciphertext);
+    expect((await ctx.program.account.bounty.fetch(bountyPda)).revealed).to.be
+      .true;
+  });
+
+  it("Catches a ciphertext that doesn't match the commitment", async () => {
+    const bountyPda = await postBounty(ctx, "Sealed screening", reward, {
+      requiresReveal: true,
+      encryptionPubkey,
+    });
+    await submit(bountyPda, ciphertextHash);
+
+    // nor does revealing the plaintext count for an encrypted bounty
+    for (const payload of [randomBytes(96), plaintext]) {
+      try {
+        await reveal(bountyPda, payload);
+        expect.fail("Should have failed - not the committed ciphertext");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("CiphertextHashMismatch");
+      }
+    }
+    expect((await ctx.program.account.bounty.fetch(bountyPda)).revealed).to.be
+      .false;
+  });
+
+  it("Lets the solver prove delivery once a dispute is open", async () => {
+    const arbiter = Keypair.generate();
+    const bountyPda = await postBounty(ctx, "Disputed screening", reward, {
+      arbiter: arbiter.publicKey,
+      encryptionPubkey,
+    });
+    await submit(bountyPda, ciphertextHash);
+    await ctx.program.methods
+      .openDispute()
+      .accountsPartial({ party: ctx.creator.publicKey, bounty: bountyPda })
+      .signers([ctx.creator])
+      .rpc();
+
+    await reveal(bountyPda, ciphertext);
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ disputed: {} });
+    expect(bounty.revealed).to.be.true;
+  });
+
+  it("Requires a commitment exactly when the bounty has a key", async () => {
+    const sealedPda = await postBounty(ctx, "Sealed screening", reward, {
+      encryptionPubkey,
+    });
+    try {
+      await submit(sealedPda, null);
+      expect.fail("Should have failed - no ciphertext commitment");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidCiphertextHash");
+    }
+
+    const plainPda = await postBounty(ctx, "Plain screening", reward);
+    try {
+      await submit(plainPda, ciphertextHash);
+      expect.fail("Should have failed - nothing to encrypt to");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidCiphertextHash");
+    }
+  });
+});
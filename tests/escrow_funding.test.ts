@@ -0,0 +1,227 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  transferChecked,
+} from "@solana/spl-token";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  nextBountyPda,
+  deriveReputationPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  bountyParams,
+  createAgentTokenAccount,
+  createAttestation,
+  createPermanentDelegateMint,
+  ensureCreatorBalance,
+  postBounty,
+  settleBounty,
+  submitSolution,
+  updateConfig,
+  waitForPostCooldown,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("escrow funding", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  after(async () => {
+    await updateConfig(ctx, { trustedMints: [] });
+  });
+
+  beforeEach(async () => {
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+  });
+
+  async function submit(bountyPda: PublicKey) {
+    const hash = generateSolutionHashWithValue(0xe5);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+  }
+
+  it("Fails with EscrowUnderfunded when the vault holds less than the reward", async () => {
+    // the creator is the mint's permanent delegate, so can drain the escrow ATA
+    const mint = await createPermanentDelegateMint(
+      ctx.connection,
+      ctx.creator,
+      6,
+      ctx.creator.publicKey
+    );
+    // otherwise post_bounty refuses a mint with a permanent delegate
+    await updateConfig(ctx, { trustedMints: [mint] });
+    const tokenAccount = async (owner: PublicKey) =>
+      (
+        await getOrCreateAssociatedTokenAccount(
+          ctx.connection,
+          ctx.creator,
+          mint,
+          owner,
+          false,
+          undefined,
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        )
+      ).address;
+
+    const creatorTokenAccount = await tokenAccount(ctx.creator.publicKey);
+    await mintTo(
+      ctx.connection,
+      ctx.creator,
+      mint,
+      creatorTokenAccount,
+      ctx.creator,
+      reward,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+    const agentTokenAccount = await tokenAccount(agent.publicKey);
+
+    const [bountyPda] = await nextBountyPda(ctx);
+    const bountyTokenAccount = getAssociatedTokenAddressSync(
+      mint,
+      bountyPda,
+      TOKEN_2022_PROGRAM_ID
+    );
+    await waitForPostCooldown(ctx.creator.publicKey);
+    await ctx.program.methods
+      .postBounty(bountyParams("Drained escrow", reward))
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        rewardMint: mint,
+        creatorTokenAccount,
+        bountyTokenAccount,
+        tokenProgram: TOKEN_2022_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+    await submit(bountyPda);
+
+    await transferChecked(
+      ctx.connection,
+      ctx.creator,
+      bountyTokenAccount,
+      mint,
+      creatorTokenAccount,
+      ctx.creator,
+      1,
+      6,
+      [],
+      undefined,
+      TOKEN_2022_PROGRAM_ID
+    );
+
+    try {
+      await ctx.program.methods
+        .settleBounty()
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          reputation: deriveReputationPda(
+            ctx.program.programId,
+            agent.publicKey
+          )[0],
+          agent: agent.publicKey,
+          agentTokenAccount,
+          bountyTokenAccount,
+          rewardMint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+      expect.fail("Should have failed - escrow underfunded");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("EscrowUnderfunded");
+    }
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+  });
+
+  it("Pays only the reward from an overfunded vault and returns the rest on close", async () => {
+    const surplus = 3 * 10 ** 6;
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      reward + surplus
+    );
+    const agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+
+    const bountyPda = await postBounty(ctx, "Overfunded escrow", reward);
+    const bountyTokenAccount = getAssociatedTokenAddressSync(
+      ctx.usdcMint,
+      bountyPda
+    );
+    // sent straight to the vault, outside any instruction that records it
+    await transferChecked(
+      ctx.connection,
+      ctx.creator,
+      ctx.creatorTokenAccount,
+      ctx.usdcMint,
+      bountyTokenAccount,
+      ctx.creator,
+      surplus,
+      6
+    );
+
+    await submit(bountyPda);
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+
+    const agentBalance = await ctx.connection.getTokenAccountBalance(
+      agentTokenAccount
+    );
+    expect(Number(agentBalance.value.amount)).to.equal(reward);
+    const escrowBalance = await ctx.connection.getTokenAccountBalance(
+      bountyTokenAccount
+    );
+    expect(Number(escrowBalance.value.amount)).to.equal(surplus);
+
+    const creatorBefore = await ctx.connection.getTokenAccountBalance(
+      ctx.creatorTokenAccount
+    );
+    await ctx.program.methods
+      .closeBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        creatorBond: null,
+        bountyTokenAccount,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+
+    const creatorAfter = await ctx.connection.getTokenAccountBalance(
+      ctx.creatorTokenAccount
+    );
+    expect(
+      Number(creatorAfter.value.amount) - Number(creatorBefore.value.amount)
+    ).to.equal(surplus);
+    expect(await ctx.connection.getAccountInfo(bountyTokenAccount)).to.be.null;
+  });
+});
@@ -0,0 +1,115 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  generateSolutionHashWithValue,
+  sleep,
+  TestContext,
+} from "./helpers";
+
+describe("expire_bounty", () => {
+  let ctx: TestContext;
+  const reward = 30 * 10 ** 6;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  async function expire(bountyPda: anchor.web3.PublicKey): Promise<void> {
+    await ctx.program.methods
+      .expireBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        unwrapAccount: null,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  it("Expires an unsubmitted bounty after its deadline and refunds the escrow", async () => {
+    const bountyPda = await postBounty(ctx, "Nobody came", reward, {
+      deadline: Math.floor(Date.now() / 1000) + 4,
+    });
+
+    await sleep(6000);
+
+    const creatorBalanceBefore = await ctx.connection.getTokenAccountBalance(
+      ctx.creatorTokenAccount
+    );
+
+    await expire(bountyPda);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ expired: {} });
+
+    const creatorBalanceAfter = await ctx.connection.getTokenAccountBalance(
+      ctx.creatorTokenAccount
+    );
+    expect(creatorBalanceAfter.value.amount).to.equal(
+      (Number(creatorBalanceBefore.value.amount) + reward).toString()
+    );
+  });
+
+  it("Fails before the deadline", async () => {
+    const bountyPda = await postBounty(ctx, "Still running", reward);
+
+    try {
+      await expire(bountyPda);
+      expect.fail("Should have failed - deadline not reached");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyDeadlineNotReached");
+    }
+  });
+
+  it("Fails while a submission is pending", async () => {
+    const bountyPda = await postBounty(ctx, "Pending review", reward, {
+      deadline: Math.floor(Date.now() / 1000) + 6,
+    });
+
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const solutionHash = generateSolutionHashWithValue(0x6e);
+    const attestationPda = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      solutionHash
+    );
+    await submitSolution(ctx, agent, bountyPda, attestationPda, solutionHash);
+
+    await sleep(8000);
+
+    try {
+      await expire(bountyPda);
+      expect.fail("Should have failed - submission pending");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotOpen");
+    }
+  });
+});
@@ -0,0 +1,146 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("leave_feedback", () => {
+  const reward = 1 * 10 ** 6;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let reputationPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  async function submitted(): Promise<anchor.web3.PublicKey> {
+    const bountyPda = await postBounty(ctx, "Rate my work", reward);
+    const hash = generateSolutionHashWithValue(0xfb);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    return bountyPda;
+  }
+
+  async function settled(): Promise<anchor.web3.PublicKey> {
+    const bountyPda = await submitted();
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+    return bountyPda;
+  }
+
+  async function leaveFeedback(
+    bountyPda: anchor.web3.PublicKey,
+    rating: number,
+    signer: Keypair = ctx.creator
+  ) {
+    await ctx.program.methods
+      .leaveFeedback(rating)
+      .accountsPartial({
+        creator: signer.publicKey,
+        bounty: bountyPda,
+        reputation: reputationPda,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  it("Adds three ratings up to the right average", async () => {
+    const before = await ctx.program.account.reputation.fetch(reputationPda);
+    for (const rating of [5, 3, 4]) {
+      const bountyPda = await settled();
+      await leaveFeedback(bountyPda, rating);
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bountyAccount.feedbackGiven).to.be.true;
+    }
+
+    const after = await ctx.program.account.reputation.fetch(reputationPda);
+    const sum = after.ratingSum.toNumber() - before.ratingSum.toNumber();
+    const count = after.ratingCount - before.ratingCount;
+    expect(sum).to.equal(12);
+    expect(count).to.equal(3);
+    expect(sum / count).to.equal(4);
+  });
+
+  it("Rejects a second rating for the same bounty", async () => {
+    const bountyPda = await settled();
+    await leaveFeedback(bountyPda, 2);
+
+    try {
+      await leaveFeedback(bountyPda, 5);
+      expect.fail("Should have failed - feedback already given");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("FeedbackAlreadyGiven");
+    }
+  });
+
+  it("Rejects ratings outside 1 to 5", async () => {
+    const bountyPda = await settled();
+
+    for (const rating of [0, 6]) {
+      try {
+        await leaveFeedback(bountyPda, rating);
+        expect.fail("Should have failed - rating out of range");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("InvalidRating");
+      }
+    }
+  });
+
+  it("Rejects feedback on an unsettled bounty", async () => {
+    const bountyPda = await submitted();
+
+    try {
+      await leaveFeedback(bountyPda, 4);
+      expect.fail("Should have failed - bounty not settled");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotSettled");
+    }
+  });
+
+  it("Rejects feedback from anyone but the creator", async () => {
+    const bountyPda = await settled();
+    const stranger = Keypair.generate();
+    await airdropSol(ctx.connection, stranger.publicKey);
+
+    try {
+      await leaveFeedback(bountyPda, 1, stranger);
+      expect.fail("Should have failed - not the creator");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedFeedback");
+    }
+  });
+});
@@ -0,0 +1,112 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  DEFAULT_MARKET,
+  setupTestContext,
+  deriveAgentFlagPda,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("flag_agent", () => {
+  let ctx: TestContext;
+  let agent: Keypair;
+  let bountyPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    bountyPda = await postBounty(ctx, "Original work only", 10 * 10 ** 6);
+  });
+
+  async function flag(banned: boolean, signer?: Keypair): Promise<void> {
+    const builder = ctx.program.methods
+      .flagAgent(DEFAULT_MARKET, agent.publicKey, banned)
+      .accountsPartial({
+        admin: signer ? signer.publicKey : ctx.provider.wallet.publicKey,
+      });
+    await (signer ? builder.signers([signer]) : builder).rpc();
+  }
+
+  async function attest(hash: Buffer): Promise<anchor.web3.PublicKey> {
+    return createAttestation(ctx, agent, bountyPda, hash);
+  }
+
+  it("Blocks a banned agent from attesting and submitting", async () => {
+    // attested before the ban, so only the submission is left to stop
+    const hash = generateSolutionHashWithValue(0xc1);
+    const attestation = await attest(hash);
+
+    await flag(true);
+    const [flagPda] = deriveAgentFlagPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    const agentFlag = await ctx.program.account.agentFlag.fetch(flagPda);
+    expect(agentFlag.agent.toString()).to.equal(agent.publicKey.toString());
+    expect(agentFlag.banned).to.be.true;
+
+    try {
+      await submitSolution(ctx, agent, bountyPda, attestation, hash);
+      expect.fail("Should have failed - agent is banned");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AgentBanned");
+    }
+
+    try {
+      await attest(generateSolutionHashWithValue(0xc2));
+      expect.fail("Should have failed - agent is banned");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AgentBanned");
+    }
+  });
+
+  it("Lets an unbanned agent submit again", async () => {
+    await flag(true);
+    await flag(false);
+
+    const [flagPda] = deriveAgentFlagPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    const agentFlag = await ctx.program.account.agentFlag.fetch(flagPda);
+    expect(agentFlag.banned).to.be.false;
+
+    const hash = generateSolutionHashWithValue(0xc3);
+    await submitSolution(ctx, agent, bountyPda, await attest(hash), hash);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.solver.toString()).to.equal(
+      agent.publicKey.toString()
+    );
+  });
+
+  it("Fails when someone other than the admin flags an agent", async () => {
+    const outsider = Keypair.generate();
+    await airdropSol(ctx.connection, outsider.publicKey);
+
+    try {
+      await flag(true, outsider);
+      expect.fail("Should have failed - not the admin");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedAdmin");
+    }
+  });
+});
@@ -0,0 +1,184 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  deriveSubmissionPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  rejectSolution,
+  updateConfig,
+  waitForSlot,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("force_settle", () => {
+  const settlementTimeoutSlots = 20;
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let bountyPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await updateConfig(ctx, {
+      defaultSettleWindowSlots: new anchor.BN(settlementTimeoutSlots),
+    });
+  });
+
+  after(async () => {
+    await updateConfig(ctx, { defaultSettleWindowSlots: new anchor.BN(0) });
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+
+    // no window of its own, so the Config timeout applies
+    bountyPda = await postBounty(ctx, "Creator went quiet", reward, {
+      challengePeriodSlots: 0,
+    });
+    const hash = generateSolutionHashWithValue(0x3f);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+  });
+
+  // the solver's reputation and submission, whoever signs
+  async function forceSettle(signer: Keypair, tokenAccount = agentTokenAccount) {
+    return ctx.program.methods
+      .forceSettle()
+      .accountsPartial({
+        agent: signer.publicKey,
+        bounty: bountyPda,
+        reputation: deriveReputationPda(
+          ctx.program.programId,
+          agent.publicKey
+        )[0],
+        submission: deriveSubmissionPda(
+          ctx.program.programId,
+          bountyPda,
+          agent.publicKey
+        )[0],
+        creatorBond: null,
+        agentTokenAccount: tokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function forceSettleSlot(): Promise<number> {
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    return (
+      bountyAccount.submittedAtSlot.toNumber() +
+      bountyAccount.challengePeriodSlots.toNumber() +
+      bountyAccount.settleWindowSlots.toNumber()
+    );
+  }
+
+  it("Takes the settlement timeout from the Config", async () => {
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.settleWindowSlots.toNumber()).to.equal(
+      settlementTimeoutSlots
+    );
+  });
+
+  it("Fails just before the timeout and pays the solver once it passes", async () => {
+    const [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    const reputationBefore = await ctx.program.account.reputation.fetch(
+      reputationPda
+    );
+    const forceAt = await forceSettleSlot();
+
+    // sent two slots early so it still lands before the timeout
+    await waitForSlot(ctx.connection, forceAt - 2);
+    try {
+      await forceSettle(agent);
+      expect.fail("Should have failed - creator still has time");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SettleWindowActive");
+    }
+
+    await waitForSlot(ctx.connection, forceAt);
+    await forceSettle(agent);
+
+    const balance = await ctx.connection.getTokenAccountBalance(
+      agentTokenAccount
+    );
+    expect(Number(balance.value.amount)).to.equal(reward);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ settled: {} });
+
+    const reputation = await ctx.program.account.reputation.fetch(
+      reputationPda
+    );
+    expect(reputation.successfulBounties.toNumber()).to.equal(
+      reputationBefore.successfulBounties.toNumber() + 1
+    );
+    expect(reputation.totalEarned.toNumber()).to.equal(
+      reputationBefore.totalEarned.toNumber() + reward
+    );
+  });
+
+  it("Fails for anyone but the recorded solver", async () => {
+    await waitForSlot(ctx.connection, await forceSettleSlot());
+
+    const stranger = Keypair.generate();
+    await airdropSol(ctx.connection, stranger.publicKey);
+    const strangerTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      stranger,
+      ctx.usdcMint
+    );
+
+    try {
+      await forceSettle(stranger, strangerTokenAccount);
+      expect.fail("Should have failed - not the solver");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedClaim");
+    }
+  });
+
+  it("Lets the creator still reject within the timeout", async () => {
+    await rejectSolution(ctx, agent.publicKey, bountyPda);
+    await waitForSlot(ctx.connection, await forceSettleSlot());
+
+    try {
+      await forceSettle(agent);
+      expect.fail("Should have failed - solution rejected");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotSubmitted");
+    }
+  });
+});
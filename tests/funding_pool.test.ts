@@ -0,0 +1,191 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  DEFAULT_MARKET,
+  setupTestContext,
+  bountyParams,
+  deriveFundingPoolPda,
+  getAssociatedTokenAddressSync,
+  ensureCreatorBalance,
+  nextBountyPda,
+  waitForPostCooldown,
+  TestContext,
+} from "./helpers";
+
+describe("funding_pool", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+  let poolPda: PublicKey;
+  let poolTokenAccount: PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    [poolPda] = deriveFundingPoolPda(
+      ctx.program.programId,
+      ctx.creator.publicKey,
+      ctx.usdcMint
+    );
+    poolTokenAccount = getAssociatedTokenAddressSync(ctx.usdcMint, poolPda);
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    // every test starts from an empty pool, whatever an earlier one left
+    const existing = await ctx.program.account.fundingPool.fetchNullable(poolPda);
+    if (existing) {
+      const available = existing.deposited.sub(existing.allocated);
+      if (!available.isZero()) {
+        await withdraw(available);
+      }
+    }
+  });
+
+  async function balanceOf(account: PublicKey): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  async function createPool(amount: number) {
+    await ctx.program.methods
+      .createPool(DEFAULT_MARKET, new anchor.BN(amount))
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function withdraw(amount: anchor.BN) {
+    await ctx.program.methods
+      .withdrawFromPool(amount)
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        fundingPool: poolPda,
+        poolTokenAccount,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function postFromPool(description: string): Promise<PublicKey> {
+    const [bountyPda] = await nextBountyPda(ctx);
+    await waitForPostCooldown(ctx.creator.publicKey);
+    await ctx.program.methods
+      .postBountyFromPool(bountyParams(description, reward))
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        creatorBond: null,
+        rewardMint: ctx.usdcMint,
+        targetAccount: null,
+        creatorTokenAccount: null,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        fundingPool: poolPda,
+        poolTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+    return bountyPda;
+  }
+
+  it("Funds two bounties from one deposit and refuses a third", async () => {
+    await createPool(2 * reward);
+    const creatorBefore = await balanceOf(ctx.creatorTokenAccount);
+    const allocatedBefore = (
+      await ctx.program.account.fundingPool.fetch(poolPda)
+    ).allocated.toNumber();
+
+    const first = await postFromPool("Pool-funded audit one");
+    const second = await postFromPool("Pool-funded audit two");
+
+    for (const bountyPda of [first, second]) {
+      const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bounty.escrowAmount.toNumber()).to.equal(reward);
+      expect(
+        await balanceOf(getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda))
+      ).to.equal(reward);
+    }
+    // the creator's own account never moved
+    expect(await balanceOf(ctx.creatorTokenAccount)).to.equal(creatorBefore);
+
+    const pool = await ctx.program.account.fundingPool.fetch(poolPda);
+    expect(pool.allocated.toNumber() - allocatedBefore).to.equal(2 * reward);
+    expect(pool.deposited.eq(pool.allocated)).to.be.true;
+
+    try {
+      await postFromPool("Pool-funded audit three");
+      expect.fail("Should have failed - pool exhausted");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PoolInsufficient");
+    }
+  });
+
+  it("Returns unallocated funds and refuses to withdraw more", async () => {
+    await createPool(reward);
+    await postFromPool("Half the budget");
+    await createPool(reward);
+    const creatorBefore = await balanceOf(ctx.creatorTokenAccount);
+
+    try {
+      await withdraw(new anchor.BN(reward + 1));
+      expect.fail("Should have failed - only one reward is unallocated");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PoolInsufficient");
+    }
+
+    await withdraw(new anchor.BN(reward));
+    expect(await balanceOf(ctx.creatorTokenAccount)).to.equal(
+      creatorBefore + reward
+    );
+    const pool = await ctx.program.account.fundingPool.fetch(poolPda);
+    expect(pool.deposited.eq(pool.allocated)).to.be.true;
+  });
+
+  it("Refunds a cancelled pool-funded bounty to the creator", async () => {
+    await createPool(reward);
+    const bountyPda = await postFromPool("Cancelled budget item");
+    const creatorBefore = await balanceOf(ctx.creatorTokenAccount);
+
+    await ctx.program.methods
+      .cancelBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        unwrapAccount: null,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+
+    // like any bounty, the refund goes to the creator rather than back to the pool
+    expect(await balanceOf(ctx.creatorTokenAccount)).to.equal(
+      creatorBefore + reward
+    );
+  });
+});
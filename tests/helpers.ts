@@ -2,17 +2,114 @@ import * as anchor from "@coral-xyz/anchor";
 import { IdlTypes, Program } from "@coral-xyz/anchor";
 import { Bountyforge } from "../target/types/bountyforge";
 import {
+    ExtensionType,
+    TOKEN_2022_PROGRAM_ID,
     TOKEN_PROGRAM_ID,
+    AccountState,
+    createInitializeDefaultAccountStateInstruction,
+    createInitializeMintInstruction,
+    createInitializePermanentDelegateInstruction,
+    createInitializeTransferFeeConfigInstruction,
+    createInitializeTransferHookInstruction,
     createMint,
+    getMintLen,
     getOrCreateAssociatedTokenAccount,
     mintTo,
 } from "@solana/spl-token";
-import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import {
+    Ed25519Program,
+    Keypair,
+    PublicKey,
+    SYSVAR_CLOCK_PUBKEY,
+    SYSVAR_INSTRUCTIONS_PUBKEY,
+    SystemProgram,
+    Transaction,
+    sendAndConfirmTransaction,
+} from "@solana/web3.js";
 
 export const ASSOCIATED_TOKEN_PROGRAM_ID = new PublicKey(
     "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL"
 );
 
+// Mocked Switchboard pull feeds loaded from tests/fixtures via Anchor.toml.
+// The fresh feed's last update is set in 2100 so it never ages past
+// MAX_ORACLE_STALENESS_SECONDS on a live validator clock. Every feed and
+// price update fixture reports 150.
+export const SWITCHBOARD_FEED_FRESH = new PublicKey(
+    "jH65tgAaYh6R6LAunwH6Jar6HExNSe95j2EW8EUDz4o"
+);
+// A second live feed, for bounties that name more than one
+export const SWITCHBOARD_FEED_FRESH_2 = new PublicKey(
+    "9K3WDR5JQy3X2vLDvspxE4d8vNhgoPEaJ9AgcrLZi8Nr"
+);
+export const SWITCHBOARD_FEED_STALE = new PublicKey(
+    "224W5xNrR12CiwtLpHpHAGvJExWuXcszXYupqxz99nu4"
+);
+export const SWITCHBOARD_FEED_WRONG_OWNER = new PublicKey(
+    "J7vQKXPpadYZnbpK6Zxofi2R74tmxT7GNSyS7hJZLf59"
+);
+
+// Pyth PriceUpdateV2 fixtures, dated the same way as the Switchboard feeds.
+export const PYTH_PRICE_FRESH = new PublicKey(
+    "3qTJLRCiRckht2H9N8jkgGgitCyPtqqX9M4yrZnC4GY5"
+);
+export const PYTH_PRICE_STALE = new PublicKey(
+    "2Cd3hpXdvmH8p68VxuD7Yu4C7oAMVSJ5snKahqxYyHqj"
+);
+export const PYTH_PRICE_WIDE_CONFIDENCE = new PublicKey(
+    "J7cCW84QuWzfUoMEie2WTDLwQ3AaL1xdToSjnS8WxBMf"
+);
+
+// Open bounty #1000 as 0.1.0 wrote it, at ["bounty", id] with 500 of a 6
+// decimal mint escrowed in its ATA, for migrate_bounty. Its creator's seed is
+// fixed here so the tests can sign for it.
+export const BOUNTY_V0 = new PublicKey(
+    "2BwFLCubzM5iCM2ugG2acFrCpRe5fPBxP1gCTmrfbWTB"
+);
+export const BOUNTY_V0_MINT = new PublicKey(
+    "14TVwKPxnMjwux5gbtbAwmPVir2zcPRNQjjifbLFsPPb"
+);
+export const BOUNTY_V0_ESCROW = new PublicKey(
+    "73L7FoLTGCC2D8NGWW4RWzc4g29qrFmrSLBByTVCQcPm"
+);
+export const BOUNTY_V0_CREATOR = Keypair.fromSeed(
+    Buffer.from(
+        "92832e0244ec837bec07ecd38b401ebc176b469aa2d04339b4ad9d7a0e913d50",
+        "hex"
+    )
+);
+export const BOUNTY_V0_REWARD = 500_000_000;
+
+// Counters of a "legacy" market written on the Borsh layout, from before
+// BountyStats was zero-copy, for migrate_stats: 7 posted, 5 submitted, 3
+// settled and 1 cancelled, with 7 USDC escrowed and 2.9 paid.
+export const LEGACY_STATS_MARKET = "legacy";
+export const LEGACY_STATS = new PublicKey(
+    "HTX28GC5ZLHN1KyRbSDc29qMxpQcM2y9SmdmqaebK2Sw"
+);
+
+// Solana Attestation Service attestations under SAS_SCHEMA, each vouching for
+// a solution hash of 0x71 bytes. The valid and expired ones were issued to
+// SAS_HOLDER, whose seed is fixed so the tests can submit as them.
+export const SAS_SCHEMA = new PublicKey(
+    "8cEuk4igaBVCue15dFbXDXx8PVM5qMRWFzmVDLiioUdx"
+);
+export const SAS_HOLDER = Keypair.fromSeed(
+    Buffer.from(
+        "0842bc18908750aba015085616cea59e660f177e005d62c5e0fccb44bc15802b",
+        "hex"
+    )
+);
+export const SAS_ATTESTATION_VALID = new PublicKey(
+    "uRuwaFP6gstBZ7rg4UUwq8RZWTKkNwh8yqDNj31x5qq"
+);
+export const SAS_ATTESTATION_WRONG_HOLDER = new PublicKey(
+    "47n9MdsqmkRyn8PSUzcTex7q4KY6uYLgY2ZzSnV8LUyW"
+);
+export const SAS_ATTESTATION_EXPIRED = new PublicKey(
+    "3ovP913w6i866dxWWJAvznjiF1SQHbWAvk6dhxRfz6gM"
+);
+
 export interface TestContext {
     provider: anchor.AnchorProvider;
     program: Program<Bountyforge>;
@@ -29,6 +126,8 @@ export async function setupTestContext(): Promise<TestContext> {
     const program = anchor.workspace.bountyforge as Program<Bountyforge>;
     const connection = provider.connection;
 
+    await ensureConfig(program, provider);
+
     const creator = Keypair.generate();
 
     const airdropSignature = await connection.requestAirdrop(
@@ -37,6 +136,12 @@ export async function setupTestContext(): Promise<TestContext> {
     );
     await connection.confirmTransaction(airdropSignature);
 
+    await program.methods
+        .initializeBountyCounter()
+        .accountsPartial({ creator: creator.publicKey })
+        .signers([creator])
+        .rpc();
+
     const usdcMint = await createMint(
         connection,
         creator,
@@ -75,39 +180,403 @@ export async function setupTestContext(): Promise<TestContext> {
 
 export function getAssociatedTokenAddressSync(
     mint: PublicKey,
-    owner: PublicKey
+    owner: PublicKey,
+    tokenProgram: PublicKey = TOKEN_PROGRAM_ID
 ): PublicKey {
     const [address] = PublicKey.findProgramAddressSync(
-        [owner.toBuffer(), TOKEN_PROGRAM_ID.toBuffer(), mint.toBuffer()],
+        [owner.toBuffer(), tokenProgram.toBuffer(), mint.toBuffer()],
         ASSOCIATED_TOKEN_PROGRAM_ID
     );
     return address;
 }
 
+// Token-2022 mint with the transfer-fee extension, `feeBasisPoints` withheld on
+// every transfer up to `maxFee`.
+export async function createTransferFeeMint(
+    connection: anchor.web3.Connection,
+    payer: Keypair,
+    decimals: number,
+    feeBasisPoints: number,
+    maxFee: bigint
+): Promise<PublicKey> {
+    const mintKeypair = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.TransferFeeConfig]);
+    const lamports = await connection.getMinimumBalanceForRentExemption(mintLen);
+
+    const tx = new Transaction().add(
+        SystemProgram.createAccount({
+            fromPubkey: payer.publicKey,
+            newAccountPubkey: mintKeypair.publicKey,
+            space: mintLen,
+            lamports,
+            programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferFeeConfigInstruction(
+            mintKeypair.publicKey,
+            payer.publicKey,
+            payer.publicKey,
+            feeBasisPoints,
+            maxFee,
+            TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(
+            mintKeypair.publicKey,
+            decimals,
+            payer.publicKey,
+            null,
+            TOKEN_2022_PROGRAM_ID
+        )
+    );
+    await sendAndConfirmTransaction(connection, tx, [payer, mintKeypair]);
+
+    return mintKeypair.publicKey;
+}
+
+// A token-2022 mint whose `delegate` can move tokens out of any account,
+// escrow ATAs included.
+export async function createPermanentDelegateMint(
+    connection: anchor.web3.Connection,
+    payer: Keypair,
+    decimals: number,
+    delegate: PublicKey
+): Promise<PublicKey> {
+    const mintKeypair = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.PermanentDelegate]);
+    const lamports = await connection.getMinimumBalanceForRentExemption(mintLen);
+
+    const tx = new Transaction().add(
+        SystemProgram.createAccount({
+            fromPubkey: payer.publicKey,
+            newAccountPubkey: mintKeypair.publicKey,
+            space: mintLen,
+            lamports,
+            programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializePermanentDelegateInstruction(
+            mintKeypair.publicKey,
+            delegate,
+            TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(
+            mintKeypair.publicKey,
+            decimals,
+            payer.publicKey,
+            null,
+            TOKEN_2022_PROGRAM_ID
+        )
+    );
+    await sendAndConfirmTransaction(connection, tx, [payer, mintKeypair]);
+
+    return mintKeypair.publicKey;
+}
+
+// A token-2022 mint whose transfers all CPI into `hookProgram`.
+export async function createTransferHookMint(
+    connection: anchor.web3.Connection,
+    payer: Keypair,
+    decimals: number,
+    hookProgram: PublicKey
+): Promise<PublicKey> {
+    const mintKeypair = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.TransferHook]);
+    const lamports = await connection.getMinimumBalanceForRentExemption(mintLen);
+
+    const tx = new Transaction().add(
+        SystemProgram.createAccount({
+            fromPubkey: payer.publicKey,
+            newAccountPubkey: mintKeypair.publicKey,
+            space: mintLen,
+            lamports,
+            programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeTransferHookInstruction(
+            mintKeypair.publicKey,
+            payer.publicKey,
+            hookProgram,
+            TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(
+            mintKeypair.publicKey,
+            decimals,
+            payer.publicKey,
+            null,
+            TOKEN_2022_PROGRAM_ID
+        )
+    );
+    await sendAndConfirmTransaction(connection, tx, [payer, mintKeypair]);
+
+    return mintKeypair.publicKey;
+}
+
+// A token-2022 mint whose new token accounts start frozen until
+// `freezeAuthority` thaws them.
+export async function createDefaultFrozenMint(
+    connection: anchor.web3.Connection,
+    payer: Keypair,
+    decimals: number,
+    freezeAuthority: PublicKey
+): Promise<PublicKey> {
+    const mintKeypair = Keypair.generate();
+    const mintLen = getMintLen([ExtensionType.DefaultAccountState]);
+    const lamports = await connection.getMinimumBalanceForRentExemption(mintLen);
+
+    const tx = new Transaction().add(
+        SystemProgram.createAccount({
+            fromPubkey: payer.publicKey,
+            newAccountPubkey: mintKeypair.publicKey,
+            space: mintLen,
+            lamports,
+            programId: TOKEN_2022_PROGRAM_ID,
+        }),
+        createInitializeDefaultAccountStateInstruction(
+            mintKeypair.publicKey,
+            AccountState.Frozen,
+            TOKEN_2022_PROGRAM_ID
+        ),
+        createInitializeMintInstruction(
+            mintKeypair.publicKey,
+            decimals,
+            payer.publicKey,
+            freezeAuthority,
+            TOKEN_2022_PROGRAM_ID
+        )
+    );
+    await sendAndConfirmTransaction(connection, tx, [payer, mintKeypair]);
+
+    return mintKeypair.publicKey;
+}
+
+export function deriveBountyCounterPda(
+    programId: PublicKey,
+    creator: PublicKey
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("counter"), creator.toBuffer()],
+        programId
+    );
+}
+
+export function deriveCreatorIndexPda(
+    programId: PublicKey,
+    creator: PublicKey
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("index"), creator.toBuffer()],
+        programId
+    );
+}
+
 export function deriveBountyPda(
     programId: PublicKey,
+    creator: PublicKey,
     bountyId: number
 ): [PublicKey, number] {
     const bountyIdBuffer = Buffer.allocUnsafe(8);
     bountyIdBuffer.writeBigUInt64LE(BigInt(bountyId), 0);
     return PublicKey.findProgramAddressSync(
-        [Buffer.from("bounty"), bountyIdBuffer],
+        [Buffer.from("bounty"), creator.toBuffer(), bountyIdBuffer],
+        programId
+    );
+}
+
+// The default market's name, an empty seed leaves its PDAs where they always were.
+export const DEFAULT_MARKET = "";
+
+export function deriveConfigPda(
+    programId: PublicKey,
+    market: string = DEFAULT_MARKET
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("config"), Buffer.from(market)],
+        programId
+    );
+}
+
+export function deriveStatsPda(
+    programId: PublicKey,
+    market: string = DEFAULT_MARKET
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("stats"), Buffer.from(market)],
+        programId
+    );
+}
+
+export function deriveLeaderboardPda(
+    programId: PublicKey
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("leaderboard")],
+        programId
+    );
+}
+
+// The default market's Config is shared by every test file, created by the
+// first setupTestContext(), with the provider wallet as admin and verifier.
+// Wide enough for every reward the tests post, in any currency.
+export const DEFAULT_MIN_REWARD = 1;
+export const DEFAULT_MAX_REWARD = new anchor.BN(10).pow(new anchor.BN(15));
+
+type InitializeConfigParams = IdlTypes<Bountyforge>["initializeConfigParams"];
+
+// Settings the test suites run under, with `admin` as treasury and verifier.
+export function defaultConfigParams(admin: PublicKey): InitializeConfigParams {
+    return {
+        feeBps: 0,
+        treasury: admin,
+        verifier: admin,
+        trustedAttestor: null,
+        attestors: [],
+        defaultChallengePeriodSlots: new anchor.BN(0),
+        challengeBondLamports: new anchor.BN(0),
+        challengeRewardBps: 0,
+        stakeSlashTreasuryBps: 0,
+        defaultSettleWindowSlots: new anchor.BN(0),
+        maxOpenPerCreator: 10,
+        minReward: new anchor.BN(DEFAULT_MIN_REWARD),
+        maxReward: DEFAULT_MAX_REWARD,
+        receiptRetentionSeconds: new anchor.BN(0),
+        attestorFeeBps: 0,
+        scoreUnit: new anchor.BN(0),
+        maxScorePerBounty: new anchor.BN(1),
+        decayPeriodSeconds: new anchor.BN(0),
+        allowSelfSettlement: false,
+        crankFeeBps: 0,
+        paymentGateway: null,
+        defaultResubmissionCooldownSeconds: new anchor.BN(0),
+        abandonmentSeconds: new anchor.BN(365 * 24 * 60 * 60),
+        trustedMints: [],
+        boostFeeBps: 0,
+        boostDurationSeconds: new anchor.BN(7 * 24 * 60 * 60),
+        attestationTtlSeconds: new anchor.BN(7 * 24 * 60 * 60),
+        attestationCloseTipLamports: new anchor.BN(0),
+        insuranceFeeBps: 0,
+        maxInsuranceTopup: new anchor.BN(0),
+        primaryMint: null,
+        appealWindowSeconds: new anchor.BN(0),
+        challengeForfeitBps: 0,
+        minVerificationFee: new anchor.BN(0),
+    };
+}
+
+export async function ensureConfig(
+    program: Program<Bountyforge>,
+    provider: anchor.AnchorProvider
+): Promise<void> {
+    const [configPda] = deriveConfigPda(program.programId);
+    if (await provider.connection.getAccountInfo(configPda)) {
+        return;
+    }
+
+    await program.methods
+        .initializeConfig(defaultConfigParams(provider.wallet.publicKey))
+        .accountsPartial({ admin: provider.wallet.publicKey })
+        .rpc();
+    await program.methods
+        .initializeStats()
+        .accountsPartial({ payer: provider.wallet.publicKey })
+        .rpc();
+    await program.methods
+        .initializeLeaderboard()
+        .accountsPartial({ payer: provider.wallet.publicKey })
+        .rpc();
+}
+
+type UpdateConfigParams = IdlTypes<Bountyforge>["updateConfigParams"];
+export const NO_CONFIG_CHANGES: UpdateConfigParams = {
+    feeBps: null,
+    treasury: null,
+    paused: null,
+    pendingAdmin: null,
+    verifier: null,
+    trustedAttestor: null,
+    attestors: null,
+    defaultChallengePeriodSlots: null,
+    challengeBondLamports: null,
+    challengeRewardBps: null,
+    stakeSlashTreasuryBps: null,
+    defaultSettleWindowSlots: null,
+    maxOpenPerCreator: null,
+    minReward: null,
+    maxReward: null,
+    receiptRetentionSeconds: null,
+    attestorFeeBps: null,
+    scoreUnit: null,
+    maxScorePerBounty: null,
+    decayPeriodSeconds: null,
+    allowSelfSettlement: null,
+    crankFeeBps: null,
+    paymentGateway: null,
+    defaultResubmissionCooldownSeconds: null,
+    abandonmentSeconds: null,
+    trustedMints: null,
+    boostFeeBps: null,
+    boostDurationSeconds: null,
+    attestationTtlSeconds: null,
+    attestationCloseTipLamports: null,
+    insuranceFeeBps: null,
+    maxInsuranceTopup: null,
+    primaryMint: null,
+    appealWindowSeconds: null,
+    challengeForfeitBps: null,
+    minVerificationFee: null,
+};
+
+// Admin-only; relies on ensureConfig() having made the provider wallet admin.
+export async function updateConfig(
+    ctx: TestContext,
+    changes: Partial<UpdateConfigParams>
+): Promise<void> {
+    await ctx.program.methods
+        .updateConfig(DEFAULT_MARKET, { ...NO_CONFIG_CHANGES, ...changes })
+        .accountsPartial({ admin: ctx.provider.wallet.publicKey })
+        .rpc();
+}
+
+export async function setFeeBps(ctx: TestContext, feeBps: number): Promise<void> {
+    await updateConfig(ctx, { feeBps });
+}
+
+export function deriveCreatorProfilePda(
+    programId: PublicKey,
+    creator: PublicKey
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("creator"), creator.toBuffer()],
         programId
     );
 }
 
 export function deriveAttestationPda(
     programId: PublicKey,
+    agent: PublicKey,
+    bounty: PublicKey,
     solutionId: number
 ): [PublicKey, number] {
     const solutionIdBuffer = Buffer.allocUnsafe(8);
     solutionIdBuffer.writeBigUInt64LE(BigInt(solutionId), 0);
     return PublicKey.findProgramAddressSync(
-        [Buffer.from("attest"), solutionIdBuffer],
+        [
+            Buffer.from("attestation"),
+            agent.toBuffer(),
+            bounty.toBuffer(),
+            solutionIdBuffer,
+        ],
         programId
     );
 }
 
+// The id attest_solution will give the agent's next attestation.
+export async function nextSolutionId(
+    ctx: TestContext,
+    agent: PublicKey
+): Promise<number> {
+    const reputation = await ctx.program.account.reputation.fetch(
+        deriveReputationPda(ctx.program.programId, agent)[0]
+    );
+    return reputation.nextSolutionId.toNumber();
+}
+
 export function deriveReputationPda(
     programId: PublicKey,
     agent: PublicKey
@@ -118,6 +587,138 @@ export function deriveReputationPda(
     );
 }
 
+// `claim` is the bounty's claims_made before the settlement, 0 unless it is repeatable
+function claimSeed(claim: number): Buffer {
+    const seed = Buffer.alloc(2);
+    seed.writeUInt16LE(claim);
+    return seed;
+}
+
+export function deriveSettlementReceiptPda(
+    programId: PublicKey,
+    bounty: PublicKey,
+    claim = 0
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("receipt"), bounty.toBuffer(), claimSeed(claim)],
+        programId
+    );
+}
+
+export function deriveSettlementRecordPda(
+    programId: PublicKey,
+    bounty: PublicKey,
+    agent: PublicKey,
+    claim = 0
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from("settlement"),
+            bounty.toBuffer(),
+            agent.toBuffer(),
+            claimSeed(claim),
+        ],
+        programId
+    );
+}
+
+export function deriveBadgeMintPda(
+    programId: PublicKey,
+    bounty: PublicKey,
+    claim = 0
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("badge"), bounty.toBuffer(), claimSeed(claim)],
+        programId
+    );
+}
+
+export function deriveBadgeAuthorityPda(programId: PublicKey): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("badge_authority")],
+        programId
+    );
+}
+
+export function deriveSubmissionPda(
+    programId: PublicKey,
+    bounty: PublicKey,
+    agent: PublicKey
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("submission"), bounty.toBuffer(), agent.toBuffer()],
+        programId
+    );
+}
+
+export function deriveRejectionPda(
+    programId: PublicKey,
+    bounty: PublicKey,
+    agent: PublicKey,
+    attemptIndex: number
+): [PublicKey, number] {
+    const attemptIndexBuffer = Buffer.allocUnsafe(4);
+    attemptIndexBuffer.writeUInt32LE(attemptIndex, 0);
+    return PublicKey.findProgramAddressSync(
+        [
+            Buffer.from("rejection"),
+            bounty.toBuffer(),
+            agent.toBuffer(),
+            attemptIndexBuffer,
+        ],
+        programId
+    );
+}
+
+// The Rejection PDA reject_solution will create for the agent's next rejection.
+export async function nextRejectionPda(
+    ctx: TestContext,
+    bounty: PublicKey,
+    agent: PublicKey
+): Promise<PublicKey> {
+    const reputation = await ctx.program.account.reputation.fetch(
+        deriveReputationPda(ctx.program.programId, agent)[0]
+    );
+    return deriveRejectionPda(
+        ctx.program.programId,
+        bounty,
+        agent,
+        reputation.rejections
+    )[0];
+}
+
+export function deriveApplicationPda(
+    programId: PublicKey,
+    bounty: PublicKey,
+    agent: PublicKey
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("application"), bounty.toBuffer(), agent.toBuffer()],
+        programId
+    );
+}
+
+export function deriveCreatorBondPda(
+    programId: PublicKey,
+    bounty: PublicKey
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("bond"), bounty.toBuffer()],
+        programId
+    );
+}
+
+export function deriveContributionPda(
+    programId: PublicKey,
+    bounty: PublicKey,
+    contributor: PublicKey
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("contrib"), bounty.toBuffer(), contributor.toBuffer()],
+        programId
+    );
+}
+
 export async function ensureCreatorBalance(
     connection: anchor.web3.Connection,
     creator: Keypair,
@@ -139,25 +740,6 @@ export async function ensureCreatorBalance(
     }
 }
 
-export async function createBountyTokenAccount(
-    connection: anchor.web3.Connection,
-    creator: Keypair,
-    usdcMint: PublicKey,
-    bountyPda: PublicKey
-): Promise<void> {
-    try {
-        await getOrCreateAssociatedTokenAccount(
-            connection,
-            creator,
-            usdcMint,
-            bountyPda,
-            true
-        );
-    } catch (e) {
-        // Account might already exist
-    }
-}
-
 export async function airdropSol(
     connection: anchor.web3.Connection,
     pubkey: PublicKey,
@@ -184,6 +766,23 @@ export async function createAgentTokenAccount(
     return accountInfo.address;
 }
 
+// Mirrors MIN_POST_INTERVAL_SECONDS in constants.rs, plus a second of slack
+// for the validator clock.
+export const POST_COOLDOWN_MS = 3000;
+
+const lastPostAt = new Map<string, number>();
+
+export async function waitForPostCooldown(creator: PublicKey): Promise<void> {
+    const last = lastPostAt.get(creator.toString());
+    if (last !== undefined) {
+        const remaining = last + POST_COOLDOWN_MS - Date.now();
+        if (remaining > 0) {
+            await sleep(remaining);
+        }
+    }
+    lastPostAt.set(creator.toString(), Date.now());
+}
+
 export function generateRandomId(): number {
     return Math.floor(Math.random() * 1000000);
 }
@@ -197,91 +796,625 @@ export function generateSolutionHashWithValue(value: number): Buffer {
 }
 
 type BountyTypeEnum = IdlTypes<Bountyforge>["bountyType"];
+type RewardCurrencyEnum = IdlTypes<Bountyforge>["rewardCurrency"];
+type OracleKindEnum = IdlTypes<Bountyforge>["oracleKind"];
+type AttestationSourceEnum = IdlTypes<Bountyforge>["attestationSource"];
+type PostBountyParams = IdlTypes<Bountyforge>["postBountyParams"];
+export type TeamShare = IdlTypes<Bountyforge>["teamShare"];
 const DEFAULT_BOUNTY_TYPE: BountyTypeEnum = { walletIntelligence: {} };
+// the wallet a WalletIntelligence bounty is about unless the test names one
+export const DEFAULT_TARGET_WALLET = new PublicKey(
+    "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU"
+);
+const DEFAULT_REWARD_CURRENCY: RewardCurrencyEnum = { spl: {} };
+const DEFAULT_ORACLE_KIND: OracleKindEnum = { switchboard: {} };
+const DEFAULT_ATTESTATION_SOURCE: AttestationSourceEnum = { internal: {} };
+export type HashAlgoEnum = IdlTypes<Bountyforge>["hashAlgo"];
+export type RejectionReasonEnum = IdlTypes<Bountyforge>["rejectionReason"];
+export const DEFAULT_REJECTION_REASON: RejectionReasonEnum = {
+    doesNotMeetCriteria: {},
+};
+export const DEFAULT_HASH_ALGO: HashAlgoEnum = { sha256: {} };
+// submit_solution's ciphertext commitment for bounties without an encryption key
+export const NO_CIPHERTEXT_HASH: number[] = new Array(32).fill(0);
+const DEFAULT_DEADLINE_SECONDS = 24 * 60 * 60;
+
+export interface PostBountyOptions {
+    bountyType?: BountyTypeEnum;
+    target?: PublicKey;
+    rewardCurrency?: RewardCurrencyEnum;
+    requiresOracle?: boolean;
+    oracleFeeds?: PublicKey[];
+    oracleKind?: OracleKindEnum;
+    oracleMinValue?: number;
+    oracleMaxValue?: number;
+    oracleExponent?: number;
+    deadline?: number;
+    maxAttestationAge?: number;
+    requiredAttestations?: number;
+    minAttestorTrackRecord?: number;
+    attestationSource?: AttestationSourceEnum;
+    requiresReveal?: boolean;
+    requiresPaymentReceipt?: boolean;
+    hashAlgo?: HashAlgoEnum;
+    encryptionPubkey?: Buffer;
+    criteriaHash?: Buffer;
+    allowCpi?: boolean;
+    competitive?: boolean;
+    maxSubmissions?: number;
+    prizes?: number[];
+    maxClaims?: number;
+    allowRepeatSolver?: boolean;
+    mintBadge?: boolean;
+    assignee?: PublicKey;
+    requiresApplication?: boolean;
+    challengePeriodSlots?: number;
+    arbiter?: PublicKey;
+    requiredStake?: number;
+    bond?: number;
+    settleWindowSlots?: number;
+    resubmissionCooldownSeconds?: number;
+    fullRewardUntil?: number;
+    decayBpsPerHour?: number;
+    decayFloorBps?: number;
+    metadataUri?: string;
+    minReputation?: number;
+    allowlist?: PublicKey[];
+    milestones?: MilestoneParams[];
+    vestingDurationSeconds?: number;
+    settlementAuthority?: PublicKey;
+    market?: string;
+    // pays the rent in the creator's place, not part of the params
+    sponsor?: Keypair;
+}
+
+export interface MilestoneParams {
+    amount: number;
+    descriptionHash: Buffer;
+}
+
+export function bountyParams(
+    description: string,
+    reward: number | anchor.BN,
+    options: PostBountyOptions = {}
+): PostBountyParams {
+    const bountyType = options.bountyType ?? DEFAULT_BOUNTY_TYPE;
+    return {
+        bountyType,
+        target:
+            options.target ??
+            ("walletIntelligence" in bountyType
+                ? DEFAULT_TARGET_WALLET
+                : PublicKey.default),
+        description,
+        reward: new anchor.BN(reward.toString()),
+        rewardCurrency: options.rewardCurrency ?? DEFAULT_REWARD_CURRENCY,
+        requiresOracle: options.requiresOracle ?? false,
+        oracleFeeds: options.oracleFeeds ?? [],
+        oracleKind: options.oracleKind ?? DEFAULT_ORACLE_KIND,
+        oracleMinValue:
+            options.oracleMinValue === undefined
+                ? null
+                : new anchor.BN(options.oracleMinValue),
+        oracleMaxValue:
+            options.oracleMaxValue === undefined
+                ? null
+                : new anchor.BN(options.oracleMaxValue),
+        oracleExponent: options.oracleExponent ?? 0,
+        deadline: new anchor.BN(
+            options.deadline ??
+                Math.floor(Date.now() / 1000) + DEFAULT_DEADLINE_SECONDS
+        ),
+        maxAttestationAge:
+            options.maxAttestationAge === undefined
+                ? null
+                : new anchor.BN(options.maxAttestationAge),
+        requiredAttestations: options.requiredAttestations ?? 0,
+        minAttestorTrackRecord: new anchor.BN(options.minAttestorTrackRecord ?? 0),
+        attestationSource: options.attestationSource ?? DEFAULT_ATTESTATION_SOURCE,
+        requiresReveal: options.requiresReveal ?? false,
+        requiresPaymentReceipt: options.requiresPaymentReceipt ?? false,
+        hashAlgo: options.hashAlgo ?? DEFAULT_HASH_ALGO,
+        encryptionPubkey: options.encryptionPubkey
+            ? Array.from(options.encryptionPubkey)
+            : null,
+        criteriaHash: Array.from(options.criteriaHash ?? Buffer.alloc(32)),
+        allowCpi: options.allowCpi ?? false,
+        competitive: options.competitive ?? false,
+        maxSubmissions: options.maxSubmissions ?? 0,
+        prizes: (options.prizes ?? []).map((prize) => new anchor.BN(prize)),
+        maxClaims: options.maxClaims ?? 1,
+        allowRepeatSolver: options.allowRepeatSolver ?? false,
+        mintBadge: options.mintBadge ?? false,
+        assignee: options.assignee ?? null,
+        requiresApplication: options.requiresApplication ?? false,
+        challengePeriodSlots:
+            options.challengePeriodSlots === undefined
+                ? null
+                : new anchor.BN(options.challengePeriodSlots),
+        arbiter: options.arbiter ?? null,
+        requiredStake: new anchor.BN(options.requiredStake ?? 0),
+        bond: new anchor.BN(options.bond ?? 0),
+        settleWindowSlots:
+            options.settleWindowSlots === undefined
+                ? null
+                : new anchor.BN(options.settleWindowSlots),
+        resubmissionCooldownSeconds:
+            options.resubmissionCooldownSeconds === undefined
+                ? null
+                : new anchor.BN(options.resubmissionCooldownSeconds),
+        fullRewardUntil: new anchor.BN(options.fullRewardUntil ?? 0),
+        decayBpsPerHour: options.decayBpsPerHour ?? 0,
+        decayFloorBps: options.decayFloorBps ?? 0,
+        metadataUri: options.metadataUri ?? "",
+        minReputation: new anchor.BN(options.minReputation ?? 0),
+        allowlist: options.allowlist ?? [],
+        milestones: (options.milestones ?? []).map((milestone) => ({
+            amount: new anchor.BN(milestone.amount),
+            descriptionHash: Array.from(milestone.descriptionHash),
+        })),
+        vestingDurationSeconds: new anchor.BN(options.vestingDurationSeconds ?? 0),
+        settlementAuthority: options.settlementAuthority ?? null,
+        market: options.market ?? DEFAULT_MARKET,
+    };
+}
+
+// The PDA and id post_bounty will assign to the creator's next bounty.
+export async function nextBountyPda(
+    ctx: TestContext
+): Promise<[PublicKey, number]> {
+    const [counterPda] = deriveBountyCounterPda(
+        ctx.program.programId,
+        ctx.creator.publicKey
+    );
+    const counter = await ctx.program.account.bountyCounter.fetch(counterPda);
+    const bountyId = counter.nextId.toNumber();
+    const [bountyPda] = deriveBountyPda(
+        ctx.program.programId,
+        ctx.creator.publicKey,
+        bountyId
+    );
+    return [bountyPda, bountyId];
+}
 
 export async function postBounty(
     ctx: TestContext,
-    bountyId: number,
     description: string,
-    reward: number,
-    bountyType: BountyTypeEnum = DEFAULT_BOUNTY_TYPE
+    reward: number | anchor.BN,
+    options: PostBountyOptions = {}
 ): Promise<anchor.web3.PublicKey> {
-    const [bountyPda] = deriveBountyPda(ctx.program.programId, bountyId);
-    const bountyTokenAccount = getAssociatedTokenAddressSync(
-        ctx.usdcMint,
-        bountyPda
-    );
+    const [bountyPda] = await nextBountyPda(ctx);
+    const isSol = options.rewardCurrency !== undefined && "sol" in options.rewardCurrency;
+    const bountyTokenAccount = isSol
+        ? null
+        : getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda);
 
-    await createBountyTokenAccount(
-        ctx.connection,
-        ctx.creator,
-        ctx.usdcMint,
-        bountyPda
-    );
+    await waitForPostCooldown(ctx.creator.publicKey);
 
     await ctx.program.methods
-        .postBounty(new anchor.BN(bountyId), bountyType, description, new anchor.BN(reward))
+        .postBounty(bountyParams(description, reward, options))
         .accountsPartial({
             creator: ctx.creator.publicKey,
+            sponsor: options.sponsor?.publicKey ?? null,
             bounty: bountyPda,
-            usdcMint: ctx.usdcMint,
-            creatorTokenAccount: ctx.creatorTokenAccount,
+            creatorBond: options.bond
+                ? deriveCreatorBondPda(ctx.program.programId, bountyPda)[0]
+                : null,
+            rewardMint: isSol ? null : ctx.usdcMint,
+            targetAccount: options.target ?? null,
+            creatorTokenAccount: isSol ? null : ctx.creatorTokenAccount,
             bountyTokenAccount: bountyTokenAccount,
             tokenProgram: TOKEN_PROGRAM_ID,
             systemProgram: SystemProgram.programId,
         })
-        .signers([ctx.creator])
+        .signers(options.sponsor ? [ctx.creator, options.sponsor] : [ctx.creator])
         .rpc();
 
     return bountyPda;
 }
 
-export async function createAttestation(
+// Decodes the Anchor events a confirmed transaction emitted, in log order.
+export async function fetchEvents(
     ctx: TestContext,
-    agent: Keypair,
+    signature: string
+): Promise<anchor.Event[]> {
+    await ctx.connection.confirmTransaction(signature, "confirmed");
+    const tx = await ctx.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(
+        ctx.program.programId,
+        new anchor.BorshCoder(ctx.program.idl)
+    );
+    return Array.from(parser.parseLogs(tx.meta.logMessages));
+}
+
+export function findEvent(events: anchor.Event[], name: string): anchor.Event {
+    // IDL event names keep their Rust casing, compare loosely
+    const event = events.find(
+        (e) => e.name.toLowerCase() === name.toLowerCase()
+    );
+    if (!event) {
+        throw new Error(`Event ${name} not found`);
+    }
+    return event;
+}
+
+export function sleep(ms: number): Promise<void> {
+    return new Promise((resolve) => setTimeout(resolve, ms));
+}
+
+// unix_timestamp of the Clock sysvar, i.e. what the program sees as `now`
+export async function getClockUnixTimestamp(
+    connection: anchor.web3.Connection
+): Promise<number> {
+    const clock = await connection.getAccountInfo(SYSVAR_CLOCK_PUBKEY);
+    return Number(clock.data.readBigInt64LE(32));
+}
+
+export async function waitForClock(
+    connection: anchor.web3.Connection,
+    unixTimestamp: number
+): Promise<void> {
+    while ((await getClockUnixTimestamp(connection)) < unixTimestamp) {
+        await sleep(200);
+    }
+}
+
+export async function waitForSlot(
+    connection: anchor.web3.Connection,
+    slot: number
+): Promise<void> {
+    while ((await connection.getSlot("processed")) < slot) {
+        await sleep(100);
+    }
+}
+
+// What an attestor signs: agent || solution_id (u64 LE) || solution_hash
+export function attestationMessage(
+    agent: PublicKey,
     solutionId: number,
     solutionHash: Buffer
+): Buffer {
+    const solutionIdBuffer = Buffer.alloc(8);
+    solutionIdBuffer.writeBigUInt64LE(BigInt(solutionId));
+    return Buffer.concat([agent.toBuffer(), solutionIdBuffer, solutionHash]);
+}
+
+export function paymentReceiptMessage(
+    bountyPda: PublicKey,
+    solutionHash: Buffer,
+    amountPaid: number
+): Buffer {
+    const amountBuffer = Buffer.alloc(8);
+    amountBuffer.writeBigUInt64LE(BigInt(amountPaid));
+    return Buffer.concat([bountyPda.toBuffer(), solutionHash, amountBuffer]);
+}
+
+export async function createAttestation(
+    ctx: TestContext,
+    agent: Keypair,
+    bountyPda: PublicKey,
+    solutionHash: Buffer,
+    verify = true,
+    attestor: Keypair | null = null,
+    hashAlgo: HashAlgoEnum = DEFAULT_HASH_ALGO
 ): Promise<anchor.web3.PublicKey> {
+    // attestations take their id from the agent's reputation
+    await ensureReputation(ctx, agent);
+    // the attestor is registered in the bounty's market, which tests of a
+    // missing bounty leave at the default
+    const market =
+        (await ctx.program.account.bounty.fetchNullable(bountyPda))?.market ??
+        DEFAULT_MARKET;
+    const solutionId = await nextSolutionId(ctx, agent.publicKey);
     const [attestationPda] = deriveAttestationPda(
         ctx.program.programId,
+        agent.publicKey,
+        bountyPda,
         solutionId
     );
 
     await ctx.program.methods
-        .attestSolution(new anchor.BN(solutionId), Array.from(solutionHash))
+        .attestSolution(Array.from(solutionHash), hashAlgo)
         .accountsPartial({
             agent: agent.publicKey,
+            bounty: bountyPda,
             attestation: attestationPda,
+            instructionsSysvar: attestor ? SYSVAR_INSTRUCTIONS_PUBKEY : null,
             systemProgram: SystemProgram.programId,
         })
+        .preInstructions([
+            await registerAttestorIx(ctx, agent.publicKey, market),
+            ...(attestor
+                ? [
+                      Ed25519Program.createInstructionWithPrivateKey({
+                          privateKey: attestor.secretKey,
+                          message: attestationMessage(
+                              agent.publicKey,
+                              solutionId,
+                              solutionHash
+                          ),
+                      }),
+                  ]
+                : []),
+        ])
+        .postInstructions(
+            verify
+                ? [await verifyAttestationIx(ctx, attestationPda, market)]
+                : []
+        )
         .signers([agent])
         .rpc();
 
     return attestationPda;
 }
 
+// Admin-only. Registering an existing attestor again just reactivates it, so
+// createAttestation() prepends this for every agent.
+export async function registerAttestorIx(
+    ctx: TestContext,
+    attestor: PublicKey,
+    market: string = DEFAULT_MARKET,
+    name = "test attestor"
+): Promise<anchor.web3.TransactionInstruction> {
+    return ctx.program.methods
+        .registerAttestor(market, attestor, name)
+        .accountsPartial({ admin: ctx.provider.wallet.publicKey })
+        .instruction();
+}
+
+export function deriveAttestorPda(
+    programId: PublicKey,
+    attestor: PublicKey,
+    market: string = DEFAULT_MARKET
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("attestor"), Buffer.from(market), attestor.toBuffer()],
+        programId
+    );
+}
+
+export function deriveFundingPoolPda(
+    programId: PublicKey,
+    creator: PublicKey,
+    mint: PublicKey
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("pool"), creator.toBuffer(), mint.toBuffer()],
+        programId
+    );
+}
+
+export function deriveInsuranceFundPda(
+    programId: PublicKey,
+    mint: PublicKey,
+    market: string = DEFAULT_MARKET
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("insurance"), Buffer.from(market), mint.toBuffer()],
+        programId
+    );
+}
+
+export function deriveAgentFlagPda(
+    programId: PublicKey,
+    agent: PublicKey,
+    market: string = DEFAULT_MARKET
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("flag"), Buffer.from(market), agent.toBuffer()],
+        programId
+    );
+}
+
+export function deriveOperatorPda(
+    programId: PublicKey,
+    agent: PublicKey,
+    operator: PublicKey
+): [PublicKey, number] {
+    return PublicKey.findProgramAddressSync(
+        [Buffer.from("operator"), agent.toBuffer(), operator.toBuffer()],
+        programId
+    );
+}
+
+// The provider wallet is the config verifier, and co-signs as fee payer. The
+// config is passed since the attestation may not exist yet to resolve it from.
+export async function verifyAttestationIx(
+    ctx: TestContext,
+    attestationPda: PublicKey,
+    market: string = DEFAULT_MARKET
+): Promise<anchor.web3.TransactionInstruction> {
+    return ctx.program.methods
+        .verifyAttestation()
+        .accountsPartial({
+            verifier: ctx.provider.wallet.publicKey,
+            attestation: attestationPda,
+            config: deriveConfigPda(ctx.program.programId, market)[0],
+        })
+        .instruction();
+}
+
+export async function initReputation(
+    ctx: TestContext,
+    agent: Keypair,
+    sponsor: Keypair | null = null
+): Promise<void> {
+    await ctx.program.methods
+        .initReputation()
+        .accountsPartial({
+            agent: agent.publicKey,
+            sponsor: sponsor?.publicKey ?? null,
+        })
+        .signers(sponsor ? [agent, sponsor] : [agent])
+        .rpc();
+}
+
+// What clients do before a submission: create the reputation unless it exists.
+export async function ensureReputation(
+    ctx: TestContext,
+    agent: Keypair
+): Promise<void> {
+    const [reputationPda] = deriveReputationPda(
+        ctx.program.programId,
+        agent.publicKey
+    );
+    if (!(await ctx.connection.getAccountInfo(reputationPda))) {
+        await initReputation(ctx, agent);
+    }
+}
+
 export async function submitSolution(
     ctx: TestContext,
     agent: Keypair,
     bountyPda: anchor.web3.PublicKey,
     attestationPda: anchor.web3.PublicKey,
-    solutionHash: Buffer
-): Promise<void> {
+    solutionHash: Buffer,
+    oracleFeeds: anchor.web3.PublicKey[] = [],
+    otherAttestations: anchor.web3.PublicKey[] = [],
+    application: anchor.web3.PublicKey | null = null,
+    solutionUri: string = "",
+    team: TeamShare[] = [],
+    ciphertextHash: Buffer | null = null
+): Promise<string> {
     const [reputationPda] = deriveReputationPda(
         ctx.program.programId,
         agent.publicKey
     );
+    await ensureReputation(ctx, agent);
 
-    await ctx.program.methods
-        .submitSolution(Array.from(solutionHash))
+    return ctx.program.methods
+        .submitSolution(
+            Array.from(solutionHash),
+            ciphertextHash ? Array.from(ciphertextHash) : NO_CIPHERTEXT_HASH,
+            solutionUri,
+            team
+        )
         .accountsPartial({
+            submitter: agent.publicKey,
             agent: agent.publicKey,
+            operatorRecord: null,
             bounty: bountyPda,
             attestation: attestationPda,
             reputation: reputationPda,
+            application,
             systemProgram: SystemProgram.programId,
         })
+        // counted co-attestations are marked consumed, so they go in writable
+        .remainingAccounts([
+            ...oracleFeeds.map((pubkey) => ({
+                pubkey,
+                isSigner: false,
+                isWritable: false,
+            })),
+            ...otherAttestations.map((pubkey) => ({
+                pubkey,
+                isSigner: false,
+                isWritable: true,
+            })),
+        ])
         .signers([agent])
         .rpc();
 }
 
+
+export async function settleBounty(
+    ctx: TestContext,
+    agent: PublicKey,
+    bountyPda: PublicKey,
+    agentTokenAccount: PublicKey,
+    memberAccounts: PublicKey[] = []
+): Promise<string> {
+    const [reputationPda] = deriveReputationPda(ctx.program.programId, agent);
+
+    return ctx.program.methods
+        .settleBounty()
+        .accountsPartial({
+            creator: ctx.creator.publicKey,
+            bounty: bountyPda,
+            reputation: reputationPda,
+            agent,
+            agentTokenAccount,
+            bountyTokenAccount: getAssociatedTokenAddressSync(
+                ctx.usdcMint,
+                bountyPda
+            ),
+            rewardMint: ctx.usdcMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        })
+        .remainingAccounts(
+            memberAccounts.map((pubkey) => ({
+                pubkey,
+                isSigner: false,
+                isWritable: true,
+            }))
+        )
+        .signers([ctx.creator])
+        .rpc();
+}
+
+export async function contributeToBounty(
+    ctx: TestContext,
+    contributor: Keypair,
+    contributorTokenAccount: PublicKey,
+    bountyPda: PublicKey,
+    amount: number
+): Promise<string> {
+    return ctx.program.methods
+        .contributeToBounty(new anchor.BN(amount))
+        .accountsPartial({
+            contributor: contributor.publicKey,
+            bounty: bountyPda,
+            contributorTokenAccount,
+            bountyTokenAccount: getAssociatedTokenAddressSync(
+                ctx.usdcMint,
+                bountyPda
+            ),
+            rewardMint: ctx.usdcMint,
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+        })
+        .signers([contributor])
+        .rpc();
+}
+
+export async function selectWinner(
+    ctx: TestContext,
+    bountyPda: PublicKey,
+    submissionPda: PublicKey
+): Promise<void> {
+    await ctx.program.methods
+        .selectWinner()
+        .accountsPartial({
+            creator: ctx.creator.publicKey,
+            bounty: bountyPda,
+            submission: submissionPda,
+        })
+        .signers([ctx.creator])
+        .rpc();
+}
+
+// Returns the Rejection PDA recording it.
+export async function rejectSolution(
+    ctx: TestContext,
+    agent: PublicKey,
+    bountyPda: PublicKey,
+    reason: RejectionReasonEnum = DEFAULT_REJECTION_REASON,
+    noteHash: Buffer | null = null
+): Promise<PublicKey> {
+    const rejection = await nextRejectionPda(ctx, bountyPda, agent);
+    await ctx.program.methods
+        .rejectSolution(reason, noteHash ? Array.from(noteHash) : null)
+        .accountsPartial({
+            creator: ctx.creator.publicKey,
+            bounty: bountyPda,
+            agent,
+            rejection,
+        })
+        .signers([ctx.creator])
+        .rpc();
+    return rejection;
+}
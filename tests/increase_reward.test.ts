@@ -0,0 +1,122 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  fetchEvents,
+  findEvent,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("increase_reward", () => {
+  const reward = 10 * 10 ** 6;
+  const additional = 5 * 10 ** 6;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let bountyPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    bountyPda = await postBounty(ctx, "Top-up bounty", reward);
+  });
+
+  async function tokenBalance(account: anchor.web3.PublicKey): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  async function increaseReward(amount: number) {
+    return ctx.program.methods
+      .increaseReward(new anchor.BN(amount))
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function submit() {
+    const hash = generateSolutionHashWithValue(0xa5);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+  }
+
+  it("Tops up the escrow and pays the increased reward on settlement", async () => {
+    const signature = await increaseReward(additional);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.reward.toNumber()).to.equal(reward + additional);
+    expect(bountyAccount.escrowAmount.toNumber()).to.equal(reward + additional);
+    expect(
+      await tokenBalance(getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda))
+    ).to.equal(reward + additional);
+
+    const event = findEvent(
+      await fetchEvents(ctx, signature),
+      "RewardIncreased"
+    );
+    expect(event.data.oldReward.toNumber()).to.equal(reward);
+    expect(event.data.newReward.toNumber()).to.equal(reward + additional);
+
+    await submit();
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+    expect(await tokenBalance(agentTokenAccount)).to.equal(reward + additional);
+  });
+
+  it("Fails to top up after a solution is submitted", async () => {
+    await submit();
+
+    try {
+      await increaseReward(additional);
+      expect.fail("Should have failed - reward locked");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RewardLocked");
+    }
+  });
+
+  it("Fails to top up by zero", async () => {
+    try {
+      await increaseReward(0);
+      expect.fail("Should have failed - zero top-up");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ZeroTopUp");
+    }
+  });
+});
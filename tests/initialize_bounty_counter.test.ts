@@ -0,0 +1,94 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, LAMPORTS_PER_SOL, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveBountyCounterPda,
+  deriveBountyPda,
+  airdropSol,
+  bountyParams,
+  TestContext,
+} from "./helpers";
+
+describe("initialize_bounty_counter", () => {
+  let ctx: TestContext;
+  let creator: Keypair;
+  let counterPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    creator = Keypair.generate();
+    await airdropSol(ctx.connection, creator.publicKey);
+    [counterPda] = deriveBountyCounterPda(
+      ctx.program.programId,
+      creator.publicKey
+    );
+  });
+
+  async function initializeCounter() {
+    await ctx.program.methods
+      .initializeBountyCounter()
+      .accountsPartial({ creator: creator.publicKey })
+      .signers([creator])
+      .rpc();
+  }
+
+  it("Starts a creator's ids at 1", async () => {
+    await initializeCounter();
+
+    const counter = await ctx.program.account.bountyCounter.fetch(counterPda);
+    expect(counter.creator.toString()).to.equal(creator.publicKey.toString());
+    expect(counter.nextId.toNumber()).to.equal(1);
+  });
+
+  it("Fails to initialize the counter twice", async () => {
+    await initializeCounter();
+
+    try {
+      await initializeCounter();
+      expect.fail("Should have failed - counter already initialized");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal(
+        "BountyCounterAlreadyInitialized"
+      );
+    }
+  });
+
+  it("Fails to post before the counter is initialized", async () => {
+    const [bountyPda] = deriveBountyPda(
+      ctx.program.programId,
+      creator.publicKey,
+      1
+    );
+
+    try {
+      await ctx.program.methods
+        .postBounty(
+          bountyParams("No counter yet", LAMPORTS_PER_SOL / 10, {
+            rewardCurrency: { sol: {} },
+          })
+        )
+        .accountsPartial({
+          creator: creator.publicKey,
+          bountyCounter: counterPda,
+          bounty: bountyPda,
+          creatorBond: null,
+          rewardMint: null,
+          creatorTokenAccount: null,
+          bountyTokenAccount: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([creator])
+        .rpc();
+      expect.fail("Should have failed - counter not initialized");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+      expect(err.error.origin).to.equal("bounty_counter");
+    }
+  });
+});
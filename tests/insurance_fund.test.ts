@@ -0,0 +1,359 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  burn,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  DEFAULT_MARKET,
+  setupTestContext,
+  nextBountyPda,
+  deriveCreatorProfilePda,
+  deriveInsuranceFundPda,
+  deriveReputationPda,
+  deriveSettlementReceiptPda,
+  deriveSettlementRecordPda,
+  deriveSubmissionPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  bountyParams,
+  createAttestation,
+  createPermanentDelegateMint,
+  submitSolution,
+  updateConfig,
+  waitForPostCooldown,
+  generateSolutionHashWithValue,
+  fetchEvents,
+  findEvent,
+  TestContext,
+} from "./helpers";
+
+describe("insurance_fund", () => {
+  const reward = 10 * 10 ** 6;
+  const feeBps = 250;
+  const insuranceFeeBps = 2_000;
+  const maxInsuranceTopup = 5;
+  const tokenProgram = TOKEN_2022_PROGRAM_ID;
+
+  let ctx: TestContext;
+  // the creator is the mint's permanent delegate, so it can skim an escrow
+  let mint: PublicKey;
+  let creatorTokenAccount: PublicKey;
+  // the admin is also the treasury
+  let adminTokenAccount: PublicKey;
+  let fundPda: PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    mint = await createPermanentDelegateMint(
+      ctx.connection,
+      ctx.creator,
+      6,
+      ctx.creator.publicKey
+    );
+    await updateConfig(ctx, {
+      trustedMints: [mint],
+      feeBps,
+      insuranceFeeBps,
+      maxInsuranceTopup: new anchor.BN(maxInsuranceTopup),
+    });
+
+    creatorTokenAccount = await fundedAccount(ctx.creator.publicKey);
+    adminTokenAccount = await fundedAccount(ctx.provider.wallet.publicKey);
+    [fundPda] = deriveInsuranceFundPda(ctx.program.programId, mint);
+  });
+
+  after(async () => {
+    await updateConfig(ctx, {
+      trustedMints: [],
+      feeBps: 0,
+      insuranceFeeBps: 0,
+      maxInsuranceTopup: new anchor.BN(0),
+    });
+  });
+
+  async function fundedAccount(owner: PublicKey): Promise<PublicKey> {
+    const account = (
+      await getOrCreateAssociatedTokenAccount(
+        ctx.connection,
+        ctx.creator,
+        mint,
+        owner,
+        false,
+        undefined,
+        undefined,
+        tokenProgram
+      )
+    ).address;
+    await mintTo(
+      ctx.connection,
+      ctx.creator,
+      mint,
+      account,
+      ctx.creator,
+      10 * reward,
+      [],
+      undefined,
+      tokenProgram
+    );
+    return account;
+  }
+
+  async function balanceOf(account: PublicKey): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  function fundTokenAccount(): PublicKey {
+    return getAssociatedTokenAddressSync(mint, fundPda, tokenProgram);
+  }
+
+  function deposit(amount: number) {
+    return ctx.program.methods
+      .depositInsurance(DEFAULT_MARKET, new anchor.BN(amount))
+      .accountsPartial({
+        admin: ctx.provider.wallet.publicKey,
+        adminTokenAccount,
+        rewardMint: mint,
+        tokenProgram,
+      })
+      .rpc();
+  }
+
+  function withdraw(amount: number) {
+    return ctx.program.methods
+      .withdrawInsurance(DEFAULT_MARKET, new anchor.BN(amount))
+      .accountsPartial({
+        admin: ctx.provider.wallet.publicKey,
+        insuranceFund: fundPda,
+        insuranceTokenAccount: fundTokenAccount(),
+        adminTokenAccount,
+        rewardMint: mint,
+        tokenProgram,
+      })
+      .rpc();
+  }
+
+  // posts and submits a bounty in `mint`, then skims `shortfall` off its escrow
+  async function shortBounty(shortfall: number) {
+    const [bountyPda] = await nextBountyPda(ctx);
+    const bountyTokenAccount = getAssociatedTokenAddressSync(
+      mint,
+      bountyPda,
+      tokenProgram
+    );
+    await waitForPostCooldown(ctx.creator.publicKey);
+    await ctx.program.methods
+      .postBounty(
+        bountyParams("Insured audit", reward, { challengePeriodSlots: 0 })
+      )
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        rewardMint: mint,
+        creatorTokenAccount,
+        bountyTokenAccount,
+        tokenProgram,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const hash = generateSolutionHashWithValue(0x1f);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    const agentTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        ctx.connection,
+        ctx.creator,
+        mint,
+        agent.publicKey,
+        false,
+        undefined,
+        undefined,
+        tokenProgram
+      )
+    ).address;
+
+    if (shortfall > 0) {
+      await burn(
+        ctx.connection,
+        ctx.creator,
+        bountyTokenAccount,
+        mint,
+        ctx.creator,
+        shortfall,
+        [],
+        undefined,
+        tokenProgram
+      );
+    }
+    return { bountyPda, bountyTokenAccount, agent, agentTokenAccount };
+  }
+
+  function settle(
+    {
+      bountyPda,
+      bountyTokenAccount,
+      agent,
+      agentTokenAccount,
+    }: Awaited<ReturnType<typeof shortBounty>>,
+    insured = true
+  ) {
+    return ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        agent: agent.publicKey,
+        agentTokenAccount,
+        bountyTokenAccount,
+        treasury: ctx.provider.wallet.publicKey,
+        treasuryTokenAccount: adminTokenAccount,
+        attestor: null,
+        attestorTokenAccount: null,
+        insuranceFund: insured ? fundPda : null,
+        insuranceTokenAccount: insured ? fundTokenAccount() : null,
+        rewardMint: mint,
+        tokenProgram,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  it("Creates the mint's fund on the first deposit", async () => {
+    const sig = await deposit(1_000);
+
+    const fund = await ctx.program.account.insuranceFund.fetch(fundPda);
+    expect(fund.mint.toString()).to.equal(mint.toString());
+    expect(fund.balance.toNumber()).to.equal(1_000);
+    expect(fund.totalDeposited.toNumber()).to.equal(1_000);
+    expect(await balanceOf(fundTokenAccount())).to.equal(1_000);
+
+    const event = findEvent(
+      await fetchEvents(ctx, sig),
+      "InsuranceFundUpdated"
+    );
+    expect(event.data.balance.toNumber()).to.equal(1_000);
+  });
+
+  it("Tops up a slightly short escrow and takes its share of the fee", async () => {
+    const before = await ctx.program.account.insuranceFund.fetch(fundPda);
+    const work = await shortBounty(1);
+
+    const sig = await settle(work);
+
+    const fee = (reward * feeBps) / 10_000;
+    const insuranceFee = (fee * insuranceFeeBps) / 10_000;
+    expect(await balanceOf(work.agentTokenAccount)).to.equal(reward - fee);
+    const settled = findEvent(await fetchEvents(ctx, sig), "BountySettled");
+    expect(settled.data.insuranceTopup.toNumber()).to.equal(1);
+
+    const fund = await ctx.program.account.insuranceFund.fetch(fundPda);
+    expect(fund.totalTopups.toNumber()).to.equal(
+      before.totalTopups.toNumber() + 1
+    );
+    expect(fund.totalFees.toNumber()).to.equal(
+      before.totalFees.toNumber() + insuranceFee
+    );
+    expect(fund.balance.toNumber()).to.equal(
+      before.balance.toNumber() - 1 + insuranceFee
+    );
+    expect(await balanceOf(fundTokenAccount())).to.equal(
+      fund.balance.toNumber()
+    );
+  });
+
+  it("Still refuses a shortfall above max_insurance_topup, or with no fund", async () => {
+    const large = await shortBounty(maxInsuranceTopup + 1);
+    try {
+      await settle(large);
+      expect.fail("Should have failed - shortfall above the cap");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("EscrowUnderfunded");
+    }
+
+    const uninsured = await shortBounty(1);
+    try {
+      await settle(uninsured, false);
+      expect.fail("Should have failed - no insurance fund passed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("EscrowUnderfunded");
+    }
+  });
+
+  it("Tops up and takes its share in a batch settlement too", async () => {
+    const before = await ctx.program.account.insuranceFund.fetch(fundPda);
+    const { bountyPda, bountyTokenAccount, agent, agentTokenAccount } =
+      await shortBounty(1);
+    const programId = ctx.program.programId;
+
+    const sig = await ctx.program.methods
+      .settleBounties()
+      .accountsPartial({
+        settler: ctx.creator.publicKey,
+        creatorProfile: deriveCreatorProfilePda(
+          programId,
+          ctx.creator.publicKey
+        )[0],
+        treasury: ctx.provider.wallet.publicKey,
+        treasuryTokenAccount: adminTokenAccount,
+        insuranceFund: fundPda,
+        insuranceTokenAccount: fundTokenAccount(),
+        rewardMint: mint,
+        tokenProgram,
+      })
+      .remainingAccounts(
+        [
+          bountyPda,
+          deriveSubmissionPda(programId, bountyPda, agent.publicKey)[0],
+          deriveReputationPda(programId, agent.publicKey)[0],
+          agent.publicKey,
+          agentTokenAccount,
+          bountyTokenAccount,
+          deriveSettlementRecordPda(programId, bountyPda, agent.publicKey)[0],
+          deriveSettlementReceiptPda(programId, bountyPda)[0],
+          programId,
+        ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }))
+      )
+      .signers([ctx.creator])
+      .rpc();
+
+    const fee = (reward * feeBps) / 10_000;
+    const insuranceFee = (fee * insuranceFeeBps) / 10_000;
+    expect(await balanceOf(agentTokenAccount)).to.equal(reward - fee);
+    const settled = findEvent(await fetchEvents(ctx, sig), "BountySettled");
+    expect(settled.data.insuranceTopup.toNumber()).to.equal(1);
+
+    const fund = await ctx.program.account.insuranceFund.fetch(fundPda);
+    expect(fund.balance.toNumber()).to.equal(
+      before.balance.toNumber() - 1 + insuranceFee
+    );
+  });
+
+  it("Lets the admin withdraw up to the fund's balance", async () => {
+    const { balance } = await ctx.program.account.insuranceFund.fetch(fundPda);
+    try {
+      await withdraw(balance.toNumber() + 1);
+      expect.fail("Should have failed - more than the fund holds");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InsuranceFundInsufficient");
+    }
+
+    const adminBefore = await balanceOf(adminTokenAccount);
+    await withdraw(balance.toNumber());
+
+    const fund = await ctx.program.account.insuranceFund.fetch(fundPda);
+    expect(fund.balance.toNumber()).to.equal(0);
+    expect(fund.totalWithdrawn.toNumber()).to.equal(balance.toNumber());
+    expect(await balanceOf(adminTokenAccount)).to.equal(
+      adminBefore + balance.toNumber()
+    );
+  });
+});
@@ -9,10 +9,12 @@ import {
   airdropSol,
   createAgentTokenAccount,
   ensureCreatorBalance,
+  initReputation,
   postBounty,
   createAttestation,
   submitSolution,
-  generateRandomId,
+  nextSolutionId,
+  nextBountyPda,
   generateSolutionHashWithValue,
   TestContext,
 } from "./helpers";
@@ -29,6 +31,7 @@ describe("integration", () => {
 
     agent = Keypair.generate();
     await airdropSol(ctx.connection, agent.publicKey);
+    await initReputation(ctx, agent);
     agentTokenAccount = await createAgentTokenAccount(
       ctx.connection,
       agent,
@@ -45,11 +48,11 @@ describe("integration", () => {
       200 * 10 ** 6
     );
 
-    const bountyId = generateRandomId();
+    const [, bountyId] = await nextBountyPda(ctx);
     const reward = 150 * 10 ** 6;
     const description = "Solve this complex problem";
 
-    const bountyPda = await postBounty(ctx, bountyId, description, reward);
+    const bountyPda = await postBounty(ctx, description, reward);
 
     const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
     expect(bountyAccount.id.toNumber()).to.equal(bountyId);
@@ -67,13 +70,13 @@ describe("integration", () => {
     );
     expect(bountyBalance.value.amount).to.equal(reward.toString());
 
-    const solutionId = generateRandomId();
+    const solutionId = await nextSolutionId(ctx, agent.publicKey);
     const solutionHash = generateSolutionHashWithValue(0x42);
 
     const attestationPda = await createAttestation(
       ctx,
       agent,
-      solutionId,
+      bountyPda,
       solutionHash
     );
 
@@ -127,7 +130,7 @@ describe("integration", () => {
         agent: agent.publicKey,
         agentTokenAccount: agentTokenAccount,
         bountyTokenAccount: bountyTokenAccount,
-        usdcMint: ctx.usdcMint,
+        rewardMint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
@@ -168,11 +171,9 @@ describe("integration", () => {
       200 * 10 ** 6
     );
 
-    const bountyId = generateRandomId();
     const reward = 100 * 10 ** 6;
     const bountyPda = await postBounty(
       ctx,
-      bountyId,
       "Test bounty for negative flow",
       reward
     );
@@ -200,7 +201,7 @@ describe("integration", () => {
           agent: agent.publicKey,
           agentTokenAccount: agentTokenAccount,
           bountyTokenAccount: bountyTokenAccount,
-          usdcMint: ctx.usdcMint,
+          rewardMint: ctx.usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -212,14 +213,13 @@ describe("integration", () => {
       expect(err).to.exist;
     }
 
-    const solutionId = generateRandomId();
     const solutionHash = generateSolutionHashWithValue(0x99);
     const wrongSolutionHash = generateSolutionHashWithValue(0x88);
 
     const attestationPda = await createAttestation(
       ctx,
       agent,
-      solutionId,
+      bountyPda,
       solutionHash
     );
 
@@ -240,12 +240,11 @@ describe("integration", () => {
     const differentAgent = Keypair.generate();
     await airdropSol(ctx.connection, differentAgent.publicKey);
 
-    const solutionId2 = generateRandomId();
     const solutionHash2 = generateSolutionHashWithValue(0x77);
     const attestationPda2 = await createAttestation(
       ctx,
       differentAgent,
-      solutionId2,
+      bountyPda,
       solutionHash2
     );
 
@@ -278,7 +277,7 @@ describe("integration", () => {
           agent: agent.publicKey,
           agentTokenAccount: agentTokenAccount,
           bountyTokenAccount: bountyTokenAccount,
-          usdcMint: ctx.usdcMint,
+          rewardMint: ctx.usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -293,5 +292,71 @@ describe("integration", () => {
     const bountyAccountFinal = await ctx.program.account.bounty.fetch(bountyPda);
     expect(bountyAccountFinal.status).to.deep.equal({ submitted: {} });
   });
-});
 
+  it("Posts and pays out a native SOL bounty end to end", async () => {
+    const reward = 0.5 * anchor.web3.LAMPORTS_PER_SOL;
+    const bountyPda = await postBounty(
+      ctx,
+      "SOL-denominated wallet analysis",
+      reward,
+      { rewardCurrency: { sol: {} } }
+    );
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.rewardCurrency).to.deep.equal({ sol: {} });
+    expect(bountyAccount.rewardMint.toString()).to.equal(
+      anchor.web3.PublicKey.default.toString()
+    );
+
+    const bountyInfo = await ctx.connection.getAccountInfo(bountyPda);
+    const rentFloor =
+      await ctx.connection.getMinimumBalanceForRentExemption(
+        bountyInfo.data.length
+      );
+    expect(bountyInfo.lamports).to.equal(rentFloor + reward);
+
+    const solutionHash = generateSolutionHashWithValue(0x5a);
+    const attestationPda = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      solutionHash
+    );
+    await submitSolution(ctx, agent, bountyPda, attestationPda, solutionHash);
+
+    const [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    const agentLamportsBefore = await ctx.connection.getBalance(
+      agent.publicKey
+    );
+
+    await ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: creator.publicKey,
+        bounty: bountyPda,
+        reputation: reputationPda,
+        agent: agent.publicKey,
+        agentTokenAccount: null,
+        bountyTokenAccount: null,
+        rewardMint: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([creator])
+      .rpc();
+
+    const agentLamportsAfter = await ctx.connection.getBalance(agent.publicKey);
+    expect(agentLamportsAfter - agentLamportsBefore).to.equal(reward);
+
+    const bountyInfoAfter = await ctx.connection.getAccountInfo(bountyPda);
+    expect(bountyInfoAfter.lamports).to.equal(rentFloor);
+
+    const bountyAccountAfter = await ctx.program.account.bounty.fetch(
+      bountyPda
+    );
+    expect(bountyAccountAfter.status).to.deep.equal({ settled: {} });
+  });
+});
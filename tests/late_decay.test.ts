@@ -0,0 +1,205 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  deriveReputationPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  getClockUnixTimestamp,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("late_decay", () => {
+  const reward = 10 * 10 ** 6;
+  const decayBpsPerHour = 1_000;
+  const decayFloorBps = 2_000;
+  const hour = 60 * 60;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+  });
+
+  async function balanceOf(account: PublicKey): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  // the payout Bounty::graded_amount computes, in the same integer steps
+  function graded(fullRewardUntil: number, submittedAt: number): number {
+    if (submittedAt <= fullRewardUntil) {
+      return reward;
+    }
+    const decayBps = Math.min(
+      Math.floor((decayBpsPerHour * (submittedAt - fullRewardUntil)) / hour),
+      10_000 - decayFloorBps
+    );
+    return Math.floor((reward * (10_000 - decayBps)) / 10_000);
+  }
+
+  // posts a decaying bounty whose cutoff is `offset` seconds from now, and submits to it
+  async function postAndSubmit(offset: number): Promise<[PublicKey, number]> {
+    const fullRewardUntil =
+      (await getClockUnixTimestamp(ctx.connection)) + offset;
+    const bountyPda = await postBounty(ctx, "Fresh wallet intel", reward, {
+      challengePeriodSlots: 0,
+      fullRewardUntil,
+      decayBpsPerHour,
+      decayFloorBps,
+    });
+
+    const hash = generateSolutionHashWithValue(0x5a);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    return [bountyPda, fullRewardUntil];
+  }
+
+  // settles with the creator's token account, so the withheld part is refunded at once
+  async function settleWithRefund(bountyPda: PublicKey) {
+    return ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        reputation: deriveReputationPda(
+          ctx.program.programId,
+          agent.publicKey
+        )[0],
+        agent: agent.publicKey,
+        agentTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function expectGradedSettlement(
+    bountyPda: PublicKey,
+    fullRewardUntil: number
+  ): Promise<number> {
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    const payable = graded(fullRewardUntil, bounty.submittedAt.toNumber());
+    const creatorBefore = await balanceOf(ctx.creatorTokenAccount);
+
+    await settleWithRefund(bountyPda);
+
+    expect(await balanceOf(agentTokenAccount)).to.equal(payable);
+    expect((await balanceOf(ctx.creatorTokenAccount)) - creatorBefore).to.equal(
+      reward - payable
+    );
+    expect(
+      await balanceOf(getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda))
+    ).to.equal(0);
+    return payable;
+  }
+
+  it("Pays the full reward for a solution landing by the cutoff", async () => {
+    const [bountyPda, fullRewardUntil] = await postAndSubmit(10 * 60);
+
+    const payable = await expectGradedSettlement(bountyPda, fullRewardUntil);
+    expect(payable).to.equal(reward);
+  });
+
+  it("Takes 90 minutes of decay off a solution 90 minutes late", async () => {
+    const [bountyPda, fullRewardUntil] = await postAndSubmit(-90 * 60);
+
+    const payable = await expectGradedSettlement(bountyPda, fullRewardUntil);
+    // 1.5 hours at 10% an hour, plus the seconds posting and submitting took
+    expect(payable).to.be.at.most(reward * 0.85);
+    expect(payable).to.be.at.least(reward * 0.84);
+  });
+
+  it("Never pays less than the floor, however late", async () => {
+    const [bountyPda, fullRewardUntil] = await postAndSubmit(-30 * 24 * hour);
+
+    const payable = await expectGradedSettlement(bountyPda, fullRewardUntil);
+    expect(payable).to.equal((reward * decayFloorBps) / 10_000);
+  });
+
+  it("Leaves the withheld part for close_bounty without a refund account", async () => {
+    const [bountyPda] = await postAndSubmit(-30 * 24 * hour);
+    const floor = (reward * decayFloorBps) / 10_000;
+    const escrow = getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda);
+
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+    expect(await balanceOf(agentTokenAccount)).to.equal(floor);
+    expect(await balanceOf(escrow)).to.equal(reward - floor);
+
+    const creatorBefore = await balanceOf(ctx.creatorTokenAccount);
+    await ctx.program.methods
+      .closeBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        creatorBond: null,
+        bountyTokenAccount: escrow,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+    expect((await balanceOf(ctx.creatorTokenAccount)) - creatorBefore).to.equal(
+      reward - floor
+    );
+  });
+
+  it("Fails to post a decay rate without a cutoff", async () => {
+    try {
+      await postBounty(ctx, "Half a schedule", reward, { decayBpsPerHour });
+      expect.fail("Should have failed - decay needs a cutoff");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidDecaySchedule");
+    }
+  });
+
+  it("Fails to post a decaying bounty with prizes", async () => {
+    try {
+      await postBounty(ctx, "Late contest", reward, {
+        competitive: true,
+        prizes: [reward],
+        fullRewardUntil: Math.floor(Date.now() / 1000),
+        decayBpsPerHour,
+      });
+      expect.fail("Should have failed - decay grades a single payout");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidDecaySchedule");
+    }
+  });
+});
@@ -0,0 +1,132 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveLeaderboardPda,
+  deriveReputationPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  updateConfig,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("leaderboard", () => {
+  // one unit per base unit, so a reward of 2^k is worth 1 + k
+  const scoreUnit = 1;
+  const maxScorePerBounty = 64;
+  const agentCount = 20;
+  const boardSize = 16;
+
+  let ctx: TestContext;
+  let leaderboardPda: PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    [leaderboardPda] = deriveLeaderboardPda(ctx.program.programId);
+    await updateConfig(ctx, {
+      scoreUnit: new anchor.BN(scoreUnit),
+      maxScorePerBounty: new anchor.BN(maxScorePerBounty),
+    });
+  });
+
+  after(async () => {
+    await updateConfig(ctx, {
+      scoreUnit: new anchor.BN(0),
+      maxScorePerBounty: new anchor.BN(1),
+    });
+  });
+
+  async function winBounty(
+    agent: Keypair,
+    agentTokenAccount: PublicKey,
+    reward: number
+  ) {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      reward + 10 ** 6
+    );
+    const bountyPda = await postBounty(ctx, "Ranked", reward, {
+      challengePeriodSlots: 0,
+    });
+    const hash = generateSolutionHashWithValue(0x7e);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+  }
+
+  async function scoreOf(agent: PublicKey): Promise<number> {
+    const reputation = await ctx.program.account.reputation.fetch(
+      deriveReputationPda(ctx.program.programId, agent)[0]
+    );
+    return reputation.score.toNumber();
+  }
+
+  async function board(): Promise<{ agent: string; score: number }[]> {
+    const leaderboard = await ctx.program.account.leaderboard.fetch(
+      leaderboardPda
+    );
+    return leaderboard.entries.slice(0, leaderboard.len).map((entry) => ({
+      agent: entry.agent.toBase58(),
+      score: entry.score.toNumber(),
+    }));
+  }
+
+  it("Ranks the top 16 of 20 agents in order, each once", async () => {
+    const agents: { keypair: Keypair; tokenAccount: PublicKey }[] = [];
+    // scores 9 to 28, well above what agents from other test files reach
+    for (let i = 0; i < agentCount; i++) {
+      const keypair = Keypair.generate();
+      await airdropSol(ctx.connection, keypair.publicKey);
+      const tokenAccount = await createAgentTokenAccount(
+        ctx.connection,
+        keypair,
+        ctx.usdcMint
+      );
+      agents.push({ keypair, tokenAccount });
+      await winBounty(keypair, tokenAccount, 2 ** (8 + i));
+    }
+
+    const scored = await Promise.all(
+      agents.map(async ({ keypair }) => ({
+        agent: keypair.publicKey.toBase58(),
+        score: await scoreOf(keypair.publicKey),
+      }))
+    );
+    const expected = scored
+      .sort((a, b) => b.score - a.score)
+      .slice(0, boardSize);
+    expect(expected[boardSize - 1].score).to.equal(13);
+    expect(await board()).to.deep.equal(expected);
+
+    // the last agent on the board wins again and moves up, without a second entry
+    const last = agents.find(
+      ({ keypair }) =>
+        keypair.publicKey.toBase58() === expected[boardSize - 1].agent
+    );
+    await winBounty(last.keypair, last.tokenAccount, 2 ** 8);
+
+    const entries = await board();
+    expect(entries).to.have.length(boardSize);
+    const own = entries.filter(
+      (entry) => entry.agent === expected[boardSize - 1].agent
+    );
+    expect(own).to.deep.equal([
+      {
+        agent: expected[boardSize - 1].agent,
+        score: await scoreOf(last.keypair.publicKey),
+      },
+    ]);
+    const scores = entries.map((entry) => entry.score);
+    expect(scores).to.deep.equal([...scores].sort((a, b) => b - a));
+  });
+});
@@ -0,0 +1,78 @@
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveSettlementReceiptPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  getClockUnixTimestamp,
+  waitForClock,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("lifecycle_timestamps", () => {
+  let ctx: TestContext;
+  let agent: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+  });
+
+  it("Stamps post, submit and settle in increasing order", async () => {
+    const postedAfter = await getClockUnixTimestamp(ctx.connection);
+    const bountyPda = await postBounty(ctx, "Timed work", 2 * 10 ** 6, {
+      challengePeriodSlots: 0,
+    });
+
+    let bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    const createdAt = bounty.createdAt.toNumber();
+    expect(createdAt).to.be.at.least(postedAfter);
+    expect(bounty.submittedAt.toNumber()).to.equal(0);
+    expect(bounty.settledAt.toNumber()).to.equal(0);
+
+    // a second apart, so each stamp is strictly later than the last
+    await waitForClock(ctx.connection, createdAt + 1);
+    const hash = generateSolutionHashWithValue(0x71);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+
+    bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    const submittedAt = bounty.submittedAt.toNumber();
+    expect(submittedAt).to.be.greaterThan(createdAt);
+    expect(bounty.settledAt.toNumber()).to.equal(0);
+
+    await waitForClock(ctx.connection, submittedAt + 1);
+    await settleBounty(
+      ctx,
+      agent.publicKey,
+      bountyPda,
+      await createAgentTokenAccount(ctx.connection, agent, ctx.usdcMint)
+    );
+
+    bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    const settledAt = bounty.settledAt.toNumber();
+    expect(settledAt).to.be.greaterThan(submittedAt);
+    expect(bounty.createdAt.toNumber()).to.equal(createdAt);
+    expect(bounty.submittedAt.toNumber()).to.equal(submittedAt);
+
+    const receipt = await ctx.program.account.settlementReceipt.fetch(
+      deriveSettlementReceiptPda(ctx.program.programId, bountyPda)[0]
+    );
+    expect(receipt.settledAt.toNumber()).to.equal(settledAt);
+  });
+});
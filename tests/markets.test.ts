@@ -0,0 +1,290 @@
+import {
+  TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import { randomBytes } from "crypto";
+import {
+  setupTestContext,
+  defaultConfigParams,
+  deriveConfigPda,
+  deriveStatsPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("markets", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+
+  // markets can't be closed, so each run opens its own
+  const staging = { name: "s" + randomBytes(3).toString("hex"), feeBps: 100 };
+  const production = {
+    name: "p" + randomBytes(3).toString("hex"),
+    feeBps: 300,
+  };
+  const treasuries: Record<string, { wallet: Keypair; account: PublicKey }> = {};
+
+  before(async () => {
+    ctx = await setupTestContext();
+    for (const market of [staging, production]) {
+      const wallet = Keypair.generate();
+      const account = (
+        await getOrCreateAssociatedTokenAccount(
+          ctx.connection,
+          ctx.creator,
+          ctx.usdcMint,
+          wallet.publicKey
+        )
+      ).address;
+      treasuries[market.name] = { wallet, account };
+      await initializeMarket(market.name, {
+        ...defaultConfigParams(ctx.provider.wallet.publicKey),
+        feeBps: market.feeBps,
+        treasury: wallet.publicKey,
+      });
+    }
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  function initializeMarket(
+    name: string,
+    params: ReturnType<typeof defaultConfigParams>,
+    admin?: Keypair
+  ) {
+    const builder = ctx.program.methods
+      .initializeMarket(name, params)
+      .accountsPartial({
+        admin: admin ? admin.publicKey : ctx.provider.wallet.publicKey,
+      });
+    return (admin ? builder.signers([admin]) : builder).rpc();
+  }
+
+  async function balanceOf(account: PublicKey): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  async function submitted(market: string) {
+    const bountyPda = await postBounty(ctx, "Market audit", reward, {
+      challengePeriodSlots: 0,
+      market,
+    });
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const hash = generateSolutionHashWithValue(0x6d);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    const agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    return { bountyPda, agent, agentTokenAccount };
+  }
+
+  function settle(
+    {
+      bountyPda,
+      agent,
+      agentTokenAccount,
+    }: Awaited<ReturnType<typeof submitted>>,
+    market: string,
+    config?: PublicKey
+  ) {
+    return ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        agent: agent.publicKey,
+        agentTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        treasury: treasuries[market].wallet.publicKey,
+        treasuryTokenAccount: treasuries[market].account,
+        attestor: null,
+        attestorTokenAccount: null,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+        ...(config ? { config } : {}),
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  function verify(
+    attestation: PublicKey,
+    signer?: Keypair,
+    config?: PublicKey
+  ) {
+    const builder = ctx.program.methods.verifyAttestation().accountsPartial({
+      verifier: signer ? signer.publicKey : ctx.provider.wallet.publicKey,
+      attestation,
+      ...(config ? { config } : {}),
+    });
+    return (signer ? builder.signers([signer]) : builder).rpc();
+  }
+
+  it("Charges each bounty the fee of the market it was posted in", async () => {
+    for (const market of [staging, production]) {
+      const statsPda = deriveStatsPda(ctx.program.programId, market.name)[0];
+      const postedBefore = (
+        await ctx.program.account.bountyStats.fetch(statsPda)
+      ).bountiesPosted.toNumber();
+
+      const work = await submitted(market.name);
+      const bounty = await ctx.program.account.bounty.fetch(work.bountyPda);
+      expect(bounty.market).to.equal(market.name);
+
+      const treasuryBefore = await balanceOf(treasuries[market.name].account);
+      await settle(work, market.name);
+
+      const fee = (reward * market.feeBps) / 10_000;
+      expect(
+        (await balanceOf(treasuries[market.name].account)) - treasuryBefore
+      ).to.equal(fee);
+      expect(await balanceOf(work.agentTokenAccount)).to.equal(reward - fee);
+
+      const stats = await ctx.program.account.bountyStats.fetch(statsPda);
+      expect(stats.bountiesPosted.toNumber()).to.equal(postedBefore + 1);
+      expect(stats.bountiesSettled.toNumber()).to.equal(1);
+    }
+  });
+
+  it("Refuses to settle a bounty against another market's config", async () => {
+    const work = await submitted(staging.name);
+
+    try {
+      await settle(
+        work,
+        production.name,
+        deriveConfigPda(ctx.program.programId, production.name)[0]
+      );
+      expect.fail("Should have failed - config of another market");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ConstraintSeeds");
+    }
+
+    await settle(work, staging.name);
+    const bounty = await ctx.program.account.bounty.fetch(work.bountyPda);
+    expect(bounty.status).to.deep.equal({ settled: {} });
+  });
+
+  it("Keeps the default market where it was", async () => {
+    const bountyPda = await postBounty(ctx, "Default market audit", reward);
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.market).to.equal("");
+    const config = await ctx.program.account.config.fetch(
+      deriveConfigPda(ctx.program.programId)[0]
+    );
+    expect(config.market).to.equal("");
+  });
+
+  it("Only lets the default admin open a market with a valid name", async () => {
+    const stranger = Keypair.generate();
+    await airdropSol(ctx.connection, stranger.publicKey);
+    try {
+      await initializeMarket(
+        "x" + randomBytes(3).toString("hex"),
+        defaultConfigParams(stranger.publicKey),
+        stranger
+      );
+      expect.fail("Should have failed - not the default admin");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedAdmin");
+    }
+
+    try {
+      await initializeMarket(
+        "toolong" + randomBytes(2).toString("hex"),
+        defaultConfigParams(ctx.provider.wallet.publicKey)
+      );
+      expect.fail("Should have failed - name longer than MAX_MARKET_LEN");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidMarketName");
+    }
+
+    const config = await ctx.program.account.config.fetch(
+      deriveConfigPda(ctx.program.programId, staging.name)[0]
+    );
+    expect(config.market).to.equal(staging.name);
+    expect(config.feeBps).to.equal(staging.feeBps);
+    expect(config.admin.toString()).to.equal(
+      ctx.provider.wallet.publicKey.toString()
+    );
+  });
+
+  it("Has attestations verified by their own market's verifier", async () => {
+    const reviewed = "v" + randomBytes(3).toString("hex");
+    const verifier = Keypair.generate();
+    await airdropSol(ctx.connection, verifier.publicKey);
+    await initializeMarket(reviewed, {
+      ...defaultConfigParams(ctx.provider.wallet.publicKey),
+      verifier: verifier.publicKey,
+    });
+
+    const bountyPda = await postBounty(ctx, "Reviewed audit", reward, {
+      market: reviewed,
+    });
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const hash = generateSolutionHashWithValue(0x76);
+    const attestation = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      hash,
+      false
+    );
+    expect(
+      (await ctx.program.account.attestation.fetch(attestation)).market
+    ).to.equal(reviewed);
+
+    // the default market's verifier can't verify it, under either config
+    try {
+      await verify(attestation);
+      expect.fail("Should have failed - verifier of another market");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedVerifier");
+    }
+    try {
+      await verify(
+        attestation,
+        undefined,
+        deriveConfigPda(ctx.program.programId)[0]
+      );
+      expect.fail("Should have failed - config of another market");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ConstraintSeeds");
+    }
+
+    await verify(attestation, verifier);
+    expect(
+      (await ctx.program.account.attestation.fetch(attestation)).verified
+    ).to.be.true;
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+  });
+});
@@ -0,0 +1,244 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  getAssociatedTokenAddressSync,
+  nextBountyPda,
+  airdropSol,
+  createAgentTokenAccount,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  fetchEvents,
+  findEvent,
+  generateSolutionHashWithValue,
+  BOUNTY_V0,
+  BOUNTY_V0_MINT,
+  BOUNTY_V0_ESCROW,
+  BOUNTY_V0_CREATOR,
+  BOUNTY_V0_REWARD,
+  TestContext,
+} from "./helpers";
+
+describe("migrate_bounty", () => {
+  const v0Len = 8 + 170;
+  const currentLen = 8 + 1823;
+
+  let ctx: TestContext;
+  let legacyCtx: TestContext;
+  let payer: Keypair;
+  let migratedPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    payer = Keypair.generate();
+    await airdropSol(ctx.connection, payer.publicKey);
+
+    // the fixture creator signs like any other, starting its own ids at 1
+    await airdropSol(ctx.connection, BOUNTY_V0_CREATOR.publicKey);
+    await ctx.program.methods
+      .initializeBountyCounter()
+      .accountsPartial({ creator: BOUNTY_V0_CREATOR.publicKey })
+      .signers([BOUNTY_V0_CREATOR])
+      .rpc();
+    legacyCtx = { ...ctx, creator: BOUNTY_V0_CREATOR };
+  });
+
+  async function migrate(
+    legacyBounty: anchor.web3.PublicKey,
+    creator: anchor.web3.PublicKey,
+    legacyTokenAccount: anchor.web3.PublicKey,
+    newBounty: anchor.web3.PublicKey
+  ) {
+    return ctx.program.methods
+      .migrateBounty()
+      .accountsPartial({
+        payer: payer.publicKey,
+        legacyBounty,
+        creator,
+        bounty: newBounty,
+        rewardMint: BOUNTY_V0_MINT,
+        legacyTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          BOUNTY_V0_MINT,
+          newBounty
+        ),
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([payer])
+      .rpc();
+  }
+
+  it("Rejects the old layout everywhere else", async () => {
+    try {
+      await ctx.program.methods
+        .cancelBounty()
+        .accountsPartial({
+          creator: BOUNTY_V0_CREATOR.publicKey,
+          bounty: BOUNTY_V0,
+          unwrapAccount: null,
+          creatorTokenAccount: null,
+          bountyTokenAccount: BOUNTY_V0_ESCROW,
+          rewardMint: BOUNTY_V0_MINT,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([BOUNTY_V0_CREATOR])
+        .rpc();
+      expect.fail("Should have failed - bounty not migrated");
+    } catch (err) {
+      // the old bytes may not even parse as the new layout
+      expect(err.error.errorCode.code).to.be.oneOf([
+        "AccountNeedsMigration",
+        "AccountDidNotDeserialize",
+        "ConstraintSeeds",
+      ]);
+    }
+  });
+
+  it("Fails for anyone but the recorded creator", async () => {
+    const [otherPda] = await nextBountyPda(ctx);
+    try {
+      await migrate(
+        BOUNTY_V0,
+        ctx.creator.publicKey,
+        BOUNTY_V0_ESCROW,
+        otherPda
+      );
+      expect.fail("Should have failed - wrong creator");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("LegacyCreatorMismatch");
+    }
+  });
+
+  it("Moves a 0.1.0 bounty and its escrow to the creator's next id", async () => {
+    const before = await ctx.connection.getAccountInfo(BOUNTY_V0);
+    expect(before.data.length).to.equal(v0Len);
+    const escrowBefore = await ctx.connection.getAccountInfo(BOUNTY_V0_ESCROW);
+    const creatorLamportsBefore = await ctx.connection.getBalance(
+      BOUNTY_V0_CREATOR.publicKey
+    );
+
+    let bountyId: number;
+    [migratedPda, bountyId] = await nextBountyPda(legacyCtx);
+    const events = await fetchEvents(
+      ctx,
+      await migrate(
+        BOUNTY_V0,
+        BOUNTY_V0_CREATOR.publicKey,
+        BOUNTY_V0_ESCROW,
+        migratedPda
+      )
+    );
+    const event = findEvent(events, "BountyMigrated");
+    expect(event.data.bounty.toString()).to.equal(migratedPda.toString());
+    expect(event.data.legacyBounty.toString()).to.equal(BOUNTY_V0.toString());
+    expect(event.data.bountyId.toNumber()).to.equal(bountyId);
+    expect(event.data.version).to.equal(1);
+
+    // the old accounts are gone, their rent back with the creator
+    expect(await ctx.connection.getAccountInfo(BOUNTY_V0)).to.be.null;
+    expect(await ctx.connection.getAccountInfo(BOUNTY_V0_ESCROW)).to.be.null;
+    const creatorLamportsAfter = await ctx.connection.getBalance(
+      BOUNTY_V0_CREATOR.publicKey
+    );
+    expect(creatorLamportsAfter - creatorLamportsBefore).to.equal(
+      before.lamports + escrowBefore.lamports
+    );
+
+    const after = await ctx.connection.getAccountInfo(migratedPda);
+    expect(after.data.length).to.equal(currentLen);
+    const escrow = await ctx.connection.getTokenAccountBalance(
+      getAssociatedTokenAddressSync(BOUNTY_V0_MINT, migratedPda)
+    );
+    expect(Number(escrow.value.amount)).to.equal(BOUNTY_V0_REWARD);
+
+    const bounty = await ctx.program.account.bounty.fetch(migratedPda);
+    expect(bounty.version).to.equal(1);
+    expect(bounty.id.toNumber()).to.equal(bountyId);
+    expect(bounty.status).to.deep.equal({ open: {} });
+    expect(bounty.creator.toString()).to.equal(
+      BOUNTY_V0_CREATOR.publicKey.toString()
+    );
+    expect(bounty.originalCreator.toString()).to.equal(
+      BOUNTY_V0_CREATOR.publicKey.toString()
+    );
+    // the payer covered the new accounts' rent, so gets it back on close
+    expect(bounty.rentPayer.toString()).to.equal(payer.publicKey.toString());
+    expect(bounty.rewardCurrency).to.deep.equal({ spl: {} });
+    expect(bounty.rewardMint.toString()).to.equal(BOUNTY_V0_MINT.toString());
+    expect(bounty.reward.toNumber()).to.equal(BOUNTY_V0_REWARD);
+    expect(bounty.escrowAmount.toNumber()).to.equal(BOUNTY_V0_REWARD);
+    expect(bounty.description).to.equal("Legacy USDC bounty");
+    expect(bounty.bountyType).to.deep.equal({ walletIntelligence: {} });
+    expect(bounty.market).to.equal("");
+    expect(bounty.createdAt.toNumber()).to.be.greaterThan(0);
+    expect(bounty.settlementAuthority).to.be.null;
+  });
+
+  it("Fails to migrate a bounty twice", async () => {
+    const [otherPda] = await nextBountyPda(legacyCtx);
+    try {
+      await migrate(
+        migratedPda,
+        BOUNTY_V0_CREATOR.publicKey,
+        getAssociatedTokenAddressSync(BOUNTY_V0_MINT, migratedPda),
+        otherPda
+      );
+      expect.fail("Should have failed - already migrated");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyAlreadyMigrated");
+    }
+  });
+
+  it("Settles the migrated bounty end to end", async () => {
+    // a first post of its own gives the fixture creator a CreatorProfile
+    await postBounty(legacyCtx, "Fresh one", BOUNTY_V0_REWARD / 10, {
+      rewardCurrency: { sol: {} },
+    });
+
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      BOUNTY_V0_MINT
+    );
+    const hash = generateSolutionHashWithValue(0x0a);
+    const attestation = await createAttestation(ctx, agent, migratedPda, hash);
+    await submitSolution(ctx, agent, migratedPda, attestation, hash);
+
+    await ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: BOUNTY_V0_CREATOR.publicKey,
+        bounty: migratedPda,
+        reputation: deriveReputationPda(
+          ctx.program.programId,
+          agent.publicKey
+        )[0],
+        agent: agent.publicKey,
+        agentTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          BOUNTY_V0_MINT,
+          migratedPda
+        ),
+        rewardMint: BOUNTY_V0_MINT,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([BOUNTY_V0_CREATOR])
+      .rpc();
+
+    const agentBalance = await ctx.connection.getTokenAccountBalance(
+      agentTokenAccount
+    );
+    expect(Number(agentBalance.value.amount)).to.equal(BOUNTY_V0_REWARD);
+
+    const bounty = await ctx.program.account.bounty.fetch(migratedPda);
+    expect(bounty.status).to.deep.equal({ settled: {} });
+    expect(bounty.settledAt.toNumber()).to.be.greaterThan(0);
+  });
+});
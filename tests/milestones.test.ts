@@ -0,0 +1,191 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  initReputation,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("milestones", () => {
+  const amounts = [3 * 10 ** 6, 3 * 10 ** 6, 4 * 10 ** 6];
+  const reward = amounts.reduce((sum, amount) => sum + amount, 0);
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let bountyPda: anchor.web3.PublicKey;
+  let bountyTokenAccount: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    await initReputation(ctx, agent);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+
+    bountyPda = await postBounty(ctx, "Audit in three parts", reward, {
+      challengePeriodSlots: 0,
+      milestones: amounts.map((amount, i) => ({
+        amount,
+        descriptionHash: generateSolutionHashWithValue(0xd0 + i),
+      })),
+    });
+    bountyTokenAccount = getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda);
+  });
+
+  async function submitMilestone(index: number) {
+    const hash = generateSolutionHashWithValue(0xe0 + index);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await ctx.program.methods
+      .submitMilestone(index, Array.from(hash))
+      .accountsPartial({
+        agent: agent.publicKey,
+        bounty: bountyPda,
+        attestation,
+        application: null,
+      })
+      .signers([agent])
+      .rpc();
+  }
+
+  async function settleMilestone(index: number) {
+    await ctx.program.methods
+      .settleMilestone(index)
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        reputation: deriveReputationPda(
+          ctx.program.programId,
+          agent.publicKey
+        )[0],
+        agent: agent.publicKey,
+        agentTokenAccount,
+        bountyTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function escrowBalance(): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(
+      bountyTokenAccount
+    );
+    return Number(balance.value.amount);
+  }
+
+  it("Records the milestones on the bounty", async () => {
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.milestones.length).to.equal(amounts.length);
+    bountyAccount.milestones.forEach((milestone, i) => {
+      expect(milestone.amount.toNumber()).to.equal(amounts[i]);
+      expect(Buffer.from(milestone.descriptionHash)).to.deep.equal(
+        generateSolutionHashWithValue(0xd0 + i)
+      );
+      expect(milestone.status).to.deep.equal({ pending: {} });
+    });
+  });
+
+  it("Pays each milestone in turn and settles with the last", async () => {
+    let paid = 0;
+    for (let i = 0; i < amounts.length; i++) {
+      await submitMilestone(i);
+      await settleMilestone(i);
+      paid += amounts[i];
+
+      expect(await escrowBalance()).to.equal(reward - paid);
+      const agentBalance = await ctx.connection.getTokenAccountBalance(
+        agentTokenAccount
+      );
+      expect(Number(agentBalance.value.amount)).to.equal(paid);
+
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bountyAccount.milestones[i].status).to.deep.equal({ paid: {} });
+      expect(bountyAccount.status).to.deep.equal(
+        i + 1 < amounts.length ? { open: {} } : { settled: {} }
+      );
+    }
+
+    const reputation = await ctx.program.account.reputation.fetch(
+      deriveReputationPda(ctx.program.programId, agent.publicKey)[0]
+    );
+    expect(reputation.successfulBounties.toNumber()).to.equal(1);
+    expect(reputation.totalEarned.toNumber()).to.equal(reward);
+  });
+
+  it("Fails to settle a milestone before the earlier ones", async () => {
+    await submitMilestone(0);
+    await submitMilestone(1);
+
+    try {
+      await settleMilestone(1);
+      expect.fail("Should have failed - milestone 0 not paid");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MilestoneOutOfOrder");
+    }
+  });
+
+  it("Fails to settle a milestone twice", async () => {
+    await submitMilestone(0);
+    await settleMilestone(0);
+
+    try {
+      await settleMilestone(0);
+      expect.fail("Should have failed - already paid");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MilestoneAlreadyPaid");
+    }
+    expect(await escrowBalance()).to.equal(reward - amounts[0]);
+  });
+
+  it("Fails to submit to a completed milestone", async () => {
+    await submitMilestone(0);
+    await settleMilestone(0);
+
+    try {
+      await submitMilestone(0);
+      expect.fail("Should have failed - milestone completed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MilestoneCompleted");
+    }
+  });
+
+  it("Fails to post milestones that don't add up to the reward", async () => {
+    try {
+      await postBounty(ctx, "Short milestones", reward, {
+        milestones: [
+          { amount: amounts[0], descriptionHash: generateSolutionHashWithValue(1) },
+        ],
+      });
+      expect.fail("Should have failed - milestones short of the reward");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MilestoneScheduleMismatch");
+    }
+  });
+});
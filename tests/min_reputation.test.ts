@@ -0,0 +1,112 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("min_reputation", () => {
+  const reward = 1 * 10 ** 6;
+  const minReputation = 3;
+
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  async function newAgent(): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    return agent;
+  }
+
+  async function submit(agent: Keypair, bountyPda: anchor.web3.PublicKey) {
+    const hash = generateSolutionHashWithValue(0x33);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+  }
+
+  // wins open bounties until the agent has `wins` successful bounties
+  async function winBounties(agent: Keypair, wins: number) {
+    const agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    for (let i = 0; i < wins; i++) {
+      const bountyPda = await postBounty(ctx, "Warm-up", reward);
+      await submit(agent, bountyPda);
+      await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+    }
+
+    const [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    const reputation = await ctx.program.account.reputation.fetch(
+      reputationPda
+    );
+    expect(reputation.successfulBounties.toNumber()).to.equal(wins);
+  }
+
+  it("Blocks an agent below the threshold and admits one at it", async () => {
+    const gatedPda = await postBounty(ctx, "Veterans only", reward, {
+      minReputation,
+    });
+    const gated = await ctx.program.account.bounty.fetch(gatedPda);
+    expect(gated.minReputation.toNumber()).to.equal(minReputation);
+
+    const novice = await newAgent();
+    await winBounties(novice, minReputation - 1);
+    try {
+      await submit(novice, gatedPda);
+      expect.fail("Should have failed - not enough wins");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InsufficientReputation");
+    }
+
+    const veteran = await newAgent();
+    await winBounties(veteran, minReputation);
+    await submit(veteran, gatedPda);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(gatedPda);
+    expect(bountyAccount.solver.toString()).to.equal(
+      veteran.publicKey.toString()
+    );
+  });
+
+  it("Counts an agent with no reputation account as zero", async () => {
+    const gatedPda = await postBounty(ctx, "Veterans only", reward, {
+      minReputation: 1,
+    });
+
+    const newcomer = await newAgent();
+    try {
+      await submit(newcomer, gatedPda);
+      expect.fail("Should have failed - no reputation");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InsufficientReputation");
+    }
+  });
+});
@@ -0,0 +1,189 @@
+import {
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  nextBountyPda,
+  getAssociatedTokenAddressSync,
+  bountyParams,
+  createDefaultFrozenMint,
+  createPermanentDelegateMint,
+  createTransferHookMint,
+  updateConfig,
+  waitForPostCooldown,
+  TestContext,
+} from "./helpers";
+
+describe("mint_safety", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  after(async () => {
+    await updateConfig(ctx, { trustedMints: [] });
+  });
+
+  async function post(
+    mint: PublicKey,
+    tokenProgram: PublicKey,
+    fund = false
+  ): Promise<PublicKey> {
+    const creatorTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        ctx.connection,
+        ctx.creator,
+        mint,
+        ctx.creator.publicKey,
+        false,
+        undefined,
+        undefined,
+        tokenProgram
+      )
+    ).address;
+    if (fund) {
+      await mintTo(
+        ctx.connection,
+        ctx.creator,
+        mint,
+        creatorTokenAccount,
+        ctx.creator,
+        reward,
+        [],
+        undefined,
+        tokenProgram
+      );
+    }
+
+    const [bountyPda] = await nextBountyPda(ctx);
+    await waitForPostCooldown(ctx.creator.publicKey);
+    await ctx.program.methods
+      .postBounty(bountyParams("Vetted mint", reward))
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        rewardMint: mint,
+        creatorTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          mint,
+          bountyPda,
+          tokenProgram
+        ),
+        tokenProgram,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+    return bountyPda;
+  }
+
+  async function expectPostError(
+    mint: PublicKey,
+    tokenProgram: PublicKey,
+    code: string
+  ) {
+    try {
+      await post(mint, tokenProgram);
+      expect.fail(`Should have failed with ${code}`);
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal(code);
+    }
+  }
+
+  it("Escrows a plain token mint", async () => {
+    const mint = await createMint(
+      ctx.connection,
+      ctx.creator,
+      ctx.creator.publicKey,
+      null,
+      6
+    );
+    const bountyPda = await post(mint, TOKEN_PROGRAM_ID, true);
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.escrowAmount.toNumber()).to.equal(reward);
+  });
+
+  it("Fails when the creator holds the mint's freeze authority", async () => {
+    const mint = await createMint(
+      ctx.connection,
+      ctx.creator,
+      ctx.creator.publicKey,
+      ctx.creator.publicKey,
+      6
+    );
+    await expectPostError(
+      mint,
+      TOKEN_PROGRAM_ID,
+      "UnsafeRewardMintFreezeAuthority"
+    );
+  });
+
+  it("Fails when the mint has a permanent delegate", async () => {
+    const mint = await createPermanentDelegateMint(
+      ctx.connection,
+      ctx.creator,
+      6,
+      ctx.creator.publicKey
+    );
+    await expectPostError(
+      mint,
+      TOKEN_2022_PROGRAM_ID,
+      "UnsafeRewardMintPermanentDelegate"
+    );
+  });
+
+  it("Fails when the mint has a transfer hook", async () => {
+    const mint = await createTransferHookMint(
+      ctx.connection,
+      ctx.creator,
+      6,
+      Keypair.generate().publicKey
+    );
+    await expectPostError(
+      mint,
+      TOKEN_2022_PROGRAM_ID,
+      "UnsafeRewardMintTransferHook"
+    );
+  });
+
+  it("Fails when the mint freezes new accounts by default", async () => {
+    // someone other than the creator, so only the default state is at fault
+    const mint = await createDefaultFrozenMint(
+      ctx.connection,
+      ctx.creator,
+      6,
+      Keypair.generate().publicKey
+    );
+    await expectPostError(
+      mint,
+      TOKEN_2022_PROGRAM_ID,
+      "UnsafeRewardMintDefaultFrozen"
+    );
+  });
+
+  it("Escrows a mint with a permanent delegate once the admin trusts it", async () => {
+    const mint = await createPermanentDelegateMint(
+      ctx.connection,
+      ctx.creator,
+      6,
+      ctx.creator.publicKey
+    );
+    await updateConfig(ctx, { trustedMints: [mint] });
+
+    const bountyPda = await post(mint, TOKEN_2022_PROGRAM_ID, true);
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.rewardMint.toString()).to.equal(mint.toString());
+    expect(bounty.escrowAmount.toNumber()).to.equal(reward);
+  });
+});
@@ -0,0 +1,173 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveOperatorPda,
+  deriveReputationPda,
+  deriveSubmissionPda,
+  airdropSol,
+  ensureCreatorBalance,
+  ensureReputation,
+  postBounty,
+  createAttestation,
+  fetchEvents,
+  findEvent,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("operator", () => {
+  let ctx: TestContext;
+  let agent: Keypair;
+  let operator: Keypair;
+  let operatorPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    agent = Keypair.generate();
+    operator = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    await airdropSol(ctx.connection, operator.publicKey);
+    await ensureReputation(ctx, agent);
+    [operatorPda] = deriveOperatorPda(
+      ctx.program.programId,
+      agent.publicKey,
+      operator.publicKey
+    );
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  // posts a bounty and has the agent attest a solution the operator then submits
+  async function attested(): Promise<
+    [anchor.web3.PublicKey, anchor.web3.PublicKey, Buffer]
+  > {
+    const bountyPda = await postBounty(ctx, "Run by the hot key", 5 * 10 ** 6);
+    const hash = generateSolutionHashWithValue(0x6a);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    return [bountyPda, attestation, hash];
+  }
+
+  async function submitAsOperator(
+    bountyPda: anchor.web3.PublicKey,
+    attestation: anchor.web3.PublicKey,
+    hash: Buffer,
+    operatorRecord: anchor.web3.PublicKey | null = operatorPda
+  ) {
+    return ctx.program.methods
+      .submitSolution(Array.from(hash), "", [])
+      .accountsPartial({
+        submitter: operator.publicKey,
+        agent: agent.publicKey,
+        operatorRecord,
+        bounty: bountyPda,
+        attestation,
+        reputation: deriveReputationPda(
+          ctx.program.programId,
+          agent.publicKey
+        )[0],
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([operator])
+      .rpc();
+  }
+
+  it("Registers an operator", async () => {
+    const events = await fetchEvents(
+      ctx,
+      await ctx.program.methods
+        .registerOperator(operator.publicKey)
+        .accountsPartial({ agent: agent.publicKey })
+        .signers([agent])
+        .rpc()
+    );
+
+    const record = await ctx.program.account.operator.fetch(operatorPda);
+    expect(record.agent.toString()).to.equal(agent.publicKey.toString());
+    expect(record.operator.toString()).to.equal(operator.publicKey.toString());
+
+    const event = findEvent(events, "OperatorRegistered");
+    expect(event.data.operator.toString()).to.equal(
+      operator.publicKey.toString()
+    );
+  });
+
+  it("Lets the operator submit for the agent", async () => {
+    const [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    const before = await ctx.program.account.reputation.fetch(reputationPda);
+
+    const [bountyPda, attestation, hash] = await attested();
+    await submitAsOperator(bountyPda, attestation, hash);
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.solver.toString()).to.equal(agent.publicKey.toString());
+
+    const submission = await ctx.program.account.submission.fetch(
+      deriveSubmissionPda(ctx.program.programId, bountyPda, agent.publicKey)[0]
+    );
+    expect(submission.agent.toString()).to.equal(agent.publicKey.toString());
+
+    // the agent's score moves, the operator never gets a reputation of its own
+    const after = await ctx.program.account.reputation.fetch(reputationPda);
+    expect(after.score.toNumber()).to.equal(before.score.toNumber() + 1);
+    expect(
+      await ctx.connection.getAccountInfo(
+        deriveReputationPda(ctx.program.programId, operator.publicKey)[0]
+      )
+    ).to.be.null;
+  });
+
+  it("Fails for a key the agent never registered", async () => {
+    const [bountyPda, attestation, hash] = await attested();
+
+    try {
+      await submitAsOperator(bountyPda, attestation, hash, null);
+      expect.fail("Should have failed - not an operator");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedOperator");
+    }
+  });
+
+  it("Rejects the operator once the agent revokes it", async () => {
+    await ctx.program.methods
+      .revokeOperator(operator.publicKey)
+      .accountsPartial({ agent: agent.publicKey })
+      .signers([agent])
+      .rpc();
+    expect(await ctx.connection.getAccountInfo(operatorPda)).to.be.null;
+
+    const [bountyPda, attestation, hash] = await attested();
+    try {
+      await submitAsOperator(bountyPda, attestation, hash);
+      expect.fail("Should have failed - operator revoked");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+      expect(err.error.origin).to.equal("operator_record");
+    }
+  });
+
+  it("Fails to make an agent its own operator", async () => {
+    try {
+      await ctx.program.methods
+        .registerOperator(agent.publicKey)
+        .accountsPartial({ agent: agent.publicKey })
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have failed - agent as its own operator");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidOperator");
+    }
+  });
+});
@@ -0,0 +1,204 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import {
+  Ed25519Program,
+  Keypair,
+  PublicKey,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SystemProgram,
+} from "@solana/web3.js";
+import { createHash } from "crypto";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  deriveReputationPda,
+  deriveSettlementReceiptPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  updateConfig,
+  paymentReceiptMessage,
+  generateSolutionHashWithValue,
+  fetchEvents,
+  findEvent,
+  TestContext,
+} from "./helpers";
+
+describe("payment_receipt", () => {
+  const reward = 10 * 10 ** 6;
+  const verificationFee = 25_000;
+  const gateway = Keypair.generate();
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await updateConfig(ctx, {
+      paymentGateway: gateway.publicKey,
+      minVerificationFee: new anchor.BN(verificationFee),
+    });
+
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+  });
+
+  after(async () => {
+    await updateConfig(ctx, {
+      paymentGateway: PublicKey.default,
+      minVerificationFee: new anchor.BN(0),
+    });
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  // posts a receipt-gated bounty and submits a solution to it
+  async function submitted(value: number): Promise<[PublicKey, Buffer]> {
+    const bountyPda = await postBounty(ctx, "Verified through x402", reward, {
+      challengePeriodSlots: 0,
+      requiresPaymentReceipt: true,
+    });
+    const hash = generateSolutionHashWithValue(value);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    return [bountyPda, hash];
+  }
+
+  async function settleWithReceipt(
+    bountyPda: PublicKey,
+    signer: Keypair,
+    message: Buffer
+  ) {
+    return ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        reputation: deriveReputationPda(ctx.program.programId, agent.publicKey)[0],
+        agent: agent.publicKey,
+        agentTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        instructionsSysvar: SYSVAR_INSTRUCTIONS_PUBKEY,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .preInstructions([
+        Ed25519Program.createInstructionWithPrivateKey({
+          privateKey: signer.secretKey,
+          message,
+        }),
+      ])
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  it("Settles with the gateway's receipt and keeps its hash and amount", async () => {
+    const [bountyPda, hash] = await submitted(0x61);
+    const message = paymentReceiptMessage(bountyPda, hash, verificationFee);
+
+    const sig = await settleWithReceipt(bountyPda, gateway, message);
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.requiresPaymentReceipt).to.be.true;
+    expect(bounty.status).to.deep.equal({ settled: {} });
+
+    const receipt = await ctx.program.account.settlementReceipt.fetch(
+      deriveSettlementReceiptPda(ctx.program.programId, bountyPda)[0]
+    );
+    expect(Buffer.from(receipt.paymentReceiptHash)).to.deep.equal(
+      createHash("sha256").update(message).digest()
+    );
+    expect(receipt.paymentAmount.toNumber()).to.equal(verificationFee);
+
+    const settled = findEvent(await fetchEvents(ctx, sig), "BountySettled");
+    expect(settled.data.paymentAmount.toNumber()).to.equal(verificationFee);
+  });
+
+  it("Fails without a receipt", async () => {
+    const [bountyPda] = await submitted(0x62);
+
+    try {
+      await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+      expect.fail("Should have failed - no receipt");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PaymentReceiptMissing");
+    }
+  });
+
+  it("Fails when someone other than the gateway signs the receipt", async () => {
+    const [bountyPda, hash] = await submitted(0x63);
+
+    try {
+      await settleWithReceipt(
+        bountyPda,
+        Keypair.generate(),
+        paymentReceiptMessage(bountyPda, hash, verificationFee)
+      );
+      expect.fail("Should have failed - not the gateway");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PaymentReceiptSignerMismatch");
+    }
+  });
+
+  it("Fails with a receipt for a different bounty", async () => {
+    const [bountyPda, hash] = await submitted(0x64);
+    const [otherBountyPda] = await submitted(0x65);
+
+    try {
+      await settleWithReceipt(
+        bountyPda,
+        gateway,
+        paymentReceiptMessage(otherBountyPda, hash, verificationFee)
+      );
+      expect.fail("Should have failed - receipt for another bounty");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PaymentReceiptMismatch");
+    }
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ submitted: {} });
+  });
+
+  it("Fails with a receipt for less than the verification fee", async () => {
+    const [bountyPda, hash] = await submitted(0x66);
+
+    for (const amountPaid of [0, verificationFee - 1]) {
+      try {
+        await settleWithReceipt(
+          bountyPda,
+          gateway,
+          paymentReceiptMessage(bountyPda, hash, amountPaid)
+        );
+        expect.fail("Should have failed - receipt underpaid");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("PaymentReceiptUnderpaid");
+      }
+    }
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ submitted: {} });
+  });
+});
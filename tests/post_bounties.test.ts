@@ -0,0 +1,182 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { ComputeBudgetProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  deriveBountyCounterPda,
+  deriveBountyPda,
+  deriveCreatorIndexPda,
+  deriveCreatorProfilePda,
+  nextBountyPda,
+  waitForPostCooldown,
+  TestContext,
+} from "./helpers";
+
+describe("post_bounties", () => {
+  const reward = 2 * 10 ** 6;
+
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  function item(description: string, amount = reward) {
+    return {
+      bountyType: { tokenScreening: {} },
+      description,
+      reward: new anchor.BN(amount),
+      deadline: new anchor.BN(Math.floor(Date.now() / 1000) + 86400),
+    };
+  }
+
+  // posts the items as the next ids, returning their bounty PDAs
+  async function postBatch(
+    items: ReturnType<typeof item>[]
+  ): Promise<anchor.web3.PublicKey[]> {
+    const [, firstId] = await nextBountyPda(ctx);
+    const bountyPdas = items.map(
+      (_, i) =>
+        deriveBountyPda(
+          ctx.program.programId,
+          ctx.creator.publicKey,
+          firstId + i
+        )[0]
+    );
+    await waitForPostCooldown(ctx.creator.publicKey);
+    await ctx.program.methods
+      .postBounties(items)
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        rewardMint: ctx.usdcMint,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(
+        bountyPdas.flatMap((bountyPda) => [
+          { pubkey: bountyPda, isSigner: false, isWritable: true },
+          {
+            pubkey: getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda),
+            isSigner: false,
+            isWritable: true,
+          },
+        ])
+      )
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 600_000 }),
+      ])
+      .signers([ctx.creator])
+      .rpc();
+    return bountyPdas;
+  }
+
+  it("Posts three bounties in one transaction and funds every escrow", async () => {
+    const [, firstId] = await nextBountyPda(ctx);
+    const bountyPdas = await postBatch([
+      item("Screen token A"),
+      item("Screen token B", reward * 2),
+      item("Screen token C", reward * 3),
+    ]);
+
+    for (const [i, bountyPda] of bountyPdas.entries()) {
+      const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bounty.id.toNumber()).to.equal(firstId + i);
+      expect(bounty.status).to.deep.equal({ open: {} });
+      expect(bounty.bountyType).to.deep.equal({ tokenScreening: {} });
+      expect(bounty.reward.toNumber()).to.equal(reward * (i + 1));
+      expect(bounty.escrowAmount.toNumber()).to.equal(reward * (i + 1));
+      expect(bounty.rewardMint.toString()).to.equal(ctx.usdcMint.toString());
+
+      const escrow = await ctx.connection.getTokenAccountBalance(
+        getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda)
+      );
+      expect(Number(escrow.value.amount)).to.equal(reward * (i + 1));
+    }
+
+    const counter = await ctx.program.account.bountyCounter.fetch(
+      deriveBountyCounterPda(ctx.program.programId, ctx.creator.publicKey)[0]
+    );
+    expect(counter.nextId.toNumber()).to.equal(firstId + 3);
+
+    const index = await ctx.program.account.creatorIndex.fetch(
+      deriveCreatorIndexPda(ctx.program.programId, ctx.creator.publicKey)[0]
+    );
+    expect(index.bountyIds.map((id) => id.toNumber())).to.include.members([
+      firstId,
+      firstId + 1,
+      firstId + 2,
+    ]);
+
+    const profile = await ctx.program.account.creatorProfile.fetch(
+      deriveCreatorProfilePda(ctx.program.programId, ctx.creator.publicKey)[0]
+    );
+    expect(profile.totalPosted.toNumber()).to.equal(3);
+  });
+
+  it("Aborts the whole batch and names the item that failed", async () => {
+    const [counterPda] = deriveBountyCounterPda(
+      ctx.program.programId,
+      ctx.creator.publicKey
+    );
+    const before = await ctx.program.account.bountyCounter.fetch(counterPda);
+
+    try {
+      await postBatch([item("Fine"), item(""), item("Also fine")]);
+      expect.fail("Should have failed - empty description");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("EmptyDescription");
+      expect(err.logs.join("\n")).to.include("post_bounties: item 1 failed");
+    }
+
+    // nothing from the first item survived
+    const after = await ctx.program.account.bountyCounter.fetch(counterPda);
+    expect(after.nextId.toNumber()).to.equal(before.nextId.toNumber());
+    const [firstPda] = await nextBountyPda(ctx);
+    expect(await ctx.connection.getAccountInfo(firstPda)).to.be.null;
+  });
+
+  it("Fails for more bounties than a batch allows", async () => {
+    try {
+      await postBatch(
+        Array.from({ length: 6 }, (_, i) => item(`Bounty ${i}`))
+      );
+      expect.fail("Should have failed - batch too large");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidBatchSize");
+    }
+  });
+
+  it("Fails when an account is not the next bounty PDA", async () => {
+    const [nextPda] = await nextBountyPda(ctx);
+    await waitForPostCooldown(ctx.creator.publicKey);
+    try {
+      await ctx.program.methods
+        .postBounties([item("Out of order")])
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          rewardMint: ctx.usdcMint,
+          creatorTokenAccount: ctx.creatorTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .remainingAccounts([
+          {
+            pubkey: anchor.web3.Keypair.generate().publicKey,
+            isSigner: false,
+            isWritable: true,
+          },
+          {
+            pubkey: getAssociatedTokenAddressSync(ctx.usdcMint, nextPda),
+            isSigner: false,
+            isWritable: true,
+          },
+        ])
+        .signers([ctx.creator])
+        .rpc();
+      expect.fail("Should have failed - wrong bounty account");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BatchAccountMismatch");
+    }
+  });
+});
@@ -1,16 +1,34 @@
 import * as anchor from "@coral-xyz/anchor";
 import {
   TOKEN_PROGRAM_ID,
+  approve,
   createMint,
+  freezeAccount,
   getOrCreateAssociatedTokenAccount,
+  mintTo,
 } from "@solana/spl-token";
-import { Keypair, SystemProgram } from "@solana/web3.js";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
 import { expect } from "chai";
 import {
   setupTestContext,
   deriveBountyPda,
+  deriveConfigPda,
+  deriveCreatorProfilePda,
   getAssociatedTokenAddressSync,
-  createBountyTokenAccount,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  waitForPostCooldown,
+  bountyParams,
+  nextBountyPda,
+  fetchEvents,
+  findEvent,
+  generateSolutionHashWithValue,
+  SWITCHBOARD_FEED_FRESH,
   TestContext,
 } from "./helpers";
 
@@ -24,9 +42,8 @@ describe("post_bounty", () => {
     ctx = await setupTestContext();
   });
 
-  beforeEach(() => {
-    bountyId = Math.floor(Math.random() * 1000000);
-    [bountyPda] = deriveBountyPda(ctx.program.programId, bountyId);
+  beforeEach(async () => {
+    [bountyPda, bountyId] = await nextBountyPda(ctx);
     bountyTokenAccount = getAssociatedTokenAddressSync(
       ctx.usdcMint,
       bountyPda
@@ -41,24 +58,16 @@ describe("post_bounty", () => {
       ctx.creatorTokenAccount
     );
 
-    await createBountyTokenAccount(
-      ctx.connection,
-      ctx.creator,
-      ctx.usdcMint,
-      bountyPda
-    );
+    await waitForPostCooldown(ctx.creator.publicKey);
 
-    await ctx.program.methods
-      .postBounty(
-        new anchor.BN(bountyId),
-        { walletIntelligence: {} },
-        description,
-        new anchor.BN(reward)
-      )
+    const beforeTimestamp = Math.floor(Date.now() / 1000);
+
+    const signature = await ctx.program.methods
+      .postBounty(bountyParams(description, reward))
       .accountsPartial({
         creator: ctx.creator.publicKey,
         bounty: bountyPda,
-        usdcMint: ctx.usdcMint,
+        rewardMint: ctx.usdcMint,
         creatorTokenAccount: ctx.creatorTokenAccount,
         bountyTokenAccount: bountyTokenAccount,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -88,6 +97,73 @@ describe("post_bounty", () => {
       (Number(creatorBalanceBefore.value.amount) - reward).toString()
     );
     expect(bountyBalance.value.amount).to.equal(reward.toString());
+
+    const event = findEvent(await fetchEvents(ctx, signature), "BountyPosted");
+    expect(event.data.bounty.toString()).to.equal(bountyPda.toString());
+    expect(event.data.bountyId.toNumber()).to.equal(bountyId);
+    expect(event.data.creator.toString()).to.equal(
+      ctx.creator.publicKey.toString()
+    );
+    expect(event.data.reward.toNumber()).to.equal(reward);
+    expect(event.data.bountyType).to.deep.equal({ walletIntelligence: {} });
+    expect(event.data.timestamp.toNumber()).to.be.at.least(beforeTimestamp - 5);
+  });
+
+  it("Creates the escrow ATA in the posting instruction itself", async () => {
+    expect(await ctx.connection.getAccountInfo(bountyTokenAccount)).to.be.null;
+
+    await waitForPostCooldown(ctx.creator.publicKey);
+    const tx = await ctx.program.methods
+      .postBounty(bountyParams("One instruction", 10 * 10 ** 6))
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        rewardMint: ctx.usdcMint,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .transaction();
+    expect(tx.instructions).to.have.length(1);
+    await anchor.web3.sendAndConfirmTransaction(ctx.connection, tx, [
+      ctx.creator,
+    ]);
+
+    const escrow = await ctx.connection.getTokenAccountBalance(
+      bountyTokenAccount
+    );
+    expect(escrow.value.amount).to.equal((10 * 10 ** 6).toString());
+  });
+
+  it("Fails when the escrow account is not the bounty's ATA", async () => {
+    // an ATA for the right mint, but owned by some other key
+    const wrongAccount = getAssociatedTokenAddressSync(
+      ctx.usdcMint,
+      Keypair.generate().publicKey
+    );
+
+    await waitForPostCooldown(ctx.creator.publicKey);
+    try {
+      await ctx.program.methods
+        .postBounty(bountyParams("Wrong escrow", 10 * 10 ** 6))
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          rewardMint: ctx.usdcMint,
+          creatorTokenAccount: ctx.creatorTokenAccount,
+          bountyTokenAccount: wrongAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+      expect.fail("Should have failed - not the bounty's ATA");
+    } catch (err) {
+      expect(err.logs.join("\n")).to.include(
+        "Associated address does not match seed derivation"
+      );
+    }
   });
 
   it("Fails when creator has insufficient USDC", async () => {
@@ -95,16 +171,11 @@ describe("post_bounty", () => {
 
     try {
       await ctx.program.methods
-        .postBounty(
-          new anchor.BN(bountyId),
-          { walletIntelligence: {} },
-          "Expensive bounty",
-          new anchor.BN(reward)
-        )
+        .postBounty(bountyParams("Expensive bounty", reward))
         .accountsPartial({
           creator: ctx.creator.publicKey,
           bounty: bountyPda,
-          usdcMint: ctx.usdcMint,
+          rewardMint: ctx.usdcMint,
           creatorTokenAccount: ctx.creatorTokenAccount,
           bountyTokenAccount: bountyTokenAccount,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -115,10 +186,116 @@ describe("post_bounty", () => {
 
       expect.fail("Should have failed with insufficient balance");
     } catch (err) {
-      expect(err).to.exist;
+      expect(err.error.errorCode.code).to.equal("InsufficientRewardBalance");
     }
   });
 
+  it("Fails with InsufficientRentFunds when the rent payer is short", async () => {
+    // enough to exist, not to pay for the bounty and its escrow
+    const sponsor = Keypair.generate();
+    await airdropSol(ctx.connection, sponsor.publicKey, 0.001);
+
+    try {
+      await postBounty(ctx, "Underfunded rent", 10 * 10 ** 6, { sponsor });
+      expect.fail("Should have failed - sponsor can't cover the rent");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InsufficientRentFunds");
+    }
+  });
+
+  describe("creator token account pre-checks", () => {
+    const reward = 10 * 10 ** 6;
+    let freezer: Keypair;
+    let mint: PublicKey;
+    let creatorAccount: PublicKey;
+
+    // a fresh mint whose freeze authority isn't the creator, so it passes the mint checks
+    beforeEach(async () => {
+      freezer = Keypair.generate();
+      mint = await createMint(
+        ctx.connection,
+        ctx.creator,
+        ctx.creator.publicKey,
+        freezer.publicKey,
+        6
+      );
+      creatorAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          ctx.connection,
+          ctx.creator,
+          mint,
+          ctx.creator.publicKey
+        )
+      ).address;
+      await mintTo(
+        ctx.connection,
+        ctx.creator,
+        mint,
+        creatorAccount,
+        ctx.creator,
+        2 * reward
+      );
+    });
+
+    async function postInMint() {
+      await waitForPostCooldown(ctx.creator.publicKey);
+      const [pda] = await nextBountyPda(ctx);
+      return ctx.program.methods
+        .postBounty(bountyParams("Pre-checked bounty", reward))
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: pda,
+          rewardMint: mint,
+          creatorTokenAccount: creatorAccount,
+          bountyTokenAccount: getAssociatedTokenAddressSync(mint, pda),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+    }
+
+    it("Fails with TokenAccountFrozen when the creator's account is frozen", async () => {
+      await freezeAccount(
+        ctx.connection,
+        ctx.creator,
+        creatorAccount,
+        mint,
+        freezer
+      );
+
+      try {
+        await postInMint();
+        expect.fail("Should have failed - creator's account is frozen");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("TokenAccountFrozen");
+      }
+    });
+
+    it("Posts despite a delegate, logging that it may spend the rest", async () => {
+      const delegate = Keypair.generate().publicKey;
+      await approve(
+        ctx.connection,
+        ctx.creator,
+        creatorAccount,
+        delegate,
+        ctx.creator,
+        2 * reward
+      );
+
+      const signature = await postInMint();
+
+      await ctx.connection.confirmTransaction(signature, "confirmed");
+      const tx = await ctx.connection.getTransaction(signature, {
+        commitment: "confirmed",
+        maxSupportedTransactionVersion: 0,
+      });
+      expect(tx.meta.logMessages.join("\n")).to.include(
+        `post_bounty: delegate may spend ${2 * reward} but only ${reward} remains after escrow`
+      );
+    });
+  });
+
   it("Fails when token account mint doesn't match", async () => {
     const wrongMint = await createMint(
       ctx.connection,
@@ -137,16 +314,11 @@ describe("post_bounty", () => {
 
     try {
       await ctx.program.methods
-        .postBounty(
-          new anchor.BN(bountyId),
-          { walletIntelligence: {} },
-          "Test bounty",
-          new anchor.BN(100 * 10 ** 6)
-        )
+        .postBounty(bountyParams("Test bounty", 100 * 10 ** 6))
         .accountsPartial({
           creator: ctx.creator.publicKey,
           bounty: bountyPda,
-          usdcMint: ctx.usdcMint,
+          rewardMint: ctx.usdcMint,
           creatorTokenAccount: wrongTokenAccount.address,
           bountyTokenAccount: bountyTokenAccount,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -171,16 +343,11 @@ describe("post_bounty", () => {
 
     try {
       await ctx.program.methods
-        .postBounty(
-          new anchor.BN(bountyId),
-          { walletIntelligence: {} },
-          "Test bounty",
-          new anchor.BN(100 * 10 ** 6)
-        )
+        .postBounty(bountyParams("Test bounty", 100 * 10 ** 6))
         .accountsPartial({
           creator: ctx.creator.publicKey,
           bounty: bountyPda,
-          usdcMint: ctx.usdcMint,
+          rewardMint: ctx.usdcMint,
           creatorTokenAccount: ctx.creatorTokenAccount,
           bountyTokenAccount: wrongBountyTokenAccount.address,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -195,13 +362,21 @@ describe("post_bounty", () => {
     }
   });
 
-  it("Can post multiple bounties", async () => {
-    const bountyId1 = 1;
-    const bountyId2 = 2;
+  it("Assigns consecutive ids to back-to-back posts", async () => {
+    const bountyId1 = bountyId;
+    const bountyId2 = bountyId + 1;
     const reward = 50 * 10 ** 6;
 
-    const [bountyPda1] = deriveBountyPda(ctx.program.programId, bountyId1);
-    const [bountyPda2] = deriveBountyPda(ctx.program.programId, bountyId2);
+    const [bountyPda1] = deriveBountyPda(
+      ctx.program.programId,
+      ctx.creator.publicKey,
+      bountyId1
+    );
+    const [bountyPda2] = deriveBountyPda(
+      ctx.program.programId,
+      ctx.creator.publicKey,
+      bountyId2
+    );
 
     const bountyTokenAccount1 = getAssociatedTokenAddressSync(
       ctx.usdcMint,
@@ -212,25 +387,14 @@ describe("post_bounty", () => {
       bountyPda2
     );
 
-    await createBountyTokenAccount(
-      ctx.connection,
-      ctx.creator,
-      ctx.usdcMint,
-      bountyPda1
-    );
-    await createBountyTokenAccount(
-      ctx.connection,
-      ctx.creator,
-      ctx.usdcMint,
-      bountyPda2
-    );
+    await waitForPostCooldown(ctx.creator.publicKey);
 
     await ctx.program.methods
-      .postBounty(new anchor.BN(bountyId1), { walletIntelligence: {} }, "Bounty 1", new anchor.BN(reward))
+      .postBounty(bountyParams("Bounty 1", reward))
       .accountsPartial({
         creator: ctx.creator.publicKey,
         bounty: bountyPda1,
-        usdcMint: ctx.usdcMint,
+        rewardMint: ctx.usdcMint,
         creatorTokenAccount: ctx.creatorTokenAccount,
         bountyTokenAccount: bountyTokenAccount1,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -239,12 +403,14 @@ describe("post_bounty", () => {
       .signers([ctx.creator])
       .rpc();
 
+    await waitForPostCooldown(ctx.creator.publicKey);
+
     await ctx.program.methods
-      .postBounty(new anchor.BN(bountyId2), { walletIntelligence: {} }, "Bounty 2", new anchor.BN(reward))
+      .postBounty(bountyParams("Bounty 2", reward))
       .accountsPartial({
         creator: ctx.creator.publicKey,
         bounty: bountyPda2,
-        usdcMint: ctx.usdcMint,
+        rewardMint: ctx.usdcMint,
         creatorTokenAccount: ctx.creatorTokenAccount,
         bountyTokenAccount: bountyTokenAccount2,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -258,8 +424,357 @@ describe("post_bounty", () => {
 
     expect(bounty1.id.toNumber()).to.equal(bountyId1);
     expect(bounty2.id.toNumber()).to.equal(bountyId2);
+    expect(bountyPda1.toString()).to.not.equal(bountyPda2.toString());
     expect(bounty1.reward.toNumber()).to.equal(reward);
     expect(bounty2.reward.toNumber()).to.equal(reward);
   });
-});
 
+  it("Tracks open bounties on the creator profile", async () => {
+    const [creatorProfilePda] = deriveCreatorProfilePda(
+      ctx.program.programId,
+      ctx.creator.publicKey
+    );
+    const profileBefore = await ctx.program.account.creatorProfile.fetch(
+      creatorProfilePda
+    );
+
+    await postBounty(ctx, "Profile bounty", 10 * 10 ** 6);
+
+    const profileAfter = await ctx.program.account.creatorProfile.fetch(
+      creatorProfilePda
+    );
+    expect(profileAfter.creator.toString()).to.equal(
+      ctx.creator.publicKey.toString()
+    );
+    expect(profileAfter.openBountyCount).to.equal(
+      profileBefore.openBountyCount + 1
+    );
+    expect(profileAfter.lastPostTs.toNumber()).to.be.greaterThan(
+      profileBefore.lastPostTs.toNumber()
+    );
+  });
+
+  it("Fails when the deadline is not in the future", async () => {
+    await waitForPostCooldown(ctx.creator.publicKey);
+
+    try {
+      await ctx.program.methods
+        .postBounty(
+          bountyParams("Already over", 10 * 10 ** 6, {
+            deadline: Math.floor(Date.now() / 1000) - 60,
+          })
+        )
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          rewardMint: ctx.usdcMint,
+          creatorTokenAccount: ctx.creatorTokenAccount,
+          bountyTokenAccount: bountyTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+
+      expect.fail("Should have failed - deadline in the past");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidDeadline");
+    }
+  });
+
+  it("Fails when an oracle is required but no feed is named", async () => {
+    await waitForPostCooldown(ctx.creator.publicKey);
+
+    try {
+      await ctx.program.methods
+        .postBounty(
+          bountyParams("SOL/USD feed check", 10 * 10 ** 6, {
+            requiresOracle: true,
+          })
+        )
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          rewardMint: ctx.usdcMint,
+          creatorTokenAccount: ctx.creatorTokenAccount,
+          bountyTokenAccount: bountyTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+
+      expect.fail("Should have failed - oracle feed missing");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("OracleFeedRequired");
+    }
+  });
+
+  it("Fails when the same oracle feed is named twice", async () => {
+    await waitForPostCooldown(ctx.creator.publicKey);
+
+    try {
+      await ctx.program.methods
+        .postBounty(
+          bountyParams("SOL/USD feed check", 10 * 10 ** 6, {
+            requiresOracle: true,
+            oracleFeeds: [SWITCHBOARD_FEED_FRESH, SWITCHBOARD_FEED_FRESH],
+          })
+        )
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          rewardMint: ctx.usdcMint,
+          creatorTokenAccount: ctx.creatorTokenAccount,
+          bountyTokenAccount: bountyTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+
+      expect.fail("Should have failed - duplicate oracle feed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidOracleFeeds");
+    }
+  });
+
+  it("Fails when the oracle minimum is above the maximum", async () => {
+    await waitForPostCooldown(ctx.creator.publicKey);
+
+    try {
+      await ctx.program.methods
+        .postBounty(
+          bountyParams("SOL/USD band", 10 * 10 ** 6, {
+            requiresOracle: true,
+            oracleFeeds: [SWITCHBOARD_FEED_FRESH],
+            oracleMinValue: 16_000,
+            oracleMaxValue: 14_000,
+            oracleExponent: -2,
+          })
+        )
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          rewardMint: ctx.usdcMint,
+          creatorTokenAccount: ctx.creatorTokenAccount,
+          bountyTokenAccount: bountyTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+
+      expect.fail("Should have failed - empty oracle band");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidOracleBounds");
+    }
+  });
+
+  it("Fails when the attestation age limit is not positive", async () => {
+    await waitForPostCooldown(ctx.creator.publicKey);
+
+    try {
+      await ctx.program.methods
+        .postBounty(
+          bountyParams("No attestation window", 10 * 10 ** 6, {
+            maxAttestationAge: 0,
+          })
+        )
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          rewardMint: ctx.usdcMint,
+          creatorTokenAccount: ctx.creatorTokenAccount,
+          bountyTokenAccount: bountyTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+
+      expect.fail("Should have failed - attestation age limit of zero");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidAttestationAge");
+    }
+  });
+
+  it("Stores a prize schedule that adds up to the reward", async () => {
+    const prizedPda = await postBounty(
+      ctx,
+      "Three places",
+      10 * 10 ** 6,
+      { competitive: true, prizes: [5 * 10 ** 6, 3 * 10 ** 6, 2 * 10 ** 6] }
+    );
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(prizedPda);
+    expect(bountyAccount.prizes.map((prize) => prize.toNumber())).to.deep.equal(
+      [5 * 10 ** 6, 3 * 10 ** 6, 2 * 10 ** 6]
+    );
+  });
+
+  it("Fails when the prize schedule does not add up to the reward", async () => {
+    try {
+      await postBounty(ctx, "Three places", 10 * 10 ** 6, {
+        competitive: true,
+        prizes: [5 * 10 ** 6, 3 * 10 ** 6, 1 * 10 ** 6],
+      });
+      expect.fail("Should have failed - prizes short of the reward");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("PrizeScheduleMismatch");
+    }
+  });
+
+  it("Fails when a prize schedule is set on a non-competitive bounty", async () => {
+    try {
+      await postBounty(ctx, "One place", 10 * 10 ** 6, {
+        prizes: [10 * 10 ** 6],
+      });
+      expect.fail("Should have failed - prizes need competing submissions");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidPrizeSchedule");
+    }
+  });
+
+  it("Accepts a description of exactly 50 characters", async () => {
+    const description = "d".repeat(50);
+    const posted = await postBounty(ctx, description, 10 * 10 ** 6);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(posted);
+    expect(bountyAccount.description).to.equal(description);
+
+    // discriminator + Bounty::INIT_SPACE, with no padding on top
+    const info = await ctx.connection.getAccountInfo(posted);
+    expect(info.data.length).to.equal(8 + 1823);
+  });
+
+  it("Fails with DescriptionTooLong past 50 characters", async () => {
+    try {
+      await postBounty(ctx, "d".repeat(51), 10 * 10 ** 6);
+      expect.fail("Should have failed - description too long");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("DescriptionTooLong");
+    }
+  });
+
+  it("Fails with an empty description", async () => {
+    try {
+      await postBounty(ctx, "", 10 * 10 ** 6);
+      expect.fail("Should have failed - description empty");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("EmptyDescription");
+    }
+  });
+
+  describe("rate limiting", () => {
+    let spamCtx: TestContext;
+
+    before(async () => {
+      spamCtx = await setupTestContext();
+    });
+
+    it("Rejects a post inside the cooldown window", async () => {
+      await postBounty(spamCtx, "First", 1 * 10 ** 6);
+
+      const [nextPda] = await nextBountyPda(spamCtx);
+
+      try {
+        await spamCtx.program.methods
+          .postBounty(bountyParams("Too soon", 1 * 10 ** 6))
+          .accountsPartial({
+            creator: spamCtx.creator.publicKey,
+            bounty: nextPda,
+            rewardMint: spamCtx.usdcMint,
+            creatorTokenAccount: spamCtx.creatorTokenAccount,
+            bountyTokenAccount: getAssociatedTokenAddressSync(
+              spamCtx.usdcMint,
+              nextPda
+            ),
+            tokenProgram: TOKEN_PROGRAM_ID,
+            systemProgram: SystemProgram.programId,
+          })
+          .signers([spamCtx.creator])
+          .rpc();
+
+        expect.fail("Should have failed - post cooldown active");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("PostCooldownActive");
+      }
+    });
+
+    let fillerPda: PublicKey;
+
+    it("Rejects a post once the open bounty cap is reached", async () => {
+      const config = await spamCtx.program.account.config.fetch(
+        deriveConfigPda(spamCtx.program.programId)[0]
+      );
+      const maxOpenPerCreator = config.maxOpenPerCreator;
+      await ensureCreatorBalance(
+        spamCtx.connection,
+        spamCtx.creator,
+        spamCtx.usdcMint,
+        spamCtx.creatorTokenAccount,
+        (maxOpenPerCreator + 1) * 10 ** 6
+      );
+
+      const [creatorProfilePda] = deriveCreatorProfilePda(
+        spamCtx.program.programId,
+        spamCtx.creator.publicKey
+      );
+      let profile = await spamCtx.program.account.creatorProfile.fetch(
+        creatorProfilePda
+      );
+      while (profile.openBountyCount < maxOpenPerCreator) {
+        fillerPda = await postBounty(spamCtx, "Filler", 1 * 10 ** 6);
+        profile = await spamCtx.program.account.creatorProfile.fetch(
+          creatorProfilePda
+        );
+      }
+
+      try {
+        await postBounty(spamCtx, "One too many", 1 * 10 ** 6);
+        expect.fail("Should have failed - too many open bounties");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("TooManyOpenBounties");
+      }
+    });
+
+    it("Frees a slot once one of the open bounties settles", async () => {
+      const agent = Keypair.generate();
+      await airdropSol(spamCtx.connection, agent.publicKey);
+      const agentTokenAccount = await createAgentTokenAccount(
+        spamCtx.connection,
+        agent,
+        spamCtx.usdcMint
+      );
+      const hash = generateSolutionHashWithValue(0x5e);
+      const attestation = await createAttestation(
+        spamCtx,
+        agent,
+        fillerPda,
+        hash
+      );
+      await submitSolution(spamCtx, agent, fillerPda, attestation, hash);
+      await settleBounty(spamCtx, agent.publicKey, fillerPda, agentTokenAccount);
+
+      const [creatorProfilePda] = deriveCreatorProfilePda(
+        spamCtx.program.programId,
+        spamCtx.creator.publicKey
+      );
+      const before = await spamCtx.program.account.creatorProfile.fetch(
+        creatorProfilePda
+      );
+      expect(before.totalSettled.toNumber()).to.equal(1);
+
+      await postBounty(spamCtx, "Back under the cap", 1 * 10 ** 6);
+
+      const after = await spamCtx.program.account.creatorProfile.fetch(
+        creatorProfilePda
+      );
+      expect(after.openBountyCount).to.equal(before.openBountyCount + 1);
+      expect(after.totalPosted.toNumber()).to.equal(
+        before.totalPosted.toNumber() + 1
+      );
+    });
+  });
+});
@@ -0,0 +1,231 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID, mintTo } from "@solana/spl-token";
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveContributionPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  contributeToBounty,
+  createAttestation,
+  submitSolution,
+  updateConfig,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("refund_contribution", () => {
+  // amounts picked so no share divides evenly
+  const reward = 10_000_007;
+  const contributions = [3_333_331, 1_234_567, 7];
+  const challengeRewardBps = 1_000;
+
+  let ctx: TestContext;
+  let bountyPda: anchor.web3.PublicKey;
+  let bountyTokenAccount: anchor.web3.PublicKey;
+  let contributors: { keypair: Keypair; tokenAccount: anchor.web3.PublicKey }[];
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await updateConfig(ctx, {
+      challengeBondLamports: new anchor.BN(LAMPORTS_PER_SOL / 10),
+      challengeRewardBps,
+    });
+  });
+
+  after(async () => {
+    await updateConfig(ctx, {
+      challengeBondLamports: new anchor.BN(0),
+      challengeRewardBps: 0,
+    });
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+
+    bountyPda = await postBounty(ctx, "Crowdfunded", reward, {
+      challengePeriodSlots: 30,
+    });
+    bountyTokenAccount = getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda);
+
+    contributors = [];
+    for (const amount of contributions) {
+      const keypair = Keypair.generate();
+      await airdropSol(ctx.connection, keypair.publicKey);
+      const tokenAccount = await createAgentTokenAccount(
+        ctx.connection,
+        keypair,
+        ctx.usdcMint
+      );
+      await mintTo(
+        ctx.connection,
+        ctx.creator,
+        ctx.usdcMint,
+        tokenAccount,
+        ctx.creator,
+        amount
+      );
+      await contributeToBounty(ctx, keypair, tokenAccount, bountyPda, amount);
+      contributors.push({ keypair, tokenAccount });
+    }
+  });
+
+  async function tokenBalance(account: anchor.web3.PublicKey): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  async function cancel() {
+    await ctx.program.methods
+      .cancelBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        unwrapAccount: null,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function refund(index: number) {
+    const { keypair, tokenAccount } = contributors[index];
+    await ctx.program.methods
+      .refundContribution()
+      .accountsPartial({
+        contributor: keypair.publicKey,
+        bounty: bountyPda,
+        contributorTokenAccount: tokenAccount,
+        bountyTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+  }
+
+  it("Refunds every contributor exactly what they put in on cancellation", async () => {
+    const creatorBefore = await tokenBalance(ctx.creatorTokenAccount);
+    await cancel();
+    expect(await tokenBalance(ctx.creatorTokenAccount)).to.equal(
+      creatorBefore + reward
+    );
+
+    for (let i = 0; i < contributors.length; i++) {
+      await refund(i);
+      expect(await tokenBalance(contributors[i].tokenAccount)).to.equal(
+        contributions[i]
+      );
+      const [contributionPda] = deriveContributionPda(
+        ctx.program.programId,
+        bountyPda,
+        contributors[i].keypair.publicKey
+      );
+      expect(await ctx.connection.getAccountInfo(contributionPda)).to.be.null;
+    }
+
+    expect(await tokenBalance(bountyTokenAccount)).to.equal(0);
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.contributed.toNumber()).to.equal(0);
+    expect(bountyAccount.contributorPool.toNumber()).to.equal(0);
+  });
+
+  it("Shares a shrunken escrow pro rata and leaves nothing behind", async () => {
+    // an upheld challenge pays part of the escrow away before the creator cancels
+    const agent = Keypair.generate();
+    const challenger = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    await airdropSol(ctx.connection, challenger.publicKey);
+    const challengerTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      challenger,
+      ctx.usdcMint
+    );
+    const hash = generateSolutionHashWithValue(0xe7);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    await ctx.program.methods
+      .challengeSolution()
+      .accountsPartial({ challenger: challenger.publicKey, bounty: bountyPda })
+      .signers([challenger])
+      .rpc();
+    await ctx.program.methods
+      .resolveChallenge(true)
+      .accountsPartial({
+        admin: ctx.provider.wallet.publicKey,
+        bounty: bountyPda,
+        challenger: challenger.publicKey,
+        creator: ctx.creator.publicKey,
+        agent: agent.publicKey,
+        challengerTokenAccount,
+        bountyTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .rpc();
+
+    const contributed = contributions.reduce((sum, c) => sum + c, 0);
+    const funded = reward + contributed;
+    const balance = await tokenBalance(bountyTokenAccount);
+    expect(balance).to.be.below(funded);
+
+    const creatorBefore = await tokenBalance(ctx.creatorTokenAccount);
+    await cancel();
+    const pool = Math.floor((balance * contributed) / funded);
+    expect(await tokenBalance(ctx.creatorTokenAccount)).to.equal(
+      creatorBefore + balance - pool
+    );
+
+    let remainingPool = pool;
+    let remainingContributed = contributed;
+    let refunded = 0;
+    for (let i = 0; i < contributors.length; i++) {
+      const expected = Math.floor(
+        (contributions[i] * remainingPool) / remainingContributed
+      );
+      await refund(i);
+      expect(await tokenBalance(contributors[i].tokenAccount)).to.equal(
+        expected
+      );
+      remainingPool -= expected;
+      remainingContributed -= contributions[i];
+      refunded += expected;
+    }
+
+    expect(refunded).to.equal(pool);
+    expect(await tokenBalance(bountyTokenAccount)).to.equal(0);
+  });
+
+  it("Fails to refund a contribution twice", async () => {
+    await cancel();
+    await refund(0);
+
+    try {
+      await refund(0);
+      expect.fail("Should have failed - contribution already refunded");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  it("Fails to refund while the bounty is still open", async () => {
+    try {
+      await refund(0);
+      expect.fail("Should have failed - bounty still open");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotFinished");
+    }
+  });
+});
@@ -0,0 +1,157 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  DEFAULT_MARKET,
+  setupTestContext,
+  DEFAULT_HASH_ALGO,
+  deriveAttestationPda,
+  deriveAttestorPda,
+  airdropSol,
+  ensureCreatorBalance,
+  ensureReputation,
+  nextSolutionId,
+  postBounty,
+  generateSolutionHash,
+  TestContext,
+} from "./helpers";
+
+describe("register_attestor", () => {
+  let ctx: TestContext;
+  let agent: Keypair;
+  let bountyPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    bountyPda = await postBounty(ctx, "Registry bounty", 10 * 10 ** 6);
+  });
+
+  beforeEach(async () => {
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+  });
+
+  async function register(name: string, signer?: Keypair): Promise<void> {
+    const builder = ctx.program.methods
+      .registerAttestor(DEFAULT_MARKET, agent.publicKey, name)
+      .accountsPartial({
+        admin: signer ? signer.publicKey : ctx.provider.wallet.publicKey,
+      });
+    await (signer ? builder.signers([signer]) : builder).rpc();
+  }
+
+  async function remove(): Promise<void> {
+    const [attestorPda] = deriveAttestorPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    await ctx.program.methods
+      .removeAttestor(DEFAULT_MARKET)
+      .accountsPartial({
+        admin: ctx.provider.wallet.publicKey,
+        attestorAccount: attestorPda,
+      })
+      .rpc();
+  }
+
+  async function attest(): Promise<anchor.web3.PublicKey> {
+    await ensureReputation(ctx, agent);
+    const [attestationPda] = deriveAttestationPda(
+      ctx.program.programId,
+      agent.publicKey,
+      bountyPda,
+      await nextSolutionId(ctx, agent.publicKey)
+    );
+    await ctx.program.methods
+      .attestSolution(Array.from(generateSolutionHash()), DEFAULT_HASH_ALGO)
+      .accountsPartial({
+        agent: agent.publicKey,
+        bounty: bountyPda,
+        attestation: attestationPda,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent])
+      .rpc();
+    return attestationPda;
+  }
+
+  it("Lets a registered attestor attest", async () => {
+    await register("Screening service");
+
+    const [attestorPda] = deriveAttestorPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    const attestor = await ctx.program.account.attestor.fetch(attestorPda);
+    expect(attestor.authority.toString()).to.equal(agent.publicKey.toString());
+    expect(attestor.name).to.equal("Screening service");
+    expect(attestor.active).to.be.true;
+    expect(attestor.registeredAt.toNumber()).to.be.greaterThan(0);
+
+    const attestationPda = await attest();
+    const attestation = await ctx.program.account.attestation.fetch(
+      attestationPda
+    );
+    expect(attestation.agent.toString()).to.equal(agent.publicKey.toString());
+  });
+
+  it("Fails when the signer was never registered", async () => {
+    try {
+      await attest();
+      expect.fail("Should have failed - unregistered attestor");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnregisteredAttestor");
+    }
+  });
+
+  it("Fails once the attestor is removed", async () => {
+    await register("Soon gone");
+    await remove();
+
+    const [attestorPda] = deriveAttestorPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    const attestor = await ctx.program.account.attestor.fetch(attestorPda);
+    expect(attestor.active).to.be.false;
+
+    try {
+      await attest();
+      expect.fail("Should have failed - attestor deactivated");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnregisteredAttestor");
+    }
+  });
+
+  it("Reactivates on re-registration instead of failing", async () => {
+    await register("First run");
+    await remove();
+    await register("Second run");
+
+    const [attestorPda] = deriveAttestorPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    const attestor = await ctx.program.account.attestor.fetch(attestorPda);
+    expect(attestor.active).to.be.true;
+    expect(attestor.name).to.equal("Second run");
+
+    await attest();
+  });
+
+  it("Fails when a non-admin registers an attestor", async () => {
+    try {
+      await register("Self-appointed", agent);
+      expect.fail("Should have failed - not the admin");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedAdmin");
+    }
+  });
+});
@@ -0,0 +1,221 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  deriveSubmissionPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  rejectSolution,
+  generateSolutionHashWithValue,
+  waitForClock,
+  TestContext,
+} from "./helpers";
+
+describe("reject_solution", () => {
+  let ctx: TestContext;
+  let agent: Keypair;
+  let bountyPda: anchor.web3.PublicKey;
+  let reputationPda: anchor.web3.PublicKey;
+  let attestationPda: anchor.web3.PublicKey;
+  let solutionHash: Buffer;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+
+    bountyPda = await postBounty(ctx, "Needs real work", 25 * 10 ** 6);
+
+    solutionHash = generateSolutionHashWithValue(0x0b);
+    attestationPda = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      solutionHash
+    );
+    await submitSolution(ctx, agent, bountyPda, attestationPda, solutionHash);
+
+    [reputationPda] = deriveReputationPda(ctx.program.programId, agent.publicKey);
+  });
+
+  it("Reopens the bounty and records the failure", async () => {
+    await rejectSolution(ctx, agent.publicKey, bountyPda);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ open: {} });
+    expect(bountyAccount.solutionHash).to.be.null;
+    expect(bountyAccount.solver).to.be.null;
+
+    const reputation = await ctx.program.account.reputation.fetch(reputationPda);
+    expect(reputation.failedBounties.toNumber()).to.equal(1);
+  });
+
+  it("Lets a different agent submit and settle after a rejection", async () => {
+    await rejectSolution(ctx, agent.publicKey, bountyPda);
+
+    const agent2 = Keypair.generate();
+    await airdropSol(ctx.connection, agent2.publicKey);
+    const agent2TokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent2,
+      ctx.usdcMint
+    );
+
+    const solutionHash2 = generateSolutionHashWithValue(0x0c);
+    const attestationPda2 = await createAttestation(
+      ctx,
+      agent2,
+      bountyPda,
+      solutionHash2
+    );
+    await submitSolution(ctx, agent2, bountyPda, attestationPda2, solutionHash2);
+    await settleBounty(ctx, agent2.publicKey, bountyPda, agent2TokenAccount);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ settled: {} });
+
+    const agent2Balance = await ctx.connection.getTokenAccountBalance(
+      agent2TokenAccount
+    );
+    expect(agent2Balance.value.amount).to.equal((25 * 10 ** 6).toString());
+  });
+
+  it("Needs a fresh attestation for the agent to try again", async () => {
+    await rejectSolution(ctx, agent.publicKey, bountyPda);
+
+    try {
+      await submitSolution(ctx, agent, bountyPda, attestationPda, solutionHash);
+      expect.fail("Should have failed - attestation already consumed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AttestationAlreadyConsumed");
+    }
+
+    const retryHash = generateSolutionHashWithValue(0x0d);
+    const retryAttestation = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      retryHash
+    );
+    await submitSolution(ctx, agent, bountyPda, retryAttestation, retryHash);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+    expect(Buffer.from(bountyAccount.solutionHash)).to.deep.equal(retryHash);
+  });
+
+  it("Fails with NotRejectable on an open bounty", async () => {
+    await rejectSolution(ctx, agent.publicKey, bountyPda);
+
+    try {
+      await rejectSolution(ctx, agent.publicKey, bountyPda);
+      expect.fail("Should have failed - bounty is open");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotRejectable");
+    }
+  });
+
+  it("Fails with NotRejectable on a settled bounty", async () => {
+    const agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+
+    try {
+      await rejectSolution(ctx, agent.publicKey, bountyPda);
+      expect.fail("Should have failed - bounty is settled");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotRejectable");
+    }
+  });
+
+  describe("resubmission cooldown", () => {
+    const cooldown = 3;
+
+    // a fresh bounty with a cooldown, where `agent` has just been rejected
+    async function rejectedOnCooldownBounty(): Promise<anchor.web3.PublicKey> {
+      const cooldownBounty = await postBounty(
+        ctx,
+        "Reviewed at a steady pace",
+        25 * 10 ** 6,
+        { resubmissionCooldownSeconds: cooldown }
+      );
+      const hash = generateSolutionHashWithValue(0x0e);
+      const attestation = await createAttestation(
+        ctx,
+        agent,
+        cooldownBounty,
+        hash
+      );
+      await submitSolution(ctx, agent, cooldownBounty, attestation, hash);
+      await rejectSolution(ctx, agent.publicKey, cooldownBounty);
+      return cooldownBounty;
+    }
+
+    async function resubmit(submitter: Keypair, bounty: anchor.web3.PublicKey) {
+      const hash = generateSolutionHashWithValue(0x0f);
+      const attestation = await createAttestation(ctx, submitter, bounty, hash);
+      await submitSolution(ctx, submitter, bounty, attestation, hash);
+    }
+
+    it("Fails when the rejected agent resubmits straight away", async () => {
+      const cooldownBounty = await rejectedOnCooldownBounty();
+
+      const bountyAccount = await ctx.program.account.bounty.fetch(cooldownBounty);
+      expect(bountyAccount.resubmissionCooldownSeconds.toNumber()).to.equal(cooldown);
+
+      try {
+        await resubmit(agent, cooldownBounty);
+        expect.fail("Should have failed - cooldown active");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("CooldownActive");
+      }
+    });
+
+    it("Lets the rejected agent resubmit once the cooldown has passed", async () => {
+      const cooldownBounty = await rejectedOnCooldownBounty();
+      const submission = await ctx.program.account.submission.fetch(
+        deriveSubmissionPda(ctx.program.programId, cooldownBounty, agent.publicKey)[0]
+      );
+      expect(submission.rejectedAt.toNumber()).to.be.greaterThan(0);
+
+      await waitForClock(ctx.connection, submission.rejectedAt.toNumber() + cooldown);
+      await resubmit(agent, cooldownBounty);
+
+      const bountyAccount = await ctx.program.account.bounty.fetch(cooldownBounty);
+      expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+      expect(bountyAccount.solver.toString()).to.equal(agent.publicKey.toString());
+    });
+
+    it("Does not hold back other agents", async () => {
+      const cooldownBounty = await rejectedOnCooldownBounty();
+      const agent2 = Keypair.generate();
+      await airdropSol(ctx.connection, agent2.publicKey);
+
+      await resubmit(agent2, cooldownBounty);
+
+      const bountyAccount = await ctx.program.account.bounty.fetch(cooldownBounty);
+      expect(bountyAccount.solver.toString()).to.equal(agent2.publicKey.toString());
+    });
+  });
+});
@@ -0,0 +1,260 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  nextRejectionPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  rejectSolution,
+  updateConfig,
+  generateSolutionHashWithValue,
+  fetchEvents,
+  findEvent,
+  waitForClock,
+  RejectionReasonEnum,
+  TestContext,
+} from "./helpers";
+
+describe("rejection_appeal", () => {
+  const reward = 10 * 10 ** 6;
+  const appealWindow = 4;
+
+  let ctx: TestContext;
+  let arbiter: Keypair;
+  let agent: Keypair;
+  let rival: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    arbiter = Keypair.generate();
+    await airdropSol(ctx.connection, arbiter.publicKey);
+    await updateConfig(ctx, {
+      appealWindowSeconds: new anchor.BN(appealWindow),
+    });
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    rival = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    await airdropSol(ctx.connection, rival.publicKey);
+  });
+
+  after(async () => {
+    await updateConfig(ctx, { appealWindowSeconds: new anchor.BN(0) });
+  });
+
+  async function submit(submitter: Keypair, bountyPda: PublicKey) {
+    const hash = generateSolutionHashWithValue(0x3a);
+    const attestation = await createAttestation(
+      ctx,
+      submitter,
+      bountyPda,
+      hash
+    );
+    await submitSolution(ctx, submitter, bountyPda, attestation, hash);
+  }
+
+  // a bounty `agent` submitted to and was then rejected from
+  async function rejected(
+    reason: RejectionReasonEnum = { doesNotMeetCriteria: {} }
+  ) {
+    const bountyPda = await postBounty(ctx, "Appealable audit", reward, {
+      challengePeriodSlots: 0,
+      arbiter: arbiter.publicKey,
+    });
+    await submit(agent, bountyPda);
+    const rejection = await rejectSolution(
+      ctx,
+      agent.publicKey,
+      bountyPda,
+      reason
+    );
+    return { bountyPda, rejection };
+  }
+
+  function appeal(bountyPda: PublicKey, rejection: PublicKey) {
+    return ctx.program.methods
+      .appealRejection()
+      .accountsPartial({ agent: agent.publicKey, bounty: bountyPda, rejection })
+      .signers([agent])
+      .rpc();
+  }
+
+  function resolveAppeal(
+    bountyPda: PublicKey,
+    rejection: PublicKey,
+    uphold: boolean,
+    signer: Keypair = arbiter
+  ) {
+    return ctx.program.methods
+      .resolveAppeal(uphold)
+      .accountsPartial({
+        arbiter: signer.publicKey,
+        bounty: bountyPda,
+        rejection,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function settleWithRival(bountyPda: PublicKey) {
+    const rivalTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      rival,
+      ctx.usdcMint
+    );
+    return settleBounty(ctx, rival.publicKey, bountyPda, rivalTokenAccount);
+  }
+
+  function fetchReputation() {
+    return ctx.program.account.reputation.fetch(
+      deriveReputationPda(ctx.program.programId, agent.publicKey)[0]
+    );
+  }
+
+  it("Records each rejection with its reason, note and signer", async () => {
+    const bountyPda = await postBounty(ctx, "Appealable audit", reward);
+    await submit(agent, bountyPda);
+    const rejectionPda = await nextRejectionPda(
+      ctx,
+      bountyPda,
+      agent.publicKey
+    );
+    const noteHash = Buffer.alloc(32, 0x7e);
+
+    const sig = await ctx.program.methods
+      .rejectSolution({ incompleteData: {} }, Array.from(noteHash))
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        agent: agent.publicKey,
+        rejection: rejectionPda,
+      })
+      .signers([ctx.creator])
+      .rpc();
+
+    const rejection = await ctx.program.account.rejection.fetch(rejectionPda);
+    expect(rejection.bounty.toString()).to.equal(bountyPda.toString());
+    expect(rejection.agent.toString()).to.equal(agent.publicKey.toString());
+    expect(rejection.attemptIndex).to.equal(0);
+    expect(rejection.reason).to.deep.equal({ incompleteData: {} });
+    expect(Buffer.from(rejection.noteHash)).to.deep.equal(noteHash);
+    expect(rejection.rejectedBy.toString()).to.equal(
+      ctx.creator.publicKey.toString()
+    );
+    expect(rejection.appeal).to.deep.equal({ unappealed: {} });
+
+    const event = findEvent(await fetchEvents(ctx, sig), "SolutionRejected");
+    expect(event.data.rejection.toString()).to.equal(rejectionPda.toString());
+    expect(event.data.attemptIndex).to.equal(0);
+    expect(event.data.reason).to.deep.equal({ incompleteData: {} });
+    expect(Buffer.from(event.data.noteHash)).to.deep.equal(noteHash);
+    expect(event.data.rejectedBy.toString()).to.equal(
+      ctx.creator.publicKey.toString()
+    );
+
+    // the next rejection, on any bounty, takes the next index
+    const { rejection: second } = await rejected({ other: { 0: 7 } });
+    const secondRecord = await ctx.program.account.rejection.fetch(second);
+    expect(secondRecord.attemptIndex).to.equal(1);
+    expect(secondRecord.noteHash).to.be.null;
+    expect((await fetchReputation()).rejections).to.equal(2);
+  });
+
+  it("Counts plagiarism rejections on the agent's reputation", async () => {
+    await rejected({ plagiarized: {} });
+    await rejected();
+
+    const reputation = await fetchReputation();
+    expect(reputation.plagiarized).to.equal(1);
+    expect(reputation.failedBounties.toNumber()).to.equal(2);
+  });
+
+  it("Holds up settling with another agent until the appeal window passes", async () => {
+    const { bountyPda, rejection } = await rejected();
+    await appeal(bountyPda, rejection);
+    try {
+      await appeal(bountyPda, rejection);
+      expect.fail("Should have failed - already appealed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AlreadyAppealed");
+    }
+
+    await submit(rival, bountyPda);
+    try {
+      await settleWithRival(bountyPda);
+      expect.fail("Should have failed - appeal pending");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AppealPending");
+    }
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.appellant.toString()).to.equal(agent.publicKey.toString());
+    await waitForClock(ctx.connection, bounty.appealEndsAt.toNumber());
+    await settleWithRival(bountyPda);
+
+    const record = await ctx.program.account.rejection.fetch(rejection);
+    expect(record.appeal).to.deep.equal({ contested: {} });
+  });
+
+  it("Refuses an appeal once the window has passed", async () => {
+    const { bountyPda, rejection } = await rejected();
+    const record = await ctx.program.account.rejection.fetch(rejection);
+    await waitForClock(
+      ctx.connection,
+      record.rejectedAt.toNumber() + appealWindow
+    );
+
+    try {
+      await appeal(bountyPda, rejection);
+      expect.fail("Should have failed - window closed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AppealWindowClosed");
+    }
+  });
+
+  it("Lets the arbiter overturn a rejection and free settlement", async () => {
+    const { bountyPda, rejection } = await rejected({ plagiarized: {} });
+    await appeal(bountyPda, rejection);
+    await submit(rival, bountyPda);
+
+    try {
+      await resolveAppeal(bountyPda, rejection, true, rival);
+      expect.fail("Should have failed - not the arbiter");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedArbiter");
+    }
+
+    const sig = await resolveAppeal(bountyPda, rejection, true);
+    const event = findEvent(await fetchEvents(ctx, sig), "AppealResolved");
+    expect(event.data.upheld).to.equal(true);
+
+    const record = await ctx.program.account.rejection.fetch(rejection);
+    expect(record.appeal).to.deep.equal({ upheld: {} });
+    const reputation = await fetchReputation();
+    expect(reputation.failedBounties.toNumber()).to.equal(0);
+    expect(reputation.plagiarized).to.equal(0);
+    expect(reputation.rejections).to.equal(1);
+
+    // the ruling lifts the hold before the window is out
+    await settleWithRival(bountyPda);
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ settled: {} });
+    expect(bounty.appellant).to.be.null;
+  });
+});
@@ -0,0 +1,230 @@
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  deriveSubmissionPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  initReputation,
+  generateSolutionHashWithValue,
+  NO_CIPHERTEXT_HASH,
+  TestContext,
+} from "./helpers";
+
+// The provider wallet pays every transaction fee here, so the creator's and
+// agent's lamports only move for rent.
+describe("rent_sponsor", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+  let sponsor: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    sponsor = Keypair.generate();
+    await airdropSol(ctx.connection, sponsor.publicKey);
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    // the creator's profile and index then exist, so a sponsored post only
+    // pays for the bounty and its escrow
+    await postBounty(ctx, "Unsponsored audit", reward);
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  function balanceOf(account: PublicKey): Promise<number> {
+    return ctx.connection.getBalance(account);
+  }
+
+  async function bountyRent(bountyPda: PublicKey): Promise<number> {
+    const bountyTokenAccount = getAssociatedTokenAddressSync(
+      ctx.usdcMint,
+      bountyPda
+    );
+    return (await balanceOf(bountyPda)) + (await balanceOf(bountyTokenAccount));
+  }
+
+  async function cancel(bountyPda: PublicKey) {
+    await ctx.program.methods
+      .cancelBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        unwrapAccount: null,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  function closeBounty(bountyPda: PublicKey, rentPayer: PublicKey | null) {
+    return ctx.program.methods
+      .closeBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        creatorBond: null,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rentPayer,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  it("Lets a sponsor pay a post's rent, leaving the creator's SOL untouched", async () => {
+    const creatorBefore = await balanceOf(ctx.creator.publicKey);
+    const sponsorBefore = await balanceOf(sponsor.publicKey);
+
+    const bountyPda = await postBounty(ctx, "Sponsored audit", reward, {
+      sponsor,
+    });
+
+    expect(await balanceOf(ctx.creator.publicKey)).to.equal(creatorBefore);
+    expect(sponsorBefore - (await balanceOf(sponsor.publicKey))).to.equal(
+      await bountyRent(bountyPda)
+    );
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.creator.toString()).to.equal(
+      ctx.creator.publicKey.toString()
+    );
+    expect(bounty.rentPayer.toString()).to.equal(sponsor.publicKey.toString());
+  });
+
+  it("Returns a sponsored bounty's rent to the sponsor on close", async () => {
+    const bountyPda = await postBounty(ctx, "Sponsored audit", reward, {
+      sponsor,
+    });
+    await cancel(bountyPda);
+
+    try {
+      await closeBounty(bountyPda, null);
+      expect.fail("Should have failed - sponsor left out");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MissingRentPayer");
+    }
+    try {
+      await closeBounty(bountyPda, ctx.creator.publicKey);
+      expect.fail("Should have failed - the creator didn't pay the rent");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RentPayerMismatch");
+    }
+
+    const rent = await bountyRent(bountyPda);
+    const creatorBefore = await balanceOf(ctx.creator.publicKey);
+    const sponsorBefore = await balanceOf(sponsor.publicKey);
+
+    await closeBounty(bountyPda, sponsor.publicKey);
+
+    expect(await ctx.connection.getAccountInfo(bountyPda)).to.be.null;
+    expect(await balanceOf(ctx.creator.publicKey)).to.equal(creatorBefore);
+    expect(await balanceOf(sponsor.publicKey)).to.equal(sponsorBefore + rent);
+  });
+
+  it("Returns a sponsored submission's and reputation's rent to the sponsor", async () => {
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    await initReputation(ctx, agent, sponsor);
+
+    const bountyPda = await postBounty(ctx, "Sponsored solution", reward);
+    const [submissionPda] = deriveSubmissionPda(
+      ctx.program.programId,
+      bountyPda,
+      agent.publicKey
+    );
+    const hash = generateSolutionHashWithValue(0x5e);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    const agentBefore = await balanceOf(agent.publicKey);
+    await ctx.program.methods
+      .submitSolution(Array.from(hash), NO_CIPHERTEXT_HASH, "", [])
+      .accountsPartial({
+        submitter: agent.publicKey,
+        sponsor: sponsor.publicKey,
+        agent: agent.publicKey,
+        operatorRecord: null,
+        bounty: bountyPda,
+        attestation,
+        reputation: reputationPda,
+        application: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([agent, sponsor])
+      .rpc();
+
+    expect(await balanceOf(agent.publicKey)).to.equal(agentBefore);
+    const submission = await ctx.program.account.submission.fetch(
+      submissionPda
+    );
+    expect(submission.rentPayer.toString()).to.equal(
+      sponsor.publicKey.toString()
+    );
+    const reputation = await ctx.program.account.reputation.fetch(
+      reputationPda
+    );
+    expect(reputation.rentPayer.toString()).to.equal(
+      sponsor.publicKey.toString()
+    );
+
+    const submissionRent = await balanceOf(submissionPda);
+    let sponsorBefore = await balanceOf(sponsor.publicKey);
+    await ctx.program.methods
+      .withdrawSubmission()
+      .accountsPartial({
+        agent: agent.publicKey,
+        bounty: bountyPda,
+        rentPayer: sponsor.publicKey,
+      })
+      .signers([agent])
+      .rpc();
+    expect(await balanceOf(sponsor.publicKey)).to.equal(
+      sponsorBefore + submissionRent
+    );
+
+    const reputationRent = await balanceOf(reputationPda);
+    sponsorBefore = await balanceOf(sponsor.publicKey);
+    await ctx.program.methods
+      .closeReputation()
+      .accountsPartial({
+        agent: agent.publicKey,
+        rentPayer: sponsor.publicKey,
+      })
+      .signers([agent])
+      .rpc();
+    expect(await ctx.connection.getAccountInfo(reputationPda)).to.be.null;
+    expect(await balanceOf(sponsor.publicKey)).to.equal(
+      sponsorBefore + reputationRent
+    );
+    expect(await balanceOf(agent.publicKey)).to.equal(agentBefore);
+  });
+});
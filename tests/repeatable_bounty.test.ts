@@ -0,0 +1,206 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  deriveSettlementReceiptPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("repeatable_bounty", () => {
+  const reward = 5 * 10 ** 6;
+  const maxClaims = 3;
+
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  async function tokenBalance(account: PublicKey): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  async function postRepeatable(allowRepeatSolver = false): Promise<PublicKey> {
+    return postBounty(ctx, "Screen this week's new listings", reward, {
+      challengePeriodSlots: 0,
+      maxClaims,
+      allowRepeatSolver,
+    });
+  }
+
+  async function newAgent(): Promise<[Keypair, PublicKey]> {
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    return [agent, agentTokenAccount];
+  }
+
+  async function submit(agent: Keypair, bountyPda: PublicKey, value: number) {
+    const hash = generateSolutionHashWithValue(value);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+  }
+
+  it("Pays one reward per claim to three agents, then settles", async () => {
+    const creatorBefore = await tokenBalance(ctx.creatorTokenAccount);
+    const bountyPda = await postRepeatable();
+    const escrow = getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda);
+
+    // the whole run is escrowed up front
+    expect(await tokenBalance(ctx.creatorTokenAccount)).to.equal(
+      creatorBefore - reward * maxClaims
+    );
+    expect(await tokenBalance(escrow)).to.equal(reward * maxClaims);
+
+    for (let claim = 0; claim < maxClaims; claim++) {
+      const [agent, agentTokenAccount] = await newAgent();
+      await submit(agent, bountyPda, 0x90 + claim);
+      await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+
+      const left = maxClaims - claim - 1;
+      expect(await tokenBalance(agentTokenAccount)).to.equal(reward);
+      expect(await tokenBalance(escrow)).to.equal(reward * left);
+
+      const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bounty.claimsMade).to.equal(claim + 1);
+      expect(bounty.escrowAmount.toNumber()).to.equal(
+        left > 0 ? reward * left : reward
+      );
+      if (left > 0) {
+        expect(bounty.status).to.deep.equal({ open: {} });
+        expect(bounty.solutionHash).to.be.null;
+        expect(bounty.solver).to.be.null;
+      } else {
+        expect(bounty.status).to.deep.equal({ settled: {} });
+      }
+
+      const receipt = await ctx.program.account.settlementReceipt.fetch(
+        deriveSettlementReceiptPda(ctx.program.programId, bountyPda, claim)[0]
+      );
+      expect(receipt.claim).to.equal(claim);
+      expect(receipt.solver.toString()).to.equal(agent.publicKey.toString());
+      expect(receipt.grossReward.toNumber()).to.equal(reward);
+    }
+  });
+
+  it("Fails when an agent already paid a claim submits again", async () => {
+    const bountyPda = await postRepeatable();
+    const [agent, agentTokenAccount] = await newAgent();
+    await submit(agent, bountyPda, 0x94);
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+
+    try {
+      await submit(agent, bountyPda, 0x95);
+      expect.fail("Should have failed - agent already claimed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AlreadyClaimed");
+    }
+  });
+
+  it("Lets one agent take several claims when the bounty allows it", async () => {
+    const bountyPda = await postRepeatable(true);
+    const [agent, agentTokenAccount] = await newAgent();
+
+    await submit(agent, bountyPda, 0x96);
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+    await submit(agent, bountyPda, 0x97);
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+
+    expect(await tokenBalance(agentTokenAccount)).to.equal(2 * reward);
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.claimsMade).to.equal(2);
+    expect(bounty.status).to.deep.equal({ open: {} });
+  });
+
+  it("Refunds only the unclaimed rewards on cancel", async () => {
+    const bountyPda = await postRepeatable();
+    const escrow = getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda);
+    const [agent, agentTokenAccount] = await newAgent();
+    await submit(agent, bountyPda, 0x98);
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+
+    const creatorBefore = await tokenBalance(ctx.creatorTokenAccount);
+    await ctx.program.methods
+      .cancelBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        unwrapAccount: null,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount: escrow,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+
+    expect(await tokenBalance(ctx.creatorTokenAccount)).to.equal(
+      creatorBefore + reward * (maxClaims - 1)
+    );
+    expect(await tokenBalance(escrow)).to.equal(0);
+  });
+
+  it("Fails to post a repeatable bounty with prizes", async () => {
+    try {
+      await postBounty(ctx, "Repeatable contest", reward, {
+        competitive: true,
+        prizes: [reward],
+        maxClaims,
+      });
+      expect.fail("Should have failed - claims can't pay prizes");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidClaimSchedule");
+    }
+  });
+
+  it("Fails to change the reward of a repeatable bounty", async () => {
+    const bountyPda = await postRepeatable();
+
+    try {
+      await ctx.program.methods
+        .decreaseReward(new anchor.BN(1))
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          creatorTokenAccount: ctx.creatorTokenAccount,
+          bountyTokenAccount: getAssociatedTokenAddressSync(
+            ctx.usdcMint,
+            bountyPda
+          ),
+          rewardMint: ctx.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([ctx.creator])
+        .rpc();
+      expect.fail("Should have failed - reward is escrowed per claim");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RepeatableBounty");
+    }
+  });
+});
@@ -0,0 +1,122 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  updateConfig,
+  waitForClock,
+  fetchEvents,
+  findEvent,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("reputation_decay", () => {
+  const decayPeriodSeconds = 5;
+  const scoreUnit = 1 * 10 ** 6;
+  const reward = 8 * scoreUnit; // weight 4
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let reputationPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await updateConfig(ctx, {
+      scoreUnit: new anchor.BN(scoreUnit),
+      maxScorePerBounty: new anchor.BN(16),
+      decayPeriodSeconds: new anchor.BN(decayPeriodSeconds),
+    });
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+
+    const bountyPda = await postBounty(ctx, "Decay warm-up", reward);
+    const hash = generateSolutionHashWithValue(0xdc);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+  });
+
+  after(async () => {
+    await updateConfig(ctx, {
+      scoreUnit: new anchor.BN(0),
+      maxScorePerBounty: new anchor.BN(1),
+      decayPeriodSeconds: new anchor.BN(0),
+    });
+  });
+
+  function decayInstruction() {
+    return ctx.program.methods
+      .decayReputation()
+      .accountsPartial({ reputation: reputationPda })
+      .instruction();
+  }
+
+  it("Leaves a recently active agent alone", async () => {
+    const before = await ctx.program.account.reputation.fetch(reputationPda);
+    expect(before.score.toNumber()).to.equal(4);
+    expect(before.lastActiveTs.toNumber()).to.be.greaterThan(0);
+
+    await ctx.program.methods
+      .decayReputation()
+      .accountsPartial({ reputation: reputationPda })
+      .rpc();
+
+    const after = await ctx.program.account.reputation.fetch(reputationPda);
+    expect(after.score.toNumber()).to.equal(4);
+    expect(after.lastDecayedTs.toNumber()).to.equal(0);
+  });
+
+  it("Halves the score once per idle period and only once per period", async () => {
+    const before = await ctx.program.account.reputation.fetch(reputationPda);
+    const lastActive = before.lastActiveTs.toNumber();
+    await waitForClock(ctx.connection, lastActive + decayPeriodSeconds);
+
+    // a second crank in the same transaction finds nothing left to decay
+    const tx = new anchor.web3.Transaction().add(
+      await decayInstruction(),
+      await decayInstruction()
+    );
+    const signature = await ctx.provider.sendAndConfirm(tx);
+
+    const after = await ctx.program.account.reputation.fetch(reputationPda);
+    const periods =
+      (after.lastDecayedTs.toNumber() - lastActive) / decayPeriodSeconds;
+    expect(Number.isInteger(periods)).to.be.true;
+    expect(periods).to.be.at.least(1);
+    expect(after.score.toNumber()).to.equal(4 >> periods);
+    expect(after.successfulBounties.toNumber()).to.equal(1);
+
+    const event = findEvent(
+      await fetchEvents(ctx, signature),
+      "ReputationDecayed"
+    );
+    expect(event.data.halvings).to.equal(periods);
+    expect(event.data.newScore.toNumber()).to.equal(after.score.toNumber());
+  });
+});
@@ -0,0 +1,178 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { keccak_256 } from "@noble/hashes/sha3";
+import { expect } from "chai";
+import {
+  DEFAULT_MARKET,
+  setupTestContext,
+  airdropSol,
+  generateRandomId,
+  TestContext,
+} from "./helpers";
+
+type Entry = { agent: PublicKey; score: number; successfulBounties: number };
+
+function u64(value: number): Buffer {
+  return new anchor.BN(value).toArrayLike(Buffer, "le", 8);
+}
+
+// keccak(0x00 || agent || score LE || successful_bounties LE)
+function leaf(entry: Entry): Buffer {
+  return Buffer.from(
+    keccak_256(
+      Buffer.concat([
+        Buffer.from([0]),
+        entry.agent.toBuffer(),
+        u64(entry.score),
+        u64(entry.successfulBounties),
+      ])
+    )
+  );
+}
+
+// keccak(0x01 || min || max), matching ReputationSnapshot::verify
+function node(a: Buffer, b: Buffer): Buffer {
+  const [left, right] = Buffer.compare(a, b) <= 0 ? [a, b] : [b, a];
+  return Buffer.from(keccak_256(Buffer.concat([Buffer.from([1]), left, right])));
+}
+
+describe("reputation_snapshot", () => {
+  let ctx: TestContext;
+  let entries: Entry[];
+  let leaves: Buffer[];
+  let root: Buffer;
+  let epoch: number;
+
+  before(async () => {
+    ctx = await setupTestContext();
+
+    entries = [3, 7, 12, 20].map((score, i) => ({
+      agent: Keypair.generate().publicKey,
+      score,
+      successfulBounties: i + 1,
+    }));
+    leaves = entries.map(leaf);
+    root = node(node(leaves[0], leaves[1]), node(leaves[2], leaves[3]));
+
+    epoch = generateRandomId();
+    await snapshot(epoch, root);
+  });
+
+  function snapshotPda(epochNumber: number): PublicKey {
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("snapshot"), Buffer.from(DEFAULT_MARKET), u64(epochNumber)],
+      ctx.program.programId
+    )[0];
+  }
+
+  async function snapshot(
+    epochNumber: number,
+    snapshotRoot: Buffer,
+    admin?: Keypair
+  ) {
+    const builder = ctx.program.methods
+      .snapshotReputation(
+        DEFAULT_MARKET,
+        new anchor.BN(epochNumber),
+        Array.from(snapshotRoot),
+        entries.length
+      )
+      .accountsPartial({
+        admin: admin ? admin.publicKey : ctx.provider.wallet.publicKey,
+        snapshot: snapshotPda(epochNumber),
+      });
+    await (admin ? builder.signers([admin]) : builder).rpc();
+  }
+
+  async function verify(epochNumber: number, entry: Entry, proof: Buffer[]) {
+    await ctx.program.methods
+      .verifyReputationProof(
+        DEFAULT_MARKET,
+        new anchor.BN(epochNumber),
+        entry.agent,
+        new anchor.BN(entry.score),
+        new anchor.BN(entry.successfulBounties),
+        proof.map((sibling) => Array.from(sibling))
+      )
+      .accountsPartial({ snapshot: snapshotPda(epochNumber) })
+      .rpc();
+  }
+
+  // agent 2 sits next to agent 3, under the pair of agents 0 and 1
+  function proofFor2(): Buffer[] {
+    return [leaves[3], node(leaves[0], leaves[1])];
+  }
+
+  it("Stores the root for the epoch", async () => {
+    const stored = await ctx.program.account.reputationSnapshot.fetch(
+      snapshotPda(epoch)
+    );
+    expect(stored.epoch.toNumber()).to.equal(epoch);
+    expect(Buffer.from(stored.root)).to.deep.equal(root);
+    expect(stored.leafCount).to.equal(entries.length);
+  });
+
+  it("Accepts a valid proof", async () => {
+    await verify(epoch, entries[2], proofFor2());
+    await verify(epoch, entries[0], [
+      leaves[1],
+      node(leaves[2], leaves[3]),
+    ]);
+  });
+
+  it("Rejects a proof for stats the agent doesn't have", async () => {
+    try {
+      await verify(epoch, { ...entries[2], score: 99 }, proofFor2());
+      expect.fail("Should have failed - inflated score");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidReputationProof");
+    }
+
+    try {
+      await verify(epoch, entries[2], [leaves[0], leaves[1]]);
+      expect.fail("Should have failed - wrong siblings");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidReputationProof");
+    }
+  });
+
+  it("Rejects a proof checked against another epoch", async () => {
+    const otherEpoch = epoch + 1;
+    await snapshot(otherEpoch, node(leaves[0], leaves[1]));
+
+    try {
+      await verify(otherEpoch, entries[2], proofFor2());
+      expect.fail("Should have failed - different epoch's root");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidReputationProof");
+    }
+
+    try {
+      await verify(otherEpoch + 1, entries[2], proofFor2());
+      expect.fail("Should have failed - no snapshot for that epoch");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+
+  it("Refuses to overwrite an epoch", async () => {
+    try {
+      await snapshot(epoch, node(leaves[2], leaves[3]));
+      expect.fail("Should have failed - epoch already snapshotted");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SnapshotAlreadyExists");
+    }
+  });
+
+  it("Refuses snapshots from anyone but the admin", async () => {
+    const stranger = Keypair.generate();
+    await airdropSol(ctx.connection, stranger.publicKey);
+
+    try {
+      await snapshot(epoch + 100, root, stranger);
+      expect.fail("Should have failed - not the admin");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedAdmin");
+    }
+  });
+});
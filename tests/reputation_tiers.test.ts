@@ -0,0 +1,111 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  generateSolutionHashWithValue,
+  PostBountyOptions,
+  TestContext,
+} from "./helpers";
+
+describe("reputation tiers", () => {
+  const reward = 1 * 10 ** 6;
+  const bronzeTierSuccesses = 5;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let reputationPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+  });
+
+  async function win(bountyType: PostBountyOptions["bountyType"]) {
+    const bountyPda = await postBounty(ctx, "Typed work", reward, {
+      bountyType,
+    });
+    const hash = generateSolutionHashWithValue(0x7e);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+  }
+
+  it("Counts settled bounties by type", async () => {
+    await win({ walletIntelligence: {} });
+    let reputation = await ctx.program.account.reputation.fetch(reputationPda);
+    expect(reputation.completedByType).to.deep.equal([1, 0, 0, 0, 0]);
+
+    await win({ tokenScreening: {} });
+    await win({ custom: { 0: 7 } });
+    reputation = await ctx.program.account.reputation.fetch(reputationPda);
+    expect(reputation.completedByType).to.deep.equal([1, 1, 0, 0, 1]);
+    expect(reputation.successfulBounties.toNumber()).to.equal(3);
+    expect(reputation.tier).to.equal(0);
+  });
+
+  it("Rolls over to the bronze tier at the threshold", async () => {
+    for (let i = 1; i < bronzeTierSuccesses; i++) {
+      await win({ walletIntelligence: {} });
+    }
+    let reputation = await ctx.program.account.reputation.fetch(reputationPda);
+    expect(reputation.successfulBounties.toNumber()).to.equal(
+      bronzeTierSuccesses - 1
+    );
+    expect(reputation.tier).to.equal(0);
+
+    await win({ tokenScreening: {} });
+    reputation = await ctx.program.account.reputation.fetch(reputationPda);
+    expect(reputation.completedByType[0]).to.equal(bronzeTierSuccesses - 1);
+    expect(reputation.completedByType[1]).to.equal(1);
+    expect(reputation.tier).to.equal(1);
+  });
+
+  it("Refuses to migrate a reputation already on the current layout", async () => {
+    await win({ dataLabeling: {} });
+
+    try {
+      await ctx.program.methods
+        .migrateReputation()
+        .accountsPartial({
+          payer: agent.publicKey,
+          agent: agent.publicKey,
+          reputation: reputationPda,
+        })
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have failed - already migrated");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ReputationAlreadyMigrated");
+    }
+  });
+});
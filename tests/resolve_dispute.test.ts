@@ -0,0 +1,224 @@
+import * as anchor from "@coral-xyz/anchor";
+import { IdlTypes } from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveCreatorProfilePda,
+  deriveReputationPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+import { Bountyforge } from "../target/types/bountyforge";
+
+type DisputeOutcome = IdlTypes<Bountyforge>["disputeOutcome"];
+
+describe("resolve_dispute", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let arbiter: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let bountyPda: anchor.web3.PublicKey;
+  let bountyTokenAccount: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    arbiter = Keypair.generate();
+    await airdropSol(ctx.connection, arbiter.publicKey);
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+
+    bountyPda = await postBounty(
+      ctx,
+      "Arbitrated bounty",
+      reward,
+      { arbiter: arbiter.publicKey }
+    );
+    bountyTokenAccount = getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda);
+
+    const hash = generateSolutionHashWithValue(0xe0);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+  });
+
+  async function openDispute(party: Keypair): Promise<void> {
+    await ctx.program.methods
+      .openDispute()
+      .accountsPartial({ party: party.publicKey, bounty: bountyPda })
+      .signers([party])
+      .rpc();
+  }
+
+  async function resolveDispute(
+    outcome: DisputeOutcome,
+    signer: Keypair = arbiter
+  ): Promise<void> {
+    await ctx.program.methods
+      .resolveDispute(outcome)
+      .accountsPartial({
+        arbiter: signer.publicKey,
+        bounty: bountyPda,
+        creator: ctx.creator.publicKey,
+        agent: agent.publicKey,
+        agentTokenAccount,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function tokenBalance(account: anchor.web3.PublicKey): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  async function fetchRecords() {
+    const [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    const [creatorProfilePda] = deriveCreatorProfilePda(
+      ctx.program.programId,
+      ctx.creator.publicKey
+    );
+    return {
+      reputation: await ctx.program.account.reputation.fetch(reputationPda),
+      creatorProfile: await ctx.program.account.creatorProfile.fetch(
+        creatorProfilePda
+      ),
+    };
+  }
+
+  it("Freezes settlement while disputed", async () => {
+    await openDispute(ctx.creator);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ disputed: {} });
+
+    try {
+      await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+      expect.fail("Should have failed - bounty disputed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotSubmitted");
+    }
+  });
+
+  it("Pays the solver in full", async () => {
+    await openDispute(agent);
+    const before = await fetchRecords();
+    const creatorBefore = await tokenBalance(ctx.creatorTokenAccount);
+
+    await resolveDispute({ paySolver: {} });
+
+    expect(await tokenBalance(agentTokenAccount)).to.equal(reward);
+    expect(await tokenBalance(ctx.creatorTokenAccount)).to.equal(creatorBefore);
+    expect(await tokenBalance(bountyTokenAccount)).to.equal(0);
+
+    const after = await fetchRecords();
+    expect(after.reputation.successfulBounties.toNumber()).to.equal(
+      before.reputation.successfulBounties.toNumber() + 1
+    );
+    expect(after.creatorProfile.disputesLost).to.equal(
+      before.creatorProfile.disputesLost + 1
+    );
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ settled: {} });
+  });
+
+  it("Refunds the creator in full", async () => {
+    await openDispute(ctx.creator);
+    const before = await fetchRecords();
+    const creatorBefore = await tokenBalance(ctx.creatorTokenAccount);
+
+    await resolveDispute({ refundCreator: {} });
+
+    expect(await tokenBalance(agentTokenAccount)).to.equal(0);
+    expect(await tokenBalance(ctx.creatorTokenAccount)).to.equal(
+      creatorBefore + reward
+    );
+
+    const after = await fetchRecords();
+    expect(after.reputation.failedBounties.toNumber()).to.equal(
+      before.reputation.failedBounties.toNumber() + 1
+    );
+    expect(after.creatorProfile.disputesLost).to.equal(
+      before.creatorProfile.disputesLost
+    );
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ cancelled: {} });
+  });
+
+  it("Splits the escrow by the given bps", async () => {
+    await openDispute(ctx.creator);
+    const before = await fetchRecords();
+    const creatorBefore = await tokenBalance(ctx.creatorTokenAccount);
+
+    await resolveDispute({ split: { 0: 3_000 } });
+
+    expect(await tokenBalance(agentTokenAccount)).to.equal(3 * 10 ** 6);
+    expect(await tokenBalance(ctx.creatorTokenAccount)).to.equal(
+      creatorBefore + 7 * 10 ** 6
+    );
+    expect(await tokenBalance(bountyTokenAccount)).to.equal(0);
+
+    const after = await fetchRecords();
+    expect(after.reputation.totalEarned.toNumber()).to.equal(
+      before.reputation.totalEarned.toNumber() + 3 * 10 ** 6
+    );
+  });
+
+  it("Fails when someone other than the arbiter resolves", async () => {
+    await openDispute(ctx.creator);
+
+    try {
+      await resolveDispute({ paySolver: {} }, agent);
+      expect.fail("Should have failed - not the arbiter");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedArbiter");
+    }
+  });
+
+  it("Fails to dispute a bounty without an arbiter", async () => {
+    bountyPda = await postBounty(ctx, "No arbiter", reward);
+    const hash = generateSolutionHashWithValue(0xe1);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+
+    try {
+      await openDispute(agent);
+      expect.fail("Should have failed - no arbiter");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NoArbiter");
+    }
+  });
+});
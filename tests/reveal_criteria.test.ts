@@ -0,0 +1,119 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import { createHash } from "crypto";
+import {
+  setupTestContext,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  rejectSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("reveal_criteria", () => {
+  const reward = 5 * 10 ** 6;
+  const criteria = Buffer.from(
+    "ar://criteria-v1 " +
+      "a report listing every wallet funded by the deployer within 24h of launch"
+  );
+  const criteriaHash = createHash("sha256").update(criteria).digest();
+
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  function revealCriteria(bountyPda: PublicKey, bytes: Buffer) {
+    return ctx.program.methods
+      .revealCriteria(bytes)
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function submitAs(bountyPda: PublicKey): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const hash = generateSolutionHashWithValue(0x3c);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    return agent;
+  }
+
+  it("Marks the criteria revealed when the bytes match the commitment", async () => {
+    const bountyPda = await postBounty(ctx, "Deployer funding map", reward, {
+      criteriaHash,
+    });
+
+    await revealCriteria(bountyPda, criteria);
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(Buffer.from(bounty.criteriaHash)).to.deep.equal(criteriaHash);
+    expect(bounty.criteriaRevealed).to.be.true;
+  });
+
+  it("Fails when the bytes don't match the commitment", async () => {
+    const bountyPda = await postBounty(ctx, "Deployer funding map", reward, {
+      criteriaHash,
+    });
+
+    try {
+      await revealCriteria(bountyPda, Buffer.from("something easier to reject"));
+      expect.fail("Should have failed - criteria don't match");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("CriteriaHashMismatch");
+    }
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.criteriaRevealed).to.be.false;
+  });
+
+  it("Blocks rejection until committed criteria are revealed", async () => {
+    const bountyPda = await postBounty(ctx, "Deployer funding map", reward, {
+      criteriaHash,
+    });
+    const agent = await submitAs(bountyPda);
+
+    try {
+      await rejectSolution(ctx, agent.publicKey, bountyPda);
+      expect.fail("Should have failed - criteria still secret");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("CriteriaNotRevealed");
+    }
+
+    await revealCriteria(bountyPda, criteria);
+    await rejectSolution(ctx, agent.publicKey, bountyPda);
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ open: {} });
+  });
+
+  it("Rejects as before when the bounty committed to no criteria", async () => {
+    const bountyPda = await postBounty(ctx, "Uncommitted audit", reward);
+    const agent = await submitAs(bountyPda);
+
+    await rejectSolution(ctx, agent.publicKey, bountyPda);
+
+    try {
+      await revealCriteria(bountyPda, criteria);
+      expect.fail("Should have failed - nothing to reveal");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NoCriteriaCommitment");
+    }
+  });
+});
@@ -0,0 +1,227 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import { createHash } from "crypto";
+import { blake3 } from "@noble/hashes/blake3";
+import { keccak_256 } from "@noble/hashes/sha3";
+import {
+  setupTestContext,
+  airdropSol,
+  ensureCreatorBalance,
+  createAgentTokenAccount,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  HashAlgoEnum,
+  TestContext,
+} from "./helpers";
+
+describe("reveal_solution", () => {
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let bountyPda: anchor.web3.PublicKey;
+
+  const payload = Buffer.from(
+    JSON.stringify({ token: "So11111111111111111111111111111111111111112", risk: "low" })
+  );
+  const solutionHash = createHash("sha256").update(payload).digest();
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+
+    bountyPda = await postBounty(
+      ctx,
+      "Screening with on-chain reveal",
+      10 * 10 ** 6,
+      { bountyType: { tokenScreening: {} }, requiresReveal: true }
+    );
+  });
+
+  async function submit(): Promise<void> {
+    const attestationPda = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      solutionHash
+    );
+    await submitSolution(ctx, agent, bountyPda, attestationPda, solutionHash);
+  }
+
+  async function reveal(data: Buffer): Promise<void> {
+    await ctx.program.methods
+      .revealSolution(data)
+      .accountsPartial({ agent: agent.publicKey, bounty: bountyPda })
+      .signers([agent])
+      .rpc();
+  }
+
+  it("Marks the bounty revealed when the payload hashes to the submission", async () => {
+    await submit();
+    await reveal(payload);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.requiresReveal).to.be.true;
+    expect(bountyAccount.revealed).to.be.true;
+  });
+
+  it("Fails when the payload does not match the submitted hash", async () => {
+    await submit();
+
+    try {
+      await reveal(Buffer.from("not the solution"));
+      expect.fail("Should have failed - wrong preimage");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SolutionHashMismatch");
+    }
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.revealed).to.be.false;
+  });
+
+  it("Fails before anything has been submitted", async () => {
+    try {
+      await reveal(payload);
+      expect.fail("Should have failed - nothing submitted");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotSubmitted");
+    }
+  });
+
+  it("Fails when the payload exceeds 512 bytes", async () => {
+    await submit();
+
+    try {
+      await reveal(Buffer.alloc(513, 1));
+      expect.fail("Should have failed - payload too large");
+    } catch (err) {
+      expect(err).to.exist;
+    }
+  });
+
+  it("Holds settlement until the solution is revealed", async () => {
+    await submit();
+
+    try {
+      await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+      expect.fail("Should have failed - not revealed yet");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SolutionNotRevealed");
+    }
+
+    await reveal(payload);
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ settled: {} });
+  });
+
+  describe("hash algorithms", () => {
+    const algorithms: {
+      name: string;
+      hashAlgo: HashAlgoEnum;
+      digest: (data: Buffer) => Buffer;
+    }[] = [
+      {
+        name: "sha256",
+        hashAlgo: { sha256: {} },
+        digest: (data) => createHash("sha256").update(data).digest(),
+      },
+      {
+        name: "keccak256",
+        hashAlgo: { keccak256: {} },
+        digest: (data) => Buffer.from(keccak_256(data)),
+      },
+      {
+        name: "blake3",
+        hashAlgo: { blake3: {} },
+        digest: (data) => Buffer.from(blake3(data)),
+      },
+    ];
+
+    for (const { name, hashAlgo, digest } of algorithms) {
+      it(`Submits and reveals a ${name} bounty`, async () => {
+        const algoBountyPda = await postBounty(
+          ctx,
+          `Hashed with ${name}`,
+          10 * 10 ** 6,
+          { requiresReveal: true, hashAlgo }
+        );
+        const hash = digest(payload);
+        const attestationPda = await createAttestation(
+          ctx,
+          agent,
+          algoBountyPda,
+          hash,
+          true,
+          null,
+          hashAlgo
+        );
+        await submitSolution(ctx, agent, algoBountyPda, attestationPda, hash);
+
+        await ctx.program.methods
+          .revealSolution(payload)
+          .accountsPartial({ agent: agent.publicKey, bounty: algoBountyPda })
+          .signers([agent])
+          .rpc();
+
+        const bountyAccount = await ctx.program.account.bounty.fetch(
+          algoBountyPda
+        );
+        expect(bountyAccount.hashAlgo).to.deep.equal(hashAlgo);
+        expect(bountyAccount.revealed).to.be.true;
+      });
+    }
+
+    for (const [bountyAlgo, attestationAlgo] of [
+      [algorithms[0], algorithms[1]],
+      [algorithms[1], algorithms[2]],
+      [algorithms[2], algorithms[0]],
+    ]) {
+      it(`Rejects a ${attestationAlgo.name} attestation on a ${bountyAlgo.name} bounty`, async () => {
+        const algoBountyPda = await postBounty(
+          ctx,
+          `Hashed with ${bountyAlgo.name}`,
+          10 * 10 ** 6,
+          { hashAlgo: bountyAlgo.hashAlgo }
+        );
+        const hash = attestationAlgo.digest(payload);
+        const attestationPda = await createAttestation(
+          ctx,
+          agent,
+          algoBountyPda,
+          hash,
+          true,
+          null,
+          attestationAlgo.hashAlgo
+        );
+
+        try {
+          await submitSolution(ctx, agent, algoBountyPda, attestationPda, hash);
+          expect.fail("Should have failed - hash algorithm mismatch");
+        } catch (err) {
+          expect(err.error.errorCode.code).to.equal("HashAlgoMismatch");
+        }
+      });
+    }
+  });
+});
@@ -0,0 +1,149 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("revoke_attestation", () => {
+  let ctx: TestContext;
+  let agent: Keypair;
+  let bountyPda: anchor.web3.PublicKey;
+  let attestationPda: anchor.web3.PublicKey;
+  const solutionHash = generateSolutionHashWithValue(0x52);
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+
+    bountyPda = await postBounty(
+      ctx,
+      "Bounty with a bad attestation",
+      10 * 10 ** 6
+    );
+    attestationPda = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      solutionHash
+    );
+  });
+
+  function revoke(signer?: Keypair) {
+    const builder = ctx.program.methods.revokeAttestation().accountsPartial({
+      authority: signer ? signer.publicKey : ctx.provider.wallet.publicKey,
+      attestation: attestationPda,
+    });
+    return signer ? builder.signers([signer]) : builder;
+  }
+
+  it("Lets the agent revoke and then blocks submission", async () => {
+    await revoke(agent).rpc();
+
+    const attestation = await ctx.program.account.attestation.fetch(
+      attestationPda
+    );
+    expect(attestation.revoked).to.be.true;
+
+    try {
+      await submitSolution(ctx, agent, bountyPda, attestationPda, solutionHash);
+      expect.fail("Should have failed - attestation revoked");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AttestationRevoked");
+    }
+  });
+
+  it("Lets the config verifier revoke", async () => {
+    await revoke().rpc();
+
+    const attestation = await ctx.program.account.attestation.fetch(
+      attestationPda
+    );
+    expect(attestation.revoked).to.be.true;
+  });
+
+  it("Fails when anyone else revokes", async () => {
+    const stranger = Keypair.generate();
+    await airdropSol(ctx.connection, stranger.publicKey);
+
+    try {
+      await revoke(stranger).rpc();
+      expect.fail("Should have failed - not the agent or verifier");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedRevoker");
+    }
+  });
+
+  it("Fails once a submission has consumed the attestation", async () => {
+    await submitSolution(ctx, agent, bountyPda, attestationPda, solutionHash);
+
+    try {
+      await revoke(agent).rpc();
+      expect.fail("Should have failed - attestation already consumed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AttestationAlreadyConsumed");
+    }
+
+    const attestation = await ctx.program.account.attestation.fetch(
+      attestationPda
+    );
+    expect(attestation.revoked).to.be.false;
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+  });
+
+  it("Leaves a consistent state when revoke and submit race", async () => {
+    // Both transactions are sent before either confirms, so they can land in
+    // the same slot; whichever the leader orders first must win outright.
+    const submit = submitSolution(
+      ctx,
+      agent,
+      bountyPda,
+      attestationPda,
+      solutionHash
+    );
+    const revocation = revoke().rpc();
+    const [submitted, revoked] = await Promise.allSettled([submit, revocation]);
+
+    const attestation = await ctx.program.account.attestation.fetch(
+      attestationPda
+    );
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+
+    if (submitted.status === "fulfilled") {
+      expect(revoked.status).to.equal("rejected");
+      const { reason } = revoked as PromiseRejectedResult;
+      expect(reason.error.errorCode.code).to.equal("AttestationAlreadyConsumed");
+      expect(attestation.consumed).to.be.true;
+      expect(attestation.revoked).to.be.false;
+      expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+    } else {
+      expect(revoked.status).to.equal("fulfilled");
+      expect(submitted.reason.error.errorCode.code).to.equal(
+        "AttestationRevoked"
+      );
+      expect(attestation.consumed).to.be.false;
+      expect(attestation.revoked).to.be.true;
+      expect(bountyAccount.status).to.deep.equal({ open: {} });
+    }
+  });
+});
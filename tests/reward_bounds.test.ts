@@ -0,0 +1,110 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  ensureCreatorBalance,
+  postBounty,
+  updateConfig,
+  DEFAULT_MIN_REWARD,
+  DEFAULT_MAX_REWARD,
+  TestContext,
+} from "./helpers";
+
+describe("reward bounds", () => {
+  const minReward = 1 * 10 ** 6;
+  const maxReward = 20 * 10 ** 6;
+
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await updateConfig(ctx, {
+      minReward: new anchor.BN(minReward),
+      maxReward: new anchor.BN(maxReward),
+    });
+  });
+
+  after(async () => {
+    await updateConfig(ctx, {
+      minReward: new anchor.BN(DEFAULT_MIN_REWARD),
+      maxReward: DEFAULT_MAX_REWARD,
+    });
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  async function expectPostToFail(reward: number, code: string) {
+    try {
+      await postBounty(ctx, "Out of bounds", reward);
+      expect.fail(`Should have failed with ${code}`);
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal(code);
+    }
+  }
+
+  it("Accepts rewards exactly at the minimum and maximum", async () => {
+    const atMin = await postBounty(ctx, "Smallest allowed", minReward);
+    const atMax = await postBounty(ctx, "Largest allowed", maxReward);
+
+    const minBounty = await ctx.program.account.bounty.fetch(atMin);
+    const maxBounty = await ctx.program.account.bounty.fetch(atMax);
+    expect(minBounty.reward.toNumber()).to.equal(minReward);
+    expect(maxBounty.reward.toNumber()).to.equal(maxReward);
+  });
+
+  it("Fails with RewardTooSmall just under the minimum", async () => {
+    await expectPostToFail(minReward - 1, "RewardTooSmall");
+  });
+
+  it("Fails with RewardTooLarge just over the maximum", async () => {
+    await expectPostToFail(maxReward + 1, "RewardTooLarge");
+  });
+
+  it("Fails with RewardTooSmall for a zero reward", async () => {
+    await expectPostToFail(0, "RewardTooSmall");
+  });
+
+  it("Fails to top a reward up past the maximum", async () => {
+    const bountyPda = await postBounty(ctx, "Grows too big", maxReward - 1);
+
+    try {
+      await ctx.program.methods
+        .increaseReward(new anchor.BN(2))
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          creatorTokenAccount: ctx.creatorTokenAccount,
+          bountyTokenAccount: getAssociatedTokenAddressSync(
+            ctx.usdcMint,
+            bountyPda
+          ),
+          rewardMint: ctx.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([ctx.creator])
+        .rpc();
+      expect.fail("Should have failed - top-up exceeds max_reward");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("RewardTooLarge");
+    }
+  });
+
+  it("Fails to set a zero minimum", async () => {
+    try {
+      await updateConfig(ctx, { minReward: new anchor.BN(0) });
+      expect.fail("Should have failed - zero minimum");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidRewardBounds");
+    }
+  });
+});
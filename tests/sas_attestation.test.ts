@@ -0,0 +1,170 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  deriveSubmissionPda,
+  airdropSol,
+  ensureCreatorBalance,
+  ensureReputation,
+  postBounty,
+  createAttestation,
+  generateSolutionHashWithValue,
+  SAS_SCHEMA,
+  SAS_HOLDER,
+  SAS_ATTESTATION_VALID,
+  SAS_ATTESTATION_WRONG_HOLDER,
+  SAS_ATTESTATION_EXPIRED,
+  NO_CIPHERTEXT_HASH,
+  TestContext,
+} from "./helpers";
+
+describe("sas_attestation", () => {
+  const reward = 10 * 10 ** 6;
+  // what every SAS fixture attests to
+  const hash = generateSolutionHashWithValue(0x71);
+
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await airdropSol(ctx.connection, SAS_HOLDER.publicKey);
+    await ensureReputation(ctx, SAS_HOLDER);
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  async function postSasBounty(
+    schema = SAS_SCHEMA
+  ): Promise<anchor.web3.PublicKey> {
+    return postBounty(ctx, "Partner-credentialed audit", reward, {
+      attestationSource: { sas: { schema } },
+    });
+  }
+
+  async function submitWithSas(
+    bountyPda: anchor.web3.PublicKey,
+    sasAttestation: anchor.web3.PublicKey
+  ) {
+    return ctx.program.methods
+      .submitSolution(Array.from(hash), NO_CIPHERTEXT_HASH, "", [])
+      .accountsPartial({
+        submitter: SAS_HOLDER.publicKey,
+        agent: SAS_HOLDER.publicKey,
+        operatorRecord: null,
+        bounty: bountyPda,
+        attestation: null,
+        sasAttestation,
+        reputation: deriveReputationPda(
+          ctx.program.programId,
+          SAS_HOLDER.publicKey
+        )[0],
+        application: null,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([SAS_HOLDER])
+      .rpc();
+  }
+
+  async function expectSubmitError(
+    bountyPda: anchor.web3.PublicKey,
+    sasAttestation: anchor.web3.PublicKey,
+    code: string
+  ) {
+    try {
+      await submitWithSas(bountyPda, sasAttestation);
+      expect.fail(`Should have failed with ${code}`);
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal(code);
+    }
+  }
+
+  it("Accepts a SAS attestation issued to the agent for the solution", async () => {
+    const bountyPda = await postSasBounty();
+
+    await submitWithSas(bountyPda, SAS_ATTESTATION_VALID);
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.attestationSource).to.deep.equal({
+      sas: { schema: SAS_SCHEMA },
+    });
+    expect(bounty.status).to.deep.equal({ submitted: {} });
+    expect(bounty.solver.toString()).to.equal(SAS_HOLDER.publicKey.toString());
+
+    const submission = await ctx.program.account.submission.fetch(
+      deriveSubmissionPda(ctx.program.programId, bountyPda, SAS_HOLDER.publicKey)[0]
+    );
+    expect(Buffer.from(submission.solutionHash)).to.deep.equal(hash);
+  });
+
+  it("Fails when the attestation was issued to someone else", async () => {
+    const bountyPda = await postSasBounty();
+    await expectSubmitError(
+      bountyPda,
+      SAS_ATTESTATION_WRONG_HOLDER,
+      "SasHolderMismatch"
+    );
+  });
+
+  it("Fails when the attestation has expired", async () => {
+    const bountyPda = await postSasBounty();
+    await expectSubmitError(
+      bountyPda,
+      SAS_ATTESTATION_EXPIRED,
+      "SasAttestationExpired"
+    );
+  });
+
+  it("Fails when the attestation is under another schema", async () => {
+    const bountyPda = await postSasBounty(Keypair.generate().publicKey);
+    await expectSubmitError(
+      bountyPda,
+      SAS_ATTESTATION_VALID,
+      "SasAttestationInvalid"
+    );
+  });
+
+  it("Fails when no SAS attestation is passed", async () => {
+    const bountyPda = await postSasBounty();
+    const attestation = await createAttestation(
+      ctx,
+      SAS_HOLDER,
+      bountyPda,
+      hash
+    );
+
+    // an internal attestation doesn't stand in for the SAS one
+    try {
+      await ctx.program.methods
+        .submitSolution(Array.from(hash), NO_CIPHERTEXT_HASH, "", [])
+        .accountsPartial({
+          submitter: SAS_HOLDER.publicKey,
+          agent: SAS_HOLDER.publicKey,
+          operatorRecord: null,
+          bounty: bountyPda,
+          attestation,
+          sasAttestation: null,
+          reputation: deriveReputationPda(
+            ctx.program.programId,
+            SAS_HOLDER.publicKey
+          )[0],
+          application: null,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([SAS_HOLDER])
+        .rpc();
+      expect.fail("Should have failed - SAS attestation missing");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MissingAttestation");
+    }
+  });
+});
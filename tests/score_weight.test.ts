@@ -0,0 +1,114 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  deriveSettlementRecordPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  updateConfig,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("score weight", () => {
+  const scoreUnit = 1 * 10 ** 6;
+  const maxScorePerBounty = 5;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+  });
+
+  after(async () => {
+    await updateConfig(ctx, {
+      scoreUnit: new anchor.BN(0),
+      maxScorePerBounty: new anchor.BN(1),
+    });
+  });
+
+  // total score the agent gains from submitting to and winning a bounty
+  async function scoreGained(reward: number): Promise<number> {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      reward + 10 ** 6
+    );
+    const bountyPda = await postBounty(ctx, "Weighted", reward);
+    const hash = generateSolutionHashWithValue(0x3c);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+
+    const [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    const before = await ctx.program.account.reputation.fetchNullable(
+      reputationPda
+    );
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+    const after = await ctx.program.account.reputation.fetch(reputationPda);
+
+    const gained =
+      after.score.toNumber() - (before ? before.score.toNumber() : 0);
+    const record = await ctx.program.account.settlementRecord.fetch(
+      deriveSettlementRecordPda(
+        ctx.program.programId,
+        bountyPda,
+        agent.publicKey
+      )[0]
+    );
+    expect(record.scoreDelta.toNumber()).to.equal(gained);
+    return gained;
+  }
+
+  it("Adds a point per doubling of the reward past one unit, up to the cap", async () => {
+    await updateConfig(ctx, {
+      scoreUnit: new anchor.BN(scoreUnit),
+      maxScorePerBounty: new anchor.BN(maxScorePerBounty),
+    });
+
+    // below one unit and exactly one unit both count as 1
+    expect(await scoreGained(scoreUnit / 2)).to.equal(1);
+    expect(await scoreGained(scoreUnit)).to.equal(1);
+    // 3 units: 1 + floor(log2(3)) = 2
+    expect(await scoreGained(3 * scoreUnit)).to.equal(2);
+    // 8 units: 1 + log2(8) = 4
+    expect(await scoreGained(8 * scoreUnit)).to.equal(4);
+    // 100 units would be 1 + 6 = 7, capped
+    expect(await scoreGained(100 * scoreUnit)).to.equal(maxScorePerBounty);
+  });
+
+  it("Weights every bounty 1 when the score unit is 0", async () => {
+    await updateConfig(ctx, { scoreUnit: new anchor.BN(0) });
+
+    expect(await scoreGained(100 * scoreUnit)).to.equal(1);
+  });
+
+  it("Fails to set a per-bounty cap below 1", async () => {
+    try {
+      await updateConfig(ctx, { maxScorePerBounty: new anchor.BN(0) });
+      expect.fail("Should have failed - cap of 0");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidScoreCap");
+    }
+  });
+});
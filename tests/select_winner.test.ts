@@ -0,0 +1,210 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveSubmissionPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  selectWinner,
+  settleBounty,
+  rejectSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("select_winner", () => {
+  let ctx: TestContext;
+  let agents: Keypair[];
+  let agentTokenAccounts: anchor.web3.PublicKey[];
+  let bountyPda: anchor.web3.PublicKey;
+  let submissionPdas: anchor.web3.PublicKey[];
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  async function postCompetitiveBounty(): Promise<anchor.web3.PublicKey> {
+    return postBounty(
+      ctx,
+      "Best solution wins",
+      30 * 10 ** 6,
+      { competitive: true }
+    );
+  }
+
+  async function closeSubmission(agent: Keypair): Promise<void> {
+    await ctx.program.methods
+      .closeSubmission()
+      .accountsPartial({ agent: agent.publicKey, bounty: bountyPda })
+      .signers([agent])
+      .rpc();
+  }
+
+  async function compete(
+    bounty: anchor.web3.PublicKey,
+    agent: Keypair,
+    value: number
+  ): Promise<anchor.web3.PublicKey> {
+    const hash = generateSolutionHashWithValue(value);
+    const attestation = await createAttestation(ctx, agent, bounty, hash);
+    await submitSolution(ctx, agent, bounty, attestation, hash);
+    const [submissionPda] = deriveSubmissionPda(
+      ctx.program.programId,
+      bounty,
+      agent.publicKey
+    );
+    return submissionPda;
+  }
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+
+    agents = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    agentTokenAccounts = [];
+    for (const agent of agents) {
+      await airdropSol(ctx.connection, agent.publicKey);
+      agentTokenAccounts.push(
+        await createAgentTokenAccount(ctx.connection, agent, ctx.usdcMint)
+      );
+    }
+
+    bountyPda = await postCompetitiveBounty();
+    submissionPdas = [];
+    for (const [i, agent] of agents.entries()) {
+      submissionPdas.push(await compete(bountyPda, agent, 0x80 + i));
+    }
+  });
+
+  it("Pays only the agent whose submission was picked", async () => {
+    await selectWinner(ctx, bountyPda, submissionPdas[1]);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+    expect(bountyAccount.solver.toString()).to.equal(
+      agents[1].publicKey.toString()
+    );
+    expect(Buffer.from(bountyAccount.solutionHash)).to.deep.equal(
+      generateSolutionHashWithValue(0x81)
+    );
+    const winning = await ctx.program.account.submission.fetch(
+      submissionPdas[1]
+    );
+    expect(winning.status).to.deep.equal({ accepted: {} });
+
+    const balancesBefore = await Promise.all(
+      agentTokenAccounts.map((account) =>
+        ctx.connection.getTokenAccountBalance(account)
+      )
+    );
+    await settleBounty(
+      ctx,
+      agents[1].publicKey,
+      bountyPda,
+      agentTokenAccounts[1]
+    );
+    const balancesAfter = await Promise.all(
+      agentTokenAccounts.map((account) =>
+        ctx.connection.getTokenAccountBalance(account)
+      )
+    );
+
+    expect(
+      Number(balancesAfter[1].value.amount) -
+        Number(balancesBefore[1].value.amount)
+    ).to.equal(30 * 10 ** 6);
+    for (const i of [0, 2]) {
+      expect(balancesAfter[i].value.amount).to.equal(
+        balancesBefore[i].value.amount
+      );
+    }
+
+    try {
+      await settleBounty(
+        ctx,
+        agents[0].publicKey,
+        bountyPda,
+        agentTokenAccounts[0]
+      );
+      expect.fail("Should have failed - bounty already settled");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotSubmitted");
+    }
+  });
+
+  it("Fails when selecting twice", async () => {
+    await selectWinner(ctx, bountyPda, submissionPdas[1]);
+
+    try {
+      await selectWinner(ctx, bountyPda, submissionPdas[2]);
+      expect.fail("Should have failed - winner already selected");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("WinnerAlreadySelected");
+    }
+  });
+
+  it("Fails with a submission from a different bounty", async () => {
+    const otherBountyPda = await postCompetitiveBounty();
+    const otherSubmission = await compete(otherBountyPda, agents[0], 0x8f);
+
+    try {
+      await selectWinner(ctx, bountyPda, otherSubmission);
+      expect.fail("Should have failed - submission for another bounty");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SubmissionBountyMismatch");
+    }
+  });
+
+  it("Fails when a rejected winner is picked again", async () => {
+    await selectWinner(ctx, bountyPda, submissionPdas[1]);
+    await rejectSolution(ctx, agents[1].publicKey, bountyPda);
+
+    try {
+      await selectWinner(ctx, bountyPda, submissionPdas[1]);
+      expect.fail("Should have failed - submission was rejected");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SubmissionNotPending");
+    }
+
+    await selectWinner(ctx, bountyPda, submissionPdas[2]);
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.solver.toString()).to.equal(
+      agents[2].publicKey.toString()
+    );
+  });
+
+  it("Lets losing agents close their submissions but not the winner", async () => {
+    try {
+      await closeSubmission(agents[0]);
+      expect.fail("Should have failed - still competing");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SubmissionStillActive");
+    }
+
+    await selectWinner(ctx, bountyPda, submissionPdas[1]);
+
+    const lamportsBefore = await ctx.connection.getBalance(agents[0].publicKey);
+    await closeSubmission(agents[0]);
+    expect(await ctx.connection.getAccountInfo(submissionPdas[0])).to.be.null;
+    expect(
+      await ctx.connection.getBalance(agents[0].publicKey)
+    ).to.be.greaterThan(lamportsBefore);
+
+    try {
+      await closeSubmission(agents[1]);
+      expect.fail("Should have failed - winning submission");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SubmissionStillActive");
+    }
+  });
+});
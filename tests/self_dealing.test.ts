@@ -0,0 +1,96 @@
+import * as anchor from "@coral-xyz/anchor";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  updateConfig,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("self_dealing", () => {
+  const reward = 5 * 10 ** 6;
+
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  after(async () => {
+    await updateConfig(ctx, { allowSelfSettlement: false });
+  });
+
+  async function submitOwn(bountyPda: anchor.web3.PublicKey) {
+    const hash = generateSolutionHashWithValue(0x5d);
+    const attestation = await createAttestation(
+      ctx,
+      ctx.creator,
+      bountyPda,
+      hash
+    );
+    await submitSolution(ctx, ctx.creator, bountyPda, attestation, hash);
+  }
+
+  it("Blocks a creator from submitting to their own bounty", async () => {
+    const bountyPda = await postBounty(ctx, "All mine", reward);
+
+    try {
+      await submitOwn(bountyPda);
+      expect.fail("Should have failed - creator is the agent");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SelfDealingNotAllowed");
+    }
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ open: {} });
+  });
+
+  it("Blocks assigning a bounty to its own creator", async () => {
+    try {
+      await postBounty(ctx, "Assigned to myself", reward, {
+        assignee: ctx.creator.publicKey,
+      });
+      expect.fail("Should have failed - creator is the assignee");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SelfDealingNotAllowed");
+    }
+  });
+
+  it("Allows it all when the Config opts in", async () => {
+    await updateConfig(ctx, { allowSelfSettlement: true });
+
+    const bountyPda = await postBounty(ctx, "Internal task", reward, {
+      assignee: ctx.creator.publicKey,
+    });
+    await submitOwn(bountyPda);
+    await settleBounty(
+      ctx,
+      ctx.creator.publicKey,
+      bountyPda,
+      ctx.creatorTokenAccount
+    );
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ settled: {} });
+    const reputation = await ctx.program.account.reputation.fetch(
+      deriveReputationPda(ctx.program.programId, ctx.creator.publicKey)[0]
+    );
+    expect(reputation.successfulBounties.toNumber()).to.be.at.least(1);
+  });
+});
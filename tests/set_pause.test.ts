@@ -0,0 +1,117 @@
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveConfigPda,
+  DEFAULT_MARKET,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("set_pause", () => {
+  let ctx: TestContext;
+  let agent: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  afterEach(async () => {
+    await setPause(false);
+  });
+
+  async function setPause(paused: boolean, signer?: Keypair): Promise<void> {
+    const builder = ctx.program.methods
+      .setPause(DEFAULT_MARKET, paused)
+      .accountsPartial({
+        admin: signer ? signer.publicKey : ctx.provider.wallet.publicKey,
+      });
+    await (signer ? builder.signers([signer]) : builder).rpc();
+  }
+
+  it("Blocks posting while paused and allows it after unpausing", async () => {
+    await setPause(true);
+
+    const [configPda] = deriveConfigPda(ctx.program.programId);
+    const config = await ctx.program.account.config.fetch(configPda);
+    expect(config.paused).to.be.true;
+
+    try {
+      await postBounty(ctx, "Paused bounty", 10 * 10 ** 6);
+      expect.fail("Should have failed - program paused");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ProgramPaused");
+    }
+
+    await setPause(false);
+
+    const bountyPda = await postBounty(ctx, "Unpaused bounty", 10 * 10 ** 6);
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ open: {} });
+  });
+
+  it("Blocks attesting and submitting while paused", async () => {
+    const bountyPda = await postBounty(
+      ctx,
+      "Bounty open before the pause",
+      10 * 10 ** 6
+    );
+    const solutionHash = generateSolutionHashWithValue(0x61);
+    const attestationPda = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      solutionHash
+    );
+
+    await setPause(true);
+
+    try {
+      await createAttestation(
+        ctx,
+        agent,
+        bountyPda,
+        generateSolutionHashWithValue(0x62)
+      );
+      expect.fail("Should have failed - program paused");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ProgramPaused");
+    }
+
+    try {
+      await submitSolution(ctx, agent, bountyPda, attestationPda, solutionHash);
+      expect.fail("Should have failed - program paused");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ProgramPaused");
+    }
+  });
+
+  it("Fails when a non-admin flips the switch", async () => {
+    const intruder = Keypair.generate();
+    await airdropSol(ctx.connection, intruder.publicKey);
+
+    try {
+      await setPause(true, intruder);
+      expect.fail("Should have failed - not the admin");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedAdmin");
+    }
+  });
+});
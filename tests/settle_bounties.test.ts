@@ -0,0 +1,232 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { ComputeBudgetProgram, Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  deriveCreatorProfilePda,
+  deriveReputationPda,
+  deriveSettlementReceiptPda,
+  deriveSettlementRecordPda,
+  deriveSubmissionPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("settle_bounties", () => {
+  const reward = 4 * 10 ** 6;
+
+  let ctx: TestContext;
+
+  interface Item {
+    bountyPda: anchor.web3.PublicKey;
+    agent: Keypair;
+    agentTokenAccount: anchor.web3.PublicKey;
+  }
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  // a bounty with its own agent, submitted unless told otherwise
+  async function newItem(
+    submit = true,
+    settlementAuthority?: anchor.web3.PublicKey
+  ): Promise<Item> {
+    const bountyPda = await postBounty(ctx, "Round bounty", reward, {
+      challengePeriodSlots: 0,
+      settlementAuthority,
+    });
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    if (submit) {
+      const hash = generateSolutionHashWithValue(0x5b);
+      const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+      await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    }
+    return { bountyPda, agent, agentTokenAccount };
+  }
+
+  function itemAccounts({ bountyPda, agent, agentTokenAccount }: Item) {
+    const programId = ctx.program.programId;
+    return [
+      bountyPda,
+      deriveSubmissionPda(programId, bountyPda, agent.publicKey)[0],
+      deriveReputationPda(programId, agent.publicKey)[0],
+      agent.publicKey,
+      agentTokenAccount,
+      getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda),
+      deriveSettlementRecordPda(programId, bountyPda, agent.publicKey)[0],
+      deriveSettlementReceiptPda(programId, bountyPda)[0],
+      // no attestor token account, self-attested solutions don't need one
+      programId,
+    ].map((pubkey) => ({ pubkey, isSigner: false, isWritable: true }));
+  }
+
+  async function settleBatch(items: Item[], settler: Keypair = ctx.creator) {
+    return ctx.program.methods
+      .settleBounties()
+      .accountsPartial({
+        settler: settler.publicKey,
+        creatorProfile: deriveCreatorProfilePda(
+          ctx.program.programId,
+          ctx.creator.publicKey
+        )[0],
+        treasury: null,
+        treasuryTokenAccount: null,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(items.flatMap(itemAccounts))
+      .preInstructions([
+        ComputeBudgetProgram.setComputeUnitLimit({ units: 600_000 }),
+      ])
+      .signers([settler])
+      .rpc();
+  }
+
+  it("Settles three bounties in one transaction", async () => {
+    const items = [await newItem(), await newItem(), await newItem()];
+
+    await settleBatch(items);
+
+    for (const { bountyPda, agent, agentTokenAccount } of items) {
+      const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bounty.status).to.deep.equal({ settled: {} });
+
+      const balance = await ctx.connection.getTokenAccountBalance(
+        agentTokenAccount
+      );
+      expect(Number(balance.value.amount)).to.equal(reward);
+
+      const reputation = await ctx.program.account.reputation.fetch(
+        deriveReputationPda(ctx.program.programId, agent.publicKey)[0]
+      );
+      expect(reputation.successfulBounties.toNumber()).to.equal(1);
+
+      const receipt = await ctx.program.account.settlementReceipt.fetch(
+        deriveSettlementReceiptPda(ctx.program.programId, bountyPda)[0]
+      );
+      expect(receipt.netPaid.toNumber()).to.equal(reward);
+      expect(receipt.solver.toString()).to.equal(agent.publicKey.toString());
+    }
+  });
+
+  it("Reverts the whole batch when the third item is bad", async () => {
+    const items = [await newItem(), await newItem(), await newItem(false)];
+
+    try {
+      await settleBatch(items);
+      expect.fail("Should have failed - third bounty not submitted");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotSubmitted");
+      expect(err.logs.join("\n")).to.include("settle_bounties: item 2 failed");
+    }
+
+    // the first two were rolled back with it
+    for (const { bountyPda, agentTokenAccount } of items.slice(0, 2)) {
+      const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bounty.status).to.deep.equal({ submitted: {} });
+
+      const balance = await ctx.connection.getTokenAccountBalance(
+        agentTokenAccount
+      );
+      expect(Number(balance.value.amount)).to.equal(0);
+
+      const escrow = await ctx.connection.getTokenAccountBalance(
+        getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda)
+      );
+      expect(Number(escrow.value.amount)).to.equal(reward);
+    }
+
+    // and still settle on their own
+    await settleBatch(items.slice(0, 2));
+  });
+
+  it("Lets the settlement authority the bounties name settle them", async () => {
+    const delegate = Keypair.generate();
+    await airdropSol(ctx.connection, delegate.publicKey);
+    const items = [
+      await newItem(true, delegate.publicKey),
+      await newItem(true, delegate.publicKey),
+    ];
+
+    try {
+      await settleBatch([...items, await newItem()], delegate);
+      expect.fail("Should have failed - third bounty not delegated");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedSettlement");
+      expect(err.logs.join("\n")).to.include("settle_bounties: item 2 failed");
+    }
+
+    await settleBatch(items, delegate);
+    for (const { bountyPda } of items) {
+      const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bounty.status).to.deep.equal({ settled: {} });
+      const receipt = await ctx.program.account.settlementReceipt.fetch(
+        deriveSettlementReceiptPda(ctx.program.programId, bountyPda)[0]
+      );
+      expect(receipt.settledBy.toString()).to.equal(
+        delegate.publicKey.toString()
+      );
+    }
+  });
+
+  it("Fails for another creator's bounty", async () => {
+    const otherCtx = await setupTestContext();
+    const ownItem = await newItem();
+
+    const bountyPda = await postBounty(otherCtx, "Not yours", reward, {
+      challengePeriodSlots: 0,
+    });
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const hash = generateSolutionHashWithValue(0x5c);
+    const attestation = await createAttestation(
+      otherCtx,
+      agent,
+      bountyPda,
+      hash
+    );
+    await submitSolution(otherCtx, agent, bountyPda, attestation, hash);
+    const foreignItem = {
+      bountyPda,
+      agent,
+      agentTokenAccount: await createAgentTokenAccount(
+        ctx.connection,
+        agent,
+        otherCtx.usdcMint
+      ),
+    };
+
+    try {
+      await settleBatch([ownItem, foreignItem]);
+      expect.fail("Should have failed - not the creator");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedSettlement");
+      expect(err.logs.join("\n")).to.include("settle_bounties: item 1 failed");
+    }
+  });
+});
@@ -1,11 +1,19 @@
 import * as anchor from "@coral-xyz/anchor";
-import { TOKEN_PROGRAM_ID, mintTo } from "@solana/spl-token";
+import {
+  TOKEN_PROGRAM_ID,
+  closeAccount,
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+  transfer,
+} from "@solana/spl-token";
 import { Keypair, SystemProgram } from "@solana/web3.js";
 import { expect } from "chai";
 import {
   setupTestContext,
   deriveBountyPda,
   deriveReputationPda,
+  deriveSettlementRecordPda,
   getAssociatedTokenAddressSync,
   airdropSol,
   createAgentTokenAccount,
@@ -13,18 +21,23 @@ import {
   postBounty,
   createAttestation,
   submitSolution,
-  generateRandomId,
+  settleBounty,
   generateSolutionHashWithValue,
+  sleep,
+  setFeeBps,
+  updateConfig,
+  waitForSlot,
+  fetchEvents,
+  findEvent,
+  PostBountyOptions,
   TestContext,
 } from "./helpers";
 
 describe("settle_bounty", () => {
   let ctx: TestContext;
   let agent: Keypair;
-  let testBountyId: number;
   let testBountyPda: anchor.web3.PublicKey;
   let testBountyTokenAccount: anchor.web3.PublicKey;
-  let solutionId: number;
   let attestationPda: anchor.web3.PublicKey;
   let solutionHash: Buffer;
   let reputationPda: anchor.web3.PublicKey;
@@ -52,10 +65,8 @@ describe("settle_bounty", () => {
       200 * 10 ** 6
     );
 
-    testBountyId = generateRandomId();
     testBountyPda = await postBounty(
       ctx,
-      testBountyId,
       "Test bounty for settlement",
       100 * 10 ** 6
     );
@@ -65,12 +76,11 @@ describe("settle_bounty", () => {
       testBountyPda
     );
 
-    solutionId = generateRandomId();
     solutionHash = generateSolutionHashWithValue(0xaa);
     attestationPda = await createAttestation(
       ctx,
       agent,
-      solutionId,
+      testBountyPda,
       solutionHash
     );
 
@@ -102,7 +112,7 @@ describe("settle_bounty", () => {
       reputationPda
     );
 
-    await ctx.program.methods
+    const signature = await ctx.program.methods
       .settleBounty()
       .accountsPartial({
         creator: ctx.creator.publicKey,
@@ -111,7 +121,7 @@ describe("settle_bounty", () => {
         agent: agent.publicKey,
         agentTokenAccount: agentTokenAccount,
         bountyTokenAccount: testBountyTokenAccount,
-        usdcMint: ctx.usdcMint,
+        rewardMint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
@@ -142,6 +152,13 @@ describe("settle_bounty", () => {
     expect(reputationAfter.totalEarned.toNumber()).to.equal(
       reputationBefore.totalEarned.toNumber() + reward
     );
+
+    const event = findEvent(await fetchEvents(ctx, signature), "BountySettled");
+    expect(event.data.bounty.toString()).to.equal(testBountyPda.toString());
+    expect(event.data.solver.toString()).to.equal(agent.publicKey.toString());
+    expect(event.data.solverAmount.toNumber()).to.equal(reward);
+    expect(event.data.feeAmount.toNumber()).to.equal(0);
+    expect(event.data.timestamp.toNumber()).to.be.greaterThan(0);
   });
 
   it("Fails when creator is not the bounty creator", async () => {
@@ -158,7 +175,7 @@ describe("settle_bounty", () => {
           agent: agent.publicKey,
           agentTokenAccount: agentTokenAccount,
           bountyTokenAccount: testBountyTokenAccount,
-          usdcMint: ctx.usdcMint,
+          rewardMint: ctx.usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -172,8 +189,7 @@ describe("settle_bounty", () => {
   });
 
   it("Fails when bounty is not in Submitted status", async () => {
-    const bountyId2 = generateRandomId();
-    const bountyPda2 = await postBounty(ctx, bountyId2, "Open bounty", 50 * 10 ** 6);
+    const bountyPda2 = await postBounty(ctx, "Open bounty", 50 * 10 ** 6);
 
     try {
       await ctx.program.methods
@@ -188,7 +204,7 @@ describe("settle_bounty", () => {
             ctx.usdcMint,
             bountyPda2
           ),
-          usdcMint: ctx.usdcMint,
+          rewardMint: ctx.usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -211,7 +227,7 @@ describe("settle_bounty", () => {
         agent: agent.publicKey,
         agentTokenAccount: agentTokenAccount,
         bountyTokenAccount: testBountyTokenAccount,
-        usdcMint: ctx.usdcMint,
+        rewardMint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
@@ -228,7 +244,7 @@ describe("settle_bounty", () => {
           agent: agent.publicKey,
           agentTokenAccount: agentTokenAccount,
           bountyTokenAccount: testBountyTokenAccount,
-          usdcMint: ctx.usdcMint,
+          rewardMint: ctx.usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -251,28 +267,21 @@ describe("settle_bounty", () => {
         agent: agent.publicKey,
         agentTokenAccount: agentTokenAccount,
         bountyTokenAccount: testBountyTokenAccount,
-        usdcMint: ctx.usdcMint,
+        rewardMint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
       .signers([ctx.creator])
       .rpc();
 
-    const bountyId2 = generateRandomId();
     const reward2 = 75 * 10 ** 6;
-    const bountyPda2 = await postBounty(
-      ctx,
-      bountyId2,
-      "Second bounty",
-      reward2
-    );
+    const bountyPda2 = await postBounty(ctx, "Second bounty", reward2);
 
-    const solutionId2 = generateRandomId();
     const solutionHash2 = generateSolutionHashWithValue(0xbb);
     const attestationPda2 = await createAttestation(
       ctx,
       agent,
-      solutionId2,
+      bountyPda2,
       solutionHash2
     );
 
@@ -294,7 +303,7 @@ describe("settle_bounty", () => {
           ctx.usdcMint,
           bountyPda2
         ),
-        usdcMint: ctx.usdcMint,
+        rewardMint: ctx.usdcMint,
         tokenProgram: TOKEN_PROGRAM_ID,
         systemProgram: SystemProgram.programId,
       })
@@ -331,7 +340,7 @@ describe("settle_bounty", () => {
           agent: agent.publicKey,
           agentTokenAccount: agentTokenAccount,
           bountyTokenAccount: testBountyTokenAccount,
-          usdcMint: ctx.usdcMint,
+          rewardMint: ctx.usdcMint,
           tokenProgram: TOKEN_PROGRAM_ID,
           systemProgram: SystemProgram.programId,
         })
@@ -343,5 +352,605 @@ describe("settle_bounty", () => {
       expect(err).to.exist;
     }
   });
-});
 
+  it("Writes a settlement record for the agent's history", async () => {
+    await ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: testBountyPda,
+        reputation: reputationPda,
+        agent: agent.publicKey,
+        agentTokenAccount: agentTokenAccount,
+        bountyTokenAccount: testBountyTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+
+    const [recordPda] = deriveSettlementRecordPda(
+      ctx.program.programId,
+      testBountyPda,
+      agent.publicKey
+    );
+    const record = await ctx.program.account.settlementRecord.fetch(recordPda);
+    const bountyAccount = await ctx.program.account.bounty.fetch(testBountyPda);
+
+    expect(record.agent.toString()).to.equal(agent.publicKey.toString());
+    expect(record.bounty.toString()).to.equal(testBountyPda.toString());
+    expect(record.bountyId.toNumber()).to.equal(bountyAccount.id.toNumber());
+    expect(record.bountyType).to.deep.equal(bountyAccount.bountyType);
+    expect(record.amountEarned.toNumber()).to.equal(100 * 10 ** 6);
+    expect(record.scoreDelta.toNumber()).to.equal(1);
+    expect(record.timestamp.toNumber()).to.be.greaterThan(0);
+
+    const history = await ctx.program.account.settlementRecord.all([
+      { memcmp: { offset: 8, bytes: agent.publicKey.toBase58() } },
+    ]);
+    expect(history.map((r) => r.publicKey.toString())).to.include(
+      recordPda.toString()
+    );
+  });
+
+  it("Settles a bounty submitted right before its deadline", async () => {
+    const reward2 = 20 * 10 ** 6;
+    const bountyPda2 = await postBounty(ctx, "Tight deadline", reward2, {
+      deadline: Math.floor(Date.now() / 1000) + 8,
+    });
+
+    const solutionHash2 = generateSolutionHashWithValue(0xcc);
+    const attestationPda2 = await createAttestation(
+      ctx,
+      agent,
+      bountyPda2,
+      solutionHash2
+    );
+    await submitSolution(ctx, agent, bountyPda2, attestationPda2, solutionHash2);
+
+    await sleep(10000);
+
+    await ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda2,
+        reputation: reputationPda,
+        agent: agent.publicKey,
+        agentTokenAccount: agentTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda2
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda2);
+    expect(bountyAccount.status).to.deep.equal({ settled: {} });
+  });
+
+  it("Fails when paying out to someone other than the recorded solver", async () => {
+    const bystander = Keypair.generate();
+    await airdropSol(ctx.connection, bystander.publicKey);
+    const bystanderTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      bystander,
+      ctx.usdcMint
+    );
+
+    try {
+      await ctx.program.methods
+        .settleBounty()
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: testBountyPda,
+          reputation: reputationPda,
+          agent: bystander.publicKey,
+          agentTokenAccount: bystanderTokenAccount,
+          bountyTokenAccount: testBountyTokenAccount,
+          rewardMint: ctx.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+
+      expect.fail("Should have failed - destination is not the solver");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SolverMismatch");
+    }
+
+    const bountyBalance = await ctx.connection.getTokenAccountBalance(
+      testBountyTokenAccount
+    );
+    expect(bountyBalance.value.amount).to.equal((100 * 10 ** 6).toString());
+  });
+
+  it("Fails when the reputation account was never initialized", async () => {
+    const [uninitializedPda] = deriveReputationPda(
+      ctx.program.programId,
+      Keypair.generate().publicKey
+    );
+
+    try {
+      await ctx.program.methods
+        .settleBounty()
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: testBountyPda,
+          reputation: uninitializedPda,
+          agent: agent.publicKey,
+          agentTokenAccount: agentTokenAccount,
+          bountyTokenAccount: testBountyTokenAccount,
+          rewardMint: ctx.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+
+      expect.fail("Should have failed - reputation not initialized");
+    } catch (err) {
+      expect(err).to.exist;
+    }
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(testBountyPda);
+    expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+  });
+
+  it("Fails with ReputationOverflow when total_earned would overflow", async () => {
+    const bigCtx = await setupTestContext();
+    const half = new anchor.BN(2).pow(new anchor.BN(63));
+
+    await mintTo(
+      bigCtx.connection,
+      bigCtx.creator,
+      bigCtx.usdcMint,
+      bigCtx.creatorTokenAccount,
+      bigCtx.creator,
+      BigInt(half.toString())
+    );
+
+    const whale = Keypair.generate();
+    await airdropSol(bigCtx.connection, whale.publicKey);
+    const whaleTokenAccount = await createAgentTokenAccount(
+      bigCtx.connection,
+      whale,
+      bigCtx.usdcMint
+    );
+
+    const firstPda = await postBounty(bigCtx, "Half of u64", half);
+    const firstHash = generateSolutionHashWithValue(0xd1);
+    const firstAttestation = await createAttestation(
+      bigCtx,
+      whale,
+      firstPda,
+      firstHash
+    );
+    await submitSolution(bigCtx, whale, firstPda, firstAttestation, firstHash);
+    await settleBounty(bigCtx, whale.publicKey, firstPda, whaleTokenAccount);
+
+    // Recycle the payout so the creator can fund a second half-of-u64 bounty
+    await transfer(
+      bigCtx.connection,
+      whale,
+      whaleTokenAccount,
+      bigCtx.creatorTokenAccount,
+      whale,
+      BigInt(half.toString())
+    );
+
+    const secondPda = await postBounty(bigCtx, "Other half", half);
+    const secondHash = generateSolutionHashWithValue(0xd2);
+    const secondAttestation = await createAttestation(
+      bigCtx,
+      whale,
+      secondPda,
+      secondHash
+    );
+    await submitSolution(bigCtx, whale, secondPda, secondAttestation, secondHash);
+
+    try {
+      await settleBounty(bigCtx, whale.publicKey, secondPda, whaleTokenAccount);
+      expect.fail("Should have failed - total_earned overflow");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ReputationOverflow");
+    }
+  });
+
+  it("Settles bounties denominated in different reward mints", async () => {
+    const bonkMint = await createMint(
+      ctx.connection,
+      ctx.creator,
+      ctx.creator.publicKey,
+      null,
+      5
+    );
+    const creatorBonkAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        ctx.connection,
+        ctx.creator,
+        bonkMint,
+        ctx.creator.publicKey
+      )
+    ).address;
+    await mintTo(
+      ctx.connection,
+      ctx.creator,
+      bonkMint,
+      creatorBonkAccount,
+      ctx.creator,
+      1_000_000 * 10 ** 5
+    );
+    const bonkCtx: TestContext = {
+      ...ctx,
+      usdcMint: bonkMint,
+      creatorTokenAccount: creatorBonkAccount,
+    };
+
+    const bonkReward = 250_000 * 10 ** 5;
+    const bonkBountyPda = await postBounty(bonkCtx, "Paid in BONK", bonkReward);
+    const bonkBounty = await ctx.program.account.bounty.fetch(bonkBountyPda);
+    expect(bonkBounty.rewardMint.toString()).to.equal(bonkMint.toString());
+
+    const bonkHash = generateSolutionHashWithValue(0xb0);
+    const bonkAttestation = await createAttestation(
+      ctx,
+      agent,
+      bonkBountyPda,
+      bonkHash
+    );
+    await submitSolution(ctx, agent, bonkBountyPda, bonkAttestation, bonkHash);
+
+    const agentBonkAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      bonkMint
+    );
+
+    await settleBounty(ctx, agent.publicKey, testBountyPda, agentTokenAccount);
+    await settleBounty(bonkCtx, agent.publicKey, bonkBountyPda, agentBonkAccount);
+
+    const usdcBalance = await ctx.connection.getTokenAccountBalance(
+      agentTokenAccount
+    );
+    const bonkBalance = await ctx.connection.getTokenAccountBalance(
+      agentBonkAccount
+    );
+    expect(usdcBalance.value.amount).to.equal((100 * 10 ** 6).toString());
+    expect(bonkBalance.value.amount).to.equal(bonkReward.toString());
+  });
+
+  it("Lets two creators use the same bounty id and settles each one", async () => {
+    const creators = [await setupTestContext(), await setupTestContext()];
+    // every creator's counter starts at 1
+    const bountyId = 1;
+    const reward = 10 * 10 ** 6;
+
+    const bountyPdas = [];
+    for (const creatorCtx of creators) {
+      const [expected] = deriveBountyPda(
+        ctx.program.programId,
+        creatorCtx.creator.publicKey,
+        bountyId
+      );
+      const bountyPda = await postBounty(
+        creatorCtx,
+        "Same id, different creator",
+        reward
+      );
+      expect(bountyPda.toString()).to.equal(expected.toString());
+      bountyPdas.push(bountyPda);
+    }
+    expect(bountyPdas[0].toString()).to.not.equal(bountyPdas[1].toString());
+
+    for (let i = 0; i < creators.length; i++) {
+      const hash = generateSolutionHashWithValue(0x10 + i);
+      const attestation = await createAttestation(
+        creators[i],
+        agent,
+        bountyPdas[i],
+        hash
+      );
+      await submitSolution(creators[i], agent, bountyPdas[i], attestation, hash);
+
+      const payout = await createAgentTokenAccount(
+        ctx.connection,
+        agent,
+        creators[i].usdcMint
+      );
+      await settleBounty(creators[i], agent.publicKey, bountyPdas[i], payout);
+
+      const bountyAccount = await ctx.program.account.bounty.fetch(
+        bountyPdas[i]
+      );
+      expect(bountyAccount.id.toNumber()).to.equal(bountyId);
+      expect(bountyAccount.creator.toString()).to.equal(
+        creators[i].creator.publicKey.toString()
+      );
+      expect(bountyAccount.status).to.deep.equal({ settled: {} });
+      const balance = await ctx.connection.getTokenAccountBalance(payout);
+      expect(Number(balance.value.amount)).to.equal(reward);
+    }
+  });
+
+  it("Fails when the destination token account is for the wrong mint", async () => {
+    const otherMint = await createMint(
+      ctx.connection,
+      ctx.creator,
+      ctx.creator.publicKey,
+      null,
+      6
+    );
+    const wrongMintAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      otherMint
+    );
+
+    try {
+      await ctx.program.methods
+        .settleBounty()
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: testBountyPda,
+          reputation: reputationPda,
+          agent: agent.publicKey,
+          agentTokenAccount: wrongMintAccount,
+          bountyTokenAccount: testBountyTokenAccount,
+          rewardMint: ctx.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+
+      expect.fail("Should have failed - destination mint mismatch");
+    } catch (err) {
+      expect(err).to.exist;
+    }
+  });
+
+  it("Fails when the creator passes their own account as the solver's", async () => {
+    try {
+      await settleBounty(
+        ctx,
+        agent.publicKey,
+        testBountyPda,
+        ctx.creatorTokenAccount
+      );
+      expect.fail("Should have failed - not the solver's ATA");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ConstraintAssociated");
+    }
+
+    const bountyBalance = await ctx.connection.getTokenAccountBalance(
+      testBountyTokenAccount
+    );
+    expect(bountyBalance.value.amount).to.equal((100 * 10 ** 6).toString());
+  });
+
+  it("Creates the solver's ATA when it doesn't exist and pays it", async () => {
+    await closeAccount(
+      ctx.connection,
+      agent,
+      agentTokenAccount,
+      agent.publicKey,
+      agent
+    );
+    expect(await ctx.connection.getAccountInfo(agentTokenAccount)).to.be.null;
+
+    await settleBounty(ctx, agent.publicKey, testBountyPda, agentTokenAccount);
+
+    const agentBalance = await ctx.connection.getTokenAccountBalance(
+      agentTokenAccount
+    );
+    expect(agentBalance.value.amount).to.equal((100 * 10 ** 6).toString());
+  });
+
+  describe("protocol fee", () => {
+    let treasury: anchor.web3.PublicKey;
+    let treasuryTokenAccount: anchor.web3.PublicKey;
+
+    before(async () => {
+      treasury = ctx.provider.wallet.publicKey;
+      treasuryTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          ctx.connection,
+          ctx.creator,
+          ctx.usdcMint,
+          treasury
+        )
+      ).address;
+    });
+
+    afterEach(async () => {
+      await setFeeBps(ctx, 0);
+    });
+
+    async function settleWithTreasury(bountyPda: anchor.web3.PublicKey) {
+      return ctx.program.methods
+        .settleBounty()
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          reputation: reputationPda,
+          agent: agent.publicKey,
+          agentTokenAccount,
+          bountyTokenAccount: getAssociatedTokenAddressSync(
+            ctx.usdcMint,
+            bountyPda
+          ),
+          treasury,
+          treasuryTokenAccount,
+          rewardMint: ctx.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+    }
+
+    async function balanceOf(account: anchor.web3.PublicKey) {
+      const balance = await ctx.connection.getTokenAccountBalance(account);
+      return Number(balance.value.amount);
+    }
+
+    it("Skips the treasury entirely at 0 bps", async () => {
+      const treasuryBefore = await balanceOf(treasuryTokenAccount);
+
+      // no treasury accounts passed at all
+      await settleBounty(ctx, agent.publicKey, testBountyPda, agentTokenAccount);
+
+      expect(await balanceOf(treasuryTokenAccount)).to.equal(treasuryBefore);
+      expect(await balanceOf(testBountyTokenAccount)).to.equal(0);
+    });
+
+    it("Splits the payout at 250 bps", async () => {
+      await setFeeBps(ctx, 250);
+      const reward = 100 * 10 ** 6;
+      const agentBefore = await balanceOf(agentTokenAccount);
+      const treasuryBefore = await balanceOf(treasuryTokenAccount);
+
+      const signature = await settleWithTreasury(testBountyPda);
+
+      expect(await balanceOf(agentTokenAccount)).to.equal(
+        agentBefore + 97_500_000
+      );
+      expect(await balanceOf(treasuryTokenAccount)).to.equal(
+        treasuryBefore + 2_500_000
+      );
+      expect(await balanceOf(testBountyTokenAccount)).to.equal(0);
+
+      const event = findEvent(
+        await fetchEvents(ctx, signature),
+        "BountySettled"
+      );
+      expect(event.data.solverAmount.toNumber()).to.equal(97_500_000);
+      expect(event.data.feeAmount.toNumber()).to.equal(2_500_000);
+      expect(
+        event.data.solverAmount.toNumber() + event.data.feeAmount.toNumber()
+      ).to.equal(reward);
+    });
+
+    it("Rounds the solver share down when the reward does not divide evenly", async () => {
+      await setFeeBps(ctx, 250);
+      const reward = 100_000_001;
+      const bountyPda = await postBounty(ctx, "Odd reward", reward);
+      const hash = generateSolutionHashWithValue(0xab);
+      const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+      await submitSolution(ctx, agent, bountyPda, attestation, hash);
+
+      const agentBefore = await balanceOf(agentTokenAccount);
+      const treasuryBefore = await balanceOf(treasuryTokenAccount);
+
+      await settleWithTreasury(bountyPda);
+
+      // 100_000_001 * 9_750 / 10_000 = 97_500_000.975, floored for the solver
+      expect(await balanceOf(agentTokenAccount)).to.equal(
+        agentBefore + 97_500_000
+      );
+      expect(await balanceOf(treasuryTokenAccount)).to.equal(
+        treasuryBefore + 2_500_001
+      );
+    });
+
+    it("Fails when a fee is due but no treasury is passed", async () => {
+      await setFeeBps(ctx, 250);
+
+      try {
+        await settleBounty(
+          ctx,
+          agent.publicKey,
+          testBountyPda,
+          agentTokenAccount
+        );
+        expect.fail("Should have failed - treasury missing");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("MissingTreasury");
+      }
+    });
+  });
+
+  describe("challenge period", () => {
+    const period = 20;
+
+    afterEach(async () => {
+      await updateConfig(ctx, { defaultChallengePeriodSlots: new anchor.BN(0) });
+    });
+
+    async function submitToChallengedBounty(
+      options: PostBountyOptions
+    ): Promise<anchor.web3.PublicKey> {
+      const bountyPda = await postBounty(
+        ctx,
+        "Crowdfunded bounty",
+        10 * 10 ** 6,
+        options
+      );
+      const hash = generateSolutionHashWithValue(0xc0);
+      const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+      await submitSolution(ctx, agent, bountyPda, attestation, hash);
+      return bountyPda;
+    }
+
+    it("Fails a slot early and settles at the boundary", async () => {
+      const bountyPda = await submitToChallengedBounty({
+        challengePeriodSlots: period,
+      });
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      const endSlot =
+        bountyAccount.submittedAtSlot.toNumber() +
+        bountyAccount.challengePeriodSlots.toNumber();
+
+      // sent while the cluster is still short of the last slot, so it
+      // lands at endSlot - 1 at the latest
+      await waitForSlot(ctx.connection, endSlot - 2);
+      try {
+        await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+        expect.fail("Should have failed - challenge period still running");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("ChallengePeriodActive");
+      }
+
+      await waitForSlot(ctx.connection, endSlot);
+      await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+
+      const settled = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(settled.status).to.deep.equal({ settled: {} });
+    });
+
+    it("Keeps the period fixed at posting when the default changes", async () => {
+      await updateConfig(ctx, {
+        defaultChallengePeriodSlots: new anchor.BN(period),
+      });
+      const bountyPda = await submitToChallengedBounty({});
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bountyAccount.challengePeriodSlots.toNumber()).to.equal(period);
+
+      await updateConfig(ctx, { defaultChallengePeriodSlots: new anchor.BN(0) });
+      try {
+        await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+        expect.fail("Should have failed - period was set at posting");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("ChallengePeriodActive");
+      }
+    });
+
+    it("Settles at once when posted with no period", async () => {
+      await updateConfig(ctx, {
+        defaultChallengePeriodSlots: new anchor.BN(period),
+      });
+      const bountyPda = await submitToChallengedBounty({
+        challengePeriodSlots: 0,
+      });
+
+      await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+    });
+  });
+});
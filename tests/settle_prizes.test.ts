@@ -0,0 +1,153 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveSubmissionPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  selectWinner,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("settle_prizes", () => {
+  const prizes = [50 * 10 ** 6, 30 * 10 ** 6, 20 * 10 ** 6];
+  const reward = 100 * 10 ** 6;
+
+  let ctx: TestContext;
+  let agents: Keypair[];
+  let agentTokenAccounts: anchor.web3.PublicKey[];
+  let submissionPdas: anchor.web3.PublicKey[];
+  let bountyPda: anchor.web3.PublicKey;
+  let bountyTokenAccount: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      reward
+    );
+
+    bountyPda = await postBounty(
+      ctx,
+      "Screening competition",
+      reward,
+      { competitive: true, prizes }
+    );
+    bountyTokenAccount = getAssociatedTokenAddressSync(
+      ctx.usdcMint,
+      bountyPda
+    );
+
+    agents = [];
+    agentTokenAccounts = [];
+    submissionPdas = [];
+    for (let i = 0; i < 4; i++) {
+      const agent = Keypair.generate();
+      await airdropSol(ctx.connection, agent.publicKey);
+      agents.push(agent);
+      agentTokenAccounts.push(
+        await createAgentTokenAccount(ctx.connection, agent, ctx.usdcMint)
+      );
+
+      const hash = generateSolutionHashWithValue(0x90 + i);
+      const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+      await submitSolution(ctx, agent, bountyPda, attestation, hash);
+      submissionPdas.push(
+        deriveSubmissionPda(ctx.program.programId, bountyPda, agent.publicKey)[0]
+      );
+    }
+  });
+
+  // places are given as indexes into agents, best first
+  async function settlePrizes(places: number[]): Promise<void> {
+    await ctx.program.methods
+      .settlePrizes()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        bountyTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .remainingAccounts(
+        places.flatMap((i) => [
+          { pubkey: submissionPdas[i], isSigner: false, isWritable: true },
+          { pubkey: agents[i].publicKey, isSigner: false, isWritable: true },
+          { pubkey: agentTokenAccounts[i], isSigner: false, isWritable: true },
+        ])
+      )
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function tokenBalance(account: anchor.web3.PublicKey): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  it("Pays each place its slice and drains the escrow", async () => {
+    const places = [2, 0, 3];
+    const before = await Promise.all(agentTokenAccounts.map(tokenBalance));
+    expect(await tokenBalance(bountyTokenAccount)).to.equal(reward);
+
+    await settlePrizes(places);
+
+    expect(await tokenBalance(bountyTokenAccount)).to.equal(0);
+    const after = await Promise.all(agentTokenAccounts.map(tokenBalance));
+    places.forEach((agentIndex, place) => {
+      expect(after[agentIndex] - before[agentIndex]).to.equal(prizes[place]);
+    });
+    expect(after[1]).to.equal(before[1]);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ settled: {} });
+    for (const i of places) {
+      const submission = await ctx.program.account.submission.fetch(
+        submissionPdas[i]
+      );
+      expect(submission.status).to.deep.equal({ accepted: {} });
+    }
+  });
+
+  it("Fails when the number of winners does not match the schedule", async () => {
+    try {
+      await settlePrizes([0, 1]);
+      expect.fail("Should have failed - one place unfilled");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("WinnerCountMismatch");
+    }
+  });
+
+  it("Fails when an agent is listed for two places", async () => {
+    try {
+      await settlePrizes([0, 1, 0]);
+      expect.fail("Should have failed - duplicate winner");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("DuplicateWinner");
+    }
+  });
+
+  it("Does not let select_winner bypass the schedule", async () => {
+    try {
+      await selectWinner(ctx, bountyPda, submissionPdas[0]);
+      expect.fail("Should have failed - bounty pays by schedule");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyHasPrizeSchedule");
+    }
+  });
+});
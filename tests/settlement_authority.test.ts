@@ -0,0 +1,189 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  nextRejectionPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  generateSolutionHashWithValue,
+  DEFAULT_REJECTION_REASON,
+  TestContext,
+} from "./helpers";
+
+describe("settlement_authority", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+  let delegate: Keypair;
+  let delegateCtx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    delegate = Keypair.generate();
+    await airdropSol(ctx.connection, delegate.publicKey);
+    // the delegate signs where the creator would, paying for the settlement records
+    delegateCtx = { ...ctx, creator: delegate };
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  async function postDelegated(): Promise<anchor.web3.PublicKey> {
+    return postBounty(ctx, "Paid out by the ops key", reward, {
+      challengePeriodSlots: 0,
+      settlementAuthority: delegate.publicKey,
+    });
+  }
+
+  async function submit(
+    bountyPda: anchor.web3.PublicKey
+  ): Promise<[Keypair, anchor.web3.PublicKey]> {
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    const hash = generateSolutionHashWithValue(0x5d);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    return [agent, agentTokenAccount];
+  }
+
+  async function setAuthority(
+    bountyPda: anchor.web3.PublicKey,
+    authority: anchor.web3.PublicKey | null,
+    signer = ctx.creator
+  ) {
+    return ctx.program.methods
+      .setSettlementAuthority(authority)
+      .accountsPartial({ creator: signer.publicKey, bounty: bountyPda })
+      .signers([signer])
+      .rpc();
+  }
+
+  it("Lets the settlement authority settle", async () => {
+    const bountyPda = await postDelegated();
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.settlementAuthority.toString()).to.equal(
+      delegate.publicKey.toString()
+    );
+
+    const [agent, agentTokenAccount] = await submit(bountyPda);
+    await settleBounty(delegateCtx, agent.publicKey, bountyPda, agentTokenAccount);
+
+    const settled = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(settled.status).to.deep.equal({ settled: {} });
+    const balance = await ctx.connection.getTokenAccountBalance(
+      agentTokenAccount
+    );
+    expect(Number(balance.value.amount)).to.equal(reward);
+  });
+
+  it("Lets the settlement authority reject, crediting the creator", async () => {
+    const bountyPda = await postDelegated();
+    const [agent] = await submit(bountyPda);
+
+    const rejectionPda = await nextRejectionPda(
+      ctx,
+      bountyPda,
+      agent.publicKey
+    );
+    await ctx.program.methods
+      .rejectSolution(DEFAULT_REJECTION_REASON, null)
+      .accountsPartial({
+        creator: delegate.publicKey,
+        bounty: bountyPda,
+        agent: agent.publicKey,
+        bountyCreator: ctx.creator.publicKey,
+        rejection: rejectionPda,
+      })
+      .signers([delegate])
+      .rpc();
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ open: {} });
+    expect(bounty.solver).to.be.null;
+    const rejection = await ctx.program.account.rejection.fetch(rejectionPda);
+    expect(rejection.rejectedBy.toString()).to.equal(
+      delegate.publicKey.toString()
+    );
+  });
+
+  it("Fails when the settlement authority tries to cancel", async () => {
+    const bountyPda = await postDelegated();
+
+    try {
+      await ctx.program.methods
+        .cancelBounty()
+        .accountsPartial({
+          creator: delegate.publicKey,
+          bounty: bountyPda,
+          unwrapAccount: null,
+          creatorTokenAccount: ctx.creatorTokenAccount,
+          bountyTokenAccount: getAssociatedTokenAddressSync(
+            ctx.usdcMint,
+            bountyPda
+          ),
+          rewardMint: ctx.usdcMint,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([delegate])
+        .rpc();
+      expect.fail("Should have failed - only the creator cancels");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedCancellation");
+    }
+  });
+
+  it("Fails when the settlement authority tries to change it", async () => {
+    const bountyPda = await postDelegated();
+
+    try {
+      await setAuthority(bountyPda, null, delegate);
+      expect.fail("Should have failed - only the creator delegates");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedDelegation");
+    }
+  });
+
+  it("Stops the settlement authority once the creator revokes it", async () => {
+    const bountyPda = await postDelegated();
+    const [agent, agentTokenAccount] = await submit(bountyPda);
+
+    await setAuthority(bountyPda, null);
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.settlementAuthority).to.be.null;
+
+    try {
+      await settleBounty(
+        delegateCtx,
+        agent.publicKey,
+        bountyPda,
+        agentTokenAccount
+      );
+      expect.fail("Should have failed - delegation revoked");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedSettlement");
+    }
+
+    // the creator still settles it
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+  });
+});
@@ -0,0 +1,209 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveSettlementReceiptPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  setFeeBps,
+  updateConfig,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("settlement_receipt", () => {
+  const reward = 40 * 10 ** 6;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let treasuryTokenAccount: anchor.web3.PublicKey;
+  let bountyPda: anchor.web3.PublicKey;
+  let bountyTokenAccount: anchor.web3.PublicKey;
+  let receiptPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    treasuryTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        ctx.connection,
+        ctx.creator,
+        ctx.usdcMint,
+        ctx.provider.wallet.publicKey
+      )
+    ).address;
+    await setFeeBps(ctx, 250);
+  });
+
+  after(async () => {
+    await updateConfig(ctx, {
+      feeBps: 0,
+      receiptRetentionSeconds: new anchor.BN(0),
+    });
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+
+    bountyPda = await postBounty(ctx, "Audited payout", reward, {
+      challengePeriodSlots: 0,
+    });
+    bountyTokenAccount = getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda);
+    [receiptPda] = deriveSettlementReceiptPda(ctx.program.programId, bountyPda);
+
+    const hash = generateSolutionHashWithValue(0x52);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+  });
+
+  async function settle() {
+    await ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        agent: agent.publicKey,
+        agentTokenAccount,
+        bountyTokenAccount,
+        treasury: ctx.provider.wallet.publicKey,
+        treasuryTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function closeBounty() {
+    await ctx.program.methods
+      .closeBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        creatorBond: null,
+        bountyTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  function closeReceipt(signer: Keypair) {
+    return ctx.program.methods
+      .closeReceipt()
+      .accountsPartial({
+        creator: signer.publicKey,
+        settlementReceipt: receiptPda,
+      })
+      .signers([signer])
+      .rpc();
+  }
+
+  async function balanceOf(account: anchor.web3.PublicKey) {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  it("Records exactly what moved at settlement", async () => {
+    const agentBefore = await balanceOf(agentTokenAccount);
+    const treasuryBefore = await balanceOf(treasuryTokenAccount);
+    const escrowBefore = await balanceOf(bountyTokenAccount);
+
+    await settle();
+
+    const receipt = await ctx.program.account.settlementReceipt.fetch(
+      receiptPda
+    );
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(receipt.bounty.toString()).to.equal(bountyPda.toString());
+    expect(receipt.bountyId.toNumber()).to.equal(bountyAccount.id.toNumber());
+    expect(receipt.creator.toString()).to.equal(
+      ctx.creator.publicKey.toString()
+    );
+    expect(receipt.solver.toString()).to.equal(agent.publicKey.toString());
+    expect(receipt.settledBy.toString()).to.equal(
+      ctx.creator.publicKey.toString()
+    );
+    expect(receipt.mint.toString()).to.equal(ctx.usdcMint.toString());
+    expect(receipt.settledAt.toNumber()).to.be.greaterThan(0);
+
+    expect(receipt.grossReward.toNumber()).to.equal(
+      escrowBefore - (await balanceOf(bountyTokenAccount))
+    );
+    expect(receipt.fee.toNumber()).to.equal(
+      (await balanceOf(treasuryTokenAccount)) - treasuryBefore
+    );
+    expect(receipt.netPaid.toNumber()).to.equal(
+      (await balanceOf(agentTokenAccount)) - agentBefore
+    );
+    expect(receipt.fee.toNumber()).to.equal(reward * 0.025);
+    expect(
+      receipt.netPaid.toNumber() + receipt.fee.toNumber()
+    ).to.equal(receipt.grossReward.toNumber());
+  });
+
+  it("Outlives the bounty and closes only after the retention period", async () => {
+    await settle();
+    await closeBounty();
+    expect(await ctx.connection.getAccountInfo(bountyPda)).to.be.null;
+    expect(await ctx.connection.getAccountInfo(receiptPda)).to.not.be.null;
+
+    await updateConfig(ctx, {
+      receiptRetentionSeconds: new anchor.BN(3600),
+    });
+    try {
+      await closeReceipt(ctx.creator);
+      expect.fail("Should have failed - receipt still retained");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ReceiptRetentionActive");
+    }
+
+    await updateConfig(ctx, { receiptRetentionSeconds: new anchor.BN(0) });
+    const rent = await ctx.connection.getBalance(receiptPda);
+    const creatorBefore = await ctx.connection.getBalance(
+      ctx.creator.publicKey
+    );
+    await closeReceipt(ctx.creator);
+
+    expect(await ctx.connection.getAccountInfo(receiptPda)).to.be.null;
+    // the provider pays the fee, so the creator gets the rent back in full
+    expect(
+      (await ctx.connection.getBalance(ctx.creator.publicKey)) - creatorBefore
+    ).to.equal(rent);
+  });
+
+  it("Fails to close the receipt for anyone but the creator", async () => {
+    await settle();
+
+    try {
+      await closeReceipt(agent);
+      expect.fail("Should have failed - not the creator");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedReceiptClose");
+    }
+  });
+});
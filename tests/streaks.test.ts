@@ -0,0 +1,106 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  rejectSolution,
+  fetchEvents,
+  findEvent,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("streaks", () => {
+  const reward = 1 * 10 ** 6;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let reputationPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  async function submitted(): Promise<anchor.web3.PublicKey> {
+    const bountyPda = await postBounty(ctx, "Streak work", reward);
+    const hash = generateSolutionHashWithValue(0x5e);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    return bountyPda;
+  }
+
+  // settles and returns the streak reported in BountySettled
+  async function win(): Promise<number> {
+    const bountyPda = await submitted();
+    const signature = await ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        reputation: reputationPda,
+        agent: agent.publicKey,
+        agentTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+    const event = findEvent(await fetchEvents(ctx, signature), "BountySettled");
+    return event.data.streak;
+  }
+
+  it("Builds a streak, resets it on a failure and keeps the longest", async () => {
+    for (let streak = 1; streak <= 3; streak++) {
+      expect(await win()).to.equal(streak);
+    }
+    let reputation = await ctx.program.account.reputation.fetch(reputationPda);
+    expect(reputation.currentStreak).to.equal(3);
+    expect(reputation.longestStreak).to.equal(3);
+
+    await rejectSolution(ctx, agent.publicKey, await submitted());
+    reputation = await ctx.program.account.reputation.fetch(reputationPda);
+    expect(reputation.currentStreak).to.equal(0);
+    expect(reputation.longestStreak).to.equal(3);
+
+    expect(await win()).to.equal(1);
+    reputation = await ctx.program.account.reputation.fetch(reputationPda);
+    expect(reputation.currentStreak).to.equal(1);
+    expect(reputation.longestStreak).to.equal(3);
+  });
+});
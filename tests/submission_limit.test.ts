@@ -0,0 +1,100 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  rejectSolution,
+  fetchEvents,
+  findEvent,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("submission_limit", () => {
+  const reward = 5 * 10 ** 6;
+
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  // a fresh agent submitting to the bounty, returning the transaction signature
+  async function submitAs(
+    bountyPda: PublicKey,
+    agent: Keypair = Keypair.generate()
+  ): Promise<[Keypair, string]> {
+    await airdropSol(ctx.connection, agent.publicKey);
+    const hash = generateSolutionHashWithValue(0x4d);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    return [agent, await submitSolution(ctx, agent, bountyPda, attestation, hash)];
+  }
+
+  it("Admits two agents under a cap of 2 and refuses the third", async () => {
+    const bountyPda = await postBounty(ctx, "Popular contest", reward, {
+      competitive: true,
+      maxSubmissions: 2,
+    });
+
+    const [, first] = await submitAs(bountyPda);
+    const [, second] = await submitAs(bountyPda);
+    expect(
+      findEvent(await fetchEvents(ctx, first), "SolutionSubmitted").data
+        .remainingSubmissions
+    ).to.equal(1);
+    expect(
+      findEvent(await fetchEvents(ctx, second), "SolutionSubmitted").data
+        .remainingSubmissions
+    ).to.equal(0);
+
+    try {
+      await submitAs(bountyPda);
+      expect.fail("Should have failed - both slots taken");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SubmissionLimitReached");
+    }
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.liveSubmissions).to.equal(2);
+  });
+
+  it("Frees the slot of a rejected submission", async () => {
+    const bountyPda = await postBounty(ctx, "Single-slot audit", reward, {
+      maxSubmissions: 1,
+    });
+    const [agent] = await submitAs(bountyPda);
+
+    await rejectSolution(ctx, agent.publicKey, bountyPda);
+    let bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.liveSubmissions).to.equal(0);
+
+    await submitAs(bountyPda);
+    bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.liveSubmissions).to.equal(1);
+    expect(bounty.submissionCount).to.equal(2);
+  });
+
+  it("Reports no limit on an uncapped bounty", async () => {
+    const bountyPda = await postBounty(ctx, "Uncapped contest", reward, {
+      competitive: true,
+    });
+
+    const [, signature] = await submitAs(bountyPda);
+
+    const event = findEvent(await fetchEvents(ctx, signature), "SolutionSubmitted");
+    expect(event.data.remainingSubmissions).to.be.null;
+  });
+});
@@ -0,0 +1,204 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveSubmissionPda,
+  getAssociatedTokenAddressSync,
+  nextRejectionPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  rejectSolution,
+  updateConfig,
+  generateSolutionHashWithValue,
+  DEFAULT_REJECTION_REASON,
+  PostBountyOptions,
+  TestContext,
+} from "./helpers";
+
+describe("submission stake", () => {
+  const stake = LAMPORTS_PER_SOL / 4;
+  const treasuryBps = 2_000;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    await updateConfig(ctx, { stakeSlashTreasuryBps: treasuryBps });
+  });
+
+  after(async () => {
+    await updateConfig(ctx, { stakeSlashTreasuryBps: 0 });
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+  });
+
+  async function postAndSubmit(options: PostBountyOptions = {}) {
+    const bountyPda = await postBounty(
+      ctx,
+      "Staked bounty",
+      10 * 10 ** 6,
+      { requiredStake: stake, ...options }
+    );
+    const hash = generateSolutionHashWithValue(0xf0);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    const [submissionPda] = deriveSubmissionPda(
+      ctx.program.programId,
+      bountyPda,
+      agent.publicKey
+    );
+    return { bountyPda, submissionPda };
+  }
+
+  async function rentOf(account: anchor.web3.PublicKey): Promise<number> {
+    const info = await ctx.connection.getAccountInfo(account);
+    return ctx.connection.getMinimumBalanceForRentExemption(info.data.length);
+  }
+
+  it("Locks the stake on the submission and returns it on settlement", async () => {
+    const { bountyPda, submissionPda } = await postAndSubmit();
+
+    const submission = await ctx.program.account.submission.fetch(
+      submissionPda
+    );
+    expect(submission.stake.toNumber()).to.equal(stake);
+    expect(await ctx.connection.getBalance(submissionPda)).to.equal(
+      (await rentOf(submissionPda)) + stake
+    );
+
+    const agentBefore = await ctx.connection.getBalance(agent.publicKey);
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+
+    expect(await ctx.connection.getBalance(agent.publicKey)).to.equal(
+      agentBefore + stake
+    );
+    const settled = await ctx.program.account.submission.fetch(submissionPda);
+    expect(settled.stake.toNumber()).to.equal(0);
+    expect(settled.status).to.deep.equal({ accepted: {} });
+  });
+
+  it("Slashes the stake to the creator and treasury on rejection", async () => {
+    const { bountyPda, submissionPda } = await postAndSubmit();
+    const treasury = ctx.provider.wallet.publicKey;
+
+    const creatorBefore = await ctx.connection.getBalance(
+      ctx.creator.publicKey
+    );
+    const treasuryBefore = await ctx.connection.getBalance(treasury);
+    const rejectionPda = await nextRejectionPda(
+      ctx,
+      bountyPda,
+      agent.publicKey
+    );
+
+    // the provider wallet, which is also the treasury, pays the fee
+    const signature = await ctx.program.methods
+      .rejectSolution(DEFAULT_REJECTION_REASON, null)
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        agent: agent.publicKey,
+        rejection: rejectionPda,
+        treasury,
+      })
+      .signers([ctx.creator])
+      .rpc();
+    const tx = await ctx.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    // the creator also pays for the rejection record
+    const treasuryShare = (stake * treasuryBps) / 10_000;
+    expect(await ctx.connection.getBalance(ctx.creator.publicKey)).to.equal(
+      creatorBefore + stake - treasuryShare - (await rentOf(rejectionPda))
+    );
+    expect(await ctx.connection.getBalance(treasury)).to.equal(
+      treasuryBefore + treasuryShare - tx.meta.fee
+    );
+    expect(await ctx.connection.getBalance(submissionPda)).to.equal(
+      await rentOf(submissionPda)
+    );
+
+    const submission = await ctx.program.account.submission.fetch(
+      submissionPda
+    );
+    expect(submission.stake.toNumber()).to.equal(0);
+  });
+
+  it("Fails to reject without the treasury when part of the stake goes there", async () => {
+    const { bountyPda } = await postAndSubmit();
+
+    try {
+      await rejectSolution(ctx, agent.publicKey, bountyPda);
+      expect.fail("Should have failed - treasury missing");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MissingTreasury");
+    }
+  });
+
+  it("Refunds the stake in full when the bounty is cancelled", async () => {
+    const { bountyPda, submissionPda } = await postAndSubmit({
+      competitive: true,
+    });
+
+    await ctx.program.methods
+      .cancelBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        unwrapAccount: null,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+
+    const held = await ctx.connection.getBalance(submissionPda);
+    expect(held).to.equal((await rentOf(submissionPda)) + stake);
+    const agentBefore = await ctx.connection.getBalance(agent.publicKey);
+    const signature = await ctx.program.methods
+      .closeSubmission()
+      .accountsPartial({ agent: agent.publicKey, bounty: bountyPda })
+      .signers([agent])
+      .rpc();
+    const tx = await ctx.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+
+    expect(await ctx.connection.getAccountInfo(submissionPda)).to.be.null;
+    expect(await ctx.connection.getBalance(agent.publicKey)).to.equal(
+      agentBefore + held - tx.meta.fee
+    );
+  });
+});
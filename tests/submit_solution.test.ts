@@ -3,21 +3,38 @@ import { Keypair, SystemProgram } from "@solana/web3.js";
 import { expect } from "chai";
 import {
   setupTestContext,
+  deriveAttestationPda,
+  deriveAttestorPda,
   deriveReputationPda,
+  deriveSubmissionPda,
   airdropSol,
+  initReputation,
+  registerAttestorIx,
   postBounty,
   createAttestation,
-  generateRandomId,
+  submitSolution,
   generateSolutionHashWithValue,
+  sleep,
+  waitForClock,
+  updateConfig,
+  SWITCHBOARD_FEED_FRESH,
+  SWITCHBOARD_FEED_FRESH_2,
+  SWITCHBOARD_FEED_STALE,
+  SWITCHBOARD_FEED_WRONG_OWNER,
+  PYTH_PRICE_FRESH,
+  PYTH_PRICE_STALE,
+  PYTH_PRICE_WIDE_CONFIDENCE,
+  fetchEvents,
+  findEvent,
+  DEFAULT_HASH_ALGO,
+  NO_CIPHERTEXT_HASH,
   TestContext,
 } from "./helpers";
 
 describe("submit_solution", () => {
   let ctx: TestContext;
   let agent: Keypair;
-  let testBountyId: number;
   let testBountyPda: anchor.web3.PublicKey;
-  let solutionId: number;
   let attestationPda: anchor.web3.PublicKey;
   let solutionHash: Buffer;
   let reputationPda: anchor.web3.PublicKey;
@@ -30,34 +47,85 @@ describe("submit_solution", () => {
     agent = Keypair.generate();
     await airdropSol(ctx.connection, agent.publicKey);
 
-    testBountyId = generateRandomId();
     testBountyPda = await postBounty(
       ctx,
-      testBountyId,
       "Test bounty for submission",
       100 * 10 ** 6
     );
 
-    solutionId = generateRandomId();
+    [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    await initReputation(ctx, agent);
+
     solutionHash = generateSolutionHashWithValue(0xab);
     attestationPda = await createAttestation(
       ctx,
       agent,
-      solutionId,
+      testBountyPda,
       solutionHash
     );
+  });
 
-    [reputationPda] = deriveReputationPda(
-      ctx.program.programId,
-      agent.publicKey
+  it("Initializes a zeroed reputation once", async () => {
+    const reputation = await ctx.program.account.reputation.fetch(
+      reputationPda
     );
+    expect(reputation.agent.toString()).to.equal(agent.publicKey.toString());
+    expect(reputation.score.toNumber()).to.equal(0);
+    expect(reputation.successfulBounties.toNumber()).to.equal(0);
+    expect(reputation.openSubmissions).to.equal(0);
+
+    try {
+      await initReputation(ctx, agent);
+      expect.fail("Should have failed - reputation already initialized");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("ReputationAlreadyInitialized");
+    }
+  });
+
+  it("Fails for a first-time agent without a reputation", async () => {
+    const newcomer = Keypair.generate();
+    await airdropSol(ctx.connection, newcomer.publicKey);
+    const hash = generateSolutionHashWithValue(0xa1);
+
+    // the reputation hands out the solution id, so not even attesting works yet
+    try {
+      await ctx.program.methods
+        .attestSolution(Array.from(hash), DEFAULT_HASH_ALGO)
+        .accountsPartial({
+          agent: newcomer.publicKey,
+          bounty: testBountyPda,
+          reputation: deriveReputationPda(
+            ctx.program.programId,
+            newcomer.publicKey
+          )[0],
+          attestation: deriveAttestationPda(
+            ctx.program.programId,
+            newcomer.publicKey,
+            testBountyPda,
+            0
+          )[0],
+          instructionsSysvar: null,
+        })
+        .preInstructions([await registerAttestorIx(ctx, newcomer.publicKey)])
+        .signers([newcomer])
+        .rpc();
+      expect.fail("Should have failed - reputation not initialized");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+      expect(err.error.origin).to.equal("reputation");
+    }
   });
 
   it("Submits a solution successfully and updates bounty and reputation", async () => {
-    await ctx.program.methods
-      .submitSolution(Array.from(solutionHash))
+    const signature = await ctx.program.methods
+      .submitSolution(Array.from(solutionHash), NO_CIPHERTEXT_HASH, "", [])
       .accountsPartial({
+        submitter: agent.publicKey,
         agent: agent.publicKey,
+        operatorRecord: null,
         bounty: testBountyPda,
         attestation: attestationPda,
         reputation: reputationPda,
@@ -80,13 +148,24 @@ describe("submit_solution", () => {
     expect(reputationAccount.successfulBounties.toNumber()).to.equal(0);
     expect(reputationAccount.failedBounties.toNumber()).to.equal(0);
     expect(reputationAccount.totalEarned.toNumber()).to.equal(0);
+
+    const event = findEvent(
+      await fetchEvents(ctx, signature),
+      "SolutionSubmitted"
+    );
+    expect(event.data.bounty.toString()).to.equal(testBountyPda.toString());
+    expect(event.data.agent.toString()).to.equal(agent.publicKey.toString());
+    expect(Buffer.from(event.data.solutionHash)).to.deep.equal(solutionHash);
+    expect(event.data.timestamp.toNumber()).to.be.greaterThan(0);
   });
 
   it("Increments reputation score for existing reputation", async () => {
     await ctx.program.methods
-      .submitSolution(Array.from(solutionHash))
+      .submitSolution(Array.from(solutionHash), NO_CIPHERTEXT_HASH, "", [])
       .accountsPartial({
+        submitter: agent.publicKey,
         agent: agent.publicKey,
+        operatorRecord: null,
         bounty: testBountyPda,
         attestation: attestationPda,
         reputation: reputationPda,
@@ -95,22 +174,22 @@ describe("submit_solution", () => {
       .signers([agent])
       .rpc();
 
-    const bountyId2 = generateRandomId();
-    const bountyPda2 = await postBounty(ctx, bountyId2, "Second bounty", 50 * 10 ** 6);
+    const bountyPda2 = await postBounty(ctx, "Second bounty", 50 * 10 ** 6);
 
-    const solutionId2 = generateRandomId();
     const solutionHash2 = generateSolutionHashWithValue(0xcd);
     const attestationPda2 = await createAttestation(
       ctx,
       agent,
-      solutionId2,
+      bountyPda2,
       solutionHash2
     );
 
     await ctx.program.methods
-      .submitSolution(Array.from(solutionHash2))
+      .submitSolution(Array.from(solutionHash2), NO_CIPHERTEXT_HASH, "", [])
       .accountsPartial({
+        submitter: agent.publicKey,
         agent: agent.publicKey,
+        operatorRecord: null,
         bounty: bountyPda2,
         attestation: attestationPda2,
         reputation: reputationPda,
@@ -127,9 +206,11 @@ describe("submit_solution", () => {
 
   it("Fails when bounty is not in Open status", async () => {
     await ctx.program.methods
-      .submitSolution(Array.from(solutionHash))
+      .submitSolution(Array.from(solutionHash), NO_CIPHERTEXT_HASH, "", [])
       .accountsPartial({
+        submitter: agent.publicKey,
         agent: agent.publicKey,
+        operatorRecord: null,
         bounty: testBountyPda,
         attestation: attestationPda,
         reputation: reputationPda,
@@ -138,20 +219,21 @@ describe("submit_solution", () => {
       .signers([agent])
       .rpc();
 
-    const solutionId2 = generateRandomId();
     const solutionHash2 = generateSolutionHashWithValue(0xef);
     const attestationPda2 = await createAttestation(
       ctx,
       agent,
-      solutionId2,
+      testBountyPda,
       solutionHash2
     );
 
     try {
       await ctx.program.methods
-        .submitSolution(Array.from(solutionHash2))
+        .submitSolution(Array.from(solutionHash2), NO_CIPHERTEXT_HASH, "", [])
         .accountsPartial({
+          submitter: agent.publicKey,
           agent: agent.publicKey,
+          operatorRecord: null,
           bounty: testBountyPda,
           attestation: attestationPda2,
           reputation: reputationPda,
@@ -171,9 +253,11 @@ describe("submit_solution", () => {
 
     try {
       await ctx.program.methods
-        .submitSolution(Array.from(wrongHash))
+        .submitSolution(Array.from(wrongHash), NO_CIPHERTEXT_HASH, "", [])
         .accountsPartial({
+          submitter: agent.publicKey,
           agent: agent.publicKey,
+          operatorRecord: null,
           bounty: testBountyPda,
           attestation: attestationPda,
           reputation: reputationPda,
@@ -192,20 +276,26 @@ describe("submit_solution", () => {
     const differentAgent = Keypair.generate();
     await airdropSol(ctx.connection, differentAgent.publicKey);
 
-    const solutionId2 = generateRandomId();
+    // same bounty and the same solution id 0, but derived from the other agent
     const solutionHash2 = generateSolutionHashWithValue(0x11);
     const attestationPda2 = await createAttestation(
       ctx,
       differentAgent,
-      solutionId2,
+      testBountyPda,
       solutionHash2
     );
+    const attestation2 = await ctx.program.account.attestation.fetch(
+      attestationPda2
+    );
+    expect(attestation2.solutionId.toNumber()).to.equal(0);
 
     try {
       await ctx.program.methods
-        .submitSolution(Array.from(solutionHash2))
+        .submitSolution(Array.from(solutionHash2), NO_CIPHERTEXT_HASH, "", [])
         .accountsPartial({
+          submitter: agent.publicKey,
           agent: agent.publicKey,
+          operatorRecord: null,
           bounty: testBountyPda,
           attestation: attestationPda2,
           reputation: reputationPda,
@@ -216,21 +306,76 @@ describe("submit_solution", () => {
 
       expect.fail("Should have failed - attestation owner mismatch");
     } catch (err) {
-      expect(err).to.exist;
+      expect(err.error.errorCode.code).to.equal("ConstraintSeeds");
+    }
+  });
+
+  it("Fails when an attestation for one bounty is replayed on another", async () => {
+    const otherBountyPda = await postBounty(
+      ctx,
+      "Bounty with the same expected hash",
+      100 * 10 ** 6
+    );
+
+    try {
+      await submitSolution(
+        ctx,
+        agent,
+        otherBountyPda,
+        attestationPda,
+        solutionHash
+      );
+      expect.fail("Should have failed - attestation bound to another bounty");
+    } catch (err) {
+      // the attestation's address is derived from the bounty it was made for
+      expect(err.error.errorCode.code).to.equal("ConstraintSeeds");
+    }
+
+    const otherBounty = await ctx.program.account.bounty.fetch(otherBountyPda);
+    expect(otherBounty.status).to.deep.equal({ open: {} });
+  });
+
+  it("Fails when a consumed attestation backs a second submission", async () => {
+    await submitSolution(ctx, agent, testBountyPda, attestationPda, solutionHash);
+    const attestation = await ctx.program.account.attestation.fetch(
+      attestationPda
+    );
+    expect(attestation.consumed).to.be.true;
+
+    const otherBountyPda = await postBounty(
+      ctx,
+      "Bounty the attestation was never for",
+      100 * 10 ** 6
+    );
+    try {
+      await submitSolution(
+        ctx,
+        agent,
+        otherBountyPda,
+        attestationPda,
+        solutionHash
+      );
+      expect.fail("Should have failed - attestation already consumed");
+    } catch (err) {
+      // caught by the address before the consumed flag, see reject_solution
+      // for the same bounty
+      expect(err.error.errorCode.code).to.equal("ConstraintSeeds");
     }
   });
 
   it("Allows different agents to submit solutions to different bounties", async () => {
     const agent2 = Keypair.generate();
     await airdropSol(ctx.connection, agent2.publicKey);
+    await initReputation(ctx, agent2);
 
-    const bountyId2 = generateRandomId();
-    const bountyPda2 = await postBounty(ctx, bountyId2, "Second bounty", 75 * 10 ** 6);
+    const bountyPda2 = await postBounty(ctx, "Second bounty", 75 * 10 ** 6);
 
     await ctx.program.methods
-      .submitSolution(Array.from(solutionHash))
+      .submitSolution(Array.from(solutionHash), NO_CIPHERTEXT_HASH, "", [])
       .accountsPartial({
+        submitter: agent.publicKey,
         agent: agent.publicKey,
+        operatorRecord: null,
         bounty: testBountyPda,
         attestation: attestationPda,
         reputation: reputationPda,
@@ -239,12 +384,11 @@ describe("submit_solution", () => {
       .signers([agent])
       .rpc();
 
-    const solutionId2 = generateRandomId();
     const solutionHash2 = generateSolutionHashWithValue(0x22);
     const attestationPda2 = await createAttestation(
       ctx,
       agent2,
-      solutionId2,
+      bountyPda2,
       solutionHash2
     );
 
@@ -254,9 +398,11 @@ describe("submit_solution", () => {
     );
 
     await ctx.program.methods
-      .submitSolution(Array.from(solutionHash2))
+      .submitSolution(Array.from(solutionHash2), NO_CIPHERTEXT_HASH, "", [])
       .accountsPartial({
+        submitter: agent2.publicKey,
         agent: agent2.publicKey,
+        operatorRecord: null,
         bounty: bountyPda2,
         attestation: attestationPda2,
         reputation: reputationPda2,
@@ -279,5 +425,713 @@ describe("submit_solution", () => {
     expect(rep1.score.toNumber()).to.equal(1);
     expect(rep2.score.toNumber()).to.equal(1);
   });
-});
 
+  async function postOracleBounty(
+    requiresOracle: boolean,
+    oracleFeeds: anchor.web3.PublicKey[] = [SWITCHBOARD_FEED_FRESH],
+    oracleKind: { switchboard: {} } | { pyth: {} } = { switchboard: {} }
+  ) {
+    return postBounty(
+      ctx,
+      "SOL/USD feed check",
+      10 * 10 ** 6,
+      requiresOracle
+        ? {
+            bountyType: { tokenScreening: {} },
+            requiresOracle: true,
+            oracleFeeds,
+            oracleKind,
+          }
+        : { bountyType: { tokenScreening: {} } }
+    );
+  }
+
+  async function attest(bountyPda: anchor.web3.PublicKey, value: number) {
+    const hash = generateSolutionHashWithValue(value);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    return { hash, attestation };
+  }
+
+  it("Does not require an oracle when the flag is off and none is passed", async () => {
+    const bountyPda = await postBounty(
+      ctx,
+      "Competitive pricing analysis",
+      10 * 10 ** 6
+    );
+    const { hash, attestation } = await attest(bountyPda, 0x33);
+
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.requiresOracle).to.be.false;
+    expect(bountyAccount.oracleFeeds).to.be.empty;
+    expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+  });
+
+  it("Fails when an account is passed that is not a feed the bounty names", async () => {
+    const bountyPda = await postOracleBounty(false);
+    const { hash, attestation } = await attest(bountyPda, 0x34);
+
+    try {
+      await submitSolution(ctx, agent, bountyPda, attestation, hash, [
+        ctx.creatorTokenAccount,
+      ]);
+      expect.fail("Should have failed - unexpected account");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("OracleVerificationFailed");
+      expect(err.logs.join("\n")).to.include(
+        "submit_solution: unexpected account at 0"
+      );
+    }
+  });
+
+  it("Fails when the flag is on and no oracle account is passed", async () => {
+    const bountyPda = await postOracleBounty(true);
+    const { hash, attestation } = await attest(bountyPda, 0x44);
+
+    try {
+      await submitSolution(ctx, agent, bountyPda, attestation, hash);
+      expect.fail("Should have failed - oracle account missing");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("OracleVerificationFailed");
+    }
+  });
+
+  it("Fails when the flag is on and the wrong feed is passed", async () => {
+    const bountyPda = await postOracleBounty(true);
+    const { hash, attestation } = await attest(bountyPda, 0x45);
+
+    try {
+      await submitSolution(
+        ctx,
+        agent,
+        bountyPda,
+        attestation,
+        hash,
+        [ctx.creatorTokenAccount]
+      );
+      expect.fail("Should have failed - oracle feed mismatch");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("OracleVerificationFailed");
+    }
+  });
+
+  it("Accepts the recorded feed when the flag is on", async () => {
+    const bountyPda = await postOracleBounty(true);
+    const { hash, attestation } = await attest(bountyPda, 0x46);
+
+    await submitSolution(
+      ctx,
+      agent,
+      bountyPda,
+      attestation,
+      hash,
+      [SWITCHBOARD_FEED_FRESH]
+    );
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.requiresOracle).to.be.true;
+    expect(bountyAccount.oracleFeeds.map((feed) => feed.toString())).to.deep.equal(
+      [SWITCHBOARD_FEED_FRESH.toString()]
+    );
+    expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+  });
+
+  it("Fails when the recorded feed is stale", async () => {
+    const bountyPda = await postOracleBounty(true, [SWITCHBOARD_FEED_STALE]);
+    const { hash, attestation } = await attest(bountyPda, 0x47);
+
+    try {
+      await submitSolution(
+        ctx,
+        agent,
+        bountyPda,
+        attestation,
+        hash,
+        [SWITCHBOARD_FEED_STALE]
+      );
+      expect.fail("Should have failed - oracle data stale");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("OracleDataStale");
+    }
+  });
+
+  it("Fails when the recorded feed is not owned by Switchboard", async () => {
+    const bountyPda = await postOracleBounty(
+      true,
+      [SWITCHBOARD_FEED_WRONG_OWNER]
+    );
+    const { hash, attestation } = await attest(bountyPda, 0x48);
+
+    try {
+      await submitSolution(
+        ctx,
+        agent,
+        bountyPda,
+        attestation,
+        hash,
+        [SWITCHBOARD_FEED_WRONG_OWNER]
+      );
+      expect.fail("Should have failed - feed owned by another program");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("OracleVerificationFailed");
+    }
+  });
+
+  describe("several oracle feeds", () => {
+    const feeds = [SWITCHBOARD_FEED_FRESH, SWITCHBOARD_FEED_FRESH_2];
+
+    it("Accepts every named feed, in any order", async () => {
+      const bountyPda = await postOracleBounty(true, feeds);
+      const { hash, attestation } = await attest(bountyPda, 0x4d);
+
+      await submitSolution(
+        ctx,
+        agent,
+        bountyPda,
+        attestation,
+        hash,
+        [...feeds].reverse()
+      );
+
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bountyAccount.oracleFeeds).to.have.length(2);
+      expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+    });
+
+    it("Fails and names the feed when one of them is stale", async () => {
+      const bountyPda = await postOracleBounty(true, [
+        SWITCHBOARD_FEED_FRESH,
+        SWITCHBOARD_FEED_STALE,
+      ]);
+      const { hash, attestation } = await attest(bountyPda, 0x4e);
+
+      try {
+        await submitSolution(ctx, agent, bountyPda, attestation, hash, [
+          SWITCHBOARD_FEED_FRESH,
+          SWITCHBOARD_FEED_STALE,
+        ]);
+        expect.fail("Should have failed - second feed stale");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("OracleDataStale");
+        expect(err.logs.join("\n")).to.include(
+          "submit_solution: oracle feed at 1 failed"
+        );
+      }
+    });
+
+    it("Fails when one of the feeds is left out", async () => {
+      const bountyPda = await postOracleBounty(true, feeds);
+      const { hash, attestation } = await attest(bountyPda, 0x4f);
+
+      try {
+        await submitSolution(ctx, agent, bountyPda, attestation, hash, [
+          SWITCHBOARD_FEED_FRESH,
+        ]);
+        expect.fail("Should have failed - second feed missing");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("OracleVerificationFailed");
+        expect(err.logs.join("\n")).to.include(
+          "submit_solution: oracle feed 1 missing"
+        );
+      }
+    });
+  });
+
+  describe("oracle value bounds", () => {
+    // the fixtures report 150, the bounds are in cents
+    async function postBounded(
+      oracleFeed: anchor.web3.PublicKey,
+      bounds: { oracleMinValue?: number; oracleMaxValue?: number },
+      oracleKind: { switchboard: {} } | { pyth: {} } = { switchboard: {} }
+    ) {
+      return postBounty(ctx, "Pay out if SOL trades in the band", 10 * 10 ** 6, {
+        bountyType: { tokenScreening: {} },
+        requiresOracle: true,
+        oracleFeeds: [oracleFeed],
+        oracleKind,
+        oracleExponent: -2,
+        ...bounds,
+      });
+    }
+
+    async function expectSubmitError(
+      bountyPda: anchor.web3.PublicKey,
+      oracleFeed: anchor.web3.PublicKey,
+      value: number,
+      code: string
+    ) {
+      const { hash, attestation } = await attest(bountyPda, value);
+      try {
+        await submitSolution(ctx, agent, bountyPda, attestation, hash, [
+          oracleFeed,
+        ]);
+        expect.fail(`Should have failed with ${code}`);
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal(code);
+      }
+    }
+
+    it("Accepts a value inside the band", async () => {
+      const bountyPda = await postBounded(SWITCHBOARD_FEED_FRESH, {
+        oracleMinValue: 14_000,
+        oracleMaxValue: 16_000,
+      });
+      const { hash, attestation } = await attest(bountyPda, 0x50);
+
+      await submitSolution(ctx, agent, bountyPda, attestation, hash, [
+        SWITCHBOARD_FEED_FRESH,
+      ]);
+
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bountyAccount.oracleMinValue.toNumber()).to.equal(14_000);
+      expect(bountyAccount.oracleMaxValue.toNumber()).to.equal(16_000);
+      expect(bountyAccount.oracleExponent).to.equal(-2);
+      expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+    });
+
+    it("Fails when the value is below the band", async () => {
+      const bountyPda = await postBounded(SWITCHBOARD_FEED_FRESH, {
+        oracleMinValue: 15_001,
+        oracleMaxValue: 16_000,
+      });
+      await expectSubmitError(
+        bountyPda,
+        SWITCHBOARD_FEED_FRESH,
+        0x51,
+        "OracleValueOutOfRange"
+      );
+    });
+
+    it("Fails when the value is above the band", async () => {
+      const bountyPda = await postBounded(SWITCHBOARD_FEED_FRESH, {
+        oracleMinValue: 14_000,
+        oracleMaxValue: 14_999,
+      });
+      await expectSubmitError(
+        bountyPda,
+        SWITCHBOARD_FEED_FRESH,
+        0x52,
+        "OracleValueOutOfRange"
+      );
+    });
+
+    it("Accepts any value above a lone minimum", async () => {
+      const bountyPda = await postBounded(SWITCHBOARD_FEED_FRESH, {
+        oracleMinValue: 15_000,
+      });
+      const { hash, attestation } = await attest(bountyPda, 0x53);
+
+      await submitSolution(ctx, agent, bountyPda, attestation, hash, [
+        SWITCHBOARD_FEED_FRESH,
+      ]);
+
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bountyAccount.oracleMaxValue).to.be.null;
+      expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+    });
+
+    it("Normalizes a Pyth price to the bounty's exponent", async () => {
+      const bountyPda = await postBounded(
+        PYTH_PRICE_FRESH,
+        { oracleMinValue: 15_001 },
+        { pyth: {} }
+      );
+      await expectSubmitError(
+        bountyPda,
+        PYTH_PRICE_FRESH,
+        0x54,
+        "OracleValueOutOfRange"
+      );
+    });
+
+    it("Fails on a stale feed even when its value is in range", async () => {
+      const bountyPda = await postBounded(SWITCHBOARD_FEED_STALE, {
+        oracleMinValue: 14_000,
+        oracleMaxValue: 16_000,
+      });
+      await expectSubmitError(
+        bountyPda,
+        SWITCHBOARD_FEED_STALE,
+        0x55,
+        "OracleDataStale"
+      );
+    });
+  });
+
+  describe("pyth oracle", () => {
+    async function expectSubmitError(
+      oracleFeed: anchor.web3.PublicKey,
+      value: number,
+      code: string
+    ) {
+      const bountyPda = await postOracleBounty(true, [oracleFeed], { pyth: {} });
+      const { hash, attestation } = await attest(bountyPda, value);
+
+      try {
+        await submitSolution(
+          ctx,
+          agent,
+          bountyPda,
+          attestation,
+          hash,
+          [oracleFeed]
+        );
+        expect.fail(`Should have failed with ${code}`);
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal(code);
+      }
+    }
+
+    it("Accepts a fresh, tight price update", async () => {
+      const bountyPda = await postOracleBounty(true, [PYTH_PRICE_FRESH], {
+        pyth: {},
+      });
+      const { hash, attestation } = await attest(bountyPda, 0x49);
+
+      await submitSolution(
+        ctx,
+        agent,
+        bountyPda,
+        attestation,
+        hash,
+        [PYTH_PRICE_FRESH]
+      );
+
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bountyAccount.oracleKind).to.deep.equal({ pyth: {} });
+      expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+    });
+
+    it("Fails when the price update is stale", async () => {
+      await expectSubmitError(PYTH_PRICE_STALE, 0x4a, "OracleDataStale");
+    });
+
+    it("Fails when the confidence interval is too wide", async () => {
+      await expectSubmitError(
+        PYTH_PRICE_WIDE_CONFIDENCE,
+        0x4b,
+        "OracleVerificationFailed"
+      );
+    });
+
+    it("Fails when a Switchboard feed backs a Pyth bounty", async () => {
+      await expectSubmitError(
+        SWITCHBOARD_FEED_FRESH,
+        0x4c,
+        "OracleVerificationFailed"
+      );
+    });
+  });
+
+  describe("attestation threshold", () => {
+    const attestors = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+    const hash = generateSolutionHashWithValue(0x3f);
+    let bountyPda: anchor.web3.PublicKey;
+
+    before(async () => {
+      await updateConfig(ctx, {
+        attestors: attestors.map((attestor) => attestor.publicKey),
+      });
+    });
+
+    after(async () => {
+      await updateConfig(ctx, { attestors: [] });
+    });
+
+    beforeEach(async () => {
+      bountyPda = await postBounty(
+        ctx,
+        "Two of three attestors",
+        10 * 10 ** 6,
+        { requiredAttestations: 2 }
+      );
+    });
+
+    function attestBy(attestor: Keypair) {
+      return createAttestation(ctx, agent, bountyPda, hash, true, attestor);
+    }
+
+    it("Submits once two distinct attestors vouch", async () => {
+      const first = await attestBy(attestors[0]);
+      const second = await attestBy(attestors[1]);
+
+      await submitSolution(ctx, agent, bountyPda, first, hash, [], [second]);
+
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bountyAccount.requiredAttestations).to.equal(2);
+      expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+    });
+
+    it("Consumes the co-attestations it counted", async () => {
+      const first = await attestBy(attestors[0]);
+      const second = await attestBy(attestors[1]);
+      const [attestorPda] = deriveAttestorPda(
+        ctx.program.programId,
+        agent.publicKey
+      );
+      const before = await ctx.program.account.attestor.fetch(attestorPda);
+
+      await submitSolution(ctx, agent, bountyPda, first, hash, [], [second]);
+
+      const coAttestation = await ctx.program.account.attestation.fetch(
+        second
+      );
+      expect(coAttestation.consumed).to.be.true;
+      const after = await ctx.program.account.attestor.fetch(attestorPda);
+      expect(after.attestationsConsumed.toNumber()).to.equal(
+        before.attestationsConsumed.toNumber() + 2
+      );
+
+      // withdrawing reopens the bounty, but the co-attestation can't back
+      // the next attempt
+      await ctx.program.methods
+        .withdrawSubmission()
+        .accountsPartial({ agent: agent.publicKey, bounty: bountyPda })
+        .signers([agent])
+        .rpc();
+      const third = await attestBy(attestors[2]);
+      try {
+        await submitSolution(ctx, agent, bountyPda, third, hash, [], [second]);
+        expect.fail("Should have failed - co-attestation consumed");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("InsufficientAttestations");
+      }
+    });
+
+    it("Fails with only one of three attestors", async () => {
+      const only = await attestBy(attestors[2]);
+
+      try {
+        await submitSolution(ctx, agent, bountyPda, only, hash);
+        expect.fail("Should have failed - threshold not met");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("InsufficientAttestations");
+      }
+    });
+
+    it("Does not double-count the same attestor", async () => {
+      const first = await attestBy(attestors[0]);
+      const again = await attestBy(attestors[0]);
+
+      try {
+        await submitSolution(ctx, agent, bountyPda, first, hash, [], [
+          again,
+          first,
+        ]);
+        expect.fail("Should have failed - duplicate attestor");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("InsufficientAttestations");
+      }
+    });
+  });
+
+  describe("attestation freshness", () => {
+    const window = 4;
+
+    async function attestWithTimestamp(bountyPda: anchor.web3.PublicKey) {
+      const hash = generateSolutionHashWithValue(0x66);
+      const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+      const { timestamp } = await ctx.program.account.attestation.fetch(
+        attestation
+      );
+      return { hash, attestation, timestamp: timestamp.toNumber() };
+    }
+
+    it("Accepts an attestation right at the bounty's age limit", async () => {
+      const bountyPda = await postBounty(
+        ctx,
+        "Fresh screening only",
+        10 * 10 ** 6,
+        { maxAttestationAge: window }
+      );
+      const { hash, attestation, timestamp } = await attestWithTimestamp(
+        bountyPda
+      );
+
+      // land the submission as close to timestamp + window as the clock allows
+      await waitForClock(ctx.connection, timestamp + window - 1);
+      await submitSolution(ctx, agent, bountyPda, attestation, hash);
+
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bountyAccount.maxAttestationAge.toNumber()).to.equal(window);
+      expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+    });
+
+    it("Fails once the attestation is past the bounty's age limit", async () => {
+      const bountyPda = await postBounty(
+        ctx,
+        "Fresh screening only",
+        10 * 10 ** 6,
+        { maxAttestationAge: window }
+      );
+      const { hash, attestation, timestamp } = await attestWithTimestamp(
+        bountyPda
+      );
+
+      await waitForClock(ctx.connection, timestamp + window + 1);
+      try {
+        await submitSolution(ctx, agent, bountyPda, attestation, hash);
+        expect.fail("Should have failed - attestation expired");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("AttestationExpired");
+      }
+    });
+
+    it("Falls back to the program-wide limit when the bounty sets none", async () => {
+      const bountyPda = await postBounty(
+        ctx,
+        "Default attestation window",
+        10 * 10 ** 6
+      );
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bountyAccount.maxAttestationAge).to.be.null;
+
+      const { hash, attestation, timestamp } = await attestWithTimestamp(
+        bountyPda
+      );
+      await waitForClock(ctx.connection, timestamp + window + 1);
+      await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    });
+  });
+
+  describe("competitive bounties", () => {
+    it("Keeps the bounty open while several agents submit", async () => {
+      const bountyPda = await postBounty(
+        ctx,
+        "Best solution wins",
+        10 * 10 ** 6,
+        { competitive: true }
+      );
+
+      const agents = [Keypair.generate(), Keypair.generate(), Keypair.generate()];
+      for (const [i, competitor] of agents.entries()) {
+        await airdropSol(ctx.connection, competitor.publicKey);
+        const hash = generateSolutionHashWithValue(0x70 + i);
+        const attestation = await createAttestation(
+          ctx,
+          competitor,
+          bountyPda,
+          hash
+        );
+        await submitSolution(ctx, competitor, bountyPda, attestation, hash);
+      }
+
+      for (const [i, competitor] of agents.entries()) {
+        const [submissionPda] = deriveSubmissionPda(
+          ctx.program.programId,
+          bountyPda,
+          competitor.publicKey
+        );
+        const submission = await ctx.program.account.submission.fetch(
+          submissionPda
+        );
+        expect(submission.bounty.toString()).to.equal(bountyPda.toString());
+        expect(submission.agent.toString()).to.equal(
+          competitor.publicKey.toString()
+        );
+        expect(Buffer.from(submission.solutionHash)).to.deep.equal(
+          generateSolutionHashWithValue(0x70 + i)
+        );
+        expect(submission.status).to.deep.equal({ pending: {} });
+      }
+
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bountyAccount.status).to.deep.equal({ open: {} });
+      expect(bountyAccount.solver).to.be.null;
+      expect(bountyAccount.solutionHash).to.be.null;
+      expect(bountyAccount.submissionCount).to.equal(3);
+    });
+
+    it("Fails when an agent submits twice", async () => {
+      const bountyPda = await postBounty(
+        ctx,
+        "Best solution wins",
+        10 * 10 ** 6,
+        { competitive: true }
+      );
+      const first = await createAttestation(
+        ctx,
+        agent,
+        bountyPda,
+        solutionHash
+      );
+      await submitSolution(ctx, agent, bountyPda, first, solutionHash);
+
+      const hash = generateSolutionHashWithValue(0x7f);
+      const second = await createAttestation(ctx, agent, bountyPda, hash);
+      try {
+        await submitSolution(ctx, agent, bountyPda, second, hash);
+        expect.fail("Should have failed - submission already pending");
+      } catch (err) {
+        expect(err.error.errorCode.code).to.equal("SubmissionAlreadyExists");
+      }
+    });
+  });
+
+  it("Stores the solution URI on the submission and emits it", async () => {
+    const bountyPda = await postBounty(ctx, "Deliverable behind a link", 10 * 10 ** 6);
+    const { hash, attestation } = await attest(bountyPda, 0x56);
+    const prefix = "ar://";
+    const uri = prefix + "x".repeat(200 - prefix.length);
+
+    const events = await fetchEvents(
+      ctx,
+      await submitSolution(ctx, agent, bountyPda, attestation, hash, [], [], null, uri)
+    );
+
+    const submission = await ctx.program.account.submission.fetch(
+      deriveSubmissionPda(ctx.program.programId, bountyPda, agent.publicKey)[0]
+    );
+    expect(submission.solutionUri).to.equal(uri);
+    expect(findEvent(events, "SolutionSubmitted").data.solutionUri).to.equal(uri);
+  });
+
+  it("Fails when the solution URI is too long", async () => {
+    const bountyPda = await postBounty(ctx, "Deliverable behind a link", 10 * 10 ** 6);
+    const { hash, attestation } = await attest(bountyPda, 0x57);
+
+    try {
+      await submitSolution(
+        ctx,
+        agent,
+        bountyPda,
+        attestation,
+        hash,
+        [],
+        [],
+        null,
+        "ar://" + "x".repeat(200)
+      );
+      expect.fail("Should have failed - URI too long");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UriTooLong");
+    }
+  });
+
+  it("Fails after the bounty deadline has passed", async () => {
+    const bountyPda = await postBounty(
+      ctx,
+      "Short-lived bounty",
+      10 * 10 ** 6,
+      { deadline: Math.floor(Date.now() / 1000) + 4 }
+    );
+
+    const solutionHash2 = generateSolutionHashWithValue(0x55);
+    const attestationPda2 = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      solutionHash2
+    );
+
+    await sleep(6000);
+
+    try {
+      await submitSolution(ctx, agent, bountyPda, attestationPda2, solutionHash2);
+      expect.fail("Should have failed - deadline passed");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyDeadlinePassed");
+    }
+  });
+});
@@ -0,0 +1,144 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  TOKEN_PROGRAM_ID,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  updateConfig,
+  sleep,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("sweep_abandoned_bounty", () => {
+  const reward = 10 * 10 ** 6;
+  const abandonmentSeconds = 2;
+
+  let ctx: TestContext;
+  let treasuryTokenAccount: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    treasuryTokenAccount = (
+      await getOrCreateAssociatedTokenAccount(
+        ctx.connection,
+        ctx.creator,
+        ctx.usdcMint,
+        ctx.provider.wallet.publicKey
+      )
+    ).address;
+    await updateConfig(ctx, {
+      abandonmentSeconds: new anchor.BN(abandonmentSeconds),
+    });
+  });
+
+  after(async () => {
+    await updateConfig(ctx, {
+      abandonmentSeconds: new anchor.BN(365 * 24 * 60 * 60),
+    });
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  async function sweep(
+    bountyPda: anchor.web3.PublicKey,
+    admin: Keypair | null = null
+  ) {
+    const signer = admin ? admin.publicKey : ctx.provider.wallet.publicKey;
+    const builder = ctx.program.methods
+      .sweepAbandonedBounty()
+      .accountsPartial({
+        admin: signer,
+        bounty: bountyPda,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        treasury: ctx.provider.wallet.publicKey,
+        treasuryTokenAccount,
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      });
+    return admin ? builder.signers([admin]).rpc() : builder.rpc();
+  }
+
+  async function expectSweepError(
+    bountyPda: anchor.web3.PublicKey,
+    code: string,
+    admin: Keypair | null = null
+  ) {
+    try {
+      await sweep(bountyPda, admin);
+      expect.fail(`Should have failed with ${code}`);
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal(code);
+    }
+  }
+
+  async function balanceOf(account: anchor.web3.PublicKey) {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  it("Sweeps the escrow of an abandoned open bounty to the treasury", async () => {
+    const bountyPda = await postBounty(ctx, "Forgotten audit", reward);
+    await sleep((abandonmentSeconds + 1) * 1000);
+
+    const treasuryBefore = await balanceOf(treasuryTokenAccount);
+    await sweep(bountyPda);
+
+    expect((await balanceOf(treasuryTokenAccount)) - treasuryBefore).to.equal(
+      reward
+    );
+    expect(
+      await balanceOf(getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda))
+    ).to.equal(0);
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ swept: {} });
+    expect(bounty.escrowAmount.toNumber()).to.equal(0);
+  });
+
+  it("Fails before the abandonment period has passed", async () => {
+    const bountyPda = await postBounty(ctx, "Fresh audit", reward);
+    await expectSweepError(bountyPda, "BountyNotAbandoned");
+  });
+
+  it("Fails while a solution waits on the creator", async () => {
+    const bountyPda = await postBounty(ctx, "Unreviewed audit", reward);
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const hash = generateSolutionHashWithValue(0x59);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    await sleep((abandonmentSeconds + 1) * 1000);
+
+    // the escrow is owed to the agent until the creator acts
+    await expectSweepError(bountyPda, "BountyNotSweepable");
+  });
+
+  it("Fails when signed by anyone but the admin", async () => {
+    const bountyPda = await postBounty(ctx, "Abandoned audit", reward);
+    await sleep((abandonmentSeconds + 1) * 1000);
+
+    const outsider = Keypair.generate();
+    await airdropSol(ctx.connection, outsider.publicKey);
+    await expectSweepError(bountyPda, "UnauthorizedAdmin", outsider);
+  });
+});
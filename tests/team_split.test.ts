@@ -0,0 +1,205 @@
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  generateSolutionHashWithValue,
+  TeamShare,
+  TestContext,
+} from "./helpers";
+
+describe("team_split", () => {
+  // odd on purpose, so the 60/40 split leaves a unit of dust
+  const reward = 10 * 10 ** 6 + 1;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: PublicKey;
+  let teammate: Keypair;
+  let teammateTokenAccount: PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    teammate = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    await airdropSol(ctx.connection, teammate.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    teammateTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      teammate,
+      ctx.usdcMint
+    );
+  });
+
+  async function balanceOf(account: PublicKey): Promise<number> {
+    const balance = await ctx.connection.getTokenAccountBalance(account);
+    return Number(balance.value.amount);
+  }
+
+  function split(agentBps: number, teammateBps: number): TeamShare[] {
+    return [
+      { member: agent.publicKey, shareBps: agentBps },
+      { member: teammate.publicKey, shareBps: teammateBps },
+    ];
+  }
+
+  async function postAndSubmit(
+    team: TeamShare[],
+    arbiter?: PublicKey
+  ): Promise<PublicKey> {
+    const bountyPda = await postBounty(ctx, "Scrape and analyse", reward, {
+      challengePeriodSlots: 0,
+      arbiter,
+    });
+    const hash = generateSolutionHashWithValue(0x6b);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(
+      ctx,
+      agent,
+      bountyPda,
+      attestation,
+      hash,
+      [],
+      [],
+      null,
+      "",
+      team
+    );
+    return bountyPda;
+  }
+
+  it("Pays a 60/40 team its exact shares, dust to the submitter", async () => {
+    const bountyPda = await postAndSubmit(split(6_000, 4_000));
+
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount, [
+      agentTokenAccount,
+      teammateTokenAccount,
+    ]);
+
+    // floor(0.6 * reward) and floor(0.4 * reward), plus the leftover unit
+    expect(await balanceOf(agentTokenAccount)).to.equal(6_000_000 + 1);
+    expect(await balanceOf(teammateTokenAccount)).to.equal(4_000_000);
+
+    // reputation is credited to the submitter alone
+    const reputation = await ctx.program.account.reputation.fetch(
+      deriveReputationPda(ctx.program.programId, agent.publicKey)[0]
+    );
+    expect(reputation.successfulBounties.toNumber()).to.equal(1);
+    expect(
+      await ctx.connection.getAccountInfo(
+        deriveReputationPda(ctx.program.programId, teammate.publicKey)[0]
+      )
+    ).to.be.null;
+  });
+
+  it("Fails at submission when the shares sum to 9999 bps", async () => {
+    try {
+      await postAndSubmit(split(6_000, 3_999));
+      expect.fail("Should have failed - shares must sum to 10000");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("InvalidTeamSplit");
+    }
+  });
+
+  it("Fails to settle without every member's account", async () => {
+    const bountyPda = await postAndSubmit(split(6_000, 4_000));
+
+    try {
+      await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount, [
+        agentTokenAccount,
+      ]);
+      expect.fail("Should have failed - teammate's account missing");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MissingTeamMemberAccount");
+    }
+
+    // nothing moved, so the creator can settle properly afterwards
+    expect(await balanceOf(agentTokenAccount)).to.equal(0);
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount, [
+      agentTokenAccount,
+      teammateTokenAccount,
+    ]);
+    expect(await balanceOf(teammateTokenAccount)).to.equal(4_000_000);
+  });
+
+  it("Fails to settle with someone else's account in a member's place", async () => {
+    const bountyPda = await postAndSubmit(split(6_000, 4_000));
+
+    try {
+      await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount, [
+        agentTokenAccount,
+        ctx.creatorTokenAccount,
+      ]);
+      expect.fail("Should have failed - not the teammate's ATA");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("TeamMemberAccountMismatch");
+    }
+  });
+
+  it("Splits a payout the arbiter awards by the team table", async () => {
+    const arbiter = Keypair.generate();
+    await airdropSol(ctx.connection, arbiter.publicKey);
+    const bountyPda = await postAndSubmit(
+      split(6_000, 4_000),
+      arbiter.publicKey
+    );
+
+    await ctx.program.methods
+      .openDispute()
+      .accountsPartial({ party: agent.publicKey, bounty: bountyPda })
+      .signers([agent])
+      .rpc();
+    await ctx.program.methods
+      .resolveDispute({ paySolver: {} })
+      .accountsPartial({
+        arbiter: arbiter.publicKey,
+        bounty: bountyPda,
+        creator: ctx.creator.publicKey,
+        agent: agent.publicKey,
+        agentTokenAccount,
+        creatorTokenAccount: ctx.creatorTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .remainingAccounts(
+        [agentTokenAccount, teammateTokenAccount].map((pubkey) => ({
+          pubkey,
+          isSigner: false,
+          isWritable: true,
+        }))
+      )
+      .signers([arbiter])
+      .rpc();
+
+    expect(await balanceOf(agentTokenAccount)).to.equal(6_000_000 + 1);
+    expect(await balanceOf(teammateTokenAccount)).to.equal(4_000_000);
+  });
+});
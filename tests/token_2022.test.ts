@@ -0,0 +1,222 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  TOKEN_2022_PROGRAM_ID,
+  TOKEN_PROGRAM_ID,
+  createMint,
+  getOrCreateAssociatedTokenAccount,
+  mintTo,
+} from "@solana/spl-token";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  nextBountyPda,
+  deriveReputationPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  bountyParams,
+  createAttestation,
+  createTransferFeeMint,
+  submitSolution,
+  waitForPostCooldown,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+const FEE_BASIS_POINTS = 100;
+
+function transferFee(amount: number, feeBasisPoints: number): number {
+  return Math.ceil((amount * feeBasisPoints) / 10_000);
+}
+
+describe("token_2022", () => {
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  const cases = [
+    { name: "a plain token-2022 mint", feeBasisPoints: 0 },
+    { name: "a transfer-fee mint", feeBasisPoints: FEE_BASIS_POINTS },
+  ];
+
+  for (const { name, feeBasisPoints } of cases) {
+    it(`Escrows and settles the received amount for ${name}`, async () => {
+      const mint =
+        feeBasisPoints === 0
+          ? await createMint(
+              ctx.connection,
+              ctx.creator,
+              ctx.creator.publicKey,
+              null,
+              6,
+              undefined,
+              undefined,
+              TOKEN_2022_PROGRAM_ID
+            )
+          : await createTransferFeeMint(
+              ctx.connection,
+              ctx.creator,
+              6,
+              feeBasisPoints,
+              BigInt(10 ** 12)
+            );
+
+      const creatorTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          ctx.connection,
+          ctx.creator,
+          mint,
+          ctx.creator.publicKey,
+          false,
+          undefined,
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        )
+      ).address;
+      await mintTo(
+        ctx.connection,
+        ctx.creator,
+        mint,
+        creatorTokenAccount,
+        ctx.creator,
+        1000 * 10 ** 6,
+        [],
+        undefined,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      const agent = Keypair.generate();
+      await airdropSol(ctx.connection, agent.publicKey);
+      const agentTokenAccount = (
+        await getOrCreateAssociatedTokenAccount(
+          ctx.connection,
+          agent,
+          mint,
+          agent.publicKey,
+          false,
+          undefined,
+          undefined,
+          TOKEN_2022_PROGRAM_ID
+        )
+      ).address;
+
+      const [bountyPda] = await nextBountyPda(ctx);
+      const bountyTokenAccount = getAssociatedTokenAddressSync(
+        mint,
+        bountyPda,
+        TOKEN_2022_PROGRAM_ID
+      );
+
+      const reward = 100 * 10 ** 6;
+      await waitForPostCooldown(ctx.creator.publicKey);
+      await ctx.program.methods
+        .postBounty(bountyParams("Token-2022 wallet analysis", reward))
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          rewardMint: mint,
+          creatorTokenAccount,
+          bountyTokenAccount,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+
+      const escrowed = reward - transferFee(reward, feeBasisPoints);
+      const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+      expect(bountyAccount.reward.toNumber()).to.equal(reward);
+      expect(bountyAccount.escrowAmount.toNumber()).to.equal(escrowed);
+
+      const escrowBalance = await ctx.connection.getTokenAccountBalance(
+        bountyTokenAccount
+      );
+      expect(escrowBalance.value.amount).to.equal(escrowed.toString());
+
+      const solutionHash = generateSolutionHashWithValue(0x22);
+      const attestationPda = await createAttestation(
+        ctx,
+        agent,
+        bountyPda,
+        solutionHash
+      );
+      await submitSolution(ctx, agent, bountyPda, attestationPda, solutionHash);
+
+      const [reputationPda] = deriveReputationPda(
+        ctx.program.programId,
+        agent.publicKey
+      );
+      await ctx.program.methods
+        .settleBounty()
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          reputation: reputationPda,
+          agent: agent.publicKey,
+          agentTokenAccount,
+          bountyTokenAccount,
+          rewardMint: mint,
+          tokenProgram: TOKEN_2022_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+
+      const escrowAfter = await ctx.connection.getTokenAccountBalance(
+        bountyTokenAccount
+      );
+      expect(escrowAfter.value.amount).to.equal("0");
+
+      const agentBalance = await ctx.connection.getTokenAccountBalance(
+        agentTokenAccount
+      );
+      expect(agentBalance.value.amount).to.equal(
+        (escrowed - transferFee(escrowed, feeBasisPoints)).toString()
+      );
+
+      const reputation = await ctx.program.account.reputation.fetch(
+        reputationPda
+      );
+      expect(reputation.totalEarned.toNumber()).to.equal(escrowed);
+    });
+  }
+
+  it("Fails when the token program does not own the mint", async () => {
+    const mint = await createTransferFeeMint(
+      ctx.connection,
+      ctx.creator,
+      6,
+      FEE_BASIS_POINTS,
+      BigInt(10 ** 12)
+    );
+
+    const [bountyPda] = await nextBountyPda(ctx);
+
+    await waitForPostCooldown(ctx.creator.publicKey);
+    try {
+      await ctx.program.methods
+        .postBounty(bountyParams("Mismatched token program", 10 * 10 ** 6))
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          rewardMint: mint,
+          creatorTokenAccount: ctx.creatorTokenAccount,
+          bountyTokenAccount: getAssociatedTokenAddressSync(
+            mint,
+            bountyPda,
+            TOKEN_2022_PROGRAM_ID
+          ),
+          tokenProgram: TOKEN_PROGRAM_ID,
+          systemProgram: SystemProgram.programId,
+        })
+        .signers([ctx.creator])
+        .rpc();
+
+      expect.fail("Should have failed - mint belongs to token-2022");
+    } catch (err) {
+      expect(err).to.exist;
+    }
+  });
+});
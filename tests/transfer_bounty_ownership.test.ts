@@ -0,0 +1,162 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveCreatorProfilePda,
+  deriveReputationPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("transfer_bounty_ownership", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let newCreator: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let bountyPda: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    newCreator = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    await airdropSol(ctx.connection, newCreator.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+
+    bountyPda = await postBounty(ctx, "Handed off", reward);
+    const hash = generateSolutionHashWithValue(0x4d);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+  });
+
+  async function propose(to: anchor.web3.PublicKey) {
+    await ctx.program.methods
+      .transferBountyOwnership(to)
+      .accountsPartial({ creator: ctx.creator.publicKey, bounty: bountyPda })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  async function accept(by: Keypair) {
+    await ctx.program.methods
+      .acceptBountyOwnership()
+      .accountsPartial({ newCreator: by.publicKey, bounty: bountyPda })
+      .signers([by])
+      .rpc();
+  }
+
+  it("Hands settlement rights to the new creator once they accept", async () => {
+    await propose(newCreator.publicKey);
+
+    // nothing changes until the new creator signs
+    let bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.creator.toString()).to.equal(
+      ctx.creator.publicKey.toString()
+    );
+    expect(bountyAccount.pendingCreator.toString()).to.equal(
+      newCreator.publicKey.toString()
+    );
+
+    await accept(newCreator);
+
+    bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.creator.toString()).to.equal(
+      newCreator.publicKey.toString()
+    );
+    expect(bountyAccount.pendingCreator).to.be.null;
+
+    const [newProfilePda] = deriveCreatorProfilePda(
+      ctx.program.programId,
+      newCreator.publicKey
+    );
+    const newProfile = await ctx.program.account.creatorProfile.fetch(
+      newProfilePda
+    );
+    expect(newProfile.openBountyCount).to.equal(1);
+
+    try {
+      await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+      expect.fail("Should have failed - old creator");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedSettlement");
+    }
+
+    const [reputationPda] = deriveReputationPda(
+      ctx.program.programId,
+      agent.publicKey
+    );
+    await ctx.program.methods
+      .settleBounty()
+      .accountsPartial({
+        creator: newCreator.publicKey,
+        bounty: bountyPda,
+        reputation: reputationPda,
+        agent: agent.publicKey,
+        agentTokenAccount,
+        bountyTokenAccount: getAssociatedTokenAddressSync(
+          ctx.usdcMint,
+          bountyPda
+        ),
+        rewardMint: ctx.usdcMint,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([newCreator])
+      .rpc();
+
+    const balance = await ctx.connection.getTokenAccountBalance(
+      agentTokenAccount
+    );
+    expect(Number(balance.value.amount)).to.equal(reward);
+  });
+
+  it("Fails to accept for anyone but the proposed creator", async () => {
+    await propose(newCreator.publicKey);
+
+    const stranger = Keypair.generate();
+    await airdropSol(ctx.connection, stranger.publicKey);
+    try {
+      await accept(stranger);
+      expect.fail("Should have failed - not the pending creator");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NotPendingCreator");
+    }
+  });
+
+  it("Fails to transfer a settled bounty", async () => {
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+
+    try {
+      await propose(newCreator.publicKey);
+      expect.fail("Should have failed - bounty settled");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotTransferable");
+    }
+  });
+});
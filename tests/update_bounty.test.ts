@@ -0,0 +1,132 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  fetchEvents,
+  findEvent,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("update_bounty", () => {
+  const reward = 10 * 10 ** 6;
+
+  let ctx: TestContext;
+  let bountyPda: anchor.web3.PublicKey;
+  let deadline: number;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    deadline = Math.floor(Date.now() / 1000) + 60 * 60;
+    bountyPda = await postBounty(ctx, "Typo bountey", reward, {
+      deadline,
+    });
+  });
+
+  async function updateBounty(
+    description: string | null,
+    newDeadline: number | null,
+    metadataUri: string | null = null
+  ) {
+    return ctx.program.methods
+      .updateBounty(
+        description,
+        newDeadline === null ? null : new anchor.BN(newDeadline),
+        metadataUri
+      )
+      .accountsPartial({ creator: ctx.creator.publicKey, bounty: bountyPda })
+      .signers([ctx.creator])
+      .rpc();
+  }
+
+  it("Edits the description and extends the deadline before any submission", async () => {
+    const extended = deadline + 24 * 60 * 60;
+    const signature = await updateBounty("Typo bounty", extended);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.description).to.equal("Typo bounty");
+    expect(bountyAccount.deadline.toNumber()).to.equal(extended);
+
+    const event = findEvent(await fetchEvents(ctx, signature), "BountyUpdated");
+    expect(event.data.oldDescription).to.equal("Typo bountey");
+    expect(event.data.newDescription).to.equal("Typo bounty");
+    expect(event.data.oldDeadline.toNumber()).to.equal(deadline);
+    expect(event.data.newDeadline.toNumber()).to.equal(extended);
+  });
+
+  it("Round-trips a 180-character metadata URI", async () => {
+    const uri = "ar://" + "x".repeat(175);
+    bountyPda = await postBounty(ctx, "Rich bounty", reward, {
+      metadataUri: uri,
+    });
+    let bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.metadataUri).to.equal(uri);
+
+    const replacement = "ipfs://" + "y".repeat(173);
+    const signature = await updateBounty(null, null, replacement);
+    bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.metadataUri).to.equal(replacement);
+
+    const event = findEvent(await fetchEvents(ctx, signature), "BountyUpdated");
+    expect(event.data.oldMetadataUri).to.equal(uri);
+    expect(event.data.newMetadataUri).to.equal(replacement);
+  });
+
+  it("Fails to set a metadata URI over the length limit", async () => {
+    try {
+      await updateBounty(null, null, "https://" + "z".repeat(193));
+      expect.fail("Should have failed - URI too long");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UriTooLong");
+    }
+  });
+
+  it("Fails to update once a solution is submitted", async () => {
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const hash = generateSolutionHashWithValue(0x5e);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+
+    try {
+      await updateBounty("Too late", null);
+      expect.fail("Should have failed - bounty not open");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("BountyNotOpen");
+    }
+  });
+
+  it("Fails to shorten the deadline", async () => {
+    try {
+      await updateBounty(null, deadline - 60);
+      expect.fail("Should have failed - deadline shortened");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("DeadlineShortened");
+    }
+  });
+
+  it("Fails to set a description over the length limit", async () => {
+    try {
+      await updateBounty("x".repeat(51), null);
+      expect.fail("Should have failed - description too long");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("DescriptionTooLong");
+    }
+  });
+});
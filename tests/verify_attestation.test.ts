@@ -0,0 +1,111 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  airdropSol,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("verify_attestation", () => {
+  let ctx: TestContext;
+  let agent: Keypair;
+  let bountyPda: anchor.web3.PublicKey;
+  let attestationPda: anchor.web3.PublicKey;
+  const solutionHash = generateSolutionHashWithValue(0x71);
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+
+    bountyPda = await postBounty(
+      ctx,
+      "Needs a verified attestation",
+      10 * 10 ** 6
+    );
+    attestationPda = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      solutionHash,
+      false
+    );
+  });
+
+  async function verify(signer?: Keypair): Promise<void> {
+    const builder = ctx.program.methods.verifyAttestation().accountsPartial({
+      verifier: signer ? signer.publicKey : ctx.provider.wallet.publicKey,
+      attestation: attestationPda,
+    });
+    await (signer ? builder.signers([signer]) : builder).rpc();
+  }
+
+  it("Blocks submission until the attestation is verified", async () => {
+    try {
+      await submitSolution(ctx, agent, bountyPda, attestationPda, solutionHash);
+      expect.fail("Should have failed - attestation not verified");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AttestationNotVerified");
+    }
+
+    await verify();
+
+    const attestation = await ctx.program.account.attestation.fetch(
+      attestationPda
+    );
+    expect(attestation.verified).to.be.true;
+
+    await submitSolution(ctx, agent, bountyPda, attestationPda, solutionHash);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ submitted: {} });
+  });
+
+  it("Verifies in the same transaction when the verifier co-signs", async () => {
+    const cosignedPda = await createAttestation(
+      ctx,
+      agent,
+      bountyPda,
+      solutionHash
+    );
+
+    const attestation = await ctx.program.account.attestation.fetch(
+      cosignedPda
+    );
+    expect(attestation.verified).to.be.true;
+  });
+
+  it("Fails when a random signer tries to verify", async () => {
+    const intruder = Keypair.generate();
+    await airdropSol(ctx.connection, intruder.publicKey);
+
+    try {
+      await verify(intruder);
+      expect.fail("Should have failed - not the verifier");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("UnauthorizedVerifier");
+    }
+
+    const attestation = await ctx.program.account.attestation.fetch(
+      attestationPda
+    );
+    expect(attestation.verified).to.be.false;
+  });
+});
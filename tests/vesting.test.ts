@@ -0,0 +1,162 @@
+import * as anchor from "@coral-xyz/anchor";
+import { TOKEN_PROGRAM_ID } from "@solana/spl-token";
+import { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  waitForClock,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("vesting", () => {
+  const reward = 1 * 10 ** 6;
+
+  let ctx: TestContext;
+  let agent: Keypair;
+  let agentTokenAccount: anchor.web3.PublicKey;
+  let bountyPda: anchor.web3.PublicKey;
+  let bountyTokenAccount: anchor.web3.PublicKey;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+  });
+
+  // posts, submits and settles a bounty vesting over `duration` seconds
+  async function settleVesting(duration: number): Promise<number> {
+    bountyPda = await postBounty(ctx, "Retainer", reward, {
+      challengePeriodSlots: 0,
+      vestingDurationSeconds: duration,
+    });
+    bountyTokenAccount = getAssociatedTokenAddressSync(ctx.usdcMint, bountyPda);
+
+    const hash = generateSolutionHashWithValue(0x71);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    return bountyAccount.vestingStart.toNumber();
+  }
+
+  function claimVested() {
+    return ctx.program.methods.claimVested().accountsPartial({
+      agent: agent.publicKey,
+      bounty: bountyPda,
+      agentTokenAccount,
+      bountyTokenAccount,
+      rewardMint: ctx.usdcMint,
+      tokenProgram: TOKEN_PROGRAM_ID,
+    });
+  }
+
+  async function balanceOf(tokenAccount: anchor.web3.PublicKey) {
+    const balance = await ctx.connection.getTokenAccountBalance(tokenAccount);
+    return Number(balance.value.amount);
+  }
+
+  it("Keeps the reward in escrow at settlement with nothing vested yet", async () => {
+    // a tenth of a token unit per second, so nothing unlocks for ten seconds
+    await settleVesting(10 * reward);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ settled: {} });
+    expect(bountyAccount.vestingAmount.toNumber()).to.equal(reward);
+    expect(await balanceOf(agentTokenAccount)).to.equal(0);
+    expect(await balanceOf(bountyTokenAccount)).to.equal(reward);
+
+    try {
+      await claimVested().signers([agent]).rpc();
+      expect.fail("Should have failed - nothing vested");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NothingVested");
+    }
+  });
+
+  it("Releases the unlocked part midway and refuses a second claim in the same second", async () => {
+    const duration = 8;
+    const start = await settleVesting(duration);
+    await waitForClock(ctx.connection, start + duration / 2);
+
+    await claimVested().signers([agent]).rpc();
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    const claimed = bountyAccount.vestedClaimed.toNumber();
+    expect(claimed).to.be.at.least(reward / 2);
+    expect(claimed).to.be.below(reward);
+    expect(await balanceOf(agentTokenAccount)).to.equal(claimed);
+    expect(await balanceOf(bountyTokenAccount)).to.equal(reward - claimed);
+
+    // both claims land in one transaction, so at the same clock
+    try {
+      await claimVested()
+        .postInstructions([await claimVested().instruction()])
+        .signers([agent])
+        .rpc();
+      expect.fail("Should have failed - second claim has nothing new");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("NothingVested");
+    }
+  });
+
+  it("Sweeps the remainder once fully vested and lets the bounty close", async () => {
+    const duration = 4;
+    const start = await settleVesting(duration);
+
+    const closeBounty = () =>
+      ctx.program.methods
+        .closeBounty()
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          creatorBond: null,
+          bountyTokenAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([ctx.creator])
+        .rpc();
+
+    try {
+      await closeBounty();
+      expect.fail("Should have failed - solver still vesting");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("VestingIncomplete");
+    }
+
+    await waitForClock(ctx.connection, start + duration + 1);
+    await claimVested().signers([agent]).rpc();
+
+    expect(await balanceOf(agentTokenAccount)).to.equal(reward);
+    expect(await balanceOf(bountyTokenAccount)).to.equal(0);
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.vestedClaimed.toNumber()).to.equal(reward);
+
+    await closeBounty();
+    expect(await ctx.connection.getAccountInfo(bountyPda)).to.be.null;
+  });
+});
@@ -0,0 +1,156 @@
+import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveReputationPda,
+  deriveSubmissionPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("withdraw_submission", () => {
+  const reward = 5 * 10 ** 6;
+  const stake = LAMPORTS_PER_SOL / 10;
+
+  let ctx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+  });
+
+  beforeEach(async () => {
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+  });
+
+  async function submitAs(bountyPda: PublicKey): Promise<Keypair> {
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const hash = generateSolutionHashWithValue(0x2e);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    return agent;
+  }
+
+  function withdraw(agent: Keypair, bountyPda: PublicKey) {
+    return ctx.program.methods
+      .withdrawSubmission()
+      .accountsPartial({ agent: agent.publicKey, bounty: bountyPda })
+      .signers([agent])
+      .rpc();
+  }
+
+  function fetchReputation(agent: Keypair) {
+    return ctx.program.account.reputation.fetch(
+      deriveReputationPda(ctx.program.programId, agent.publicKey)[0]
+    );
+  }
+
+  it("Reopens the bounty for another agent to submit and settle", async () => {
+    const bountyPda = await postBounty(ctx, "Retractable audit", reward, {
+      challengePeriodSlots: 0,
+      requiredStake: stake,
+    });
+    const quitter = await submitAs(bountyPda);
+    const before = await fetchReputation(quitter);
+    const lamportsBefore = await ctx.connection.getBalance(quitter.publicKey);
+
+    await withdraw(quitter, bountyPda);
+
+    let bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ open: {} });
+    expect(bounty.solver).to.be.null;
+    expect(bounty.solutionHash).to.be.null;
+    // the submission is closed, its stake and rent back with the agent
+    const [submissionPda] = deriveSubmissionPda(
+      ctx.program.programId,
+      bountyPda,
+      quitter.publicKey
+    );
+    expect(await ctx.connection.getAccountInfo(submissionPda)).to.be.null;
+    expect(
+      (await ctx.connection.getBalance(quitter.publicKey)) - lamportsBefore
+    ).to.be.greaterThan(stake);
+
+    const after = await fetchReputation(quitter);
+    expect(after.withdrawals).to.equal(before.withdrawals + 1);
+    expect(after.failedBounties.toNumber()).to.equal(
+      before.failedBounties.toNumber()
+    );
+    expect(after.openSubmissions).to.equal(before.openSubmissions - 1);
+
+    const solver = await submitAs(bountyPda);
+    const solverTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      solver,
+      ctx.usdcMint
+    );
+    await settleBounty(ctx, solver.publicKey, bountyPda, solverTokenAccount);
+
+    bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ settled: {} });
+    expect(bounty.solver.toString()).to.equal(solver.publicKey.toString());
+  });
+
+  it("Frees a capped competitive bounty's slot for the next agent", async () => {
+    const bountyPda = await postBounty(ctx, "Crowded contest", reward, {
+      competitive: true,
+      maxSubmissions: 2,
+    });
+    const first = await submitAs(bountyPda);
+    await submitAs(bountyPda);
+
+    await withdraw(first, bountyPda);
+    await submitAs(bountyPda);
+
+    const bounty = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bounty.status).to.deep.equal({ open: {} });
+    expect(bounty.liveSubmissions).to.equal(2);
+    expect(bounty.submissionCount).to.equal(3);
+  });
+
+  it("Fails once the creator has approved the settlement", async () => {
+    const bountyPda = await postBounty(ctx, "Approved audit", reward, {
+      challengePeriodSlots: 0,
+    });
+    const agent = await submitAs(bountyPda);
+    await ctx.program.methods
+      .approveSettlement()
+      .accountsPartial({ creator: ctx.creator.publicKey, bounty: bountyPda })
+      .signers([ctx.creator])
+      .rpc();
+
+    try {
+      await withdraw(agent, bountyPda);
+      expect.fail("Should have failed - settlement approved");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("SubmissionNotWithdrawable");
+    }
+  });
+
+  it("Fails for an agent who isn't the solver", async () => {
+    const bountyPda = await postBounty(ctx, "Someone else's audit", reward);
+    await submitAs(bountyPda);
+    const bystander = Keypair.generate();
+    await airdropSol(ctx.connection, bystander.publicKey);
+
+    try {
+      await withdraw(bystander, bountyPda);
+      expect.fail("Should have failed - no submission of theirs");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountNotInitialized");
+    }
+  });
+});
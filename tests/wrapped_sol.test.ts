@@ -0,0 +1,167 @@
+import * as anchor from "@coral-xyz/anchor";
+import {
+  NATIVE_MINT,
+  TOKEN_PROGRAM_ID,
+  createAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { Keypair, LAMPORTS_PER_SOL, PublicKey, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  nextBountyPda,
+  getAssociatedTokenAddressSync,
+  airdropSol,
+  bountyParams,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  waitForPostCooldown,
+  generateSolutionHashWithValue,
+  TestContext,
+} from "./helpers";
+
+describe("wrapped SOL", () => {
+  const reward = LAMPORTS_PER_SOL / 4;
+
+  let ctx: TestContext;
+  // settleBounty reads the reward mint from the context
+  let wsolCtx: TestContext;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    wsolCtx = { ...ctx, usdcMint: NATIVE_MINT };
+  });
+
+  // posted from the creator's lamports, with no wSOL account of their own
+  async function postWrapped(): Promise<[PublicKey, PublicKey]> {
+    const [bountyPda] = await nextBountyPda(ctx);
+    const bountyTokenAccount = getAssociatedTokenAddressSync(
+      NATIVE_MINT,
+      bountyPda
+    );
+    await waitForPostCooldown(ctx.creator.publicKey);
+    await ctx.program.methods
+      .postBounty(bountyParams("Paid in wrapped SOL", reward))
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        creatorBond: null,
+        rewardMint: NATIVE_MINT,
+        creatorTokenAccount: null,
+        bountyTokenAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([ctx.creator])
+      .rpc();
+    return [bountyPda, bountyTokenAccount];
+  }
+
+  it("Wraps the creator's lamports into the escrow when posting", async () => {
+    const lamportsBefore = await ctx.connection.getBalance(
+      ctx.creator.publicKey
+    );
+    const [bountyPda, bountyTokenAccount] = await postWrapped();
+
+    const escrow = await ctx.connection.getTokenAccountBalance(
+      bountyTokenAccount
+    );
+    expect(Number(escrow.value.amount)).to.equal(reward);
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.rewardMint.toString()).to.equal(
+      NATIVE_MINT.toString()
+    );
+    expect(bountyAccount.escrowAmount.toNumber()).to.equal(reward);
+
+    // the reward plus the rent of the accounts posting created
+    const lamportsAfter = await ctx.connection.getBalance(
+      ctx.creator.publicKey
+    );
+    expect(lamportsBefore - lamportsAfter).to.be.greaterThan(reward);
+  });
+
+  it("Settles to the solver's wSOL account", async () => {
+    const [bountyPda] = await postWrapped();
+
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const agentTokenAccount = await createAssociatedTokenAccount(
+      ctx.connection,
+      agent,
+      NATIVE_MINT,
+      agent.publicKey
+    );
+
+    const hash = generateSolutionHashWithValue(0x50);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    await settleBounty(wsolCtx, agent.publicKey, bountyPda, agentTokenAccount);
+
+    const agentBalance = await ctx.connection.getTokenAccountBalance(
+      agentTokenAccount
+    );
+    expect(Number(agentBalance.value.amount)).to.equal(reward);
+  });
+
+  it("Refunds a cancelled bounty to the creator as lamports", async () => {
+    const [bountyPda, bountyTokenAccount] = await postWrapped();
+    const [unwrapAccount] = PublicKey.findProgramAddressSync(
+      [Buffer.from("unwrap"), bountyPda.toBuffer()],
+      ctx.program.programId
+    );
+
+    const lamportsBefore = await ctx.connection.getBalance(
+      ctx.creator.publicKey
+    );
+    await ctx.program.methods
+      .cancelBounty()
+      .accountsPartial({
+        creator: ctx.creator.publicKey,
+        bounty: bountyPda,
+        creatorTokenAccount: null,
+        bountyTokenAccount,
+        rewardMint: NATIVE_MINT,
+        unwrapAccount,
+        tokenProgram: TOKEN_PROGRAM_ID,
+      })
+      .signers([ctx.creator])
+      .rpc();
+
+    // the provider pays the fee and the unwrap account's rent comes straight back
+    const lamportsAfter = await ctx.connection.getBalance(ctx.creator.publicKey);
+    expect(lamportsAfter - lamportsBefore).to.equal(reward);
+
+    const escrow = await ctx.connection.getTokenAccountBalance(
+      bountyTokenAccount
+    );
+    expect(escrow.value.amount).to.equal("0");
+    expect(await ctx.connection.getAccountInfo(unwrapAccount)).to.be.null;
+
+    const bountyAccount = await ctx.program.account.bounty.fetch(bountyPda);
+    expect(bountyAccount.status).to.deep.equal({ cancelled: {} });
+  });
+
+  it("Fails to refund as lamports without the unwrap account", async () => {
+    const [bountyPda, bountyTokenAccount] = await postWrapped();
+
+    try {
+      await ctx.program.methods
+        .cancelBounty()
+        .accountsPartial({
+          creator: ctx.creator.publicKey,
+          bounty: bountyPda,
+          creatorTokenAccount: null,
+          bountyTokenAccount,
+          rewardMint: NATIVE_MINT,
+          unwrapAccount: null,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        })
+        .signers([ctx.creator])
+        .rpc();
+      expect.fail("Should have failed - no unwrap account");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("MissingUnwrapAccount");
+    }
+  });
+});
@@ -0,0 +1,176 @@
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+import {
+  setupTestContext,
+  deriveLeaderboardPda,
+  deriveStatsPda,
+  airdropSol,
+  createAgentTokenAccount,
+  ensureCreatorBalance,
+  postBounty,
+  createAttestation,
+  submitSolution,
+  settleBounty,
+  generateSolutionHashWithValue,
+  LEGACY_STATS,
+  LEGACY_STATS_MARKET,
+  TestContext,
+} from "./helpers";
+
+// BountyStats and Leaderboard are read in place, so their bytes are the
+// struct: every field at a fixed offset, the discriminator included, and the
+// padding at the end the only thing the Borsh layout didn't have.
+describe("zero_copy", () => {
+  const statsLen = 8 + 80;
+  const legacyStatsLen = 8 + 65;
+  const boardSize = 16;
+  const entryLen = 32 + 8;
+  const boardLenOffset = 8 + boardSize * entryLen;
+  const leaderboardLen = boardLenOffset + 8;
+
+  let ctx: TestContext;
+  let payer: Keypair;
+
+  before(async () => {
+    ctx = await setupTestContext();
+    payer = Keypair.generate();
+    await airdropSol(ctx.connection, payer.publicKey);
+
+    // a settlement puts at least one agent on the board
+    await ensureCreatorBalance(
+      ctx.connection,
+      ctx.creator,
+      ctx.usdcMint,
+      ctx.creatorTokenAccount,
+      100 * 10 ** 6
+    );
+    const bountyPda = await postBounty(ctx, "Ranked layout", 10 * 10 ** 6, {
+      challengePeriodSlots: 0,
+    });
+    const agent = Keypair.generate();
+    await airdropSol(ctx.connection, agent.publicKey);
+    const agentTokenAccount = await createAgentTokenAccount(
+      ctx.connection,
+      agent,
+      ctx.usdcMint
+    );
+    const hash = generateSolutionHashWithValue(0x2c);
+    const attestation = await createAttestation(ctx, agent, bountyPda, hash);
+    await submitSolution(ctx, agent, bountyPda, attestation, hash);
+    await settleBounty(ctx, agent.publicKey, bountyPda, agentTokenAccount);
+  });
+
+  function readU128(data: Buffer, offset: number): bigint {
+    return (
+      data.readBigUInt64LE(offset) +
+      (data.readBigUInt64LE(offset + 8) << BigInt(64))
+    );
+  }
+
+  async function expectStatsAt(stats: PublicKey) {
+    const info = await ctx.connection.getAccountInfo(stats);
+    const decoded = await ctx.program.account.bountyStats.fetch(stats);
+
+    expect(info.data.length).to.equal(statsLen);
+    expect(info.data.readBigUInt64LE(8).toString()).to.equal(
+      decoded.bountiesPosted.toString()
+    );
+    expect(info.data.readBigUInt64LE(16).toString()).to.equal(
+      decoded.bountiesSubmitted.toString()
+    );
+    expect(info.data.readBigUInt64LE(24).toString()).to.equal(
+      decoded.bountiesSettled.toString()
+    );
+    expect(info.data.readBigUInt64LE(32).toString()).to.equal(
+      decoded.bountiesCancelled.toString()
+    );
+    expect(readU128(info.data, 40).toString()).to.equal(
+      decoded.totalEscrowed.toString()
+    );
+    expect(readU128(info.data, 56).toString()).to.equal(
+      decoded.totalPaid.toString()
+    );
+    expect(info.data[72]).to.equal(decoded.bump);
+    expect([...info.data.subarray(73)].every((b) => b === 0)).to.be.true;
+    return decoded;
+  }
+
+  it("Keeps the stats counters at their offsets", async () => {
+    const decoded = await expectStatsAt(
+      deriveStatsPda(ctx.program.programId)[0]
+    );
+    expect(decoded.bountiesSettled.toNumber()).to.be.greaterThan(0);
+  });
+
+  it("Keeps the leaderboard entries at their offsets", async () => {
+    const [leaderboardPda] = deriveLeaderboardPda(ctx.program.programId);
+    const info = await ctx.connection.getAccountInfo(leaderboardPda);
+    const decoded = await ctx.program.account.leaderboard.fetch(
+      leaderboardPda
+    );
+
+    expect(info.data.length).to.equal(leaderboardLen);
+    expect(info.data[boardLenOffset]).to.equal(decoded.len);
+    expect(info.data[boardLenOffset + 1]).to.equal(decoded.bump);
+    expect(decoded.len).to.be.greaterThan(0);
+    for (let i = 0; i < decoded.len; i++) {
+      const offset = 8 + i * entryLen;
+      expect(
+        new PublicKey(info.data.subarray(offset, offset + 32)).toString()
+      ).to.equal(decoded.entries[i].agent.toString());
+      expect(info.data.readBigUInt64LE(offset + 32).toString()).to.equal(
+        decoded.entries[i].score.toString()
+      );
+    }
+  });
+
+  it("Grows counters on the Borsh layout without touching them", async () => {
+    const before = await ctx.connection.getAccountInfo(LEGACY_STATS);
+    expect(before.data.length).to.equal(legacyStatsLen);
+
+    await ctx.program.methods
+      .migrateStats(LEGACY_STATS_MARKET)
+      .accountsPartial({ payer: payer.publicKey, stats: LEGACY_STATS })
+      .signers([payer])
+      .rpc();
+
+    const after = await ctx.connection.getAccountInfo(LEGACY_STATS);
+    expect(after.data.subarray(0, legacyStatsLen)).to.deep.equal(
+      before.data
+    );
+    expect(after.lamports).to.equal(
+      await ctx.connection.getMinimumBalanceForRentExemption(statsLen)
+    );
+    const decoded = await expectStatsAt(LEGACY_STATS);
+    expect(decoded.bountiesPosted.toNumber()).to.equal(7);
+    expect(decoded.bountiesSubmitted.toNumber()).to.equal(5);
+    expect(decoded.bountiesSettled.toNumber()).to.equal(3);
+    expect(decoded.bountiesCancelled.toNumber()).to.equal(1);
+    expect(decoded.totalEscrowed.toString()).to.equal("7000000");
+    expect(decoded.totalPaid.toString()).to.equal("2900000");
+  });
+
+  it("Fails to migrate counters or the leaderboard twice", async () => {
+    try {
+      await ctx.program.methods
+        .migrateStats(LEGACY_STATS_MARKET)
+        .accountsPartial({ payer: payer.publicKey, stats: LEGACY_STATS })
+        .signers([payer])
+        .rpc();
+      expect.fail("Should have failed - already migrated");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountAlreadyMigrated");
+    }
+
+    try {
+      await ctx.program.methods
+        .migrateLeaderboard()
+        .accountsPartial({ payer: payer.publicKey })
+        .signers([payer])
+        .rpc();
+      expect.fail("Should have failed - created zero-copy");
+    } catch (err) {
+      expect(err.error.errorCode.code).to.equal("AccountAlreadyMigrated");
+    }
+  });
+});